@@ -49,18 +49,18 @@ const SHOULD_ADD: [&str; 39] = [
 ];
 
 fn main() {
-    // On docs.rs, files are read-only and we can't write to them.
-    // So we skip this step when building the docs.
-    if std::env::var("DOCS_RS").is_ok() {
-        return;
-    }
-    let raw_dir = fs::read_dir("./tests/rfc9553/raws");
-    if raw_dir.is_err() {
+    println!("cargo::rerun-if-changed=build.rs");
+    println!("cargo::rerun-if-changed=tests/rfc9553/raws");
+    // the raw figures are not packaged, only the generated figures of tests/rfc9553 are
+    let Ok(raws) = fs::read_dir("./tests/rfc9553/raws") else {
         return;
-    }
-    let raws = raw_dir.unwrap();
+    };
 
-    let dest_path = Path::new("./tests/rfc9553/");
+    // the figures are generated in OUT_DIR, the source tree may be read-only
+    // tests/test_figures.rs checks them against tests/rfc9553
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let dest_path = Path::new(&out_dir).join("rfc9553");
+    fs::create_dir_all(&dest_path).unwrap();
     for one_entry in raws {
         let entry = one_entry.unwrap();
         let path = entry.path();
@@ -129,6 +129,4 @@ fn main() {
         let out_path = dest_path.join(file_name);
         fs::write(&out_path, &contents).unwrap();
     }
-
-    println!("cargo::rerun-if-changed=build.rs");
 }
@@ -0,0 +1,27 @@
+//! Convert the vCards of a file to Cards, printed as a JSON array.
+//!
+//! ```sh
+//! cargo run --example from_vcard --features vcard4-interop -- examples/data/card.vcf
+//! ```
+
+use std::error::Error;
+
+use jscontact::Card;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "examples/data/card.vcf".to_string());
+    let text = std::fs::read_to_string(&path)?;
+
+    let mut cards = Vec::new();
+    for vcard in vcard4::parse(text)? {
+        let (card, report) = Card::from_vcard4(&vcard)?;
+        for loss in &report.lossy {
+            eprintln!("{}: lost {}: {}", card.uid, loss.path, loss.reason);
+        }
+        cards.push(card);
+    }
+    println!("{}", serde_json::to_string_pretty(&cards)?);
+    Ok(())
+}
@@ -0,0 +1,28 @@
+//! Localize a Card into a language and print the localized Card as JSON.
+//!
+//! ```sh
+//! cargo run --example localize -- examples/data/card.json fr
+//! ```
+
+use std::error::Error;
+use std::fs::File;
+
+use jscontact::Card;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = std::env::args().skip(1);
+    let path = args
+        .next()
+        .unwrap_or_else(|| "examples/data/card.json".to_string());
+    let language = args.next().unwrap_or_else(|| "fr".to_string());
+    let card = Card::from_reader(File::open(&path)?)?;
+
+    let languages: Vec<&str> = card.get_available_languages().collect();
+    eprintln!("available languages: {}", languages.join(", "));
+    let (localized, issues) = card.get_localized_lenient(&language)?;
+    for issue in issues {
+        eprintln!("skipped {}", issue);
+    }
+    println!("{}", serde_json::to_string_pretty(&localized)?);
+    Ok(())
+}
@@ -0,0 +1,36 @@
+//! Find the duplicates of a list of Cards and merge them, the list without duplicates being printed as JSON.
+//!
+//! ```sh
+//! cargo run --example merge -- examples/data/duplicates.json --latest
+//! ```
+
+use std::error::Error;
+use std::fs::File;
+
+use jscontact::dedup::{find_duplicates, merge_group};
+use jscontact::{Card, DedupOptions, MergeStrategy};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut path = "examples/data/duplicates.json".to_string();
+    let mut strategy = MergeStrategy::PreferFirst;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--latest" => strategy = MergeStrategy::PreferLatest,
+            _ => path = arg,
+        }
+    }
+    let cards: Vec<Card> = serde_json::from_reader(File::open(&path)?)?;
+
+    let groups = find_duplicates(&cards, &DedupOptions::default());
+    let mut merged: Vec<Option<Card>> = cards.into_iter().map(Some).collect();
+    for group in &groups {
+        eprintln!("merging the Cards {:?}", group.indexes);
+        for idx in &group.indexes {
+            merged[*idx] = None;
+        }
+        merged[group.indexes[0]] = Some(merge_group(group, strategy));
+    }
+    let merged: Vec<Card> = merged.into_iter().flatten().collect();
+    println!("{}", serde_json::to_string_pretty(&merged)?);
+    Ok(())
+}
@@ -0,0 +1,27 @@
+//! Convert a Card to a vCard, the properties lost in the conversion being printed on stderr.
+//!
+//! ```sh
+//! cargo run --example to_vcard --features vcard4-interop -- examples/data/card.json
+//! ```
+
+use std::error::Error;
+use std::fs::File;
+
+use jscontact::Card;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "examples/data/card.json".to_string());
+    let card = Card::from_reader(File::open(&path)?)?;
+
+    let (vcard, report) = card.to_vcard4();
+    for loss in &report.lossy {
+        eprintln!("lost {}: {}", loss.path, loss.reason);
+    }
+    for warning in &report.warnings {
+        eprintln!("warning: {}", warning);
+    }
+    print!("{}", vcard);
+    Ok(())
+}
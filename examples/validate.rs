@@ -0,0 +1,31 @@
+//! Validate a Card and print the violations.
+//!
+//! ```sh
+//! cargo run --example validate -- examples/data/invalid.json
+//! ```
+
+use std::error::Error;
+use std::fs::File;
+use std::process::ExitCode;
+
+use jscontact::Card;
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "examples/data/card.json".to_string());
+    let card = Card::from_reader(File::open(&path)?)?;
+
+    let report = card.validate();
+    let summary = report.summary();
+    print!("{}", report);
+    println!(
+        "{}: {} error(s), {} warning(s)",
+        path, summary.errors, summary.warnings
+    );
+    Ok(if report.is_valid() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
@@ -0,0 +1,172 @@
+//! Flat accessors of the common nested fields of a [`Card`], borrowing them without allocation.
+//!
+//! The entries of the maps are chosen as elsewhere in the crate: the preferred email and phone (see
+//! [`crate::preferred`]), the organization and the title with the lowest key.
+//!
+//! The `_mut` accessors borrow an existing field only, they create nothing. Borrowing a field counts as a
+//! modification of the Card, bumping its revision with the `tracking` feature.
+
+use crate::preferred::preferred_entry;
+use crate::{Card, NameComponentKind, Title, TitleKind};
+
+/// Check if a title is a job title, not a role
+fn is_job_title(title: &Title) -> bool {
+    title.kind != Some(TitleKind::Role)
+}
+
+impl Card {
+    /// Get the full name, as set in the name (see [`Name::to_full_string`] to build it from the components).
+    ///
+    /// ```rust
+    /// use jscontact::Card;
+    ///
+    /// let card: Card = serde_json::from_value(serde_json::json!({
+    ///     "@type": "Card",
+    ///     "version": "1.0",
+    ///     "uid": "1234",
+    ///     "name": { "full": "Jane Doe" },
+    ///     "titles": { "t1": { "name": "Research Scientist" } },
+    ///     "organizations": { "o1": { "name": "ABC, Inc." } },
+    ///     "emails": { "e1": { "address": "jane@example.com" } }
+    /// }))
+    /// .unwrap();
+    /// let summary = format!(
+    ///     "{} - {} at {} <{}>",
+    ///     card.full_name().unwrap_or("?"),
+    ///     card.job_title_name().unwrap_or("?"),
+    ///     card.primary_org_name().unwrap_or("?"),
+    ///     card.first_email().unwrap_or("?"),
+    /// );
+    /// assert_eq!(summary, "Jane Doe - Research Scientist at ABC, Inc. <jane@example.com>");
+    /// ```
+    pub fn full_name(&self) -> Option<&str> {
+        self.name.as_ref()?.full.as_deref()
+    }
+
+    /// Get the full name of the name to set it, `None` if the Card has no name.
+    pub fn full_name_mut(&mut self) -> Option<&mut Option<String>> {
+        self.name.as_ref()?;
+        self.touch();
+        Some(&mut self.name.as_mut()?.full)
+    }
+
+    /// Get the value of the first given name component.
+    pub fn given_name(&self) -> Option<&str> {
+        self.name_component(NameComponentKind::Given)
+    }
+
+    /// Get the value of the first given name component to set it.
+    pub fn given_name_mut(&mut self) -> Option<&mut String> {
+        self.name_component_mut(NameComponentKind::Given)
+    }
+
+    /// Get the value of the first surname component.
+    pub fn surname(&self) -> Option<&str> {
+        self.name_component(NameComponentKind::Surname)
+    }
+
+    /// Get the value of the first surname component to set it.
+    pub fn surname_mut(&mut self) -> Option<&mut String> {
+        self.name_component_mut(NameComponentKind::Surname)
+    }
+
+    /// Get the value of the first name component of a kind
+    fn name_component(&self, kind: NameComponentKind) -> Option<&str> {
+        self.name
+            .as_ref()?
+            .components
+            .iter()
+            .flatten()
+            .find(|component| component.kind == kind)
+            .map(|component| component.value.as_str())
+    }
+
+    /// Get the value of the first name component of a kind to set it
+    fn name_component_mut(&mut self, kind: NameComponentKind) -> Option<&mut String> {
+        self.name_component(kind.clone())?;
+        self.touch();
+        self.name
+            .as_mut()?
+            .components
+            .iter_mut()
+            .flatten()
+            .find(|component| component.kind == kind)
+            .map(|component| &mut component.value)
+    }
+
+    /// Get the name of the primary organization, see [`Card::primary_organization`].
+    pub fn primary_org_name(&self) -> Option<&str> {
+        self.primary_organization()?.name.as_deref()
+    }
+
+    /// Get the name of the primary organization to set it, see [`Card::primary_organization`].
+    pub fn primary_org_name_mut(&mut self) -> Option<&mut Option<String>> {
+        let key = self.organizations.as_ref()?.keys().min()?.clone();
+        self.touch();
+        let organization = self.organizations.as_mut()?.get_mut(&key)?;
+        Some(&mut organization.name)
+    }
+
+    /// Get the name of the job title with the lowest key, the roles being left out.
+    pub fn job_title_name(&self) -> Option<&str> {
+        self.titles
+            .iter()
+            .flatten()
+            .filter(|(_, title)| is_job_title(title))
+            .min_by_key(|(key, _)| key.as_str())
+            .map(|(_, title)| title.name.as_str())
+    }
+
+    /// Get the name of the job title with the lowest key to set it, the roles being left out.
+    pub fn job_title_name_mut(&mut self) -> Option<&mut String> {
+        let key = self
+            .titles
+            .iter()
+            .flatten()
+            .filter(|(_, title)| is_job_title(title))
+            .map(|(key, _)| key)
+            .min()?
+            .clone();
+        self.touch();
+        let title = self.titles.as_mut()?.get_mut(&key)?;
+        Some(&mut title.name)
+    }
+
+    /// Get the address of the preferred email: the lowest `pref`, then the lowest key.
+    pub fn first_email(&self) -> Option<&str> {
+        self.preferred_email_address()
+    }
+
+    /// Get the address of the preferred email to set it, see [`Card::first_email`].
+    pub fn first_email_mut(&mut self) -> Option<&mut String> {
+        let (key, _) = preferred_entry(
+            None,
+            self.emails.as_ref()?,
+            |email| email.contexts.as_ref(),
+            |email| email.pref.map(u64::from),
+        )?;
+        let key = key.clone();
+        self.touch();
+        let email = self.emails.as_mut()?.get_mut(&key)?;
+        Some(&mut email.address)
+    }
+
+    /// Get the number of the preferred phone: the lowest `pref`, then the lowest key.
+    pub fn first_phone_number(&self) -> Option<&str> {
+        self.preferred_phone_number()
+    }
+
+    /// Get the number of the preferred phone to set it, see [`Card::first_phone_number`].
+    pub fn first_phone_number_mut(&mut self) -> Option<&mut String> {
+        let (key, _) = preferred_entry(
+            None,
+            self.phones.as_ref()?,
+            |phone| phone.contexts.as_ref(),
+            |phone| phone.pref.map(u64::from),
+        )?;
+        let key = key.clone();
+        self.touch();
+        let phone = self.phones.as_mut()?.get_mut(&key)?;
+        Some(&mut phone.number)
+    }
+}
@@ -0,0 +1,238 @@
+//! Anonymization of a [`Card`], to share bug reproductions without personal data.
+//!
+//! The structure of the Card is kept: same properties, same map keys, same component kinds and counts.
+//! The values are replaced by deterministic placeholders derived from the seed and the path of the value,
+//! so a localization patching a property gets the same placeholder as the property itself.
+
+use serde_json::{Map, Value};
+
+use crate::keygen::fnv1a;
+use crate::Card;
+
+/// The properties whose values are kept, because they hold no personal data (kinds, media types, etc.)
+const KEPT_PROPERTIES: [&str; 18] = [
+    "@type",
+    "version",
+    "kind",
+    "language",
+    "mediaType",
+    "phoneticSystem",
+    "phoneticScript",
+    "grammaticalGender",
+    "level",
+    "calendarScale",
+    "countryCode",
+    "timeZone",
+    "created",
+    "updated",
+    "prodId",
+    "defaultSeparator",
+    "organizationId",
+    "service",
+];
+
+/// The maps whose keys are values (keywords and uids of other Cards), so the keys are replaced
+const REKEYED_MAPS: [&str; 3] = ["keywords", "members", "relatedTo"];
+
+/// Placeholder of the free text values
+const REDACTED: &str = "REDACTED";
+
+impl Card {
+    /// Returns a copy of the Card with the personal data replaced by placeholders.
+    ///
+    /// - names become `Given1 Surname1`, with one placeholder per name component kind
+    /// - emails become `user1@example.com`, phones `tel:+1-555-0101` and URIs `https://example.com/r1`
+    /// - uids, members and relations become `urn:uuid:` values, the same uid always giving the same value
+    /// - dates become `1970-01-01`, and the other free text values `REDACTED`
+    ///
+    /// The output only depends on the Card and the seed, so two anonymizations with the same seed can be diffed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Card cannot be serialized, i.e. if it is nested deeper than [`crate::DEFAULT_MAX_DEPTH`]
+    /// (see [`Card::check_depth`]).
+    pub fn anonymize(&self, seed: u64) -> Card {
+        let base = serde_json::to_value(self).expect("Failed to convert card to value");
+        let scrubber = Scrubber { seed, base: &base };
+        let mut anonymized = base.clone();
+        if let Value::Object(map) = &mut anonymized {
+            let localizations = map.remove("localizations");
+            for (key, value) in map.iter_mut() {
+                scrubber.scrub(value, &mut vec![escape(key)], None);
+            }
+            if let Some(Value::Object(localizations)) = localizations {
+                let localizations = localizations
+                    .into_iter()
+                    .map(|(language, patches)| (language, scrubber.scrub_patches(patches)))
+                    .collect();
+                map.insert("localizations".to_string(), Value::Object(localizations));
+            }
+        }
+        serde_json::from_value(anonymized).expect("Failed to convert value to card")
+    }
+}
+
+/// Replaces the values of a serialized Card
+struct Scrubber<'a> {
+    /// The seed of the placeholders
+    seed: u64,
+    /// The serialized Card, to find the kind of the components patched by the localizations
+    base: &'a Value,
+}
+
+impl Scrubber<'_> {
+    /// Hash a text with the seed
+    fn hash(&self, text: &str) -> u64 {
+        fnv1a(&[&self.seed.to_le_bytes(), text.as_bytes()])
+    }
+
+    /// Get the number of a placeholder
+    fn number(&self, text: &str) -> u64 {
+        self.hash(text) % 1000 + 1
+    }
+
+    /// Get the placeholder of a uid, the same uid always giving the same placeholder
+    fn uid(&self, uid: &str) -> String {
+        let high = self.hash(uid);
+        let low = self.hash(&format!("{:016x}", high));
+        format!(
+            "urn:uuid:{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            high >> 32,
+            (high >> 16) & 0xffff,
+            high & 0xffff,
+            low >> 48,
+            low & 0xffff_ffff_ffff
+        )
+    }
+
+    /// Get the placeholder of a key of the keywords, members or relatedTo maps
+    fn rekey(&self, map: &str, key: &str) -> String {
+        if map == "keywords" {
+            format!("keyword-{:08x}", self.hash(key) & 0xffff_ffff)
+        } else {
+            self.uid(key)
+        }
+    }
+
+    /// Get the placeholder of a name component
+    fn name_component(&self, kind: &str) -> String {
+        let mut chars = kind.chars();
+        let capitalized: String = chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect())
+            .unwrap_or_default();
+        format!("{}{}", capitalized, self.number("name"))
+    }
+
+    /// Replace the values of the patches of a localization
+    fn scrub_patches(&self, patches: Value) -> Value {
+        let Value::Object(patches) = patches else {
+            return patches;
+        };
+        let patches = patches
+            .into_iter()
+            .map(|(key, mut value)| {
+                let mut path: Vec<String> = key.split('/').map(String::from).collect();
+                if path.len() > 1 && REKEYED_MAPS.contains(&path[0].as_str()) {
+                    path[1] = self.rekey(&path[0], &unescape(&path[1]));
+                }
+                self.scrub(&mut value, &mut path, None);
+                (path.join("/"), value)
+            })
+            .collect();
+        Value::Object(patches)
+    }
+
+    /// Replace the values of a property, `path` being the escaped segments of its JSON pointer
+    fn scrub(&self, value: &mut Value, path: &mut Vec<String>, kind: Option<&str>) {
+        match value {
+            Value::Object(map) => {
+                let kind = map.get("kind").and_then(Value::as_str).map(String::from);
+                for (key, value) in map.iter_mut() {
+                    path.push(escape(key));
+                    self.scrub(value, path, kind.as_deref());
+                    path.pop();
+                }
+                if path.len() == 1 && REKEYED_MAPS.contains(&path[0].as_str()) {
+                    let rekeyed: Map<String, Value> = std::mem::take(map)
+                        .into_iter()
+                        .map(|(key, value)| (self.rekey(&path[0], &key), value))
+                        .collect();
+                    *map = rekeyed;
+                }
+            }
+            Value::Array(values) => {
+                for (idx, value) in values.iter_mut().enumerate() {
+                    path.push(idx.to_string());
+                    self.scrub(value, path, None);
+                    path.pop();
+                }
+            }
+            Value::String(text) => {
+                if let Some(placeholder) = self.placeholder(text, path, kind) {
+                    *text = placeholder;
+                }
+            }
+            Value::Number(_) => match path.last().map(String::as_str) {
+                Some("year") => *value = Value::from(1970),
+                Some("month") | Some("day") => *value = Value::from(1),
+                _ => (),
+            },
+            Value::Bool(_) | Value::Null => (),
+        }
+    }
+
+    /// Get the placeholder of a string, or `None` if the string is kept
+    fn placeholder(&self, text: &str, path: &[String], kind: Option<&str>) -> Option<String> {
+        let root = path.first().map(String::as_str).unwrap_or_default();
+        let property = path.last().map(String::as_str).unwrap_or_default();
+        let pointer = path.join("/");
+        match property {
+            property if KEPT_PROPERTIES.contains(&property) => None,
+            "uid" => Some(self.uid(text)),
+            "address" if root == "emails" => {
+                Some(format!("user{}@example.com", self.number(&pointer)))
+            }
+            "number" if root == "phones" => {
+                Some(format!("tel:+1-555-01{:02}", self.hash(&pointer) % 100))
+            }
+            "uri" => Some(format!("https://example.com/r{}", self.number(&pointer))),
+            "coordinates" => Some("geo:0,0".to_string()),
+            "utc" => Some("1970-01-01T00:00:00Z".to_string()),
+            "full" if root == "name" => Some(format!(
+                "{} {}",
+                self.name_component("given"),
+                self.name_component("surname")
+            )),
+            "value" => {
+                // a localization can patch the value of a component without its kind
+                let parent = &path[..path.len() - 1];
+                let kind = kind.map(String::from).or_else(|| {
+                    self.base
+                        .pointer(&format!("/{}/kind", parent.join("/")))
+                        .and_then(Value::as_str)
+                        .map(String::from)
+                });
+                match kind.as_deref() {
+                    Some("separator") => None,
+                    Some(kind) if root == "name" => Some(self.name_component(kind)),
+                    _ => Some(REDACTED.to_string()),
+                }
+            }
+            kind if root == "name" && path.len() == 3 && path[1] == "sortAs" => {
+                Some(self.name_component(kind))
+            }
+            _ => Some(REDACTED.to_string()),
+        }
+    }
+}
+
+/// Escape a key to be used as a segment of a JSON pointer
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Unescape a segment of a JSON pointer
+fn unescape(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
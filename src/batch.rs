@@ -0,0 +1,103 @@
+//! Localization of lists of Cards, e.g. to export a whole address book in a language.
+//!
+//! With the `rayon` feature the Cards are localized in parallel,
+//! the results being the same as the sequential localization, in the order of the Cards.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{Card, PatchLimits};
+
+/// The options of [`localize_all`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalizeOptions {
+    /// Use the fallback chain of the language tag, see [`Card::get_localized_chain`].
+    pub fallback_chain: bool,
+    /// Skip the patches of values which are not localizable instead of failing, see [`Card::get_localized_lenient`].
+    pub lenient: bool,
+    /// Localize the Cards in parallel, only with the `rayon` feature.
+    pub parallel: bool,
+    /// The limits of the patches, a Card exceeding them fails to localize.
+    pub limits: PatchLimits,
+}
+
+impl Default for LocalizeOptions {
+    fn default() -> Self {
+        Self {
+            fallback_chain: false,
+            lenient: false,
+            parallel: true,
+            limits: PatchLimits::default(),
+        }
+    }
+}
+
+/// An error while localizing a Card of a list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchError {
+    /// The position of the Card in the list.
+    pub index: usize,
+    /// The uid of the Card.
+    pub uid: String,
+    /// The description of the error.
+    pub message: String,
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Card {} ({}): {}", self.index, self.uid, self.message)
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+/// Localize a Card of a list
+fn localize(
+    index: usize,
+    card: &Card,
+    language: &str,
+    opts: &LocalizeOptions,
+) -> Result<Card, BatchError> {
+    card.get_localized_with(language, opts)
+        .map_err(|message| BatchError {
+            index,
+            uid: card.uid.clone(),
+            message,
+        })
+}
+
+/// Localize all the Cards of a list, a Card failing to localize does not stop the others.
+///
+/// The results are in the order of the Cards, a Card without localization for the language is returned as is.
+/// The fallback chain is not lenient: with both options, the chain is used.
+pub fn localize_all(
+    cards: &[Card],
+    language: &str,
+    opts: &LocalizeOptions,
+) -> Vec<Result<Card, BatchError>> {
+    #[cfg(feature = "rayon")]
+    if opts.parallel {
+        return cards
+            .par_iter()
+            .enumerate()
+            .map(|(index, card)| localize(index, card, language, opts))
+            .collect();
+    }
+    cards
+        .iter()
+        .enumerate()
+        .map(|(index, card)| localize(index, card, language, opts))
+        .collect()
+}
+
+/// Get the languages of the localizations of a list of Cards, sorted.
+pub fn collect_languages(cards: &[Card]) -> BTreeSet<String> {
+    cards
+        .iter()
+        .flat_map(Card::get_available_languages)
+        .map(String::from)
+        .collect()
+}
@@ -0,0 +1,118 @@
+//! A builder of [`Card`], setting the common properties in a single expression.
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{
+    Address, Card, CardKind, CardVersion, EmailAddress, KeyGen, Link, Media, Name, Nickname, Note,
+    PersonalInfo, Phone, Sequential, Title,
+};
+
+/// A builder of [`Card`].
+#[derive(Clone)]
+pub struct CardBuilder {
+    /// The Card being built
+    card: Card,
+    /// The generator of the keys of the added entries, [`Sequential`] if `None`
+    key_gen: Option<Arc<dyn KeyGen>>,
+}
+
+impl fmt::Debug for CardBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CardBuilder")
+            .field("card", &self.card)
+            .field("key_gen", &self.key_gen.is_some())
+            .finish()
+    }
+}
+
+/// Generate the methods adding the entries of the map properties
+macro_rules! with_properties {
+    ($($property:literal => $method:ident: $value:ty, $extend:ident;)+) => {
+        $(
+            #[doc = concat!("Adds the ", $property, " of an iterator, keyed by the key generator, see [`CardBuilder::with_key_gen`].")]
+            pub fn $method(mut self, values: impl IntoIterator<Item = $value>) -> Self {
+                match &self.key_gen {
+                    Some(key_gen) => self.card.$extend(values, key_gen.as_ref()),
+                    None => self.card.$extend(values, &Sequential::default()),
+                }
+                self
+            }
+        )+
+    };
+}
+
+impl CardBuilder {
+    /// Creates a new builder of a Card with the latest version and the specified unique identifier.
+    pub fn new(uid: &str) -> Self {
+        Self {
+            card: Card::new_with_latest_version(uid),
+            key_gen: None,
+        }
+    }
+
+    /// Sets the version of the Card.
+    pub fn with_version(mut self, version: CardVersion) -> Self {
+        self.card.version = version;
+        self
+    }
+
+    /// Sets the kind of the Card.
+    pub fn with_kind(mut self, kind: CardKind) -> Self {
+        self.card.kind = Some(kind);
+        self
+    }
+
+    /// Sets the language of the Card.
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.card.language = Some(language.to_string());
+        self
+    }
+
+    /// Sets the name of the Card.
+    pub fn with_name(mut self, name: Name) -> Self {
+        self.card.name = Some(name);
+        self
+    }
+
+    /// Sets the `prodId` of the built Card to `product version`, see [`Card::set_generator`].
+    pub fn with_generator(mut self, product: &str, version: &str) -> Self {
+        self.card.set_generator(product, version);
+        self
+    }
+
+    /// Sets the generator of the keys of the entries added by the builder, [`Sequential`] by default
+    /// (`e1`, `e2`, ... for the emails).
+    ///
+    /// ```rust
+    /// use jscontact::{CardBuilder, ContentHash, EmailAddress};
+    ///
+    /// let card = CardBuilder::new("1234")
+    ///     .with_key_gen(ContentHash)
+    ///     .with_emails([EmailAddress::new("jane@example.com")])
+    ///     .build();
+    /// let emails = card.emails.unwrap();
+    /// assert!(emails.keys().all(|key| key.starts_with('e') && key.len() == 17));
+    /// ```
+    pub fn with_key_gen(mut self, key_gen: impl KeyGen + 'static) -> Self {
+        self.key_gen = Some(Arc::new(key_gen));
+        self
+    }
+
+    with_properties! {
+        "emails" => with_emails: EmailAddress, extend_emails_with;
+        "phones" => with_phones: Phone, extend_phones_with;
+        "addresses" => with_addresses: Address, extend_addresses_with;
+        "links" => with_links: Link, extend_links_with;
+        "media" => with_media: Media, extend_media_with;
+        "notes" => with_notes: Note, extend_notes_with;
+        "nicknames" => with_nicknames: Nickname, extend_nicknames_with;
+        "titles" => with_titles: Title, extend_titles_with;
+        "personal information" => with_personal_info: PersonalInfo, extend_personal_info_with;
+    }
+
+    /// Builds the Card.
+    pub fn build(self) -> Card {
+        self.card
+    }
+}
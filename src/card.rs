@@ -1,26 +1,44 @@
 //! The primary Card object as defined in RFC 9553
 
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashMap},
+    fmt,
+    str::FromStr,
+    sync::Arc,
+};
 
-use serde::{Deserialize, Serialize};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 
+use crate::depth::{check_owned_depth, RawValues, DEFAULT_MAX_DEPTH};
+use crate::limit::{ReadError, DEFAULT_MAX_BYTES};
+use crate::migration::check_aliases;
+#[cfg(not(feature = "jsonptr"))]
+use crate::path::set_values_limited;
+use crate::path::CardPath;
+#[cfg(feature = "jsonptr")]
+use crate::PathSegment;
 use crate::{
     Address, Anniversary, Calendar, CardKind, CardVersion, CryptoKey, Directory, EmailAddress,
-    LanguagePref, Link, Media, Name, Nickname, Note, OnlineService, Organization, PersonalInfo,
-    Phone, Relation, SchedulingAddress, SpeakToAs, Title,
+    LanguagePref, Link, LocalizeOptions, Media, Name, Nickname, Note, OnlineService, Organization,
+    PatchLimits, PersonalInfo, Phone, Relation, SchedulingAddress, SmallString, SpeakToAs, Title,
 };
-#[cfg(not(feature = "jsonptr"))]
-use crate::{AddressComponent, AddressComponentKind, NameComponent};
+
+use crate::localizable::{localizable_patches, LocalizationIssue, ParseIssues, Patches};
+#[cfg(feature = "tracking")]
+use crate::tracking::Revision;
 
 /// Represents the primary Card object as defined in RFC 9553, storing metadata and contact properties.
+// the derived implementations are wrapped to report the missing required properties, see `MissingRequired`
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(remote = "Self")]
 #[serde(rename_all = "camelCase")]
 pub struct Card {
     /// The JSContact type of the Card object. Must be "Card".
     /// Not localized.
     #[serde(rename = "@type")]
-    card_type: String,
+    card_type: SmallString,
     /// The JSContact version of this Card.
     /// Not localized.
     pub version: CardVersion,
@@ -42,7 +60,7 @@ pub struct Card {
     /// Members of a group Card, if applicable.
     /// Not localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub members: Option<HashMap<String, bool>>,
+    pub members: Option<HashMap<SmallString, bool>>,
     /// Identifier for the product that created the Card.
     /// Not localized.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -50,103 +68,115 @@ pub struct Card {
     /// Related Cards with their relationship types.
     /// Not localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub related_to: Option<HashMap<String, Relation>>,
+    pub related_to: Option<HashMap<SmallString, Relation>>,
     /// The last modification time of the Card.
     /// Not localized.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated: Option<String>,
     /// The name of the entity represented by the Card.
-    /// Localized by [`localize_name`]
+    /// Localized.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<Name>,
     /// Nicknames of the entity.
-    /// Localized by [`localize_nicknames`]
+    /// Localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub nicknames: Option<HashMap<String, Nickname>>,
+    pub nicknames: Option<HashMap<SmallString, Nickname>>,
     /// Organizations associated with the entity.
     /// Not localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub organizations: Option<HashMap<String, Organization>>,
+    pub organizations: Option<HashMap<SmallString, Organization>>,
     /// How to address or refer to the entity.
     /// Not localized.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// An empty SpeakToAs is not serialized.
+    #[serde(skip_serializing_if = "is_none_or_empty")]
     pub speak_to_as: Option<SpeakToAs>,
     /// Job titles or roles of the entity.
-    /// Localized by [`localize_titles`]
+    /// Localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub titles: Option<HashMap<String, Title>>,
+    pub titles: Option<HashMap<SmallString, Title>>,
     /// Email addresses for contacting the entity.
     /// Not localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub emails: Option<HashMap<String, EmailAddress>>,
+    pub emails: Option<HashMap<SmallString, EmailAddress>>,
     /// Online services or social media associated with the entity.
     /// Not localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub online_services: Option<HashMap<String, OnlineService>>,
+    pub online_services: Option<HashMap<SmallString, OnlineService>>,
     /// Phone numbers for contacting the entity.
     /// Not localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub phones: Option<HashMap<String, Phone>>,
+    pub phones: Option<HashMap<SmallString, Phone>>,
     /// Preferred languages for communication.
     /// Not localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub preferred_languages: Option<HashMap<String, LanguagePref>>,
+    pub preferred_languages: Option<HashMap<SmallString, LanguagePref>>,
     /// The calendaring resources of the entity represented by the Card, such as to look up free-busy information.
-    /// Localized by [`localize_calendars`]
+    /// Localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub calendars: Option<HashMap<String, Calendar>>,
+    pub calendars: Option<HashMap<SmallString, Calendar>>,
     /// The scheduling addresses by which the entity may receive calendar scheduling invitations.
-    /// Localized by [`localize_scheduling_addresses`]
+    /// Localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub scheduling_addresses: Option<HashMap<String, SchedulingAddress>>,
+    pub scheduling_addresses: Option<HashMap<SmallString, SchedulingAddress>>,
     /// Localizations provide language-specific alternatives for existing property values and SHOULD NOT add new properties.
     /// Not localized
     /// This is a special case, the localization is done by the [`crate::Card::get_localized`] method.
     #[serde(skip_serializing_if = "Option::is_none")]
-    localizations: Option<HashMap<String, HashMap<String, Value>>>,
+    pub(crate) localizations: Option<HashMap<String, RawValues>>,
     /// The memorable dates and events for the entity represented by the Card.
     /// Not localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub anniversaries: Option<HashMap<String, Anniversary>>,
+    pub anniversaries: Option<HashMap<SmallString, Anniversary>>,
     /// The scheduling addresses by which the entity may receive calendar scheduling invitations.
-    /// Localized by [`localize_addresses`]
+    /// Localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub addresses: Option<HashMap<String, Address>>,
+    pub addresses: Option<HashMap<SmallString, Address>>,
     /// The cryptographic resources such as public keys and certificates associated with the entity represented by the Card.
     /// Not localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub crypto_keys: Option<HashMap<String, CryptoKey>>,
+    pub crypto_keys: Option<HashMap<SmallString, CryptoKey>>,
     /// The directories containing information about the entity represented by the Card.
-    /// Localized by [`localize_directories`]
+    /// Localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub directories: Option<HashMap<String, Directory>>,
+    pub directories: Option<HashMap<SmallString, Directory>>,
     /// The links to resources that do not fit any of the other use-case-specific resource properties.
-    /// Localized by [`localize_links`]
+    /// Localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub links: Option<HashMap<String, Link>>,
+    pub links: Option<HashMap<SmallString, Link>>,
     /// The media resources such as photographs, avatars, or sounds that are associated with the entity represented by the Card.
-    /// Localized by [`localize_media`]
+    /// Localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub media: Option<HashMap<String, Media>>,
+    pub media: Option<HashMap<SmallString, Media>>,
     /// The set of free-text keywords, also known as tags.
-    /// Localized by [`localize_keywords`]
+    /// Localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub keywords: Option<HashMap<String, bool>>,
+    pub keywords: Option<HashMap<SmallString, bool>>,
     /// The free-text notes that are associated with the Card.
-    /// Localized by [`localize_notes`]
+    /// Localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub notes: Option<HashMap<String, Note>>,
+    pub notes: Option<HashMap<SmallString, Note>>,
     /// The personal information of the entity represented by the Card.
-    /// Localized by [`localize_personal_info`]
+    /// Localized.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub personal_info: Option<HashMap<String, PersonalInfo>>,
+    pub personal_info: Option<HashMap<SmallString, PersonalInfo>>,
+    /// The properties unknown to this crate, such as vendor-specific properties (e.g. `example.com:foo`)
+    /// or properties of a later version, kept as is.
+    #[serde(flatten)]
+    pub extensions: RawValues,
+    /// The revision of the Card, see [`Card::revision`].
+    #[cfg(feature = "tracking")]
+    #[serde(skip)]
+    pub(crate) revision: Revision,
+    /// The issues of the localizations found when parsing, see [`Card::localization_parse_issues`].
+    #[serde(skip)]
+    pub(crate) localization_parse_issues: ParseIssues,
 }
 
 impl Card {
     /// Creates a new Card object with the specified version and unique identifier.
     pub fn new(version: CardVersion, uid: &str) -> Self {
         Self {
-            card_type: "Card".to_string(),
+            card_type: "Card".into(),
             version,
             uid: uid.to_string(),
             created: None,
@@ -177,885 +207,680 @@ impl Card {
             keywords: None,
             notes: None,
             personal_info: None,
+            extensions: RawValues::new(),
+            #[cfg(feature = "tracking")]
+            revision: Revision::default(),
+            localization_parse_issues: ParseIssues::default(),
         }
     }
 
-    /// Wrapper around serde_json
+    /// Wrapper around serde_json, reading at most [`DEFAULT_MAX_BYTES`] bytes (see [`Card::from_reader_limited`]).
     /// # Errors
-    /// Will return an error if the input is not a valid Card object.
+    /// Will return an error if the input is not a valid Card object or is larger than the limit,
+    /// the [`ReadError::TooLarge`] error being the source of an I/O error.
     pub fn from_reader<R: std::io::Read>(reader: R) -> Result<Self, serde_json::Error> {
-        serde_json::from_reader(reader)
+        Card::from_reader_limited(reader, DEFAULT_MAX_BYTES).map_err(|error| match error {
+            ReadError::Json(error) => error,
+            error => serde_json::Error::io(std::io::Error::other(error)),
+        })
     }
 
     /// Creates a new Card object with the latest version and the specified unique identifier.
     pub fn new_with_latest_version(uid: &str) -> Self {
         Self {
-            #[cfg(feature = "typed")]
-            card_type: "Card".to_string(),
+            card_type: "Card".into(),
             uid: uid.to_string(),
             ..Card::new(CardVersion::OneDotZero, uid)
         }
     }
 
     /// Get the Raw Localizations
-    pub fn get_raw_localizations(&self) -> Option<&HashMap<String, HashMap<String, Value>>> {
+    pub fn get_raw_localizations(&self) -> Option<&HashMap<String, RawValues>> {
         self.localizations.as_ref()
     }
 
     /// Adds a localization to the Card object.
     pub fn add_localization(&mut self, language: &str, value: HashMap<String, Value>) {
+        self.touch();
         match &mut self.localizations {
             Some(localizations_map) => {
-                localizations_map.insert(language.to_string(), value);
+                localizations_map.insert(language.to_string(), value.into());
             }
             None => {
                 let mut localizations_map = HashMap::new();
-                localizations_map.insert(language.to_string(), value);
+                localizations_map.insert(language.to_string(), value.into());
                 self.localizations = Some(localizations_map);
             }
         };
     }
 
     /// Get available languages from the [`Card::localizations`]
-    pub fn get_available_languages(&self) -> Vec<String> {
-        match &self.localizations {
-            Some(localizations_map) => localizations_map.keys().cloned().collect(),
-            None => Vec::new(),
-        }
+    pub fn get_available_languages(&self) -> impl Iterator<Item = &str> + '_ {
+        self.localizations
+            .iter()
+            .flat_map(|localizations_map| localizations_map.keys())
+            .map(String::as_str)
+    }
+
+    /// Get available languages from the [`Card::localizations`] as owned strings
+    #[deprecated(since = "0.2.2", note = "use `get_available_languages` instead")]
+    pub fn get_available_languages_vec(&self) -> Vec<String> {
+        self.get_available_languages().map(String::from).collect()
+    }
+
+    /// Get all the languages of the Card: its `language`, the languages of its localizations
+    /// and of its `preferredLanguages`, as written (the tags are not normalized).
+    pub fn languages_in_use(&self) -> BTreeSet<String> {
+        let preferred = self
+            .preferred_languages
+            .iter()
+            .flatten()
+            .map(|(_, pref)| pref.language.as_str());
+        self.language
+            .as_deref()
+            .into_iter()
+            .chain(self.get_available_languages())
+            .chain(preferred)
+            .map(String::from)
+            .collect()
+    }
+
+    /// Check if the Card has a localization for the specified language.
+    pub fn has_localization(&self, language: &str) -> bool {
+        self.localizations
+            .as_ref()
+            .is_some_and(|localizations_map| localizations_map.contains_key(language))
     }
 
     /// Get the localized Card object for the specified language.
+    ///
+    /// A copy of the Card is returned if the Card is already in the language or has no localization for it.
     /// # Errors
     /// Will return an error if translation are invalid.
+    ///
+    /// A patch of a value which is not localizable (e.g. `phones/tel0/contexts`) is rejected,
+    /// see [`Card::get_localized_lenient`] to skip it instead.
     pub fn get_localized(&self, language: &str) -> Result<Card, String> {
-        let lang = language.to_string();
+        self.localize(language, true, &PatchLimits::default())
+            .map(|(localized_card, _)| localized_card)
+    }
+
+    /// Get the localized Card object for the specified language, skipping the patches of values which are not localizable.
+    ///
+    /// The skipped patches are returned as warnings, the non-localizable fields of an object patch keep their value.
+    /// # Errors
+    /// Will return an error if translation are invalid.
+    pub fn get_localized_lenient(
+        &self,
+        language: &str,
+    ) -> Result<(Card, Vec<LocalizationIssue>), String> {
+        self.localize(language, false, &PatchLimits::default())
+    }
+
+    /// Get the localized Card object for the specified language, with the options of [`localize_all`](crate::localize_all).
+    ///
+    /// The patches are checked against the limits of the options, see [`PatchLimits`].
+    /// # Errors
+    /// Will return an error if translation are invalid or exceed the limits.
+    pub fn get_localized_with(
+        &self,
+        language: &str,
+        opts: &LocalizeOptions,
+    ) -> Result<Card, String> {
+        if opts.fallback_chain {
+            self.localize_chain(language, &opts.limits)
+        } else {
+            self.localize(language, !opts.lenient, &opts.limits)
+                .map(|(localized_card, _)| localized_card)
+        }
+    }
+
+    /// Localize the Card, a non-localizable patch is an error if strict
+    fn localize(
+        &self,
+        language: &str,
+        strict: bool,
+        limits: &PatchLimits,
+    ) -> Result<(Card, Vec<LocalizationIssue>), String> {
+        if self.is_in_language(language) {
+            // the patches of the other languages do not apply
+            return Ok((self.clone(), Vec::new()));
+        }
         let localizations = match &self.localizations {
             Some(localizations_map) => localizations_map,
-            None => return Ok(self.clone()),
+            None => return Ok((self.clone(), Vec::new())),
         };
-        let localized_lang = match localizations.get(&lang) {
+        let localized_lang = match localizations.get(language) {
             Some(lang) => lang,
-            None => return Ok(self.clone()),
+            None => return Ok((self.clone(), Vec::new())),
         };
         // iter on localized_lang and set the values
         let mut localized_card = self.clone();
         // remove localizations of the localized card
         localized_card.localizations = None;
         // set the language of the localized card
-        localized_card.language = Some(lang);
-        localize_card(&mut localized_card, localized_lang)?;
-        Ok(localized_card)
+        localized_card.language = Some(language.to_string());
+        let (patches, issues) =
+            localizable_patches(&localized_card, language, localized_lang, limits)?;
+        if let (true, Some(issue)) = (strict, issues.first()) {
+            return Err(issue.to_string());
+        }
+        localize_card(&mut localized_card, patches, limits)?;
+        Ok((localized_card, issues))
+    }
+
+    /// Get the issues of the localizations found when parsing the Card with [`ParseOptions::verify_localizations`](crate::ParseOptions::verify_localizations).
+    ///
+    /// Each issue is a patch whose value does not fit the patched property, e.g. a number patching a title name,
+    /// the same patch making [`Card::get_localized`] fail. The issues are not updated when the Card is modified.
+    pub fn localization_parse_issues(&self) -> &[LocalizationIssue] {
+        &self.localization_parse_issues.0
+    }
+
+    /// Type-check the patches of the localizations, applying them one by one in the order of [`Card::get_localized`].
+    ///
+    /// A patch that cannot be applied is an issue and is skipped, so the next patches are still checked.
+    pub(crate) fn verify_localizations(&mut self) {
+        let mut issues = Vec::new();
+        let mut languages: Vec<(&String, &RawValues)> =
+            self.localizations.iter().flatten().collect();
+        languages.sort_by_key(|(language, _)| *language);
+        for (language, localized_lang) in languages {
+            let issue = |path: &str, message: String| LocalizationIssue {
+                language: language.clone(),
+                path: path.to_string(),
+                message,
+            };
+            let patches = match localizable_patches(
+                self,
+                language,
+                localized_lang,
+                &PatchLimits::default(),
+            ) {
+                Ok((patches, _)) => patches,
+                Err(error) => {
+                    issues.push(issue("", error));
+                    continue;
+                }
+            };
+            let mut localized_card = self.clone();
+            localized_card.localizations = None;
+            for (key, value) in patches {
+                let mut patched = localized_card.clone();
+                match localize_card(
+                    &mut patched,
+                    vec![(key.clone(), value)],
+                    &PatchLimits::default(),
+                ) {
+                    Ok(()) => localized_card = patched,
+                    Err(error) => issues.push(issue(&key, error)),
+                }
+            }
+        }
+        self.localization_parse_issues = ParseIssues(issues);
     }
-}
 
-impl FromStr for Card {
-    type Err = serde_json::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        serde_json::from_str(s)
+    /// Get the localized Card object for the specified language, or a copy of the Card if the localization fails.
+    ///
+    /// Useful to display a Card, which must always render something.
+    pub fn get_localized_or_base(&self, language: &str) -> Card {
+        self.get_localized(language)
+            .unwrap_or_else(|_| self.clone())
     }
-}
-
-impl TryFrom<&[u8]> for Card {
-    type Error = serde_json::Error;
 
-    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
-        serde_json::from_slice(slice)
+    /// Check if the Card is in the specified language or has a localization for it.
+    pub fn is_localizable_into(&self, language: &str) -> bool {
+        self.is_in_language(language) || self.has_localization(language)
     }
-}
 
-impl TryFrom<Value> for Card {
-    type Error = String;
-
-    fn try_from(value: Value) -> Result<Self, Self::Error> {
-        let card: Card = serde_json::from_value(value.clone()).map_err(|e| e.to_string())?;
-        Ok(card)
+    /// Check if the language of the Card is the specified language, ignoring the case
+    fn is_in_language(&self, language: &str) -> bool {
+        self.language
+            .as_deref()
+            .is_some_and(|card_language| card_language.eq_ignore_ascii_case(language))
     }
-}
 
-impl TryFrom<Card> for String {
-    type Error = serde_json::Error;
+    /// Get the localized Card object for the specified language from a shared Card.
+    ///
+    /// The shared Card is returned without copy if there is no localization for the language.
+    /// # Errors
+    /// Will return an error if translation are invalid.
+    pub fn get_localized_shared(self: &Arc<Self>, language: &str) -> Result<Arc<Card>, String> {
+        if self.has_localization(language) {
+            self.get_localized(language).map(Arc::new)
+        } else {
+            Ok(Arc::clone(self))
+        }
+    }
 
-    fn try_from(card: Card) -> Result<Self, Self::Error> {
-        serde_json::to_string(&card)
+    /// Get the localized Card object for the specified language tag, using the fallback chain of the tag.
+    ///
+    /// The tag is truncated right-to-left (`zh-Hant-TW`, `zh-Hant`, `zh`) and the localizations found
+    /// for each language are applied from the least specific to the most specific one,
+    /// so a patch of `fr-CA` overrides the same patch of `fr` and inherits the others.
+    /// As with [`Card::get_localized`], a patch of a value which is not localizable is rejected.
+    /// # Errors
+    /// Will return an error if translation are invalid.
+    pub fn get_localized_chain(&self, tag: &str) -> Result<Card, String> {
+        self.localize_chain(tag, &PatchLimits::default())
     }
-}
 
-/// Localize the Card object with jsonptr
-#[cfg(feature = "jsonptr")]
-fn localize_card(
-    localized_card: &mut Card,
-    localized_lang: &HashMap<String, Value>,
-) -> Result<(), String> {
-    use jsonptr::Pointer;
-    let Ok(mut card_value) = serde_json::to_value(&localized_card) else {
-        return Err("Failed to convert card to value".into());
-    };
-    for (key, value) in localized_lang.iter() {
-        let key = format!("/{}", key);
-        let ptr = match Pointer::parse(&key) {
-            Ok(ptr) => ptr,
-            Err(e) => return Err(format!("Failed to parse pointer: {}", e)),
+    /// Localize the Card with the fallback chain of a language tag
+    fn localize_chain(&self, tag: &str, limits: &PatchLimits) -> Result<Card, String> {
+        let localizations = match &self.localizations {
+            Some(localizations_map) => localizations_map,
+            None => return Ok(self.clone()),
         };
-        match ptr.assign(&mut card_value, value.clone()) {
-            Ok(_) => (),
-            Err(e) => return Err(format!("Failed to assign value: {}", e)),
+        let layers: Vec<(&String, &RawValues)> = language_fallback_chain(tag)
+            .into_iter()
+            .rev()
+            .filter_map(|language| {
+                localizations
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case(language))
+            })
+            .collect();
+        let Some((most_specific, _)) = layers.last() else {
+            return Ok(self.clone());
+        };
+        let mut localized_card = self.clone();
+        localized_card.localizations = None;
+        localized_card.language = Some(most_specific.to_string());
+        for (language, localized_lang) in &layers {
+            let (patches, issues) =
+                localizable_patches(&localized_card, language, localized_lang, limits)?;
+            if let Some(issue) = issues.first() {
+                return Err(issue.to_string());
+            }
+            localize_card(&mut localized_card, patches, limits)?;
         }
+        Ok(localized_card)
     }
-    *localized_card = serde_json::from_value(card_value).unwrap();
-    Ok(())
 }
 
-/// Localize the Card object
-#[cfg(not(feature = "jsonptr"))]
-fn localize_card(
-    localized_card: &mut Card,
-    localized_lang: &HashMap<String, Value>,
-) -> Result<(), String> {
-    for (key, value) in localized_lang.iter() {
-        // Deliberately not using jsonptr here
-        if key.starts_with("name") {
-            localize_name(localized_card, key, value)?;
-        } else if key.starts_with("titles") {
-            localize_titles(localized_card, key, value)?;
-        } else if key.starts_with("addresses") {
-            localize_addresses(localized_card, key, value)?;
-        } else if key.starts_with("nicknames") {
-            localize_nicknames(localized_card, key, value)?;
-        } else if key.starts_with("personalInfo") {
-            localize_personal_info(localized_card, key, value)?;
-        } else if key.starts_with("notes") {
-            localize_notes(localized_card, key, value)?;
-        } else if key.starts_with("keywords") {
-            localize_keywords(localized_card, key, value)?;
-        } else if key.starts_with("media") {
-            localize_media(localized_card, key, value)?;
-        } else if key.starts_with("links") {
-            localize_links(localized_card, key, value)?;
-        } else if key.starts_with("directories") {
-            localize_directories(localized_card, key, value)?;
-        } else if key.starts_with("calendars") {
-            localize_calendars(localized_card, key, value)?;
-        } else if key.starts_with("schedulingAddresses") {
-            localize_scheduling_addresses(localized_card, key, value)?;
+/// Get the fallback chain of a language tag, from the most specific to the least specific
+///
+/// Single-character subtags (extensions and private use) are removed along with the subtag following them.
+fn language_fallback_chain(tag: &str) -> Vec<&str> {
+    let mut chain = Vec::new();
+    let mut current = tag;
+    while !current.is_empty() {
+        chain.push(current);
+        current = match current.rfind('-') {
+            Some(idx) => &current[..idx],
+            None => "",
+        };
+        if let Some(idx) = current.rfind('-') {
+            if current.len() - idx == 2 {
+                current = &current[..idx];
+            }
         }
     }
-    Ok(())
+    chain
 }
 
-/// remove the first character of a string
-#[cfg(not(feature = "jsonptr"))]
-#[inline]
-fn remove_first(s: &str) -> &str {
-    let mut chars = s.chars();
-    chars.next();
-    chars.as_str()
+/// Skip the serialization of a SpeakToAs without grammatical gender nor pronouns
+fn is_none_or_empty(speak_to_as: &Option<SpeakToAs>) -> bool {
+    speak_to_as.as_ref().is_none_or(SpeakToAs::is_empty)
 }
 
-/// Localize the [`crate::Name`]
-#[cfg(not(feature = "jsonptr"))]
-fn localize_name(card: &mut Card, key: &str, value: &Value) -> Result<(), String> {
-    if key == "name" {
-        card.name = serde_json::from_value(value.clone()).ok();
-        return Ok(());
-    }
-    let curr_name = match &mut card.name {
-        Some(name) => name,
-        None => &mut Name::default(),
-    };
-    let key = key.replace("name/", "");
-    if key.starts_with("components") {
-        if key == "components" {
-            curr_name.components = serde_json::from_value(value.clone()).ok();
-            card.name = Some(curr_name.clone());
-            return Ok(());
-        }
-        let components = match &mut curr_name.components {
-            Some(components) => components,
-            None => &mut vec![],
-        };
-        let key = key.replace("components/", "");
-        let keys = key.split("/").collect::<Vec<&str>>();
-        let Some(idx) = keys.first() else {
-            return Err("Index out of bounds".into());
-        };
-        let key = key.replace(&format!("{}/", idx), "");
-        let Ok(idx) = idx.parse::<usize>() else {
-            return Err("Index out of bounds".into());
-        };
-        if components.len() <= idx {
-            return Err("Index out of bounds".into());
-        }
-        let component: &mut NameComponent = &mut components[idx];
-        if key == "value" {
-            let Ok(str) = serde_json::from_value::<String>(value.clone()) else {
-                return Err("Invalid value".into());
-            };
-            component.value = str;
-        } else if key == "phonetic" {
-            component.phonetic = serde_json::from_value(value.clone()).ok();
-        }
-        curr_name.components = Some(components.clone());
-    } else if key == "full" {
-        curr_name.full = serde_json::from_value(value.clone()).ok();
-    } else if key == "phoneticSystem" {
-        curr_name.phonetic_system = serde_json::from_value(value.clone()).ok();
-    } else if key == "phoneticScript" {
-        curr_name.phonetic_script = serde_json::from_value(value.clone()).ok();
-    }
-    card.name = Some(curr_name.clone());
-    Ok(())
+/// A required property missing from a Card, reported instead of the generic `missing field` error of serde.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingRequired {
+    /// The missing property (e.g. `uid`).
+    pub field: &'static str,
+    /// What the property must be.
+    pub hint: &'static str,
 }
 
-/// Localize the [`crate::Titles`]
-#[cfg(not(feature = "jsonptr"))]
-fn localize_titles(card: &mut Card, key: &str, value: &Value) -> Result<(), String> {
-    if key == "titles" {
-        card.titles = serde_json::from_value(value.clone()).ok();
-        return Ok(());
-    }
-    let titles = match &mut card.titles {
-        Some(titles) => titles,
-        None => &mut HashMap::new(),
-    };
-    let key = key.replace("titles/", "");
-    let keys = key.split("/").collect::<Vec<&str>>();
-    let Some(idx_key) = keys.first() else {
-        return Err("Index out of bounds".into());
-    };
-    let idx_key = idx_key.to_string();
-    let key = key.replace(&idx_key, "");
-    let key = if key.is_empty() {
-        let Ok(title) = serde_json::from_value::<Title>(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        titles.insert(idx_key, title);
-        card.titles = Some(titles.clone());
-        return Ok(());
-    } else {
-        remove_first(&key)
-    };
-    let Some(title) = titles.get_mut(&idx_key) else {
-        return Err(format!("titles key '{}' not found", idx_key));
-    };
-    if key == "name" {
-        let Ok(str) = serde_json::from_value::<String>(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        title.name = str;
-    } else if key == "kind" {
-        title.kind = serde_json::from_value(value.clone()).ok();
-    } else if key == "organizationId" {
-        let Ok(str) = serde_json::from_value::<String>(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        title.organization_id = Some(str);
+/// The required properties of a Card, with their hints
+const REQUIRED: [MissingRequired; 3] = [
+    MissingRequired {
+        field: "@type",
+        hint: "@type must be \"Card\"",
+    },
+    MissingRequired {
+        field: "version",
+        hint: "version must be the JSContact version of the Card, e.g. \"1.0\"",
+    },
+    MissingRequired {
+        field: "uid",
+        hint: "uid must be a URI identifying the contact",
+    },
+];
+
+impl fmt::Display for MissingRequired {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "missing required property `{}`: {}",
+            self.field, self.hint
+        )
     }
-    card.titles = Some(titles.clone());
-    Ok(())
 }
 
-/// Localize the [`crate::Addresses`]
-#[cfg(not(feature = "jsonptr"))]
-fn localize_addresses(card: &mut Card, key: &str, value: &Value) -> Result<(), String> {
-    let full_key = key;
-    if key == "addresses" {
-        card.addresses = serde_json::from_value(value.clone()).ok();
-        return Ok(());
-    }
-    let key = key.replace("addresses/", "");
-    let addresses = match &mut card.addresses {
-        Some(addresses) => addresses,
-        None => &mut HashMap::new(),
-    };
-    let keys = key.split("/").collect::<Vec<&str>>();
-    let Some(idx_key) = keys.first() else {
-        return Err("Invalid addresses key".into());
-    };
-    let idx_key = idx_key.to_string();
-    let key = key.replace(&idx_key, "");
-    let key = remove_first(&key);
-    let Some(address) = addresses.get_mut(&idx_key) else {
-        return Err(format!("addresses key '{}' not found", idx_key));
-    };
-    if key.starts_with("components") {
-        if key == "components" {
-            address.components = serde_json::from_value(value.clone()).ok();
-            card.addresses = Some(addresses.clone());
-            return Ok(());
-        }
-        let components = match &mut address.components {
-            Some(components) => components,
-            None => &mut vec![],
-        };
-        let key = key.replace("components/", "");
-        let keys = key.split("/").collect::<Vec<&str>>();
-        let Some(idx) = keys.first() else {
-            return Err("Index out of bounds".into());
-        };
-        let key = key.replace(idx, "");
-        let key = remove_first(&key);
-        let Ok(idx) = idx.parse::<usize>() else {
-            return Err("Index out of bounds".into());
-        };
-        while components.len() <= idx {
-            components.push(AddressComponent::new(
-                AddressComponentKind::Apartment,
-                "DEFAULT",
-            ));
-        }
-        if key.is_empty() {
-            let Ok(component) = serde_json::from_value::<AddressComponent>(value.clone()) else {
-                return Err("Invalid value".into());
-            };
-            components[idx] = component;
-            address.components = Some(components.clone());
-            card.addresses = Some(addresses.clone());
-            return Ok(());
-        }
-        let component: &mut AddressComponent = &mut components[idx];
-        if key == "value" {
-            let Ok(str) = serde_json::from_value::<String>(value.clone()) else {
-                return Err(format!(
-                    "Invalid value: {} for value (at {})",
-                    value, full_key
-                ));
-            };
-            component.value = str;
-        } else if key == "kind" {
-            let Ok(kind) = serde_json::from_value::<AddressComponentKind>(value.clone()) else {
-                return Err(format!(
-                    "Invalid value: {} for kind (at {})",
-                    value, full_key
-                ));
-            };
-            component.kind = kind;
-        } else if key == "phonetic" {
-            component.phonetic = serde_json::from_value(value.clone()).ok();
-        }
-        address.components = Some(components.clone());
-    } else if key == "full" {
-        address.full = serde_json::from_value(value.clone()).ok();
-    } else if key == "countryCode" {
-        address.country_code = serde_json::from_value(value.clone()).ok();
-    } else if key == "coordinates" {
-        address.coordinates = serde_json::from_value(value.clone()).ok();
-    } else if key == "timeZone" {
-        address.time_zone = serde_json::from_value(value.clone()).ok();
-    } else if key == "contexts" {
-        address.contexts = serde_json::from_value(value.clone()).ok();
-    } else if key.is_empty() {
-        let Ok(addr) = serde_json::from_value::<Address>(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        addresses.insert(idx_key, addr);
+impl std::error::Error for MissingRequired {}
+
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // serde recurses once per level of the unknown properties and of the localizations
+        self.check_depth(DEFAULT_MAX_DEPTH)
+            .map_err(ser::Error::custom)?;
+        Card::serialize(self, serializer)
     }
-    card.addresses = Some(addresses.clone());
-    Ok(())
 }
 
-/// Localize the [`crate::Nicknames`]
-#[cfg(not(feature = "jsonptr"))]
-fn localize_nicknames(card: &mut Card, key: &str, value: &Value) -> Result<(), String> {
-    if key == "nicknames" {
-        card.nicknames = serde_json::from_value(value.clone()).ok();
-        return Ok(());
-    }
-    let nicknames = match &mut card.nicknames {
-        Some(nicknames) => nicknames,
-        None => &mut HashMap::new(),
-    };
-    let key = key.replace("nicknames", "");
-    let key = if key.is_empty() {
-        let Ok(nicks) = serde_json::from_value::<HashMap<String, Nickname>>(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        *nicknames = nicks;
-        card.nicknames = Some(nicknames.clone());
-        return Ok(());
-    } else {
-        remove_first(&key)
-    };
-    let keys = key.split("/").collect::<Vec<&str>>();
-    let Some(idx_key) = keys.first() else {
-        return Err("Invalid nicknames key".into());
-    };
-    let idx_key = idx_key.to_string();
-    let key = key.replace(&idx_key, "");
-    let key = if key.is_empty() {
-        let Ok(nick) = serde_json::from_value::<Nickname>(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        nicknames.insert(idx_key, nick);
-        card.nicknames = Some(nicknames.clone());
-        return Ok(());
-    } else {
-        remove_first(&key)
-    };
-    let Some(nick) = nicknames.get_mut(&idx_key) else {
-        return Err(format!("nicknames key '{}' not found", idx_key));
-    };
-    if key == "name" {
-        let Ok(str) = serde_json::from_value::<String>(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        nick.name = str;
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(CardVisitor)
     }
-    card.nicknames = Some(nicknames.clone());
-    Ok(())
 }
 
-/// Localize the [`crate::PersonalInfos`]
-#[cfg(not(feature = "jsonptr"))]
-fn localize_personal_info(card: &mut Card, key: &str, value: &Value) -> Result<(), String> {
-    if key == "personalInfo" {
-        card.personal_info = serde_json::from_value(value.clone()).ok();
-        return Ok(());
-    }
-    let personal_infos = match &mut card.personal_info {
-        Some(personal_infos) => personal_infos,
-        None => &mut HashMap::new(),
-    };
-    let key = key.replace("personalInfo", "");
-    if key.is_empty() {
-        let Ok(personal_infos_map) =
-            serde_json::from_value::<HashMap<String, PersonalInfo>>(value.clone())
-        else {
-            return Err("Invalid value".into());
-        };
-        *personal_infos = personal_infos_map;
-        card.personal_info = Some(personal_infos.clone());
-        return Ok(());
-    }
-    let key = remove_first(&key);
-    let keys = key.split("/").collect::<Vec<&str>>();
-    let Some(idx_key) = keys.first() else {
-        return Err("Invalid personalInfo key".into());
-    };
-    let idx_key = idx_key.to_string();
-    let key = key.replace(&idx_key, "");
-    if key.is_empty() {
-        let Ok(personal_info) = serde_json::from_value::<PersonalInfo>(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        personal_infos.insert(idx_key, personal_info);
-        card.personal_info = Some(personal_infos.clone());
-        return Ok(());
+/// Deserialize a Card with the derived implementation, recording the required properties of the object
+struct CardVisitor;
+
+impl<'de> de::Visitor<'de> for CardVisitor {
+    type Value = Card;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a Card object")
     }
-    let key = remove_first(&key);
-    let Some(personal_info) = personal_infos.get_mut(&idx_key) else {
-        return Err(format!("personalInfo key '{}' not found", idx_key));
-    };
-    if key == "value" {
-        let Ok(str) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        personal_info.value = str;
-    } else if key == "kind" {
-        let Ok(kind) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
+
+    fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<Card, A::Error> {
+        let mut seen = [false; REQUIRED.len()];
+        let mut ended = false;
+        let map = RequiredMap {
+            map,
+            seen: &mut seen,
+            ended: &mut ended,
         };
-        personal_info.kind = kind;
+        let result = Card::deserialize(de::value::MapAccessDeserializer::new(map));
+        result.map_err(|error| {
+            // serde checks the required properties once all the members are read,
+            // an error before the end is about the members read so far
+            let missing = REQUIRED.iter().zip(seen).find(|(_, seen)| !seen);
+            match missing {
+                Some((missing, _)) if ended => de::Error::custom(missing),
+                _ => error,
+            }
+        })
     }
-    card.personal_info = Some(personal_infos.clone());
-    Ok(())
 }
 
-/// Localize the [`crate::Notes`]
-#[cfg(not(feature = "jsonptr"))]
-fn localize_notes(card: &mut Card, key: &str, value: &Value) -> Result<(), String> {
-    if key == "notes" {
-        card.notes = serde_json::from_value(value.clone()).ok();
-        return Ok(());
-    }
-    let notes = match &mut card.notes {
-        Some(notes) => notes,
-        None => &mut HashMap::new(),
-    };
-    let key = key.replace("notes", "");
-    if key.is_empty() {
-        let Ok(notes_map) = serde_json::from_value::<HashMap<String, Note>>(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        *notes = notes_map;
-        card.notes = Some(notes.clone());
-        return Ok(());
+/// The members of a Card object, recording the required properties read and the end of the object
+struct RequiredMap<'a, A> {
+    /// The members of the object
+    map: A,
+    /// Whether each of the `REQUIRED` properties was read
+    seen: &'a mut [bool; REQUIRED.len()],
+    /// Whether all the members were read
+    ended: &'a mut bool,
+}
+
+impl<'de, A: de::MapAccess<'de>> de::MapAccess<'de> for RequiredMap<'_, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, A::Error> {
+        let key = self.map.next_key_seed(RequiredKey {
+            seed,
+            seen: self.seen,
+        })?;
+        *self.ended = key.is_none();
+        Ok(key)
     }
-    let key = remove_first(&key);
-    let keys = key.split("/").collect::<Vec<&str>>();
-    let Some(idx_key) = keys.first() else {
-        return Err("Invalid notes key".into());
-    };
-    let idx_key = idx_key.to_string();
-    let key = key.replace(&idx_key, "");
-    if key.is_empty() {
-        let Ok(note) = serde_json::from_value::<Note>(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        notes.insert(idx_key, note);
-        card.notes = Some(notes.clone());
-        return Ok(());
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, A::Error> {
+        self.map.next_value_seed(seed)
     }
-    let key = remove_first(&key);
-    let Some(note) = notes.get_mut(&idx_key) else {
-        return Err(format!("notes key '{}' not found", idx_key));
-    };
-    if key == "note" {
-        let Ok(str) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        note.note = str;
-    } else if key == "created" {
-        note.created = serde_json::from_value(value.clone()).ok();
-    } else if key == "author" {
-        let Ok(author) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        note.author = author;
+
+    fn size_hint(&self) -> Option<usize> {
+        self.map.size_hint()
     }
-    card.notes = Some(notes.clone());
-    Ok(())
 }
 
-/// Localize the Keywords
-#[cfg(not(feature = "jsonptr"))]
-fn localize_keywords(card: &mut Card, key: &str, value: &Value) -> Result<(), String> {
-    if key == "keywords" {
-        card.keywords = serde_json::from_value(value.clone()).ok();
-        return Ok(());
+/// The key of a member of a Card object, recorded if it is a required property before being given to the derived implementation
+struct RequiredKey<'a, K> {
+    /// The key seed of the derived implementation
+    seed: K,
+    /// Whether each of the `REQUIRED` properties was read
+    seen: &'a mut [bool; REQUIRED.len()],
+}
+
+impl<'de, K: de::DeserializeSeed<'de>> de::DeserializeSeed<'de> for RequiredKey<'_, K> {
+    type Value = K::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<K::Value, D::Error> {
+        let key = deserializer.deserialize_str(KeyVisitor)?;
+        if let Some(idx) = REQUIRED.iter().position(|required| required.field == key) {
+            self.seen[idx] = true;
+        }
+        match key {
+            Cow::Borrowed(key) => self
+                .seed
+                .deserialize(de::value::BorrowedStrDeserializer::new(key)),
+            Cow::Owned(key) => self
+                .seed
+                .deserialize(de::IntoDeserializer::<D::Error>::into_deserializer(key)),
+        }
     }
-    Ok(())
 }
 
-/// Localize the [`crate::Media`]
-#[cfg(not(feature = "jsonptr"))]
-fn localize_media(card: &mut Card, key: &str, value: &Value) -> Result<(), String> {
-    if key == "media" {
-        card.media = serde_json::from_value(value.clone()).ok();
-        return Ok(());
+/// The key of a member of a Card object, borrowed from the input when possible
+struct KeyVisitor;
+
+impl<'de> de::Visitor<'de> for KeyVisitor {
+    type Value = Cow<'de, str>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a property name")
     }
-    let medias_hash_map = match &mut card.media {
-        Some(media) => media,
-        None => &mut HashMap::new(),
-    };
-    let key = key.replace("media", "");
-    if key.is_empty() {
-        let Ok(media_map) = serde_json::from_value::<HashMap<String, Media>>(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        *medias_hash_map = media_map;
-        card.media = Some(medias_hash_map.clone());
-        return Ok(());
+
+    fn visit_borrowed_str<E: de::Error>(self, key: &'de str) -> Result<Self::Value, E> {
+        Ok(Cow::Borrowed(key))
     }
-    let key = remove_first(&key);
-    let keys = key.split("/").collect::<Vec<&str>>();
-    let Some(idx_key) = keys.first() else {
-        return Err("Invalid media key".into());
-    };
-    let idx_key = idx_key.to_string();
-    let key = key.replace(&idx_key, "");
-    if key.is_empty() {
-        let Ok(media_serde) = serde_json::from_value::<Media>(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        medias_hash_map.insert(idx_key, media_serde);
-        card.media = Some(medias_hash_map.clone());
-        return Ok(());
+
+    fn visit_str<E: de::Error>(self, key: &str) -> Result<Self::Value, E> {
+        Ok(Cow::Owned(key.to_string()))
     }
-    let key = remove_first(&key);
-    let Some(media) = medias_hash_map.get_mut(&idx_key) else {
-        return Err(format!("media key '{}' not found", idx_key));
-    };
-    if key == "type" {
-        let Ok(media_type) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        media.media_type = media_type;
-    } else if key == "uri" {
-        let Ok(uri) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        media.uri = uri;
-    } else if key == "contexts" {
-        let Ok(contexts_map) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        media.contexts = contexts_map;
-    } else if key == "pref" {
-        let Ok(pref) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        media.pref = pref;
-    } else if key == "label" {
-        let Ok(label) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        media.label = label;
+
+    fn visit_string<E: de::Error>(self, key: String) -> Result<Self::Value, E> {
+        Ok(Cow::Owned(key))
     }
-    card.media = Some(medias_hash_map.clone());
-    Ok(())
 }
 
-/// Localize the Links
-#[cfg(not(feature = "jsonptr"))]
-fn localize_links(card: &mut Card, key: &str, value: &Value) -> Result<(), String> {
-    if key == "links" {
-        card.links = serde_json::from_value(value.clone()).ok();
-        return Ok(());
-    }
-    let links = match &mut card.links {
-        Some(links) => links,
-        None => &mut HashMap::new(),
-    };
-    let key = key.replace("links", "");
-    if key.is_empty() {
-        let Ok(links_map) = serde_json::from_value::<HashMap<String, Link>>(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        *links = links_map;
-        card.links = Some(links.clone());
-        return Ok(());
+impl FromStr for Card {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
     }
-    let key = remove_first(&key);
-    let keys = key.split("/").collect::<Vec<&str>>();
-    let Some(idx_key) = keys.first() else {
-        return Err("Invalid links key".into());
-    };
-    let idx_key = idx_key.to_string();
-    let key = key.replace(&idx_key, "");
-    if key.is_empty() {
-        let Ok(link) = serde_json::from_value::<Link>(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        links.insert(idx_key, link);
-        card.links = Some(links.clone());
-        return Ok(());
+}
+
+/// A one-line summary of the contact, e.g. `John Smith <jqpublic@xyz.example.com> (+1-555-555-5555) — ABC, Inc.`.
+///
+/// The summary is made of the full name, the preferred email and phone, and the name of the primary organization
+/// (see [`Card::primary_organization`]), the missing values being left out. A Card of the `org` kind without name
+/// is named after its organization. A Card with none of these values is `Card <uid>`.
+///
+/// The personal data are not masked, see [`Card::debug_redacted`] to log a Card without them.
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let org = self
+            .primary_organization()
+            .and_then(|organization| organization.name.as_deref())
+            .filter(|org| !org.is_empty());
+        let name = self
+            .name
+            .as_ref()
+            .map(|name| name.to_full_string())
+            .filter(|name| !name.is_empty())
+            .or_else(|| {
+                org.filter(|_| self.kind == Some(CardKind::Org))
+                    .map(str::to_string)
+            });
+        let mut parts = Vec::new();
+        parts.extend(name.clone());
+        if let Some(address) = self.preferred_email_address() {
+            parts.push(format!("<{}>", address));
+        }
+        if let Some(number) = self.preferred_phone_number() {
+            parts.push(format!(
+                "({})",
+                number.strip_prefix("tel:").unwrap_or(number)
+            ));
+        }
+        let summary = parts.join(" ");
+        match org.filter(|org| name.as_deref() != Some(*org)) {
+            Some(org) if summary.is_empty() => write!(f, "{}", org),
+            Some(org) => write!(f, "{} — {}", summary, org),
+            None if summary.is_empty() => write!(f, "Card <{}>", self.uid),
+            None => write!(f, "{}", summary),
+        }
     }
-    let key = remove_first(&key);
-    let Some(link) = links.get_mut(&idx_key) else {
-        return Err(format!("links key '{}' not found", idx_key));
-    };
-    if key == "uri" {
-        let Ok(uri) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        link.uri = uri;
-    } else if key == "contexts" {
-        let Ok(contexts_map) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        link.contexts = contexts_map;
-    } else if key == "pref" {
-        let Ok(pref) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        link.pref = pref;
-    } else if key == "label" {
-        let Ok(label) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        link.label = label;
+}
+
+impl TryFrom<&[u8]> for Card {
+    type Error = serde_json::Error;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(slice)
     }
-    Ok(())
 }
 
-/// Localize the [`crate::Directory`]
-#[cfg(not(feature = "jsonptr"))]
-fn localize_directories(card: &mut Card, key: &str, value: &Value) -> Result<(), String> {
-    if key == "directories" {
-        card.directories = serde_json::from_value(value.clone()).ok();
-        return Ok(());
+impl TryFrom<Value> for Card {
+    type Error = String;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let value = check_owned_depth(value, DEFAULT_MAX_DEPTH).map_err(|e| e.to_string())?;
+        if let Value::Object(object) = &value {
+            check_aliases(object).map_err(|e| e.to_string())?;
+        }
+        let card: Card = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        Ok(card)
     }
-    let directories = match &mut card.directories {
-        Some(directories) => directories,
-        None => &mut HashMap::new(),
-    };
-    let key = key.replace("directories", "");
-    if key.is_empty() {
-        let Ok(directories_map) =
-            serde_json::from_value::<HashMap<String, Directory>>(value.clone())
-        else {
-            return Err("Invalid value".into());
-        };
-        *directories = directories_map;
-        card.directories = Some(directories.clone());
-        return Ok(());
+}
+
+impl TryFrom<Card> for String {
+    type Error = serde_json::Error;
+
+    fn try_from(card: Card) -> Result<Self, Self::Error> {
+        serde_json::to_string(&card)
     }
-    let key = remove_first(&key);
-    let keys = key.split("/").collect::<Vec<&str>>();
-    let Some(idx_key) = keys.first() else {
-        return Err("Invalid directories key".into());
-    };
-    let idx_key = idx_key.to_string();
-    let key = key.replace(&idx_key, "");
-    if key.is_empty() {
-        let Ok(directory) = serde_json::from_value::<Directory>(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        directories.insert(idx_key, directory);
-        card.directories = Some(directories.clone());
+}
+
+/// Prepare the target of a patch as [`crate::path::set_values`] does, so both implementations agree:
+/// the missing parents are created, the missing array elements before the patched one are created as empty
+/// objects, and a path through a value that is neither an object nor an array cannot be resolved.
+#[cfg(feature = "jsonptr")]
+fn prepare_patch(
+    target: &mut Value,
+    path: &CardPath,
+    max_index_delta: usize,
+) -> Result<(), String> {
+    use crate::path::{check_index_delta, child};
+    let Some((last, parents)) = path.segments.split_last() else {
         return Ok(());
-    }
-    let key = remove_first(&key);
-    let Some(directory) = directories.get_mut(&idx_key) else {
-        return Err(format!("directories key '{}' not found", idx_key));
     };
-    if key == "uri" {
-        let Ok(uri) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        directory.uri = uri;
-    } else if key == "contexts" {
-        let Ok(contexts_map) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        directory.contexts = contexts_map;
-    } else if key == "listAs" {
-        let Ok(list_as) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        directory.list_as = list_as;
-    } else if key == "pref" {
-        let Ok(pref) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        directory.pref = pref;
-    } else if key == "label" {
-        let Ok(label) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        directory.label = label;
+    let mut current = target;
+    for segment in parents {
+        current =
+            child(current, segment, max_index_delta).map_err(|e| format!("{}: {}", path, e))?;
+    }
+    if current.is_null() {
+        *current = match last {
+            PathSegment::Index(_) => Value::Array(Vec::new()),
+            PathSegment::Key(_) => Value::Object(serde_json::Map::new()),
+        };
+    }
+    match (current, last) {
+        (Value::Object(_), _) => Ok(()),
+        (Value::Array(_), PathSegment::Key(key)) if key == "-" => Ok(()),
+        (Value::Array(values), PathSegment::Index(idx)) => {
+            check_index_delta(values, *idx, max_index_delta)
+                .map_err(|e| format!("{}: {}", path, e))?;
+            if values.len() < *idx {
+                values.resize(*idx, Value::Object(serde_json::Map::new()));
+            }
+            Ok(())
+        }
+        (_, last) => Err(format!("{}: cannot resolve '{}'", path, last)),
     }
-    Ok(())
 }
 
-/// Localize the [`crate::Calendar`]
-#[cfg(not(feature = "jsonptr"))]
-fn localize_calendars(card: &mut Card, key: &str, value: &Value) -> Result<(), String> {
-    if key == "calendars" {
-        card.calendars = serde_json::from_value(value.clone()).ok();
-        return Ok(());
-    }
-    let calendars = match &mut card.calendars {
-        Some(calendars) => calendars,
-        None => &mut HashMap::new(),
-    };
-    let key = key.replace("calendars", "");
-    if key.is_empty() {
-        let Ok(calendars_map) = serde_json::from_value::<HashMap<String, Calendar>>(value.clone())
-        else {
-            return Err("Invalid value".into());
+/// Localize the Card object with jsonptr
+///
+/// Only the patched properties are converted to JSON, the other properties of the Card are kept as is.
+#[cfg(feature = "jsonptr")]
+fn localize_card(
+    localized_card: &mut Card,
+    patches: Patches,
+    limits: &PatchLimits,
+) -> Result<(), String> {
+    use crate::path::{property_from_value, property_to_value};
+    use jsonptr::Pointer;
+    let mut card_value = Value::Object(serde_json::Map::new());
+    for (key, value) in patches {
+        let path = CardPath::parse(&key)?;
+        let Value::Object(properties) = &mut card_value else {
+            return Err("Failed to convert card to value".into());
+        };
+        let property = match properties.entry(path.property.clone()) {
+            serde_json::map::Entry::Occupied(entry) => entry.into_mut(),
+            serde_json::map::Entry::Vacant(entry) => {
+                entry.insert(property_to_value(localized_card, &path.property)?)
+            }
         };
-        *calendars = calendars_map;
-        card.calendars = Some(calendars.clone());
-        return Ok(());
-    }
-    let key = remove_first(&key);
-    let keys = key.split("/").collect::<Vec<&str>>();
-    let Some(idx_key) = keys.first() else {
-        return Err("Invalid calendars key".into());
-    };
-    let idx_key = idx_key.to_string();
-    let key = key.replace(&idx_key, "");
-    if key.is_empty() {
-        let Ok(calendar) = serde_json::from_value::<Calendar>(value.clone()) else {
-            return Err("Invalid value".into());
+        let key = format!("/{}", key);
+        let ptr = match Pointer::parse(&key) {
+            Ok(ptr) => ptr,
+            Err(e) => return Err(format!("Failed to parse pointer: {}", e)),
         };
-        calendars.insert(idx_key, calendar);
-        card.calendars = Some(calendars.clone());
-        return Ok(());
+        if value.is_null() {
+            // a null value removes the patched property
+            ptr.delete(&mut card_value);
+            if let Value::Object(properties) = &mut card_value {
+                properties.entry(path.property).or_insert(Value::Null);
+            }
+            continue;
+        }
+        prepare_patch(property, &path, limits.max_index_delta)?;
+        if let Err(e) = ptr.assign(&mut card_value, value) {
+            return Err(format!("{}: {}", path, e));
+        }
     }
-    let key = remove_first(&key);
-    let Some(calendar) = calendars.get_mut(&idx_key) else {
-        return Err(format!("calendars key '{}' not found", idx_key));
+    let Value::Object(properties) = card_value else {
+        return Err("Failed to convert card to value".into());
     };
-    println!("{:?}", key);
-    if key == "uri" {
-        let Ok(uri) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        calendar.uri = uri;
-    } else if key == "contexts" {
-        let Ok(contexts_map) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        calendar.contexts = contexts_map;
-    } else if key == "pref" {
-        let Ok(pref) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        calendar.pref = pref;
-    } else if key == "label" {
-        let Ok(label) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        calendar.label = label;
+    // a patch may replace a value with a value of another type
+    for (property, value) in properties {
+        property_from_value(localized_card, &property, value)?;
     }
     Ok(())
 }
 
-/// Localize the [`crate::SchedulingAddress`]
+/// Localize the Card object
 #[cfg(not(feature = "jsonptr"))]
-fn localize_scheduling_addresses(card: &mut Card, key: &str, value: &Value) -> Result<(), String> {
-    if key == "schedulingAddresses" {
-        card.scheduling_addresses = serde_json::from_value(value.clone()).ok();
-        return Ok(());
-    }
-    let scheduling_addresses = match &mut card.scheduling_addresses {
-        Some(scheduling_addresses) => scheduling_addresses,
-        None => &mut HashMap::new(),
-    };
-    let key = key.replace("schedulingAddresses", "");
-    if key.is_empty() {
-        let Ok(scheduling_addresses_map) =
-            serde_json::from_value::<HashMap<String, SchedulingAddress>>(value.clone())
-        else {
-            return Err("Invalid value".into());
-        };
-        *scheduling_addresses = scheduling_addresses_map;
-        card.scheduling_addresses = Some(scheduling_addresses.clone());
-        return Ok(());
-    }
-    let key = remove_first(&key);
-    let keys = key.split("/").collect::<Vec<&str>>();
-    let Some(idx_key) = keys.first() else {
-        return Err("Invalid schedulingAddresses key".into());
-    };
-    let idx_key = idx_key.to_string();
-    let key = key.replace(&idx_key, "");
-    if key.is_empty() {
-        let Ok(scheduling_address) = serde_json::from_value::<SchedulingAddress>(value.clone())
-        else {
-            return Err("Invalid value".into());
-        };
-        scheduling_addresses.insert(idx_key, scheduling_address);
-        card.scheduling_addresses = Some(scheduling_addresses.clone());
-        return Ok(());
-    }
-    let key = remove_first(&key);
-    let Some(scheduling_address) = scheduling_addresses.get_mut(&idx_key) else {
-        return Err(format!("schedulingAddresses key '{}' not found", idx_key));
-    };
-    if key == "uri" {
-        let Ok(uri) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        scheduling_address.uri = uri;
-    } else if key == "contexts" {
-        let Ok(contexts_map) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        scheduling_address.contexts = contexts_map;
-    } else if key == "pref" {
-        let Ok(pref) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        scheduling_address.pref = pref
-    } else if key == "label" {
-        let Ok(label) = serde_json::from_value(value.clone()) else {
-            return Err("Invalid value".into());
-        };
-        scheduling_address.label = label;
-    }
-    Ok(())
+fn localize_card(
+    localized_card: &mut Card,
+    patches: Patches,
+    limits: &PatchLimits,
+) -> Result<(), String> {
+    // Deliberately not using jsonptr here
+    let paths = patches
+        .iter()
+        .map(|(key, _)| CardPath::parse(key))
+        .collect::<Result<Vec<CardPath>, String>>()?;
+    set_values_limited(
+        localized_card,
+        paths
+            .iter()
+            .zip(patches.into_iter().map(|(_, value)| value)),
+        limits.max_index_delta,
+    )
 }
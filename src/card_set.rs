@@ -0,0 +1,136 @@
+//! A list of Cards, whose deserialization reports the index of the invalid Card.
+
+use std::fmt;
+#[cfg(feature = "shared")]
+use std::io::Read;
+
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+#[cfg(feature = "shared")]
+use crate::limit::{Limited, ReadError, DEFAULT_MAX_BYTES};
+use crate::Card;
+#[cfg(feature = "shared")]
+use crate::{CardSeed, Interner};
+
+/// A list of Cards, e.g. an address book exported as a JSON array.
+///
+/// It is serialized as an array, the error of an invalid Card giving its index (e.g. `Card at index 2: missing required property `uid`: ...`).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CardSet(pub Vec<Card>);
+
+impl From<Vec<Card>> for CardSet {
+    fn from(cards: Vec<Card>) -> Self {
+        Self(cards)
+    }
+}
+
+impl From<CardSet> for Vec<Card> {
+    fn from(cards: CardSet) -> Self {
+        cards.0
+    }
+}
+
+impl Serialize for CardSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// Visits the Cards of an array one by one, to know the index of an invalid Card, with the values of an
+/// interner if any
+#[derive(Default)]
+struct CardSetVisitor<'a> {
+    /// The interner of the values of the Cards, see [`CardSet::from_reader_interned`]
+    #[cfg(feature = "shared")]
+    interner: Option<&'a mut Interner>,
+    /// The lifetime of the interner
+    #[cfg(not(feature = "shared"))]
+    interner: std::marker::PhantomData<&'a ()>,
+}
+
+impl CardSetVisitor<'_> {
+    /// Get the next Card, with the values of the interner if any
+    fn next_card<'de, A: SeqAccess<'de>>(&mut self, seq: &mut A) -> Result<Option<Card>, A::Error> {
+        #[cfg(feature = "shared")]
+        if let Some(interner) = self.interner.as_deref_mut() {
+            return seq.next_element_seed(CardSeed::new(interner));
+        }
+        seq.next_element::<Card>()
+    }
+}
+
+impl<'de> Visitor<'de> for CardSetVisitor<'_> {
+    type Value = CardSet;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an array of Cards")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<Self::Value, A::Error> {
+        // the size hint comes from the input, it is not trusted for large arrays
+        let mut cards = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(1024));
+        loop {
+            match self.next_card(&mut seq) {
+                Ok(Some(card)) => cards.push(card),
+                Ok(None) => return Ok(CardSet(cards)),
+                Err(e) => {
+                    return Err(de::Error::custom(format!(
+                        "Card at index {}: {}",
+                        cards.len(),
+                        e
+                    )))
+                }
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CardSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(CardSetVisitor::default())
+    }
+}
+
+#[cfg(feature = "shared")]
+impl CardSet {
+    /// Deserialize an array of Cards from a reader of at most [`DEFAULT_MAX_BYTES`] bytes, the values repeated
+    /// across the Cards being interned, see the [`crate::intern`] module.
+    ///
+    /// ```rust
+    /// use jscontact::CardSet;
+    ///
+    /// let json = r#"[{"@type":"Card","version":"1.0","uid":"1"},{"@type":"Card","version":"1.0","uid":"2"}]"#;
+    /// let cards = CardSet::from_reader_interned(json.as_bytes()).unwrap();
+    /// assert_eq!(cards, serde_json::from_str(json).unwrap());
+    /// ```
+    /// # Errors
+    /// Will return an error if the input is too large, cannot be read or is not a valid array of Cards.
+    pub fn from_reader_interned<R: Read>(reader: R) -> Result<CardSet, serde_json::Error> {
+        CardSet::from_reader_interned_limited(reader, DEFAULT_MAX_BYTES).map_err(
+            |error| match error {
+                ReadError::Json(error) => error,
+                error => serde_json::Error::io(std::io::Error::other(error)),
+            },
+        )
+    }
+
+    /// Deserialize an array of Cards from a reader of at most `max_bytes` bytes, the values repeated across the
+    /// Cards being interned, see [`CardSet::from_reader_interned`] and [`CardSet::from_reader_limited`].
+    /// # Errors
+    /// Will return an error if the input is larger than the limit, cannot be read or is not a valid array of Cards.
+    pub fn from_reader_interned_limited<R: Read>(
+        reader: R,
+        max_bytes: usize,
+    ) -> Result<CardSet, ReadError> {
+        let mut interner = Interner::new();
+        let mut limited = Limited::new(reader, max_bytes);
+        let mut deserializer = serde_json::Deserializer::from_reader(&mut limited);
+        let cards = deserializer
+            .deserialize_seq(CardSetVisitor {
+                interner: Some(&mut interner),
+            })
+            .and_then(|cards| deserializer.end().map(|()| cards));
+        cards.map_err(|error| limited.error(error))
+    }
+}
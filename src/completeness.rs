@@ -0,0 +1,193 @@
+//! The completeness of a Card, e.g. for the "profile completeness" gauge of a CRM.
+//!
+//! The score is the sum of the weights of the items present in the Card divided by the sum of all the
+//! weights, between 0.0 and 1.0. The weights are public so products can tune them, a weight of 0.0
+//! leaving the item out.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AnniversaryKind, Card, MediaKind};
+
+/// An item rated by [`Card::completeness`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompletenessItem {
+    /// A name, with a full name or components.
+    Name,
+    /// At least one email address.
+    Email,
+    /// At least one phone.
+    Phone,
+    /// At least one address.
+    Address,
+    /// A media of the `photo` kind.
+    Photo,
+    /// An organization or a title.
+    Organization,
+    /// An anniversary of the `birth` kind.
+    Birthday,
+}
+
+impl CompletenessItem {
+    /// All the items, in the order of the fields of [`CompletenessWeights`].
+    pub const ALL: [CompletenessItem; 7] = [
+        CompletenessItem::Name,
+        CompletenessItem::Email,
+        CompletenessItem::Phone,
+        CompletenessItem::Address,
+        CompletenessItem::Photo,
+        CompletenessItem::Organization,
+        CompletenessItem::Birthday,
+    ];
+
+    /// Check if the item is present in a Card
+    fn is_in(self, card: &Card) -> bool {
+        match self {
+            CompletenessItem::Name => card.name.as_ref().is_some_and(|name| {
+                name.full.as_deref().is_some_and(|full| !full.is_empty())
+                    || name
+                        .components
+                        .as_ref()
+                        .is_some_and(|components| !components.is_empty())
+            }),
+            CompletenessItem::Email => card.emails.as_ref().is_some_and(|map| !map.is_empty()),
+            CompletenessItem::Phone => card.phones.as_ref().is_some_and(|map| !map.is_empty()),
+            CompletenessItem::Address => card.addresses.as_ref().is_some_and(|map| !map.is_empty()),
+            CompletenessItem::Photo => card
+                .media
+                .iter()
+                .flatten()
+                .any(|(_, media)| media.kind == MediaKind::Photo),
+            CompletenessItem::Organization => {
+                card.organizations
+                    .as_ref()
+                    .is_some_and(|map| !map.is_empty())
+                    || card.titles.as_ref().is_some_and(|map| !map.is_empty())
+            }
+            CompletenessItem::Birthday => card
+                .anniversaries
+                .iter()
+                .flatten()
+                .any(|(_, anniversary)| anniversary.kind == AnniversaryKind::Birth),
+        }
+    }
+}
+
+impl fmt::Display for CompletenessItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CompletenessItem::Name => "name",
+            CompletenessItem::Email => "email",
+            CompletenessItem::Phone => "phone",
+            CompletenessItem::Address => "address",
+            CompletenessItem::Photo => "photo",
+            CompletenessItem::Organization => "organization",
+            CompletenessItem::Birthday => "birthday",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The weights of the items of [`Card::completeness`], the negative weights counting as 0.0.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CompletenessWeights {
+    /// The weight of [`CompletenessItem::Name`].
+    pub name: f32,
+    /// The weight of [`CompletenessItem::Email`].
+    pub email: f32,
+    /// The weight of [`CompletenessItem::Phone`].
+    pub phone: f32,
+    /// The weight of [`CompletenessItem::Address`].
+    pub address: f32,
+    /// The weight of [`CompletenessItem::Photo`].
+    pub photo: f32,
+    /// The weight of [`CompletenessItem::Organization`].
+    pub organization: f32,
+    /// The weight of [`CompletenessItem::Birthday`].
+    pub birthday: f32,
+}
+
+impl Default for CompletenessWeights {
+    fn default() -> Self {
+        Self {
+            name: 3.0,
+            email: 2.0,
+            phone: 2.0,
+            address: 1.0,
+            photo: 1.0,
+            organization: 1.0,
+            birthday: 0.5,
+        }
+    }
+}
+
+impl CompletenessWeights {
+    /// Get the weight of an item, 0.0 for a negative weight.
+    pub fn weight(&self, item: CompletenessItem) -> f32 {
+        let weight = match item {
+            CompletenessItem::Name => self.name,
+            CompletenessItem::Email => self.email,
+            CompletenessItem::Phone => self.phone,
+            CompletenessItem::Address => self.address,
+            CompletenessItem::Photo => self.photo,
+            CompletenessItem::Organization => self.organization,
+            CompletenessItem::Birthday => self.birthday,
+        };
+        weight.max(0.0)
+    }
+}
+
+/// The completeness of a Card, see [`Card::completeness`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Completeness {
+    /// The score, between 0.0 and 1.0.
+    pub score: f32,
+    /// The missing items with a weight, the highest weight first.
+    pub missing: Vec<CompletenessItem>,
+}
+
+impl Card {
+    /// Get the completeness score of the Card, between 0.0 and 1.0, see [`Card::completeness`].
+    pub fn completeness_score(&self, weights: &CompletenessWeights) -> f32 {
+        self.completeness(weights).score
+    }
+
+    /// Get the completeness of the Card: its score and the missing items, the highest weight first.
+    ///
+    /// The score of weights summing to 0.0 is 1.0, nothing being expected.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, CompletenessItem, CompletenessWeights, Name};
+    ///
+    /// let mut card = Card::new_with_latest_version("1234");
+    /// let mut name = Name::default();
+    /// name.full = Some("Jane Doe".to_string());
+    /// card.name = Some(name);
+    /// let completeness = card.completeness(&CompletenessWeights::default());
+    /// assert_eq!(completeness.score, 3.0 / 10.5);
+    /// assert_eq!(completeness.missing[0], CompletenessItem::Email);
+    /// ```
+    pub fn completeness(&self, weights: &CompletenessWeights) -> Completeness {
+        let mut total = 0.0;
+        let mut present = 0.0;
+        let mut missing = Vec::new();
+        for item in CompletenessItem::ALL {
+            let weight = weights.weight(item);
+            if weight == 0.0 {
+                continue;
+            }
+            total += weight;
+            if item.is_in(self) {
+                present += weight;
+            } else {
+                missing.push(item);
+            }
+        }
+        // stable, the items of the same weight stay in the order of ALL
+        missing.sort_by(|a, b| weights.weight(*b).total_cmp(&weights.weight(*a)));
+        let score = if total == 0.0 { 1.0 } else { present / total };
+        Completeness { score, missing }
+    }
+}
@@ -0,0 +1,405 @@
+//! Mutations of the components of a name or an address keeping the localizations of the Card valid.
+//!
+//! The localizations patch the components by index (e.g. `name/components/0/phonetic`), so inserting or
+//! removing a component shifts the components the patches refer to.
+//! The guards of [`Card::name_mut_guarded`] and [`Card::address_mut_guarded`] re-index the patches,
+//! or reject the mutation, according to a [`ReindexPolicy`].
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{Address, AddressComponent, Card, Name, NameComponent, PathSegment, RawValues};
+
+/// What to do with the localization patches of the components shifted by a mutation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReindexPolicy {
+    /// Re-index the patches, the patches of a removed component are removed with it.
+    #[default]
+    Reindex,
+    /// Reject the mutation if a patch refers to a shifted or removed component.
+    Reject,
+}
+
+/// A mutation of the components conflicting with the localizations of the Card.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalizationConflict {
+    /// The languages of the conflicting localizations, sorted.
+    pub languages: Vec<String>,
+    /// The paths of the conflicting patches, sorted.
+    pub paths: Vec<String>,
+}
+
+impl fmt::Display for LocalizationConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The localizations {} patch the components: {}",
+            self.languages.join(", "),
+            self.paths.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for LocalizationConflict {}
+
+/// A mutation of a list of components
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Change {
+    /// A component inserted at an index
+    Insert(usize),
+    /// The component at an index removed
+    Remove(usize),
+}
+
+/// The effect of a mutation on a patch
+enum Reindexed {
+    /// The patch refers to a component before the mutated one, or to another property
+    Unchanged,
+    /// The patch refers to a shifted component, at its new path
+    Moved(String),
+    /// The patch refers to the removed component
+    Removed,
+    /// The patch replaces the whole list of components or one of its parents, it cannot be re-indexed
+    Conflict,
+}
+
+impl Change {
+    /// Get the effect of the mutation on the patch of a path, the components being at `prefix`
+    fn reindex(self, prefix: &str, path: &str) -> Reindexed {
+        if path == prefix || prefix.starts_with(&format!("{}/", path)) {
+            return Reindexed::Conflict;
+        }
+        let Some(rest) = path
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_prefix('/'))
+        else {
+            return Reindexed::Unchanged;
+        };
+        let (index, tail) = rest
+            .split_once('/')
+            .map_or((rest, None), |(index, tail)| (index, Some(tail)));
+        let Ok(index) = index.parse::<usize>() else {
+            return Reindexed::Unchanged;
+        };
+        let moved = match self {
+            Change::Insert(at) if index >= at => index + 1,
+            Change::Remove(at) if index == at => return Reindexed::Removed,
+            Change::Remove(at) if index > at => index - 1,
+            _ => return Reindexed::Unchanged,
+        };
+        Reindexed::Moved(match tail {
+            Some(tail) => format!("{}/{}/{}", prefix, moved, tail),
+            None => format!("{}/{}", prefix, moved),
+        })
+    }
+}
+
+/// The localizations of a Card
+type Localizations = HashMap<String, RawValues>;
+
+/// Re-index the patches of the components at `prefix`, nothing is changed if there is a conflict.
+pub(crate) fn reindex(
+    localizations: &mut Option<Localizations>,
+    prefix: &str,
+    change: Change,
+    policy: ReindexPolicy,
+) -> Result<(), LocalizationConflict> {
+    let Some(localizations) = localizations else {
+        return Ok(());
+    };
+    let mut languages = Vec::new();
+    let mut paths = Vec::new();
+    for (language, patches) in localizations.iter() {
+        let mut conflicting = false;
+        for path in patches.keys() {
+            let reindexed = change.reindex(prefix, path);
+            let conflict = match reindexed {
+                Reindexed::Unchanged => false,
+                Reindexed::Conflict => true,
+                Reindexed::Moved(_) | Reindexed::Removed => policy == ReindexPolicy::Reject,
+            };
+            if conflict {
+                conflicting = true;
+                paths.push(path.clone());
+            }
+        }
+        if conflicting {
+            languages.push(language.clone());
+        }
+    }
+    if !languages.is_empty() {
+        languages.sort();
+        paths.sort();
+        return Err(LocalizationConflict { languages, paths });
+    }
+    for patches in localizations.values_mut() {
+        *patches = std::mem::take(patches)
+            .into_iter()
+            .filter_map(|(path, value)| match change.reindex(prefix, &path) {
+                Reindexed::Moved(moved) => Some((moved, value)),
+                Reindexed::Removed => None,
+                Reindexed::Unchanged | Reindexed::Conflict => Some((path, value)),
+            })
+            .collect();
+    }
+    Ok(())
+}
+
+/// Insert a component in an optional list, creating the list if needed
+fn insert<T>(components: &mut Option<Vec<T>>, idx: usize, component: T) {
+    components
+        .get_or_insert_with(Vec::new)
+        .insert(idx, component);
+}
+
+/// Remove a component of an optional list, `None` if the index is out of bounds
+fn remove<T>(components: &mut Option<Vec<T>>, idx: usize) -> Option<T> {
+    let components = components.as_mut()?;
+    (idx < components.len()).then(|| components.remove(idx))
+}
+
+/// Get the number of components of an optional list
+fn len<T>(components: &Option<Vec<T>>) -> usize {
+    components.as_ref().map_or(0, Vec::len)
+}
+
+impl Name {
+    /// Insert a component at an index, shifting the following components.
+    ///
+    /// Use [`Card::name_mut_guarded`] to keep the localizations of the Card valid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the number of components.
+    pub fn insert_component(&mut self, idx: usize, component: NameComponent) {
+        insert(&mut self.components, idx, component);
+    }
+
+    /// Remove the component at an index, `None` if there is no such component.
+    ///
+    /// Use [`Card::name_mut_guarded`] to keep the localizations of the Card valid.
+    pub fn remove_component(&mut self, idx: usize) -> Option<NameComponent> {
+        remove(&mut self.components, idx)
+    }
+}
+
+impl Address {
+    /// Insert a component at an index, shifting the following components.
+    ///
+    /// Use [`Card::address_mut_guarded`] to keep the localizations of the Card valid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the number of components.
+    pub fn insert_component(&mut self, idx: usize, component: AddressComponent) {
+        insert(&mut self.components, idx, component);
+    }
+
+    /// Remove the component at an index, `None` if there is no such component.
+    ///
+    /// Use [`Card::address_mut_guarded`] to keep the localizations of the Card valid.
+    pub fn remove_component(&mut self, idx: usize) -> Option<AddressComponent> {
+        remove(&mut self.components, idx)
+    }
+}
+
+/// Mutations of the components of the name of a Card, see [`Card::name_mut_guarded`].
+pub struct GuardedName<'a> {
+    /// The Card of the name
+    card: &'a mut Card,
+    /// What to do with the shifted patches
+    policy: ReindexPolicy,
+}
+
+impl GuardedName<'_> {
+    /// Insert a component at an index, creating the name if needed, and re-index the localizations.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error listing the conflicting localizations, the name being unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the number of components.
+    pub fn insert_component(
+        &mut self,
+        idx: usize,
+        component: NameComponent,
+    ) -> Result<(), LocalizationConflict> {
+        let components = self.card.name.as_ref().map(|name| &name.components);
+        assert!(
+            idx <= components.map_or(0, len),
+            "insertion index {} is out of bounds",
+            idx
+        );
+        let change = Change::Insert(idx);
+        reindex(
+            &mut self.card.localizations,
+            "name/components",
+            change,
+            self.policy,
+        )?;
+        self.card
+            .name
+            .get_or_insert_with(Name::default)
+            .insert_component(idx, component);
+        self.card.touch();
+        Ok(())
+    }
+
+    /// Remove the component at an index and re-index the localizations, `None` if there is no such component.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error listing the conflicting localizations, the name being unchanged.
+    pub fn remove_component(
+        &mut self,
+        idx: usize,
+    ) -> Result<Option<NameComponent>, LocalizationConflict> {
+        let Some(name) = &self.card.name else {
+            return Ok(None);
+        };
+        if idx >= len(&name.components) {
+            return Ok(None);
+        }
+        let change = Change::Remove(idx);
+        reindex(
+            &mut self.card.localizations,
+            "name/components",
+            change,
+            self.policy,
+        )?;
+        let component = self
+            .card
+            .name
+            .as_mut()
+            .and_then(|name| name.remove_component(idx));
+        self.card.touch();
+        Ok(component)
+    }
+}
+
+/// Mutations of the components of an address of a Card, see [`Card::address_mut_guarded`].
+pub struct GuardedAddress<'a> {
+    /// The Card of the address
+    card: &'a mut Card,
+    /// The key of the address
+    key: String,
+    /// What to do with the shifted patches
+    policy: ReindexPolicy,
+}
+
+impl GuardedAddress<'_> {
+    /// Get the guarded address
+    fn address(&mut self) -> Option<&mut Address> {
+        self.card.addresses.as_mut()?.get_mut(self.key.as_str())
+    }
+
+    /// Get the path of the components of the address
+    fn prefix(&self) -> String {
+        format!(
+            "addresses/{}/components",
+            PathSegment::Key(self.key.clone())
+        )
+    }
+
+    /// Insert a component at an index and re-index the localizations.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error listing the conflicting localizations, the address being unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the number of components.
+    pub fn insert_component(
+        &mut self,
+        idx: usize,
+        component: AddressComponent,
+    ) -> Result<(), LocalizationConflict> {
+        let count = self.address().map_or(0, |address| len(&address.components));
+        assert!(idx <= count, "insertion index {} is out of bounds", idx);
+        let prefix = self.prefix();
+        reindex(
+            &mut self.card.localizations,
+            &prefix,
+            Change::Insert(idx),
+            self.policy,
+        )?;
+        if let Some(address) = self.address() {
+            address.insert_component(idx, component);
+        }
+        self.card.touch();
+        Ok(())
+    }
+
+    /// Remove the component at an index and re-index the localizations, `None` if there is no such component.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error listing the conflicting localizations, the address being unchanged.
+    pub fn remove_component(
+        &mut self,
+        idx: usize,
+    ) -> Result<Option<AddressComponent>, LocalizationConflict> {
+        let count = self.address().map_or(0, |address| len(&address.components));
+        if idx >= count {
+            return Ok(None);
+        }
+        let prefix = self.prefix();
+        reindex(
+            &mut self.card.localizations,
+            &prefix,
+            Change::Remove(idx),
+            self.policy,
+        )?;
+        let component = self
+            .address()
+            .and_then(|address| address.remove_component(idx));
+        self.card.touch();
+        Ok(component)
+    }
+}
+
+impl Card {
+    /// Get a guard to insert or remove the components of the name, keeping the localization patches
+    /// of the components (e.g. `name/components/0/phonetic`) on the same components.
+    ///
+    /// A patch replacing the whole list of components, or the whole name, is always a conflict.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, NameComponent, NameComponentKind, ReindexPolicy};
+    /// use serde_json::json;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut card = Card::new_with_latest_version("my:uri");
+    /// card.name_mut_guarded(ReindexPolicy::Reindex)
+    ///     .insert_component(0, NameComponent::new(NameComponentKind::Surname, "孫"))
+    ///     .unwrap();
+    /// card.add_localization("yue", HashMap::from([("name/components/0/phonetic".to_string(), json!("syun1"))]));
+    ///
+    /// let given = NameComponent::new(NameComponentKind::Given, "中山");
+    /// card.name_mut_guarded(ReindexPolicy::Reindex).insert_component(0, given).unwrap();
+    /// let patches = &card.get_raw_localizations().unwrap()["yue"];
+    /// assert_eq!(patches["name/components/1/phonetic"], "syun1");
+    /// ```
+    pub fn name_mut_guarded(&mut self, policy: ReindexPolicy) -> GuardedName<'_> {
+        GuardedName { card: self, policy }
+    }
+
+    /// Get a guard to insert or remove the components of an address, `None` if there is no address with this key.
+    ///
+    /// See [`Card::name_mut_guarded`].
+    pub fn address_mut_guarded(
+        &mut self,
+        key: &str,
+        policy: ReindexPolicy,
+    ) -> Option<GuardedAddress<'_>> {
+        self.addresses.as_ref()?.get(key)?;
+        Some(GuardedAddress {
+            card: self,
+            key: key.to_string(),
+            policy,
+        })
+    }
+}
@@ -0,0 +1,205 @@
+//! One list of the ways to reach the contact of a [`Card`]: its phones, emails, online services and
+//! scheduling addresses, e.g. for the details screen of a contact app.
+//!
+//! The methods are ordered by:
+//! 1. context: the methods without context first, then `private`, `work` and the other contexts by name
+//! 2. `pref`: the lowest first, a method without `pref` coming last
+//! 3. kind: phone, email, online service, then scheduling address (see [`ContactMethodKind`])
+//! 4. key of the entry in its map
+//!
+//! A method is listed once per context in which it is set, so a phone for both `private` and `work`
+//! appears in both groups of [`Card::contact_methods_by_context`].
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{Card, Context, EmailAddress, OnlineService, Phone, SchedulingAddress, SmallString};
+
+/// The kind of a [`ContactMethod`], in the order of the methods with the same context and `pref`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ContactMethodKind {
+    /// An entry of `phones`.
+    Phone,
+    /// An entry of `emails`.
+    Email,
+    /// An entry of `onlineServices`.
+    OnlineService,
+    /// An entry of `schedulingAddresses`.
+    SchedulingAddress,
+}
+
+/// The fields shared by all the kinds of [`ContactMethod`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContactMethodInfo<'a> {
+    /// The key of the entry in its map (e.g. `tel0`).
+    pub key: &'a str,
+    /// The label of the entry.
+    pub label: Option<&'a str>,
+    /// The value to display: the phone number, the email address, the uri (or else the user) of the
+    /// online service, or the uri of the scheduling address.
+    pub value: &'a str,
+    /// The context of the method, `None` for an entry without context.
+    pub context: Option<Context>,
+    /// The preference of the entry.
+    pub pref: Option<u64>,
+    /// The kind of the entry.
+    pub kind: ContactMethodKind,
+}
+
+/// A way to reach the contact, borrowing the entry of the Card, see [`Card::contact_methods`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContactMethod<'a> {
+    /// A phone.
+    Phone(&'a Phone, ContactMethodInfo<'a>),
+    /// An email address.
+    Email(&'a EmailAddress, ContactMethodInfo<'a>),
+    /// An online service.
+    OnlineService(&'a OnlineService, ContactMethodInfo<'a>),
+    /// A scheduling address.
+    SchedulingAddress(&'a SchedulingAddress, ContactMethodInfo<'a>),
+}
+
+impl<'a> ContactMethod<'a> {
+    /// Get the fields shared by all the kinds of methods.
+    pub fn info(&self) -> &ContactMethodInfo<'a> {
+        match self {
+            ContactMethod::Phone(_, info)
+            | ContactMethod::Email(_, info)
+            | ContactMethod::OnlineService(_, info)
+            | ContactMethod::SchedulingAddress(_, info) => info,
+        }
+    }
+
+    /// The sort key of the method, see the module documentation
+    fn sort_key(&self) -> (&Option<Context>, u64, ContactMethodKind, &str) {
+        let info = self.info();
+        (
+            &info.context,
+            info.pref.unwrap_or(u64::MAX),
+            info.kind,
+            info.key,
+        )
+    }
+}
+
+/// Get the contexts of an entry, `None` alone for an entry without context
+fn contexts(contexts: &Option<HashMap<Context, bool>>) -> Vec<Option<Context>> {
+    let contexts: Vec<Option<Context>> = contexts
+        .iter()
+        .flatten()
+        .filter(|(_, set)| **set)
+        .map(|(context, _)| Some(context.clone()))
+        .collect();
+    if contexts.is_empty() {
+        vec![None]
+    } else {
+        contexts
+    }
+}
+
+/// Add the methods of the entries of a map, one per context of each entry
+fn push_methods<'a, T>(
+    methods: &mut Vec<ContactMethod<'a>>,
+    entries: &'a Option<HashMap<SmallString, T>>,
+    entry_contexts: impl Fn(&'a T) -> &'a Option<HashMap<Context, bool>>,
+    info: impl Fn(&'a str, &'a T, Option<Context>) -> ContactMethod<'a>,
+) {
+    for (key, entry) in entries.iter().flatten() {
+        for context in contexts(entry_contexts(entry)) {
+            methods.push(info(key.as_str(), entry, context));
+        }
+    }
+}
+
+impl Card {
+    /// Get the phones, emails, online services and scheduling addresses in one list,
+    /// ordered by context, `pref`, kind and key (see the [module documentation](crate::contact_method)).
+    ///
+    /// The methods without value to display are left out, e.g. an online service without uri nor user.
+    pub fn contact_methods(&self) -> Vec<ContactMethod<'_>> {
+        let mut methods = Vec::new();
+        push_methods(
+            &mut methods,
+            &self.phones,
+            |phone| &phone.contexts,
+            |key, phone, context| {
+                let info = ContactMethodInfo {
+                    key,
+                    label: phone.label.as_deref(),
+                    value: &phone.number,
+                    context,
+                    pref: phone.pref.map(u64::from),
+                    kind: ContactMethodKind::Phone,
+                };
+                ContactMethod::Phone(phone, info)
+            },
+        );
+        push_methods(
+            &mut methods,
+            &self.emails,
+            |email| &email.contexts,
+            |key, email, context| {
+                let info = ContactMethodInfo {
+                    key,
+                    label: email.label.as_deref(),
+                    value: &email.address,
+                    context,
+                    pref: email.pref.map(u64::from),
+                    kind: ContactMethodKind::Email,
+                };
+                ContactMethod::Email(email, info)
+            },
+        );
+        push_methods(
+            &mut methods,
+            &self.online_services,
+            |service| &service.contexts,
+            |key, service, context| {
+                let info = ContactMethodInfo {
+                    key,
+                    label: service.label.as_deref(),
+                    value: service
+                        .uri
+                        .as_deref()
+                        .or(service.user.as_deref())
+                        .unwrap_or_default(),
+                    context,
+                    pref: service.pref.map(u64::from),
+                    kind: ContactMethodKind::OnlineService,
+                };
+                ContactMethod::OnlineService(service, info)
+            },
+        );
+        push_methods(
+            &mut methods,
+            &self.scheduling_addresses,
+            |address| &address.contexts,
+            |key, address, context| {
+                let info = ContactMethodInfo {
+                    key,
+                    label: address.label.as_deref(),
+                    value: &address.uri,
+                    context,
+                    pref: address.pref,
+                    kind: ContactMethodKind::SchedulingAddress,
+                };
+                ContactMethod::SchedulingAddress(address, info)
+            },
+        );
+        methods.retain(|method| !method.info().value.is_empty());
+        methods.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+        methods
+    }
+
+    /// Get the methods of [`Card::contact_methods`] grouped by context, in the same order,
+    /// the methods without context being under `None`.
+    pub fn contact_methods_by_context(&self) -> BTreeMap<Option<Context>, Vec<ContactMethod<'_>>> {
+        let mut groups: BTreeMap<Option<Context>, Vec<ContactMethod<'_>>> = BTreeMap::new();
+        for method in self.contact_methods() {
+            groups
+                .entry(method.info().context.clone())
+                .or_default()
+                .push(method);
+        }
+        groups
+    }
+}
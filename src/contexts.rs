@@ -0,0 +1,114 @@
+//! Shorthands for the `contexts` maps of the entries of a [`crate::Card`], which are nearly always set to a
+//! single context (e.g. `{ "work": true }`).
+//!
+//! A context only counts when its value is `true`, as for the other maps of RFC 9553 whose values are `true`.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{
+    Address, AddressContext, Calendar, Context, CryptoKey, Directory, EmailAddress, LanguagePref,
+    Link, Media, Nickname, OnlineService, Organization, Phone, Pronouns, Resource,
+    SchedulingAddress,
+};
+
+impl Context {
+    /// Create a `contexts` map with a single context.
+    ///
+    /// ```rust
+    /// use jscontact::Context;
+    /// use std::collections::HashMap;
+    ///
+    /// assert_eq!(Context::single(Context::Work), HashMap::from([(Context::Work, true)]));
+    /// ```
+    pub fn single(context: Context) -> HashMap<Context, bool> {
+        HashMap::from([(context, true)])
+    }
+}
+
+/// An entry with a `contexts` property.
+pub trait HasContexts {
+    /// The type of the contexts, [`Context`] except for [`Address`].
+    type Context: Eq + Hash + Clone;
+
+    /// Get the `contexts` of the entry.
+    fn context_map(&self) -> &Option<HashMap<Self::Context, bool>>;
+
+    /// Get the `contexts` of the entry, mutably.
+    fn context_map_mut(&mut self) -> &mut Option<HashMap<Self::Context, bool>>;
+
+    /// Set the entry to a single context, removing the others.
+    ///
+    /// ```rust
+    /// use jscontact::{Context, HasContexts, Phone};
+    ///
+    /// let mut phone = Phone::new("+1-555-555-5555");
+    /// phone.set_context(Context::Private);
+    /// phone.set_context(Context::Work);
+    /// assert_eq!(phone.contexts, Some(Context::single(Context::Work)));
+    /// ```
+    fn set_context(&mut self, context: Self::Context) {
+        *self.context_map_mut() = Some(HashMap::from([(context, true)]));
+    }
+
+    /// Get the context of the entry when it has exactly one context set to `true`.
+    ///
+    /// Returns `None` for an entry without context or with several contexts.
+    fn only_context(&self) -> Option<Self::Context> {
+        let mut contexts = self
+            .context_map()
+            .iter()
+            .flatten()
+            .filter(|(_, set)| **set)
+            .map(|(context, _)| context);
+        match (contexts.next(), contexts.next()) {
+            (Some(context), None) => Some(context.clone()),
+            _ => None,
+        }
+    }
+
+    /// Check if a context of the entry is set to `true`.
+    fn has_context(&self, context: &Self::Context) -> bool {
+        self.context_map()
+            .as_ref()
+            .is_some_and(|contexts| contexts.get(context) == Some(&true))
+    }
+}
+
+/// Implement [`HasContexts`] for entries with a `contexts` field
+macro_rules! has_contexts {
+    ($context:ty => $($entry:ty),+ $(,)?) => {
+        $(
+            impl HasContexts for $entry {
+                type Context = $context;
+
+                fn context_map(&self) -> &Option<HashMap<$context, bool>> {
+                    &self.contexts
+                }
+
+                fn context_map_mut(&mut self) -> &mut Option<HashMap<$context, bool>> {
+                    &mut self.contexts
+                }
+            }
+        )+
+    };
+}
+
+has_contexts!(Context =>
+    Calendar,
+    SchedulingAddress,
+    CryptoKey,
+    Directory,
+    Media,
+    Link,
+    Nickname,
+    Organization,
+    Pronouns,
+    EmailAddress,
+    OnlineService,
+    Phone,
+    LanguagePref,
+    Resource,
+);
+
+has_contexts!(AddressContext => Address);
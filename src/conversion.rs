@@ -0,0 +1,69 @@
+//! Report of the information lost when converting a [`crate::Card`] to or from another format.
+//!
+//! The same report is returned by all the conversions (jCard, vCard, MeCard, ...), so the losses are
+//! shown to the users the same way whatever the format.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A property that could not be represented in the target format.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LossRecord {
+    /// The path of the dropped property, relative to the source object (e.g. `anniversaries/k9`).
+    pub path: String,
+    /// Why the property was dropped.
+    pub reason: String,
+    /// The dropped value, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dropped_value: Option<Value>,
+}
+
+/// The conversion report collects everything that was dropped or approximated during a conversion.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct ConversionReport {
+    /// The properties that could not be represented.
+    pub lossy: Vec<LossRecord>,
+    /// The properties that were converted with an approximation.
+    pub warnings: Vec<String>,
+}
+
+impl ConversionReport {
+    /// Creates an empty ConversionReport.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a dropped property.
+    pub fn add_loss(&mut self, path: &str, reason: &str) {
+        self.lossy.push(LossRecord {
+            path: path.to_string(),
+            reason: reason.to_string(),
+            dropped_value: None,
+        });
+    }
+
+    /// Records a dropped property with its value.
+    pub fn add_loss_with_value(&mut self, path: &str, reason: &str, value: Value) {
+        self.lossy.push(LossRecord {
+            path: path.to_string(),
+            reason: reason.to_string(),
+            dropped_value: Some(value),
+        });
+    }
+
+    /// Records an approximated property.
+    pub fn add_warning(&mut self, warning: &str) {
+        self.warnings.push(warning.to_string());
+    }
+
+    /// Appends the losses and the warnings of another report, e.g. of a step of a conversion.
+    pub fn merge(&mut self, other: ConversionReport) {
+        self.lossy.extend(other.lossy);
+        self.warnings.extend(other.warnings);
+    }
+
+    /// Returns true if nothing was dropped during the conversion.
+    pub fn is_lossless(&self) -> bool {
+        self.lossy.is_empty()
+    }
+}
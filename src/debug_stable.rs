@@ -0,0 +1,130 @@
+//! A `Debug` formatting of a [`Card`] that does not depend on the order of its maps, for snapshot tests.
+//!
+//! The derived `Debug` of the Card prints the entries of its `HashMap`s in an order that changes between
+//! runs. [`Card::debug_stable`] prints the properties of the registry of [`crate::path`] with their
+//! JSON names, then the localizations and the unknown properties, the entries of all the maps being
+//! sorted by key. The absent properties are left out.
+
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::depth::{check_depth, DEFAULT_MAX_DEPTH};
+use crate::path::{property_to_value, PROPERTIES};
+use crate::{Card, RawValues};
+
+/// Format a JSON value with the members of its objects sorted by key
+struct Sorted<'a>(&'a Value);
+
+impl fmt::Debug for Sorted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Value::Null => f.write_str("null"),
+            Value::Bool(value) => fmt::Debug::fmt(value, f),
+            Value::Number(value) => fmt::Display::fmt(value, f),
+            Value::String(value) => fmt::Debug::fmt(value, f),
+            Value::Array(values) => f.debug_list().entries(values.iter().map(Sorted)).finish(),
+            Value::Object(members) => {
+                let mut members: Vec<(&String, &Value)> = members.iter().collect();
+                members.sort_by_key(|(key, _)| *key);
+                f.debug_map()
+                    .entries(members.into_iter().map(|(key, value)| (key, Sorted(value))))
+                    .finish()
+            }
+        }
+    }
+}
+
+/// Format a raw value of the Card sorted, or its error if it is nested deeper than [`DEFAULT_MAX_DEPTH`]
+struct SortedRaw<'a>(&'a Value);
+
+impl fmt::Debug for SortedRaw<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match check_depth(self.0, "", DEFAULT_MAX_DEPTH) {
+            Ok(()) => fmt::Debug::fmt(&Sorted(self.0), f),
+            Err(error) => write!(f, "<{}>", error),
+        }
+    }
+}
+
+/// Format a map of raw values sorted by key
+struct SortedMap<'a>(&'a RawValues);
+
+impl fmt::Debug for SortedMap<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut entries: Vec<(&String, &Value)> = self.0.iter().collect();
+        entries.sort_by_key(|(key, _)| *key);
+        f.debug_map()
+            .entries(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, SortedRaw(value))),
+            )
+            .finish()
+    }
+}
+
+/// A Card formatted by `Debug` in a stable order, see [`Card::debug_stable`].
+pub struct StableCard<'a>(&'a Card);
+
+impl fmt::Debug for StableCard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let card = self.0;
+        let mut debug = f.debug_struct("Card");
+        for property in PROPERTIES {
+            match property_to_value(card, property) {
+                Ok(Value::Null) => {}
+                Ok(value) => {
+                    debug.field(property, &Sorted(&value));
+                }
+                Err(error) => {
+                    debug.field(property, &error);
+                }
+            }
+        }
+        if let Some(localizations) = card.get_raw_localizations() {
+            let mut languages: Vec<(&String, &RawValues)> = localizations.iter().collect();
+            languages.sort_by_key(|(language, _)| *language);
+            let languages: Vec<(&String, SortedMap<'_>)> = languages
+                .into_iter()
+                .map(|(language, patches)| (language, SortedMap(patches)))
+                .collect();
+            debug.field("localizations", &DebugMap(&languages));
+        }
+        if !card.extensions.is_empty() {
+            debug.field("extensions", &SortedMap(&card.extensions));
+        }
+        debug.finish()
+    }
+}
+
+/// Format sorted entries as a map
+struct DebugMap<'a, K, V>(&'a [(K, V)]);
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for DebugMap<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(key, value)| (key, value)))
+            .finish()
+    }
+}
+
+impl Card {
+    /// Get the Card formatted by `Debug` with the entries of its maps sorted by key, e.g. for
+    /// `insta::assert_debug_snapshot!`, see the [module documentation](crate::debug_stable).
+    ///
+    /// ```rust
+    /// use jscontact::Card;
+    ///
+    /// let mut card = Card::new_with_latest_version("1234");
+    /// card.add_keyword("b");
+    /// card.add_keyword("a");
+    /// assert_eq!(
+    ///     format!("{:?}", card.debug_stable()),
+    ///     r#"Card { version: "1.0", uid: "1234", keywords: {"a": true, "b": true} }"#
+    /// );
+    /// ```
+    pub fn debug_stable(&self) -> StableCard<'_> {
+        StableCard(self)
+    }
+}
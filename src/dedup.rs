@@ -0,0 +1,493 @@
+//! Detection and merge of the duplicate Cards of a list, e.g. Cards imported from several sources.
+//!
+//! Two Cards are duplicates if they share a uid, a normalized email address, a normalized phone number,
+//! or if their names are similar enough. The duplicates are grouped transitively,
+//! and each group records the keys that matched for audit.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::depth::clone_iteratively;
+use crate::provenance::{record_merged, PROVENANCE_PROPERTY};
+use crate::{Card, PathSegment, PlaceRef, SmallString};
+
+/// The options of [`find_duplicates`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DedupOptions {
+    /// Group the Cards with the same uid.
+    pub match_uid: bool,
+    /// Group the Cards with the same email address, compared case-insensitively.
+    pub match_email: bool,
+    /// Group the Cards with the same phone number, compared by their digits.
+    pub match_phone: bool,
+    /// Group the Cards whose names have a similarity of at least this value, between 0 and 1.
+    ///
+    /// The similarity is the ratio of the words shared by the two names (Jaccard index), `None` to disable.
+    pub name_threshold: Option<f64>,
+}
+
+impl Default for DedupOptions {
+    fn default() -> Self {
+        Self {
+            match_uid: true,
+            match_email: true,
+            match_phone: true,
+            name_threshold: Some(0.8),
+        }
+    }
+}
+
+/// A key shared by two duplicate Cards.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchKey {
+    /// The same uid.
+    Uid(String),
+    /// The same normalized email address.
+    Email(String),
+    /// The same normalized phone number.
+    Phone(String),
+    /// Similar names, with their similarity.
+    Name(f64),
+}
+
+/// A match between two Cards of a [`DuplicateGroup`], the indexes are the positions in the list of Cards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateMatch {
+    /// The index of the first Card.
+    pub first: usize,
+    /// The index of the second Card.
+    pub second: usize,
+    /// The key shared by the two Cards.
+    pub key: MatchKey,
+}
+
+/// A group of duplicate Cards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateGroup {
+    /// The positions of the Cards in the list given to [`find_duplicates`], sorted.
+    pub indexes: Vec<usize>,
+    /// The Cards of the group, in the order of the indexes.
+    pub cards: Vec<Card>,
+    /// The matches that grouped the Cards.
+    pub matches: Vec<DuplicateMatch>,
+}
+
+/// The strategy of [`merge_group`] when the Cards of a group have different values for a property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// The values of the first Card of the group are kept.
+    #[default]
+    PreferFirst,
+    /// The values of the most recently updated Card are kept, see [`Card::updated`].
+    ///
+    /// The Cards without `updated` come last, the first Card wins a tie.
+    PreferLatest,
+}
+
+/// Normalize an email address for comparison
+fn normalize_email(address: &str) -> String {
+    address.trim().to_lowercase()
+}
+
+/// Normalize a phone number for comparison, keeping the digits and a leading `+`
+fn normalize_phone(number: &str) -> String {
+    let number = number.trim();
+    let number = number.strip_prefix("tel:").unwrap_or(number);
+    let mut normalized: String = number.chars().filter(char::is_ascii_digit).collect();
+    if number.starts_with('+') {
+        normalized.insert(0, '+');
+    }
+    normalized
+}
+
+/// Get the lowercase words of the name of a Card
+fn name_tokens(card: &Card) -> Vec<String> {
+    let full = card
+        .name
+        .as_ref()
+        .map(|name| name.to_full_string())
+        .unwrap_or_default();
+    let mut tokens: Vec<String> = full
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    tokens.sort();
+    tokens.dedup();
+    tokens
+}
+
+/// Get the similarity of two sets of sorted words, `None` if a set is empty
+fn similarity(a: &[String], b: &[String]) -> Option<f64> {
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    let shared = a
+        .iter()
+        .filter(|token| b.binary_search(token).is_ok())
+        .count();
+    let total = a.len() + b.len() - shared;
+    Some(shared as f64 / total as f64)
+}
+
+/// Find the root of a Card in the union-find of the groups
+fn root(parents: &mut [usize], mut idx: usize) -> usize {
+    while parents[idx] != idx {
+        parents[idx] = parents[parents[idx]];
+        idx = parents[idx];
+    }
+    idx
+}
+
+/// Record the matches of the Cards sharing a key
+fn match_keys<K: Eq + Hash + Clone>(
+    cards: &[Card],
+    keys: impl Fn(&Card) -> Vec<K>,
+    to_match: impl Fn(K) -> MatchKey,
+    matches: &mut Vec<DuplicateMatch>,
+) {
+    let mut first_with_key: HashMap<K, usize> = HashMap::new();
+    for (idx, card) in cards.iter().enumerate() {
+        let mut card_keys = keys(card);
+        card_keys.dedup();
+        for key in card_keys {
+            match first_with_key.get(&key) {
+                Some(first) => matches.push(DuplicateMatch {
+                    first: *first,
+                    second: idx,
+                    key: to_match(key),
+                }),
+                None => {
+                    first_with_key.insert(key, idx);
+                }
+            }
+        }
+    }
+}
+
+/// Find the groups of duplicate Cards of a list, sorted by their first index.
+///
+/// The names are compared pairwise, the other keys are indexed.
+pub fn find_duplicates(cards: &[Card], opts: &DedupOptions) -> Vec<DuplicateGroup> {
+    let mut matches = Vec::new();
+    if opts.match_uid {
+        match_keys(
+            cards,
+            |card| vec![card.uid.clone()],
+            MatchKey::Uid,
+            &mut matches,
+        );
+    }
+    if opts.match_email {
+        let emails = |card: &Card| {
+            let mut emails: Vec<String> = card
+                .emails
+                .iter()
+                .flatten()
+                .map(|(_, email)| normalize_email(&email.address))
+                .filter(|email| !email.is_empty())
+                .collect();
+            emails.sort();
+            emails
+        };
+        match_keys(cards, emails, MatchKey::Email, &mut matches);
+    }
+    if opts.match_phone {
+        let phones = |card: &Card| {
+            let mut phones: Vec<String> = card
+                .phones
+                .iter()
+                .flatten()
+                .map(|(_, phone)| normalize_phone(&phone.number))
+                .filter(|phone| !phone.trim_start_matches('+').is_empty())
+                .collect();
+            phones.sort();
+            phones
+        };
+        match_keys(cards, phones, MatchKey::Phone, &mut matches);
+    }
+    if let Some(threshold) = opts.name_threshold {
+        let tokens: Vec<Vec<String>> = cards.iter().map(name_tokens).collect();
+        for first in 0..cards.len() {
+            for second in first + 1..cards.len() {
+                match similarity(&tokens[first], &tokens[second]) {
+                    Some(score) if score >= threshold => matches.push(DuplicateMatch {
+                        first,
+                        second,
+                        key: MatchKey::Name(score),
+                    }),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let mut parents: Vec<usize> = (0..cards.len()).collect();
+    for duplicate in &matches {
+        let first = root(&mut parents, duplicate.first);
+        let second = root(&mut parents, duplicate.second);
+        parents[first.max(second)] = first.min(second);
+    }
+    let mut groups: Vec<DuplicateGroup> = Vec::new();
+    let mut group_of_root: HashMap<usize, usize> = HashMap::new();
+    for (idx, card) in cards.iter().enumerate() {
+        let group_root = root(&mut parents, idx);
+        let group = *group_of_root.entry(group_root).or_insert_with(|| {
+            groups.push(DuplicateGroup {
+                indexes: Vec::new(),
+                cards: Vec::new(),
+                matches: Vec::new(),
+            });
+            groups.len() - 1
+        });
+        groups[group].indexes.push(idx);
+        groups[group].cards.push(card.clone());
+    }
+    for duplicate in matches {
+        let group = group_of_root[&root(&mut parents, duplicate.first)];
+        groups[group].matches.push(duplicate);
+    }
+    groups.retain(|group| group.indexes.len() > 1);
+    for group in &mut groups {
+        group
+            .matches
+            .sort_by_key(|duplicate| (duplicate.first, duplicate.second));
+    }
+    groups
+}
+
+/// The keys of the entries of a map of another Card in the merged Card, see [`merge_entries`]
+#[derive(Default)]
+struct MergedKeys {
+    /// The keys of the added entries, in the other Card and in the merged Card
+    added: Vec<(SmallString, SmallString)>,
+    /// The keys of the entries of the other Card that have another key in the merged Card, with that key
+    renamed: HashMap<String, String>,
+}
+
+/// Add the entries of a map of another Card that are not in the map of the merged Card,
+/// an entry with the key of a different entry gets a new key.
+fn merge_entries<T: Clone>(
+    merged: &mut Option<HashMap<SmallString, T>>,
+    other: &Option<HashMap<SmallString, T>>,
+    is_same: impl Fn(&T, &T) -> bool,
+) -> MergedKeys {
+    let Some(other) = other else {
+        return MergedKeys::default();
+    };
+    let mut keys: Vec<&SmallString> = other.keys().collect();
+    keys.sort();
+    let merged = merged.get_or_insert_with(HashMap::new);
+    let mut merged_keys = MergedKeys::default();
+    for key in keys {
+        let value = &other[key];
+        // the smallest key for a stable reference when several entries are the same
+        let same = merged
+            .iter()
+            .filter(|(_, existing)| is_same(existing, value))
+            .map(|(existing_key, _)| existing_key)
+            .min();
+        if let Some(existing_key) = same {
+            if existing_key != key {
+                merged_keys
+                    .renamed
+                    .insert(key.to_string(), existing_key.to_string());
+            }
+            continue;
+        }
+        let mut new_key = key.clone();
+        let mut suffix = 2;
+        while merged.contains_key(&new_key) {
+            new_key = SmallString::from(format!("{}-{}", key, suffix).as_str());
+            suffix += 1;
+        }
+        merged.insert(new_key.clone(), value.clone());
+        if new_key != *key {
+            merged_keys
+                .renamed
+                .insert(key.to_string(), new_key.to_string());
+        }
+        merged_keys.added.push((key.clone(), new_key));
+    }
+    merged_keys
+}
+
+/// Get the entries of a map of another Card with their references to the renamed entries of another map
+/// rewritten to the keys in the merged Card, `None` when no entry was renamed
+fn rewrite_references<T: Clone>(
+    entries: &Option<HashMap<SmallString, T>>,
+    renamed: &HashMap<String, String>,
+    reference: impl Fn(&mut T) -> Option<&mut String>,
+) -> Option<Option<HashMap<SmallString, T>>> {
+    if renamed.is_empty() {
+        return None;
+    }
+    let mut entries = entries.clone();
+    for entry in entries.iter_mut().flat_map(|entries| entries.values_mut()) {
+        if let Some(key) = reference(entry) {
+            if let Some(new_key) = renamed.get(key.as_str()) {
+                key.clone_from(new_key);
+            }
+        }
+    }
+    Some(entries)
+}
+
+/// Record the sources of the entries of a map added from another Card, see [`crate::provenance`]
+fn record_entries(
+    merged: &mut Card,
+    other: &Card,
+    property: &str,
+    added: Vec<(SmallString, SmallString)>,
+) {
+    for (key, new_key) in added {
+        let other_path = format!("{}/{}", property, PathSegment::Key(key.to_string()));
+        let merged_path = format!("{}/{}", property, PathSegment::Key(new_key.to_string()));
+        record_merged(merged, other, &other_path, &merged_path);
+    }
+}
+
+/// Add the entries of a set (keywords, members) or of the related Cards that are not in the merged Card,
+/// returning the keys of the added entries
+fn merge_keys<T: Clone>(
+    merged: &mut Option<HashMap<SmallString, T>>,
+    other: &Option<HashMap<SmallString, T>>,
+) -> Vec<(SmallString, SmallString)> {
+    let mut added = Vec::new();
+    if let Some(other) = other {
+        let merged = merged.get_or_insert_with(HashMap::new);
+        for (key, value) in other {
+            if !merged.contains_key(key) {
+                merged.insert(key.clone(), value.clone());
+                added.push((key.clone(), key.clone()));
+            }
+        }
+    }
+    added
+}
+
+/// Merge the Cards of a group into a single Card.
+///
+/// The preferred Card (see [`MergeStrategy`]) is completed with the properties of the other Cards:
+/// the properties it does not have are added, and the entries of its maps (emails, phones, ...)
+/// are completed with the entries of the other Cards that are not already present.
+/// An entry whose key is taken by a different entry gets a new key (e.g. `o1-2`), and the `organizationId`
+/// of the titles and the address keys of the places of the anniversaries of that Card follow it.
+/// The uid and the localizations of the preferred Card are kept, an empty group gives a Card with an empty uid.
+///
+/// The sources of the properties and entries taken from the other Cards are recorded in the merged Card,
+/// see [`Card::provenance_of`].
+pub fn merge_group(group: &DuplicateGroup, strategy: MergeStrategy) -> Card {
+    let mut order: Vec<&Card> = group.cards.iter().collect();
+    if strategy == MergeStrategy::PreferLatest {
+        // stable sort, the first Card wins a tie and `None` is lower than any date
+        order.sort_by(|a, b| b.updated.cmp(&a.updated));
+    }
+    let Some((preferred, others)) = order.split_first() else {
+        return Card::new_with_latest_version("");
+    };
+    let mut merged = (*preferred).clone();
+    for other in others {
+        macro_rules! merge_options {
+            ($($field:ident => $name:literal),*) => {
+                $(
+                    if merged.$field.is_none() && other.$field.is_some() {
+                        merged.$field.clone_from(&other.$field);
+                        record_merged(&mut merged, other, $name, $name);
+                    }
+                )*
+            };
+        }
+        macro_rules! merge_maps {
+            ($($field:ident => $name:literal),*) => {
+                $(
+                    let merged_keys = merge_entries(&mut merged.$field, &other.$field, |a, b| a == b);
+                    record_entries(&mut merged, other, $name, merged_keys.added);
+                )*
+            };
+        }
+        merge_options!(
+            created => "created",
+            kind => "kind",
+            language => "language",
+            prod_id => "prodId",
+            updated => "updated",
+            name => "name",
+            speak_to_as => "speakToAs"
+        );
+        let added = merge_keys(&mut merged.members, &other.members);
+        record_entries(&mut merged, other, "members", added);
+        let added = merge_keys(&mut merged.keywords, &other.keywords);
+        record_entries(&mut merged, other, "keywords", added);
+        let added = merge_keys(&mut merged.related_to, &other.related_to);
+        record_entries(&mut merged, other, "relatedTo", added);
+        let merged_keys = merge_entries(&mut merged.emails, &other.emails, |a, b| {
+            normalize_email(&a.address) == normalize_email(&b.address)
+        });
+        record_entries(&mut merged, other, "emails", merged_keys.added);
+        let merged_keys = merge_entries(&mut merged.phones, &other.phones, |a, b| {
+            normalize_phone(&a.number) == normalize_phone(&b.number)
+        });
+        record_entries(&mut merged, other, "phones", merged_keys.added);
+        // the titles reference the organizations and the places of the anniversaries the addresses by key,
+        // the references of the other Card are rewritten to the keys of the entries in the merged Card
+        let organizations =
+            merge_entries(&mut merged.organizations, &other.organizations, |a, b| {
+                a == b
+            });
+        record_entries(&mut merged, other, "organizations", organizations.added);
+        let titles = rewrite_references(&other.titles, &organizations.renamed, |title| {
+            title.organization_id.as_mut()
+        });
+        let merged_keys = merge_entries(
+            &mut merged.titles,
+            titles.as_ref().unwrap_or(&other.titles),
+            |a, b| a == b,
+        );
+        record_entries(&mut merged, other, "titles", merged_keys.added);
+        let addresses = merge_entries(&mut merged.addresses, &other.addresses, |a, b| a == b);
+        record_entries(&mut merged, other, "addresses", addresses.added);
+        let anniversaries =
+            rewrite_references(&other.anniversaries, &addresses.renamed, |anniversary| {
+                match &mut anniversary.place {
+                    Some(PlaceRef::Key(key)) => Some(key),
+                    _ => None,
+                }
+            });
+        let merged_keys = merge_entries(
+            &mut merged.anniversaries,
+            anniversaries.as_ref().unwrap_or(&other.anniversaries),
+            |a, b| a == b,
+        );
+        record_entries(&mut merged, other, "anniversaries", merged_keys.added);
+        merge_maps!(
+            nicknames => "nicknames",
+            online_services => "onlineServices",
+            preferred_languages => "preferredLanguages",
+            calendars => "calendars",
+            scheduling_addresses => "schedulingAddresses",
+            crypto_keys => "cryptoKeys",
+            directories => "directories",
+            links => "links",
+            media => "media",
+            notes => "notes",
+            personal_info => "personalInfo"
+        );
+        let mut keys: Vec<&String> = other.extensions.keys().collect();
+        keys.sort();
+        for key in keys {
+            // the sources of the other Card are recorded entry by entry
+            if key == PROVENANCE_PROPERTY || merged.extensions.contains_key(key) {
+                continue;
+            }
+            merged
+                .extensions
+                .insert(key.clone(), clone_iteratively(&other.extensions[key]));
+            record_merged(&mut merged, other, key, key);
+        }
+    }
+    if !others.is_empty() {
+        merged.touch();
+    }
+    merged
+}
@@ -0,0 +1,336 @@
+//! A limit on the nesting depth of the raw JSON values of a [`Card`]: the unknown properties and the localizations.
+//!
+//! The other properties are typed, so their depth is bounded. A raw value can be nested at will when the Card
+//! is built in code or from a [`Value`], while serde, the serialization with ordered keys and the anonymization
+//! recurse once per level. The values are checked without recursion, a value nested too deeply being rejected
+//! with a [`DepthExceeded`] error before it reaches them.
+//!
+//! The raw values are stored in [`RawValues`], cloned, compared and dropped without recursion, so a Card
+//! holding a value nested too deeply can still be cloned (e.g. by [`Card::get_localized`]), compared and dropped.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::path::PathSegment;
+use crate::Card;
+
+/// The default maximum nesting depth, the recursion limit of `serde_json` when parsing JSON text.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// A value nested deeper than the maximum depth.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepthExceeded {
+    /// The path of the property holding the value (e.g. `example.com:foo` or `localizations/fr/titles~1t1`),
+    /// empty for a whole document.
+    pub path: String,
+    /// The maximum depth, an array or an object being one level deeper than its parent.
+    pub max_depth: usize,
+}
+
+impl fmt::Display for DepthExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(
+                f,
+                "The value is nested deeper than {} levels",
+                self.max_depth
+            )
+        } else {
+            write!(
+                f,
+                "The value of '{}' is nested deeper than {} levels",
+                self.path, self.max_depth
+            )
+        }
+    }
+}
+
+impl std::error::Error for DepthExceeded {}
+
+/// Check the depth of a value without recursion, `[]` and `{}` having a depth of 1 and the other values of 0
+pub(crate) fn check_depth(
+    value: &Value,
+    path: &str,
+    max_depth: usize,
+) -> Result<(), DepthExceeded> {
+    let mut stack = vec![(value, 1)];
+    while let Some((value, depth)) = stack.pop() {
+        let children: Box<dyn Iterator<Item = &Value>> = match value {
+            Value::Array(values) => Box::new(values.iter()),
+            Value::Object(object) => Box::new(object.values()),
+            _ => continue,
+        };
+        if depth > max_depth {
+            return Err(DepthExceeded {
+                path: path.to_string(),
+                max_depth,
+            });
+        }
+        stack.extend(children.map(|child| (child, depth + 1)));
+    }
+    Ok(())
+}
+
+/// Drop a value without recursion, the drop of `serde_json` recursing once per level
+pub(crate) fn drop_iteratively(value: Value) {
+    let mut stack = vec![value];
+    while let Some(value) = stack.pop() {
+        match value {
+            Value::Array(values) => stack.extend(values),
+            Value::Object(object) => stack.extend(object.into_iter().map(|(_, value)| value)),
+            _ => {}
+        }
+    }
+}
+
+/// Clone a value without recursion, the clone of `serde_json` recursing once per level
+pub(crate) fn clone_iteratively(value: &Value) -> Value {
+    /// A step of the clone, the children of an array or an object being cloned before it
+    enum Step<'a> {
+        Clone(&'a Value),
+        Array(usize),
+        Object(Vec<&'a String>),
+    }
+
+    let mut steps = vec![Step::Clone(value)];
+    let mut cloned: Vec<Value> = Vec::new();
+    while let Some(step) = steps.pop() {
+        match step {
+            Step::Clone(Value::Array(values)) => {
+                steps.push(Step::Array(values.len()));
+                steps.extend(values.iter().rev().map(Step::Clone));
+            }
+            Step::Clone(Value::Object(object)) => {
+                steps.push(Step::Object(object.keys().collect()));
+                steps.extend(object.values().rev().map(Step::Clone));
+            }
+            Step::Clone(value) => cloned.push(value.clone()),
+            Step::Array(len) => {
+                let values = cloned.split_off(cloned.len() - len);
+                cloned.push(Value::Array(values));
+            }
+            Step::Object(keys) => {
+                let values = cloned.split_off(cloned.len() - keys.len());
+                cloned.push(Value::Object(
+                    keys.into_iter().cloned().zip(values).collect(),
+                ));
+            }
+        }
+    }
+    cloned.pop().unwrap_or_default()
+}
+
+/// Compare two values without recursion, the comparison of `serde_json` recursing once per level
+pub(crate) fn eq_iteratively(a: &Value, b: &Value) -> bool {
+    let mut stack = vec![(a, b)];
+    while let Some(pair) = stack.pop() {
+        match pair {
+            (Value::Array(a), Value::Array(b)) if a.len() == b.len() => {
+                stack.extend(a.iter().zip(b))
+            }
+            (Value::Object(a), Value::Object(b)) if a.len() == b.len() => {
+                for (key, a) in a {
+                    match b.get(key) {
+                        Some(b) => stack.push((a, b)),
+                        None => return false,
+                    }
+                }
+            }
+            (Value::Array(_) | Value::Object(_), _) | (_, Value::Array(_) | Value::Object(_)) => {
+                return false
+            }
+            (a, b) if a != b => return false,
+            _ => {}
+        }
+    }
+    true
+}
+
+/// Formats a raw value, or its error if it is nested deeper than [`DEFAULT_MAX_DEPTH`]
+struct DebugRaw<'a>(&'a Value);
+
+impl fmt::Debug for DebugRaw<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match check_depth(self.0, "", DEFAULT_MAX_DEPTH) {
+            Ok(()) => fmt::Debug::fmt(self.0, f),
+            Err(error) => write!(f, "<{}>", error),
+        }
+    }
+}
+
+/// The raw JSON values of a Card by key: its unknown properties, or the patches of a localization.
+///
+/// It dereferences to its `HashMap`. Unlike a `HashMap` of [`Value`]s, it is cloned, compared and dropped without
+/// recursion, and `Debug` formats a value nested deeper than [`DEFAULT_MAX_DEPTH`] as its error.
+///
+/// ```rust
+/// use jscontact::Card;
+/// use serde_json::json;
+///
+/// let mut card = Card::new_with_latest_version("1234");
+/// card.extensions.insert("example.com:foo".to_string(), json!([1]));
+/// assert_eq!(card.clone().extensions["example.com:foo"], json!([1]));
+/// ```
+#[derive(Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RawValues(HashMap<String, Value>);
+
+impl RawValues {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the `HashMap` of the values, the caller then dropping them.
+    pub fn into_inner(mut self) -> HashMap<String, Value> {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl Deref for RawValues {
+    type Target = HashMap<String, Value>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for RawValues {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<HashMap<String, Value>> for RawValues {
+    fn from(values: HashMap<String, Value>) -> Self {
+        Self(values)
+    }
+}
+
+impl FromIterator<(String, Value)> for RawValues {
+    fn from_iter<I: IntoIterator<Item = (String, Value)>>(values: I) -> Self {
+        Self(values.into_iter().collect())
+    }
+}
+
+impl IntoIterator for RawValues {
+    type Item = (String, Value);
+    type IntoIter = std::collections::hash_map::IntoIter<String, Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_inner().into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a RawValues {
+    type Item = (&'a String, &'a Value);
+    type IntoIter = std::collections::hash_map::Iter<'a, String, Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl Clone for RawValues {
+    fn clone(&self) -> Self {
+        self.0
+            .iter()
+            .map(|(key, value)| (key.clone(), clone_iteratively(value)))
+            .collect()
+    }
+}
+
+impl PartialEq<HashMap<String, Value>> for RawValues {
+    fn eq(&self, other: &HashMap<String, Value>) -> bool {
+        self.0.len() == other.len()
+            && self
+                .0
+                .iter()
+                .all(|(key, a)| other.get(key).is_some_and(|b| eq_iteratively(a, b)))
+    }
+}
+
+impl PartialEq for RawValues {
+    fn eq(&self, other: &Self) -> bool {
+        *self == other.0
+    }
+}
+
+impl fmt::Debug for RawValues {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(key, value)| (key, DebugRaw(value))))
+            .finish()
+    }
+}
+
+impl Drop for RawValues {
+    fn drop(&mut self) {
+        for (_, value) in self.0.drain() {
+            if matches!(value, Value::Array(_) | Value::Object(_)) {
+                drop_iteratively(value);
+            }
+        }
+    }
+}
+
+/// Check the depth of a value about to be deserialized, dropping it without recursion if it is too deep
+pub(crate) fn check_owned_depth(value: Value, max_depth: usize) -> Result<Value, DepthExceeded> {
+    match check_depth(&value, "", max_depth) {
+        Ok(()) => Ok(value),
+        Err(error) => {
+            drop_iteratively(value);
+            Err(error)
+        }
+    }
+}
+
+impl Card {
+    /// Check that the unknown properties and the localizations are not nested deeper than a maximum depth,
+    /// counted from the Card object (e.g. an unknown property holding an array of arrays has a depth of 3).
+    ///
+    /// The serialization fails for a Card nested deeper than [`DEFAULT_MAX_DEPTH`].
+    ///
+    /// ```rust
+    /// use jscontact::Card;
+    /// use serde_json::json;
+    ///
+    /// let mut card = Card::new_with_latest_version("1234");
+    /// card.extensions.insert("example.com:foo".to_string(), json!([[1]]));
+    /// assert!(card.check_depth(3).is_ok());
+    /// assert_eq!(card.check_depth(2).unwrap_err().path, "example.com:foo");
+    /// ```
+    /// # Errors
+    /// Will return an error with the path of the first property nested too deeply.
+    pub fn check_depth(&self, max_depth: usize) -> Result<(), DepthExceeded> {
+        let mut extensions: Vec<_> = self.extensions.iter().collect();
+        extensions.sort_by_key(|(key, _)| *key);
+        for (key, value) in extensions {
+            let path = PathSegment::Key(key.clone()).to_string();
+            // the value is in the Card
+            check_depth(value, &path, max_depth.saturating_sub(1))
+                .map_err(|error| DepthExceeded { max_depth, ..error })?;
+        }
+        let mut languages: Vec<_> = self.localizations.iter().flatten().collect();
+        languages.sort_by_key(|(language, _)| *language);
+        for (language, patches) in languages {
+            let mut patches: Vec<_> = patches.iter().collect();
+            patches.sort_by_key(|(key, _)| *key);
+            for (key, value) in patches {
+                let path = format!(
+                    "localizations/{}/{}",
+                    PathSegment::Key(language.clone()),
+                    PathSegment::Key(key.clone())
+                );
+                // the value is in the localizations, in a language, in the Card
+                check_depth(value, &path, max_depth.saturating_sub(3))
+                    .map_err(|error| DepthExceeded { max_depth, ..error })?;
+            }
+        }
+        Ok(())
+    }
+}
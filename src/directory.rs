@@ -0,0 +1,181 @@
+//! Helpers for the directories of a [`Card`], and parsing of the LDAP URLs (RFC 4516) of the directories.
+//!
+//! A directory with the `entry` kind is the entry of the Card in a directory,
+//! while a directory with the `directory` kind is a whole directory (e.g. the directory of an organization).
+
+use crate::{Card, Directory, DirectoryKind};
+
+/// The scope of an LDAP search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LdapScope {
+    /// Only the entry of the base DN, the default scope.
+    #[default]
+    Base,
+    /// The entries immediately below the base DN.
+    One,
+    /// The base DN and all the entries below it.
+    Sub,
+}
+
+/// The components of an LDAP URL, `ldap://host:port/dn?attributes?scope?filter?extensions`.
+///
+/// The base DN, the attributes and the filter are percent-decoded.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LdapUrl {
+    /// True for a `ldaps://` URL.
+    pub secure: bool,
+    /// The host, `None` if the client should use its default server.
+    pub host: Option<String>,
+    /// The port, `None` for the default port.
+    pub port: Option<u16>,
+    /// The distinguished name of the base entry (e.g. `o=Example Tech,ou=Engineering`).
+    pub base_dn: String,
+    /// The attributes to return, all the attributes if empty.
+    pub attributes: Vec<String>,
+    /// The scope of the search.
+    pub scope: LdapScope,
+    /// The search filter, `(objectClass=*)` if not set.
+    pub filter: Option<String>,
+    /// The extensions (e.g. `!bindname=cn=Manager`).
+    pub extensions: Vec<String>,
+}
+
+impl LdapUrl {
+    /// Parse an LDAP URL, `None` if the URL is not a valid `ldap://` or `ldaps://` URL.
+    pub fn parse(uri: &str) -> Option<Self> {
+        let (scheme, rest) = uri.split_once("://")?;
+        let secure = match scheme.to_ascii_lowercase().as_str() {
+            "ldap" => false,
+            "ldaps" => true,
+            _ => return None,
+        };
+        let (hostport, rest) = match rest.find('/') {
+            Some(idx) => (&rest[..idx], &rest[idx + 1..]),
+            None => (rest, ""),
+        };
+        let (host, port) = parse_hostport(hostport)?;
+        let mut parts = rest.split('?');
+        let base_dn = percent_decode(parts.next().unwrap_or_default())?;
+        let attributes = match parts.next() {
+            Some(attributes) if !attributes.is_empty() => attributes
+                .split(',')
+                .map(percent_decode)
+                .collect::<Option<Vec<String>>>()?,
+            _ => Vec::new(),
+        };
+        let scope = match parts.next().map(str::to_ascii_lowercase).as_deref() {
+            None | Some("") | Some("base") => LdapScope::Base,
+            Some("one") => LdapScope::One,
+            Some("sub") => LdapScope::Sub,
+            Some(_) => return None,
+        };
+        let filter = match parts.next() {
+            Some(filter) if !filter.is_empty() => Some(percent_decode(filter)?),
+            _ => None,
+        };
+        let extensions = match parts.next() {
+            Some(extensions) if !extensions.is_empty() => extensions
+                .split(',')
+                .map(percent_decode)
+                .collect::<Option<Vec<String>>>()?,
+            _ => Vec::new(),
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self {
+            secure,
+            host,
+            port,
+            base_dn,
+            attributes,
+            scope,
+            filter,
+            extensions,
+        })
+    }
+}
+
+/// Parse the host and the port of an URL, the host of an IPv6 address is kept in brackets
+fn parse_hostport(hostport: &str) -> Option<(Option<String>, Option<u16>)> {
+    if hostport.is_empty() {
+        return Some((None, None));
+    }
+    let port_separator = match hostport.rfind(']') {
+        Some(end) => hostport[end..].find(':').map(|idx| idx + end),
+        None => hostport.rfind(':'),
+    };
+    match port_separator {
+        Some(idx) => {
+            let port = &hostport[idx + 1..];
+            let port = if port.is_empty() {
+                None
+            } else {
+                Some(port.parse().ok()?)
+            };
+            let host = &hostport[..idx];
+            Some(((!host.is_empty()).then(|| host.to_string()), port))
+        }
+        None => Some((Some(hostport.to_string()), None)),
+    }
+}
+
+/// Decode the percent-encoded characters of a string
+pub(crate) fn percent_decode(text: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut iter = text.bytes();
+    while let Some(byte) = iter.next() {
+        if byte != b'%' {
+            bytes.push(byte);
+            continue;
+        }
+        let high = char::from(iter.next()?).to_digit(16)?;
+        let low = char::from(iter.next()?).to_digit(16)?;
+        bytes.push(u8::try_from(high * 16 + low).ok()?);
+    }
+    String::from_utf8(bytes).ok()
+}
+
+impl Directory {
+    /// Get the components of the URI of the directory, `None` if it is not an LDAP URL.
+    pub fn ldap_components(&self) -> Option<LdapUrl> {
+        LdapUrl::parse(&self.uri)
+    }
+}
+
+impl Card {
+    /// Get the URI of the preferred directory with the `entry` kind, the entry of the Card in a directory.
+    ///
+    /// A lower `pref` is preferred, a directory without `pref` comes last.
+    pub fn directory_entry_uri(&self) -> Option<&str> {
+        self.directories
+            .iter()
+            .flatten()
+            .filter(|(_, directory)| directory.kind == Some(DirectoryKind::Entry))
+            .min_by(|(a_key, a), (b_key, b)| {
+                let a_pref = a.pref.unwrap_or(u64::MAX);
+                let b_pref = b.pref.unwrap_or(u64::MAX);
+                a_pref.cmp(&b_pref).then_with(|| a_key.cmp(b_key))
+            })
+            .map(|(_, directory)| directory.uri.as_str())
+    }
+
+    /// Get the directories with the `directory` kind with their ids, sorted by `listAs`.
+    ///
+    /// The directories without `listAs` come last, sorted by id.
+    pub fn org_directories(&self) -> Vec<(&str, &Directory)> {
+        let mut directories: Vec<(&str, &Directory)> = self
+            .directories
+            .iter()
+            .flatten()
+            .filter(|(_, directory)| directory.kind == Some(DirectoryKind::Directory))
+            .map(|(key, directory)| (key.as_str(), directory))
+            .collect();
+        directories.sort_by(|(a_key, a), (b_key, b)| {
+            let a_list_as = a.list_as.unwrap_or(u64::MAX);
+            let b_list_as = b.list_as.unwrap_or(u64::MAX);
+            a_list_as.cmp(&b_list_as).then_with(|| a_key.cmp(b_key))
+        });
+        directories
+    }
+}
@@ -0,0 +1,87 @@
+//! Copies of a [`Card`] under a new uid, e.g. for the "duplicate contact" action of a contact app.
+//!
+//! The copy keeps all the properties of the contact, but not the metadata of the original Card:
+//! `created`, `updated` and `prodId` are unset, as the copy is a new Card that has not been produced by the
+//! product of the original yet. The relations of the original to itself (a `relatedTo` entry keyed by its uid)
+//! are removed or moved to the new uid, see [`SelfRelation`].
+
+#[cfg(feature = "tracking")]
+use crate::tracking::Revision;
+use crate::Card;
+
+/// What to do with a `relatedTo` entry keyed by the uid of the original Card, see [`CloneOptions`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SelfRelation {
+    /// Remove the entry, the default.
+    #[default]
+    Remove,
+    /// Key the entry by the new uid, so the copy relates to itself as the original did.
+    Rewrite,
+}
+
+/// The options of [`Card::clone_with_new_uid_with`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CloneOptions {
+    /// What to do with the relation of the original Card to itself.
+    pub self_relation: SelfRelation,
+    /// Remove the localizations from the copy.
+    pub strip_localizations: bool,
+}
+
+impl Card {
+    /// Copy the Card with a new uid and the default options, see [`Card::clone_with_new_uid_with`].
+    pub fn clone_with_new_uid(&self, new_uid: &str) -> Card {
+        self.clone_with_new_uid_with(new_uid, &CloneOptions::default())
+    }
+
+    /// Copy the Card with a new uid.
+    ///
+    /// The copy has all the properties of the Card, except:
+    /// - `uid`, set to the new uid
+    /// - `created`, `updated` and `prodId`, unset
+    /// - the `relatedTo` entry keyed by the uid of the Card, removed or keyed by the new uid (see [`SelfRelation`])
+    /// - the localizations, removed if [`CloneOptions::strip_localizations`] is set
+    ///
+    /// With the `tracking` feature, the revision of the copy starts from zero.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, CloneOptions};
+    ///
+    /// let mut card = Card::new_with_latest_version("urn:uuid:1");
+    /// card.prod_id = Some("ACME Contacts".to_string());
+    /// let copy = card.clone_with_new_uid_with("urn:uuid:2", &CloneOptions::default());
+    /// assert_eq!(copy.uid, "urn:uuid:2");
+    /// assert_eq!(copy.prod_id, None);
+    /// assert_eq!(card.uid, "urn:uuid:1");
+    /// ```
+    pub fn clone_with_new_uid_with(&self, new_uid: &str, opts: &CloneOptions) -> Card {
+        let mut copy = self.clone();
+        copy.uid = new_uid.to_string();
+        copy.created = None;
+        copy.updated = None;
+        copy.prod_id = None;
+        if let Some(related_to) = &mut copy.related_to {
+            if let Some(relation) = related_to.remove(self.uid.as_str()) {
+                if opts.self_relation == SelfRelation::Rewrite {
+                    related_to.insert(new_uid.into(), relation);
+                }
+            }
+        }
+        if opts.strip_localizations {
+            copy.localizations = None;
+        }
+        #[cfg(feature = "tracking")]
+        {
+            copy.revision = Revision::default();
+        }
+        copy
+    }
+
+    /// Copy the Card with a random `urn:uuid:` uid (a version 4 UUID) and the default options,
+    /// see [`Card::clone_with_new_uid_with`].
+    #[cfg(feature = "uuid")]
+    pub fn clone_with_random_uid(&self) -> Card {
+        let uid = format!("urn:uuid:{}", uuid::Uuid::new_v4());
+        self.clone_with_new_uid(&uid)
+    }
+}
@@ -0,0 +1,62 @@
+//! The Cards embedded in the unknown properties of another Card, e.g. the Card of an assistant in
+//! `example.com:assistant`.
+//!
+//! An embedded Card is a raw value of [`Card::extensions`], so its depth counts in the depth of the Card
+//! holding it: both methods check it against [`DEFAULT_MAX_DEPTH`], one level being taken by the holding Card.
+
+use crate::depth::{check_depth, DEFAULT_MAX_DEPTH};
+use crate::{Card, DepthExceeded, PathSegment};
+
+impl Card {
+    /// Parse the value of an unknown property as a Card, `None` if the Card has no such property.
+    ///
+    /// ```rust
+    /// use jscontact::Card;
+    ///
+    /// let mut card = Card::new_with_latest_version("1234");
+    /// let assistant = Card::new_with_latest_version("5678");
+    /// card.set_extension_card("example.com:assistant", &assistant).unwrap();
+    /// let embedded = card.extension_as_card("example.com:assistant").unwrap().unwrap();
+    /// assert_eq!(embedded, assistant);
+    /// assert!(card.extension_as_card("example.com:manager").is_none());
+    /// ```
+    /// # Errors
+    /// Will return an error if the value is nested too deeply or is not a valid Card.
+    pub fn extension_as_card(&self, key: &str) -> Option<Result<Card, String>> {
+        let value = self.extensions.get(key)?;
+        let path = PathSegment::Key(key.to_string()).to_string();
+        // the value is in the Card, it is checked before being copied
+        let card = check_depth(value, &path, DEFAULT_MAX_DEPTH - 1)
+            .map_err(|error| {
+                DepthExceeded {
+                    max_depth: DEFAULT_MAX_DEPTH,
+                    ..error
+                }
+                .to_string()
+            })
+            .and_then(|()| Card::try_from(value.clone()));
+        Some(card)
+    }
+
+    /// Set the value of an unknown property to a Card, replacing the previous value if any.
+    ///
+    /// The name of the property should be vendor-specific (e.g. `example.com:assistant`), see
+    /// [`crate::unknown::is_vendor_property`].
+    /// # Errors
+    /// Will return an error if the Card would be nested deeper than [`DEFAULT_MAX_DEPTH`] once embedded,
+    /// or cannot be serialized.
+    pub fn set_extension_card(&mut self, key: &str, card: &Card) -> Result<(), String> {
+        let path = PathSegment::Key(key.to_string()).to_string();
+        card.check_depth(DEFAULT_MAX_DEPTH - 1).map_err(|error| {
+            DepthExceeded {
+                path: format!("{}/{}", path, error.path),
+                max_depth: DEFAULT_MAX_DEPTH,
+            }
+            .to_string()
+        })?;
+        let value = serde_json::to_value(card).map_err(|e| e.to_string())?;
+        self.extensions.insert(key.to_string(), value);
+        self.touch();
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+//! The required text values that must not be empty, e.g. the `number` of a Phone or the `uri` of a Link.
+//!
+//! RFC 9553 requires these properties but does not forbid an empty string, which carries no information.
+//! The policy of the crate is to accept them when deserializing and with the `new` constructors,
+//! to reject them with the `new_checked` constructors, to report them with [`Card::validate`]
+//! (see [`crate::ValidationCode::EmptyValue`]) and to remove them with [`Card::fix_common_issues`]
+//! (see [`crate::FixRules::empty_entries`]). A value made only of whitespace is empty.
+
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::{
+    Calendar, CryptoKey, Directory, EmailAddress, Link, Media, MediaKind, Nickname, Note, OrgUnit,
+    PersonalInfo, PersonalInfoKind, Phone, SchedulingAddress, Title,
+};
+
+/// The properties whose entries have a required text, with the name of the text field.
+pub(crate) const REQUIRED_TEXTS: &[(&str, &str)] = &[
+    ("nicknames", "name"),
+    ("titles", "name"),
+    ("emails", "address"),
+    ("phones", "number"),
+    ("calendars", "uri"),
+    ("schedulingAddresses", "uri"),
+    ("cryptoKeys", "uri"),
+    ("directories", "uri"),
+    ("links", "uri"),
+    ("media", "uri"),
+    ("notes", "note"),
+    ("personalInfo", "value"),
+];
+
+/// A required text value that is empty or made only of whitespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmptyValue {
+    /// The name of the field, as serialized (e.g. `number`).
+    pub field: &'static str,
+}
+
+impl fmt::Display for EmptyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The {} must not be empty", self.field)
+    }
+}
+
+impl std::error::Error for EmptyValue {}
+
+/// Check if a text is empty or made only of whitespace
+pub(crate) fn is_blank(text: &str) -> bool {
+    text.trim().is_empty()
+}
+
+/// Check if a map entry of a property has an empty required text, e.g. a phone without number
+pub(crate) fn has_empty_required_text(property: &str, entry: &Value) -> bool {
+    REQUIRED_TEXTS
+        .iter()
+        .find(|(name, _)| *name == property)
+        .and_then(|(_, field)| entry.get(field)?.as_str())
+        .is_some_and(is_blank)
+}
+
+/// Reject an empty required text
+fn check(field: &'static str, text: &str) -> Result<(), EmptyValue> {
+    if is_blank(text) {
+        Err(EmptyValue { field })
+    } else {
+        Ok(())
+    }
+}
+
+/// Implement `new_checked` for the types whose `new` takes the required text only
+macro_rules! new_checked {
+    ($($ty:ident($field:ident: $name:literal)),* $(,)?) => {
+        $(
+            impl $ty {
+                #[doc = concat!("Creates a new ", stringify!($ty), " object, see [`", stringify!($ty), "::new`].")]
+                /// # Errors
+                #[doc = concat!("Will return an error if the ", $name, " is empty or made only of whitespace.")]
+                pub fn new_checked($field: &str) -> Result<Self, EmptyValue> {
+                    check($name, $field)?;
+                    Ok(Self::new($field))
+                }
+            }
+        )*
+    };
+}
+
+new_checked!(
+    Nickname(name: "name"),
+    OrgUnit(name: "name"),
+    Title(name: "name"),
+    EmailAddress(address: "address"),
+    Phone(number: "number"),
+    Calendar(uri: "uri"),
+    SchedulingAddress(uri: "uri"),
+    CryptoKey(uri: "uri"),
+    Directory(uri: "uri"),
+    Link(uri: "uri"),
+    Note(note: "note"),
+);
+
+impl Media {
+    /// Creates a new Media object, see [`Media::new`].
+    /// # Errors
+    /// Will return an error if the uri is empty or made only of whitespace.
+    pub fn new_checked(uri: &str, kind: MediaKind) -> Result<Self, EmptyValue> {
+        check("uri", uri)?;
+        Ok(Self::new(uri, kind))
+    }
+}
+
+impl PersonalInfo {
+    /// Creates a new PersonalInfo object, see [`PersonalInfo::new`].
+    /// # Errors
+    /// Will return an error if the value is empty or made only of whitespace.
+    pub fn new_checked(kind: PersonalInfoKind, value: &str) -> Result<Self, EmptyValue> {
+        check("value", value)?;
+        Ok(Self::new(kind, value))
+    }
+}
@@ -0,0 +1,87 @@
+//! Splicing of a [`Card`] into a larger JSON object, e.g. a JMAP `ContactCard/set` request whose objects hold
+//! the properties of the Card next to fields of the server (`id`, `addressBookIds`, ...).
+//!
+//! The Card is written to or read from the object directly, without converting the whole object to a Card.
+
+use serde::de::{self, value::MapDeserializer};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::depth::{check_depth, DEFAULT_MAX_DEPTH};
+use crate::{Card, DepthExceeded, PathSegment};
+
+impl Card {
+    /// Write the top-level properties of the Card into an object, the other members of the object being kept.
+    ///
+    /// A member of the object with the name of a property of the Card is overwritten,
+    /// the names of the overwritten members are returned, sorted.
+    ///
+    /// ```rust
+    /// use jscontact::Card;
+    /// use serde_json::{json, Map};
+    ///
+    /// let card = Card::new_with_latest_version("1234");
+    /// let mut object = Map::new();
+    /// object.insert("id".to_string(), json!("c1"));
+    /// object.insert("uid".to_string(), json!("old"));
+    /// let overwritten = card.serialize_into(&mut object).unwrap();
+    /// assert_eq!(overwritten, ["uid"]);
+    /// assert_eq!(object["id"], "c1");
+    /// assert_eq!(object["uid"], "1234");
+    /// ```
+    /// # Errors
+    /// Will return an error if the Card cannot be serialized.
+    pub fn serialize_into(
+        &self,
+        object: &mut Map<String, Value>,
+    ) -> Result<Vec<String>, serde_json::Error> {
+        let Value::Object(properties) = serde_json::to_value(self)? else {
+            unreachable!("a Card is serialized as an object");
+        };
+        let mut overwritten: Vec<String> = properties
+            .into_iter()
+            .filter_map(|(key, value)| object.insert(key.clone(), value).map(|_| key))
+            .collect();
+        overwritten.sort();
+        Ok(overwritten)
+    }
+
+    /// Read a Card from an object holding its properties, ignoring the members of the object that are not
+    /// part of the Card (e.g. `id` and `addressBookIds`), which would be unknown properties otherwise.
+    ///
+    /// ```rust
+    /// use jscontact::Card;
+    /// use serde_json::json;
+    ///
+    /// let object = json!({ "id": "c1", "@type": "Card", "version": "1.0", "uid": "1234" });
+    /// let card = Card::deserialize_from(object.as_object().unwrap(), &["id"]).unwrap();
+    /// assert_eq!(card.uid, "1234");
+    /// assert!(card.extensions.is_empty());
+    /// ```
+    /// # Errors
+    /// Will return an error if the object without the ignored members is not a valid Card,
+    /// or is nested deeper than [`crate::DEFAULT_MAX_DEPTH`].
+    pub fn deserialize_from(
+        object: &Map<String, Value>,
+        ignore: &[&str],
+    ) -> Result<Card, serde_json::Error> {
+        let members = || {
+            object
+                .iter()
+                .filter(|(key, _)| !ignore.contains(&key.as_str()))
+        };
+        for (key, value) in members() {
+            let path = PathSegment::Key(key.clone()).to_string();
+            // the value is in the Card
+            check_depth(value, &path, DEFAULT_MAX_DEPTH - 1).map_err(|error| {
+                de::Error::custom(DepthExceeded {
+                    max_depth: DEFAULT_MAX_DEPTH,
+                    ..error
+                })
+            })?;
+        }
+        let deserializer =
+            MapDeserializer::new(members().map(|(key, value)| (key.as_str(), value)));
+        <Card as Deserialize>::deserialize(deserializer)
+    }
+}
@@ -0,0 +1,145 @@
+//! Extension of the map properties of a [`Card`] from iterators, without building the maps by hand.
+//!
+//! The values are keyed automatically with a prefix and a number (`e1`, `e2`, ... for the emails),
+//! the numbers continuing after the largest existing key of this form, keyed by a [`KeyGen`], or keyed by the caller.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::{
+    Address, Card, EmailAddress, KeyGen, Link, Media, Nickname, Note, PersonalInfo, Phone,
+    SmallString, Title,
+};
+
+/// Get the first number after the largest key made of the prefix and a number, 1 if there is none
+pub(crate) fn next_number<V>(map: &HashMap<SmallString, V>, prefix: &str) -> usize {
+    next_number_of(map.keys().map(SmallString::as_str), prefix)
+}
+
+/// Get the first number after the largest of the keys made of the prefix and a number, 1 if there is none
+pub(crate) fn next_number_of<'a>(keys: impl Iterator<Item = &'a str>, prefix: &str) -> usize {
+    keys.filter_map(|key| key.strip_prefix(prefix))
+        .filter(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+        .filter_map(|number| number.parse::<usize>().ok())
+        .max()
+        .map_or(1, |number| number + 1)
+}
+
+/// Insert the values keyed with the prefix and the next numbers, returns `true` if a value was inserted
+fn extend_auto<V>(
+    map: &mut Option<HashMap<SmallString, V>>,
+    prefix: &str,
+    values: impl IntoIterator<Item = V>,
+) -> bool {
+    let mut values = values.into_iter().peekable();
+    if values.peek().is_none() {
+        return false;
+    }
+    let map = map.get_or_insert_with(HashMap::new);
+    let first = next_number(map, prefix);
+    for (number, value) in (first..).zip(values) {
+        let mut key = SmallString::from(prefix);
+        key.push_str(&number.to_string());
+        map.insert(key, value);
+    }
+    true
+}
+
+/// Insert the values keyed by a generator, returns `true` if a value was inserted
+pub(crate) fn extend_with<V: Serialize>(
+    map: &mut Option<HashMap<SmallString, V>>,
+    prefix: &str,
+    values: impl IntoIterator<Item = V>,
+    key_gen: &dyn KeyGen,
+) -> bool {
+    let mut values = values.into_iter().peekable();
+    if values.peek().is_none() {
+        return false;
+    }
+    let map = map.get_or_insert_with(HashMap::new);
+    // the generator sees the entries as JSON, the new ones included
+    let mut entries: Map<String, Value> = map
+        .iter()
+        .map(|(key, value)| {
+            (
+                key.to_string(),
+                serde_json::to_value(value).unwrap_or_default(),
+            )
+        })
+        .collect();
+    for value in values {
+        let entry = serde_json::to_value(&value).unwrap_or_default();
+        let key = key_gen.key(prefix, &entry, &entries);
+        map.insert(SmallString::from(key.as_str()), value);
+        entries.insert(key, entry);
+    }
+    true
+}
+
+/// Insert the keyed values, returns `true` if a value was inserted
+fn extend_keyed<K: Into<SmallString>, V>(
+    map: &mut Option<HashMap<SmallString, V>>,
+    values: impl IntoIterator<Item = (K, V)>,
+) -> bool {
+    let mut values = values.into_iter().peekable();
+    if values.peek().is_none() {
+        return false;
+    }
+    map.get_or_insert_with(HashMap::new)
+        .extend(values.map(|(key, value)| (key.into(), value)));
+    true
+}
+
+/// Generate the extension methods of the map properties and the `Extend` implementations
+macro_rules! extend_properties {
+    ($($property:literal => $field:ident: $value:ty, $prefix:literal, $auto:ident, $with:ident, $keyed:ident;)+) => {
+        impl Card {
+            $(
+                #[doc = concat!("Add the ", $property, " of an iterator, keyed `", $prefix, "1`, `", $prefix, "2`, ...")]
+                ///
+                /// The numbers continue after the largest existing key of this form, the existing entries are kept.
+                pub fn $auto(&mut self, values: impl IntoIterator<Item = $value>) {
+                    if extend_auto(&mut self.$field, $prefix, values) {
+                        self.touch();
+                    }
+                }
+
+                #[doc = concat!("Add the ", $property, " of an iterator, keyed by a [`KeyGen`], its prefix being `", $prefix, "`.")]
+                pub fn $with(&mut self, values: impl IntoIterator<Item = $value>, key_gen: &dyn KeyGen) {
+                    if extend_with(&mut self.$field, $prefix, values, key_gen) {
+                        self.touch();
+                    }
+                }
+
+                #[doc = concat!("Add the ", $property, " of an iterator with their keys, an entry with the same key being replaced.")]
+                pub fn $keyed<K: Into<SmallString>>(&mut self, values: impl IntoIterator<Item = (K, $value)>) {
+                    if extend_keyed(&mut self.$field, values) {
+                        self.touch();
+                    }
+                }
+            )+
+        }
+
+        $(
+            impl Extend<$value> for Card {
+                fn extend<I: IntoIterator<Item = $value>>(&mut self, values: I) {
+                    self.$auto(values);
+                }
+            }
+        )+
+    };
+}
+
+extend_properties! {
+    "emails" => emails: EmailAddress, "e", extend_emails, extend_emails_with, extend_emails_keyed;
+    "phones" => phones: Phone, "tel", extend_phones, extend_phones_with, extend_phones_keyed;
+    "addresses" => addresses: Address, "a", extend_addresses, extend_addresses_with, extend_addresses_keyed;
+    "links" => links: Link, "l", extend_links, extend_links_with, extend_links_keyed;
+    "media" => media: Media, "m", extend_media, extend_media_with, extend_media_keyed;
+    "notes" => notes: Note, "n", extend_notes, extend_notes_with, extend_notes_keyed;
+    "nicknames" => nicknames: Nickname, "k", extend_nicknames, extend_nicknames_with, extend_nicknames_keyed;
+    "titles" => titles: Title, "t", extend_titles, extend_titles_with, extend_titles_keyed;
+    "personal information" => personal_info: PersonalInfo, "pi", extend_personal_info, extend_personal_info_with, extend_personal_info_keyed;
+}
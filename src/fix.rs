@@ -0,0 +1,272 @@
+//! Repair of common mistakes of the producers of Cards, e.g. a `pref` of 0 or a lowercase country code.
+//!
+//! Each rule of [`FixRules`] can be enabled separately, the default rules only fix values that are invalid
+//! and have a single possible correction. The rules guessing the intent of the producer are disabled by default.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::empty::has_empty_required_text;
+use crate::path::{get_value, set_values, PROPERTIES};
+use crate::{Card, CardPath, PathSegment};
+
+/// The rules applied by [`Card::fix_common_issues`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixRules {
+    /// Set the `pref` values of 0 to 1, the most preferred.
+    pub zero_pref: bool,
+    /// Uppercase the lowercase country codes (e.g. `de` to `DE`).
+    pub country_code_case: bool,
+    /// Replace the `+00:00` offset of the timestamps by `Z`.
+    pub utc_offset: bool,
+    /// Remove the month and day of 0 of the partial dates, a day being removed with its month.
+    pub partial_date_zero: bool,
+    /// Add the `tel:` scheme to the phone numbers in the E.164 format (e.g. `+15551234567`).
+    pub phone_tel_scheme: bool,
+    /// Add the `mailto:` scheme to the scheduling addresses that are email addresses.
+    pub scheduling_mailto_scheme: bool,
+    /// Remove the entries of the maps whose required text is empty, e.g. a phone without number
+    /// (see [`crate::EmptyValue`]).
+    pub empty_entries: bool,
+}
+
+impl Default for FixRules {
+    fn default() -> Self {
+        Self {
+            zero_pref: true,
+            country_code_case: true,
+            utc_offset: true,
+            partial_date_zero: true,
+            phone_tel_scheme: false,
+            scheduling_mailto_scheme: false,
+            empty_entries: false,
+        }
+    }
+}
+
+impl FixRules {
+    /// All the rules enabled.
+    pub fn all() -> Self {
+        Self {
+            zero_pref: true,
+            country_code_case: true,
+            utc_offset: true,
+            partial_date_zero: true,
+            phone_tel_scheme: true,
+            scheduling_mailto_scheme: true,
+            empty_entries: true,
+        }
+    }
+
+    /// No rule enabled, to enable some rules one by one.
+    pub fn none() -> Self {
+        Self {
+            zero_pref: false,
+            country_code_case: false,
+            utc_offset: false,
+            partial_date_zero: false,
+            phone_tel_scheme: false,
+            scheduling_mailto_scheme: false,
+            empty_entries: false,
+        }
+    }
+}
+
+/// A change made by [`Card::fix_common_issues`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FixApplied {
+    /// The path of the changed value (e.g. `phones/tel0/pref`).
+    pub path: String,
+    /// The value before the change.
+    pub before: Value,
+    /// The value after the change, `null` if the value was removed.
+    pub after: Value,
+}
+
+impl fmt::Display for FixApplied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.path, self.before, self.after)
+    }
+}
+
+/// Get the name of the value at a path, its last key or its property
+fn name(path: &CardPath) -> &str {
+    match path.segments.last() {
+        Some(PathSegment::Key(key)) => key,
+        Some(PathSegment::Index(_)) => "",
+        None => &path.property,
+    }
+}
+
+/// Check if a path is the field of the date of an anniversary, e.g. `anniversaries/k8/date/month`
+fn is_date_field(path: &CardPath) -> bool {
+    path.property == "anniversaries"
+        && path.segments.len() == 3
+        && path.segments[1] == PathSegment::Key("date".to_string())
+}
+
+/// Check if a number is in the E.164 format: a `+` and up to 15 digits, the first one not being 0
+fn is_e164(number: &str) -> bool {
+    let Some(digits) = number.strip_prefix('+') else {
+        return false;
+    };
+    (2..=15).contains(&digits.len())
+        && digits.chars().all(|c| c.is_ascii_digit())
+        && !digits.starts_with('0')
+}
+
+/// Check if a value without scheme is an email address
+fn is_email(address: &str) -> bool {
+    match address.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && domain.contains('.')
+                && !domain.contains('@')
+                && !address.contains(':')
+                && !address.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+impl FixRules {
+    /// Get the fixed value of a field, `None` if it is not changed
+    fn fix_field(
+        &self,
+        path: &CardPath,
+        value: &Value,
+        parent: Option<&Map<String, Value>>,
+    ) -> Option<Value> {
+        if self.empty_entries
+            && path.segments.len() == 1
+            && has_empty_required_text(&path.property, value)
+        {
+            return Some(Value::Null);
+        }
+        match (name(path), value) {
+            ("pref", Value::Number(pref)) if self.zero_pref && pref.as_u64() == Some(0) => {
+                Some(Value::from(1))
+            }
+            ("countryCode", Value::String(code))
+                if self.country_code_case
+                    && code.len() == 2
+                    && code.chars().all(|c| c.is_ascii_alphabetic())
+                    && code.chars().any(|c| c.is_ascii_lowercase()) =>
+            {
+                Some(Value::String(code.to_ascii_uppercase()))
+            }
+            ("created" | "updated" | "utc", Value::String(timestamp)) if self.utc_offset => {
+                timestamp
+                    .strip_suffix("+00:00")
+                    .map(|utc| Value::String(format!("{}Z", utc)))
+            }
+            ("month", Value::Number(month))
+                if self.partial_date_zero && is_date_field(path) && month.as_u64() == Some(0) =>
+            {
+                Some(Value::Null)
+            }
+            ("day", Value::Number(day)) if self.partial_date_zero && is_date_field(path) => {
+                let month = parent.and_then(|parent| parent.get("month")?.as_u64());
+                (day.as_u64() == Some(0) || month == Some(0)).then_some(Value::Null)
+            }
+            ("number", Value::String(number))
+                if self.phone_tel_scheme
+                    && path.property == "phones"
+                    && path.segments.len() == 2
+                    && is_e164(number) =>
+            {
+                Some(Value::String(format!("tel:{}", number)))
+            }
+            ("uri", Value::String(uri))
+                if self.scheduling_mailto_scheme
+                    && path.property == "schedulingAddresses"
+                    && path.segments.len() == 2
+                    && is_email(uri) =>
+            {
+                Some(Value::String(format!("mailto:{}", uri)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Collect the fixes of a value and of its fields
+    fn collect(
+        &self,
+        path: &CardPath,
+        value: &Value,
+        parent: Option<&Map<String, Value>>,
+        fixes: &mut Vec<(CardPath, FixApplied)>,
+    ) {
+        if let Some(after) = self.fix_field(path, value, parent) {
+            let fix = FixApplied {
+                path: path.to_string(),
+                before: value.clone(),
+                after,
+            };
+            fixes.push((path.clone(), fix));
+            return;
+        }
+        match value {
+            Value::Object(map) => {
+                for (key, field) in map {
+                    let mut child = path.clone();
+                    child.segments.push(PathSegment::Key(key.clone()));
+                    self.collect(&child, field, Some(map), fixes);
+                }
+            }
+            Value::Array(values) => {
+                for (idx, field) in values.iter().enumerate() {
+                    let mut child = path.clone();
+                    child.segments.push(PathSegment::Index(idx));
+                    self.collect(&child, field, None, fixes);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Card {
+    /// Fix the common mistakes of the producers of Cards enabled by the rules, e.g. a `pref` of 0.
+    ///
+    /// Returns the changes, sorted by path. The localizations are not fixed.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, FixRules, Phone, SmallString};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut card = Card::new_with_latest_version("my:uri");
+    /// let mut phone = Phone::new("+15551234567");
+    /// phone.pref = Some(0);
+    /// card.phones = Some(HashMap::from([(SmallString::from("tel0"), phone)]));
+    ///
+    /// let fixes = card.fix_common_issues(&FixRules::default());
+    /// assert_eq!(fixes[0].path, "phones/tel0/pref");
+    /// assert_eq!(card.phones.unwrap()[&SmallString::from("tel0")].pref, Some(1));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a fixed value cannot be set, which cannot happen as the fixed values have the types of the original values.
+    pub fn fix_common_issues(&mut self, rules: &FixRules) -> Vec<FixApplied> {
+        let mut fixes = Vec::new();
+        for property in PROPERTIES {
+            let path = CardPath {
+                property: property.to_string(),
+                segments: Vec::new(),
+            };
+            if let Some(value) = get_value(self, &path) {
+                rules.collect(&path, &value, None, &mut fixes);
+            }
+        }
+        fixes.sort_by(|(_, a), (_, b)| a.path.cmp(&b.path));
+        set_values(
+            self,
+            fixes.iter().map(|(path, fix)| (path, fix.after.clone())),
+        )
+        .expect("Failed to set a fixed value");
+        fixes.into_iter().map(|(_, fix)| fix).collect()
+    }
+}
@@ -0,0 +1,248 @@
+//! Construction of a [`Card`] from the flat fields of a web form submission.
+//!
+//! The fields are named after the properties, the parts being separated by dots or slashes:
+//!
+//! | Field | Value |
+//! |---|---|
+//! | `name.<kind>`, e.g. `name.given`, `name.surname` | a component of the name, `name.full` for the full name |
+//! | `emails`, `emails.<context>`, e.g. `emails.work` | an email address |
+//! | `phones`, `phones.<context or feature>`, e.g. `phones.private`, `phones.mobile` | a phone number |
+//! | `address.<kind>`, e.g. `address.locality`, `address.postcode` | a component of the address, `address.full` and `address.countryCode` |
+//! | `organization` | the name of the organization |
+//! | `title` | the job title |
+//! | `birthday` | the birth date, `YYYY-MM-DD`, `YYYY-MM`, `--MM-DD` or `YYYY` (see [`crate::partial_date`]) |
+//!
+//! An invalid field is recorded with its error and the others are still set, to render the form again with the errors.
+//! The empty values are ignored, as the empty inputs of a form.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::syntax;
+use crate::{
+    AddressComponent, AddressComponentKind, Anniversary, Card, Context, DateObject, EmailAddress,
+    Name, NameComponent, NameComponentKind, Organization, PartialDate, Phone, PhoneFeature, Title,
+};
+
+/// The errors of the fields of a form, by field name.
+pub type FormErrors = BTreeMap<String, String>;
+
+/// A builder of [`Card`] from the fields of a form, collecting the errors by field, see the [module](crate::form) documentation.
+///
+/// ```rust
+/// use jscontact::CardFormBuilder;
+///
+/// let mut form = CardFormBuilder::new("urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af");
+/// form.set("name.given", "John");
+/// form.set("emails.work", "john.doe");
+/// let (card, errors) = form.build();
+/// assert_eq!(card.name.unwrap().to_full_string(), "John");
+/// assert_eq!(errors["emails.work"], "invalid email address 'john.doe'");
+/// ```
+#[derive(Debug, Clone)]
+pub struct CardFormBuilder {
+    /// The Card being built
+    card: Card,
+    /// The errors by field
+    errors: FormErrors,
+}
+
+/// Parse the name of an enum value, as serialized
+fn parse_enum<T: DeserializeOwned>(name: &str) -> Option<T> {
+    serde_json::from_value(Value::String(name.to_string())).ok()
+}
+
+/// Parse a registered context, the vendor-specific contexts are not fields of the form
+fn parse_context(name: &str) -> Option<Context> {
+    parse_enum(name).filter(|context| !matches!(context, Context::Other(_)))
+}
+
+/// Parse a registered phone feature, the vendor-specific features are not fields of the form
+fn parse_feature(name: &str) -> Option<PhoneFeature> {
+    parse_enum(name).filter(|feature| !matches!(feature, PhoneFeature::Other(_)))
+}
+
+/// Check an email address, see [`syntax::is_email_addr_spec`]
+fn check_email(address: &str) -> Result<(), String> {
+    if syntax::is_email_addr_spec(address) {
+        Ok(())
+    } else {
+        Err(format!("invalid email address '{}'", address))
+    }
+}
+
+/// Check a phone number: a `tel:` URI, or digits with the usual separators
+fn check_phone(number: &str) -> Result<(), String> {
+    let digits = number.strip_prefix("tel:").unwrap_or(number);
+    let allowed = |c: char| c.is_ascii_digit() || " +-().".contains(c);
+    if digits.chars().all(allowed) && digits.chars().any(|c| c.is_ascii_digit()) {
+        Ok(())
+    } else {
+        Err(format!("invalid phone number '{}'", number))
+    }
+}
+
+impl CardFormBuilder {
+    /// Creates a new builder of a Card with the latest version and the specified unique identifier.
+    pub fn new(uid: &str) -> Self {
+        Self {
+            card: Card::new_with_latest_version(uid),
+            errors: FormErrors::new(),
+        }
+    }
+
+    /// Sets a field of the form, an invalid field being recorded in the errors.
+    ///
+    /// Setting a field again replaces its error, and its value if the new one is valid.
+    pub fn set(&mut self, field: &str, value: &str) -> &mut Self {
+        let field = field.replace('/', ".");
+        let value = value.trim();
+        self.errors.remove(&field);
+        if value.is_empty() {
+            return self;
+        }
+        if let Err(error) = self.set_field(&field, value) {
+            self.errors.insert(field, error);
+        }
+        self
+    }
+
+    /// Sets a field, the field being normalized with dots
+    fn set_field(&mut self, field: &str, value: &str) -> Result<(), String> {
+        let (property, suffix) = match field.split_once('.') {
+            Some((property, suffix)) => (property, Some(suffix)),
+            None => (field, None),
+        };
+        match (property, suffix) {
+            ("name", Some(kind)) => self.set_name(kind, value),
+            ("emails", suffix) => self.set_email(suffix, value),
+            ("phones", suffix) => self.set_phone(suffix, value),
+            ("address", Some(kind)) => self.set_address(kind, value),
+            ("organization", None) => {
+                let organization = Organization {
+                    name: Some(value.to_string()),
+                    ..Default::default()
+                };
+                self.card
+                    .organizations
+                    .get_or_insert_with(HashMap::new)
+                    .insert("o1".into(), organization);
+                Ok(())
+            }
+            ("title", None) => {
+                self.card
+                    .titles
+                    .get_or_insert_with(HashMap::new)
+                    .insert("t1".into(), Title::new(value));
+                Ok(())
+            }
+            ("birthday", None) => {
+                let date = DateObject::PartialDate(
+                    PartialDate::from_iso_str(value).map_err(|error| error.to_string())?,
+                );
+                self.card
+                    .anniversaries
+                    .get_or_insert_with(HashMap::new)
+                    .insert("birthday".into(), Anniversary::birth(date));
+                Ok(())
+            }
+            _ => Err(format!("unknown field '{}'", field)),
+        }
+    }
+
+    /// Sets a component of the name, or its full name
+    fn set_name(&mut self, kind: &str, value: &str) -> Result<(), String> {
+        if kind == "full" {
+            let name = self.card.name.get_or_insert_with(Name::default);
+            name.full = Some(value.to_string());
+            return Ok(());
+        }
+        let kind: NameComponentKind = match parse_enum(kind) {
+            Some(NameComponentKind::Separator) | None => {
+                return Err(format!("unknown name component '{}'", kind))
+            }
+            Some(kind) => kind,
+        };
+        let name = self.card.name.get_or_insert_with(Name::default);
+        let components = name.components.get_or_insert_with(Vec::new);
+        components.retain(|component| component.kind != kind);
+        components.push(NameComponent::new(kind, value));
+        Ok(())
+    }
+
+    /// Sets an email address, the suffix being its context
+    fn set_email(&mut self, suffix: Option<&str>, value: &str) -> Result<(), String> {
+        let mut email = EmailAddress::new(value);
+        if let Some(suffix) = suffix {
+            let context = parse_context(suffix)
+                .ok_or_else(|| format!("unknown email context '{}'", suffix))?;
+            email.contexts = Some(HashMap::from([(context, true)]));
+        }
+        check_email(value)?;
+        self.card
+            .emails
+            .get_or_insert_with(HashMap::new)
+            .insert(suffix.unwrap_or("email").into(), email);
+        Ok(())
+    }
+
+    /// Sets a phone number, the suffix being its context or its feature
+    fn set_phone(&mut self, suffix: Option<&str>, value: &str) -> Result<(), String> {
+        let mut phone = Phone::new(value);
+        if let Some(suffix) = suffix {
+            if let Some(context) = parse_context(suffix) {
+                phone.contexts = Some(HashMap::from([(context, true)]));
+            } else if let Some(feature) = parse_feature(suffix) {
+                phone.features = Some(HashMap::from([(feature, true)]));
+            } else {
+                return Err(format!("unknown phone context or feature '{}'", suffix));
+            }
+        }
+        check_phone(value)?;
+        self.card
+            .phones
+            .get_or_insert_with(HashMap::new)
+            .insert(suffix.unwrap_or("phone").into(), phone);
+        Ok(())
+    }
+
+    /// Sets a component of the address, its full address or its country code
+    fn set_address(&mut self, kind: &str, value: &str) -> Result<(), String> {
+        let component_kind = match kind {
+            "full" | "countryCode" => None,
+            _ => match parse_enum(kind) {
+                Some(AddressComponentKind::Separator) | None => {
+                    return Err(format!("unknown address component '{}'", kind))
+                }
+                kind => kind,
+            },
+        };
+        if kind == "countryCode" && !syntax::is_country_code(value) {
+            return Err(format!("invalid country code '{}'", value));
+        }
+        let addresses = self.card.addresses.get_or_insert_with(HashMap::new);
+        let address = addresses.entry("a1".into()).or_default();
+        match component_kind {
+            Some(component_kind) => {
+                let components = address.components.get_or_insert_with(Vec::new);
+                components.retain(|component| component.kind != component_kind);
+                components.push(AddressComponent::new(component_kind, value));
+            }
+            None if kind == "full" => address.full = Some(value.to_string()),
+            None => address.country_code = Some(value.to_ascii_uppercase()),
+        }
+        Ok(())
+    }
+
+    /// Get the errors of the fields set so far.
+    pub fn errors(&self) -> &FormErrors {
+        &self.errors
+    }
+
+    /// Builds the Card from the valid fields, with the errors of the invalid fields.
+    pub fn build(self) -> (Card, FormErrors) {
+        (self.card, self.errors)
+    }
+}
@@ -0,0 +1,392 @@
+//! Formatting of the addresses and phone numbers for display, following the conventions of their country.
+//!
+//! The formatting is pluggable: [`Address::to_label_with`] takes any [`AddressFormatter`],
+//! and [`Phone::to_display_with`] any [`PhoneFormatter`].
+//!
+//! [`CountryAddressFormatter`] lays out the unordered addresses according to their `countryCode`
+//! (US, JP, DE, FR and GB layouts, the US layout being used for the other countries),
+//! while the ordered addresses keep the order and the separators of their components.
+//! [`DisplayOrderFormatter`] puts the components on one line in an order of kinds chosen by the caller,
+//! by default the [`AddressComponentKind::default_display_order`](crate::AddressComponentKind::default_display_order).
+//! With the `phonenumber` feature, [`PhoneNumberFormatter`] formats the phone numbers with the
+//! national format of their country, or the international format for the other countries.
+
+use crate::{display_position, Address, AddressComponent, AddressComponentKind as Kind, Phone};
+
+/// The context of the formatting, e.g. the country of the reader.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatContext {
+    /// The Alpha-2 country code of the reader: the country of an address in this country is omitted,
+    /// and the phone numbers of this country are formatted nationally.
+    pub country_code: Option<String>,
+    /// The separator of the lines of an address.
+    pub line_separator: String,
+}
+
+impl Default for FormatContext {
+    fn default() -> Self {
+        Self {
+            country_code: None,
+            line_separator: "\n".to_string(),
+        }
+    }
+}
+
+impl FormatContext {
+    /// Creates a FormatContext for a reader in a country.
+    pub fn with_country_code(country_code: &str) -> Self {
+        Self {
+            country_code: Some(country_code.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Check if a country is the country of the reader
+    fn is_local(&self, country_code: Option<&str>) -> bool {
+        match (&self.country_code, country_code) {
+            (Some(local), Some(country_code)) => local.eq_ignore_ascii_case(country_code),
+            _ => false,
+        }
+    }
+}
+
+/// Formats an address for display, e.g. the lines of a postal label.
+pub trait AddressFormatter {
+    /// Format the address.
+    fn format(&self, address: &Address, context: &FormatContext) -> String;
+}
+
+/// Formats a phone number for display.
+pub trait PhoneFormatter {
+    /// Format the phone number.
+    fn format(&self, phone: &Phone, context: &FormatContext) -> String;
+}
+
+/// A line of an address layout: the component kinds, with the separator put before a value if the line is not empty
+type Line = &'static [(Kind, &'static str)];
+
+/// The layout of the addresses of the United States, also used for the countries without layout
+const US: [Line; 6] = [
+    &[
+        (Kind::Number, ""),
+        (Kind::Direction, " "),
+        (Kind::Name, " "),
+    ],
+    &[
+        (Kind::Building, ""),
+        (Kind::Floor, ", "),
+        (Kind::Apartment, ", "),
+        (Kind::Room, ", "),
+        (Kind::Landmark, ", "),
+    ],
+    &[
+        (Kind::Block, ""),
+        (Kind::Subdistrict, ", "),
+        (Kind::District, ", "),
+    ],
+    &[(Kind::PostOfficeBox, "")],
+    &[
+        (Kind::Locality, ""),
+        (Kind::Region, ", "),
+        (Kind::Postcode, " "),
+    ],
+    &[(Kind::Country, "")],
+];
+
+/// The layout of the addresses of Japan, from the largest to the smallest area
+const JP: [Line; 5] = [
+    &[(Kind::Postcode, "")],
+    &[
+        (Kind::Region, ""),
+        (Kind::Locality, ""),
+        (Kind::District, ""),
+        (Kind::Subdistrict, ""),
+        (Kind::Direction, ""),
+        (Kind::Name, ""),
+        (Kind::Block, ""),
+        (Kind::Number, ""),
+    ],
+    &[
+        (Kind::Building, ""),
+        (Kind::Floor, " "),
+        (Kind::Apartment, " "),
+        (Kind::Room, " "),
+        (Kind::Landmark, " "),
+    ],
+    &[(Kind::PostOfficeBox, "")],
+    &[(Kind::Country, "")],
+];
+
+/// The layout of the addresses of Germany
+const DE: [Line; 6] = [
+    &[
+        (Kind::Direction, ""),
+        (Kind::Name, " "),
+        (Kind::Number, " "),
+    ],
+    &[
+        (Kind::Building, ""),
+        (Kind::Floor, ", "),
+        (Kind::Apartment, ", "),
+        (Kind::Room, ", "),
+        (Kind::Landmark, ", "),
+    ],
+    &[
+        (Kind::Block, ""),
+        (Kind::Subdistrict, ", "),
+        (Kind::District, ", "),
+    ],
+    &[(Kind::PostOfficeBox, "")],
+    &[
+        (Kind::Postcode, ""),
+        (Kind::Locality, " "),
+        (Kind::Region, ", "),
+    ],
+    &[(Kind::Country, "")],
+];
+
+/// The layout of the addresses of France, the building before the street
+const FR: [Line; 6] = [
+    &[
+        (Kind::Room, ""),
+        (Kind::Apartment, ", "),
+        (Kind::Floor, ", "),
+        (Kind::Building, ", "),
+        (Kind::Landmark, ", "),
+    ],
+    &[
+        (Kind::Number, ""),
+        (Kind::Direction, " "),
+        (Kind::Name, " "),
+    ],
+    &[
+        (Kind::Block, ""),
+        (Kind::Subdistrict, ", "),
+        (Kind::District, ", "),
+    ],
+    &[(Kind::PostOfficeBox, "")],
+    &[
+        (Kind::Postcode, ""),
+        (Kind::Locality, " "),
+        (Kind::Region, ", "),
+    ],
+    &[(Kind::Country, "")],
+];
+
+/// The layout of the addresses of the United Kingdom, the postcode on its own line
+const GB: [Line; 8] = [
+    &[
+        (Kind::Room, ""),
+        (Kind::Apartment, ", "),
+        (Kind::Floor, ", "),
+        (Kind::Building, ", "),
+        (Kind::Landmark, ", "),
+    ],
+    &[
+        (Kind::Number, ""),
+        (Kind::Direction, " "),
+        (Kind::Name, " "),
+    ],
+    &[
+        (Kind::Block, ""),
+        (Kind::Subdistrict, ", "),
+        (Kind::District, ", "),
+    ],
+    &[(Kind::PostOfficeBox, "")],
+    &[(Kind::Locality, "")],
+    &[(Kind::Region, "")],
+    &[(Kind::Postcode, "")],
+    &[(Kind::Country, "")],
+];
+
+/// Get the layout of the addresses of a country, the US layout if the country has none
+fn layout(country_code: Option<&str>) -> &'static [Line] {
+    match country_code.map(str::to_ascii_uppercase).as_deref() {
+        Some("JP") => &JP,
+        Some("DE") => &DE,
+        Some("FR") => &FR,
+        Some("GB") => &GB,
+        _ => &US,
+    }
+}
+
+/// Formats the addresses with the layout of their country, see the [`crate::format`] module.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CountryAddressFormatter;
+
+impl CountryAddressFormatter {
+    /// Format an ordered address on one line, with its separators
+    fn format_ordered(address: &Address, context: &FormatContext) -> String {
+        let default_separator = address.default_separator.as_deref().unwrap_or(" ");
+        let local = context.is_local(address.country_code.as_deref());
+        let mut formatted = String::new();
+        let mut pending_separator: Option<String> = None;
+        for component in address.components.iter().flatten() {
+            match component.kind {
+                Kind::Separator => {
+                    if !formatted.is_empty() {
+                        pending_separator
+                            .get_or_insert_with(String::new)
+                            .push_str(&component.value);
+                    }
+                    continue;
+                }
+                Kind::Country if local => continue,
+                _ => {}
+            }
+            if !formatted.is_empty() {
+                match pending_separator.take() {
+                    Some(separator) => formatted.push_str(&separator),
+                    None => formatted.push_str(default_separator),
+                }
+            }
+            formatted.push_str(&component.value);
+        }
+        formatted
+    }
+
+    /// Format an unordered address with the layout of its country
+    fn format_layout(address: &Address, context: &FormatContext) -> String {
+        let local = context.is_local(address.country_code.as_deref());
+        let mut lines = Vec::new();
+        for line in layout(address.country_code.as_deref()) {
+            let mut formatted = String::new();
+            for (kind, separator) in line.iter() {
+                if *kind == Kind::Country && local {
+                    continue;
+                }
+                let values: Vec<&str> = address
+                    .components_of_kind(kind.clone())
+                    .map(|component| component.value.as_str())
+                    .collect();
+                if values.is_empty() {
+                    continue;
+                }
+                if !formatted.is_empty() {
+                    formatted.push_str(separator);
+                }
+                formatted.push_str(&values.join(" "));
+            }
+            if !formatted.is_empty() {
+                lines.push(formatted);
+            }
+        }
+        lines.join(&context.line_separator)
+    }
+}
+
+impl AddressFormatter for CountryAddressFormatter {
+    fn format(&self, address: &Address, context: &FormatContext) -> String {
+        match &address.components {
+            Some(components) if !components.is_empty() => {}
+            _ => return address.full.clone().unwrap_or_default(),
+        }
+        if address.is_ordered == Some(true) {
+            Self::format_ordered(address, context)
+        } else {
+            Self::format_layout(address, context)
+        }
+    }
+}
+
+/// Formats the addresses on one line, the components of the addresses that are not ordered
+/// being in an order of kinds and separated by a space.
+///
+/// The ordered addresses keep the order and the separators of their components, as with [`CountryAddressFormatter`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayOrderFormatter {
+    /// The order of the kinds, the kinds missing from it coming last.
+    pub order: Vec<Kind>,
+}
+
+impl Default for DisplayOrderFormatter {
+    fn default() -> Self {
+        Self {
+            order: Kind::default_display_order().to_vec(),
+        }
+    }
+}
+
+impl AddressFormatter for DisplayOrderFormatter {
+    fn format(&self, address: &Address, context: &FormatContext) -> String {
+        let components = match &address.components {
+            Some(components) if !components.is_empty() => components,
+            _ => return address.full.clone().unwrap_or_default(),
+        };
+        if address.is_ordered == Some(true) {
+            return CountryAddressFormatter::format_ordered(address, context);
+        }
+        let local = context.is_local(address.country_code.as_deref());
+        let mut sorted: Vec<&AddressComponent> = components
+            .iter()
+            .filter(|component| match component.kind {
+                Kind::Separator => false,
+                Kind::Country => !local,
+                _ => true,
+            })
+            .collect();
+        sorted.sort_by_key(|component| display_position(&self.order, &component.kind));
+        sorted
+            .iter()
+            .map(|component| component.value.as_str())
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+}
+
+impl Address {
+    /// Format the address with the layout of its country, see [`CountryAddressFormatter`].
+    pub fn to_label(&self) -> String {
+        self.to_label_with(&CountryAddressFormatter, &FormatContext::default())
+    }
+
+    /// Format the address with a custom formatter.
+    pub fn to_label_with(
+        &self,
+        formatter: &impl AddressFormatter,
+        context: &FormatContext,
+    ) -> String {
+        formatter.format(self, context)
+    }
+}
+
+impl Phone {
+    /// Format the phone number with a custom formatter.
+    pub fn to_display_with(
+        &self,
+        formatter: &impl PhoneFormatter,
+        context: &FormatContext,
+    ) -> String {
+        formatter.format(self, context)
+    }
+}
+
+/// Formats the phone numbers with the conventions of their country (e.g. `(555) 555-5555` or `+1 555-555-5555`).
+///
+/// The numbers that cannot be parsed are displayed without their `tel:` scheme.
+#[cfg(feature = "phonenumber")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhoneNumberFormatter;
+
+#[cfg(feature = "phonenumber")]
+impl PhoneFormatter for PhoneNumberFormatter {
+    fn format(&self, phone: &Phone, context: &FormatContext) -> String {
+        use phonenumber::{country, Mode};
+
+        let number = phone.number.strip_prefix("tel:").unwrap_or(&phone.number);
+        // the parameters of a tel URI, e.g. `;ext=123`
+        let number = number.split(';').next().unwrap_or_default();
+        let local: Option<country::Id> = context
+            .country_code
+            .as_deref()
+            .and_then(|country_code| country_code.to_ascii_uppercase().parse().ok());
+        let Ok(parsed) = phonenumber::parse(local, number) else {
+            return number.to_string();
+        };
+        let mode = match (local, parsed.country().id()) {
+            (Some(local), Some(id)) if local == id => Mode::National,
+            _ => Mode::International,
+        };
+        parsed.format().mode(mode).to_string()
+    }
+}
@@ -0,0 +1,160 @@
+//! A microformats2 h-card (<https://microformats.org/wiki/h-card>) of a [`Card`], to embed the contact in a web page.
+//!
+//! The snippet only holds the preferred values, each in an element with its h-card class (e.g. `p-name`).
+//! The values are HTML-escaped, the page embedding the snippet does not need to escape it again.
+
+use crate::Card;
+
+/// The properties included by [`Card::to_h_card_with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HCardOptions {
+    /// The full name, as `p-name`.
+    pub name: bool,
+    /// The preferred email address, as `u-email`.
+    pub email: bool,
+    /// The preferred phone number, as `p-tel`.
+    pub tel: bool,
+    /// The name of the primary organization, as `p-org`.
+    pub org: bool,
+    /// The preferred photo, as `u-photo`.
+    pub photo: bool,
+    /// The preferred address on one line, as `p-adr`.
+    pub adr: bool,
+    /// The first note, as `p-note`.
+    pub note: bool,
+}
+
+impl Default for HCardOptions {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl HCardOptions {
+    /// All the properties included, the default.
+    pub fn all() -> Self {
+        Self {
+            name: true,
+            email: true,
+            tel: true,
+            org: true,
+            photo: true,
+            adr: true,
+            note: true,
+        }
+    }
+
+    /// No property included, to include some properties one by one.
+    pub fn none() -> Self {
+        Self {
+            name: false,
+            email: false,
+            tel: false,
+            org: false,
+            photo: false,
+            adr: false,
+            note: false,
+        }
+    }
+}
+
+/// Escape the special characters of HTML, in a text or an attribute value
+fn html_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl Card {
+    /// Build the h-card of the Card with all the properties, see [`Card::to_h_card_with`].
+    pub fn to_h_card(&self) -> String {
+        self.to_h_card_with(&HCardOptions::default())
+    }
+
+    /// Build the h-card of the Card, one element per line in a `<div class="h-card">`.
+    ///
+    /// The missing values are left out. The email and the photo are links (`<a>` and `<img>`),
+    /// the other values are `<span>` elements.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, HCardOptions, Name};
+    ///
+    /// let mut card = Card::new_with_latest_version("1234");
+    /// let mut name = Name::default();
+    /// name.full = Some("Tom & Jerry".to_string());
+    /// card.name = Some(name);
+    /// let mut opts = HCardOptions::none();
+    /// opts.name = true;
+    /// assert_eq!(
+    ///     card.to_h_card_with(&opts),
+    ///     "<div class=\"h-card\">\n<span class=\"p-name\">Tom &amp; Jerry</span>\n</div>"
+    /// );
+    /// ```
+    pub fn to_h_card_with(&self, opts: &HCardOptions) -> String {
+        let mut lines = vec![r#"<div class="h-card">"#.to_string()];
+        let name = self
+            .name
+            .as_ref()
+            .map(|name| name.to_full_string())
+            .filter(|name| !name.is_empty());
+        if let (true, Some(name)) = (opts.name, &name) {
+            lines.push(format!(
+                r#"<span class="p-name">{}</span>"#,
+                html_escape(name)
+            ));
+        }
+        if let (true, Some(address)) = (opts.email, self.preferred_email_address()) {
+            let address = html_escape(address);
+            lines.push(format!(
+                r#"<a class="u-email" href="mailto:{}">{}</a>"#,
+                address, address
+            ));
+        }
+        if let (true, Some(number)) = (opts.tel, self.preferred_phone_number()) {
+            let number = number.strip_prefix("tel:").unwrap_or(number);
+            lines.push(format!(
+                r#"<span class="p-tel">{}</span>"#,
+                html_escape(number)
+            ));
+        }
+        let org = self
+            .primary_organization()
+            .and_then(|organization| organization.name.as_deref());
+        if let (true, Some(org)) = (opts.org, org) {
+            lines.push(format!(
+                r#"<span class="p-org">{}</span>"#,
+                html_escape(org)
+            ));
+        }
+        if let (true, Some(photo)) = (opts.photo, self.photo_preferring(&["image/*"])) {
+            lines.push(format!(
+                r#"<img class="u-photo" src="{}" alt="{}">"#,
+                html_escape(&photo.uri),
+                html_escape(name.as_deref().unwrap_or_default())
+            ));
+        }
+        if let (true, Some(label)) = (opts.adr, self.preferred_address_label()) {
+            lines.push(format!(
+                r#"<span class="p-adr">{}</span>"#,
+                html_escape(&label)
+            ));
+        }
+        if let (true, Some(note)) = (opts.note, self.first_note()) {
+            lines.push(format!(
+                r#"<span class="p-note">{}</span>"#,
+                html_escape(note)
+            ));
+        }
+        lines.push("</div>".to_string());
+        lines.join("\n")
+    }
+}
@@ -0,0 +1,297 @@
+//! Loading of many Cards (a directory of JSON files or an NDJSON stream) with an aggregate quality report.
+//!
+//! Each Card is deserialized leniently (see [`Card::from_value_collecting`]) then validated (see [`Card::validate`]),
+//! the report giving where each problem comes from (file and line).
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::File;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::limit::{check_size, read_to_string_limited, ReadError, DEFAULT_MAX_BYTES};
+use crate::{Card, CardSet};
+
+/// The number of unknown keys listed by [`ImportReport::summary`].
+const SUMMARY_UNKNOWN_KEYS: usize = 5;
+
+/// Read a file of at most a number of bytes
+fn read_file(file: &Path, max_bytes: usize) -> Result<String, ReadError> {
+    let file = File::open(file).map_err(ReadError::Io)?;
+    read_to_string_limited(file, max_bytes)
+}
+
+/// The Cards to load with [`CardSet::load_with_report`].
+#[derive(Debug, Clone, Copy)]
+pub enum ImportSource<'a> {
+    /// A directory of `.json` files, each holding a Card or an array of Cards, loaded in the order of the file names.
+    Directory(&'a Path),
+    /// A file with a Card per line (NDJSON), the empty lines being skipped.
+    NdjsonFile(&'a Path),
+    /// Text with a Card per line (NDJSON), the empty lines being skipped.
+    Ndjson(&'a str),
+}
+
+/// A problem found while loading a Card, with where the Card comes from.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ParseWarning {
+    /// The file of the Card, `None` for NDJSON text.
+    pub file: Option<String>,
+    /// The line of the Card in an NDJSON source, starting at 1.
+    pub line: Option<usize>,
+    /// The path of the problem in the Card (e.g. `phones/p1`), empty for the whole Card.
+    ///
+    /// For a file holding an array, the path starts with the index of the Card (e.g. `2/phones/p1`).
+    pub path: String,
+    /// The description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.file, self.line) {
+            (Some(file), Some(line)) => write!(f, "{}:{}", file, line)?,
+            (Some(file), None) => write!(f, "{}", file)?,
+            (None, Some(line)) => write!(f, "line {}", line)?,
+            (None, None) => write!(f, "input")?,
+        }
+        if !self.path.is_empty() {
+            write!(f, ": {}", self.path)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// The aggregate report of [`CardSet::load_with_report`].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ImportReport {
+    /// The number of loaded Cards.
+    pub cards: usize,
+    /// The number of loaded Cards with a parse warning or a validation violation.
+    pub cards_with_warnings: usize,
+    /// The Cards that could not be loaded, e.g. invalid JSON or a missing `uid`.
+    pub rejected: Vec<ParseWarning>,
+    /// The skipped properties and entries of the loaded Cards.
+    pub warnings: Vec<ParseWarning>,
+    /// The validation violations of the loaded Cards, the path of the warning being the path of the violation.
+    pub violations: Vec<ParseWarning>,
+    /// The number of Cards with each unknown top-level property (e.g. `example.com:foo`).
+    pub unknown_keys: BTreeMap<String, usize>,
+    /// The number of validation violations by code (e.g. `pref-out-of-range`).
+    pub validation_codes: BTreeMap<String, usize>,
+}
+
+impl ImportReport {
+    /// Get the most frequent unknown keys with their number of Cards, the most frequent first then by name.
+    pub fn top_unknown_keys(&self, count: usize) -> Vec<(&str, usize)> {
+        let mut keys: Vec<(&str, usize)> = self
+            .unknown_keys
+            .iter()
+            .map(|(key, cards)| (key.as_str(), *cards))
+            .collect();
+        keys.sort_by(|(a_key, a_cards), (b_key, b_cards)| {
+            b_cards.cmp(a_cards).then(a_key.cmp(b_key))
+        });
+        keys.truncate(count);
+        keys
+    }
+
+    /// Get a human-readable summary of the report, one fact per line.
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} cards loaded, {} with warnings, {} rejected\n",
+            self.cards,
+            self.cards_with_warnings,
+            self.rejected.len()
+        );
+        for rejected in &self.rejected {
+            summary.push_str(&format!("  rejected {}\n", rejected));
+        }
+        if !self.unknown_keys.is_empty() {
+            summary.push_str("unknown keys:\n");
+            for (key, cards) in self.top_unknown_keys(SUMMARY_UNKNOWN_KEYS) {
+                summary.push_str(&format!("  {}: {} cards\n", key, cards));
+            }
+        }
+        if !self.validation_codes.is_empty() {
+            summary.push_str("validation:\n");
+            for (code, count) in &self.validation_codes {
+                summary.push_str(&format!("  {}: {}\n", code, count));
+            }
+        }
+        summary
+    }
+
+    /// Load a Card value, recording its problems
+    fn load(&mut self, cards: &mut Vec<Card>, value: Value, at: &ParseWarning) {
+        let warning = |path: &str, message: &str| {
+            let path = match (at.path.as_str(), path) {
+                ("", path) => path.to_string(),
+                (prefix, "") => prefix.to_string(),
+                (prefix, path) => format!("{}/{}", prefix, path),
+            };
+            ParseWarning {
+                path,
+                message: message.to_string(),
+                ..at.clone()
+            }
+        };
+        let (card, errors) = Card::from_value_collecting(value);
+        let Some(card) = card else {
+            let message = errors
+                .iter()
+                .map(|error| error.message.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            self.rejected.push(warning("", &message));
+            return;
+        };
+        let report = card.validate();
+        if !errors.is_empty() || !report.errors.is_empty() {
+            self.cards_with_warnings += 1;
+        }
+        self.warnings.extend(
+            errors
+                .iter()
+                .map(|error| warning(&error.path, &error.message)),
+        );
+        for error in &report.errors {
+            let code = serde_json::to_value(error.code)
+                .ok()
+                .and_then(|code| code.as_str().map(str::to_string))
+                .unwrap_or_default();
+            *self.validation_codes.entry(code).or_default() += 1;
+            self.violations.push(warning(&error.path, &error.message));
+        }
+        for key in card.extensions.keys() {
+            *self.unknown_keys.entry(key.clone()).or_default() += 1;
+        }
+        self.cards += 1;
+        cards.push(card);
+    }
+
+    /// Load the Cards of NDJSON text
+    fn load_ndjson(&mut self, cards: &mut Vec<Card>, text: &str, file: Option<String>) {
+        for (idx, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let at = ParseWarning {
+                file: file.clone(),
+                line: Some(idx + 1),
+                path: String::new(),
+                message: String::new(),
+            };
+            match serde_json::from_str(line) {
+                Ok(value) => self.load(cards, value, &at),
+                Err(error) => self.rejected.push(ParseWarning {
+                    message: format!("Invalid JSON: {}", error),
+                    ..at
+                }),
+            }
+        }
+    }
+
+    /// Load the Cards of the `.json` files of a directory
+    fn load_directory(&mut self, cards: &mut Vec<Card>, directory: &Path, max_bytes: usize) {
+        let rejected = |file: &Path, message: String| ParseWarning {
+            file: Some(file.display().to_string()),
+            line: None,
+            path: String::new(),
+            message,
+        };
+        let entries = match std::fs::read_dir(directory) {
+            Ok(entries) => entries,
+            Err(error) => {
+                self.rejected.push(rejected(directory, error.to_string()));
+                return;
+            }
+        };
+        let mut files: Vec<_> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        files.sort();
+        for file in files {
+            let value = read_file(&file, max_bytes)
+                .map_err(|error| error.to_string())
+                .and_then(|text| {
+                    serde_json::from_str(&text).map_err(|error| format!("Invalid JSON: {}", error))
+                });
+            let at = rejected(&file, String::new());
+            match value {
+                Ok(Value::Array(values)) => {
+                    for (idx, value) in values.into_iter().enumerate() {
+                        let at = ParseWarning {
+                            path: idx.to_string(),
+                            ..at.clone()
+                        };
+                        self.load(cards, value, &at);
+                    }
+                }
+                Ok(value) => self.load(cards, value, &at),
+                Err(message) => self.rejected.push(rejected(&file, message)),
+            }
+        }
+    }
+}
+
+impl CardSet {
+    /// Load the Cards of a directory or of an NDJSON stream, with a report of the problems.
+    ///
+    /// The Cards are loaded leniently: an invalid property is skipped with a warning,
+    /// a Card that cannot be built (e.g. without `uid`) is rejected. An unreadable file is rejected too.
+    ///
+    /// ```rust
+    /// use jscontact::{CardSet, ImportSource};
+    ///
+    /// let ndjson = r#"{"@type":"Card","version":"1.0","uid":"1","example.com:foo":1}
+    /// {"@type":"Card","version":"1.0"}"#;
+    /// let (cards, report) = CardSet::load_with_report(ImportSource::Ndjson(ndjson));
+    /// assert_eq!(cards.0.len(), 1);
+    /// assert_eq!(report.rejected[0].line, Some(2));
+    /// assert_eq!(report.unknown_keys["example.com:foo"], 1);
+    /// ```
+    ///
+    /// Each file and the NDJSON text are limited to [`DEFAULT_MAX_BYTES`], see [`CardSet::load_with_report_limited`].
+    pub fn load_with_report(source: ImportSource) -> (CardSet, ImportReport) {
+        CardSet::load_with_report_limited(source, DEFAULT_MAX_BYTES)
+    }
+
+    /// Load the Cards of a directory or of an NDJSON stream with a report, see [`CardSet::load_with_report`],
+    /// a file or an NDJSON text larger than `max_bytes` bytes being rejected without reading it whole.
+    pub fn load_with_report_limited(
+        source: ImportSource,
+        max_bytes: usize,
+    ) -> (CardSet, ImportReport) {
+        let mut report = ImportReport::default();
+        let mut cards = Vec::new();
+        match source {
+            ImportSource::Directory(directory) => {
+                report.load_directory(&mut cards, directory, max_bytes)
+            }
+            ImportSource::NdjsonFile(file) => match read_file(file, max_bytes) {
+                Ok(text) => report.load_ndjson(&mut cards, &text, Some(file.display().to_string())),
+                Err(error) => report.rejected.push(ParseWarning {
+                    file: Some(file.display().to_string()),
+                    line: None,
+                    path: String::new(),
+                    message: error.to_string(),
+                }),
+            },
+            ImportSource::Ndjson(text) => match check_size(text, max_bytes) {
+                Ok(()) => report.load_ndjson(&mut cards, text, None),
+                Err(error) => report.rejected.push(ParseWarning {
+                    file: None,
+                    line: None,
+                    path: String::new(),
+                    message: error.to_string(),
+                }),
+            },
+        }
+        (CardSet(cards), report)
+    }
+}
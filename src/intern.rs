@@ -0,0 +1,159 @@
+//! Interning of the values repeated across a batch of Cards, see [`CardSeed`] and [`crate::CardSet::from_reader_interned`].
+//!
+//! The Cards are the same as without interning, only their memory differs: a [`SharedString`] value (the uri of
+//! the media) equal to a value parsed before with the same [`Interner`] is not allocated again, it shares the
+//! allocation of the first one (e.g. the logo of a company on the Cards of its employees).
+//!
+//! The values are interned while they are parsed, so the repeated values make no allocation at all.
+//! The other values cannot be shared: the registered values of the enums (e.g. the `work` context) are stored without
+//! allocation, and the map keys (`e1`, `tel0`, ...) are stored inline with the `compact` feature.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::de::{self, DeserializeSeed, Deserializer, Visitor};
+use serde::Deserialize;
+
+use crate::{Card, SharedString};
+
+thread_local! {
+    /// The values of the interner of the Card being deserialized by a [`CardSeed`] on this thread
+    static ACTIVE: RefCell<Option<HashSet<SharedString>>> = const { RefCell::new(None) };
+}
+
+/// The values shared between the Cards of a batch, see the [module](crate::intern) documentation.
+#[derive(Debug, Default)]
+pub struct Interner {
+    /// The interned values
+    values: HashSet<SharedString>,
+}
+
+impl Interner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the interned value equal to a value, the value itself if it is the first one.
+    pub fn intern(&mut self, value: SharedString) -> SharedString {
+        if let Some(interned) = self.values.get(&*value) {
+            return interned.clone();
+        }
+        self.values.insert(value.clone());
+        value
+    }
+
+    /// Get the number of interned values.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Check if no value is interned.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Get the value of the active interner equal to a text, allocated and interned if it is the first one
+fn intern_str(text: &str) -> SharedString {
+    ACTIVE.with_borrow_mut(|active| match active {
+        Some(values) => match values.get(text) {
+            Some(interned) => interned.clone(),
+            None => {
+                let value = SharedString::from(text);
+                values.insert(value.clone());
+                value
+            }
+        },
+        None => SharedString::from(text),
+    })
+}
+
+/// Visits a [`SharedString`], interning it when a [`CardSeed`] is active
+struct SharedStringVisitor;
+
+impl Visitor<'_> for SharedStringVisitor {
+    type Value = SharedString;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<SharedString, E> {
+        Ok(intern_str(text))
+    }
+}
+
+/// Deserialize a [`SharedString`] with the values of the active interner, see [`CardSeed`]
+pub(crate) fn deserialize_shared<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<SharedString, D::Error> {
+    deserializer.deserialize_str(SharedStringVisitor)
+}
+
+/// Makes the values of an interner the active ones, until dropped (the deserialization may fail or panic)
+struct ActiveGuard<'a> {
+    /// The interner receiving back its values
+    interner: &'a mut Interner,
+    /// The values active before, those of an enclosing seed
+    previous: Option<HashSet<SharedString>>,
+}
+
+impl<'a> ActiveGuard<'a> {
+    /// Make the values of an interner the active ones
+    fn new(interner: &'a mut Interner) -> Self {
+        let values = std::mem::take(&mut interner.values);
+        let previous = ACTIVE.with_borrow_mut(|active| active.replace(values));
+        Self { interner, previous }
+    }
+}
+
+impl Drop for ActiveGuard<'_> {
+    fn drop(&mut self) {
+        let values =
+            ACTIVE.with_borrow_mut(|active| std::mem::replace(active, self.previous.take()));
+        self.interner.values = values.unwrap_or_default();
+    }
+}
+
+/// The seed deserializing a Card with the values of an [`Interner`].
+///
+/// ```rust
+/// use jscontact::{CardSeed, Interner};
+/// use serde::de::DeserializeSeed;
+///
+/// let mut interner = Interner::new();
+/// let json = r#"{"@type":"Card","version":"1.0","uid":"1234"}"#;
+/// let mut deserializer = serde_json::Deserializer::from_str(json);
+/// let card = CardSeed::new(&mut interner).deserialize(&mut deserializer).unwrap();
+/// assert_eq!(card.uid, "1234");
+/// ```
+pub struct CardSeed<'a> {
+    /// The interner shared by the Cards of the batch
+    interner: &'a mut Interner,
+}
+
+impl<'a> CardSeed<'a> {
+    /// Creates a seed deserializing a Card with the values of an interner.
+    pub fn new(interner: &'a mut Interner) -> Self {
+        Self { interner }
+    }
+}
+
+impl fmt::Debug for CardSeed<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CardSeed")
+            .field("interned", &self.interner.len())
+            .finish()
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for CardSeed<'_> {
+    type Value = Card;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Card, D::Error> {
+        let _active = ActiveGuard::new(self.interner);
+        <Card as Deserialize>::deserialize(deserializer)
+    }
+}
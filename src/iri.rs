@@ -0,0 +1,208 @@
+//! Conversions between IRIs (RFC 3987) and URIs (RFC 3986).
+//!
+//! The uri of the resources may be an IRI, e.g. `https://例え.jp/プロフィール`: it is kept as written in the Card,
+//! and converted to a URI by the exporters to the formats only allowing ASCII (vCard, MeCard).
+//!
+//! The non-ASCII characters are percent-encoded as UTF-8, except in the host with the `idna` feature, converted to
+//! punycode (e.g. `xn--r8jz45g.jp`). The ASCII characters are kept as written, the percent-encoded ones included.
+
+use crate::resource::uri_scheme;
+
+/// Convert an IRI to a URI (RFC 3987, section 3.1), a URI being returned unchanged.
+///
+/// ```rust
+/// use jscontact::iri_to_uri;
+///
+/// assert_eq!(
+///     iri_to_uri("https://example.com/プロフィール?q=😀"),
+///     "https://example.com/%E3%83%97%E3%83%AD%E3%83%95%E3%82%A3%E3%83%BC%E3%83%AB?q=%F0%9F%98%80"
+/// );
+/// assert_eq!(iri_to_uri("mailto:jane@example.com"), "mailto:jane@example.com");
+/// ```
+pub fn iri_to_uri(iri: &str) -> String {
+    if iri.is_ascii() {
+        return iri.to_string();
+    }
+    match host_range(iri) {
+        Some((start, end)) => format!(
+            "{}{}{}",
+            percent_encode(&iri[..start]),
+            host_to_uri(&iri[start..end]),
+            percent_encode(&iri[end..])
+        ),
+        None => percent_encode(iri),
+    }
+}
+
+/// Convert a URI to an IRI (RFC 3987, section 3.2), decoding the percent-encoded UTF-8 characters that are not ASCII.
+///
+/// The percent-encoded ASCII characters (e.g. `%2F`), the invalid UTF-8 sequences, and the characters not allowed
+/// in an IRI (controls, spaces and bidirectional formatting) stay percent-encoded.
+///
+/// ```rust
+/// use jscontact::uri_to_iri;
+///
+/// assert_eq!(
+///     uri_to_iri("https://example.com/%E3%83%97%E3%83%AD%E3%83%95%E3%82%A3%E3%83%BC%E3%83%AB%2F"),
+///     "https://example.com/プロフィール%2F"
+/// );
+/// ```
+pub fn uri_to_iri(uri: &str) -> String {
+    match host_range(uri) {
+        Some((start, end)) => format!(
+            "{}{}{}",
+            percent_decode(&uri[..start]),
+            host_to_iri(&uri[start..end]),
+            percent_decode(&uri[end..])
+        ),
+        None => percent_decode(uri),
+    }
+}
+
+/// Get the range of the host of a URI with an authority (e.g. `https://host:443/`), `None` without authority
+fn host_range(uri: &str) -> Option<(usize, usize)> {
+    let scheme = uri_scheme(uri)?;
+    let authority_start = scheme.len() + 3;
+    if uri.get(scheme.len() + 1..authority_start) != Some("//") {
+        return None;
+    }
+    let authority_end = uri[authority_start..]
+        .find(['/', '?', '#'])
+        .map_or(uri.len(), |idx| idx + authority_start);
+    let authority = &uri[authority_start..authority_end];
+    let start = authority.rfind('@').map_or(0, |idx| idx + 1);
+    let hostport = &authority[start..];
+    let end = if hostport.starts_with('[') {
+        // an IP literal, e.g. [2001:db8::1]:8080
+        hostport.find(']').map_or(hostport.len(), |idx| idx + 1)
+    } else {
+        match hostport.rfind(':') {
+            Some(idx) if hostport[idx + 1..].bytes().all(|b| b.is_ascii_digit()) => idx,
+            _ => hostport.len(),
+        }
+    };
+    Some((authority_start + start, authority_start + start + end))
+}
+
+/// Convert the host of an IRI to punycode, percent-encoding it if it is not a valid domain name
+#[cfg(feature = "idna")]
+fn host_to_uri(host: &str) -> String {
+    if host.is_ascii() {
+        return host.to_string();
+    }
+    idna::domain_to_ascii(host).unwrap_or_else(|_| percent_encode(host))
+}
+
+/// Percent-encode the host of an IRI
+#[cfg(not(feature = "idna"))]
+fn host_to_uri(host: &str) -> String {
+    percent_encode(host)
+}
+
+/// Convert the punycode labels of the host of a URI to Unicode, then decode its percent-encoded characters
+#[cfg(feature = "idna")]
+fn host_to_iri(host: &str) -> String {
+    let has_ace_label = host.split('.').any(|label| {
+        label
+            .get(..4)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("xn--"))
+    });
+    if has_ace_label {
+        let (unicode, result) = idna::domain_to_unicode(host);
+        if result.is_ok() {
+            return unicode;
+        }
+    }
+    percent_decode(host)
+}
+
+/// Decode the percent-encoded characters of the host of a URI
+#[cfg(not(feature = "idna"))]
+fn host_to_iri(host: &str) -> String {
+    percent_decode(host)
+}
+
+/// Percent-encode the UTF-8 bytes of the characters that are not ASCII
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii() {
+            encoded.push(c);
+        } else {
+            let mut buffer = [0; 4];
+            for b in c.encode_utf8(&mut buffer).bytes() {
+                encoded.push_str(&format!("%{:02X}", b));
+            }
+        }
+    }
+    encoded
+}
+
+/// Check if a decoded character is allowed in an IRI (RFC 3987, sections 2.2 and 4.1)
+fn is_iri_char(c: char) -> bool {
+    !c.is_ascii()
+        && !c.is_control()
+        && !c.is_whitespace()
+        && !matches!(
+            c,
+            '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{FEFF}'
+        )
+}
+
+/// Get the byte percent-encoded at the start of a text (e.g. `%E3`)
+fn escaped_byte(text: &[u8]) -> Option<u8> {
+    match text {
+        [b'%', high, low, ..] if high.is_ascii_hexdigit() && low.is_ascii_hexdigit() => {
+            let hex = |b: u8| (b as char).to_digit(16).unwrap_or_default() as u8;
+            Some(hex(*high) << 4 | hex(*low))
+        }
+        _ => None,
+    }
+}
+
+/// Decode the percent-encoded UTF-8 sequences of characters allowed in an IRI, keeping the others as written
+fn percent_decode(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(idx) = rest.find('%') {
+        decoded.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+        // the run of percent-encoded bytes, 3 characters each
+        let mut bytes = Vec::new();
+        while let Some(b) = escaped_byte(&rest.as_bytes()[3 * bytes.len()..]) {
+            bytes.push(b);
+        }
+        if bytes.is_empty() {
+            decoded.push('%');
+            rest = &rest[1..];
+            continue;
+        }
+        let mut i = 0;
+        while i < bytes.len() {
+            let len = match bytes[i] {
+                0xC2..=0xDF => 2,
+                0xE0..=0xEF => 3,
+                0xF0..=0xF4 => 4,
+                _ => 1,
+            };
+            let c = bytes
+                .get(i..i + len)
+                .and_then(|sequence| std::str::from_utf8(sequence).ok())
+                .and_then(|sequence| sequence.chars().next())
+                .filter(|c| is_iri_char(*c));
+            match c {
+                Some(c) => {
+                    decoded.push(c);
+                    i += len;
+                }
+                None => {
+                    decoded.push_str(&rest[3 * i..3 * i + 3]);
+                    i += 1;
+                }
+            }
+        }
+        rest = &rest[3 * bytes.len()..];
+    }
+    decoded.push_str(rest);
+    decoded
+}
@@ -0,0 +1,945 @@
+//! Conversions between [`Card`] and jCard (RFC 7095), the JSON encoding of vCard exchanged by RDAP (RFC 9083).
+//!
+//! A jCard is the `["vcard", [properties]]` array, each property being `[name, parameters, type, value]`,
+//! e.g. `["fn", {}, "text", "Jane Doe"]`. The mapping follows RFC 9555 for the same properties as the
+//! `vcard4-interop` conversions, the TYPE and PREF parameters being mapped by [`crate::vcard_map`]. The values
+//! are JSON values: there is no text escaping, and the components of `n`, `adr` and `org` are arrays.
+//!
+//! Everything that has no counterpart in the target format is collected in a [`ConversionReport`].
+
+use std::collections::HashMap;
+
+use serde_json::{json, Map, Value};
+
+use crate::vcard_map;
+use crate::{
+    Address, AddressComponent, AddressComponentKind, Anniversary, AnniversaryKind, Card, CardKind,
+    CardVersion, Context, ConversionReport, DateObject, EmailAddress, Link, Media, MediaKind, Name,
+    NameComponent, NameComponentKind, Nickname, Note, OrgUnit, Organization, PartialDate, Phone,
+    PhoneFeature, SmallString, Timestamp, Title, TitleKind,
+};
+
+/// The vCard version of a jCard
+const VERSION: &str = "4.0";
+
+/// A property of a jCard
+struct Property<'a> {
+    /// The name, in lowercase
+    name: String,
+    /// The parameters
+    parameters: &'a Map<String, Value>,
+    /// The value type (e.g. `text` or `uri`)
+    value_type: &'a str,
+    /// The values, more than one for a multi-valued property (e.g. `categories`)
+    values: &'a [Value],
+}
+
+impl Property<'_> {
+    /// Get the first value as a text
+    fn text(&self) -> String {
+        self.values.first().map(text).unwrap_or_default()
+    }
+}
+
+impl Card {
+    /// Converts the Card to a jCard, see [`Card::to_jcard_with_report`].
+    ///
+    /// ```rust
+    /// use jscontact::Card;
+    /// use serde_json::json;
+    ///
+    /// let card = Card::new_with_latest_version("urn:uuid:1234");
+    /// assert_eq!(
+    ///     card.to_jcard(),
+    ///     json!(["vcard", [
+    ///         ["version", {}, "text", "4.0"],
+    ///         ["uid", {}, "uri", "urn:uuid:1234"],
+    ///         ["fn", {}, "text", "urn:uuid:1234"],
+    ///     ]])
+    /// );
+    /// ```
+    pub fn to_jcard(&self) -> Value {
+        self.to_jcard_with_report().0
+    }
+
+    /// Converts the Card to a jCard.
+    /// The properties without vCard counterpart are listed in the returned [`ConversionReport`].
+    pub fn to_jcard_with_report(&self) -> (Value, ConversionReport) {
+        let mut report = ConversionReport::new();
+        let mut properties = vec![
+            property("version", Map::new(), "text", json!(VERSION)),
+            property("uid", Map::new(), uri_or_text(&self.uid), json!(self.uid)),
+            property("fn", Map::new(), "text", json!(formatted_name(self))),
+        ];
+
+        if let Some(kind) = &self.kind {
+            if let Ok(kind) = serde_json::to_value(kind) {
+                properties.push(property("kind", Map::new(), "text", kind));
+            }
+        }
+        if let Some(prod_id) = &self.prod_id {
+            properties.push(property("prodid", Map::new(), "text", json!(prod_id)));
+        }
+        if let Some(updated) = &self.updated {
+            properties.push(property("rev", Map::new(), "timestamp", json!(updated)));
+        }
+        if let Some(name) = &self.name {
+            if let Some(n) = name_to_jcard(name, &mut report) {
+                properties.push(property("n", Map::new(), "text", n));
+            }
+        }
+        for (key, nickname) in sorted(&self.nicknames) {
+            let path = format!("nicknames/{}", key);
+            let params = parameters(
+                &path,
+                &nickname.contexts,
+                nickname.pref.map(u64::from),
+                &mut report,
+            );
+            properties.push(property("nickname", params, "text", json!(nickname.name)));
+        }
+        for (key, email) in sorted(&self.emails) {
+            let path = format!("emails/{}", key);
+            let params = parameters(
+                &path,
+                &email.contexts,
+                email.pref.map(u64::from),
+                &mut report,
+            );
+            properties.push(property("email", params, "text", json!(email.address)));
+        }
+        for (key, phone) in sorted(&self.phones) {
+            properties.push(phone_to_jcard(key, phone, &mut report));
+        }
+        for (key, address) in sorted(&self.addresses) {
+            properties.push(address_to_jcard(key, address, &mut report));
+        }
+        for (key, organization) in sorted(&self.organizations) {
+            let mut values = vec![organization.name.clone().unwrap_or_default()];
+            for unit in organization.units.iter().flatten() {
+                values.push(unit.name.clone());
+            }
+            let value = match values.len() {
+                1 => json!(values[0]),
+                _ => json!(values),
+            };
+            let path = format!("organizations/{}", key);
+            let params = parameters(&path, &organization.contexts, None, &mut report);
+            properties.push(property("org", params, "text", value));
+        }
+        for (key, title) in sorted(&self.titles) {
+            let name = match title.kind {
+                Some(TitleKind::Role) => "role",
+                _ => "title",
+            };
+            properties.push(property(name, Map::new(), "text", json!(title.name)));
+            if title.organization_id.is_some() {
+                report.add_loss(
+                    &format!("titles/{}/organizationId", key),
+                    "vCard has no link between TITLE and ORG",
+                );
+            }
+        }
+        for (key, media) in sorted(&self.media) {
+            let name = match media.kind {
+                MediaKind::Photo => "photo",
+                MediaKind::Logo => "logo",
+                MediaKind::Sound => "sound",
+            };
+            let path = format!("media/{}", key);
+            let mut params = parameters(&path, &media.contexts, media.pref, &mut report);
+            if let Some(media_type) = &media.media_type {
+                params.insert("mediatype".into(), json!(media_type.to_string()));
+            }
+            properties.push(property(name, params, "uri", json!(media.uri)));
+        }
+        let mut dated = [
+            (AnniversaryKind::Birth, "bday", false),
+            (AnniversaryKind::Wedding, "anniversary", false),
+        ];
+        for (key, anniversary) in sorted(&self.anniversaries) {
+            let path = format!("anniversaries/{}", key);
+            let Some((_, name, done)) = dated
+                .iter_mut()
+                .find(|(kind, _, done)| *kind == anniversary.kind && !*done)
+            else {
+                let reason = match &anniversary.kind {
+                    AnniversaryKind::Birth | AnniversaryKind::Wedding => {
+                        "vCard supports one BDAY and one ANNIVERSARY only".to_string()
+                    }
+                    AnniversaryKind::Death => "vCard has no death-date property".to_string(),
+                    AnniversaryKind::Other(kind) => {
+                        format!("vCard has no {} anniversary property", kind)
+                    }
+                };
+                report.add_loss(&path, &reason);
+                continue;
+            };
+            match date_to_jcard(&anniversary.date) {
+                Some((value_type, value)) => {
+                    properties.push(property(name, Map::new(), value_type, json!(value)));
+                    *done = true;
+                }
+                None => report.add_loss(&path, "the date has no vCard representation"),
+            }
+            if anniversary.place.is_some() {
+                report.add_loss(
+                    &format!("{}/place", path),
+                    "not supported by the jCard conversion",
+                );
+            }
+        }
+        for (key, link) in sorted(&self.links) {
+            let path = format!("links/{}", key);
+            let params = parameters(&path, &link.contexts, link.pref, &mut report);
+            properties.push(property("url", params, "uri", json!(link.uri)));
+        }
+        for (key, note) in sorted(&self.notes) {
+            properties.push(property("note", Map::new(), "text", json!(note.note)));
+            if note.author.is_some() || note.created.is_some() {
+                report.add_loss(
+                    &format!("notes/{}", key),
+                    "vCard has no AUTHOR or CREATED parameters",
+                );
+            }
+        }
+        if let Some(keywords) = &self.keywords {
+            let mut values: Vec<&str> = keywords
+                .iter()
+                .filter(|(_, enabled)| **enabled)
+                .map(|(keyword, _)| keyword.as_str())
+                .collect();
+            values.sort_unstable();
+            if !values.is_empty() {
+                let mut categories = vec![json!("categories"), json!({}), json!("text")];
+                categories.extend(values.into_iter().map(|value| json!(value)));
+                properties.push(Value::Array(categories));
+            }
+        }
+
+        let unsupported = [
+            ("created", self.created.is_some()),
+            ("language", self.language.is_some()),
+            ("members", self.members.is_some()),
+            ("relatedTo", self.related_to.is_some()),
+            ("speakToAs", self.speak_to_as.is_some()),
+            ("onlineServices", self.online_services.is_some()),
+            ("preferredLanguages", self.preferred_languages.is_some()),
+            ("calendars", self.calendars.is_some()),
+            ("schedulingAddresses", self.scheduling_addresses.is_some()),
+            ("cryptoKeys", self.crypto_keys.is_some()),
+            ("directories", self.directories.is_some()),
+            ("personalInfo", self.personal_info.is_some()),
+            ("localizations", self.get_raw_localizations().is_some()),
+        ];
+        for (path, is_set) in unsupported {
+            if is_set {
+                report.add_loss(path, "not supported by the jCard conversion");
+            }
+        }
+        (json!(["vcard", properties]), report)
+    }
+
+    /// Creates a Card from a jCard, see [`Card::from_jcard_with_uid`] for a jCard without UID.
+    /// The vCard properties without JSContact counterpart are listed in the returned [`ConversionReport`].
+    ///
+    /// ```rust
+    /// use jscontact::Card;
+    /// use serde_json::json;
+    ///
+    /// let jcard = json!(["vcard", [
+    ///     ["version", {}, "text", "4.0"],
+    ///     ["uid", {}, "uri", "urn:uuid:1234"],
+    ///     ["fn", {}, "text", "Jane Doe"],
+    ///     ["email", {"type": "work"}, "text", "jane@example.com"],
+    /// ]]);
+    /// let (card, report) = Card::from_jcard(&jcard).unwrap();
+    /// assert_eq!(card.uid, "urn:uuid:1234");
+    /// assert_eq!(card.emails.unwrap()["e1"].address, "jane@example.com");
+    /// assert!(report.is_lossless());
+    /// ```
+    /// # Errors
+    /// Will return an error if the value is not a jCard of vCard 4.0 or if it has no UID.
+    pub fn from_jcard(jcard: &Value) -> Result<(Card, ConversionReport), String> {
+        let properties = read_properties(jcard)?;
+        let Some(uid) = properties.iter().find(|property| property.name == "uid") else {
+            return Err("jCard has no UID".into());
+        };
+        Ok(from_properties(&properties, &uid.text()))
+    }
+
+    /// Creates a Card from a jCard, with a UID for a jCard without UID (e.g. the handle of an RDAP entity).
+    /// # Errors
+    /// Will return an error if the value is not a jCard of vCard 4.0.
+    pub fn from_jcard_with_uid(
+        jcard: &Value,
+        uid: &str,
+    ) -> Result<(Card, ConversionReport), String> {
+        let properties = read_properties(jcard)?;
+        let uid = properties
+            .iter()
+            .find(|property| property.name == "uid")
+            .map_or_else(|| uid.to_string(), Property::text);
+        Ok(from_properties(&properties, &uid))
+    }
+}
+
+/// Read the properties of a jCard
+fn read_properties(jcard: &Value) -> Result<Vec<Property<'_>>, String> {
+    let Some([Value::String(tag), Value::Array(properties)]) = jcard.as_array().map(Vec::as_slice)
+    else {
+        return Err("jCard is not a [\"vcard\", [properties]] array".into());
+    };
+    if !tag.eq_ignore_ascii_case("vcard") {
+        return Err(format!("jCard is tagged '{}' instead of 'vcard'", tag));
+    }
+    let mut read = Vec::new();
+    for (idx, property) in properties.iter().enumerate() {
+        let Some(
+            [Value::String(name), Value::Object(parameters), Value::String(value_type), values @ ..],
+        ) = property.as_array().map(Vec::as_slice)
+        else {
+            return Err(format!("jCard property {} is invalid", idx));
+        };
+        if values.is_empty() {
+            return Err(format!("jCard property {} has no value", idx));
+        }
+        read.push(Property {
+            name: name.to_lowercase(),
+            parameters,
+            value_type,
+            values,
+        });
+    }
+    match read.iter().find(|property| property.name == "version") {
+        Some(version) if version.text() != VERSION => Err(format!(
+            "jCard version {} is not {}",
+            version.text(),
+            VERSION
+        )),
+        _ => Ok(read),
+    }
+}
+
+/// Build a Card from the properties of a jCard
+fn from_properties(properties: &[Property], uid: &str) -> (Card, ConversionReport) {
+    let mut report = ConversionReport::new();
+    let mut card = Card::new(CardVersion::OneDotZero, uid);
+    let named = |name: &str| -> Vec<&Property> {
+        properties
+            .iter()
+            .filter(|property| property.name == name)
+            .collect()
+    };
+
+    card.kind = named("kind").first().and_then(|kind| {
+        serde_json::from_value::<CardKind>(json!(kind.text().to_lowercase())).ok()
+    });
+    card.prod_id = named("prodid").first().map(|prod_id| prod_id.text());
+    card.updated = named("rev").first().map(|rev| rev.text());
+
+    let mut name = Name::default();
+    let formatted_names = named("fn");
+    name.full = formatted_names
+        .first()
+        .map(|formatted_name| formatted_name.text());
+    if formatted_names.len() > 1 {
+        report.add_loss("FN", "only the first FN is kept");
+    }
+    if let Some(n) = named("n").first() {
+        name.components = Some(name_from_jcard(n.values.first().unwrap_or(&Value::Null)));
+    }
+    if name.full.is_some() || name.components.is_some() {
+        card.name = Some(name);
+    }
+
+    card.nicknames = keyed("k", &named("nickname"), |nickname| {
+        let mut value = Nickname::new(&nickname.text());
+        value.contexts = contexts(nickname.parameters);
+        value.pref = pref(nickname.parameters).map(|pref| pref as u32);
+        value
+    });
+    card.emails = keyed("e", &named("email"), |email| {
+        let mut email_address = EmailAddress::new(&email.text());
+        email_address.contexts = contexts(email.parameters);
+        email_address.pref = pref(email.parameters).map(|pref| pref as u32);
+        email_address
+    });
+    card.phones = keyed("tel", &named("tel"), |tel| {
+        let mut phone = Phone::new(&tel.text());
+        phone.contexts = contexts(tel.parameters);
+        phone.features = features(tel.parameters);
+        phone.pref = pref(tel.parameters).map(|pref| pref as u32);
+        phone
+    });
+    card.addresses = keyed("a", &named("adr"), |adr| address_from_jcard(adr));
+    card.organizations = keyed("o", &named("org"), |org| {
+        let mut values = components(org.values.first().unwrap_or(&Value::Null)).into_iter();
+        let mut organization = Organization {
+            org_type: Some(crate::OrganizationType::Organization),
+            ..Default::default()
+        };
+        organization.name = values.next().filter(|name| !name.is_empty());
+        let units: Vec<OrgUnit> = values.map(|unit| OrgUnit::new(&unit)).collect();
+        if !units.is_empty() {
+            organization.units = Some(units);
+        }
+        organization.contexts = contexts(org.parameters);
+        organization
+    });
+    let titles = named("title")
+        .into_iter()
+        .map(|title| (title, TitleKind::Title))
+        .chain(
+            named("role")
+                .into_iter()
+                .map(|role| (role, TitleKind::Role)),
+        )
+        .collect::<Vec<_>>();
+    card.titles = keyed("t", &titles, |(property, kind)| {
+        let mut title = Title::new(&property.text());
+        title.kind = Some(kind.clone());
+        title
+    });
+
+    let mut media = Vec::new();
+    for (name, kind) in [
+        ("photo", MediaKind::Photo),
+        ("logo", MediaKind::Logo),
+        ("sound", MediaKind::Sound),
+    ] {
+        for property in named(name) {
+            match property.value_type {
+                "uri" => media.push((property, kind.clone())),
+                _ => report.add_loss(&name.to_uppercase(), "the value is not a URI"),
+            }
+        }
+    }
+    card.media = keyed("m", &media, |(property, kind)| {
+        let mut media = Media::new(&property.text(), kind.clone());
+        media.contexts = contexts(property.parameters);
+        media.pref = pref(property.parameters);
+        media.media_type = parameter(property.parameters, "mediatype")
+            .map(|media_type| media_type.as_str().into());
+        media
+    });
+
+    let mut anniversaries = HashMap::new();
+    for (key, kind, name) in [
+        ("birth", AnniversaryKind::Birth, "bday"),
+        ("wedding", AnniversaryKind::Wedding, "anniversary"),
+    ] {
+        let Some(property) = named(name).into_iter().next() else {
+            continue;
+        };
+        match date_from_jcard(property) {
+            Some(date) => {
+                anniversaries.insert(key.into(), Anniversary::new(kind, date));
+            }
+            None => report.add_loss(key, "only dates and UTC date-times are supported"),
+        }
+    }
+    if !anniversaries.is_empty() {
+        card.anniversaries = Some(anniversaries);
+    }
+
+    card.links = keyed("l", &named("url"), |url| {
+        let mut link = Link::new(&url.text());
+        link.contexts = contexts(url.parameters);
+        link.pref = pref(url.parameters);
+        link
+    });
+    card.notes = keyed("n", &named("note"), |note| Note::new(&note.text()));
+    let keywords: HashMap<SmallString, bool> = named("categories")
+        .iter()
+        .flat_map(|categories| categories.values.iter())
+        .map(text)
+        .filter(|keyword| !keyword.is_empty())
+        .map(|keyword| (keyword.as_str().into(), true))
+        .collect();
+    if !keywords.is_empty() {
+        card.keywords = Some(keywords);
+    }
+
+    let supported = [
+        "version",
+        "uid",
+        "fn",
+        "kind",
+        "prodid",
+        "rev",
+        "n",
+        "nickname",
+        "email",
+        "tel",
+        "adr",
+        "org",
+        "title",
+        "role",
+        "photo",
+        "logo",
+        "sound",
+        "bday",
+        "anniversary",
+        "url",
+        "note",
+        "categories",
+    ];
+    let mut unsupported: Vec<String> = properties
+        .iter()
+        .filter(|property| !supported.contains(&property.name.as_str()))
+        .map(|property| property.name.to_uppercase())
+        .collect();
+    unsupported.dedup();
+    for property in unsupported {
+        report.add_loss(&property, "not supported by the jCard conversion");
+    }
+    (card, report)
+}
+
+/// Build a property of a jCard
+fn property(name: &str, parameters: Map<String, Value>, value_type: &str, value: Value) -> Value {
+    json!([name, parameters, value_type, value])
+}
+
+/// Get a value as a text, the numbers and the booleans included
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null | Value::Array(_) | Value::Object(_) => String::new(),
+        value => value.to_string(),
+    }
+}
+
+/// Get the values of a structured value, e.g. the ones of an `org`
+fn components(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(values) => values.iter().map(text).collect(),
+        value => vec![text(value)],
+    }
+}
+
+/// Get the values of a component of a structured value, e.g. the given names of an `n`
+fn component_values(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::Array(values)) => values.iter().map(text).collect(),
+        Some(value) => vec![text(value)],
+        None => Vec::new(),
+    }
+    .into_iter()
+    .filter(|value| !value.is_empty())
+    .collect()
+}
+
+/// Build a component of a structured value: a text, or an array for several values
+fn component(values: &[&str]) -> Value {
+    match values {
+        [] => json!(""),
+        [value] => json!(value),
+        values => json!(values),
+    }
+}
+
+/// The value type of a value, `uri` if it looks like a URI
+fn uri_or_text(value: &str) -> &'static str {
+    match value.split_once(':') {
+        Some((scheme, _))
+            if !scheme.is_empty()
+                && scheme
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.')) =>
+        {
+            "uri"
+        }
+        _ => "text",
+    }
+}
+
+/// Iterate over a map property sorted by key, to have a deterministic output
+fn sorted<V>(map: &Option<HashMap<SmallString, V>>) -> Vec<(&str, &V)> {
+    let mut entries: Vec<(&str, &V)> = map
+        .iter()
+        .flatten()
+        .map(|(key, value)| (key.as_str(), value))
+        .collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+/// Build a map property with generated keys (`prefix1`, `prefix2`, ...)
+fn keyed<T, V, F: Fn(&T) -> V>(
+    prefix: &str,
+    values: &[T],
+    f: F,
+) -> Option<HashMap<SmallString, V>> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(
+        values
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                let mut key = SmallString::from(prefix);
+                key.push_str(&(idx + 1).to_string());
+                (key, f(value))
+            })
+            .collect(),
+    )
+}
+
+/// The FN value: the full name, the name components or the uid as last resort
+fn formatted_name(card: &Card) -> String {
+    card.name
+        .as_ref()
+        .and_then(|name| name.full.clone().or_else(|| Some(name.to_full_string())))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| card.uid.clone())
+}
+
+/// Build the `type` and `pref` parameters, a single TYPE value being a text
+fn type_parameters(mut types: Vec<&str>, pref: Option<u64>) -> Map<String, Value> {
+    let mut parameters = Map::new();
+    types.sort_unstable();
+    match types.as_slice() {
+        [] => {}
+        [value] => {
+            parameters.insert("type".into(), json!(value));
+        }
+        values => {
+            parameters.insert("type".into(), json!(values));
+        }
+    }
+    if let Some(pref) = pref.and_then(vcard_map::pref_to_parameter) {
+        parameters.insert("pref".into(), json!(pref.to_string()));
+    }
+    parameters
+}
+
+/// Build the `type` parameter of the contexts and the `pref` parameter, see [`context_types`]
+fn parameters(
+    path: &str,
+    contexts: &Option<HashMap<Context, bool>>,
+    pref: Option<u64>,
+    report: &mut ConversionReport,
+) -> Map<String, Value> {
+    type_parameters(context_types(path, contexts, report), pref)
+}
+
+/// Map the JSContact contexts to TYPE values, the vendor-specific contexts being reported as lost
+fn context_types(
+    path: &str,
+    contexts: &Option<HashMap<Context, bool>>,
+    report: &mut ConversionReport,
+) -> Vec<&'static str> {
+    let mut enabled: Vec<&Context> = contexts
+        .iter()
+        .flatten()
+        .filter(|(_, enabled)| **enabled)
+        .map(|(context, _)| context)
+        .collect();
+    enabled.sort();
+    let mut types = Vec::new();
+    for context in enabled {
+        match vcard_map::context_to_type(context) {
+            Some(value) => types.push(value),
+            None => {
+                let value = vcard_map::jscontact_value(context);
+                report.add_loss(
+                    &format!("{}/contexts/{}", path, value),
+                    &format!("vCard has no {} TYPE", value),
+                );
+            }
+        }
+    }
+    types
+}
+
+/// Get a parameter as a text, the first value of a multi-valued parameter
+fn parameter(parameters: &Map<String, Value>, name: &str) -> Option<String> {
+    let (_, value) = parameters
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))?;
+    match value {
+        Value::Array(values) => values.first().map(text),
+        value => Some(text(value)),
+    }
+}
+
+/// Get the TYPE values of the parameters
+fn type_values(parameters: &Map<String, Value>) -> Vec<String> {
+    parameters
+        .iter()
+        .filter(|(key, _)| key.eq_ignore_ascii_case("type"))
+        .flat_map(|(_, value)| match value {
+            Value::Array(values) => values.iter().map(text).collect(),
+            value => vec![text(value)],
+        })
+        .flat_map(|value| {
+            value
+                .split(',')
+                .map(|value| value.trim().to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Map the TYPE values to JSContact contexts
+fn contexts(parameters: &Map<String, Value>) -> Option<HashMap<Context, bool>> {
+    let contexts: HashMap<Context, bool> = type_values(parameters)
+        .iter()
+        .filter_map(|value| vcard_map::type_to_context(value))
+        .map(|context| (context, true))
+        .collect();
+    (!contexts.is_empty()).then_some(contexts)
+}
+
+/// Map the TYPE values of a `tel` property to phone features
+fn features(parameters: &Map<String, Value>) -> Option<HashMap<PhoneFeature, bool>> {
+    let features: HashMap<PhoneFeature, bool> = type_values(parameters)
+        .iter()
+        .filter_map(|value| vcard_map::type_to_feature(value))
+        .map(|feature| (feature, true))
+        .collect();
+    (!features.is_empty()).then_some(features)
+}
+
+/// Get the preference from the `pref` parameter or the legacy `TYPE=pref`
+fn pref(parameters: &Map<String, Value>) -> Option<u64> {
+    let types = type_values(parameters);
+    let types: Vec<&str> = types.iter().map(String::as_str).collect();
+    let pref = parameter(parameters, "pref").and_then(|pref| pref.parse().ok());
+    vcard_map::pref_from_vcard(pref, &types)
+}
+
+/// Convert the name components to the `n` value
+fn name_to_jcard(name: &Name, report: &mut ConversionReport) -> Option<Value> {
+    let components = name.components.as_ref()?;
+    // family; given; additional; prefix; suffix
+    let mut values: [Vec<&str>; 5] = Default::default();
+    for (idx, component) in components.iter().enumerate() {
+        let position = match component.kind {
+            NameComponentKind::Surname | NameComponentKind::Surname2 => 0,
+            NameComponentKind::Given => 1,
+            NameComponentKind::Given2 => 2,
+            NameComponentKind::Title => 3,
+            NameComponentKind::Credential | NameComponentKind::Generation => 4,
+            NameComponentKind::Separator => continue,
+        };
+        if matches!(
+            component.kind,
+            NameComponentKind::Surname2 | NameComponentKind::Generation
+        ) {
+            report.add_warning(&format!(
+                "name/components/{} merged into a standard N component",
+                idx
+            ));
+        }
+        values[position].push(&component.value);
+    }
+    if name.phonetic_script.is_some() || name.phonetic_system.is_some() {
+        report.add_loss("name/phonetic", "vCard has no phonetic parameters");
+    }
+    if name.sort_as.is_some() {
+        report.add_loss("name/sortAs", "SORT-AS is not converted");
+    }
+    Some(Value::Array(
+        values.iter().map(|value| component(value)).collect(),
+    ))
+}
+
+/// Convert the `n` value to name components
+fn name_from_jcard(value: &Value) -> Vec<NameComponent> {
+    let values = value.as_array().map(Vec::as_slice).unwrap_or_default();
+    let kinds = [
+        (3, NameComponentKind::Title),
+        (1, NameComponentKind::Given),
+        (2, NameComponentKind::Given2),
+        (0, NameComponentKind::Surname),
+        (4, NameComponentKind::Credential),
+    ];
+    let mut components = Vec::new();
+    for (position, kind) in kinds {
+        for part in component_values(values.get(position)) {
+            components.push(NameComponent::new(kind.clone(), &part));
+        }
+    }
+    components
+}
+
+/// Convert a phone to the `tel` property
+fn phone_to_jcard(key: &str, phone: &Phone, report: &mut ConversionReport) -> Value {
+    let path = format!("phones/{}", key);
+    let mut types = context_types(&path, &phone.contexts, report);
+    let mut features: Vec<&PhoneFeature> = phone
+        .features
+        .iter()
+        .flatten()
+        .filter(|(_, enabled)| **enabled)
+        .map(|(feature, _)| feature)
+        .collect();
+    features.sort_by_cached_key(|feature| vcard_map::jscontact_value(*feature));
+    for feature in features {
+        match vcard_map::feature_to_type(feature) {
+            Some(value) => types.push(value),
+            None => {
+                let value = vcard_map::jscontact_value(feature);
+                report.add_loss(
+                    &format!("{}/features/{}", path, value),
+                    &format!("vCard has no {} TYPE", value),
+                );
+            }
+        }
+    }
+    let params = type_parameters(types, phone.pref.map(u64::from));
+    property(
+        "tel",
+        params,
+        uri_or_text(&phone.number),
+        json!(phone.number),
+    )
+}
+
+/// Convert an address to the `adr` property
+fn address_to_jcard(key: &str, address: &Address, report: &mut ConversionReport) -> Value {
+    // post office box; extended address; street address; locality; region; postal code; country
+    let mut values: [Vec<&str>; 7] = Default::default();
+    for (idx, component) in address.components.iter().flatten().enumerate() {
+        let position = match component.kind {
+            AddressComponentKind::PostOfficeBox => 0,
+            AddressComponentKind::Apartment
+            | AddressComponentKind::Room
+            | AddressComponentKind::Floor
+            | AddressComponentKind::Building => 1,
+            AddressComponentKind::Number
+            | AddressComponentKind::Name
+            | AddressComponentKind::Direction => 2,
+            AddressComponentKind::Locality => 3,
+            AddressComponentKind::Region => 4,
+            AddressComponentKind::Postcode => 5,
+            AddressComponentKind::Country => 6,
+            AddressComponentKind::Separator => continue,
+            AddressComponentKind::Block
+            | AddressComponentKind::District
+            | AddressComponentKind::Subdistrict
+            | AddressComponentKind::Landmark => {
+                report.add_loss(
+                    &format!("addresses/{}/components/{}", key, idx),
+                    "no ADR component for this kind",
+                );
+                continue;
+            }
+        };
+        values[position].push(&component.value);
+    }
+    let value: Vec<Value> = values
+        .iter()
+        .enumerate()
+        .map(|(position, value)| match position {
+            1 | 2 => json!(value.join(" ")),
+            _ => component(value),
+        })
+        .collect();
+    let mut types = Vec::new();
+    if let Some(contexts) = &address.contexts {
+        for (context, enabled) in contexts {
+            if !enabled {
+                continue;
+            }
+            match vcard_map::address_context_to_type(context) {
+                Some(value) => types.push(value),
+                None => report.add_loss(
+                    &format!("addresses/{}/contexts", key),
+                    "vCard has no billing or delivery TYPE",
+                ),
+            }
+        }
+    }
+    let mut params = type_parameters(types, address.pref);
+    if let Some(full) = &address.full {
+        params.insert("label".into(), json!(full));
+    }
+    if let Some(coordinates) = &address.coordinates {
+        params.insert("geo".into(), json!(coordinates));
+    }
+    for (path, is_set) in [
+        ("countryCode", address.country_code.is_some()),
+        ("timeZone", address.time_zone.is_some()),
+    ] {
+        if is_set {
+            report.add_loss(
+                &format!("addresses/{}/{}", key, path),
+                "not supported by the jCard conversion",
+            );
+        }
+    }
+    property("adr", params, "text", Value::Array(value))
+}
+
+/// Convert an `adr` property to an address
+fn address_from_jcard(property: &Property) -> Address {
+    let values = property
+        .values
+        .first()
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let kinds = [
+        AddressComponentKind::PostOfficeBox,
+        AddressComponentKind::Apartment,
+        AddressComponentKind::Name,
+        AddressComponentKind::Locality,
+        AddressComponentKind::Region,
+        AddressComponentKind::Postcode,
+        AddressComponentKind::Country,
+    ];
+    let mut components = Vec::new();
+    for (position, kind) in kinds.into_iter().enumerate() {
+        for part in component_values(values.get(position)) {
+            components.push(AddressComponent::new(kind.clone(), &part));
+        }
+    }
+    let mut address = Address {
+        address_type: Some(crate::AddressType::Address),
+        ..Default::default()
+    };
+    if !components.is_empty() {
+        address.components = Some(components);
+    }
+    address.full = parameter(property.parameters, "label");
+    address.coordinates = parameter(property.parameters, "geo");
+    address.pref = pref(property.parameters);
+    let contexts: HashMap<crate::AddressContext, bool> = type_values(property.parameters)
+        .iter()
+        .filter_map(|value| vcard_map::type_to_address_context(value))
+        .map(|context| (context, true))
+        .collect();
+    address.contexts = (!contexts.is_empty()).then_some(contexts);
+    address
+}
+
+/// Convert an anniversary date to the value type and the value of a `bday` or an `anniversary`
+fn date_to_jcard(date: &DateObject) -> Option<(&'static str, String)> {
+    match date {
+        DateObject::Timestamp(timestamp) => Some(("date-time", timestamp.utc.clone())),
+        DateObject::PartialDate(partial) if partial.calendar_scale.is_none() => {
+            partial.to_iso_string().map(|date| ("date", date))
+        }
+        DateObject::PartialDate(_) => None,
+    }
+}
+
+/// Convert a `bday` or an `anniversary` to an anniversary date
+fn date_from_jcard(property: &Property) -> Option<DateObject> {
+    let value = property.text();
+    match property.value_type {
+        "date-time" | "timestamp" | "date-and-or-time" if value.contains('T') => {
+            (!value.starts_with('T') && value.ends_with('Z'))
+                .then(|| DateObject::Timestamp(Timestamp::new(&value)))
+        }
+        "date" | "date-and-or-time" => PartialDate::from_iso_str(&value)
+            .ok()
+            .map(DateObject::PartialDate),
+        _ => None,
+    }
+}
@@ -0,0 +1,91 @@
+//! The strategies generating the keys of the entries added to the map properties, see [`KeyGen`].
+//!
+//! - [`Sequential`]: a prefix and a number (`e1`, `e2`, ...), the numbers continuing after the largest existing one
+//! - [`Uuid`]: a random UUID (with the `uuid` feature)
+//! - [`ContentHash`]: a hash of the entry, the same entry always getting the same key, so importing
+//!   the same entries again replaces them instead of adding copies
+
+use serde_json::{Map, Value};
+
+use crate::depth::eq_iteratively;
+use crate::extend::next_number_of;
+use crate::serialize::alphabetical_string;
+
+/// A strategy generating the keys of the entries added to a map property, e.g. by [`crate::Card::extend_emails_with`]
+/// or [`crate::CardBuilder::with_key_gen`].
+///
+/// A Card builder being thread-safe, so must be its key generator.
+pub trait KeyGen: Send + Sync {
+    /// Get the key of an entry added to a map.
+    ///
+    /// `prefix` is the usual prefix of the keys of the map (`e` for the emails), `entry` is the entry
+    /// and `entries` are the entries of the map, as JSON. The entry replaces the entry with the returned key, if any.
+    fn key(&self, prefix: &str, entry: &Value, entries: &Map<String, Value>) -> String;
+}
+
+/// Keys made of a prefix and a number, the numbers continuing after the largest existing key of this form.
+///
+/// An empty prefix (the default) uses the usual prefix of the map, e.g. `e` for the emails.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sequential(pub String);
+
+impl KeyGen for Sequential {
+    fn key(&self, prefix: &str, _entry: &Value, entries: &Map<String, Value>) -> String {
+        let prefix = if self.0.is_empty() { prefix } else { &self.0 };
+        let number = next_number_of(entries.keys().map(String::as_str), prefix);
+        format!("{}{}", prefix, number)
+    }
+}
+
+/// Random keys, version 4 UUIDs.
+#[cfg(feature = "uuid")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Uuid;
+
+#[cfg(feature = "uuid")]
+impl KeyGen for Uuid {
+    fn key(&self, _prefix: &str, _entry: &Value, entries: &Map<String, Value>) -> String {
+        loop {
+            let key = ::uuid::Uuid::new_v4().to_string();
+            if !entries.contains_key(&key) {
+                return key;
+            }
+        }
+    }
+}
+
+/// Keys made of the usual prefix of the map and a hash of the entry (e.g. `e3b1a2f0c9d8e7f65`),
+/// stable across runs and Rust versions.
+///
+/// The entry is hashed in its canonical form, the keys of its objects being sorted, so the same entry
+/// always gets the same key and replaces itself. An entry colliding with a different entry gets a
+/// suffix (`-2`, `-3`, ...).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContentHash;
+
+/// Hash the concatenation of byte strings (FNV-1a, which is stable across Rust versions)
+pub(crate) fn fnv1a(parts: &[&[u8]]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in parts.iter().flat_map(|part| part.iter()) {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+impl KeyGen for ContentHash {
+    fn key(&self, prefix: &str, entry: &Value, entries: &Map<String, Value>) -> String {
+        let canonical = alphabetical_string(entry);
+        let base = format!("{}{:016x}", prefix, fnv1a(&[canonical.as_bytes()]));
+        let mut key = base.clone();
+        let mut number = 1;
+        while entries
+            .get(&key)
+            .is_some_and(|existing| !eq_iteratively(existing, entry))
+        {
+            number += 1;
+            key = format!("{}-{}", base, number);
+        }
+        key
+    }
+}
@@ -0,0 +1,178 @@
+//! Normalization of the keywords of a [`Card`], whose keys are distinct even if they only differ by case (`IETF` and `ietf`).
+//!
+//! With the `unicode` feature the keywords are compared with the Unicode default case folding and
+//! normalized to NFC. Otherwise only the ASCII letters are folded and the NFC normalization is not applied,
+//! e.g. the Turkish `İ` (U+0130) is kept as is, while it is folded to `i̇` (`i` and U+0307) with the feature.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Card, SmallString};
+
+/// The steps of the normalization of the keywords, see [`Card::normalize_keywords`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeywordNormalization {
+    /// Fold the case of the keywords, the ASCII letters only without the `unicode` feature.
+    pub lowercase: bool,
+    /// Normalize the keywords to NFC, only with the `unicode` feature.
+    pub nfc: bool,
+    /// Remove the whitespace at the start and the end of the keywords.
+    pub trim: bool,
+}
+
+impl Default for KeywordNormalization {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl KeywordNormalization {
+    /// All the steps enabled, the default.
+    pub fn all() -> Self {
+        Self {
+            lowercase: true,
+            nfc: true,
+            trim: true,
+        }
+    }
+
+    /// No step enabled, to enable some steps one by one.
+    pub fn none() -> Self {
+        Self {
+            lowercase: false,
+            nfc: false,
+            trim: false,
+        }
+    }
+
+    /// Normalize a keyword.
+    pub fn normalize(&self, keyword: &str) -> String {
+        let keyword = if self.trim { keyword.trim() } else { keyword };
+        let keyword = if self.lowercase {
+            fold_case(keyword)
+        } else {
+            keyword.to_string()
+        };
+        if self.nfc {
+            nfc(keyword)
+        } else {
+            keyword
+        }
+    }
+}
+
+/// Fold the case of a keyword with the Unicode default case folding
+#[cfg(feature = "unicode")]
+fn fold_case(keyword: &str) -> String {
+    caseless::default_case_fold_str(keyword)
+}
+
+/// Fold the case of the ASCII letters of a keyword
+#[cfg(not(feature = "unicode"))]
+fn fold_case(keyword: &str) -> String {
+    keyword.to_ascii_lowercase()
+}
+
+/// Normalize a keyword to NFC
+#[cfg(feature = "unicode")]
+fn nfc(keyword: String) -> String {
+    use unicode_normalization::UnicodeNormalization;
+    keyword.nfc().collect()
+}
+
+/// Keep a keyword as is, the NFC normalization needs the `unicode` feature
+#[cfg(not(feature = "unicode"))]
+fn nfc(keyword: String) -> String {
+    keyword
+}
+
+/// Keywords merged into one by [`Card::normalize_keywords`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct KeywordMerge {
+    /// The normalized keyword, kept in the Card.
+    pub keyword: String,
+    /// The keywords merged into it, sorted.
+    pub merged: Vec<String>,
+}
+
+impl fmt::Display for KeywordMerge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} -> {}", self.merged.join(", "), self.keyword)
+    }
+}
+
+impl Card {
+    /// Check if the Card has a keyword, ignoring the case.
+    ///
+    /// The case is folded with the Unicode default case folding with the `unicode` feature, only the ASCII letters otherwise.
+    pub fn has_keyword_ci(&self, keyword: &str) -> bool {
+        let folded = fold_case(keyword);
+        self.keywords
+            .iter()
+            .flatten()
+            .any(|(key, value)| *value && fold_case(key) == folded)
+    }
+
+    /// Add a keyword as is.
+    pub fn add_keyword(&mut self, keyword: &str) {
+        self.keywords
+            .get_or_insert_with(HashMap::new)
+            .insert(SmallString::from(keyword), true);
+        self.touch();
+    }
+
+    /// Add a keyword normalized, the existing keywords are not changed, see [`Card::normalize_keywords`].
+    ///
+    /// An empty keyword once normalized is not added.
+    pub fn add_keyword_normalized(&mut self, keyword: &str, mode: &KeywordNormalization) {
+        let keyword = mode.normalize(keyword);
+        if !keyword.is_empty() {
+            self.add_keyword(&keyword);
+        }
+    }
+
+    /// Normalize the keywords, the keywords with the same normalized form being merged.
+    ///
+    /// The keywords that are empty once normalized are removed.
+    /// Returns the merges, sorted by keyword.
+    pub fn normalize_keywords(&mut self, mode: &KeywordNormalization) -> Vec<KeywordMerge> {
+        let Some(keywords) = self.keywords.take() else {
+            return Vec::new();
+        };
+        let mut groups: BTreeMap<String, (Vec<String>, bool)> = BTreeMap::new();
+        for (keyword, value) in &keywords {
+            let normalized = mode.normalize(keyword);
+            if normalized.is_empty() {
+                continue;
+            }
+            let (merged, merged_value) = groups.entry(normalized).or_default();
+            merged.push(keyword.to_string());
+            *merged_value |= *value;
+        }
+        let changed = groups.len() != keywords.len()
+            || groups
+                .iter()
+                .any(|(normalized, (merged, _))| merged[0] != *normalized);
+        if !changed {
+            self.keywords = Some(keywords);
+            return Vec::new();
+        }
+        let mut merges = Vec::new();
+        let mut normalized_keywords = HashMap::new();
+        for (normalized, (mut merged, value)) in groups {
+            normalized_keywords.insert(SmallString::from(normalized.as_str()), value);
+            if merged.len() > 1 {
+                merged.sort();
+                merges.push(KeywordMerge {
+                    keyword: normalized,
+                    merged,
+                });
+            }
+        }
+        self.keywords = (!normalized_keywords.is_empty()).then_some(normalized_keywords);
+        self.touch();
+        merges
+    }
+}
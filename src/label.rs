@@ -0,0 +1,205 @@
+//! Display labels for the contact information, e.g. "Work fax" for a phone number.
+//!
+//! The label of the object is used if set, otherwise a label is derived from its features and contexts:
+//! - the feature beats the context, and both are combined if set (e.g. "Work mobile")
+//! - phone features are chosen in this order: mobile, fax, pager, video, textphone, text, main-number, voice
+//! - contexts are chosen in this order: work, private, billing, delivery
+//! - without features and contexts, the generic label of the object is used (e.g. "Phone")
+//!
+//! The derived labels can be translated with a custom [`LabelStrings`].
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::{Address, AddressContext, Context, EmailAddress, Phone, PhoneFeature};
+
+/// The strings used to derive the display labels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelStrings {
+    /// The template combining a context and a feature, with the `{context}`, `{feature}`
+    /// and `{feature_lower}` (the feature in lowercase) placeholders.
+    pub combined: String,
+    /// Generic label of a phone number.
+    pub phone: String,
+    /// Generic label of an email address.
+    pub email: String,
+    /// Generic label of an address.
+    pub address: String,
+    /// Label of the private context.
+    pub private: String,
+    /// Label of the work context.
+    pub work: String,
+    /// Label of the billing address context.
+    pub billing: String,
+    /// Label of the delivery address context.
+    pub delivery: String,
+    /// Label of the mobile phone feature.
+    pub mobile: String,
+    /// Label of the fax phone feature.
+    pub fax: String,
+    /// Label of the pager phone feature.
+    pub pager: String,
+    /// Label of the video phone feature.
+    pub video: String,
+    /// Label of the textphone phone feature.
+    pub textphone: String,
+    /// Label of the text phone feature.
+    pub text: String,
+    /// Label of the main-number phone feature.
+    pub main_number: String,
+    /// Label of the voice phone feature.
+    pub voice: String,
+}
+
+impl Default for LabelStrings {
+    fn default() -> Self {
+        Self {
+            combined: "{context} {feature_lower}".to_string(),
+            phone: "Phone".to_string(),
+            email: "Email".to_string(),
+            address: "Address".to_string(),
+            private: "Home".to_string(),
+            work: "Work".to_string(),
+            billing: "Billing".to_string(),
+            delivery: "Delivery".to_string(),
+            mobile: "Mobile".to_string(),
+            fax: "Fax".to_string(),
+            pager: "Pager".to_string(),
+            video: "Video".to_string(),
+            textphone: "Textphone".to_string(),
+            text: "Text".to_string(),
+            main_number: "Main".to_string(),
+            voice: "Voice".to_string(),
+        }
+    }
+}
+
+impl LabelStrings {
+    /// Get the label of a phone feature, a vendor-specific feature as is
+    fn feature<'a>(&'a self, feature: &'a PhoneFeature) -> &'a str {
+        match feature {
+            PhoneFeature::Mobile => &self.mobile,
+            PhoneFeature::Fax => &self.fax,
+            PhoneFeature::Pager => &self.pager,
+            PhoneFeature::Video => &self.video,
+            PhoneFeature::Textphone => &self.textphone,
+            PhoneFeature::Text => &self.text,
+            PhoneFeature::MainNumber => &self.main_number,
+            PhoneFeature::Voice => &self.voice,
+            PhoneFeature::Other(feature) => feature,
+        }
+    }
+
+    /// Get the label of a context, a vendor-specific context as is
+    fn context<'a>(&'a self, context: &'a Context) -> &'a str {
+        match context {
+            Context::Work => &self.work,
+            Context::Private => &self.private,
+            Context::Other(context) => context,
+        }
+    }
+
+    /// Get the label of an address context
+    fn address_context(&self, context: &AddressContext) -> &str {
+        match context {
+            AddressContext::Work => &self.work,
+            AddressContext::Private => &self.private,
+            AddressContext::Billing => &self.billing,
+            AddressContext::Delivery => &self.delivery,
+        }
+    }
+
+    /// Combine the context and the feature labels
+    fn combine(&self, context: Option<&str>, feature: Option<&str>, generic: &str) -> String {
+        match (context, feature) {
+            (Some(context), Some(feature)) => self
+                .combined
+                .replace("{context}", context)
+                .replace("{feature_lower}", &feature.to_lowercase())
+                .replace("{feature}", feature),
+            (None, Some(label)) | (Some(label), None) => label.to_string(),
+            (None, None) => generic.to_string(),
+        }
+    }
+}
+
+/// The order in which the phone features are chosen for the label
+const FEATURE_ORDER: [PhoneFeature; 8] = [
+    PhoneFeature::Mobile,
+    PhoneFeature::Fax,
+    PhoneFeature::Pager,
+    PhoneFeature::Video,
+    PhoneFeature::Textphone,
+    PhoneFeature::Text,
+    PhoneFeature::MainNumber,
+    PhoneFeature::Voice,
+];
+
+/// The order in which the contexts are chosen for the label
+const CONTEXT_ORDER: [Context; 2] = [Context::Work, Context::Private];
+
+/// The order in which the address contexts are chosen for the label
+const ADDRESS_CONTEXT_ORDER: [AddressContext; 4] = [
+    AddressContext::Work,
+    AddressContext::Private,
+    AddressContext::Billing,
+    AddressContext::Delivery,
+];
+
+/// Get the first enabled key of the map, in the specified order
+fn first_enabled<'a, T: Eq + std::hash::Hash>(
+    map: &Option<HashMap<T, bool>>,
+    order: &'a [T],
+) -> Option<&'a T> {
+    let map = map.as_ref()?;
+    order.iter().find(|key| map.get(key) == Some(&true))
+}
+
+impl Phone {
+    /// Get the label to display for the phone number, see the [`crate::label`] module for the rules.
+    pub fn display_label(&self) -> Cow<'_, str> {
+        self.display_label_with(&LabelStrings::default())
+    }
+
+    /// Get the label to display for the phone number, with custom strings for the derived labels.
+    pub fn display_label_with(&self, strings: &LabelStrings) -> Cow<'_, str> {
+        if let Some(label) = &self.label {
+            return Cow::Borrowed(label);
+        }
+        let context = first_enabled(&self.contexts, &CONTEXT_ORDER).map(|c| strings.context(c));
+        let feature = first_enabled(&self.features, &FEATURE_ORDER).map(|f| strings.feature(f));
+        Cow::Owned(strings.combine(context, feature, &strings.phone))
+    }
+}
+
+impl EmailAddress {
+    /// Get the label to display for the email address, see the [`crate::label`] module for the rules.
+    pub fn display_label(&self) -> Cow<'_, str> {
+        self.display_label_with(&LabelStrings::default())
+    }
+
+    /// Get the label to display for the email address, with custom strings for the derived labels.
+    pub fn display_label_with(&self, strings: &LabelStrings) -> Cow<'_, str> {
+        if let Some(label) = &self.label {
+            return Cow::Borrowed(label);
+        }
+        let context = first_enabled(&self.contexts, &CONTEXT_ORDER).map(|c| strings.context(c));
+        Cow::Owned(strings.combine(context, None, &strings.email))
+    }
+}
+
+impl Address {
+    /// Get the label to display for the address, see the [`crate::label`] module for the rules.
+    ///
+    /// Addresses have no label property, so the label is always derived from the contexts.
+    pub fn display_label(&self) -> Cow<'_, str> {
+        self.display_label_with(&LabelStrings::default())
+    }
+
+    /// Get the label to display for the address, with custom strings for the derived labels.
+    pub fn display_label_with(&self, strings: &LabelStrings) -> Cow<'_, str> {
+        let context = first_enabled(&self.contexts, &ADDRESS_CONTEXT_ORDER)
+            .map(|c| strings.address_context(c));
+        Cow::Owned(strings.combine(context, None, &strings.address))
+    }
+}
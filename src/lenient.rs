@@ -0,0 +1,148 @@
+//! Best-effort deserialization of a [`Card`], collecting all the errors instead of failing at the first one.
+//!
+//! The properties are deserialized one by one, an invalid property is left unset,
+//! and only the invalid entries of a map (or of the name) are skipped.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::depth::{check_owned_depth, DEFAULT_MAX_DEPTH};
+use crate::migration::rename_aliases;
+use crate::nulls::remove_nulls;
+use crate::path::{property_from_value, PROPERTIES};
+use crate::unknown::ignored_members;
+use crate::{Card, PathSegment};
+
+/// An error of a property of a Card, see [`Card::from_value_collecting`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldError {
+    /// The path of the invalid value (e.g. `phones/p1`), empty for the whole Card.
+    pub path: String,
+    /// The description of the error.
+    pub message: String,
+}
+
+impl FieldError {
+    /// Create a new error at a path.
+    pub fn new(path: &str, message: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Set a property of the Card, keeping the valid entries of an object if the whole object is invalid
+fn set_property(card: &mut Card, property: &str, value: Value, errors: &mut Vec<FieldError>) {
+    let error = match property_from_value(card, property, value.clone()) {
+        Ok(()) => return,
+        Err(error) => error,
+    };
+    let Value::Object(entries) = value else {
+        errors.push(FieldError::new(property, &error));
+        return;
+    };
+    let mut valid = Map::new();
+    for (key, entry) in entries {
+        let single = Value::Object(Map::from_iter([(key.clone(), entry.clone())]));
+        match property_from_value(card, property, single) {
+            Ok(()) => {
+                valid.insert(key, entry);
+            }
+            Err(error) => {
+                let path = format!("{}/{}", property, PathSegment::Key(key));
+                errors.push(FieldError::new(&path, &error));
+            }
+        }
+    }
+    if let Err(error) = property_from_value(card, property, Value::Object(valid)) {
+        // the valid entries may be invalid together, e.g. a missing required field
+        errors.push(FieldError::new(property, &error));
+        let _ = property_from_value(card, property, Value::Null);
+    }
+}
+
+impl Card {
+    /// Deserialize a Card property by property, collecting the errors of all the invalid properties.
+    ///
+    /// An invalid property is left unset and an invalid entry of a map is skipped, each with an error at its path,
+    /// the errors being sorted by path. The `null` values are removed, each with an error (see [`crate::nulls`]).
+    /// The unknown properties that are not vendor-specific are kept, and the unknown members of the properties
+    /// ignored, each with an error (see [`crate::unknown`]). A known alias of a missing property (e.g. `@version`)
+    /// is read as the property, with an error (see [`crate::migration::PROPERTY_ALIASES`]).
+    /// The Card is `None` if it cannot be built, e.g. if it is not an object or if its `uid` or `version` is invalid.
+    pub fn from_value_collecting(value: Value) -> (Option<Card>, Vec<FieldError>) {
+        let mut errors = Vec::new();
+        let value = match check_owned_depth(value, DEFAULT_MAX_DEPTH) {
+            Ok(value) => value,
+            Err(error) => {
+                errors.push(FieldError::new("", &error.to_string()));
+                return (None, errors);
+            }
+        };
+        let Value::Object(mut object) = value else {
+            errors.push(FieldError::new("", "The Card is not an object"));
+            return (None, errors);
+        };
+        for path in remove_nulls(&mut object) {
+            errors.push(FieldError::new(&path, "null value, read as absent"));
+        }
+        for alias in rename_aliases(&mut object) {
+            let path = PathSegment::Key(alias.alias.to_string()).to_string();
+            let message = format!(
+                "alias of `{}`, read as `{}`",
+                alias.property, alias.property
+            );
+            errors.push(FieldError::new(&path, &message));
+        }
+        let mut properties: Vec<(String, Value)> = Vec::new();
+        for property in PROPERTIES {
+            if matches!(*property, "version" | "uid") {
+                continue;
+            }
+            if let Some(value) = object.remove(*property) {
+                properties.push((property.to_string(), value));
+            }
+        }
+        let mut card = match Card::deserialize(&Value::Object(object.clone())) {
+            Ok(card) => card,
+            Err(error) => {
+                // the Card may be valid without its localizations
+                let has_localizations = object.remove("localizations").is_some();
+                match Card::deserialize(Value::Object(object)) {
+                    Ok(card) if has_localizations => {
+                        errors.push(FieldError::new("localizations", &error.to_string()));
+                        card
+                    }
+                    _ => {
+                        errors.push(FieldError::new("", &error.to_string()));
+                        return (None, errors);
+                    }
+                }
+            }
+        };
+        let json: Map<String, Value> = properties.iter().cloned().collect();
+        for (property, value) in properties {
+            set_property(&mut card, &property, value, &mut errors);
+        }
+        for property in card.unknown_properties() {
+            let path = PathSegment::Key(property.to_string()).to_string();
+            errors.push(FieldError::new(&path, "unknown property, kept as is"));
+        }
+        // the members of the invalid values are already reported with them
+        let ignored: Vec<String> = ignored_members(&json, &card)
+            .into_iter()
+            .filter(|path| {
+                !errors.iter().any(|error| {
+                    path.strip_prefix(error.path.as_str())
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+                })
+            })
+            .collect();
+        for path in ignored {
+            errors.push(FieldError::new(&path, "unknown property, ignored"));
+        }
+        errors.sort_by(|a, b| a.path.cmp(&b.path));
+        (Some(card), errors)
+    }
+}
@@ -55,9 +55,9 @@
 //! card.add_localization("en", translations);
 //!
 //! // use localized card
-//! let langs = card.get_available_languages();
+//! let langs: Vec<&str> = card.get_available_languages().collect();
 //! assert_eq!(langs, vec!["en"]);
-//! let localized = card.get_localized(&langs[0]).unwrap();
+//! let localized = card.get_localized(langs[0]).unwrap();
 //! assert_eq!(localized.name.unwrap().full.unwrap(), "Johny");
 //! ```
 
@@ -74,11 +74,231 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// The string type of the map keys and of the short string values (e.g. media types).
+///
+/// This is a [`String`], or a `compact_str::CompactString` storing short strings inline with the `compact` feature.
+/// Both implement `From<&str>`, so `SmallString::from("k1")` builds a key with any set of features.
+#[cfg(not(feature = "compact"))]
+pub type SmallString = String;
+
+/// The string type of the map keys and of the short string values (e.g. media types).
+///
+/// This is a [`String`], or a `compact_str::CompactString` storing short strings inline with the `compact` feature.
+/// Both implement `From<&str>`, so `SmallString::from("k1")` builds a key with any set of features.
+#[cfg(feature = "compact")]
+pub type SmallString = compact_str::CompactString;
+
+/// The string type of the large values (e.g. the `data:` URIs of the photos).
+///
+/// This is a [`String`], or an `Arc<str>` shared between the clones of a Card with the `shared` feature,
+/// so cloning or localizing a Card does not copy its photos. The serialized format is the same.
+/// Both implement `From<&str>` and `From<String>`, and dereference to `str`.
+#[cfg(not(feature = "shared"))]
+pub type SharedString = String;
+
+/// The string type of the large values (e.g. the `data:` URIs of the photos).
+///
+/// This is a [`String`], or an `Arc<str>` shared between the clones of a Card with the `shared` feature,
+/// so cloning or localizing a Card does not copy its photos. The serialized format is the same.
+/// Both implement `From<&str>` and `From<String>`, and dereference to `str`.
+#[cfg(feature = "shared")]
+pub type SharedString = std::sync::Arc<str>;
+
 pub mod card;
-pub use card::Card;
+pub use card::{Card, MissingRequired};
+
+mod card_set;
+pub use card_set::CardSet;
+
+#[cfg(feature = "shared")]
+pub mod intern;
+#[cfg(feature = "shared")]
+pub use intern::{CardSeed, Interner};
 
 mod resource;
-pub use resource::Resource;
+pub use resource::{Resource, ResourceRef};
+
+mod validate;
+pub use validate::{
+    syntax, ValidationCode, ValidationError, ValidationReport, ValidationSeverity,
+    ValidationSummary,
+};
+
+mod anonymize;
+
+pub mod label;
+pub use label::LabelStrings;
+
+pub mod format;
+#[cfg(feature = "phonenumber")]
+pub use format::PhoneNumberFormatter;
+pub use format::{
+    AddressFormatter, CountryAddressFormatter, DisplayOrderFormatter, FormatContext, PhoneFormatter,
+};
+
+pub mod path;
+pub use path::{CardPath, PathSegment};
+
+pub mod directory;
+pub use directory::{LdapScope, LdapUrl};
+
+pub mod iri;
+pub use iri::{iri_to_uri, uri_to_iri};
+
+mod preferred;
+
+mod scheduling;
+
+mod sort;
+pub use sort::SortOrder;
+
+mod qr;
+
+mod hcard;
+pub use hcard::HCardOptions;
+
+pub mod migration;
+pub use migration::{MigrationRegistry, PropertyAlias, UpgradeError, VersionMigration};
+
+pub mod contact_method;
+pub use contact_method::{ContactMethod, ContactMethodInfo, ContactMethodKind};
+
+pub mod pref;
+pub use pref::PrefProperty;
+
+pub mod lookup;
+
+pub mod contexts;
+pub use contexts::HasContexts;
+
+pub mod partial_date;
+pub use partial_date::PartialDateError;
+
+mod stats;
+pub use stats::{CardSetStats, CardStats, Distribution, PropertySize, LARGEST_PROPERTIES};
+
+mod completeness;
+pub use completeness::{Completeness, CompletenessItem, CompletenessWeights};
+
+mod duplicate;
+pub use duplicate::{CloneOptions, SelfRelation};
+
+mod provenance;
+pub use provenance::PROVENANCE_PROPERTY;
+
+pub mod dedup;
+pub use dedup::{DedupOptions, DuplicateGroup, MergeStrategy};
+
+pub mod media_type;
+pub use media_type::infer_media_type;
+
+pub mod registry;
+pub use registry::RegisteredEnum;
+
+pub mod batch;
+pub use batch::{collect_languages, localize_all, BatchError, LocalizeOptions};
+
+mod lenient;
+pub use lenient::FieldError;
+
+mod localizable;
+pub use localizable::{
+    LocalizationIssue, LocalizationMergePolicy, LocalizationMergeReport, PatchLimits,
+};
+
+mod prod_id;
+pub use prod_id::ProdId;
+
+mod builder;
+pub use builder::CardBuilder;
+
+mod redact;
+pub use redact::RedactedCard;
+
+pub mod debug_stable;
+pub use debug_stable::StableCard;
+
+mod components;
+pub use components::{GuardedAddress, GuardedName, LocalizationConflict, ReindexPolicy};
+
+mod extend;
+
+mod keygen;
+#[cfg(feature = "uuid")]
+pub use keygen::Uuid;
+pub use keygen::{ContentHash, KeyGen, Sequential};
+
+pub mod nicknames;
+
+pub mod skeleton;
+pub use skeleton::SkeletonFields;
+
+mod fix;
+pub use fix::{FixApplied, FixRules};
+
+pub mod text;
+pub use text::{TextChange, TextPolicy};
+
+mod keywords;
+pub use keywords::{KeywordMerge, KeywordNormalization};
+
+mod normalize;
+pub use normalize::{AddressChange, AddressChangeKind, AddressNormalizeOptions};
+
+mod empty;
+pub use empty::EmptyValue;
+
+mod place;
+pub use place::{PlaceRef, PLACE_KEY_PROPERTY};
+
+pub mod nulls;
+pub use nulls::NullValue;
+
+pub mod unknown;
+pub use unknown::UnknownProperties;
+
+mod depth;
+pub use depth::{DepthExceeded, RawValues, DEFAULT_MAX_DEPTH};
+
+mod embedded;
+
+mod accessors;
+
+#[cfg(feature = "chrono-tz")]
+mod occurrence;
+#[cfg(feature = "chrono-tz")]
+pub use occurrence::LeapDayPolicy;
+
+mod limit;
+pub use limit::{LimitKind, LimitViolation, Limits, ReadError, TextClass, DEFAULT_MAX_BYTES};
+
+mod parse;
+pub use parse::{parse, parse_with, ParseOptions, Parsed};
+
+mod serialize;
+pub use serialize::{KeyOrder, SerializeOptions, TypeMembers};
+
+mod envelope;
+
+pub mod form;
+pub use form::{CardFormBuilder, FormErrors};
+
+mod import;
+pub use import::{ImportReport, ImportSource, ParseWarning};
+
+mod tracking;
+#[cfg(feature = "tracking")]
+pub use tracking::{CardEdit, Clock, SystemClock};
+
+mod conversion;
+pub use conversion::{ConversionReport, LossRecord};
+
+pub mod vcard_map;
+
+mod jcard;
+
+#[cfg(feature = "vcard4-interop")]
+mod vcard4_interop;
 
 /// Represents the card version.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -86,6 +306,19 @@ pub enum CardVersion {
     /// version 1.0
     #[serde(rename = "1.0")]
     OneDotZero,
+    /// A version unknown to this crate (e.g. a later version), see [`Card::upgrade_to`].
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl CardVersion {
+    /// Get the version as a string (e.g. `1.0`).
+    pub fn as_str(&self) -> &str {
+        match self {
+            CardVersion::OneDotZero => "1.0",
+            CardVersion::Other(version) => version,
+        }
+    }
 }
 
 /// [`crate::Resource`] The calendaring resources of the entity represented by the Card, such as to look up free-busy information.
@@ -93,7 +326,6 @@ pub enum CardVersion {
 #[serde(rename_all = "camelCase")]
 pub struct Calendar {
     /// The @type property value MUST be "Calendar", if set.
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     calendar_type: Option<CalendarType>,
@@ -102,7 +334,7 @@ pub struct Calendar {
     pub kind: Option<CalendarKind>,
     /// The media type RFC2046 of the resource identified by the uri property value.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub media_type: Option<String>,
+    pub media_type: Option<SmallString>,
     /// The resource value.
     pub uri: String,
     /// The contexts in which to use this resource.
@@ -117,7 +349,6 @@ pub struct Calendar {
 }
 
 /// Calendar @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum CalendarType {
     /// Calendar @type
@@ -125,10 +356,14 @@ enum CalendarType {
 }
 
 impl Calendar {
+    /// Get the kind as a string, as serialized, e.g. for a generic table of resources.
+    pub fn kind_str(&self) -> Option<&str> {
+        self.kind.as_ref().map(CalendarKind::as_str)
+    }
+
     /// Creates a new Calendar object with the specified URI.
     pub fn new(uri: &str) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             calendar_type: Some(CalendarType::Calendar),
             uri: uri.to_string(),
             ..Resource::default().into()
@@ -156,12 +391,27 @@ impl From<String> for CalendarKind {
     }
 }
 
+impl CalendarKind {
+    /// Get the kind as a string, as serialized (e.g. `freeBusy`).
+    pub fn as_str(&self) -> &str {
+        match self {
+            CalendarKind::Calendar => "calendar",
+            CalendarKind::FreeBusy => "freeBusy",
+        }
+    }
+}
+
+impl std::fmt::Display for CalendarKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// The scheduling addresses by which the entity may receive calendar scheduling invitations.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SchedulingAddress {
     /// The JSContact type of the object. The value MUST be "SchedulingAddress", if set.
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     scheduling_address_type: Option<SchedulingAddressType>,
@@ -179,7 +429,6 @@ pub struct SchedulingAddress {
 }
 
 /// SchedulingAddress @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum SchedulingAddressType {
     /// SchedulingAddress @type
@@ -190,7 +439,6 @@ impl SchedulingAddress {
     /// Creates a new SchedulingAddress object with the specified URI.
     pub fn new(uri: &str) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             scheduling_address_type: Some(SchedulingAddressType::SchedulingAddress),
             uri: uri.to_string(),
             contexts: None,
@@ -237,7 +485,6 @@ impl From<String> for CardKind {
 #[serde(rename_all = "camelCase")]
 pub struct CryptoKey {
     /// The @type property value MUST be "CryptoKey", if set.
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     crypto_key_type: Option<CryptoKeyType>,
@@ -245,7 +492,7 @@ pub struct CryptoKey {
     pub uri: String,
     /// The media type RFC2046 of the resource identified by the uri property value.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub media_type: Option<String>,
+    pub media_type: Option<SmallString>,
     /// The kind of the resource.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub kind: Option<String>,
@@ -261,7 +508,6 @@ pub struct CryptoKey {
 }
 
 /// CryptoKey @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum CryptoKeyType {
     /// CryptoKey @type
@@ -269,10 +515,17 @@ enum CryptoKeyType {
 }
 
 impl CryptoKey {
+    /// Get the kind as a string, e.g. for a generic table of resources.
+    ///
+    /// RFC 9553 (with its errata) defines no kind for the crypto keys,
+    /// so this is `None` unless the producer set a vendor-specific kind, returned as is.
+    pub fn kind_str(&self) -> Option<&str> {
+        self.kind.as_deref()
+    }
+
     /// Creates a new CryptoKey object with the specified URI.
     pub fn new(uri: &str) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             crypto_key_type: Some(CryptoKeyType::CryptoKey),
             uri: uri.to_string(),
             ..Resource::default().into()
@@ -285,7 +538,6 @@ impl CryptoKey {
 #[serde(rename_all = "camelCase")]
 pub struct Directory {
     /// The @type property value MUST be "Directory", if set.
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     directory_type: Option<DirectoryType>,
@@ -296,7 +548,7 @@ pub struct Directory {
     pub uri: String,
     /// The media type RFC2046 of the resource identified by the uri property value.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub media_type: Option<String>,
+    pub media_type: Option<SmallString>,
     /// The contexts in which to use this resource.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub contexts: Option<HashMap<Context, bool>>,
@@ -312,7 +564,6 @@ pub struct Directory {
 }
 
 /// Directory @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum DirectoryType {
     /// Directory @type
@@ -320,10 +571,14 @@ enum DirectoryType {
 }
 
 impl Directory {
+    /// Get the kind as a string, as serialized, e.g. for a generic table of resources.
+    pub fn kind_str(&self) -> Option<&str> {
+        self.kind.as_ref().map(DirectoryKind::as_str)
+    }
+
     /// Creates a new Directory object with the specified URI.
     pub fn new(uri: &str) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             directory_type: Some(DirectoryType::Directory),
             uri: uri.to_string(),
             ..Resource::default().into()
@@ -351,22 +606,41 @@ impl From<String> for DirectoryKind {
     }
 }
 
+impl DirectoryKind {
+    /// Get the kind as a string, as serialized (e.g. `entry`).
+    pub fn as_str(&self) -> &str {
+        match self {
+            DirectoryKind::Directory => "directory",
+            DirectoryKind::Entry => "entry",
+        }
+    }
+}
+
+impl std::fmt::Display for DirectoryKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// [`crate::Resource`] The media resources such as photographs, avatars, or sounds that are associated with the entity represented by the Card.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Media {
     /// The @type property value MUST be "Media", if set.
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     media_hidden_type: Option<MediaType>,
     /// The kind of the media.
     pub kind: MediaKind,
-    /// The resource value.
-    pub uri: String,
+    /// The resource value, shared between the clones with the `shared` feature.
+    #[cfg_attr(
+        feature = "shared",
+        serde(deserialize_with = "crate::intern::deserialize_shared")
+    )]
+    pub uri: SharedString,
     /// The media type RFC2046 of the resource identified by the uri property value.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub media_type: Option<String>,
+    pub media_type: Option<SmallString>,
     /// The contexts in which to use this resource.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub contexts: Option<HashMap<Context, bool>>,
@@ -379,7 +653,6 @@ pub struct Media {
 }
 
 /// Media @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum MediaType {
     /// Media @type
@@ -387,14 +660,18 @@ enum MediaType {
 }
 
 impl Media {
+    /// Get the kind as a string, as serialized, e.g. for a generic table of resources.
+    pub fn kind_str(&self) -> Option<&str> {
+        Some(self.kind.as_str())
+    }
+
     /// Creates a new Media object with the specified URI and kind.
     /// Kind is mandatory on [`crate::Media`] struct
     pub fn new(uri: &str, kind: MediaKind) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             media_hidden_type: Some(MediaType::Media),
             kind,
-            uri: uri.to_string(),
+            uri: SharedString::from(uri),
             ..Resource::default().into()
         }
     }
@@ -424,12 +701,28 @@ impl From<String> for MediaKind {
     }
 }
 
+impl MediaKind {
+    /// Get the kind as a string, as serialized (e.g. `logo`).
+    pub fn as_str(&self) -> &str {
+        match self {
+            MediaKind::Photo => "photo",
+            MediaKind::Sound => "sound",
+            MediaKind::Logo => "logo",
+        }
+    }
+}
+
+impl std::fmt::Display for MediaKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// [`crate::Resource`] The links to resources that do not fit any of the other use-case-specific resource properties.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Link {
     /// The @type property value MUST be "Link", if set.
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     link_type: Option<LinkType>,
@@ -440,7 +733,7 @@ pub struct Link {
     pub uri: String,
     /// The media type RFC2046 of the resource identified by the uri property value.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub media_type: Option<String>,
+    pub media_type: Option<SmallString>,
     /// The contexts in which to use this resource.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub contexts: Option<HashMap<Context, bool>>,
@@ -453,7 +746,6 @@ pub struct Link {
 }
 
 /// Link @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum LinkType {
     /// Link @type
@@ -461,10 +753,14 @@ enum LinkType {
 }
 
 impl Link {
+    /// Get the kind as a string, as serialized, e.g. for a generic table of resources.
+    pub fn kind_str(&self) -> Option<&str> {
+        self.kind.as_ref().map(LinkKind::as_str)
+    }
+
     /// Creates a new Link object with the specified URI.
     pub fn new(uri: &str) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             link_type: Some(LinkType::Link),
             uri: uri.to_string(),
             ..Resource::default().into()
@@ -489,12 +785,26 @@ impl From<String> for LinkKind {
     }
 }
 
+impl LinkKind {
+    /// Get the kind as a string, as serialized (e.g. `contact`).
+    pub fn as_str(&self) -> &str {
+        match self {
+            LinkKind::Contact => "contact",
+        }
+    }
+}
+
+impl std::fmt::Display for LinkKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// Represents the Relation object for associating related Cards.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Relation {
     /// The JSContact type of the object. Must be "Relation".
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     relation_type: Option<RelationType>,
@@ -552,19 +862,28 @@ pub enum RelationshipType {
 }
 
 /// Relation @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum RelationType {
     /// Relation @type
     Relation,
 }
 
+impl Relation {
+    /// Whether the relationship to the related Card is unspecified, no relation type being set to true.
+    ///
+    /// An empty `relation` object (e.g. `"relation": {}`) is kept as `Some` of an empty map,
+    /// so that it is serialized back as is, while a missing `relation` is `None`.
+    pub fn is_unspecified(&self) -> bool {
+        !self.relation.iter().flatten().any(|(_, is_set)| *is_set)
+    }
+}
+
 /// Defines the Name object, which contains information about the entity's name components.
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(not(feature = "redact-debug"), derive(Debug))]
 #[serde(rename_all = "camelCase")]
 pub struct Name {
     /// The JSContact type of the object. The value MUST be "Name", if set.
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     name_type: Option<NameType>,
@@ -582,7 +901,7 @@ pub struct Name {
     pub full: Option<String>,
     /// Custom sorting order for the name components.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub sort_as: Option<HashMap<String, String>>,
+    pub sort_as: Option<HashMap<SmallString, String>>,
     /// The script used in the phonetic property.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub phonetic_script: Option<String>,
@@ -606,7 +925,6 @@ pub enum PhoneticSystem {
 impl Default for Name {
     fn default() -> Self {
         Self {
-            #[cfg(feature = "typed")]
             name_type: Some(NameType::Name),
             components: None,
             is_ordered: None,
@@ -619,8 +937,74 @@ impl Default for Name {
     }
 }
 
+/// Get the position of a kind in a display order, the kinds missing from the order coming last
+pub(crate) fn display_position<T: PartialEq>(order: &[T], kind: &T) -> usize {
+    order
+        .iter()
+        .position(|ordered| ordered == kind)
+        .unwrap_or(order.len())
+}
+
+impl Name {
+    /// Builds the full name from the name components.
+    ///
+    /// If the name is ordered, a separator component between two components takes precedence over
+    /// the [`Name::default_separator`], which itself defaults to a single space.
+    /// If the name is not ordered, separator components and the default separator are ignored,
+    /// and the components are in the [`NameComponentKind::default_display_order`].
+    /// The [`Name::full`] property is returned when there are no components.
+    pub fn to_full_string(&self) -> String {
+        self.to_full_string_with_order(NameComponentKind::default_display_order())
+    }
+
+    /// Builds the full name from the name components, the components of a name that is not ordered
+    /// being in the given order of kinds, see [`Name::to_full_string`].
+    ///
+    /// The kinds missing from the order come last, in their order in the name.
+    pub fn to_full_string_with_order(&self, order: &[NameComponentKind]) -> String {
+        let components = match &self.components {
+            Some(components) if !components.is_empty() => components,
+            _ => return self.full.clone().unwrap_or_default(),
+        };
+        if self.is_ordered != Some(true) {
+            let mut sorted: Vec<&NameComponent> = components
+                .iter()
+                .filter(|component| component.kind != NameComponentKind::Separator)
+                .collect();
+            sorted.sort_by_key(|component| display_position(order, &component.kind));
+            return sorted
+                .iter()
+                .map(|component| component.value.as_str())
+                .collect::<Vec<&str>>()
+                .join(" ");
+        }
+        let default_separator = self.default_separator.as_deref().unwrap_or(" ");
+        let mut full = String::new();
+        let mut has_value = false;
+        let mut pending_separator: Option<String> = None;
+        for component in components {
+            if component.kind == NameComponentKind::Separator {
+                if has_value {
+                    pending_separator
+                        .get_or_insert_with(String::new)
+                        .push_str(&component.value);
+                }
+                continue;
+            }
+            if has_value {
+                match pending_separator.take() {
+                    Some(separator) => full.push_str(&separator),
+                    None => full.push_str(default_separator),
+                }
+            }
+            full.push_str(&component.value);
+            has_value = true;
+        }
+        full
+    }
+}
+
 /// Name @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum NameType {
     /// Name @type
@@ -632,7 +1016,6 @@ enum NameType {
 #[serde(rename_all = "camelCase")]
 pub struct NameComponent {
     /// The JSContact type of the object. Must be "NameComponent".
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     name_component_type: Option<NameComponentType>,
@@ -649,7 +1032,6 @@ impl NameComponent {
     /// Creates a new NameComponent object with the specified kind and value.
     pub fn new(kind: NameComponentKind, value: &str) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             name_component_type: Some(NameComponentType::NameComponent),
             value: value.to_string(),
             kind,
@@ -659,7 +1041,6 @@ impl NameComponent {
 }
 
 /// NameComponent @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum NameComponentType {
     /// NameComponent @type
@@ -689,6 +1070,25 @@ pub enum NameComponentKind {
     Title,
 }
 
+impl NameComponentKind {
+    /// The display order of the components of a name that is not ordered:
+    /// the title, the given names, the surnames, the generation and the credential.
+    pub const DEFAULT_DISPLAY_ORDER: [NameComponentKind; 7] = [
+        NameComponentKind::Title,
+        NameComponentKind::Given,
+        NameComponentKind::Given2,
+        NameComponentKind::Surname,
+        NameComponentKind::Surname2,
+        NameComponentKind::Generation,
+        NameComponentKind::Credential,
+    ];
+
+    /// Get the display order of the components of a name that is not ordered, see [`NameComponentKind::DEFAULT_DISPLAY_ORDER`].
+    pub fn default_display_order() -> &'static [NameComponentKind] {
+        &Self::DEFAULT_DISPLAY_ORDER
+    }
+}
+
 impl From<String> for NameComponentKind {
     fn from(kind: String) -> Self {
         match kind.as_str() {
@@ -710,7 +1110,6 @@ impl From<String> for NameComponentKind {
 #[serde(rename_all = "camelCase")]
 pub struct Nickname {
     /// The JSContact type of the object. Must be "Nickname".
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     nickname_type: Option<NicknameType>,
@@ -725,19 +1124,43 @@ pub struct Nickname {
 }
 
 /// Nickname @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum NicknameType {
     /// Nickname @type
     Nickname,
 }
 
+impl Nickname {
+    /// Creates a new Nickname object with the specified name.
+    pub fn new(name: &str) -> Self {
+        Self {
+            nickname_type: Some(NicknameType::Nickname),
+            name: name.to_string(),
+            contexts: None,
+            pref: None,
+        }
+    }
+
+    /// Adds a context in which to use the nickname.
+    pub fn with_context(mut self, context: Context) -> Self {
+        self.contexts
+            .get_or_insert_with(HashMap::new)
+            .insert(context, true);
+        self
+    }
+
+    /// Sets the preference of the nickname.
+    pub fn with_pref(mut self, pref: u32) -> Self {
+        self.pref = Some(pref);
+        self
+    }
+}
+
 /// Represents an Organization object containing company or organization information.
 #[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Organization {
     /// The JSContact type of the object. Must be "Organization".
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     org_type: Option<OrganizationType>,
@@ -756,7 +1179,6 @@ pub struct Organization {
 }
 
 /// Organization @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum OrganizationType {
     /// Organization @type
@@ -768,7 +1190,6 @@ enum OrganizationType {
 #[serde(rename_all = "camelCase")]
 pub struct OrgUnit {
     /// The JSContact type of the object. Must be "OrgUnit".
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     unit_type: Option<OrgUnitType>,
@@ -780,7 +1201,6 @@ pub struct OrgUnit {
 }
 
 /// OrgUnit @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum OrgUnitType {
     /// OrgUnit @type
@@ -791,7 +1211,6 @@ impl OrgUnit {
     /// Creates a new OrgUnit object with the specified name.
     pub fn new(name: &str) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             unit_type: Some(OrgUnitType::OrgUnit),
             name: name.to_string(),
             sort_as: None,
@@ -804,7 +1223,6 @@ impl OrgUnit {
 #[serde(rename_all = "camelCase")]
 pub struct SpeakToAs {
     /// The JSContact type of the object. Must be "SpeakToAs".
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     speak_to_as_type: Option<SpeakToAsType>,
@@ -813,7 +1231,14 @@ pub struct SpeakToAs {
     pub grammatical_gender: Option<GrammaticalGender>,
     /// Pronouns associated with the entity.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub pronouns: Option<HashMap<String, Pronouns>>,
+    pub pronouns: Option<HashMap<SmallString, Pronouns>>,
+}
+
+impl SpeakToAs {
+    /// Returns true if neither the grammatical gender nor any pronouns are set.
+    pub fn is_empty(&self) -> bool {
+        self.grammatical_gender.is_none() && self.pronouns.as_ref().is_none_or(HashMap::is_empty)
+    }
 }
 
 /// The grammatical gender to use in salutations and other grammatical constructs.
@@ -836,7 +1261,6 @@ pub enum GrammaticalGender {
 }
 
 /// SpeakToAs @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum SpeakToAsType {
     /// SpeakToAs @type
@@ -848,7 +1272,6 @@ enum SpeakToAsType {
 #[serde(rename_all = "camelCase")]
 pub struct Pronouns {
     /// The JSContact type of the object. Must be "Pronouns".
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     pronoun_type: Option<PronounsType>,
@@ -863,7 +1286,6 @@ pub struct Pronouns {
 }
 
 /// Pronouns @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum PronounsType {
     /// Pronouns @type
@@ -874,7 +1296,6 @@ impl Pronouns {
     /// Creates a new Pronouns object with the specified pronouns.
     pub fn new(pronouns: &str) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             pronoun_type: Some(PronounsType::Pronouns),
             pronouns: pronouns.to_string(),
             contexts: None,
@@ -888,7 +1309,6 @@ impl Pronouns {
 #[serde(rename_all = "camelCase")]
 pub struct Title {
     /// The JSContact type of the object. Must be "Title".
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     title_type: Option<TitleType>,
@@ -903,7 +1323,6 @@ pub struct Title {
 }
 
 /// Title @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum TitleType {
     /// Title @type
@@ -911,10 +1330,14 @@ enum TitleType {
 }
 
 impl Title {
+    /// Get the kind as a string, as serialized, e.g. for a generic table of resources.
+    pub fn kind_str(&self) -> Option<&str> {
+        self.kind.as_ref().map(TitleKind::as_str)
+    }
+
     /// Creates a new Title object with the specified name.
     pub fn new(name: &str) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             title_type: Some(TitleType::Title),
             name: name.to_string(),
             kind: None,
@@ -943,12 +1366,28 @@ impl From<String> for TitleKind {
     }
 }
 
+impl TitleKind {
+    /// Get the kind as a string, as serialized (e.g. `title`).
+    pub fn as_str(&self) -> &str {
+        match self {
+            TitleKind::Role => "role",
+            TitleKind::Title => "title",
+        }
+    }
+}
+
+impl std::fmt::Display for TitleKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// Defines email addresses associated with the entity.
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(not(feature = "redact-debug"), derive(Debug))]
 #[serde(rename_all = "camelCase")]
 pub struct EmailAddress {
     /// The JSContact type of the object. Must be "EmailAddress".
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     email_type: Option<EmailAddressType>,
@@ -966,7 +1405,6 @@ pub struct EmailAddress {
 }
 
 /// EmailAddress @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum EmailAddressType {
     /// EmailAddress @type
@@ -977,7 +1415,6 @@ impl EmailAddress {
     /// Creates a new EmailAddress object with the specified email address.
     pub fn new(address: &str) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             email_type: Some(EmailAddressType::EmailAddress),
             address: address.to_string(),
             contexts: None,
@@ -988,11 +1425,11 @@ impl EmailAddress {
 }
 
 /// Represents online services such as social media or messaging accounts.
-#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, Default, PartialEq, Clone)]
+#[cfg_attr(not(feature = "redact-debug"), derive(Debug))]
 #[serde(rename_all = "camelCase")]
 pub struct OnlineService {
     /// The JSContact type of the object. Must be "OnlineService".
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     service_type: Option<OnlineServiceType>,
@@ -1017,7 +1454,6 @@ pub struct OnlineService {
 }
 
 /// OnlineService @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum OnlineServiceType {
     /// OnlineService @type
@@ -1025,11 +1461,11 @@ enum OnlineServiceType {
 }
 
 /// Defines phone numbers for the entity, including features like voice or text.
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(not(feature = "redact-debug"), derive(Debug))]
 #[serde(rename_all = "camelCase")]
 pub struct Phone {
     /// The JSContact type of the object. Must be "Phone".
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     phone_type: Option<PhoneType>,
@@ -1070,21 +1506,26 @@ pub enum PhoneFeature {
     Video,
     ///  this number supports calling by voice.
     Voice,
+    /// a vendor-specific or future feature (e.g. `example.com:satellite`), kept as is
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// The contexts in which to use the contact information.
 /// For example, someone might have distinct phone numbers for work and private contexts and may set the desired context on the respective phone number in the phones property.
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum Context {
     /// the contact information that may be used in a private context.
     Private,
     /// the contact information that may be used in a professional context.
     Work,
+    /// a vendor-specific or future context (e.g. `example.com:oncall`), kept as is
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// Phone @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum PhoneType {
     /// Phone @type
@@ -1095,7 +1536,6 @@ impl Phone {
     /// Creates a new Phone object with the specified phone number.
     pub fn new(number: &str) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             phone_type: Some(PhoneType::Phone),
             number: number.to_string(),
             features: None,
@@ -1111,7 +1551,6 @@ impl Phone {
 #[serde(rename_all = "camelCase")]
 pub struct LanguagePref {
     /// The JSContact type of the object. Must be "LanguagePref".
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     lang_pref_type: Option<LanguagePrefType>,
@@ -1126,7 +1565,6 @@ pub struct LanguagePref {
 }
 
 /// LanguagePref @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum LanguagePrefType {
     /// LanguagePref @type
@@ -1137,7 +1575,6 @@ impl LanguagePref {
     /// Creates a new LanguagePref object with the specified language.
     pub fn new(language: &str) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             lang_pref_type: Some(LanguagePrefType::LanguagePref),
             language: language.to_string(),
             contexts: None,
@@ -1147,11 +1584,11 @@ impl LanguagePref {
 }
 
 /// Represents memorable dates and events for the entity.
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(not(feature = "redact-debug"), derive(Debug))]
 #[serde(rename_all = "camelCase")]
 pub struct Anniversary {
     /// The JSContact type of the object. Must be "Anniversary".
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     anniversary_type: Option<AnniversaryType>,
@@ -1161,10 +1598,10 @@ pub struct Anniversary {
     pub kind: AnniversaryKind,
     /// Contexts in which to use the anniversary.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub contexts: Option<HashMap<String, bool>>,
-    /// Preference of the anniversary relative to others.
+    pub contexts: Option<HashMap<SmallString, bool>>,
+    /// The place where the anniversary took place (e.g. the birthplace), or a reference to an address of the Card.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub place: Option<Address>,
+    pub place: Option<PlaceRef>,
 }
 
 /// The kind of anniversary
@@ -1177,6 +1614,9 @@ pub enum AnniversaryKind {
     Death,
     /// a wedding day anniversary
     Wedding,
+    /// a vendor-specific or future anniversary kind, kept as is
+    #[serde(untagged)]
+    Other(String),
 }
 
 impl From<String> for AnniversaryKind {
@@ -1185,13 +1625,38 @@ impl From<String> for AnniversaryKind {
             "birth" => AnniversaryKind::Birth,
             "death" => AnniversaryKind::Death,
             "wedding" => AnniversaryKind::Wedding,
-            _ => panic!("Invalid AnniversaryKind"),
+            _ => AnniversaryKind::Other(kind),
         }
     }
 }
 
+impl std::str::FromStr for AnniversaryKind {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(AnniversaryKind::from(s.to_string()))
+    }
+}
+
+impl AnniversaryKind {
+    /// Get the kind as a string, as serialized (e.g. `birth`), a vendor-specific kind as is.
+    pub fn as_str(&self) -> &str {
+        match self {
+            AnniversaryKind::Birth => "birth",
+            AnniversaryKind::Death => "death",
+            AnniversaryKind::Wedding => "wedding",
+            AnniversaryKind::Other(kind) => kind,
+        }
+    }
+}
+
+impl std::fmt::Display for AnniversaryKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// Anniversary @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum AnniversaryType {
     /// Anniversary @type
@@ -1199,10 +1664,14 @@ enum AnniversaryType {
 }
 
 impl Anniversary {
+    /// Get the kind as a string, as serialized, e.g. for a generic table of resources.
+    pub fn kind_str(&self) -> Option<&str> {
+        Some(self.kind.as_str())
+    }
+
     /// Creates a new Anniversary object with the specified date and kind.
     pub fn new(kind: AnniversaryKind, date: DateObject) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             anniversary_type: Some(AnniversaryType::Anniversary),
             date,
             kind,
@@ -1210,6 +1679,21 @@ impl Anniversary {
             place: None,
         }
     }
+
+    /// Creates a new birthday Anniversary.
+    pub fn birth(date: DateObject) -> Self {
+        Self::new(AnniversaryKind::Birth, date)
+    }
+
+    /// Creates a new deathday Anniversary.
+    pub fn death(date: DateObject) -> Self {
+        Self::new(AnniversaryKind::Death, date)
+    }
+
+    /// Creates a new wedding day Anniversary.
+    pub fn wedding(date: DateObject) -> Self {
+        Self::new(AnniversaryKind::Wedding, date)
+    }
 }
 
 /// Represents a date object, which can be a timestamp or a partial date.
@@ -1228,7 +1712,6 @@ pub enum DateObject {
 #[serde(rename_all = "camelCase")]
 pub struct Timestamp {
     /// The JSContact type of the object. The value MUST be "Timestamp", if set.
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     timestamp_type: Option<TimestampType>,
@@ -1238,7 +1721,6 @@ pub struct Timestamp {
 }
 
 /// Timestamp @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum TimestampType {
     /// Timestamp @type
@@ -1249,7 +1731,6 @@ impl Timestamp {
     /// Creates a new Timestamp object with the specified UTC time.
     pub fn new(utc: &str) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             timestamp_type: Some(TimestampType::Timestamp),
             utc: utc.to_string(),
         }
@@ -1261,7 +1742,6 @@ impl Timestamp {
 #[serde(rename_all = "camelCase")]
 pub struct PartialDate {
     /// The JSContact type of the object. The value MUST be "PartialDate", if set.
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     partial_date_type: Option<PartialDateType>,
@@ -1283,7 +1763,6 @@ pub struct PartialDate {
 }
 
 /// PartialDate @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum PartialDateType {
     /// PartialDate @type
@@ -1291,11 +1770,11 @@ enum PartialDateType {
 }
 
 /// The addresses of the entity represented by the Card, such as postal addresses or geographic locations.
-#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, Default, PartialEq, Clone)]
+#[cfg_attr(not(feature = "redact-debug"), derive(Debug))]
 #[serde(rename_all = "camelCase")]
 pub struct Address {
     /// The JSContact type of the object. The value MUST be "Address", if set.
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     address_type: Option<AddressType>,
@@ -1349,19 +1828,274 @@ pub enum AddressContext {
 }
 
 /// Address @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum AddressType {
     /// Address @type
     Address,
 }
 
+impl Address {
+    /// Creates a new ordered Address from the common "street, locality, region postcode" parts.
+    ///
+    /// The region and the postcode are separated by a space, the other components by the `", "` default separator,
+    /// and the empty parts are skipped. The country is only set as the country code.
+    ///
+    /// ```rust
+    /// use jscontact::Address;
+    ///
+    /// let address = Address::from_simple("54321 Oak St", "Reston", Some("VA"), "20190", "US");
+    /// assert_eq!(address.locality(), Some("Reston"));
+    /// assert_eq!(address.country_code.as_deref(), Some("US"));
+    /// ```
+    ///
+    /// Addresses in another order are built from the components, e.g. a Japanese address without separators:
+    /// ```rust
+    /// use jscontact::{Address, AddressComponent};
+    ///
+    /// let mut address = Address::default();
+    /// address.components = Some(vec![
+    ///     AddressComponent::region("東京都"),
+    ///     AddressComponent::locality("千代田区"),
+    ///     AddressComponent::district("丸の内"),
+    ///     AddressComponent::block("1-9-1"),
+    /// ]);
+    /// address.is_ordered = Some(true);
+    /// address.default_separator = Some(String::new());
+    /// address.country_code = Some("JP".to_string());
+    /// assert_eq!(address.region(), Some("東京都"));
+    /// ```
+    pub fn from_simple(
+        street: &str,
+        locality: &str,
+        region: Option<&str>,
+        postcode: &str,
+        country_code: &str,
+    ) -> Self {
+        let mut components = Vec::new();
+        if !street.is_empty() {
+            components.push(AddressComponent::name(street));
+        }
+        if !locality.is_empty() {
+            components.push(AddressComponent::locality(locality));
+        }
+        match region.filter(|region| !region.is_empty()) {
+            Some(region) if !postcode.is_empty() => {
+                components.push(AddressComponent::region(region));
+                components.push(AddressComponent::separator(" "));
+                components.push(AddressComponent::postcode(postcode));
+            }
+            Some(region) => components.push(AddressComponent::region(region)),
+            None if !postcode.is_empty() => components.push(AddressComponent::postcode(postcode)),
+            None => {}
+        }
+        Self {
+            address_type: Some(AddressType::Address),
+            components: Some(components),
+            is_ordered: Some(true),
+            country_code: (!country_code.is_empty()).then(|| country_code.to_string()),
+            default_separator: Some(", ".to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Get the value of the first component of the specified kind.
+    pub fn component(&self, kind: AddressComponentKind) -> Option<&str> {
+        self.components_of_kind(kind)
+            .next()
+            .map(|component| component.value.as_str())
+    }
+
+    /// Get all the components of the specified kind, in order.
+    pub fn components_of_kind(
+        &self,
+        kind: AddressComponentKind,
+    ) -> impl Iterator<Item = &AddressComponent> + '_ {
+        self.components
+            .iter()
+            .flatten()
+            .filter(move |component| component.kind == kind)
+    }
+
+    /// Set the value of the first component of the specified kind.
+    ///
+    /// The component keeps its position if it exists, otherwise it is added after the other components.
+    pub fn set_component(&mut self, kind: AddressComponentKind, value: &str) {
+        let components = self.components.get_or_insert_with(Vec::new);
+        match components
+            .iter_mut()
+            .find(|component| component.kind == kind)
+        {
+            Some(component) => component.value = value.to_string(),
+            None => components.push(AddressComponent::new(kind, value)),
+        }
+    }
+
+    /// Get the first street name component of the address.
+    pub fn street_name(&self) -> Option<&str> {
+        self.component(AddressComponentKind::Name)
+    }
+
+    /// Set the street name component of the address, see [`Address::set_component`].
+    pub fn set_street_name(&mut self, value: &str) {
+        self.set_component(AddressComponentKind::Name, value)
+    }
+
+    /// Get the first street number component of the address.
+    pub fn street_number(&self) -> Option<&str> {
+        self.component(AddressComponentKind::Number)
+    }
+
+    /// Set the street number component of the address, see [`Address::set_component`].
+    pub fn set_street_number(&mut self, value: &str) {
+        self.set_component(AddressComponentKind::Number, value)
+    }
+
+    /// Get the first apartment component of the address.
+    pub fn apartment(&self) -> Option<&str> {
+        self.component(AddressComponentKind::Apartment)
+    }
+
+    /// Set the apartment component of the address, see [`Address::set_component`].
+    pub fn set_apartment(&mut self, value: &str) {
+        self.set_component(AddressComponentKind::Apartment, value)
+    }
+
+    /// Get the first room component of the address.
+    pub fn room(&self) -> Option<&str> {
+        self.component(AddressComponentKind::Room)
+    }
+
+    /// Set the room component of the address, see [`Address::set_component`].
+    pub fn set_room(&mut self, value: &str) {
+        self.set_component(AddressComponentKind::Room, value)
+    }
+
+    /// Get the first floor component of the address.
+    pub fn floor(&self) -> Option<&str> {
+        self.component(AddressComponentKind::Floor)
+    }
+
+    /// Set the floor component of the address, see [`Address::set_component`].
+    pub fn set_floor(&mut self, value: &str) {
+        self.set_component(AddressComponentKind::Floor, value)
+    }
+
+    /// Get the first building component of the address.
+    pub fn building(&self) -> Option<&str> {
+        self.component(AddressComponentKind::Building)
+    }
+
+    /// Set the building component of the address, see [`Address::set_component`].
+    pub fn set_building(&mut self, value: &str) {
+        self.set_component(AddressComponentKind::Building, value)
+    }
+
+    /// Get the first block component of the address.
+    pub fn block(&self) -> Option<&str> {
+        self.component(AddressComponentKind::Block)
+    }
+
+    /// Set the block component of the address, see [`Address::set_component`].
+    pub fn set_block(&mut self, value: &str) {
+        self.set_component(AddressComponentKind::Block, value)
+    }
+
+    /// Get the first direction component of the address.
+    pub fn direction(&self) -> Option<&str> {
+        self.component(AddressComponentKind::Direction)
+    }
+
+    /// Set the direction component of the address, see [`Address::set_component`].
+    pub fn set_direction(&mut self, value: &str) {
+        self.set_component(AddressComponentKind::Direction, value)
+    }
+
+    /// Get the first landmark component of the address.
+    pub fn landmark(&self) -> Option<&str> {
+        self.component(AddressComponentKind::Landmark)
+    }
+
+    /// Set the landmark component of the address, see [`Address::set_component`].
+    pub fn set_landmark(&mut self, value: &str) {
+        self.set_component(AddressComponentKind::Landmark, value)
+    }
+
+    /// Get the first subdistrict component of the address.
+    pub fn subdistrict(&self) -> Option<&str> {
+        self.component(AddressComponentKind::Subdistrict)
+    }
+
+    /// Set the subdistrict component of the address, see [`Address::set_component`].
+    pub fn set_subdistrict(&mut self, value: &str) {
+        self.set_component(AddressComponentKind::Subdistrict, value)
+    }
+
+    /// Get the first district component of the address.
+    pub fn district(&self) -> Option<&str> {
+        self.component(AddressComponentKind::District)
+    }
+
+    /// Set the district component of the address, see [`Address::set_component`].
+    pub fn set_district(&mut self, value: &str) {
+        self.set_component(AddressComponentKind::District, value)
+    }
+
+    /// Get the first locality component of the address.
+    pub fn locality(&self) -> Option<&str> {
+        self.component(AddressComponentKind::Locality)
+    }
+
+    /// Set the locality component of the address, see [`Address::set_component`].
+    pub fn set_locality(&mut self, value: &str) {
+        self.set_component(AddressComponentKind::Locality, value)
+    }
+
+    /// Get the first region component of the address.
+    pub fn region(&self) -> Option<&str> {
+        self.component(AddressComponentKind::Region)
+    }
+
+    /// Set the region component of the address, see [`Address::set_component`].
+    pub fn set_region(&mut self, value: &str) {
+        self.set_component(AddressComponentKind::Region, value)
+    }
+
+    /// Get the first postcode component of the address.
+    pub fn postcode(&self) -> Option<&str> {
+        self.component(AddressComponentKind::Postcode)
+    }
+
+    /// Set the postcode component of the address, see [`Address::set_component`].
+    pub fn set_postcode(&mut self, value: &str) {
+        self.set_component(AddressComponentKind::Postcode, value)
+    }
+
+    /// Get the first country name component of the address.
+    pub fn country_name(&self) -> Option<&str> {
+        self.component(AddressComponentKind::Country)
+    }
+
+    /// Set the country name component of the address, see [`Address::set_component`].
+    pub fn set_country_name(&mut self, value: &str) {
+        self.set_component(AddressComponentKind::Country, value)
+    }
+
+    /// Get the first post office box component of the address.
+    pub fn post_office_box(&self) -> Option<&str> {
+        self.component(AddressComponentKind::PostOfficeBox)
+    }
+
+    /// Set the post office box component of the address, see [`Address::set_component`].
+    pub fn set_post_office_box(&mut self, value: &str) {
+        self.set_component(AddressComponentKind::PostOfficeBox, value)
+    }
+}
+
 /// The components that make up the address.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AddressComponent {
     /// The JSContact type of the object. The value MUST be "AddressComponent", if set.
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     component_type: Option<AddressComponentType>,
@@ -1375,7 +2109,6 @@ pub struct AddressComponent {
 }
 
 /// AddressComponent @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum AddressComponentType {
     /// AddressComponent @type
@@ -1386,13 +2119,97 @@ impl AddressComponent {
     /// Creates a new AddressComponent object with the specified kind and value.
     pub fn new(kind: AddressComponentKind, value: &str) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             component_type: Some(AddressComponentType::AddressComponent),
             value: value.to_string(),
             kind,
             phonetic: None,
         }
     }
+
+    /// Creates a new AddressComponent of an apartment.
+    pub fn apartment(value: &str) -> Self {
+        Self::new(AddressComponentKind::Apartment, value)
+    }
+
+    /// Creates a new AddressComponent of a block.
+    pub fn block(value: &str) -> Self {
+        Self::new(AddressComponentKind::Block, value)
+    }
+
+    /// Creates a new AddressComponent of a building.
+    pub fn building(value: &str) -> Self {
+        Self::new(AddressComponentKind::Building, value)
+    }
+
+    /// Creates a new AddressComponent of a country name.
+    pub fn country(value: &str) -> Self {
+        Self::new(AddressComponentKind::Country, value)
+    }
+
+    /// Creates a new AddressComponent of a direction.
+    pub fn direction(value: &str) -> Self {
+        Self::new(AddressComponentKind::Direction, value)
+    }
+
+    /// Creates a new AddressComponent of a district.
+    pub fn district(value: &str) -> Self {
+        Self::new(AddressComponentKind::District, value)
+    }
+
+    /// Creates a new AddressComponent of a floor.
+    pub fn floor(value: &str) -> Self {
+        Self::new(AddressComponentKind::Floor, value)
+    }
+
+    /// Creates a new AddressComponent of a landmark.
+    pub fn landmark(value: &str) -> Self {
+        Self::new(AddressComponentKind::Landmark, value)
+    }
+
+    /// Creates a new AddressComponent of a locality.
+    pub fn locality(value: &str) -> Self {
+        Self::new(AddressComponentKind::Locality, value)
+    }
+
+    /// Creates a new AddressComponent of a street name.
+    pub fn name(value: &str) -> Self {
+        Self::new(AddressComponentKind::Name, value)
+    }
+
+    /// Creates a new AddressComponent of a street number.
+    pub fn number(value: &str) -> Self {
+        Self::new(AddressComponentKind::Number, value)
+    }
+
+    /// Creates a new AddressComponent of a postcode.
+    pub fn postcode(value: &str) -> Self {
+        Self::new(AddressComponentKind::Postcode, value)
+    }
+
+    /// Creates a new AddressComponent of a post office box.
+    pub fn post_office_box(value: &str) -> Self {
+        Self::new(AddressComponentKind::PostOfficeBox, value)
+    }
+
+    /// Creates a new AddressComponent of a region.
+    pub fn region(value: &str) -> Self {
+        Self::new(AddressComponentKind::Region, value)
+    }
+
+    /// Creates a new AddressComponent of a room.
+    pub fn room(value: &str) -> Self {
+        Self::new(AddressComponentKind::Room, value)
+    }
+
+    /// Creates a new AddressComponent of a separator.
+    pub fn separator(value: &str) -> Self {
+        Self::new(AddressComponentKind::Separator, value)
+    }
+
+    /// Creates a new AddressComponent of a subdistrict.
+    pub fn subdistrict(value: &str) -> Self {
+        Self::new(AddressComponentKind::Subdistrict, value)
+    }
 }
 
 /// The kind of the address component.
@@ -1424,7 +2241,6 @@ pub enum AddressComponentKind {
     /// the postal code, post code, ZIP code, or other short code associated with the address by the relevant country's postal system.
     Postcode,
     ///  the post office box number or identifier.
-    #[serde(rename = "postOfficeBox")]
     PostOfficeBox,
     /// the administrative area such as province, state, prefecture, county, or canton.
     Region,
@@ -1436,6 +2252,34 @@ pub enum AddressComponentKind {
     Subdistrict,
 }
 
+impl AddressComponentKind {
+    /// The display order of the components of an address that is not ordered, from the street to the country,
+    /// as the lines of the default layout of [`CountryAddressFormatter`].
+    pub const DEFAULT_DISPLAY_ORDER: [AddressComponentKind; 16] = [
+        AddressComponentKind::Number,
+        AddressComponentKind::Direction,
+        AddressComponentKind::Name,
+        AddressComponentKind::Building,
+        AddressComponentKind::Floor,
+        AddressComponentKind::Apartment,
+        AddressComponentKind::Room,
+        AddressComponentKind::Landmark,
+        AddressComponentKind::Block,
+        AddressComponentKind::Subdistrict,
+        AddressComponentKind::District,
+        AddressComponentKind::PostOfficeBox,
+        AddressComponentKind::Locality,
+        AddressComponentKind::Region,
+        AddressComponentKind::Postcode,
+        AddressComponentKind::Country,
+    ];
+
+    /// Get the display order of the components of an address that is not ordered, see [`AddressComponentKind::DEFAULT_DISPLAY_ORDER`].
+    pub fn default_display_order() -> &'static [AddressComponentKind] {
+        &Self::DEFAULT_DISPLAY_ORDER
+    }
+}
+
 impl From<String> for AddressComponentKind {
     fn from(kind: String) -> Self {
         match kind.as_str() {
@@ -1462,11 +2306,11 @@ impl From<String> for AddressComponentKind {
 }
 
 /// The free-text notes that are associated with the Card.
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(not(feature = "redact-debug"), derive(Debug))]
 #[serde(rename_all = "camelCase")]
 pub struct Note {
     /// The JSContact type of the object. The value MUST be "Note", if set.
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     note_type: Option<NoteType>,
@@ -1481,19 +2325,29 @@ pub struct Note {
 }
 
 /// Note @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum NoteType {
     /// Note @type
     Note,
 }
 
+impl Note {
+    /// Creates a new Note object with the specified note.
+    pub fn new(note: &str) -> Self {
+        Self {
+            note_type: Some(NoteType::Note),
+            note: note.to_string(),
+            created: None,
+            author: None,
+        }
+    }
+}
+
 /// The author of a note.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Author {
     /// The JSContact type of the object. The value MUST be "Author", if set.
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     author_type: Option<AuthorType>,
@@ -1506,7 +2360,6 @@ pub struct Author {
 }
 
 /// Author @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum AuthorType {
     /// Author @type
@@ -1518,7 +2371,6 @@ enum AuthorType {
 #[serde(rename_all = "camelCase")]
 pub struct PersonalInfo {
     ///The JSContact type of the object.  The value MUST be "PersonalInfo", if set.
-    #[cfg(feature = "typed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "@type")]
     personal_info_type: Option<PersonalInfoType>,
@@ -1560,8 +2412,24 @@ impl From<String> for PersonalInfoKind {
     }
 }
 
+impl PersonalInfoKind {
+    /// Get the kind as a string, as serialized (e.g. `interest`).
+    pub fn as_str(&self) -> &str {
+        match self {
+            PersonalInfoKind::Expertise => "expertise",
+            PersonalInfoKind::Hobby => "hobby",
+            PersonalInfoKind::Interest => "interest",
+        }
+    }
+}
+
+impl std::fmt::Display for PersonalInfoKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// PersonalInfo @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 enum PersonalInfoType {
     /// PersonalInfo @type
@@ -1569,10 +2437,14 @@ enum PersonalInfoType {
 }
 
 impl PersonalInfo {
+    /// Get the kind as a string, as serialized, e.g. for a generic table of resources.
+    pub fn kind_str(&self) -> Option<&str> {
+        Some(self.kind.as_str())
+    }
+
     /// Creates a new PersonalInfo object with the specified kind and value.
     pub fn new(kind: PersonalInfoKind, value: &str) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             personal_info_type: Some(PersonalInfoType::PersonalInfo),
             kind,
             value: value.to_string(),
@@ -0,0 +1,363 @@
+//! Limits on the size of the Cards.
+//!
+//! The JSON read from an untrusted source (e.g. a socket) is limited in size: a reader is wrapped with a counting
+//! limiter, so a stream larger than the limit is rejected with a [`ReadError::TooLarge`] error once the limit is
+//! reached, instead of being buffered whole. [`Card::from_reader`], [`crate::parse`] and
+//! [`CardSet::load_with_report`] use [`DEFAULT_MAX_BYTES`].
+//!
+//! The limits advertised by a server (e.g. at most 10 emails, notes of at most 4096 bytes) are checked before
+//! sending a Card with [`Card::check_limits`]. A [`LimitViolation`] is not an RFC 9553 violation, the Card may be
+//! valid (see [`Card::validate`]).
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, Read};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::path::PathSegment;
+use crate::serialize::MAP_PROPERTIES;
+use crate::text::for_each_card_text;
+use crate::{Card, CardPath, CardSet};
+
+/// The default maximum size of the JSON, in bytes: 64 MiB, enough for an address book with inline photos.
+pub const DEFAULT_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+/// An error of [`Card::from_reader_limited`] and [`CardSet::from_reader_limited`].
+#[derive(Debug)]
+pub enum ReadError {
+    /// The input is larger than the limit, in bytes.
+    TooLarge {
+        /// The maximum size of the input, in bytes.
+        limit: usize,
+    },
+    /// The input cannot be read.
+    Io(io::Error),
+    /// The input is not valid JSON or not a valid Card.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::TooLarge { limit } => {
+                write!(f, "The input is larger than the limit of {} bytes", limit)
+            }
+            ReadError::Io(error) => write!(f, "{}", error),
+            ReadError::Json(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadError::TooLarge { .. } => None,
+            ReadError::Io(error) => Some(error),
+            ReadError::Json(error) => Some(error),
+        }
+    }
+}
+
+/// A reader failing once more than a number of bytes was read
+pub(crate) struct Limited<R> {
+    /// The wrapped reader
+    inner: R,
+    /// The maximum number of bytes
+    limit: usize,
+    /// The number of bytes that can still be read
+    remaining: usize,
+    /// If the input is larger than the limit
+    exceeded: bool,
+}
+
+impl<R: Read> Limited<R> {
+    /// Wrap a reader
+    pub(crate) fn new(inner: R, limit: usize) -> Self {
+        Self {
+            inner,
+            limit,
+            remaining: limit,
+            exceeded: false,
+        }
+    }
+
+    /// Convert an error of the deserialization, which may come from the limit
+    pub(crate) fn error(&self, error: serde_json::Error) -> ReadError {
+        if self.exceeded {
+            return ReadError::TooLarge { limit: self.limit };
+        }
+        ReadError::Json(error)
+    }
+}
+
+impl<R: Read> Read for Limited<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.remaining == 0 {
+            // the input may end exactly at the limit
+            let mut byte = [0; 1];
+            if self.inner.read(&mut byte)? == 0 {
+                return Ok(0);
+            }
+            self.exceeded = true;
+            return Err(io::Error::other(ReadError::TooLarge { limit: self.limit }));
+        }
+        let max = buf.len().min(self.remaining);
+        let read = self.inner.read(&mut buf[..max])?;
+        self.remaining -= read;
+        Ok(read)
+    }
+}
+
+/// Read text of at most a number of bytes
+pub(crate) fn read_to_string_limited<R: Read>(
+    reader: R,
+    limit: usize,
+) -> Result<String, ReadError> {
+    let mut limited = Limited::new(reader, limit);
+    let mut text = String::new();
+    match limited.read_to_string(&mut text) {
+        Ok(_) => Ok(text),
+        Err(_) if limited.exceeded => Err(ReadError::TooLarge { limit }),
+        Err(error) => Err(ReadError::Io(error)),
+    }
+}
+
+/// Check the size of JSON text already read
+pub(crate) fn check_size(json: &str, limit: usize) -> Result<(), ReadError> {
+    if json.len() > limit {
+        return Err(ReadError::TooLarge { limit });
+    }
+    Ok(())
+}
+
+impl Card {
+    /// Deserialize a Card from a reader of at most `max_bytes` bytes, without buffering a larger input.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, ReadError};
+    ///
+    /// let json = r#"{"@type":"Card","version":"1.0","uid":"1"}"#;
+    /// assert!(Card::from_reader_limited(json.as_bytes(), json.len()).is_ok());
+    /// let error = Card::from_reader_limited(json.as_bytes(), 10).unwrap_err();
+    /// assert!(matches!(error, ReadError::TooLarge { limit: 10 }));
+    /// ```
+    /// # Errors
+    /// Will return an error if the input is larger than the limit, cannot be read or is not a valid Card object.
+    pub fn from_reader_limited<R: Read>(reader: R, max_bytes: usize) -> Result<Card, ReadError> {
+        let mut limited = Limited::new(reader, max_bytes);
+        serde_json::from_reader(&mut limited).map_err(|error| limited.error(error))
+    }
+}
+
+impl CardSet {
+    /// Deserialize an array of Cards from a reader of at most `max_bytes` bytes, see [`Card::from_reader_limited`].
+    /// # Errors
+    /// Will return an error if the input is larger than the limit, cannot be read or is not a valid array of Cards.
+    pub fn from_reader_limited<R: Read>(reader: R, max_bytes: usize) -> Result<CardSet, ReadError> {
+        let mut limited = Limited::new(reader, max_bytes);
+        serde_json::from_reader(&mut limited).map_err(|error| limited.error(error))
+    }
+}
+
+/// The class of a text of a Card, for the length limits of [`Limits`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum TextClass {
+    /// The texts of the `name` and the names of the nicknames, organizations and titles.
+    Name,
+    /// The texts of the notes.
+    Note,
+    /// The `uid` and the `uri` of the resources.
+    Uri,
+    /// The other texts.
+    Other,
+}
+
+impl TextClass {
+    /// Get the class of the text at a path, e.g. [`TextClass::Note`] for `notes/n1/note`.
+    pub fn of(path: &CardPath) -> TextClass {
+        let field = match path.segments.as_slice() {
+            [_, PathSegment::Key(field)] => Some(field.as_str()),
+            _ => None,
+        };
+        match (path.property.as_str(), field) {
+            ("uid", _) | (_, Some("uri")) => TextClass::Uri,
+            ("notes", Some("note")) => TextClass::Note,
+            ("name", _) | ("nicknames" | "organizations" | "titles", Some("name")) => {
+                TextClass::Name
+            }
+            _ => TextClass::Other,
+        }
+    }
+}
+
+/// The limits of [`Card::check_limits`], `None` for no limit.
+///
+/// The lengths and the sizes are in bytes of UTF-8, as the sizes of JMAP.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum number of entries of each map property (e.g. `emails`).
+    pub max_entries: Option<usize>,
+    /// The maximum number of entries of some map properties, overriding `max_entries` (e.g. 10 for `emails`).
+    pub max_entries_by_property: BTreeMap<String, usize>,
+    /// The maximum length of the texts of the [`TextClass::Name`] class.
+    pub max_name_length: Option<usize>,
+    /// The maximum length of the texts of the [`TextClass::Note`] class.
+    pub max_note_length: Option<usize>,
+    /// The maximum length of the texts of the [`TextClass::Uri`] class.
+    pub max_uri_length: Option<usize>,
+    /// The maximum length of the texts of the [`TextClass::Other`] class.
+    pub max_text_length: Option<usize>,
+    /// The maximum size of the JSON of the Card.
+    pub max_bytes: Option<usize>,
+}
+
+impl Limits {
+    /// A conservative preset in the style of the limits advertised by JMAP servers: 100 entries per map property,
+    /// names of 1024 bytes, notes of 4096 bytes, URIs of 2048 bytes (an inline photo is larger), other texts of
+    /// 1024 bytes and Cards of 256 KiB.
+    pub fn jmap_defaults() -> Self {
+        Self {
+            max_entries: Some(100),
+            max_entries_by_property: BTreeMap::new(),
+            max_name_length: Some(1024),
+            max_note_length: Some(4096),
+            max_uri_length: Some(2048),
+            max_text_length: Some(1024),
+            max_bytes: Some(256 * 1024),
+        }
+    }
+
+    /// Get the maximum length of the texts of a class.
+    pub fn max_length(&self, class: TextClass) -> Option<usize> {
+        match class {
+            TextClass::Name => self.max_name_length,
+            TextClass::Note => self.max_note_length,
+            TextClass::Uri => self.max_uri_length,
+            TextClass::Other => self.max_text_length,
+        }
+    }
+
+    /// Get the maximum number of entries of a map property.
+    pub fn max_entries_of(&self, property: &str) -> Option<usize> {
+        self.max_entries_by_property
+            .get(property)
+            .copied()
+            .or(self.max_entries)
+    }
+}
+
+/// The kind of a limit of [`Limits`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum LimitKind {
+    /// The number of entries of a map property.
+    Entries,
+    /// The length of a text.
+    Length,
+    /// The size of the JSON of the Card.
+    Size,
+}
+
+/// A limit exceeded by a Card, see [`Card::check_limits`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LimitViolation {
+    /// The path of the value (e.g. `emails` or `notes/n1/note`), empty for the whole Card.
+    pub path: String,
+    /// The kind of the limit.
+    pub kind: LimitKind,
+    /// The limit.
+    pub limit: usize,
+    /// The actual value, above the limit.
+    pub actual: usize,
+}
+
+impl fmt::Display for LimitViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "the Card"
+        } else {
+            &self.path
+        };
+        let unit = match self.kind {
+            LimitKind::Entries => "entries",
+            LimitKind::Length | LimitKind::Size => "bytes",
+        };
+        write!(
+            f,
+            "{}: {} {}, the limit is {}",
+            path, self.actual, unit, self.limit
+        )
+    }
+}
+
+impl Card {
+    /// Check the Card against the limits of a server, returning the exceeded limits sorted by path.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, LimitKind, Limits};
+    ///
+    /// let mut card = Card::new_with_latest_version("1234");
+    /// card.add_keyword("friend");
+    /// card.add_keyword("family");
+    /// let mut limits = Limits::default();
+    /// limits.max_entries_by_property.insert("keywords".to_string(), 1);
+    /// let violations = card.check_limits(&limits);
+    /// assert_eq!(violations[0].kind, LimitKind::Entries);
+    /// assert_eq!(violations[0].to_string(), "keywords: 2 entries, the limit is 1");
+    /// ```
+    pub fn check_limits(&self, limits: &Limits) -> Vec<LimitViolation> {
+        let mut violations = Vec::new();
+        let Ok(Value::Object(properties)) = serde_json::to_value(self) else {
+            return violations;
+        };
+        if let Some(limit) = limits.max_bytes {
+            let actual = serde_json::to_vec(&properties).map_or(0, |json| json.len());
+            if actual > limit {
+                violations.push(LimitViolation {
+                    path: String::new(),
+                    kind: LimitKind::Size,
+                    limit,
+                    actual,
+                });
+            }
+        }
+        for (property, value) in &properties {
+            let (Some(entries), Some(limit)) = (
+                value
+                    .as_object()
+                    .filter(|_| MAP_PROPERTIES.contains(&property.as_str())),
+                limits.max_entries_of(property),
+            ) else {
+                continue;
+            };
+            if entries.len() > limit {
+                violations.push(LimitViolation {
+                    path: PathSegment::Key(property.clone()).to_string(),
+                    kind: LimitKind::Entries,
+                    limit,
+                    actual: entries.len(),
+                });
+            }
+        }
+        for_each_card_text(self, |path, text| {
+            if let Some(limit) = limits.max_length(TextClass::of(path)) {
+                if text.len() > limit {
+                    violations.push(LimitViolation {
+                        path: path.to_string(),
+                        kind: LimitKind::Length,
+                        limit,
+                        actual: text.len(),
+                    });
+                }
+            }
+        });
+        violations.sort_by(|a, b| a.path.cmp(&b.path));
+        violations
+    }
+}
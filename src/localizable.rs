@@ -0,0 +1,427 @@
+//! The properties of a [`Card`] that can be localized, checked before applying the patches of a localization.
+//!
+//! Only the text of a Card is localized (names, titles, addresses, labels, notes, ...),
+//! a patch of another value (e.g. `phones/tel0/contexts`) is a [`LocalizationIssue`].
+//! A patch replacing a whole object only localizes its localizable fields, the other fields must be unchanged.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::path::{get_value, PROPERTIES};
+use crate::{Card, CardPath, PathSegment};
+
+/// The localizable paths, `*` matching any map key or array index.
+///
+/// The values below a localizable path are localizable too.
+const LOCALIZABLE_PATHS: [&str; 23] = [
+    "name",
+    "nicknames/*/name",
+    "organizations/*/name",
+    "organizations/*/units",
+    "organizations/*/sortAs",
+    "speakToAs/pronouns/*/pronouns",
+    "titles/*/name",
+    "addresses/*/components",
+    "addresses/*/full",
+    "addresses/*/defaultSeparator",
+    "addresses/*/isOrdered",
+    "addresses/*/phoneticScript",
+    "addresses/*/phoneticSystem",
+    "anniversaries/*/place/components",
+    "anniversaries/*/place/full",
+    "anniversaries/*/place/defaultSeparator",
+    "anniversaries/*/place/isOrdered",
+    "*/*/label",
+    // a resource may be language-specific, e.g. a translated web page
+    "*/*/uri",
+    "*/*/mediaType",
+    "notes/*/note",
+    "personalInfo/*/value",
+    "keywords",
+];
+
+/// A patch of a localization that does not localize a localizable property.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LocalizationIssue {
+    /// The language of the localization.
+    pub language: String,
+    /// The path of the patched value (e.g. `phones/tel0/contexts`).
+    pub path: String,
+    /// The description of the issue.
+    pub message: String,
+}
+
+impl fmt::Display for LocalizationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.language, self.path, self.message)
+    }
+}
+
+/// The issues of the localizations of a Card found when parsing, ignored by the comparisons of Cards.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ParseIssues(pub(crate) Vec<LocalizationIssue>);
+
+impl PartialEq for ParseIssues {
+    fn eq(&self, _other: &Self) -> bool {
+        // two Cards with the same content are equal however they were parsed
+        true
+    }
+}
+
+/// The limits of the patches of a localization, so a hostile Card fails to localize instead of exhausting the memory.
+///
+/// A patch key with too many segments, too many patches for a language or an array index too far
+/// past the end of its array is an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchLimits {
+    /// The maximum number of segments of a patch path, the property included.
+    pub max_depth: usize,
+    /// The maximum number of elements between the end of an array and the index of a patch,
+    /// `0` only allowing to patch the existing elements or append one.
+    pub max_index_delta: usize,
+    /// The maximum number of patches of a language.
+    pub max_patches: usize,
+}
+
+impl Default for PatchLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 16,
+            max_index_delta: 64,
+            max_patches: 10_000,
+        }
+    }
+}
+
+impl PatchLimits {
+    /// Check the number of patches and the depth of their paths, before parsing them
+    fn check_patches(
+        &self,
+        language: &str,
+        patches: &HashMap<String, Value>,
+    ) -> Result<(), String> {
+        if patches.len() > self.max_patches {
+            return Err(format!(
+                "Too many patches for '{}': {} (maximum {})",
+                language,
+                patches.len(),
+                self.max_patches
+            ));
+        }
+        for key in patches.keys() {
+            let depth = key.bytes().filter(|b| *b == b'/').count() + 1;
+            if depth > self.max_depth {
+                return Err(format!(
+                    "Invalid path '{}...': {} segments (maximum {})",
+                    key.chars().take(32).collect::<String>(),
+                    depth,
+                    self.max_depth
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the array indices of a path against the length of the arrays of the Card
+    fn check_indices(&self, card: &Card, path: &CardPath) -> Result<(), String> {
+        for (position, segment) in path.segments.iter().enumerate() {
+            let PathSegment::Index(idx) = segment else {
+                continue;
+            };
+            if *idx <= self.max_index_delta {
+                continue;
+            }
+            let parent = CardPath {
+                property: path.property.clone(),
+                segments: path.segments[..position].to_vec(),
+            };
+            let len = match get_value(card, &parent) {
+                Some(Value::Array(values)) => values.len(),
+                _ => 0,
+            };
+            if idx.saturating_sub(len) > self.max_index_delta {
+                return Err(format!(
+                    "Invalid path '{}': index {} is more than {} elements past the end of the array",
+                    path, idx, self.max_index_delta
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Get the segments of a path, the property first
+fn path_parts(path: &CardPath) -> Vec<String> {
+    let mut parts = vec![path.property.clone()];
+    parts.extend(path.segments.iter().map(|segment| match segment {
+        PathSegment::Key(key) => key.clone(),
+        PathSegment::Index(idx) => idx.to_string(),
+    }));
+    parts
+}
+
+/// Check if the start of a path matches a pattern
+fn matches(pattern: &[&str], parts: &[String]) -> bool {
+    pattern
+        .iter()
+        .zip(parts)
+        .all(|(expected, part)| *expected == "*" || expected == part)
+}
+
+/// Check if a path is localizable
+fn is_localizable(parts: &[String]) -> bool {
+    LOCALIZABLE_PATHS.iter().any(|pattern| {
+        let pattern: Vec<&str> = pattern.split('/').collect();
+        pattern.len() <= parts.len() && matches(&pattern, parts)
+    })
+}
+
+/// Check if a path is the parent of a localizable path, e.g. `addresses/k26`
+fn has_localizable_children(parts: &[String]) -> bool {
+    LOCALIZABLE_PATHS.iter().any(|pattern| {
+        let pattern: Vec<&str> = pattern.split('/').collect();
+        pattern.len() > parts.len() && matches(&pattern, parts)
+    })
+}
+
+/// Get the path of a field of an object
+fn child(path: &CardPath, key: &str) -> CardPath {
+    let mut child = path.clone();
+    child.segments.push(PathSegment::Key(key.to_string()));
+    child
+}
+
+/// Keep the localizable values of a patch, an issue is recorded for each other value changing the Card.
+///
+/// The non-localizable fields of the Card missing from an object patch are kept.
+/// Returns `None` if nothing is left to patch.
+fn localizable_value(
+    card: &Card,
+    path: &CardPath,
+    value: Value,
+    issues: &mut Vec<(String, String)>,
+) -> Option<Value> {
+    let parts = path_parts(path);
+    if is_localizable(&parts) {
+        return Some(value);
+    }
+    let base = get_value(card, path);
+    let value = match value {
+        Value::Object(object) if has_localizable_children(&parts) => object,
+        value => {
+            if base.as_ref() == Some(&value) || (base.is_none() && value.is_null()) {
+                // unchanged
+                return Some(value);
+            }
+            issues.push((path.to_string(), "The value is not localizable".to_string()));
+            return None;
+        }
+    };
+    let mut localized = Map::new();
+    for (key, field) in value {
+        let field_path = child(path, &key);
+        if key == "@type" {
+            localized.insert(key, field);
+        } else if let Some(field) = localizable_value(card, &field_path, field, issues) {
+            localized.insert(key, field);
+        } else if let Some(base_field) = get_value(card, &field_path) {
+            localized.insert(key, base_field);
+        }
+    }
+    if let Some(Value::Object(base)) = base {
+        for (key, base_field) in base {
+            if !localized.contains_key(&key) && !is_localizable(&path_parts(&child(path, &key))) {
+                localized.insert(key, base_field);
+            }
+        }
+    }
+    Some(Value::Object(localized))
+}
+
+/// The patches of a localization, as (path, value)
+pub(crate) type Patches = Vec<(String, Value)>;
+
+/// Get the localizable patches of a localization, sorted by path, and the issues of the other patches.
+///
+/// A patch of an unknown property (e.g. a typo like `pgones/tel0/label`) is an issue, not an error,
+/// so the lenient localization skips it.
+///
+/// # Errors
+/// Will return an error if a path is invalid or if the patches exceed the limits.
+pub(crate) fn localizable_patches(
+    card: &Card,
+    language: &str,
+    patches: &HashMap<String, Value>,
+    limits: &PatchLimits,
+) -> Result<(Patches, Vec<LocalizationIssue>), String> {
+    limits.check_patches(language, patches)?;
+    let mut sorted: Vec<(&String, &Value)> = patches.iter().collect();
+    // a patch of an object is applied before the patches of its fields
+    sorted.sort_by_key(|(key, _)| *key);
+    let mut localizable = Vec::new();
+    let mut issues = Vec::new();
+    for (key, value) in sorted {
+        // the property is the exact first segment, e.g. `namespace/x` is not a patch of `name`
+        let property = key.split('/').next().unwrap_or_default();
+        if !property.is_empty() && !PROPERTIES.contains(&property) {
+            issues.push((
+                key.clone(),
+                format!(
+                    "Unknown property '{}', expected one of: {}",
+                    property,
+                    PROPERTIES.join(", ")
+                ),
+            ));
+            continue;
+        }
+        let path = CardPath::parse(key)?;
+        limits.check_indices(card, &path)?;
+        if let Some(value) = localizable_value(card, &path, value.clone(), &mut issues) {
+            localizable.push((key.clone(), value));
+        }
+    }
+    let issues = issues
+        .into_iter()
+        .map(|(path, message)| LocalizationIssue {
+            language: language.to_string(),
+            path,
+            message,
+        })
+        .collect();
+    Ok((localizable, issues))
+}
+
+/// What to do with a language of [`Card::merge_localizations_from`] that the Card already has, with other patches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LocalizationMergePolicy {
+    /// Fail without changing the Card.
+    #[default]
+    Error,
+    /// Replace the patches of the language by the patches of the other Card.
+    Overwrite,
+    /// Add the patches of the other Card to the patches of the language, the patches of the other Card winning.
+    MergePatches,
+}
+
+/// The languages of [`Card::merge_localizations_from`], sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocalizationMergeReport {
+    /// The languages the Card did not have.
+    pub added: Vec<String>,
+    /// The languages the Card had, overwritten or merged according to the policy.
+    pub merged: Vec<String>,
+    /// The languages the Card had with the same patches, left unchanged.
+    pub skipped: Vec<String>,
+}
+
+/// Check the patches of a language against a Card: the patches must be localizable and their parent must exist
+fn check_merged_patches(
+    card: &Card,
+    language: &str,
+    patches: &HashMap<String, Value>,
+) -> Result<(), String> {
+    let (localizable, issues) =
+        localizable_patches(card, language, patches, &PatchLimits::default())?;
+    if let Some(issue) = issues.first() {
+        return Err(issue.to_string());
+    }
+    for (key, _) in localizable {
+        let path = CardPath::parse(&key)?;
+        let Some((_, parent)) = path.segments.split_last() else {
+            continue;
+        };
+        let parent = CardPath {
+            property: path.property.clone(),
+            segments: parent.to_vec(),
+        };
+        if get_value(card, &parent).is_none() {
+            return Err(format!(
+                "{}: {}: the patched value does not exist in the Card",
+                language, key
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl Card {
+    /// Copy the localizations of another Card, e.g. a Card made by a translation workflow with only `localizations`.
+    ///
+    /// The patches are checked against this Card as by [`Card::get_localized`], and their parent must exist
+    /// (e.g. `titles/t1` for `titles/t1/name`). The Card is unchanged if an error is returned.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, LocalizationMergePolicy};
+    /// use serde_json::json;
+    ///
+    /// let mut card: Card = serde_json::from_value(json!({
+    ///     "@type": "Card", "version": "1.0", "uid": "1234",
+    ///     "titles": { "t1": { "name": "Research Scientist" } }
+    /// })).unwrap();
+    /// let translated: Card = serde_json::from_value(json!({
+    ///     "@type": "Card", "version": "1.0", "uid": "1234",
+    ///     "localizations": { "fr": { "titles/t1/name": "Chercheur" } }
+    /// })).unwrap();
+    /// let report = card.merge_localizations_from(&translated, LocalizationMergePolicy::Error).unwrap();
+    /// assert_eq!(report.added, ["fr"]);
+    /// assert_eq!(card.get_localized("fr").unwrap().titles.unwrap()["t1"].name, "Chercheur");
+    /// ```
+    /// # Errors
+    /// Will return an error if a patch is invalid for this Card,
+    /// or if a language has other patches with the [`LocalizationMergePolicy::Error`] policy.
+    pub fn merge_localizations_from(
+        &mut self,
+        other: &Card,
+        policy: LocalizationMergePolicy,
+    ) -> Result<LocalizationMergeReport, String> {
+        let mut report = LocalizationMergeReport::default();
+        let Some(donor) = &other.localizations else {
+            return Ok(report);
+        };
+        let mut languages: Vec<&String> = donor.keys().collect();
+        languages.sort();
+        let mut merged_patches = Vec::new();
+        for language in languages {
+            let patches = &donor[language];
+            let existing = self
+                .localizations
+                .as_ref()
+                .and_then(|localizations| localizations.get(language));
+            let patches = match (existing, policy) {
+                (None, _) => {
+                    report.added.push(language.clone());
+                    patches.clone()
+                }
+                (Some(existing), _) if existing == patches => {
+                    report.skipped.push(language.clone());
+                    continue;
+                }
+                (Some(_), LocalizationMergePolicy::Error) => {
+                    return Err(format!(
+                        "The Card already has other patches for '{}'",
+                        language
+                    ));
+                }
+                (Some(_), LocalizationMergePolicy::Overwrite) => {
+                    report.merged.push(language.clone());
+                    patches.clone()
+                }
+                (Some(existing), LocalizationMergePolicy::MergePatches) => {
+                    report.merged.push(language.clone());
+                    let mut existing = existing.clone();
+                    existing.extend(patches.clone());
+                    existing
+                }
+            };
+            check_merged_patches(self, language, &patches)?;
+            merged_patches.push((language.clone(), patches));
+        }
+        for (language, patches) in merged_patches {
+            self.add_localization(&language, patches.into_inner());
+        }
+        Ok(report)
+    }
+}
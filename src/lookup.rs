@@ -0,0 +1,64 @@
+//! Lookup of the entries of a map by a key typed by a human, e.g. in a configuration file (`Dir1` for `dir1`).
+//!
+//! The keys of the maps of RFC 9553 are case-sensitive identifiers: these helpers are a convenience that
+//! RFC 9553 does not define, not a way to compare the keys. The key is trimmed, then:
+//! 1. an entry with exactly the key wins
+//! 2. otherwise the entries whose key only differs by case match, the first key in sorted order winning
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{Address, Card, EmailAddress, Phone};
+
+/// Get the entry of a map whose key matches a key, ignoring the case and the surrounding whitespace of the key.
+///
+/// See the [module documentation](crate::lookup) for the entry returned when several keys match.
+///
+/// ```rust
+/// use jscontact::lookup::get_ci;
+/// use std::collections::HashMap;
+///
+/// let map = HashMap::from([("Dir1".to_string(), 1), ("dir1".to_string(), 2), ("DIR1".to_string(), 3)]);
+/// assert_eq!(get_ci(&map, "dir1"), Some((&"dir1".to_string(), &2)));
+/// assert_eq!(get_ci(&map, " dIr1 "), Some((&"DIR1".to_string(), &3)));
+/// assert_eq!(get_ci(&map, "dir2"), None);
+/// ```
+pub fn get_ci<'a, K, V>(map: &'a HashMap<K, V>, key: &str) -> Option<(&'a K, &'a V)>
+where
+    K: Borrow<str> + Eq + Hash + Ord,
+{
+    let key = key.trim();
+    if let Some(entry) = map.get_key_value(key) {
+        return Some(entry);
+    }
+    let key = key.to_lowercase();
+    map.iter()
+        .filter(|(candidate, _)| (*candidate).borrow().to_lowercase() == key)
+        .min_by(|(a, _), (b, _)| a.cmp(b))
+}
+
+/// Get the entry of an optional map, see [`get_ci`]
+fn entry_ci<'a, K, V>(map: &'a Option<HashMap<K, V>>, key: &str) -> Option<(&'a str, &'a V)>
+where
+    K: Borrow<str> + Eq + Hash + Ord,
+{
+    get_ci(map.as_ref()?, key).map(|(key, value)| (key.borrow(), value))
+}
+
+impl Card {
+    /// Get an email by its key, ignoring the case, with the key of the entry, see [`get_ci`].
+    pub fn email_by_key_ci(&self, key: &str) -> Option<(&str, &EmailAddress)> {
+        entry_ci(&self.emails, key)
+    }
+
+    /// Get a phone by its key, ignoring the case, with the key of the entry, see [`get_ci`].
+    pub fn phone_by_key_ci(&self, key: &str) -> Option<(&str, &Phone)> {
+        entry_ci(&self.phones, key)
+    }
+
+    /// Get an address by its key, ignoring the case, with the key of the entry, see [`get_ci`].
+    pub fn address_by_key_ci(&self, key: &str) -> Option<(&str, &Address)> {
+        entry_ci(&self.addresses, key)
+    }
+}
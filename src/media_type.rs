@@ -0,0 +1,240 @@
+//! Inference of the media type (RFC 2046) of the resources of a [`Card`] from their URI.
+//!
+//! The media type of a data URI (RFC 2397) is read from its header,
+//! the media type of another URI is guessed from the extension of its path.
+
+use crate::resource::uri_scheme;
+use crate::{Calendar, Card, CryptoKey, Link, Media, MediaKind, SmallString};
+
+/// The media types by file extension, the extensions are lowercase
+const MEDIA_TYPES: [(&str, &str); 29] = [
+    ("asc", "application/pgp-keys"),
+    ("bmp", "image/bmp"),
+    ("cer", "application/pkix-cert"),
+    ("crt", "application/pkix-cert"),
+    ("gif", "image/gif"),
+    ("gpg", "application/pgp-keys"),
+    ("heic", "image/heic"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("ics", "text/calendar"),
+    ("ifb", "text/calendar"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("json", "application/json"),
+    ("m4a", "audio/mp4"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("oga", "audio/ogg"),
+    ("ogg", "audio/ogg"),
+    ("pdf", "application/pdf"),
+    ("pem", "application/x-pem-file"),
+    ("pgp", "application/pgp-keys"),
+    ("png", "image/png"),
+    ("svg", "image/svg+xml"),
+    ("txt", "text/plain"),
+    ("vcard", "text/vcard"),
+    ("vcf", "text/vcard"),
+    ("wav", "audio/wav"),
+    ("webp", "image/webp"),
+];
+
+/// Get the media type of a URI, from the header of a data URI or from the extension of the path.
+///
+/// The media type of a data URI must be one of the media types of the embedded table,
+/// a data URI without media type is `text/plain`. Returns `None` if the media type is unknown.
+pub fn infer_media_type(uri: &str) -> Option<&'static str> {
+    let (scheme, rest) = uri.split_once(':')?;
+    if scheme.eq_ignore_ascii_case("data") {
+        let (header, _) = rest.split_once(',')?;
+        let media_type = header.split(';').next().unwrap_or_default().trim();
+        if media_type.is_empty() {
+            return Some("text/plain");
+        }
+        return MEDIA_TYPES
+            .iter()
+            .map(|(_, known)| *known)
+            .find(|known| known.eq_ignore_ascii_case(media_type));
+    }
+    let path = rest.split(['?', '#']).next().unwrap_or_default();
+    // the authority is not part of the path, e.g. `https://example.com`
+    let path = match path.strip_prefix("//") {
+        Some(authority_and_path) => authority_and_path.split_once('/')?.1,
+        None => path,
+    };
+    let file_name = path.rsplit('/').next().unwrap_or_default();
+    let (_, extension) = file_name.rsplit_once('.')?;
+    let extension = extension.to_ascii_lowercase();
+    MEDIA_TYPES
+        .iter()
+        .find(|(known, _)| *known == extension)
+        .map(|(_, media_type)| *media_type)
+}
+
+/// Set the media type of a resource from its URI if it is not set, true if the media type was set
+fn ensure_media_type(media_type: &mut Option<SmallString>, uri: &str) -> bool {
+    if media_type.is_some() {
+        return false;
+    }
+    *media_type = infer_media_type(uri).map(SmallString::from);
+    media_type.is_some()
+}
+
+impl Media {
+    /// Set the media type from the URI if it is not set, see [`infer_media_type`].
+    pub fn ensure_media_type(&mut self) -> bool {
+        ensure_media_type(&mut self.media_type, &self.uri)
+    }
+}
+
+impl CryptoKey {
+    /// Set the media type from the URI if it is not set, see [`infer_media_type`].
+    pub fn ensure_media_type(&mut self) -> bool {
+        ensure_media_type(&mut self.media_type, &self.uri)
+    }
+}
+
+/// Check if a media type is one of the media types, without its parameters (e.g. `text/calendar; charset=utf-8`)
+fn is_media_type(media_type: &str, expected: &[&str]) -> bool {
+    let essence = media_type.split(';').next().unwrap_or_default().trim();
+    expected
+        .iter()
+        .any(|expected| expected.eq_ignore_ascii_case(essence))
+}
+
+/// Check if a media type matches an accepted media type, which can be a wildcard (e.g. `image/*` or `*/*`)
+fn accepts(accepted: &str, media_type: &str) -> bool {
+    let accepted = accepted.split(';').next().unwrap_or_default().trim();
+    let essence = media_type.split(';').next().unwrap_or_default().trim();
+    if accepted == "*/*" {
+        return true;
+    }
+    match accepted.strip_suffix("/*") {
+        Some(top_level) => essence
+            .split_once('/')
+            .is_some_and(|(essence_top_level, _)| {
+                essence_top_level.eq_ignore_ascii_case(top_level)
+            }),
+        None => accepted.eq_ignore_ascii_case(essence),
+    }
+}
+
+/// Check if the scheme of a URI is one of the schemes, ignoring the case
+fn has_scheme(uri: &str, schemes: &[&str]) -> bool {
+    uri_scheme(uri).is_some_and(|scheme| {
+        schemes
+            .iter()
+            .any(|expected| expected.eq_ignore_ascii_case(scheme))
+    })
+}
+
+impl Calendar {
+    /// Set the media type from the URI if it is not set, see [`infer_media_type`].
+    pub fn ensure_media_type(&mut self) -> bool {
+        ensure_media_type(&mut self.media_type, &self.uri)
+    }
+
+    /// Check if the calendar is subscribed with the `webcal` (or `webcals`) scheme, to open it in a calendar application.
+    pub fn is_webcal(&self) -> bool {
+        has_scheme(&self.uri, &["webcal", "webcals"])
+    }
+
+    /// Check if the calendar is an iCalendar file: its media type is `text/calendar`,
+    /// or, without media type, its URI is a webcal one or has the `.ics` extension.
+    pub fn is_ics(&self) -> bool {
+        match &self.media_type {
+            Some(media_type) => is_media_type(media_type, &["text/calendar"]),
+            None => self.is_webcal() || infer_media_type(&self.uri) == Some("text/calendar"),
+        }
+    }
+}
+
+impl Link {
+    /// Check if the link is probably a web page, to open it in a browser.
+    ///
+    /// The media type is `text/html` or `application/xhtml+xml`, or, without media type,
+    /// the URI is an `http` or `https` one without an extension of another media type (e.g. `.pdf`).
+    pub fn is_probably_html(&self) -> bool {
+        if let Some(media_type) = &self.media_type {
+            return is_media_type(media_type, &["text/html", "application/xhtml+xml"]);
+        }
+        has_scheme(&self.uri, &["http", "https"])
+            && infer_media_type(&self.uri).is_none_or(|media_type| media_type == "text/html")
+    }
+}
+
+impl Card {
+    /// Set the media types of the media, crypto keys and calendars from their URI, if they are not set.
+    ///
+    /// Returns the number of media types that were set.
+    pub fn infer_media_types(&mut self) -> usize {
+        let media = self
+            .media
+            .iter_mut()
+            .flatten()
+            .map(|(_, media)| media.ensure_media_type());
+        let crypto_keys = self
+            .crypto_keys
+            .iter_mut()
+            .flatten()
+            .map(|(_, key)| key.ensure_media_type());
+        let calendars = self
+            .calendars
+            .iter_mut()
+            .flatten()
+            .map(|(_, calendar)| calendar.ensure_media_type());
+        let count = media
+            .chain(crypto_keys)
+            .chain(calendars)
+            .filter(|is_set| *is_set)
+            .count();
+        if count > 0 {
+            self.touch();
+        }
+        count
+    }
+
+    /// Get the photo of the Card to use for a client accepting the given media types, in order of preference.
+    ///
+    /// The photos are ranked by the first accepted media type they match (wildcards like `image/*` are allowed),
+    /// then by `pref`, then by key. The media type of a photo without `mediaType` is inferred from its URI
+    /// (see [`infer_media_type`]), the photos whose media type is unknown coming last.
+    /// If no photo matches, any photo is returned, the ones with a known media type first.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, Media, MediaKind};
+    ///
+    /// let mut card = Card::new_with_latest_version("1234");
+    /// let media = card.media.get_or_insert_with(Default::default);
+    /// media.insert("m1".into(), Media::new("https://example.com/photo.png", MediaKind::Photo));
+    /// media.insert("m2".into(), Media::new("https://example.com/photo.webp", MediaKind::Photo));
+    /// let photo = card.photo_preferring(&["image/webp", "image/png"]).unwrap();
+    /// assert_eq!(&*photo.uri, "https://example.com/photo.webp");
+    /// ```
+    pub fn photo_preferring(&self, accepted: &[&str]) -> Option<&Media> {
+        self.media
+            .iter()
+            .flatten()
+            .filter(|(_, media)| media.kind == MediaKind::Photo)
+            .min_by_key(|(key, media)| {
+                let media_type = media
+                    .media_type
+                    .as_deref()
+                    .or_else(|| infer_media_type(&media.uri));
+                let rank = media_type
+                    .and_then(|media_type| {
+                        accepted
+                            .iter()
+                            .position(|accepted| accepts(accepted, media_type))
+                    })
+                    .unwrap_or(accepted.len());
+                (
+                    rank,
+                    media_type.is_none(),
+                    media.pref.unwrap_or(u64::MAX),
+                    key.as_str(),
+                )
+            })
+            .map(|(_, media)| media)
+    }
+}
@@ -0,0 +1,313 @@
+//! Migrations of a [`Card`] between versions of the JSContact specification.
+//!
+//! A Card with a version unknown to this crate is deserialized with [`CardVersion::Other`],
+//! its unknown properties being kept in [`Card::extensions`].
+//! The migration steps implement [`VersionMigration`] and are registered in a [`MigrationRegistry`],
+//! [`Card::upgrade_to_with`] then chains the registered steps from the version of the Card to the target version.
+//!
+//! Some producers misname the required properties, e.g. `@version` instead of `version`. The known aliases
+//! are listed in [`PROPERTY_ALIASES`]: [`Card::from_value_collecting`] reads an alias as its property with an
+//! error at the path of the alias, the other ways of reading a Card reject it with a [`PropertyAlias`] error
+//! instead of the generic missing property error. An alias of a property that is set is an unknown property.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::{Card, CardPath, CardVersion};
+
+/// A migration step of the JSON of a Card from a version to another (upgrade or downgrade).
+pub trait VersionMigration: Send + Sync {
+    /// The version of the Cards migrated by this step.
+    fn source_version(&self) -> CardVersion;
+
+    /// The version of the Cards after this step.
+    fn target_version(&self) -> CardVersion;
+
+    /// Migrate the JSON object of a Card, the version property is updated after the migration.
+    /// # Errors
+    /// Will return an error if the Card cannot be migrated.
+    fn migrate(&self, card: &mut Value) -> Result<(), UpgradeError>;
+}
+
+/// An error while migrating a Card to another version.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpgradeError {
+    /// No chain of registered migrations goes from the version of the Card to the target version.
+    NoMigration {
+        /// The version of the Card.
+        from: CardVersion,
+        /// The target version.
+        to: CardVersion,
+        /// The properties of the Card unknown to the target version, sorted.
+        incompatible_properties: Vec<String>,
+    },
+    /// A migration step failed.
+    Migration(String),
+}
+
+impl fmt::Display for UpgradeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpgradeError::NoMigration {
+                from,
+                to,
+                incompatible_properties,
+            } => {
+                write!(f, "No migration from {} to {}", from.as_str(), to.as_str())?;
+                if !incompatible_properties.is_empty() {
+                    write!(
+                        f,
+                        ", incompatible properties: {}",
+                        incompatible_properties.join(", ")
+                    )?;
+                }
+                Ok(())
+            }
+            UpgradeError::Migration(message) => write!(f, "Migration failed: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for UpgradeError {}
+
+/// A known alias of a property of a Card, e.g. `@version` for `version`, see [`PROPERTY_ALIASES`].
+///
+/// It is the error of a Card with the alias instead of the property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PropertyAlias {
+    /// The alias (e.g. `@version`).
+    pub alias: &'static str,
+    /// The property (e.g. `version`).
+    pub property: &'static str,
+}
+
+impl fmt::Display for PropertyAlias {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a JSContact property, rename it to `{}`",
+            self.alias, self.property
+        )
+    }
+}
+
+impl std::error::Error for PropertyAlias {}
+
+/// The known aliases of the properties of a Card, seen in the wild.
+pub const PROPERTY_ALIASES: [PropertyAlias; 4] = [
+    PropertyAlias {
+        alias: "@version",
+        property: "version",
+    },
+    PropertyAlias {
+        alias: "@uid",
+        property: "uid",
+    },
+    PropertyAlias {
+        alias: "@kind",
+        property: "kind",
+    },
+    PropertyAlias {
+        alias: "type",
+        property: "@type",
+    },
+];
+
+/// Get the first alias of a Card object whose property is not set
+pub(crate) fn check_aliases(object: &Map<String, Value>) -> Result<(), PropertyAlias> {
+    match PROPERTY_ALIASES
+        .into_iter()
+        .find(|alias| object.contains_key(alias.alias) && !object.contains_key(alias.property))
+    {
+        Some(alias) => Err(alias),
+        None => Ok(()),
+    }
+}
+
+/// Rename the aliases of a Card object whose property is not set, returning them
+pub(crate) fn rename_aliases(object: &mut Map<String, Value>) -> Vec<PropertyAlias> {
+    let mut renamed = Vec::new();
+    for alias in PROPERTY_ALIASES {
+        if object.contains_key(alias.property) {
+            continue;
+        }
+        if let Some(value) = object.remove(alias.alias) {
+            object.insert(alias.property.to_string(), value);
+            renamed.push(alias);
+        }
+    }
+    renamed
+}
+
+/// The migration steps available to [`Card::upgrade_to_with`].
+///
+/// ```rust
+/// use jscontact::MigrationRegistry;
+///
+/// let registry = MigrationRegistry::new();
+/// assert!(registry.is_empty());
+/// ```
+#[derive(Default)]
+pub struct MigrationRegistry {
+    /// The registered steps
+    steps: Vec<Box<dyn VersionMigration>>,
+}
+
+impl MigrationRegistry {
+    /// Creates a registry without migration steps.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a migration step.
+    ///
+    /// A step registered for the same versions as a previous step replaces it.
+    pub fn register(&mut self, migration: impl VersionMigration + 'static) {
+        self.steps.retain(|step| {
+            step.source_version() != migration.source_version()
+                || step.target_version() != migration.target_version()
+        });
+        self.steps.push(Box::new(migration));
+    }
+
+    /// Get the number of registered steps.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Check if no step is registered.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Find the shortest chain of registered steps between two versions
+    fn find_chain(
+        &self,
+        from: &CardVersion,
+        to: &CardVersion,
+    ) -> Option<Vec<&dyn VersionMigration>> {
+        let mut previous: HashMap<String, (String, usize)> = HashMap::new();
+        let mut queue = VecDeque::from([from.as_str().to_string()]);
+        while let Some(version) = queue.pop_front() {
+            if version == to.as_str() {
+                let mut chain = Vec::new();
+                let mut current = version;
+                while let Some((before, idx)) = previous.get(&current) {
+                    chain.push(self.steps[*idx].as_ref());
+                    current = before.clone();
+                }
+                chain.reverse();
+                return Some(chain);
+            }
+            for (idx, step) in self.steps.iter().enumerate() {
+                let next = step.target_version().as_str().to_string();
+                if step.source_version().as_str() == version
+                    && next != from.as_str()
+                    && !previous.contains_key(&next)
+                {
+                    previous.insert(next.clone(), (version.clone(), idx));
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl fmt::Debug for MigrationRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.steps.iter().map(|step| {
+                format!(
+                    "{} -> {}",
+                    step.source_version().as_str(),
+                    step.target_version().as_str()
+                )
+            }))
+            .finish()
+    }
+}
+
+/// Get the properties of a Card unknown to version 1.0, vendor-specific properties (e.g. `example.com:foo`) excepted
+fn incompatible_properties(card: &Value) -> Vec<String> {
+    let mut properties: Vec<String> = card
+        .as_object()
+        .into_iter()
+        .flat_map(|object| object.keys())
+        .filter(|key| !key.contains(':') && !matches!(key.as_str(), "@type" | "localizations"))
+        .filter(|key| CardPath::parse(key).is_err())
+        .cloned()
+        .collect();
+    properties.sort();
+    properties
+}
+
+impl Card {
+    /// Migrate the Card to another version without migration steps, see [`Card::upgrade_to_with`].
+    ///
+    /// Only a Card of the target version, or a Card downgraded to version 1.0 without property unknown to 1.0, is
+    /// migrated.
+    /// # Errors
+    /// Will return an error if the Card cannot be migrated without migration steps.
+    pub fn upgrade_to(&self, target: CardVersion) -> Result<Card, UpgradeError> {
+        self.upgrade_to_with(&MigrationRegistry::new(), target)
+    }
+
+    /// Migrate the Card to another version with the steps of a registry.
+    ///
+    /// Upgrading a Card to its own version returns a copy of the Card.
+    /// A Card without registered migration to version 1.0 is still downgraded if it has no property unknown to 1.0.
+    /// # Errors
+    /// Will return an error if no chain of migrations goes to the target version, with the incompatible properties,
+    /// or if a migration fails.
+    pub fn upgrade_to_with(
+        &self,
+        registry: &MigrationRegistry,
+        target: CardVersion,
+    ) -> Result<Card, UpgradeError> {
+        if self.version == target {
+            return Ok(self.clone());
+        }
+        let mut card =
+            serde_json::to_value(self).map_err(|e| UpgradeError::Migration(e.to_string()))?;
+        match registry.find_chain(&self.version, &target) {
+            Some(chain) => {
+                for step in chain {
+                    step.migrate(&mut card)?;
+                    if let Some(object) = card.as_object_mut() {
+                        object.insert(
+                            "version".to_string(),
+                            Value::from(step.target_version().as_str()),
+                        );
+                    }
+                }
+            }
+            // the properties are checked below, a Card using only properties of 1.0 is a valid 1.0 Card
+            None if target == CardVersion::OneDotZero => {
+                if let Some(object) = card.as_object_mut() {
+                    object.insert("version".to_string(), Value::from(target.as_str()));
+                }
+            }
+            None => {
+                return Err(UpgradeError::NoMigration {
+                    from: self.version.clone(),
+                    to: target,
+                    incompatible_properties: Vec::new(),
+                })
+            }
+        }
+        if target == CardVersion::OneDotZero {
+            let incompatible = incompatible_properties(&card);
+            if !incompatible.is_empty() {
+                return Err(UpgradeError::NoMigration {
+                    from: self.version.clone(),
+                    to: target,
+                    incompatible_properties: incompatible,
+                });
+            }
+        }
+        serde_json::from_value(card).map_err(|e| UpgradeError::Migration(e.to_string()))
+    }
+}
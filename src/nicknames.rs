@@ -0,0 +1,131 @@
+//! A view of the nicknames of a [`Card`] as a plain list of names, as most contact apps model them.
+//!
+//! [`Card::set_nicknames_from`] replaces the nicknames by a list of names: an existing nickname with
+//! one of the names is kept with its key, its contexts and its `pref`, the other names are added with
+//! the keys `k1`, `k2`, ... (see [`Card::extend_nicknames`]) and the other nicknames are removed.
+//! The localization patches of the removed nicknames (e.g. `nicknames/k2/name`) are handled
+//! according to a [`ReindexPolicy`].
+
+use std::collections::HashMap;
+
+use crate::extend::next_number;
+use crate::{Card, LocalizationConflict, Nickname, PathSegment, ReindexPolicy, SmallString};
+
+/// Check if a patch refers to the whole nicknames or to a nickname, `prefix` being its path
+fn patches(path: &str, prefix: &str) -> bool {
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+impl Card {
+    /// Get the names of the nicknames sorted by `pref`, as [`Card::nicknames_sorted`].
+    ///
+    /// ```rust
+    /// use jscontact::{Card, Nickname};
+    ///
+    /// let mut card = Card::new_with_latest_version("1234");
+    /// card.extend_nicknames([Nickname::new("Johnny"), Nickname::new("Jo").with_pref(1)]);
+    /// assert_eq!(card.nickname_strings(), ["Jo", "Johnny"]);
+    /// ```
+    pub fn nickname_strings(&self) -> Vec<&str> {
+        self.nicknames_sorted()
+            .into_iter()
+            .map(|(_, nickname)| nickname.name.as_str())
+            .collect()
+    }
+
+    /// Replace the nicknames by a list of names, see the [module documentation](crate::nicknames).
+    ///
+    /// The nicknames are removed for an empty list. A patch replacing all the nicknames (`nicknames`)
+    /// is always a conflict.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, Context, Nickname, ReindexPolicy};
+    ///
+    /// let mut card = Card::new_with_latest_version("1234");
+    /// card.extend_nicknames([Nickname::new("Jo").with_context(Context::Private)]);
+    /// card.set_nicknames_from(["Johnny".to_string(), "Jo".to_string()], ReindexPolicy::Reindex)
+    ///     .unwrap();
+    /// let nicknames = card.nicknames.as_ref().unwrap();
+    /// assert_eq!(nicknames["k1"].name, "Jo");
+    /// assert!(nicknames["k1"].contexts.is_some());
+    /// assert_eq!(nicknames["k2"].name, "Johnny");
+    /// ```
+    /// # Errors
+    /// Will return an error listing the conflicting localizations, the nicknames being unchanged.
+    pub fn set_nicknames_from<I: IntoIterator<Item = String>>(
+        &mut self,
+        names: I,
+        policy: ReindexPolicy,
+    ) -> Result<(), LocalizationConflict> {
+        // the existing nicknames by name, the most preferred first
+        let mut existing: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (key, nickname) in self.nicknames_sorted() {
+            existing.entry(&nickname.name).or_default().push(key);
+        }
+        for keys in existing.values_mut() {
+            keys.reverse();
+        }
+        let mut kept: Vec<SmallString> = Vec::new();
+        let mut added: Vec<String> = Vec::new();
+        for name in names {
+            match existing.get_mut(name.as_str()).and_then(Vec::pop) {
+                Some(key) => kept.push(SmallString::from(key)),
+                None => added.push(name),
+            }
+        }
+        let removed: Vec<String> = self
+            .nicknames
+            .iter()
+            .flatten()
+            .map(|(key, _)| key)
+            .filter(|key| !kept.contains(key))
+            .map(|key| format!("nicknames/{}", PathSegment::Key(key.to_string())))
+            .collect();
+
+        if let Some(localizations) = &mut self.localizations {
+            let mut languages = Vec::new();
+            let mut paths = Vec::new();
+            for (language, localized) in localizations.iter() {
+                let mut conflicting = false;
+                for path in localized.keys() {
+                    let conflict = path == "nicknames"
+                        || (policy == ReindexPolicy::Reject
+                            && removed.iter().any(|prefix| patches(path, prefix)));
+                    if conflict {
+                        conflicting = true;
+                        paths.push(path.clone());
+                    }
+                }
+                if conflicting {
+                    languages.push(language.clone());
+                }
+            }
+            if !languages.is_empty() {
+                languages.sort();
+                paths.sort();
+                return Err(LocalizationConflict { languages, paths });
+            }
+            for localized in localizations.values_mut() {
+                localized.retain(|path, _| !removed.iter().any(|prefix| patches(path, prefix)));
+            }
+        }
+
+        let mut nicknames: HashMap<SmallString, Nickname> = self
+            .nicknames
+            .take()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(key, _)| kept.contains(key))
+            .collect();
+        let first = next_number(&nicknames, "k");
+        for (number, name) in (first..).zip(added) {
+            let mut key = SmallString::from("k");
+            key.push_str(&number.to_string());
+            nicknames.insert(key, Nickname::new(&name));
+        }
+        self.nicknames = (!nicknames.is_empty()).then_some(nicknames);
+        self.touch();
+        Ok(())
+    }
+}
@@ -0,0 +1,206 @@
+//! Normalization of the components of the addresses of a [`Card`], cleaning up the output of bad converters.
+//!
+//! The whitespace around the values is trimmed, the components repeating a kind and the empty components are removed.
+//! The separator components are kept as is, their whitespace being meaningful.
+//! [`Card::normalize_addresses`] re-indexes the localization patches of the removed components, see [`ReindexPolicy`].
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::components::{reindex, Change};
+use crate::{
+    Address, AddressComponentKind, Card, LocalizationConflict, PathSegment, ReindexPolicy,
+    SmallString,
+};
+
+/// The rules of [`Address::normalize`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressNormalizeOptions {
+    /// Remove the whitespace at the start and the end of the values.
+    pub trim: bool,
+    /// Remove the components of a kind already used by a previous component, the first one being kept.
+    pub remove_duplicates: bool,
+    /// Remove the components without value, after trimming them.
+    pub remove_empty: bool,
+}
+
+impl Default for AddressNormalizeOptions {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl AddressNormalizeOptions {
+    /// All the rules enabled, the default.
+    pub fn all() -> Self {
+        Self {
+            trim: true,
+            remove_duplicates: true,
+            remove_empty: true,
+        }
+    }
+
+    /// No rule enabled, to enable some rules one by one.
+    pub fn none() -> Self {
+        Self {
+            trim: false,
+            remove_duplicates: false,
+            remove_empty: false,
+        }
+    }
+}
+
+/// The rule applied by an [`AddressChange`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum AddressChangeKind {
+    /// The whitespace around the value was removed.
+    Trimmed,
+    /// The component was removed, its kind being used by a previous component.
+    DuplicateRemoved,
+    /// The component was removed, its value being empty.
+    EmptyRemoved,
+}
+
+/// A change made by [`Address::normalize`] or [`Card::normalize_addresses`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AddressChange {
+    /// The path of the component, with its index before the normalization (e.g. `components/3`).
+    ///
+    /// The path starts with the address for [`Card::normalize_addresses`] (e.g. `addresses/k25/components/3`).
+    pub path: String,
+    /// The applied rule.
+    pub kind: AddressChangeKind,
+    /// The value of the component before the change.
+    pub before: String,
+    /// The value of the component after the change, `None` if the component was removed.
+    pub after: Option<String>,
+}
+
+impl fmt::Display for AddressChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.after {
+            Some(after) => write!(f, "{}: {:?} -> {:?}", self.path, self.before, after),
+            None => write!(f, "{}: {:?} removed", self.path, self.before),
+        }
+    }
+}
+
+/// Normalize the components of an address, returning the changes with the index of their component
+fn normalize_components(
+    address: &mut Address,
+    opts: &AddressNormalizeOptions,
+) -> Vec<(usize, AddressChange)> {
+    let Some(components) = &mut address.components else {
+        return Vec::new();
+    };
+    let mut changes = Vec::new();
+    let mut kinds: Vec<AddressComponentKind> = Vec::new();
+    let mut kept = Vec::with_capacity(components.len());
+    for (idx, mut component) in std::mem::take(components).into_iter().enumerate() {
+        if component.kind == AddressComponentKind::Separator {
+            kept.push(component);
+            continue;
+        }
+        let change = |kind, after: Option<&str>| AddressChange {
+            path: format!("components/{}", idx),
+            kind,
+            before: component.value.clone(),
+            after: after.map(str::to_string),
+        };
+        let value = if opts.trim {
+            component.value.trim()
+        } else {
+            component.value.as_str()
+        };
+        if opts.remove_empty && value.is_empty() {
+            changes.push((idx, change(AddressChangeKind::EmptyRemoved, None)));
+            continue;
+        }
+        if opts.remove_duplicates && kinds.contains(&component.kind) {
+            changes.push((idx, change(AddressChangeKind::DuplicateRemoved, None)));
+            continue;
+        }
+        if value.len() != component.value.len() {
+            let trimmed = value.to_string();
+            changes.push((idx, change(AddressChangeKind::Trimmed, Some(&trimmed))));
+            component.value = trimmed;
+        }
+        kinds.push(component.kind.clone());
+        kept.push(component);
+    }
+    *components = kept;
+    changes
+}
+
+impl Address {
+    /// Normalize the components of the address with the rules of the options, returning the changes in the order of the components.
+    ///
+    /// The separator components are not changed.
+    /// Use [`Card::normalize_addresses`] to keep the localizations of the Card valid.
+    pub fn normalize(&mut self, opts: &AddressNormalizeOptions) -> Vec<AddressChange> {
+        normalize_components(self, opts)
+            .into_iter()
+            .map(|(_, change)| change)
+            .collect()
+    }
+}
+
+impl Card {
+    /// Normalize the components of the addresses, see [`Address::normalize`], and re-index the localization patches
+    /// of the components according to the policy.
+    ///
+    /// Returns the changes sorted by address key, then in the order of the components.
+    /// # Errors
+    /// Will return an error listing the conflicting localizations if a component is removed while a patch
+    /// replaces the whole components of its address or, with [`ReindexPolicy::Reject`], while a patch refers to
+    /// a removed or shifted component. The Card is unchanged in that case.
+    pub fn normalize_addresses(
+        &mut self,
+        opts: &AddressNormalizeOptions,
+        policy: ReindexPolicy,
+    ) -> Result<Vec<AddressChange>, LocalizationConflict> {
+        let Some(addresses) = &self.addresses else {
+            return Ok(Vec::new());
+        };
+        let mut keys: Vec<&SmallString> = addresses.keys().collect();
+        keys.sort();
+        let mut localizations = self.localizations.clone();
+        let mut normalized = Vec::new();
+        let mut changes = Vec::new();
+        for key in keys {
+            let mut address = addresses[key].clone();
+            let address_changes = normalize_components(&mut address, opts);
+            if address_changes.is_empty() {
+                continue;
+            }
+            let prefix = format!("addresses/{}", PathSegment::Key(key.to_string()));
+            // from the last removed component, so the indices of the others do not shift
+            for (idx, change) in address_changes.iter().rev() {
+                if change.after.is_none() {
+                    reindex(
+                        &mut localizations,
+                        &format!("{}/components", prefix),
+                        Change::Remove(*idx),
+                        policy,
+                    )?;
+                }
+            }
+            changes.extend(address_changes.into_iter().map(|(_, mut change)| {
+                change.path = format!("{}/{}", prefix, change.path);
+                change
+            }));
+            normalized.push((key.clone(), address));
+        }
+        if changes.is_empty() {
+            return Ok(changes);
+        }
+        if let Some(addresses) = &mut self.addresses {
+            addresses.extend(normalized);
+        }
+        self.localizations = localizations;
+        self.touch();
+        Ok(changes)
+    }
+}
@@ -0,0 +1,138 @@
+//! The `null` values in the JSON of a Card, e.g. `"nicknames": null` written by some producers instead of
+//! omitting the property.
+//!
+//! RFC 9553 does not allow `null` for the properties of a Card, only in the patches of the localizations
+//! (to remove a property). They are handled according to the way the Card is read:
+//! - the deserialization reads a `null` property of the Card as absent, but rejects a `null` value inside
+//!   a property (e.g. `"components": [null]`) as an invalid value
+//! - [`crate::ParseOptions::reject_nulls`] rejects all the `null` values with their path
+//! - [`crate::Card::from_value_collecting`] removes all the `null` values, each with an error at its path
+//!
+//! The patches of the localizations and the unknown properties are left as is.
+
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::path::PROPERTIES;
+use crate::PathSegment;
+
+/// A `null` value in a Card, see [`crate::ParseOptions::reject_nulls`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NullValue {
+    /// The path of the value (e.g. `name/components/1`).
+    pub path: String,
+}
+
+impl fmt::Display for NullValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "null value at {}, the property must be omitted instead",
+            self.path
+        )
+    }
+}
+
+impl std::error::Error for NullValue {}
+
+/// The localizations, whose patches may be `null`
+const LOCALIZATIONS: &str = "localizations";
+
+/// Check if a property of the Card is checked for `null` values, the unknown properties are not
+fn is_checked(property: &str) -> bool {
+    property == LOCALIZATIONS || PROPERTIES.contains(&property)
+}
+
+/// Get the path of a child of a value
+fn child(path: &str, segment: PathSegment) -> String {
+    if path.is_empty() {
+        segment.to_string()
+    } else {
+        format!("{}/{}", path, segment)
+    }
+}
+
+/// Collect the paths of the `null` values of a value
+fn collect(value: &Value, path: &str, nulls: &mut Vec<String>) {
+    match value {
+        Value::Null => nulls.push(path.to_string()),
+        Value::Array(values) => {
+            for (idx, value) in values.iter().enumerate() {
+                collect(value, &child(path, PathSegment::Index(idx)), nulls);
+            }
+        }
+        Value::Object(members) => {
+            for (key, value) in members {
+                collect(value, &child(path, PathSegment::Key(key.clone())), nulls);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Check that the properties of a Card have no `null` value, the error being at the first path
+/// in sorted order.
+pub(crate) fn check_nulls(object: &Map<String, Value>) -> Result<(), NullValue> {
+    let mut nulls = Vec::new();
+    for (property, value) in object.iter().filter(|(property, _)| is_checked(property)) {
+        match property.as_str() {
+            LOCALIZATIONS if value.is_null() => nulls.push(property.clone()),
+            LOCALIZATIONS => {}
+            _ => collect(value, property, &mut nulls),
+        }
+    }
+    match nulls.into_iter().min() {
+        Some(path) => Err(NullValue { path }),
+        None => Ok(()),
+    }
+}
+
+/// Remove the `null` values of a value, collecting their paths
+fn remove(value: &mut Value, path: &str, removed: &mut Vec<String>) {
+    match value {
+        Value::Array(values) => {
+            let mut idx = 0;
+            values.retain_mut(|value| {
+                let item = child(path, PathSegment::Index(idx));
+                idx += 1;
+                if value.is_null() {
+                    removed.push(item);
+                    return false;
+                }
+                remove(value, &item, removed);
+                true
+            });
+        }
+        Value::Object(members) => members.retain(|key, value| {
+            let member = child(path, PathSegment::Key(key.clone()));
+            if value.is_null() {
+                removed.push(member);
+                return false;
+            }
+            remove(value, &member, removed);
+            true
+        }),
+        _ => {}
+    }
+}
+
+/// Remove the `null` values of the properties of a Card, returning their paths sorted.
+pub(crate) fn remove_nulls(object: &mut Map<String, Value>) -> Vec<String> {
+    let mut removed = Vec::new();
+    object.retain(|property, value| {
+        if !is_checked(property) {
+            return true;
+        }
+        if value.is_null() {
+            removed.push(PathSegment::Key(property.clone()).to_string());
+            return false;
+        }
+        if property != LOCALIZATIONS {
+            remove(value, property, &mut removed);
+        }
+        true
+    });
+    removed.sort();
+    removed
+}
@@ -0,0 +1,143 @@
+//! The next occurrences of the anniversaries of a [`Card`] in a time zone, e.g. to schedule the birthday reminders.
+//!
+//! An anniversary recurs every year on the month and day of its date, a [`PartialDate`] without year
+//! included. The day of a [`Timestamp`] is its day in the time zone. An occurrence starts at midnight,
+//! local time, or at the first local time of the day if midnight is skipped by a change of offset.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::{AnniversaryKind, Card, DateObject, PartialDate, Timestamp};
+
+/// When an anniversary on February 29 occurs in the years without such a day, see [`Card::next_occurrence_with`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum LeapDayPolicy {
+    /// On February 28.
+    #[default]
+    February28,
+    /// On March 1.
+    March1,
+}
+
+/// Get the day of an anniversary in a year, `None` if the year has no such day
+fn day_in_year(year: i32, month: u32, day: u32, policy: LeapDayPolicy) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, month, day).or_else(|| match (month, day, policy) {
+        (2, 29, LeapDayPolicy::February28) => NaiveDate::from_ymd_opt(year, 2, 28),
+        (2, 29, LeapDayPolicy::March1) => NaiveDate::from_ymd_opt(year, 3, 1),
+        _ => None,
+    })
+}
+
+/// Get the start of a day in a time zone, in UTC
+fn start_of_day(date: NaiveDate, tz: Tz) -> Option<DateTime<Utc>> {
+    let midnight = date.and_hms_opt(0, 0, 0)?;
+    // a change of offset skips at most a few hours
+    (0..24)
+        .find_map(|hour| {
+            tz.from_local_datetime(&(midnight + Duration::hours(hour)))
+                .earliest()
+        })
+        .map(|start| start.with_timezone(&Utc))
+}
+
+/// Get the month, the day and the first local date of the date of an anniversary, `None` if it does not recur
+fn recurrence(date: &DateObject, tz: Tz) -> Option<(u32, u32, Option<NaiveDate>)> {
+    match date {
+        DateObject::Timestamp(timestamp) => {
+            let local = DateTime::parse_from_rfc3339(&timestamp.utc)
+                .ok()?
+                .with_timezone(&tz)
+                .date_naive();
+            Some((local.month(), local.day(), Some(local)))
+        }
+        DateObject::PartialDate(PartialDate {
+            year,
+            month: Some(month),
+            day: Some(day),
+            ..
+        }) => {
+            let first = match year {
+                Some(year) => Some(
+                    i32::try_from(*year)
+                        .ok()
+                        .and_then(|year| NaiveDate::from_ymd_opt(year, *month, *day))?,
+                ),
+                None => None,
+            };
+            // a day that exists in a leap year at least
+            NaiveDate::from_ymd_opt(2000, *month, *day)?;
+            Some((*month, *day, first))
+        }
+        DateObject::PartialDate(_) => None,
+    }
+}
+
+impl Card {
+    /// Get the next occurrence of the anniversaries of a kind after a time, at midnight in an IANA time zone
+    /// (e.g. `Europe/Paris`), the anniversaries on February 29 occurring on February 28 in the other years.
+    ///
+    /// See [`Card::next_occurrence_with`].
+    ///
+    /// ```rust
+    /// use jscontact::{Anniversary, AnniversaryKind, Card, DateObject, PartialDate, Timestamp};
+    ///
+    /// let mut card = Card::new_with_latest_version("1234");
+    /// let mut date = PartialDate::default();
+    /// date.month = Some(4);
+    /// date.day = Some(15);
+    /// card.anniversaries = Some(
+    ///     [("k1".into(), Anniversary::birth(DateObject::PartialDate(date)))].into(),
+    /// );
+    /// let next = card
+    ///     .next_occurrence(AnniversaryKind::Birth, Timestamp::new("2025-06-01T00:00:00Z"), "Europe/Paris")
+    ///     .unwrap();
+    /// assert_eq!(next.utc, "2026-04-14T22:00:00Z");
+    /// ```
+    pub fn next_occurrence(
+        &self,
+        kind: AnniversaryKind,
+        after: Timestamp,
+        tz: &str,
+    ) -> Option<Timestamp> {
+        self.next_occurrence_with(kind, after, tz, LeapDayPolicy::default())
+    }
+
+    /// Get the next occurrence of the anniversaries of a kind strictly after a time, at midnight in an IANA
+    /// time zone (e.g. `Europe/Paris`), as a UTC timestamp.
+    ///
+    /// The anniversaries recur every year from their date, every year for a date without year. An anniversary
+    /// without month or day does not recur. The earliest occurrence of the anniversaries of the kind is returned.
+    ///
+    /// Returns `None` if no anniversary of the kind recurs, or if the time or the time zone is invalid.
+    pub fn next_occurrence_with(
+        &self,
+        kind: AnniversaryKind,
+        after: Timestamp,
+        tz: &str,
+        policy: LeapDayPolicy,
+    ) -> Option<Timestamp> {
+        let tz: Tz = tz.parse().ok()?;
+        let after = DateTime::parse_from_rfc3339(&after.utc)
+            .ok()?
+            .with_timezone(&Utc);
+        // the occurrences are local days, none of them starts after the time in an earlier year
+        let from_year = after.with_timezone(&tz).year();
+        self.anniversaries
+            .iter()
+            .flatten()
+            .filter(|(_, anniversary)| anniversary.kind == kind)
+            .filter_map(|(_, anniversary)| {
+                let (month, day, first) = recurrence(&anniversary.date, tz)?;
+                let from_year = first.map_or(from_year, |first| first.year().max(from_year));
+                (from_year..=from_year + 2)
+                    .filter_map(|year| day_in_year(year, month, day, policy))
+                    .filter(|date| first.is_none_or(|first| *date >= first))
+                    .filter_map(|date| start_of_day(date, tz))
+                    .find(|start| *start > after)
+            })
+            .min()
+            .map(|start| Timestamp::new(&start.format("%Y-%m-%dT%H:%M:%SZ").to_string()))
+    }
+}
@@ -0,0 +1,212 @@
+//! Parsing of JSON whose shape is not known in advance: a Card, an array of Cards or a JMAP-like envelope.
+//!
+//! The JSON is parsed once into a [`Value`], the Cards being then taken from it without copy.
+
+use serde::{de, Deserialize};
+use serde_json::{Map, Value};
+
+use crate::depth::{check_depth, DEFAULT_MAX_DEPTH};
+use crate::limit::{check_size, DEFAULT_MAX_BYTES};
+use crate::migration::check_aliases;
+use crate::nulls::{check_nulls, NullValue};
+use crate::unknown::check_unknown;
+use crate::Card;
+
+/// The result of [`parse`].
+// one value per parsed document, the Card is not boxed so it can be matched directly
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Parsed {
+    /// A Card object.
+    Card(Card),
+    /// An array of Cards, or the `list` of Cards of a JMAP-like envelope (e.g. the response of `ContactCard/get`).
+    Cards(Vec<Card>),
+    /// An object that is neither a Card nor an envelope, returned as is.
+    Unknown(Value),
+}
+
+/// The options of [`parse_with`] and [`Card::from_str_with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Type-check the patches of the localizations when parsing, recording the invalid ones
+    /// (see [`Card::localization_parse_issues`]) instead of failing at the first [`Card::get_localized`].
+    pub verify_localizations: bool,
+    /// The maximum nesting depth of the JSON, [`DEFAULT_MAX_DEPTH`] by default.
+    /// A higher limit has no effect, `serde_json` rejecting the JSON nested deeper than 128 levels.
+    pub max_depth: usize,
+    /// Reject the Cards with a `null` value in a property (e.g. `"nicknames": null`), which is otherwise
+    /// read as an absent property (see [`crate::nulls`]).
+    pub reject_nulls: bool,
+    /// Reject the Cards with an unknown property that is not vendor-specific (e.g. the typo `nmae`),
+    /// which is otherwise kept in [`Card::extensions`] (see [`crate::unknown`]).
+    pub reject_unknown_properties: bool,
+    /// The maximum size of the JSON in bytes, [`DEFAULT_MAX_BYTES`] by default.
+    pub max_bytes: usize,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            verify_localizations: false,
+            max_depth: DEFAULT_MAX_DEPTH,
+            reject_nulls: false,
+            reject_unknown_properties: false,
+            max_bytes: DEFAULT_MAX_BYTES,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Check the JSON of a Card before its deserialization
+    fn check(&self, value: &Value) -> Result<(), NullValue> {
+        match value {
+            Value::Object(object) if self.reject_nulls => check_nulls(object),
+            _ => Ok(()),
+        }
+    }
+
+    /// Read a Card from its JSON checked by [`ParseOptions::check`], rejecting the aliases of the missing properties
+    fn read(&self, value: Value) -> Result<Card, serde_json::Error> {
+        if let Value::Object(object) = &value {
+            check_aliases(object).map_err(de::Error::custom)?;
+        }
+        if !self.reject_unknown_properties {
+            return serde_json::from_value(value);
+        }
+        let card = <Card as Deserialize>::deserialize(&value)?;
+        if let Value::Object(object) = &value {
+            check_unknown(object, &card).map_err(de::Error::custom)?;
+        }
+        Ok(card)
+    }
+
+    /// Apply the options to a parsed Card
+    fn apply(&self, card: &mut Card) {
+        if self.verify_localizations {
+            card.verify_localizations();
+        }
+    }
+}
+
+/// Check if an object looks like a Card: `@type` is `Card`, or the `version` and `uid` properties are set
+fn is_card(object: &Map<String, Value>) -> bool {
+    match object.get("@type") {
+        Some(card_type) => card_type == "Card",
+        None => object.contains_key("version") && object.contains_key("uid"),
+    }
+}
+
+/// Get the type of a value, for the errors
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Convert the values of an array to Cards
+fn cards(values: Vec<Value>, context: &str, opts: &ParseOptions) -> Result<Vec<Card>, String> {
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(idx, value)| {
+            opts.check(&value)
+                .map_err(|e| format!("Invalid Card at {}{}: {}", context, idx, e))?;
+            opts.read(value)
+                .map_err(|e| format!("Invalid Card at {}{}: {}", context, idx, e))
+        })
+        .collect()
+}
+
+/// Parse JSON holding a Card, an array of Cards or an object with a `list` of Cards.
+///
+/// - an object whose `@type` is `Card`, or without `@type` but with `version` and `uid`, is a Card (the `@type` being added)
+/// - an array is an array of Cards
+/// - an object with a `list` array is an envelope, e.g. `{ "accountId": "a1", "list": [...], "notFound": [] }`
+/// - another object is returned as [`Parsed::Unknown`]
+/// # Errors
+/// Will return an error if the JSON is invalid, is not an object or an array, or if one of the Cards is invalid.
+pub fn parse(json: &str) -> Result<Parsed, String> {
+    parse_with(json, &ParseOptions::default())
+}
+
+/// Parse JSON holding a Card, an array of Cards or an object with a `list` of Cards, see [`parse`], with options.
+///
+/// ```rust
+/// use jscontact::{parse_with, ParseOptions, Parsed};
+///
+/// let json = r#"{"@type":"Card","version":"1.0","uid":"1","titles":{"t1":{"name":"Boss"}},
+///     "localizations":{"fr":{"titles/t1/name":42}}}"#;
+/// let opts = ParseOptions {
+///     verify_localizations: true,
+///     ..Default::default()
+/// };
+/// let Ok(Parsed::Card(card)) = parse_with(json, &opts) else { panic!() };
+/// assert_eq!(card.localization_parse_issues()[0].path, "titles/t1/name");
+/// ```
+/// # Errors
+/// Will return an error if the JSON is invalid, larger than the maximum size or nested deeper than the maximum depth,
+/// is not an object or an array, or if one of the Cards is invalid.
+pub fn parse_with(json: &str, opts: &ParseOptions) -> Result<Parsed, String> {
+    let mut parsed = parse_value(json, opts)?;
+    match &mut parsed {
+        Parsed::Card(card) => opts.apply(card),
+        Parsed::Cards(cards) => cards.iter_mut().for_each(|card| opts.apply(card)),
+        Parsed::Unknown(_) => {}
+    }
+    Ok(parsed)
+}
+
+/// Parse JSON holding a Card, an array of Cards or an object with a `list` of Cards
+fn parse_value(json: &str, opts: &ParseOptions) -> Result<Parsed, String> {
+    check_size(json, opts.max_bytes).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let value: Value = serde_json::from_str(json).map_err(|e| format!("Invalid JSON: {}", e))?;
+    check_depth(&value, "", opts.max_depth).map_err(|e| format!("Invalid JSON: {}", e))?;
+    match value {
+        Value::Array(values) => cards(values, "index ", opts).map(Parsed::Cards),
+        Value::Object(mut object) if is_card(&object) => {
+            // the @type of the Card is required
+            object
+                .entry("@type")
+                .or_insert_with(|| Value::String("Card".to_string()));
+            if opts.reject_nulls {
+                check_nulls(&object).map_err(|e| format!("Invalid Card: {}", e))?;
+            }
+            opts.read(Value::Object(object))
+                .map(Parsed::Card)
+                .map_err(|e| format!("Invalid Card: {}", e))
+        }
+        Value::Object(mut object) => match object.remove("list") {
+            Some(Value::Array(values)) => cards(values, "list/", opts).map(Parsed::Cards),
+            Some(list) => Err(format!(
+                "Invalid envelope: list must be an array, found {}",
+                type_name(&list)
+            )),
+            None => Ok(Parsed::Unknown(Value::Object(object))),
+        },
+        value => Err(format!(
+            "Expected a Card, an array of Cards or an object with a list of Cards, found {}",
+            type_name(&value)
+        )),
+    }
+}
+
+impl Card {
+    /// Parse a Card from JSON with options.
+    /// # Errors
+    /// Will return an error if the input is not a valid Card object, is larger than the maximum size
+    /// or is nested deeper than the maximum depth.
+    pub fn from_str_with(json: &str, opts: &ParseOptions) -> Result<Card, serde_json::Error> {
+        check_size(json, opts.max_bytes).map_err(de::Error::custom)?;
+        let value: Value = serde_json::from_str(json)?;
+        check_depth(&value, "", opts.max_depth).map_err(de::Error::custom)?;
+        opts.check(&value).map_err(de::Error::custom)?;
+        let mut card = opts.read(value)?;
+        opts.apply(&mut card);
+        Ok(card)
+    }
+}
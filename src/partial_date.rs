@@ -0,0 +1,170 @@
+//! The ISO 8601 / RFC 3339 strings of a [`PartialDate`], as used by the vCard `BDAY` and `ANNIVERSARY`
+//! properties (RFC 6350) and by the date pickers:
+//!
+//! | Date | String |
+//! |---|---|
+//! | year, month and day | `1953-04-15` (or `19530415`) |
+//! | year and month | `1953-04` |
+//! | year | `1953` |
+//! | month and day | `--04-15` (or `--0415`) |
+//!
+//! The other combinations (a day alone, a month alone) are not valid PartialDates and have no string.
+//! The calendar scale is not part of the string.
+
+use std::fmt;
+
+use crate::PartialDate;
+
+/// An error while parsing the string of a [`PartialDate`], see [`PartialDate::from_iso_str`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartialDateError {
+    /// The string is not one of the forms of a PartialDate.
+    Format(String),
+    /// The month is not between 1 and 12.
+    Month(String),
+    /// The day is not a day of the month.
+    Day(String),
+}
+
+impl fmt::Display for PartialDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartialDateError::Format(date) => write!(
+                f,
+                "invalid date '{}', expected YYYY-MM-DD, YYYY-MM, YYYY or --MM-DD",
+                date
+            ),
+            PartialDateError::Month(month) => write!(f, "invalid month '{}'", month),
+            PartialDateError::Day(day) => write!(f, "invalid day '{}'", day),
+        }
+    }
+}
+
+impl std::error::Error for PartialDateError {}
+
+/// The highest year with 4 digits
+const MAX_YEAR: u64 = 9999;
+
+/// Get the number of days of a month, February having 29 days without year
+fn days_in_month(year: Option<u64>, month: u32) -> u32 {
+    match month {
+        2 if year.is_some_and(|year| year % 4 != 0 || (year % 100 == 0 && year % 400 != 0)) => 28,
+        2 => 29,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Check that a part of the string has the expected number of digits
+fn digits(part: &str, len: usize) -> Option<&str> {
+    (part.len() == len && part.bytes().all(|b| b.is_ascii_digit())).then_some(part)
+}
+
+impl PartialDate {
+    /// Get the ISO 8601 string of the date, see the [module documentation](crate::partial_date).
+    ///
+    /// Returns `None` for a date without string: a day or a month alone, a year of more than 4 digits,
+    /// or a month or a day out of range.
+    ///
+    /// ```rust
+    /// use jscontact::PartialDate;
+    ///
+    /// let mut date = PartialDate::default();
+    /// date.month = Some(4);
+    /// date.day = Some(15);
+    /// assert_eq!(date.to_iso_string().as_deref(), Some("--04-15"));
+    /// date.year = Some(1953);
+    /// assert_eq!(date.to_iso_string().as_deref(), Some("1953-04-15"));
+    /// ```
+    pub fn to_iso_string(&self) -> Option<String> {
+        if self.year.is_some_and(|year| year > MAX_YEAR)
+            || self.month.is_some_and(|month| !(1..=12).contains(&month))
+        {
+            return None;
+        }
+        if let (Some(month), Some(day)) = (self.month, self.day) {
+            if !(1..=days_in_month(self.year, month)).contains(&day) {
+                return None;
+            }
+        }
+        match (self.year, self.month, self.day) {
+            (Some(year), Some(month), Some(day)) => {
+                Some(format!("{:04}-{:02}-{:02}", year, month, day))
+            }
+            (Some(year), Some(month), None) => Some(format!("{:04}-{:02}", year, month)),
+            (Some(year), None, None) => Some(format!("{:04}", year)),
+            (None, Some(month), Some(day)) => Some(format!("--{:02}-{:02}", month, day)),
+            _ => None,
+        }
+    }
+
+    /// Parse the ISO 8601 string of a date, in the extended (`1953-04-15`) or the basic (`19530415`) format,
+    /// see the [module documentation](crate::partial_date).
+    ///
+    /// ```rust
+    /// use jscontact::PartialDate;
+    ///
+    /// let date = PartialDate::from_iso_str("--0415").unwrap();
+    /// assert_eq!((date.year, date.month, date.day), (None, Some(4), Some(15)));
+    /// assert!(PartialDate::from_iso_str("---15").is_err());
+    /// ```
+    /// # Errors
+    /// Will return an error if the string is not one of the forms of a PartialDate,
+    /// or if its month or its day is out of range.
+    pub fn from_iso_str(date: &str) -> Result<PartialDate, PartialDateError> {
+        let format = || PartialDateError::Format(date.to_string());
+        let (year, month, day) = match date.strip_prefix("--") {
+            Some(month_day) => {
+                let (month, day) = match month_day.split_once('-') {
+                    Some(month_day) => month_day,
+                    // the basic format
+                    None => (
+                        month_day.get(..2).ok_or_else(format)?,
+                        month_day.get(2..).ok_or_else(format)?,
+                    ),
+                };
+                (None, Some(month), Some(day))
+            }
+            None => match date.split('-').collect::<Vec<_>>().as_slice() {
+                // the basic format
+                [date] if digits(date, 8).is_some() => {
+                    (Some(&date[..4]), Some(&date[4..6]), Some(&date[6..]))
+                }
+                [year] => (Some(*year), None, None),
+                [year, month] => (Some(*year), Some(*month), None),
+                [year, month, day] => (Some(*year), Some(*month), Some(*day)),
+                _ => return Err(format()),
+            },
+        };
+        let number = |part: Option<&str>, len: usize| -> Result<Option<u32>, PartialDateError> {
+            match part {
+                Some(part) => digits(part, len)
+                    .and_then(|part| part.parse().ok())
+                    .map(Some)
+                    .ok_or_else(format),
+                None => Ok(None),
+            }
+        };
+        let year = number(year, 4)?.map(u64::from);
+        let month_value = number(month, 2)?;
+        let day_value = number(day, 2)?;
+        if let Some(month_value) = month_value {
+            if !(1..=12).contains(&month_value) {
+                return Err(PartialDateError::Month(
+                    month.unwrap_or_default().to_string(),
+                ));
+            }
+            if let Some(day_value) = day_value {
+                if !(1..=days_in_month(year, month_value)).contains(&day_value) {
+                    return Err(PartialDateError::Day(day.unwrap_or_default().to_string()));
+                }
+            }
+        }
+        Ok(PartialDate {
+            year,
+            month: month_value,
+            day: day_value,
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,413 @@
+//! Paths into a [`Card`], as used by the patches of the localizations.
+//!
+//! A path is a JSON pointer without the leading `/`, starting with a property of the Card,
+//! e.g. `name/components/0/value` or `addresses/k26/full`.
+//! The `~` and `/` characters of the map keys are escaped as `~0` and `~1`.
+
+use std::collections::hash_map::{Entry, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use crate::{Card, PatchLimits};
+
+/// A segment of a [`CardPath`] after the property.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    /// A map key or the name of a field (unescaped).
+    Key(String),
+    /// An array index.
+    Index(usize),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathSegment::Key(key) => write!(f, "{}", key.replace('~', "~0").replace('/', "~1")),
+            PathSegment::Index(idx) => write!(f, "{}", idx),
+        }
+    }
+}
+
+/// A parsed path into a [`Card`], e.g. `name/components/0/value`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CardPath {
+    /// The property of the Card (e.g. `name`).
+    pub property: String,
+    /// The segments after the property (e.g. `components`, `0` and `value`).
+    pub segments: Vec<PathSegment>,
+}
+
+impl CardPath {
+    /// Parse a path, the property must be a property of the Card.
+    /// # Errors
+    /// Will return an error if the path is empty, has an empty segment, an invalid escape or an unknown property.
+    pub fn parse(path: &str) -> Result<Self, String> {
+        let mut parts = path.split('/');
+        let property = parts.next().unwrap_or_default();
+        if property.is_empty() {
+            return Err(format!("Invalid path '{}': empty property", path));
+        }
+        if !PROPERTIES.contains(&property) {
+            return Err(format!("Invalid path '{}': unknown property", path));
+        }
+        let segments = parts
+            .map(|part| parse_segment(part).map_err(|e| format!("Invalid path '{}': {}", path, e)))
+            .collect::<Result<Vec<PathSegment>, String>>()?;
+        Ok(Self {
+            property: property.to_string(),
+            segments,
+        })
+    }
+}
+
+impl FromStr for CardPath {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for CardPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.property)?;
+        for segment in &self.segments {
+            write!(f, "/{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a segment of a path
+fn parse_segment(part: &str) -> Result<PathSegment, String> {
+    if part.is_empty() {
+        return Err("empty segment".into());
+    }
+    let is_index =
+        part.bytes().all(|b| b.is_ascii_digit()) && (part == "0" || !part.starts_with('0'));
+    if is_index {
+        if let Ok(idx) = part.parse::<usize>() {
+            return Ok(PathSegment::Index(idx));
+        }
+    }
+    let mut key = String::with_capacity(part.len());
+    let mut chars = part.chars();
+    while let Some(c) = chars.next() {
+        if c != '~' {
+            key.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('0') => key.push('~'),
+            Some('1') => key.push('/'),
+            _ => return Err(format!("invalid escape in '{}'", part)),
+        }
+    }
+    Ok(PathSegment::Key(key))
+}
+
+/// Generate the conversions between the properties of the Card and their JSON values
+macro_rules! card_properties {
+    ($($property:literal => $field:ident),+ $(,)?) => {
+        /// The properties of the Card that can be resolved by a path
+        pub(crate) const PROPERTIES: &[&str] = &[$($property),+];
+
+        /// Get the JSON value of a property, `Null` if the property is not set
+        pub(crate) fn property_to_value(card: &Card, property: &str) -> Result<Value, String> {
+            let value = match property {
+                $($property => serde_json::to_value(&card.$field),)+
+                _ => return Err(format!("Unknown property '{}'", property)),
+            };
+            value.map_err(|e| format!("Failed to convert {} to value: {}", property, e))
+        }
+
+        /// Set a property from its JSON value
+        pub(crate) fn property_from_value(card: &mut Card, property: &str, value: Value) -> Result<(), String> {
+            match property {
+                $($property => {
+                    card.$field = serde_json::from_value(value)
+                        .map_err(|e| format!("Invalid value for {}: {}", property, e))?;
+                },)+
+                _ => return Err(format!("Unknown property '{}'", property)),
+            }
+            Ok(())
+        }
+    };
+}
+
+card_properties! {
+    "version" => version,
+    "created" => created,
+    "uid" => uid,
+    "kind" => kind,
+    "language" => language,
+    "members" => members,
+    "prodId" => prod_id,
+    "relatedTo" => related_to,
+    "updated" => updated,
+    "name" => name,
+    "nicknames" => nicknames,
+    "organizations" => organizations,
+    "speakToAs" => speak_to_as,
+    "titles" => titles,
+    "emails" => emails,
+    "onlineServices" => online_services,
+    "phones" => phones,
+    "preferredLanguages" => preferred_languages,
+    "calendars" => calendars,
+    "schedulingAddresses" => scheduling_addresses,
+    "anniversaries" => anniversaries,
+    "addresses" => addresses,
+    "cryptoKeys" => crypto_keys,
+    "directories" => directories,
+    "links" => links,
+    "media" => media,
+    "keywords" => keywords,
+    "notes" => notes,
+    "personalInfo" => personal_info,
+}
+
+/// The name of the localizations, a top-level property which is not a path of the Card
+const LOCALIZATIONS: &str = "localizations";
+
+/// Get the value at a path of the Card, `None` if the path does not exist.
+pub fn get_value(card: &Card, path: &CardPath) -> Option<Value> {
+    let mut current = property_to_value(card, &path.property).ok()?;
+    for segment in &path.segments {
+        current = match (current, segment) {
+            (Value::Object(mut map), segment) => map.remove(&segment.to_key())?,
+            (Value::Array(mut values), PathSegment::Index(idx)) if *idx < values.len() => {
+                values.swap_remove(*idx)
+            }
+            _ => return None,
+        };
+    }
+    (!current.is_null()).then_some(current)
+}
+
+/// Set the value at a path of the Card, a `null` value removes the property, map entry or array element.
+/// # Errors
+/// Will return an error if the path cannot be resolved or if the patched property is invalid.
+pub fn set_value(card: &mut Card, path: &CardPath, value: Value) -> Result<(), String> {
+    set_values(card, [(path, value)])
+}
+
+/// Set the values at several paths of the Card, the patched properties being checked once all values are set.
+///
+/// The missing array elements are created as empty objects, so the fields of an element can be set
+/// by separate paths in any order. An index cannot be more than [`PatchLimits::max_index_delta`] (by default)
+/// elements past the end of its array, so a path cannot allocate an array of any length.
+/// # Errors
+/// Will return an error if a path cannot be resolved or if a patched property is invalid,
+/// in which case the properties checked before the invalid one are kept.
+pub fn set_values<'a>(
+    card: &mut Card,
+    patches: impl IntoIterator<Item = (&'a CardPath, Value)>,
+) -> Result<(), String> {
+    set_values_limited(card, patches, PatchLimits::default().max_index_delta)
+}
+
+/// Set the values at several paths of the Card as [`set_values`], with a maximum number of missing array elements
+pub(crate) fn set_values_limited<'a>(
+    card: &mut Card,
+    patches: impl IntoIterator<Item = (&'a CardPath, Value)>,
+    max_index_delta: usize,
+) -> Result<(), String> {
+    let mut properties: HashMap<&str, Value> = HashMap::new();
+    for (path, value) in patches {
+        let property = match properties.entry(path.property.as_str()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(property_to_value(card, &path.property)?),
+        };
+        assign(property, &path.segments, value, max_index_delta)
+            .map_err(|e| format!("{}: {}", path, e))?;
+    }
+    if !properties.is_empty() {
+        // bumped even if a property is invalid, the properties before it being kept
+        card.touch();
+    }
+    for (property, value) in properties {
+        property_from_value(card, property, value)?;
+    }
+    Ok(())
+}
+
+impl PathSegment {
+    /// Get the segment as a map key
+    fn to_key(&self) -> String {
+        match self {
+            PathSegment::Key(key) => key.clone(),
+            PathSegment::Index(idx) => idx.to_string(),
+        }
+    }
+}
+
+/// Get the child of a value, creating it if missing, at most `max_index_delta` elements past the end of an array
+pub(crate) fn child<'a>(
+    current: &'a mut Value,
+    segment: &PathSegment,
+    max_index_delta: usize,
+) -> Result<&'a mut Value, String> {
+    if current.is_null() {
+        *current = match segment {
+            PathSegment::Index(_) => Value::Array(Vec::new()),
+            PathSegment::Key(_) => Value::Object(Map::new()),
+        };
+    }
+    match (current, segment) {
+        (Value::Object(map), segment) => Ok(map.entry(segment.to_key()).or_insert(Value::Null)),
+        (Value::Array(values), PathSegment::Index(idx)) => {
+            check_index_delta(values, *idx, max_index_delta)?;
+            if values.len() <= *idx {
+                values.resize(*idx + 1, Value::Object(Map::new()));
+            }
+            Ok(&mut values[*idx])
+        }
+        (_, segment) => Err(format!("cannot resolve '{}'", segment)),
+    }
+}
+
+/// Check that an index is at most `max_index_delta` elements past the end of an array
+pub(crate) fn check_index_delta(
+    values: &[Value],
+    idx: usize,
+    max_index_delta: usize,
+) -> Result<(), String> {
+    if idx.saturating_sub(values.len()) > max_index_delta {
+        return Err(format!(
+            "cannot resolve '{}': more than {} elements past the end of the array",
+            idx, max_index_delta
+        ));
+    }
+    Ok(())
+}
+
+/// Assign a value at the segments of a value
+fn assign(
+    target: &mut Value,
+    segments: &[PathSegment],
+    value: Value,
+    max_index_delta: usize,
+) -> Result<(), String> {
+    let Some((last, parents)) = segments.split_last() else {
+        *target = value;
+        return Ok(());
+    };
+    if value.is_null() {
+        remove(target, parents, last);
+        return Ok(());
+    }
+    let mut current = target;
+    for segment in parents {
+        current = child(current, segment, max_index_delta)?;
+    }
+    match (current, last) {
+        (Value::Array(values), PathSegment::Key(key)) if key == "-" => values.push(value),
+        (current, last) => *child(current, last, max_index_delta)? = value,
+    }
+    Ok(())
+}
+
+/// Remove the value at the segments of a value, if it exists
+fn remove(target: &mut Value, parents: &[PathSegment], last: &PathSegment) {
+    let mut current = target;
+    for segment in parents {
+        let next = match (current, segment) {
+            (Value::Object(map), segment) => map.get_mut(&segment.to_key()),
+            (Value::Array(values), PathSegment::Index(idx)) => values.get_mut(*idx),
+            _ => None,
+        };
+        let Some(next) = next else {
+            return;
+        };
+        current = next;
+    }
+    match (current, last) {
+        (Value::Object(map), last) => {
+            map.remove(&last.to_key());
+        }
+        (Value::Array(values), PathSegment::Index(idx)) if *idx < values.len() => {
+            values.remove(*idx);
+        }
+        _ => (),
+    }
+}
+
+impl Card {
+    /// Get the value at a path of the Card, `None` if the path does not exist.
+    ///
+    /// The path is either a [`CardPath`] (e.g. `name/full`) or, with a leading `/`, a JSON pointer (RFC 6901)
+    /// which also resolves the properties that are not paths of the Card (e.g. `/localizations/en` or an extension).
+    /// Only the property of the path is converted to JSON.
+    pub fn get_pointer(&self, ptr: &str) -> Option<Value> {
+        let Some(pointer) = ptr.strip_prefix('/') else {
+            return get_value(self, &CardPath::parse(ptr).ok()?);
+        };
+        match CardPath::parse(pointer) {
+            Ok(path) => get_value(self, &path),
+            Err(_) => serde_json::to_value(self).ok()?.pointer(ptr).cloned(),
+        }
+    }
+
+    /// Get the value at a path of the Card as a type, see [`Card::get_pointer`].
+    ///
+    /// Returns `None` if the path does not exist or if the value is not of the type.
+    pub fn get_pointer_as<T: DeserializeOwned>(&self, ptr: &str) -> Option<T> {
+        serde_json::from_value(self.get_pointer(ptr)?).ok()
+    }
+
+    /// Get the names of the top-level properties of [`Card::property_as_value`], as serialized (e.g. `prodId`).
+    pub fn property_names() -> impl Iterator<Item = &'static str> {
+        PROPERTIES.iter().copied().chain([LOCALIZATIONS])
+    }
+
+    /// Check if a top-level property is set, by its name as serialized (e.g. `emails`).
+    ///
+    /// Returns false for an unknown property, the required `version` and `uid` are always set.
+    pub fn property_is_set(&self, name: &str) -> bool {
+        self.property_as_value(name)
+            .is_some_and(|value| !value.is_null())
+    }
+
+    /// Get the JSON value of a top-level property by its name as serialized, e.g. for a template engine.
+    ///
+    /// ```rust
+    /// use jscontact::Card;
+    ///
+    /// let card = Card::new_with_latest_version("1234");
+    /// assert_eq!(card.property_as_value("uid"), Some("1234".into()));
+    /// assert_eq!(card.property_as_value("name"), Some(serde_json::Value::Null));
+    /// assert_eq!(card.property_as_value("nmae"), None);
+    /// ```
+    ///
+    /// Returns `Null` if the property is not set, `None` if the property is unknown.
+    pub fn property_as_value(&self, name: &str) -> Option<Value> {
+        if name == LOCALIZATIONS {
+            return serde_json::to_value(&self.localizations).ok();
+        }
+        property_to_value(self, name).ok()
+    }
+
+    /// Set a top-level property from its JSON value by its name as serialized, `Null` unsetting an optional property.
+    /// # Errors
+    /// Will return an error if the property is unknown, with the list of the properties, or if the value is invalid.
+    pub fn set_property_from_value(&mut self, name: &str, value: Value) -> Result<(), String> {
+        if name == LOCALIZATIONS {
+            self.localizations = serde_json::from_value(value)
+                .map_err(|e| format!("Invalid value for {}: {}", name, e))?;
+        } else if PROPERTIES.contains(&name) {
+            property_from_value(self, name, value)?;
+        } else {
+            return Err(format!(
+                "Unknown property '{}', expected one of: {}",
+                name,
+                Card::property_names().collect::<Vec<_>>().join(", ")
+            ));
+        }
+        self.touch();
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+//! The place of an [`Anniversary`], an inline [`Address`] or a reference to an address of the Card.
+//!
+//! RFC 9553 defines the place as an Address object, so both forms are serialized as an Address object:
+//! the reference is an Address without other property than the vendor-specific [`PLACE_KEY_PROPERTY`],
+//! whose value is the key of the address in the `addresses` of the Card.
+//!
+//! ```json
+//! "place": { "@type": "Address", "jscontact.rs:addressKey": "k23" }
+//! ```
+//!
+//! The other implementations ignore the unknown property and see an empty Address,
+//! use [`Card::expand_place_refs`] to replace the references by copies of the addresses before sharing a Card.
+
+use serde::de::{self, Deserializer};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{Address, Anniversary, Card};
+
+/// The vendor-specific property of an Address object holding the key of the referenced address.
+pub const PLACE_KEY_PROPERTY: &str = "jscontact.rs:addressKey";
+
+/// The place of an anniversary, a reference being serialized as an Address object with the [`PLACE_KEY_PROPERTY`].
+// most places are inline, as the previous `Option<Address>`, boxing would allocate for each of them
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq, Clone)]
+pub enum PlaceRef {
+    /// The address of the place.
+    Inline(Address),
+    /// The key of an address of the Card (e.g. `k23` for `addresses/k23`).
+    Key(String),
+}
+
+impl PlaceRef {
+    /// Get the inline address, `None` for a reference.
+    pub fn as_address(&self) -> Option<&Address> {
+        match self {
+            PlaceRef::Inline(address) => Some(address),
+            PlaceRef::Key(_) => None,
+        }
+    }
+}
+
+impl From<Address> for PlaceRef {
+    fn from(address: Address) -> Self {
+        PlaceRef::Inline(address)
+    }
+}
+
+impl Serialize for PlaceRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            PlaceRef::Inline(address) => address.serialize(serializer),
+            PlaceRef::Key(key) => {
+                let mut map = serializer.serialize_map(None)?;
+                map.serialize_entry("@type", "Address")?;
+                map.serialize_entry(PLACE_KEY_PROPERTY, key)?;
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PlaceRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        // the reference wins over the other properties of the Address object
+        if let Some(Value::String(key)) = value.get(PLACE_KEY_PROPERTY) {
+            return Ok(PlaceRef::Key(key.clone()));
+        }
+        Address::deserialize(value)
+            .map(PlaceRef::Inline)
+            .map_err(de::Error::custom)
+    }
+}
+
+impl Anniversary {
+    /// Get the address of the place, the referenced address of the Card for a reference.
+    ///
+    /// Returns `None` if there is no place or if the referenced address does not exist.
+    pub fn place_resolved<'a>(&'a self, card: &'a Card) -> Option<&'a Address> {
+        match self.place.as_ref()? {
+            PlaceRef::Inline(address) => Some(address),
+            PlaceRef::Key(key) => card.addresses.as_ref()?.get(key.as_str()),
+        }
+    }
+}
+
+impl Card {
+    /// Replace the references to the addresses of the places of the anniversaries by copies of the addresses.
+    ///
+    /// A reference to an address that does not exist is kept. Returns the number of replaced references.
+    pub fn expand_place_refs(&mut self) -> usize {
+        let Some(anniversaries) = self.anniversaries.as_mut() else {
+            return 0;
+        };
+        let mut expanded = 0;
+        for anniversary in anniversaries.values_mut() {
+            let Some(PlaceRef::Key(key)) = &anniversary.place else {
+                continue;
+            };
+            let Some(address) = self
+                .addresses
+                .as_ref()
+                .and_then(|addresses| addresses.get(key.as_str()))
+            else {
+                continue;
+            };
+            anniversary.place = Some(PlaceRef::Inline(address.clone()));
+            expanded += 1;
+        }
+        if expanded > 0 {
+            self.touch();
+        }
+        expanded
+    }
+}
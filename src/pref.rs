@@ -0,0 +1,168 @@
+//! Renumbering of the `pref` properties of the entries of a map of a [`Card`], e.g. when several emails
+//! have a `pref` of 1 and the preferred one is ambiguous (see [`crate::ValidationCode::DuplicatePref`]).
+//!
+//! The entries are ordered by their current `pref` (an entry without `pref` coming last) then by key,
+//! and get the `pref` values 1, 2, 3... in that order, so the most preferred entry stays first.
+//! As `pref` is at most 100, the entries after the 100th all get 100.
+
+use std::collections::HashMap;
+
+use crate::{Card, SmallString};
+
+/// A map of a [`Card`] whose entries have a `pref` property, see [`Card::renumber_prefs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrefProperty {
+    /// `nicknames`
+    Nicknames,
+    /// `speakToAs/pronouns`
+    Pronouns,
+    /// `emails`
+    Emails,
+    /// `onlineServices`
+    OnlineServices,
+    /// `phones`
+    Phones,
+    /// `preferredLanguages`
+    PreferredLanguages,
+    /// `calendars`
+    Calendars,
+    /// `schedulingAddresses`
+    SchedulingAddresses,
+    /// `addresses`
+    Addresses,
+    /// `cryptoKeys`
+    CryptoKeys,
+    /// `directories`
+    Directories,
+    /// `links`
+    Links,
+    /// `media`
+    Media,
+}
+
+impl PrefProperty {
+    /// All the maps with a `pref` property.
+    pub const ALL: [PrefProperty; 13] = [
+        PrefProperty::Nicknames,
+        PrefProperty::Pronouns,
+        PrefProperty::Emails,
+        PrefProperty::OnlineServices,
+        PrefProperty::Phones,
+        PrefProperty::PreferredLanguages,
+        PrefProperty::Calendars,
+        PrefProperty::SchedulingAddresses,
+        PrefProperty::Addresses,
+        PrefProperty::CryptoKeys,
+        PrefProperty::Directories,
+        PrefProperty::Links,
+        PrefProperty::Media,
+    ];
+
+    /// Get the path of the map, as serialized (e.g. `speakToAs/pronouns`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrefProperty::Nicknames => "nicknames",
+            PrefProperty::Pronouns => "speakToAs/pronouns",
+            PrefProperty::Emails => "emails",
+            PrefProperty::OnlineServices => "onlineServices",
+            PrefProperty::Phones => "phones",
+            PrefProperty::PreferredLanguages => "preferredLanguages",
+            PrefProperty::Calendars => "calendars",
+            PrefProperty::SchedulingAddresses => "schedulingAddresses",
+            PrefProperty::Addresses => "addresses",
+            PrefProperty::CryptoKeys => "cryptoKeys",
+            PrefProperty::Directories => "directories",
+            PrefProperty::Links => "links",
+            PrefProperty::Media => "media",
+        }
+    }
+}
+
+/// The highest `pref` allowed by RFC 9553
+const MAX_PREF: u64 = 100;
+
+/// Renumber the `pref` of the entries of a map, returning the number of changed entries
+fn renumber<T, P: Copy + TryFrom<u64> + Into<u64>>(
+    entries: &mut Option<HashMap<SmallString, T>>,
+    pref: impl Fn(&mut T) -> &mut Option<P>,
+) -> usize {
+    let mut sorted: Vec<(u64, &SmallString, &mut T)> = entries
+        .iter_mut()
+        .flatten()
+        .map(|(key, entry)| (pref(entry).map_or(u64::MAX, Into::into), key, entry))
+        .collect();
+    sorted.sort_by(|(a_pref, a_key, _), (b_pref, b_key, _)| (a_pref, a_key).cmp(&(b_pref, b_key)));
+    let mut changed = 0;
+    for (idx, (_, _, entry)) in sorted.into_iter().enumerate() {
+        let renumbered = P::try_from((idx as u64 + 1).min(MAX_PREF)).ok();
+        let current = pref(entry);
+        if current.map(Into::into) != renumbered.map(Into::into) {
+            *current = renumbered;
+            changed += 1;
+        }
+    }
+    changed
+}
+
+impl Card {
+    /// Renumber the `pref` of the entries of a map from 1, keeping their order, see the [module documentation](crate::pref).
+    ///
+    /// Returns the number of entries whose `pref` changed.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, EmailAddress, PrefProperty, SmallString};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut card = Card::new_with_latest_version("1234");
+    /// let mut work = EmailAddress::new("jane@example.com");
+    /// work.pref = Some(1);
+    /// let mut home = EmailAddress::new("jane@example.org");
+    /// home.pref = Some(1);
+    /// card.emails = Some(HashMap::from([
+    ///     (SmallString::from("home"), home),
+    ///     (SmallString::from("work"), work),
+    /// ]));
+    /// assert_eq!(card.renumber_prefs(PrefProperty::Emails), 1);
+    /// let emails = card.emails.unwrap();
+    /// assert_eq!(emails[&SmallString::from("home")].pref, Some(1));
+    /// assert_eq!(emails[&SmallString::from("work")].pref, Some(2));
+    /// ```
+    pub fn renumber_prefs(&mut self, property: PrefProperty) -> usize {
+        let changed = match property {
+            PrefProperty::Nicknames => renumber(&mut self.nicknames, |n| &mut n.pref),
+            PrefProperty::Pronouns => match &mut self.speak_to_as {
+                Some(speak_to_as) => renumber(&mut speak_to_as.pronouns, |p| &mut p.pref),
+                None => 0,
+            },
+            PrefProperty::Emails => renumber(&mut self.emails, |e| &mut e.pref),
+            PrefProperty::OnlineServices => renumber(&mut self.online_services, |o| &mut o.pref),
+            PrefProperty::Phones => renumber(&mut self.phones, |p| &mut p.pref),
+            PrefProperty::PreferredLanguages => {
+                renumber(&mut self.preferred_languages, |l| &mut l.pref)
+            }
+            PrefProperty::Calendars => renumber(&mut self.calendars, |c| &mut c.pref),
+            PrefProperty::SchedulingAddresses => {
+                renumber(&mut self.scheduling_addresses, |s| &mut s.pref)
+            }
+            PrefProperty::Addresses => renumber(&mut self.addresses, |a| &mut a.pref),
+            PrefProperty::CryptoKeys => renumber(&mut self.crypto_keys, |c| &mut c.pref),
+            PrefProperty::Directories => renumber(&mut self.directories, |d| &mut d.pref),
+            PrefProperty::Links => renumber(&mut self.links, |l| &mut l.pref),
+            PrefProperty::Media => renumber(&mut self.media, |m| &mut m.pref),
+        };
+        if changed > 0 {
+            self.touch();
+        }
+        changed
+    }
+
+    /// Renumber the `pref` of the entries of all the maps, see [`Card::renumber_prefs`].
+    ///
+    /// Returns the number of entries whose `pref` changed.
+    pub fn renumber_all_prefs(&mut self) -> usize {
+        PrefProperty::ALL
+            .iter()
+            .map(|property| self.renumber_prefs(*property))
+            .sum()
+    }
+}
@@ -0,0 +1,223 @@
+//! Selection of the preferred entry of a map of a [`Card`] (nicknames, emails, online services, ...).
+//!
+//! An entry in the requested context is preferred, then the entry with the lowest `pref`,
+//! an entry without `pref` coming last. The key of the entries breaks the ties.
+//! The sorted entries follow the same order, the entries not in the context being left out.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::resource::uri_scheme;
+use crate::{Card, Context, Nickname, OnlineService, Pronouns, SmallString, SpeakToAs, Title};
+
+/// Get the preferred entry of a map, in a context if set
+pub(crate) fn preferred<'a, T>(
+    context: Option<&Context>,
+    entries: &'a HashMap<SmallString, T>,
+    contexts: impl Fn(&T) -> Option<&HashMap<Context, bool>>,
+    pref: impl Fn(&T) -> Option<u64>,
+) -> Option<&'a T> {
+    preferred_entry(context, entries, contexts, pref).map(|(_, entry)| entry)
+}
+
+/// Get the key and the preferred entry of a map, in a context if set
+pub(crate) fn preferred_entry<'a, T>(
+    context: Option<&Context>,
+    entries: &'a HashMap<SmallString, T>,
+    contexts: impl Fn(&T) -> Option<&HashMap<Context, bool>>,
+    pref: impl Fn(&T) -> Option<u64>,
+) -> Option<(&'a SmallString, &'a T)> {
+    entries.iter().min_by_key(|(key, entry)| {
+        let in_context = context.is_some_and(|context| {
+            contexts(entry).is_some_and(|map| map.get(context) == Some(&true))
+        });
+        (!in_context, pref(entry).unwrap_or(u64::MAX), key.as_str())
+    })
+}
+
+/// Get the entries of a map sorted by `pref` then by key, only the entries in a context if set
+pub(crate) fn sorted_by_pref<'a, T>(
+    context: Option<&Context>,
+    entries: &'a Option<HashMap<SmallString, T>>,
+    contexts: impl Fn(&T) -> Option<&HashMap<Context, bool>>,
+    pref: impl Fn(&T) -> Option<u64>,
+) -> Vec<(&'a str, &'a T)> {
+    let mut sorted: Vec<(&str, &T)> = entries
+        .iter()
+        .flatten()
+        .filter(|(_, entry)| {
+            context.is_none_or(|context| {
+                contexts(entry).is_some_and(|map| map.get(context) == Some(&true))
+            })
+        })
+        .map(|(key, entry)| (key.as_str(), entry))
+        .collect();
+    sorted.sort_by_key(|(key, entry)| (pref(entry).unwrap_or(u64::MAX), *key));
+    sorted
+}
+
+impl Card {
+    /// Get the titles sorted by key, the titles having no `pref` nor `contexts`.
+    pub fn titles_sorted(&self) -> Vec<(&str, &Title)> {
+        let mut sorted: Vec<(&str, &Title)> = self
+            .titles
+            .iter()
+            .flatten()
+            .map(|(key, title)| (key.as_str(), title))
+            .collect();
+        sorted.sort_by_key(|(key, _)| *key);
+        sorted
+    }
+
+    /// Get the nicknames sorted by `pref`, a nickname without `pref` coming last, then by key.
+    pub fn nicknames_sorted(&self) -> Vec<(&str, &Nickname)> {
+        sorted_by_pref(
+            None,
+            &self.nicknames,
+            |nickname| nickname.contexts.as_ref(),
+            |nickname| nickname.pref.map(u64::from),
+        )
+    }
+
+    /// Get the nicknames in a context, sorted as [`Card::nicknames_sorted`].
+    pub fn nicknames_sorted_in(&self, context: Context) -> Vec<(&str, &Nickname)> {
+        sorted_by_pref(
+            Some(&context),
+            &self.nicknames,
+            |nickname| nickname.contexts.as_ref(),
+            |nickname| nickname.pref.map(u64::from),
+        )
+    }
+
+    /// The number of the preferred phone, a `tel:` URI or text
+    pub(crate) fn preferred_phone_number(&self) -> Option<&str> {
+        let phone = preferred(
+            None,
+            self.phones.as_ref()?,
+            |phone| phone.contexts.as_ref(),
+            |phone| phone.pref.map(u64::from),
+        )?;
+        Some(phone.number.as_str())
+    }
+
+    /// The address of the preferred email
+    pub(crate) fn preferred_email_address(&self) -> Option<&str> {
+        let email = preferred(
+            None,
+            self.emails.as_ref()?,
+            |email| email.contexts.as_ref(),
+            |email| email.pref.map(u64::from),
+        )?;
+        Some(email.address.as_str())
+    }
+
+    /// The preferred address on one line: its full address, or its label with the lines separated by commas
+    pub(crate) fn preferred_address_label(&self) -> Option<String> {
+        let address = preferred(
+            None,
+            self.addresses.as_ref()?,
+            |_| None,
+            |address| address.pref,
+        )?;
+        let label = match &address.full {
+            Some(full) => full.clone(),
+            None => address.to_label().lines().collect::<Vec<_>>().join(", "),
+        };
+        (!label.is_empty()).then_some(label)
+    }
+
+    /// The text of the first note by key, the notes having no `pref`
+    pub(crate) fn first_note(&self) -> Option<&str> {
+        let note = preferred(None, self.notes.as_ref()?, |_| None, |_| None)?;
+        Some(note.note.as_str())
+    }
+
+    /// Get the preferred nickname, in the given context if any.
+    pub fn preferred_nickname(&self, context: Option<Context>) -> Option<&Nickname> {
+        preferred(
+            context.as_ref(),
+            self.nicknames.as_ref()?,
+            |nickname| nickname.contexts.as_ref(),
+            |nickname| nickname.pref.map(u64::from),
+        )
+    }
+
+    /// Get the preferred online service of a service (e.g. `Mastodon`, compared case-insensitively),
+    /// in the given context if any.
+    ///
+    /// An online service without `service` is of the service named by the scheme of its URI (e.g. `xmpp`).
+    pub fn preferred_online_service(
+        &self,
+        service: &str,
+        context: Option<Context>,
+    ) -> Option<&OnlineService> {
+        let service = service.trim().to_lowercase();
+        let matching: HashMap<SmallString, &OnlineService> = self
+            .online_services
+            .iter()
+            .flatten()
+            .filter(|(_, online_service)| service_name(online_service).as_ref() == Some(&service))
+            .map(|(key, online_service)| (key.clone(), online_service))
+            .collect();
+        preferred(
+            context.as_ref(),
+            &matching,
+            |online_service| online_service.contexts.as_ref(),
+            |online_service| online_service.pref.map(u64::from),
+        )
+        .copied()
+    }
+
+    /// Get the online services grouped by service, the name of the service being lowercased,
+    /// each group sorted by `pref` then by key.
+    ///
+    /// An online service without `service` is grouped under the scheme of its URI (e.g. `xmpp`),
+    /// an online service without both is left out.
+    pub fn online_services_grouped(&self) -> BTreeMap<String, Vec<&OnlineService>> {
+        let mut groups: BTreeMap<String, Vec<&OnlineService>> = BTreeMap::new();
+        let sorted = sorted_by_pref(
+            None,
+            &self.online_services,
+            |online_service| online_service.contexts.as_ref(),
+            |online_service| online_service.pref.map(u64::from),
+        );
+        for (_, online_service) in sorted {
+            if let Some(service) = service_name(online_service) {
+                groups.entry(service).or_default().push(online_service);
+            }
+        }
+        groups
+    }
+}
+
+/// Get the service of an online service lowercased, else the scheme of its URI
+fn service_name(online_service: &OnlineService) -> Option<String> {
+    online_service
+        .service
+        .as_deref()
+        .map(str::trim)
+        .filter(|service| !service.is_empty())
+        .or_else(|| uri_scheme(online_service.uri.as_deref()?))
+        .map(str::to_lowercase)
+}
+
+impl SpeakToAs {
+    /// Get the pronouns sorted by `pref`, pronouns without `pref` coming last, then by key.
+    pub fn pronouns_sorted(&self) -> Vec<(&str, &Pronouns)> {
+        sorted_by_pref(
+            None,
+            &self.pronouns,
+            |pronouns| pronouns.contexts.as_ref(),
+            |pronouns| pronouns.pref.map(u64::from),
+        )
+    }
+
+    /// Get the pronouns in a context, sorted as [`SpeakToAs::pronouns_sorted`].
+    pub fn pronouns_sorted_in(&self, context: Context) -> Vec<(&str, &Pronouns)> {
+        sorted_by_pref(
+            Some(&context),
+            &self.pronouns,
+            |pronouns| pronouns.contexts.as_ref(),
+            |pronouns| pronouns.pref.map(u64::from),
+        )
+    }
+}
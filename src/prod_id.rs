@@ -0,0 +1,92 @@
+//! The `prodId` of a [`Card`], identifying the product that created or last modified it.
+//!
+//! The property is free text, most products write their name and version in it,
+//! either as a simple `product version` string or as an ISO 9070 public identifier (`-//owner//product version//EN`).
+
+use std::fmt;
+
+use crate::Card;
+
+/// A product name and its version, formatted as `product version` in the `prodId` property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProdId {
+    /// The name of the product.
+    pub product: String,
+    /// The version of the product.
+    pub version: Option<String>,
+}
+
+impl ProdId {
+    /// Creates a new ProdId with the specified product and version.
+    pub fn new(product: &str, version: &str) -> Self {
+        Self {
+            product: product.to_string(),
+            version: (!version.is_empty()).then(|| version.to_string()),
+        }
+    }
+
+    /// Parse a `prodId`, best-effort, `None` if it is empty.
+    ///
+    /// The product of a public identifier is its description, e.g. `Sabre VObject` for `-//Sabre//Sabre VObject 4.4.2//EN`.
+    /// The version is the last word if it starts with a digit (e.g. `1.23.5` or `v2`), or follows a `/` (e.g. `DAVx5/4.3`).
+    pub fn parse(prod_id: &str) -> Option<Self> {
+        let mut text = prod_id.trim();
+        if text.starts_with("-//") || text.starts_with("+//") {
+            let mut parts = text.split("//").skip(1).map(str::trim);
+            let owner = parts.next().unwrap_or_default();
+            text = match parts.next() {
+                Some(description) if !description.is_empty() => description,
+                _ => owner,
+            };
+        }
+        if text.is_empty() {
+            return None;
+        }
+        if let Some((product, version)) = text.split_once('/') {
+            if !product.contains(' ') && version.starts_with(|c: char| c.is_ascii_digit()) {
+                return Some(Self::new(product, version));
+            }
+        }
+        if let Some((product, version)) = text.rsplit_once(' ') {
+            let number = version
+                .strip_prefix(['v', 'V'])
+                .filter(|number| number.starts_with(|c: char| c.is_ascii_digit()))
+                .unwrap_or(version);
+            if number.starts_with(|c: char| c.is_ascii_digit()) {
+                let product = product.trim_end();
+                let product = product
+                    .strip_suffix("version")
+                    .or_else(|| product.strip_suffix("Version"))
+                    .map_or(product, str::trim_end);
+                return Some(Self::new(product, number));
+            }
+        }
+        Some(Self {
+            product: text.to_string(),
+            version: None,
+        })
+    }
+}
+
+impl fmt::Display for ProdId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.version {
+            Some(version) => write!(f, "{} {}", self.product, version),
+            None => write!(f, "{}", self.product),
+        }
+    }
+}
+
+impl Card {
+    /// Set the `prodId` of the Card to `product version`.
+    pub fn set_generator(&mut self, product: &str, version: &str) {
+        self.prod_id = Some(ProdId::new(product, version).to_string());
+        self.touch();
+    }
+
+    /// Get the product and the version of the product that created the Card, parsed from `prodId`, see [`ProdId::parse`].
+    pub fn generator(&self) -> Option<(String, Option<String>)> {
+        let prod_id = ProdId::parse(self.prod_id.as_deref()?)?;
+        Some((prod_id.product, prod_id.version))
+    }
+}
@@ -0,0 +1,82 @@
+//! The source of a [`Card`] and of its entries (e.g. a CardDAV server, a CSV file), when merging address books.
+//!
+//! The sources are stored in the vendor-specific property [`PROVENANCE_PROPERTY`] of the Card, an object whose
+//! keys are paths of the Card (e.g. `phones/p1`, the empty path being the whole Card) and whose values are
+//! the identifiers of the sources. As an unknown property, it is kept by the serialization and ignored
+//! by the other implementations.
+//!
+//! ```json
+//! "jscontact.rs:provenance": { "": "carddav", "phones/p1": "csv" }
+//! ```
+//!
+//! [`crate::dedup::merge_group`] records the source of the properties and entries taken from each Card.
+
+use serde_json::{Map, Value};
+
+use crate::Card;
+
+/// The vendor-specific property of a Card holding the sources of the Card and of its entries.
+pub const PROVENANCE_PROPERTY: &str = "jscontact.rs:provenance";
+
+impl Card {
+    /// Set the source of the whole Card, e.g. after parsing it.
+    ///
+    /// The sources of the entries set by [`Card::set_provenance_of`] are kept.
+    pub fn set_provenance(&mut self, source_id: &str) {
+        self.set_provenance_of("", source_id);
+    }
+
+    /// Set the source of a property or an entry of the Card, by its path (e.g. `phones/p1`).
+    pub fn set_provenance_of(&mut self, path: &str, source_id: &str) {
+        self.insert_provenance(path, source_id);
+        self.touch();
+    }
+
+    /// Set the source of a path, without bumping the revision
+    fn insert_provenance(&mut self, path: &str, source_id: &str) {
+        let provenance = self
+            .extensions
+            .entry(PROVENANCE_PROPERTY.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !provenance.is_object() {
+            *provenance = Value::Object(Map::new());
+        }
+        if let Value::Object(sources) = provenance {
+            sources.insert(path.to_string(), Value::String(source_id.to_string()));
+        }
+    }
+
+    /// Get the source of a path of the Card: the source of the path, else of its nearest parent
+    /// (e.g. `phones/p1` for `phones/p1/number`), else of the whole Card.
+    ///
+    /// ```rust
+    /// use jscontact::Card;
+    ///
+    /// let mut card = Card::new_with_latest_version("1234");
+    /// card.set_provenance("carddav");
+    /// card.set_provenance_of("phones/p1", "csv");
+    /// assert_eq!(card.provenance_of("phones/p1/number"), Some("csv"));
+    /// assert_eq!(card.provenance_of("emails/e1"), Some("carddav"));
+    /// ```
+    pub fn provenance_of(&self, path: &str) -> Option<&str> {
+        let sources = self.extensions.get(PROVENANCE_PROPERTY)?.as_object()?;
+        let mut path = path;
+        loop {
+            if let Some(source) = sources.get(path).and_then(Value::as_str) {
+                return Some(source);
+            }
+            if path.is_empty() {
+                return None;
+            }
+            path = path.rsplit_once('/').map_or("", |(parent, _)| parent);
+        }
+    }
+}
+
+/// Record in the merged Card the source of a property or an entry taken from another Card, if known
+pub(crate) fn record_merged(merged: &mut Card, other: &Card, other_path: &str, merged_path: &str) {
+    if let Some(source) = other.provenance_of(other_path) {
+        let source = source.to_string();
+        merged.insert_provenance(merged_path, &source);
+    }
+}
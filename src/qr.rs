@@ -0,0 +1,277 @@
+//! Compact text payloads of a [`Card`] to encode in a QR code: a MeCard or a minimal vCard.
+//!
+//! Only the preferred values are kept (the lowest `pref`, then the first key), so the payload stays small.
+//! The QR code encoding itself is left to the application.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::iri::iri_to_uri;
+use crate::preferred::{preferred, preferred_entry};
+use crate::{Card, Context, ConversionReport, NameComponentKind, SmallString};
+
+/// The reason of the losses of the entries other than the preferred one
+const NOT_PREFERRED: &str = "only the preferred entry is kept in a MeCard";
+
+/// The reason of the losses of the properties without MeCard field
+const NO_FIELD: &str = "no MeCard field";
+
+/// Get the key of the preferred entry of a map, see [`preferred_entry`]
+fn preferred_key<T>(
+    entries: &HashMap<SmallString, T>,
+    contexts: impl Fn(&T) -> Option<&HashMap<Context, bool>>,
+    pref: impl Fn(&T) -> Option<u64>,
+) -> Option<&str> {
+    preferred_entry(None, entries, contexts, pref).map(|(key, _)| key.as_str())
+}
+
+/// Escape the special characters of a MeCard value: `\`, `;`, `:` and `,`
+fn mecard_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | ';' | ':' | ',') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escape the special characters of a vCard text value (RFC 6350, Section 3.4)
+fn vcard_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl Card {
+    /// The MeCard N value: `surname,given`, or the full name if the name has neither
+    fn mecard_name(&self) -> Option<String> {
+        let name = self.name.as_ref()?;
+        let join = |kinds: &[NameComponentKind]| {
+            name.components
+                .iter()
+                .flatten()
+                .filter(|component| kinds.contains(&component.kind))
+                .map(|component| mecard_escape(&component.value))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let surname = join(&[NameComponentKind::Surname, NameComponentKind::Surname2]);
+        let given = join(&[NameComponentKind::Given, NameComponentKind::Given2]);
+        if !surname.is_empty() || !given.is_empty() {
+            return Some(format!("{},{}", surname, given));
+        }
+        let full = name.to_full_string();
+        (!full.is_empty()).then(|| mecard_escape(&full))
+    }
+
+    /// The formatted name of the vCard: the full name, or the uid as last resort
+    fn minimal_formatted_name(&self) -> String {
+        let full = self
+            .name
+            .as_ref()
+            .map(|name| name.to_full_string())
+            .unwrap_or_default();
+        if full.is_empty() {
+            self.uid.clone()
+        } else {
+            full
+        }
+    }
+
+    /// Build the MeCard of the Card, e.g. `MECARD:N:Doe,Jane;TEL:+15555555555;EMAIL:jane@example.com;;`.
+    ///
+    /// The fields are the name (`N`, as `surname,given`), then the preferred phone (`TEL`), email (`EMAIL`),
+    /// address (`ADR`, its full address or its label on one line), link (`URL`) and the first note (`NOTE`),
+    /// the missing values being left out.
+    /// The `\`, `;`, `:` and `,` characters of the values are escaped with a backslash.
+    /// The link is converted to a URI, see [`crate::iri_to_uri`].
+    ///
+    /// See [`Card::to_mecard_with_report`] for the list of what is left out.
+    pub fn to_mecard(&self) -> String {
+        let mut fields = Vec::new();
+        if let Some(name) = self.mecard_name() {
+            fields.push(format!("N:{}", name));
+        }
+        if let Some(number) = self.preferred_phone_number() {
+            let number = number.strip_prefix("tel:").unwrap_or(number);
+            fields.push(format!("TEL:{}", mecard_escape(number)));
+        }
+        if let Some(address) = self.preferred_email_address() {
+            fields.push(format!("EMAIL:{}", mecard_escape(address)));
+        }
+        if let Some(label) = self.preferred_address_label() {
+            fields.push(format!("ADR:{}", mecard_escape(&label)));
+        }
+        let link = self.links.as_ref().and_then(|links| {
+            preferred(None, links, |link| link.contexts.as_ref(), |link| link.pref)
+        });
+        if let Some(link) = link {
+            fields.push(format!("URL:{}", mecard_escape(&iri_to_uri(&link.uri))));
+        }
+        if let Some(note) = self.first_note() {
+            fields.push(format!("NOTE:{}", mecard_escape(note)));
+        }
+        let mut mecard = String::from("MECARD:");
+        for field in fields {
+            mecard.push_str(&field);
+            mecard.push(';');
+        }
+        mecard.push(';');
+        mecard
+    }
+
+    /// Build the MeCard of the Card, see [`Card::to_mecard`], and the report of what is left out.
+    ///
+    /// The losses are the entries other than the preferred ones, the name components and properties
+    /// not in `N`, and the properties without MeCard field, with their values. `@type`, `version`
+    /// and `uid` are not reported. The losses are sorted by path.
+    ///
+    /// ```rust
+    /// use jscontact::Card;
+    ///
+    /// let card: Card = serde_json::from_value(serde_json::json!({
+    ///     "@type": "Card",
+    ///     "version": "1.0",
+    ///     "uid": "1234",
+    ///     "phones": { "tel1": { "number": "tel:+1-555-555-0100" } },
+    ///     "titles": { "t1": { "name": "Research Scientist" } }
+    /// }))
+    /// .unwrap();
+    /// let (mecard, report) = card.to_mecard_with_report();
+    /// assert_eq!(mecard, "MECARD:TEL:+1-555-555-0100;;");
+    /// assert_eq!(report.lossy[0].path, "titles");
+    /// ```
+    pub fn to_mecard_with_report(&self) -> (String, ConversionReport) {
+        let mut report = ConversionReport::new();
+        let Ok(Value::Object(properties)) = serde_json::to_value(self) else {
+            return (self.to_mecard(), report);
+        };
+        for (property, value) in properties {
+            let kept = match property.as_str() {
+                "@type" | "version" | "uid" => continue,
+                "name" => {
+                    self.mecard_name_losses(value, &mut report);
+                    continue;
+                }
+                "phones" => self.phones.as_ref().and_then(|phones| {
+                    preferred_key(
+                        phones,
+                        |phone| phone.contexts.as_ref(),
+                        |phone| phone.pref.map(u64::from),
+                    )
+                }),
+                "emails" => self.emails.as_ref().and_then(|emails| {
+                    preferred_key(
+                        emails,
+                        |email| email.contexts.as_ref(),
+                        |email| email.pref.map(u64::from),
+                    )
+                }),
+                "addresses" => self
+                    .preferred_address_label()
+                    .and(self.addresses.as_ref())
+                    .and_then(|addresses| {
+                        preferred_key(addresses, |_| None, |address| address.pref)
+                    }),
+                "links" => self.links.as_ref().and_then(|links| {
+                    preferred_key(links, |link| link.contexts.as_ref(), |link| link.pref)
+                }),
+                "notes" => self
+                    .notes
+                    .as_ref()
+                    .and_then(|notes| preferred_key(notes, |_| None, |_| None)),
+                _ => None,
+            };
+            match (kept, value) {
+                (Some(kept), Value::Object(entries)) => {
+                    for (key, entry) in entries.into_iter().filter(|(key, _)| key != kept) {
+                        let path = format!("{}/{}", property, key);
+                        report.add_loss_with_value(&path, NOT_PREFERRED, entry);
+                    }
+                }
+                (_, value) => report.add_loss_with_value(&property, NO_FIELD, value),
+            }
+        }
+        report.lossy.sort_by(|a, b| a.path.cmp(&b.path));
+        (self.to_mecard(), report)
+    }
+
+    /// Record the parts of the name left out of the MeCard `N` field
+    fn mecard_name_losses(&self, name: Value, report: &mut ConversionReport) {
+        let Value::Object(name) = name else {
+            return;
+        };
+        let kept = [
+            NameComponentKind::Surname,
+            NameComponentKind::Surname2,
+            NameComponentKind::Given,
+            NameComponentKind::Given2,
+        ];
+        let by_components = self
+            .name
+            .iter()
+            .flat_map(|name| name.components.iter().flatten())
+            .any(|component| kept.contains(&component.kind) && !component.value.is_empty());
+        for (key, value) in name {
+            match (key.as_str(), value) {
+                ("@type", _) => {}
+                // the full name is built from the components without full name
+                ("full" | "components", _) if !by_components => {}
+                ("components", Value::Array(components)) => {
+                    for (index, component) in components.into_iter().enumerate() {
+                        let is_kept = self
+                            .name
+                            .as_ref()
+                            .and_then(|name| name.components.as_ref()?.get(index))
+                            .is_some_and(|component| kept.contains(&component.kind));
+                        if !is_kept {
+                            let path = format!("name/components/{}", index);
+                            report.add_loss_with_value(&path, NO_FIELD, component);
+                        }
+                    }
+                }
+                (key, value) => {
+                    report.add_loss_with_value(&format!("name/{}", key), NO_FIELD, value);
+                }
+            }
+        }
+    }
+
+    /// Build a vCard 4.0 limited to the formatted name (`FN`), the preferred phone (`TEL`) and email (`EMAIL`).
+    ///
+    /// The lines end with CRLF and are not folded. The text values are escaped as defined by RFC 6350.
+    /// See the `vcard4-interop` feature for a complete conversion.
+    pub fn to_vcard_minimal(&self) -> String {
+        let mut vcard = String::from("BEGIN:VCARD\r\nVERSION:4.0\r\n");
+        vcard.push_str(&format!(
+            "FN:{}\r\n",
+            vcard_escape(&self.minimal_formatted_name())
+        ));
+        if let Some(number) = self.preferred_phone_number() {
+            let tel = if number.starts_with("tel:") {
+                format!("TEL;VALUE=uri:{}\r\n", iri_to_uri(number))
+            } else {
+                format!("TEL:{}\r\n", vcard_escape(number))
+            };
+            vcard.push_str(&tel);
+        }
+        if let Some(address) = self.preferred_email_address() {
+            vcard.push_str(&format!("EMAIL:{}\r\n", vcard_escape(address)));
+        }
+        vcard.push_str("END:VCARD\r\n");
+        vcard
+    }
+}
@@ -0,0 +1,261 @@
+//! Redacted `Debug` formatting of the personal data of a [`Card`], to log Cards without leaking them.
+//!
+//! The values of the emails, phones, addresses, notes, anniversaries, online services and name
+//! are masked, keeping their first character (e.g. `j***@e***` for `jane@example.com`), while their kinds are kept.
+//! [`Card::debug_redacted`] always formats with the redaction,
+//! the `redact-debug` feature also uses it for the `Debug` implementations of these types.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{
+    Address, Anniversary, Card, EmailAddress, Name, Note, OnlineService, Phone, PlaceRef,
+    SmallString,
+};
+
+/// The mask of the masked values
+const MASK: &str = "***";
+
+/// Format with a closure
+struct DebugFn<F>(F);
+
+impl<F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result> fmt::Debug for DebugFn<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.0)(f)
+    }
+}
+
+/// Mask a value, keeping its first character
+fn mask(value: &str) -> String {
+    match value.chars().next() {
+        Some(first) => format!("{}{}", first, MASK),
+        None => String::new(),
+    }
+}
+
+/// Mask an email address, keeping the first character of the local part and of the domain
+fn mask_email(address: &str) -> String {
+    match address.split_once('@') {
+        Some((local, domain)) => format!("{}@{}", mask(local), mask(domain)),
+        None => mask(address),
+    }
+}
+
+/// Mask an optional value
+fn mask_option(value: &Option<String>) -> Option<String> {
+    value.as_deref().map(mask)
+}
+
+/// The `Debug` formatting of a type with its personal data masked
+pub(crate) trait RedactedDebug {
+    /// Format the value with its personal data masked
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+/// Format a value with [`RedactedDebug`]
+struct Redacted<'a, T: ?Sized>(&'a T);
+
+impl<T: RedactedDebug + ?Sized> fmt::Debug for Redacted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_redacted(f)
+    }
+}
+
+/// Format a map of the Card with its values redacted, sorted by key
+fn redacted_map<T: RedactedDebug>(map: &HashMap<SmallString, T>) -> impl fmt::Debug + '_ {
+    DebugFn(move |f: &mut fmt::Formatter<'_>| {
+        let mut entries: Vec<(&SmallString, &T)> = map.iter().collect();
+        entries.sort_by_key(|(key, _)| *key);
+        f.debug_map()
+            .entries(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, Redacted(value))),
+            )
+            .finish()
+    })
+}
+
+impl RedactedDebug for EmailAddress {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmailAddress")
+            .field("address", &mask_email(&self.address))
+            .field("contexts", &self.contexts)
+            .field("pref", &self.pref)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RedactedDebug for Phone {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Phone")
+            .field("number", &mask(&self.number))
+            .field("features", &self.features)
+            .field("contexts", &self.contexts)
+            .field("pref", &self.pref)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RedactedDebug for Address {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let components = self.components.as_ref().map(|components| {
+            components
+                .iter()
+                .map(|component| (&component.kind, mask(&component.value)))
+                .collect::<Vec<_>>()
+        });
+        f.debug_struct("Address")
+            .field("components", &components)
+            .field("full", &mask_option(&self.full))
+            .field("country_code", &self.country_code)
+            .field("contexts", &self.contexts)
+            .field("pref", &self.pref)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RedactedDebug for Note {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Note")
+            .field("note", &format!("{} chars", self.note.chars().count()))
+            .field("created", &self.created)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RedactedDebug for Anniversary {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Anniversary")
+            .field("kind", &self.kind)
+            .field("date", &MASK)
+            .field("place", &self.place.as_ref().map(Redacted))
+            .finish_non_exhaustive()
+    }
+}
+
+impl RedactedDebug for PlaceRef {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlaceRef::Inline(address) => f.debug_tuple("Inline").field(&Redacted(address)).finish(),
+            PlaceRef::Key(key) => f.debug_tuple("Key").field(key).finish(),
+        }
+    }
+}
+
+impl RedactedDebug for OnlineService {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnlineService")
+            .field("service", &self.service)
+            .field("uri", &mask_option(&self.uri))
+            .field("user", &mask_option(&self.user))
+            .field("contexts", &self.contexts)
+            .field("pref", &self.pref)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RedactedDebug for Name {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let components = self.components.as_ref().map(|components| {
+            components
+                .iter()
+                .map(|component| (&component.kind, mask(&component.value)))
+                .collect::<Vec<_>>()
+        });
+        f.debug_struct("Name")
+            .field("components", &components)
+            .field("full", &mask_option(&self.full))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Use the redacted formatting for the `Debug` implementation of the types
+#[cfg(feature = "redact-debug")]
+macro_rules! redacted_debug {
+    ($($name:ident),*) => {
+        $(
+            impl fmt::Debug for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    self.fmt_redacted(f)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "redact-debug")]
+redacted_debug!(
+    EmailAddress,
+    Phone,
+    Address,
+    Note,
+    Anniversary,
+    OnlineService,
+    Name
+);
+
+/// A Card formatted by `Debug` with its personal data masked, see [`Card::debug_redacted`].
+pub struct RedactedCard<'a>(&'a Card);
+
+impl fmt::Debug for RedactedCard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let card = self.0;
+        let mut languages: Vec<&String> = card
+            .get_raw_localizations()
+            .map(|localizations| localizations.keys().collect())
+            .unwrap_or_default();
+        languages.sort();
+        let mut extensions: Vec<&String> = card.extensions.keys().collect();
+        extensions.sort();
+        f.debug_struct("Card")
+            .field("version", &card.version)
+            .field("uid", &card.uid)
+            .field("kind", &card.kind)
+            .field("language", &card.language)
+            .field("created", &card.created)
+            .field("updated", &card.updated)
+            .field("prod_id", &card.prod_id)
+            .field("members", &card.members)
+            .field("related_to", &card.related_to)
+            .field("name", &card.name.as_ref().map(Redacted))
+            .field("nicknames", &card.nicknames)
+            .field("organizations", &card.organizations)
+            .field("speak_to_as", &card.speak_to_as)
+            .field("titles", &card.titles)
+            .field("emails", &card.emails.as_ref().map(redacted_map))
+            .field(
+                "online_services",
+                &card.online_services.as_ref().map(redacted_map),
+            )
+            .field("phones", &card.phones.as_ref().map(redacted_map))
+            .field("preferred_languages", &card.preferred_languages)
+            .field("calendars", &card.calendars)
+            .field("scheduling_addresses", &card.scheduling_addresses)
+            .field(
+                "anniversaries",
+                &card.anniversaries.as_ref().map(redacted_map),
+            )
+            .field("addresses", &card.addresses.as_ref().map(redacted_map))
+            .field("crypto_keys", &card.crypto_keys)
+            .field("directories", &card.directories)
+            .field("links", &card.links)
+            .field("media", &card.media)
+            .field("keywords", &card.keywords)
+            .field("notes", &card.notes.as_ref().map(redacted_map))
+            .field("personal_info", &card.personal_info)
+            .field("localizations", &languages)
+            .field("extensions", &extensions)
+            .finish()
+    }
+}
+
+impl Card {
+    /// Get the Card formatted by `Debug` with its personal data masked, whatever the `redact-debug` feature.
+    ///
+    /// The values of the emails, phones, addresses, notes, anniversaries, online services and name are masked,
+    /// only the languages of the localizations and the names of the extensions are kept.
+    pub fn debug_redacted(&self) -> RedactedCard<'_> {
+        RedactedCard(self)
+    }
+}
@@ -0,0 +1,237 @@
+//! Snapshot of the IANA "JSContact Enum Values" registry (RFC 9553), the allowed values of the enumerated properties.
+//!
+//! This table is the artifact to update when IANA registers new values,
+//! `tests/test_enums.rs` checks that the enums of the crate are in sync with it.
+
+/// The registered values of an enumerated property, in the contexts (object types) where the property is defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisteredEnum {
+    /// The name of the property, e.g. `kind`.
+    pub property: &'static str,
+    /// The object types in which the property has these values, e.g. `Media`.
+    pub contexts: &'static [&'static str],
+    /// The JSContact version in which the values were registered.
+    pub since: &'static str,
+    /// The registered values.
+    pub values: &'static [&'static str],
+}
+
+/// The object types whose `contexts` property has the common values `private` and `work`
+const CONTEXTS: [&str; 14] = [
+    "Address",
+    "Calendar",
+    "CryptoKey",
+    "Directory",
+    "EmailAddress",
+    "LanguagePref",
+    "Link",
+    "Media",
+    "Nickname",
+    "OnlineService",
+    "Organization",
+    "Phone",
+    "Pronouns",
+    "SchedulingAddress",
+];
+
+/// The registered enum values.
+///
+/// The `version` of a Card is not listed, it has its own "JSContact Version" registry.
+pub const ENUM_VALUES: [RegisteredEnum; 17] = [
+    RegisteredEnum {
+        property: "contexts",
+        contexts: &["Address"],
+        since: "1.0",
+        values: &["billing", "delivery"],
+    },
+    RegisteredEnum {
+        property: "contexts",
+        contexts: &CONTEXTS,
+        since: "1.0",
+        values: &["private", "work"],
+    },
+    RegisteredEnum {
+        property: "kind",
+        contexts: &["AddressComponent"],
+        since: "1.0",
+        values: &[
+            "apartment",
+            "block",
+            "building",
+            "country",
+            "direction",
+            "district",
+            "floor",
+            "landmark",
+            "locality",
+            "name",
+            "number",
+            "postcode",
+            "postOfficeBox",
+            "region",
+            "room",
+            "separator",
+            "subdistrict",
+        ],
+    },
+    RegisteredEnum {
+        property: "phoneticSystem",
+        contexts: &["Address", "Name"],
+        since: "1.0",
+        values: &["ipa", "jyut", "piny"],
+    },
+    RegisteredEnum {
+        property: "kind",
+        contexts: &["Anniversary"],
+        since: "1.0",
+        values: &["birth", "death", "wedding"],
+    },
+    RegisteredEnum {
+        property: "kind",
+        contexts: &["Calendar"],
+        since: "1.0",
+        values: &["calendar", "freeBusy"],
+    },
+    RegisteredEnum {
+        property: "kind",
+        contexts: &["Card"],
+        since: "1.0",
+        values: &[
+            "application",
+            "device",
+            "group",
+            "individual",
+            "location",
+            "org",
+        ],
+    },
+    RegisteredEnum {
+        property: "kind",
+        contexts: &["Directory"],
+        since: "1.0",
+        values: &["directory", "entry"],
+    },
+    RegisteredEnum {
+        property: "kind",
+        contexts: &["Link"],
+        since: "1.0",
+        values: &["contact"],
+    },
+    RegisteredEnum {
+        property: "kind",
+        contexts: &["Media"],
+        since: "1.0",
+        values: &["logo", "photo", "sound"],
+    },
+    RegisteredEnum {
+        property: "kind",
+        contexts: &["NameComponent"],
+        since: "1.0",
+        values: &[
+            "credential",
+            "generation",
+            "given",
+            "given2",
+            "separator",
+            "surname",
+            "surname2",
+            "title",
+        ],
+    },
+    RegisteredEnum {
+        property: "kind",
+        contexts: &["PersonalInfo"],
+        since: "1.0",
+        values: &["expertise", "hobby", "interest"],
+    },
+    RegisteredEnum {
+        property: "level",
+        contexts: &["PersonalInfo"],
+        since: "1.0",
+        values: &["high", "low", "medium"],
+    },
+    RegisteredEnum {
+        property: "features",
+        contexts: &["Phone"],
+        since: "1.0",
+        values: &[
+            "fax",
+            "main-number",
+            "mobile",
+            "pager",
+            "text",
+            "textphone",
+            "video",
+            "voice",
+        ],
+    },
+    RegisteredEnum {
+        property: "relation",
+        contexts: &["Relation"],
+        since: "1.0",
+        values: &[
+            "acquaintance",
+            "agent",
+            "child",
+            "co-resident",
+            "co-worker",
+            "colleague",
+            "contact",
+            "crush",
+            "date",
+            "emergency",
+            "friend",
+            "kin",
+            "me",
+            "met",
+            "muse",
+            "neighbor",
+            "parent",
+            "sibling",
+            "spouse",
+            "sweetheart",
+        ],
+    },
+    RegisteredEnum {
+        property: "grammaticalGender",
+        contexts: &["SpeakToAs"],
+        since: "1.0",
+        values: &[
+            "animate",
+            "common",
+            "feminine",
+            "inanimate",
+            "masculine",
+            "neuter",
+        ],
+    },
+    RegisteredEnum {
+        property: "kind",
+        contexts: &["Title"],
+        since: "1.0",
+        values: &["role", "title"],
+    },
+];
+
+/// Parse a JSContact version (e.g. `1.0`) to compare it, an invalid number is 0
+fn parse_version(version: &str) -> Vec<u32> {
+    version
+        .split('.')
+        .map(|number| number.parse().unwrap_or_default())
+        .collect()
+}
+
+/// Get the registered values of a property in an object type, e.g. `("Media", "kind")`, in a JSContact version.
+///
+/// Returns an empty list if the property is not enumerated.
+pub fn registered_values(context: &str, property: &str, version: &str) -> Vec<&'static str> {
+    ENUM_VALUES
+        .iter()
+        .filter(|entry| {
+            entry.property == property
+                && parse_version(entry.since) <= parse_version(version)
+                && entry.contexts.contains(&context)
+        })
+        .flat_map(|entry| entry.values.iter().copied())
+        .collect()
+}
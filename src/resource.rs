@@ -4,10 +4,9 @@
 //! Property definitions making use of Resource MAY define additional properties for their value types.
 
 use crate::{
-    Calendar, CalendarKind, Context, CryptoKey, Directory, DirectoryKind, Link, LinkKind, Media,
-    MediaKind,
+    Calendar, CalendarKind, Card, Context, CryptoKey, Directory, DirectoryKind, Link, LinkKind,
+    Media, MediaKind, SmallString,
 };
-#[cfg(feature = "typed")]
 use crate::{CalendarType, CryptoKeyType, DirectoryType, LinkType, MediaType};
 
 use serde::{Deserialize, Serialize};
@@ -21,7 +20,6 @@ use std::collections::HashMap;
 #[serde(rename_all = "camelCase")]
 pub struct Resource {
     /// The JSContact type of the object.
-    #[cfg(feature = "typed")]
     #[serde(rename = "@type")]
     resource_type: Option<ResourceType>,
     /// The kind of the resource.
@@ -29,7 +27,7 @@ pub struct Resource {
     /// The resource value.
     pub uri: String,
     /// The media type RFC2046 of the resource identified by the uri property value.
-    pub media_type: Option<String>,
+    pub media_type: Option<SmallString>,
     /// The contexts in which to use this resource.
     pub contexts: Option<HashMap<Context, bool>>,
     /// The preference of the resource in relation to other resources.
@@ -42,7 +40,6 @@ impl Resource {
     /// Create a new Resource
     pub fn new(uri: String) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             resource_type: Some(ResourceType::Resource),
             kind: None,
             uri,
@@ -55,7 +52,6 @@ impl Resource {
 }
 
 /// Resource @type
-#[cfg(feature = "typed")]
 #[derive(Serialize, Deserialize, Debug)]
 enum ResourceType {
     /// Resource @type
@@ -66,7 +62,6 @@ impl From<Resource> for Calendar {
     fn from(resource: Resource) -> Self {
         let kind: Option<CalendarKind> = resource.kind.as_deref().map(|s| s.to_string().into());
         Self {
-            #[cfg(feature = "typed")]
             calendar_type: Some(CalendarType::Calendar),
             kind,
             uri: resource.uri,
@@ -81,7 +76,6 @@ impl From<Resource> for Calendar {
 impl From<Resource> for CryptoKey {
     fn from(resource: Resource) -> Self {
         Self {
-            #[cfg(feature = "typed")]
             crypto_key_type: Some(CryptoKeyType::CryptoKey),
             kind: resource.kind,
             uri: resource.uri,
@@ -97,7 +91,6 @@ impl From<Resource> for Directory {
     fn from(resource: Resource) -> Self {
         let kind: Option<DirectoryKind> = resource.kind.as_deref().map(|s| s.to_string().into());
         Self {
-            #[cfg(feature = "typed")]
             directory_type: Some(DirectoryType::Directory),
             kind,
             uri: resource.uri,
@@ -117,10 +110,11 @@ impl From<Resource> for Media {
             None => MediaKind::default(),
         };
         Self {
-            #[cfg(feature = "typed")]
             media_hidden_type: Some(MediaType::Media),
             kind,
-            uri: resource.uri,
+            // moved without the `shared` feature
+            #[allow(clippy::useless_conversion)]
+            uri: resource.uri.into(),
             media_type: resource.media_type,
             contexts: resource.contexts,
             pref: resource.pref,
@@ -133,7 +127,6 @@ impl From<Resource> for Link {
     fn from(resource: Resource) -> Self {
         let kind: Option<LinkKind> = resource.kind.as_deref().map(|s| s.to_string().into());
         Self {
-            #[cfg(feature = "typed")]
             link_type: Some(LinkType::Link),
             kind,
             uri: resource.uri,
@@ -144,3 +137,156 @@ impl From<Resource> for Link {
         }
     }
 }
+
+/// Get the scheme of a URI (e.g. `https` for `HTTPS://example.com`), as written.
+///
+/// Returns `None` if the URI has no colon or if the part before it is not a scheme (RFC 3986),
+/// e.g. for a relative reference like `photos/jane.jpg`.
+pub(crate) fn uri_scheme(uri: &str) -> Option<&str> {
+    let (scheme, _) = uri.split_once(':')?;
+    let mut bytes = scheme.bytes();
+    let valid = bytes.next().is_some_and(|b| b.is_ascii_alphabetic())
+        && bytes.all(|b| b.is_ascii_alphanumeric() || b"+-.".contains(&b));
+    valid.then_some(scheme)
+}
+
+impl Resource {
+    /// Get the scheme of the URI as written, compare it with [`str::eq_ignore_ascii_case`]. `None` for a relative URI.
+    pub fn uri_scheme(&self) -> Option<&str> {
+        uri_scheme(&self.uri)
+    }
+}
+
+impl Calendar {
+    /// Get the scheme of the URI as written, compare it with [`str::eq_ignore_ascii_case`]. `None` for a relative URI.
+    pub fn uri_scheme(&self) -> Option<&str> {
+        uri_scheme(&self.uri)
+    }
+}
+
+impl CryptoKey {
+    /// Get the scheme of the URI as written, compare it with [`str::eq_ignore_ascii_case`]. `None` for a relative URI.
+    pub fn uri_scheme(&self) -> Option<&str> {
+        uri_scheme(&self.uri)
+    }
+}
+
+impl Directory {
+    /// Get the scheme of the URI as written, compare it with [`str::eq_ignore_ascii_case`]. `None` for a relative URI.
+    pub fn uri_scheme(&self) -> Option<&str> {
+        uri_scheme(&self.uri)
+    }
+}
+
+impl Link {
+    /// Get the scheme of the URI as written, compare it with [`str::eq_ignore_ascii_case`]. `None` for a relative URI.
+    pub fn uri_scheme(&self) -> Option<&str> {
+        uri_scheme(&self.uri)
+    }
+}
+
+impl Media {
+    /// Get the scheme of the URI as written, compare it with [`str::eq_ignore_ascii_case`]. `None` for a relative URI.
+    pub fn uri_scheme(&self) -> Option<&str> {
+        uri_scheme(&self.uri)
+    }
+}
+
+/// A reference to a resource of a Card, whatever its type, see [`Card::resources`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResourceRef<'a> {
+    /// A calendar of [`Card::calendars`].
+    Calendar(&'a Calendar),
+    /// A crypto key of [`Card::crypto_keys`].
+    CryptoKey(&'a CryptoKey),
+    /// A directory of [`Card::directories`].
+    Directory(&'a Directory),
+    /// A link of [`Card::links`].
+    Link(&'a Link),
+    /// A media of [`Card::media`].
+    Media(&'a Media),
+}
+
+impl<'a> ResourceRef<'a> {
+    /// Get the kind of the resource as a string, as serialized.
+    pub fn kind_str(&self) -> Option<&'a str> {
+        match self {
+            ResourceRef::Calendar(calendar) => calendar.kind_str(),
+            ResourceRef::CryptoKey(key) => key.kind_str(),
+            ResourceRef::Directory(directory) => directory.kind_str(),
+            ResourceRef::Link(link) => link.kind_str(),
+            ResourceRef::Media(media) => media.kind_str(),
+        }
+    }
+
+    /// Get the URI of the resource.
+    pub fn uri(&self) -> &'a str {
+        match self {
+            ResourceRef::Calendar(calendar) => &calendar.uri,
+            ResourceRef::CryptoKey(key) => &key.uri,
+            ResourceRef::Directory(directory) => &directory.uri,
+            ResourceRef::Link(link) => &link.uri,
+            ResourceRef::Media(media) => &media.uri,
+        }
+    }
+
+    /// Get the scheme of the URI of the resource, see [`Link::uri_scheme`].
+    pub fn uri_scheme(&self) -> Option<&'a str> {
+        uri_scheme(self.uri())
+    }
+
+    /// Get the label of the resource.
+    pub fn label(&self) -> Option<&'a str> {
+        match self {
+            ResourceRef::Calendar(calendar) => calendar.label.as_deref(),
+            ResourceRef::CryptoKey(key) => key.label.as_deref(),
+            ResourceRef::Directory(directory) => directory.label.as_deref(),
+            ResourceRef::Link(link) => link.label.as_deref(),
+            ResourceRef::Media(media) => media.label.as_deref(),
+        }
+    }
+}
+
+impl Card {
+    /// Get all the resources of the Card with their property and key,
+    /// sorted by property (`calendars`, `cryptoKeys`, `directories`, `links`, `media`) then by key.
+    pub fn resources(&self) -> Vec<(&'static str, &SmallString, ResourceRef<'_>)> {
+        /// Add the entries of a map of resources
+        fn add<'a, T>(
+            resources: &mut Vec<(&'static str, &'a SmallString, ResourceRef<'a>)>,
+            property: &'static str,
+            map: &'a Option<HashMap<SmallString, T>>,
+            to_ref: fn(&'a T) -> ResourceRef<'a>,
+        ) {
+            let mut entries: Vec<(&SmallString, &T)> = map.iter().flatten().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            resources.extend(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (property, key, to_ref(value))),
+            );
+        }
+        let mut resources = Vec::new();
+        add(
+            &mut resources,
+            "calendars",
+            &self.calendars,
+            ResourceRef::Calendar,
+        );
+        add(
+            &mut resources,
+            "cryptoKeys",
+            &self.crypto_keys,
+            ResourceRef::CryptoKey,
+        );
+        add(
+            &mut resources,
+            "directories",
+            &self.directories,
+            ResourceRef::Directory,
+        );
+        add(&mut resources, "links", &self.links, ResourceRef::Link);
+        add(&mut resources, "media", &self.media, ResourceRef::Media);
+        resources
+    }
+}
@@ -0,0 +1,75 @@
+//! Selection of the address to send calendar scheduling invitations (iTIP) to.
+//!
+//! The scheduling addresses are usually `mailto:` URIs (RFC 6068),
+//! when a Card has no scheduling address its preferred email address is used instead.
+
+use std::borrow::Cow;
+
+use crate::directory::percent_decode;
+use crate::preferred::preferred;
+use crate::{Card, Context, SchedulingAddress};
+
+/// Percent-encode the characters of an addr-spec not allowed in a `mailto:` URI
+fn mailto_encode(address: &str) -> String {
+    let mut uri = String::with_capacity(address.len() + 7);
+    uri.push_str("mailto:");
+    for byte in address.bytes() {
+        // the `qchar` of RFC 6068 without `,`, which separates the addresses of the URI
+        // while a quoted local part may contain it
+        if byte.is_ascii_alphanumeric() || b"-._~!$'()*+;:@".contains(&byte) {
+            uri.push(char::from(byte));
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    uri
+}
+
+impl SchedulingAddress {
+    /// Get the email address of a `mailto:` scheduling address, percent-decoded.
+    ///
+    /// Only the first address of the URI is returned, the header fields (e.g. `?subject=`) are ignored.
+    /// Returns `None` if the URI is not a valid `mailto:` URI.
+    pub fn email(&self) -> Option<Cow<'_, str>> {
+        let (scheme, rest) = self.uri.split_once(':')?;
+        if !scheme.eq_ignore_ascii_case("mailto") {
+            return None;
+        }
+        let to = rest.split('?').next().unwrap_or_default();
+        let address = to.split(',').next().unwrap_or_default();
+        if address.is_empty() {
+            return None;
+        }
+        if !address.contains('%') {
+            return Some(Cow::Borrowed(address));
+        }
+        percent_decode(address).map(Cow::Owned)
+    }
+}
+
+impl Card {
+    /// Get the URI to send calendar scheduling invitations to.
+    ///
+    /// The scheduling addresses in the given context (`work` if not set) are preferred, then the lowest `pref`.
+    /// Without scheduling addresses, the preferred email address is returned as a `mailto:` URI.
+    pub fn scheduling_uri(&self, context: Option<Context>) -> Option<Cow<'_, str>> {
+        let context = context.unwrap_or(Context::Work);
+        if let Some(addresses) = self.scheduling_addresses.as_ref() {
+            if let Some(address) = preferred(
+                Some(&context),
+                addresses,
+                |address| address.contexts.as_ref(),
+                |address| address.pref,
+            ) {
+                return Some(Cow::Borrowed(address.uri.as_str()));
+            }
+        }
+        let email = preferred(
+            Some(&context),
+            self.emails.as_ref()?,
+            |email| email.contexts.as_ref(),
+            |email| email.pref.map(u64::from),
+        )?;
+        Some(Cow::Owned(mailto_encode(&email.address)))
+    }
+}
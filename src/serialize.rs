@@ -0,0 +1,274 @@
+//! Serialization of a [`Card`] with a stable order of the keys.
+//!
+//! The properties of a Card are serialized in the order of the struct, but the maps (e.g. `emails`, `contexts`)
+//! are `HashMap`s, whose order changes between runs. The Card is serialized to a [`Value`] then re-emitted in order.
+//!
+//! The `@type` members are emitted whatever the features of the crate, so two crates depending on it serialize
+//! the same JSON. A caller preferring the style of the examples of RFC 9553 omits them with [`TypeMembers::Omit`].
+
+use serde_json::{Map, Value};
+
+use crate::path::PROPERTIES;
+use crate::Card;
+
+/// The properties whose value is a map (an `Id` or an enum as key), their keys are sorted.
+pub(crate) const MAP_PROPERTIES: &[&str] = &[
+    "members",
+    "relatedTo",
+    "nicknames",
+    "organizations",
+    "titles",
+    "emails",
+    "onlineServices",
+    "phones",
+    "preferredLanguages",
+    "calendars",
+    "schedulingAddresses",
+    "anniversaries",
+    "addresses",
+    "cryptoKeys",
+    "directories",
+    "links",
+    "media",
+    "keywords",
+    "notes",
+    "personalInfo",
+    "localizations",
+    "pronouns",
+    "contexts",
+    "features",
+    "relation",
+    "sortAs",
+];
+
+/// The properties serialized first, in order.
+const LEADING_PROPERTIES: [&str; 3] = ["@type", "version", "uid"];
+
+/// The order of the keys of the objects, see [`SerializeOptions`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyOrder {
+    /// `@type`, `version` and `uid` first, then the other properties in the order of the structs.
+    /// The keys of the maps and the unknown properties are sorted.
+    #[default]
+    Canonical,
+    /// The keys of all the objects are sorted.
+    Alphabetical,
+}
+
+/// The `@type` members of the objects of the properties, see [`SerializeOptions`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TypeMembers {
+    /// The `@type` members of the objects: set by their constructors, or read with them.
+    #[default]
+    Keep,
+    /// No `@type` member in the properties, only the `@type` of the Card, as in the examples of RFC 9553.
+    /// The unknown properties and the localizations are kept as they are.
+    Omit,
+}
+
+/// The options of [`Card::to_string_with`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SerializeOptions {
+    /// The order of the keys.
+    pub key_order: KeyOrder,
+    /// Indent the JSON.
+    pub pretty: bool,
+    /// The `@type` members of the objects of the properties.
+    pub type_members: TypeMembers,
+}
+
+/// Sort the keys of an object
+fn sorted(object: Map<String, Value>) -> Map<String, Value> {
+    let mut entries: Vec<(String, Value)> = object.into_iter().collect();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    entries.into_iter().collect()
+}
+
+/// Sort the keys of all the objects of a value, without recursion as the value may be nested at will
+pub(crate) fn alphabetical(value: Value) -> Value {
+    /// A step of the sort, the children of an array or an object being sorted before it
+    enum Step {
+        Sort(Value),
+        Array(usize),
+        Object(Vec<String>),
+    }
+
+    let mut steps = vec![Step::Sort(value)];
+    let mut sorted_values: Vec<Value> = Vec::new();
+    while let Some(step) = steps.pop() {
+        match step {
+            Step::Sort(Value::Array(values)) => {
+                steps.push(Step::Array(values.len()));
+                steps.extend(values.into_iter().rev().map(Step::Sort));
+            }
+            Step::Sort(Value::Object(object)) => {
+                let (keys, values): (Vec<String>, Vec<Value>) = sorted(object).into_iter().unzip();
+                steps.push(Step::Object(keys));
+                steps.extend(values.into_iter().rev().map(Step::Sort));
+            }
+            Step::Sort(value) => sorted_values.push(value),
+            Step::Array(len) => {
+                let values = sorted_values.split_off(sorted_values.len() - len);
+                sorted_values.push(Value::Array(values));
+            }
+            Step::Object(keys) => {
+                let values = sorted_values.split_off(sorted_values.len() - keys.len());
+                sorted_values.push(Value::Object(keys.into_iter().zip(values).collect()));
+            }
+        }
+    }
+    sorted_values.pop().unwrap_or_default()
+}
+
+/// Get the compact JSON of a value with the keys of all its objects sorted, as `alphabetical(value).to_string()`
+/// but without recursion
+pub(crate) fn alphabetical_string(value: &Value) -> String {
+    /// A part of the JSON, the parts of a child being written before the rest of its parent
+    enum Part<'a> {
+        Value(&'a Value),
+        Key(&'a String),
+        Text(&'static str),
+    }
+
+    let mut json = String::new();
+    let mut parts = vec![Part::Value(value)];
+    while let Some(part) = parts.pop() {
+        match part {
+            Part::Value(Value::Array(values)) => {
+                json.push('[');
+                parts.push(Part::Text("]"));
+                for (idx, value) in values.iter().enumerate().rev() {
+                    parts.push(Part::Value(value));
+                    if idx > 0 {
+                        parts.push(Part::Text(","));
+                    }
+                }
+            }
+            Part::Value(Value::Object(object)) => {
+                let mut members: Vec<(&String, &Value)> = object.iter().collect();
+                members.sort_by_key(|(key, _)| *key);
+                json.push('{');
+                parts.push(Part::Text("}"));
+                for (idx, (key, value)) in members.into_iter().enumerate().rev() {
+                    parts.push(Part::Value(value));
+                    parts.push(Part::Key(key));
+                    if idx > 0 {
+                        parts.push(Part::Text(","));
+                    }
+                }
+            }
+            Part::Value(value) => json.push_str(&value.to_string()),
+            Part::Key(key) => {
+                json.push_str(&Value::from(key.as_str()).to_string());
+                json.push(':');
+            }
+            Part::Text(text) => json.push_str(text),
+        }
+    }
+    json
+}
+
+/// Sort the keys of the maps of a value, `is_map` telling if the value is itself a map
+fn canonical(value: Value, is_map: bool) -> Value {
+    match value {
+        Value::Object(object) => {
+            let object = if is_map { sorted(object) } else { object };
+            Value::Object(
+                object
+                    .into_iter()
+                    .map(|(key, value)| {
+                        // the values of a map are objects, their maps are found by their name
+                        let is_map = !is_map && MAP_PROPERTIES.contains(&key.as_str());
+                        (key, canonical(value, is_map))
+                    })
+                    .collect(),
+            )
+        }
+        Value::Array(values) => Value::Array(
+            values
+                .into_iter()
+                .map(|value| canonical(value, false))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+/// Remove the `@type` members of the objects of a value
+fn omit_types(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            object.remove("@type");
+            object.values_mut().for_each(omit_types);
+        }
+        Value::Array(values) => values.iter_mut().for_each(omit_types),
+        _ => {}
+    }
+}
+
+/// Order the top-level properties of a Card: the leading properties, the known ones, then the sorted unknown ones
+fn canonical_card(mut object: Map<String, Value>) -> Map<String, Value> {
+    // the patches of a language are a map too
+    if let Some(Value::Object(localizations)) = object.get_mut("localizations") {
+        for patches in localizations.values_mut() {
+            if let Value::Object(map) = patches {
+                *map = sorted(std::mem::take(map));
+            }
+        }
+    }
+    let mut ordered = Map::new();
+    for property in LEADING_PROPERTIES {
+        if let Some(value) = object.remove(property) {
+            ordered.insert(property.to_string(), value);
+        }
+    }
+    let (known, unknown): (Vec<_>, Vec<_>) = object
+        .into_iter()
+        .partition(|(key, _)| key == "localizations" || PROPERTIES.contains(&key.as_str()));
+    ordered.extend(known);
+    ordered.extend(sorted(unknown.into_iter().collect()));
+    ordered
+        .into_iter()
+        .map(|(key, value)| {
+            let is_map = MAP_PROPERTIES.contains(&key.as_str());
+            (key, canonical(value, is_map))
+        })
+        .collect()
+}
+
+impl Card {
+    /// Serialize the Card to a JSON string, with a stable order of the keys.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, KeyOrder, SerializeOptions};
+    ///
+    /// let card = Card::new_with_latest_version("1234");
+    /// let opts = SerializeOptions {
+    ///     key_order: KeyOrder::Alphabetical,
+    ///     ..Default::default()
+    /// };
+    /// let json = card.to_string_with(&opts).unwrap();
+    /// assert!(json.ends_with(r#""uid":"1234","version":"1.0"}"#));
+    /// ```
+    /// # Errors
+    /// Will return an error if the Card cannot be serialized.
+    pub fn to_string_with(&self, opts: &SerializeOptions) -> Result<String, serde_json::Error> {
+        let mut value = serde_json::to_value(self)?;
+        if let (Value::Object(object), TypeMembers::Omit) = (&mut value, opts.type_members) {
+            object
+                .iter_mut()
+                .filter(|(key, _)| PROPERTIES.contains(&key.as_str()))
+                .for_each(|(_, value)| omit_types(value));
+        }
+        let value = match (value, opts.key_order) {
+            (value, KeyOrder::Alphabetical) => alphabetical(value),
+            (Value::Object(object), KeyOrder::Canonical) => Value::Object(canonical_card(object)),
+            (value, KeyOrder::Canonical) => value,
+        };
+        if opts.pretty {
+            serde_json::to_string_pretty(&value)
+        } else {
+            serde_json::to_string(&value)
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Skeletons of a [`Card`], e.g. for a sync protocol reserving an entry before uploading the full Card.
+//!
+//! A skeleton has the required properties of the Card (`@type`, `version` and `uid`) and the identity
+//! properties selected by [`SkeletonFields`], nothing else: no metadata, no localization and no unknown
+//! property.
+
+use crate::Card;
+
+/// The identity properties copied by [`Card::to_skeleton_with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkeletonFields {
+    /// Copy the `name`.
+    pub name: bool,
+    /// Copy the `kind`.
+    pub kind: bool,
+    /// Copy the `members` of a group.
+    pub members: bool,
+}
+
+impl Default for SkeletonFields {
+    fn default() -> Self {
+        Self {
+            name: true,
+            kind: true,
+            members: false,
+        }
+    }
+}
+
+impl SkeletonFields {
+    /// All the identity properties.
+    pub fn all() -> Self {
+        Self {
+            name: true,
+            kind: true,
+            members: true,
+        }
+    }
+
+    /// No identity property, only the required properties are copied.
+    pub fn none() -> Self {
+        Self {
+            name: false,
+            kind: false,
+            members: false,
+        }
+    }
+
+    /// The identity properties set in a Card
+    fn of(card: &Card) -> Self {
+        Self {
+            name: card.name.is_some(),
+            kind: card.kind.is_some(),
+            members: card.members.is_some(),
+        }
+    }
+}
+
+impl Card {
+    /// Get the skeleton of the Card with the `name` and the `kind`, see [`Card::to_skeleton_with`].
+    pub fn to_skeleton(&self) -> Card {
+        self.to_skeleton_with(&SkeletonFields::default())
+    }
+
+    /// Get the skeleton of the Card: a new Card with the same `@type`, `version` and `uid`, and the
+    /// selected identity properties.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, CardKind, SkeletonFields};
+    ///
+    /// let mut card = Card::new_with_latest_version("1234");
+    /// card.kind = Some(CardKind::Individual);
+    /// card.add_keyword("friend");
+    /// let skeleton = card.to_skeleton_with(&SkeletonFields::all());
+    /// assert_eq!(skeleton.kind, Some(CardKind::Individual));
+    /// assert!(skeleton.keywords.is_none());
+    /// assert!(skeleton.is_skeleton_of(&card));
+    /// ```
+    pub fn to_skeleton_with(&self, fields: &SkeletonFields) -> Card {
+        let mut skeleton = Card::new(self.version.clone(), &self.uid);
+        if fields.name {
+            skeleton.name = self.name.clone();
+        }
+        if fields.kind {
+            skeleton.kind = self.kind.clone();
+        }
+        if fields.members {
+            skeleton.members = self.members.clone();
+        }
+        skeleton
+    }
+
+    /// Check if the Card is a skeleton of a full Card: it has the same required properties, its identity
+    /// properties are those of the full Card, and it has no other property.
+    pub fn is_skeleton_of(&self, full: &Card) -> bool {
+        *self == full.to_skeleton_with(&SkeletonFields::of(self))
+    }
+}
@@ -0,0 +1,171 @@
+//! Sort keys of a [`Card`], to render lists of contacts and org charts in a deterministic order.
+//!
+//! The `sortAs` properties (RFC 9553, Sections 2.2.1.2 and 2.2.3) take precedence over the values they replace.
+//!
+//! [`CardSet::sort`] orders the Cards of an address book, so its exports are the same across runs.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Card, CardKind, CardSet, KeyOrder, Name, NameComponentKind, OrgUnit, Organization,
+    SerializeOptions,
+};
+
+/// The name components used to sort a name, in order
+const NAME_SORT_ORDER: [(&str, NameComponentKind); 5] = [
+    ("surname", NameComponentKind::Surname),
+    ("surname2", NameComponentKind::Surname2),
+    ("given", NameComponentKind::Given),
+    ("given2", NameComponentKind::Given2),
+    ("generation", NameComponentKind::Generation),
+];
+
+impl Name {
+    /// Get the sort key of the name, the values are compared in order.
+    ///
+    /// The key is made of the surnames, then the given names and the generation, each replaced by its `sortAs` value if set.
+    /// The key always has one value per component, empty if missing, so the same components are compared together.
+    /// A name without such components is sorted by its full name, in place of the surname.
+    pub fn sort_key(&self) -> Vec<String> {
+        let mut key: Vec<String> = NAME_SORT_ORDER
+            .iter()
+            .map(|(name, kind)| {
+                let sort_as = self.sort_as.as_ref().and_then(|sort_as| sort_as.get(*name));
+                match sort_as {
+                    Some(value) => value.clone(),
+                    None => self
+                        .components
+                        .iter()
+                        .flatten()
+                        .filter(|component| component.kind == *kind)
+                        .map(|component| component.value.as_str())
+                        .collect::<Vec<&str>>()
+                        .join(" "),
+                }
+            })
+            .collect();
+        if key.iter().all(String::is_empty) {
+            key[0] = self.to_full_string();
+        }
+        key
+    }
+}
+
+impl OrgUnit {
+    /// Get the value to sort the unit by, its `sortAs` value or its name.
+    pub fn sort_value(&self) -> &str {
+        self.sort_as.as_deref().unwrap_or(&self.name)
+    }
+}
+
+impl Organization {
+    /// Get the value to sort the organization by, its `sortAs` value or its name.
+    pub fn sort_value(&self) -> Option<&str> {
+        self.sort_as.as_deref().or(self.name.as_deref())
+    }
+
+    /// Get the units of the organization sorted by their `sortAs` value or their name.
+    ///
+    /// The sort is stable, units with the same value keep their order.
+    pub fn sorted_units(&self) -> Vec<&OrgUnit> {
+        let mut units: Vec<&OrgUnit> = self.units.iter().flatten().collect();
+        units.sort_by_key(|unit| unit.sort_value());
+        units
+    }
+}
+
+impl Card {
+    /// Get the primary organization of the Card, the organization with the lowest key.
+    pub fn primary_organization(&self) -> Option<&Organization> {
+        self.organizations
+            .iter()
+            .flatten()
+            .min_by_key(|(key, _)| key.as_str())
+            .map(|(_, organization)| organization)
+    }
+
+    /// Get the sort key of the Card, the values are compared in order.
+    ///
+    /// The key of a Card of the `org` kind starts with the sort value of its primary organization,
+    /// see [`Card::primary_organization`], empty without organization, followed by the key of its name,
+    /// see [`Name::sort_key`], empty values without name.
+    pub fn sort_key(&self) -> Vec<String> {
+        let mut key = Vec::new();
+        if self.kind == Some(CardKind::Org) {
+            let value = self
+                .primary_organization()
+                .and_then(Organization::sort_value)
+                .unwrap_or_default();
+            key.push(value.to_string());
+        }
+        match &self.name {
+            Some(name) => key.extend(name.sort_key()),
+            None => key.extend(NAME_SORT_ORDER.iter().map(|_| String::new())),
+        }
+        key
+    }
+}
+
+/// The order of the Cards of [`CardSet::sort`], the ties being broken by uid.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortOrder {
+    /// By uid.
+    #[default]
+    ByUid,
+    /// By sort key, see [`Card::sort_key`], the Cards with an empty key (no name, no organization) last.
+    ByDisplayName,
+    /// By `updated`, the oldest first, the Cards without `updated` last.
+    ///
+    /// The timestamps are compared as texts, which is the order of time for the UTC timestamps of the same precision.
+    ByUpdated,
+}
+
+impl CardSet {
+    /// Sort the Cards, the Cards with the same key being sorted by uid.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, CardSet, SortOrder};
+    ///
+    /// let mut cards = CardSet(vec![
+    ///     Card::new_with_latest_version("2"),
+    ///     Card::new_with_latest_version("1"),
+    /// ]);
+    /// cards.sort(SortOrder::ByDisplayName);
+    /// assert_eq!(cards.0[0].uid, "1");
+    /// ```
+    pub fn sort(&mut self, order: SortOrder) {
+        match order {
+            SortOrder::ByUid => self.0.sort_by(|a, b| a.uid.cmp(&b.uid)),
+            SortOrder::ByDisplayName => self.0.sort_by_cached_key(|card| {
+                let key = card.sort_key();
+                (key.iter().all(String::is_empty), key, card.uid.clone())
+            }),
+            SortOrder::ByUpdated => self.0.sort_by(|a, b| {
+                (a.updated.is_none(), &a.updated, &a.uid).cmp(&(
+                    b.updated.is_none(),
+                    &b.updated,
+                    &b.uid,
+                ))
+            }),
+        }
+    }
+
+    /// Serialize the Cards to a JSON array sorted by uid, the keys of each Card in the canonical order
+    /// (see [`KeyOrder::Canonical`]), the same Cards always giving the same text.
+    /// # Errors
+    /// Will return an error if a Card cannot be serialized.
+    pub fn to_string_sorted(&self) -> Result<String, serde_json::Error> {
+        let mut cards: Vec<&Card> = self.0.iter().collect();
+        cards.sort_by(|a, b| a.uid.cmp(&b.uid));
+        let opts = SerializeOptions {
+            key_order: KeyOrder::Canonical,
+            ..Default::default()
+        };
+        let cards = cards
+            .into_iter()
+            .map(|card| card.to_string_with(&opts))
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(format!("[{}]", cards.join(",")))
+    }
+}
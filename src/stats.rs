@@ -0,0 +1,259 @@
+//! Statistics of the Cards of an address book, e.g. for a data-quality dashboard.
+//!
+//! [`Card::stats`] gives the statistics of a Card, [`CardSetStats`] aggregates them over several Cards.
+//! Both are serializable to be sent as is to the dashboard.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{AnniversaryKind, Card, MediaKind, ValidationSummary};
+
+/// The number of properties in [`CardStats::largest_properties`].
+pub const LARGEST_PROPERTIES: usize = 5;
+
+/// The serialized size of a top-level property of a Card.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PropertySize {
+    /// The name of the property (e.g. `media`).
+    pub property: String,
+    /// The size of the JSON of its value, in bytes.
+    pub bytes: usize,
+}
+
+/// The statistics of a Card, see [`Card::stats`].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct CardStats {
+    /// The number of entries of each map of the Card (e.g. `emails`), the absent maps being left out.
+    pub entries: BTreeMap<String, usize>,
+    /// The languages of the localizations, sorted.
+    pub localization_languages: Vec<String>,
+    /// The largest top-level properties, the largest first, at most [`LARGEST_PROPERTIES`].
+    ///
+    /// Empty when the Card cannot be serialized (see [`crate::DEFAULT_MAX_DEPTH`]).
+    pub largest_properties: Vec<PropertySize>,
+    /// The Card has a name, with a full name or components.
+    pub has_name: bool,
+    /// The Card has a media of the `photo` kind.
+    pub has_photo: bool,
+    /// The Card has an anniversary of the `birth` kind.
+    pub has_birthday: bool,
+    /// The number of validation violations by severity.
+    pub validation: ValidationSummary,
+}
+
+/// Get the number of entries of the maps of a Card, by property name
+macro_rules! map_entries {
+    ($card:expr, $($field:ident => $name:literal),+ $(,)?) => {{
+        let mut entries = BTreeMap::new();
+        $(
+            if let Some(map) = &$card.$field {
+                entries.insert($name.to_string(), map.len());
+            }
+        )+
+        entries
+    }};
+}
+
+impl Card {
+    /// Get the statistics of the Card.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, EmailAddress};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut card = Card::new_with_latest_version("1234");
+    /// card.emails = Some(HashMap::from([("e1".into(), EmailAddress::new("jane@example.com"))]));
+    /// let stats = card.stats();
+    /// assert_eq!(stats.entries["emails"], 1);
+    /// assert!(!stats.has_name);
+    /// ```
+    pub fn stats(&self) -> CardStats {
+        let entries = map_entries!(self,
+            members => "members",
+            related_to => "relatedTo",
+            nicknames => "nicknames",
+            organizations => "organizations",
+            titles => "titles",
+            emails => "emails",
+            online_services => "onlineServices",
+            phones => "phones",
+            preferred_languages => "preferredLanguages",
+            calendars => "calendars",
+            scheduling_addresses => "schedulingAddresses",
+            anniversaries => "anniversaries",
+            addresses => "addresses",
+            crypto_keys => "cryptoKeys",
+            directories => "directories",
+            links => "links",
+            media => "media",
+            keywords => "keywords",
+            notes => "notes",
+            personal_info => "personalInfo",
+        );
+        let mut largest_properties: Vec<PropertySize> = match serde_json::to_value(self) {
+            Ok(Value::Object(properties)) => properties
+                .iter()
+                .map(|(property, value)| PropertySize {
+                    property: property.clone(),
+                    bytes: serde_json::to_vec(value).map_or(0, |json| json.len()),
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        largest_properties.sort_by(|a, b| b.bytes.cmp(&a.bytes).then(a.property.cmp(&b.property)));
+        largest_properties.truncate(LARGEST_PROPERTIES);
+        let mut localization_languages: Vec<String> =
+            self.get_available_languages().map(String::from).collect();
+        localization_languages.sort();
+        CardStats {
+            entries,
+            localization_languages,
+            largest_properties,
+            has_name: self.name.as_ref().is_some_and(|name| {
+                name.full.as_deref().is_some_and(|full| !full.is_empty())
+                    || name
+                        .components
+                        .as_ref()
+                        .is_some_and(|components| !components.is_empty())
+            }),
+            has_photo: self
+                .media
+                .iter()
+                .flatten()
+                .any(|(_, media)| media.kind == MediaKind::Photo),
+            has_birthday: self
+                .anniversaries
+                .iter()
+                .flatten()
+                .any(|(_, anniversary)| anniversary.kind == AnniversaryKind::Birth),
+            validation: self.validate().summary(),
+        }
+    }
+}
+
+/// The distribution of a number over the Cards, e.g. the number of emails.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Distribution {
+    /// The smallest number.
+    pub min: usize,
+    /// The median, the mean of the two middle numbers for an even number of Cards.
+    pub median: f64,
+    /// The largest number.
+    pub max: usize,
+}
+
+impl Distribution {
+    /// Get the distribution of numbers, `None` without number
+    fn of(mut values: Vec<usize>) -> Option<Self> {
+        values.sort_unstable();
+        let (min, max) = (*values.first()?, *values.last()?);
+        let middle = values.len() / 2;
+        let median = if values.len() % 2 == 1 {
+            values[middle] as f64
+        } else {
+            (values[middle - 1] + values[middle]) as f64 / 2.0
+        };
+        Some(Self { min, median, max })
+    }
+}
+
+/// The statistics of several Cards, built from a slice of Cards.
+///
+/// ```rust
+/// use jscontact::{Card, CardSetStats};
+///
+/// let mut card = Card::new_with_latest_version("1234");
+/// card.add_keyword("friend");
+/// let stats = CardSetStats::from(&[card, Card::new_with_latest_version("5678")][..]);
+/// assert_eq!(stats.cards, 2);
+/// assert_eq!(stats.top_keywords(1), [("friend", 1)]);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct CardSetStats {
+    /// The number of Cards.
+    pub cards: usize,
+    /// The distribution of the number of entries of each map, over all the Cards (a Card without the map
+    /// counting as 0), for the maps set in at least one Card.
+    pub entries: BTreeMap<String, Distribution>,
+    /// The number of Cards with each keyword.
+    pub keywords: BTreeMap<String, usize>,
+    /// The percentage of Cards in each language, as their `language` or as a localization.
+    pub language_coverage: BTreeMap<String, f64>,
+    /// The number of Cards with a name.
+    pub with_name: usize,
+    /// The number of Cards with a photo.
+    pub with_photo: usize,
+    /// The number of Cards with a birthday.
+    pub with_birthday: usize,
+    /// The number of Cards with a violation of the error severity.
+    pub invalid: usize,
+    /// The number of validation violations of all the Cards by severity.
+    pub validation: ValidationSummary,
+}
+
+impl CardSetStats {
+    /// Get the most common keywords with their number of Cards, the most common first then by name.
+    pub fn top_keywords(&self, count: usize) -> Vec<(&str, usize)> {
+        let mut keywords: Vec<(&str, usize)> = self
+            .keywords
+            .iter()
+            .map(|(keyword, cards)| (keyword.as_str(), *cards))
+            .collect();
+        keywords.sort_by(|(a_keyword, a_cards), (b_keyword, b_cards)| {
+            b_cards.cmp(a_cards).then(a_keyword.cmp(b_keyword))
+        });
+        keywords.truncate(count);
+        keywords
+    }
+}
+
+impl From<&[Card]> for CardSetStats {
+    fn from(cards: &[Card]) -> Self {
+        let mut set_stats = CardSetStats {
+            cards: cards.len(),
+            ..Default::default()
+        };
+        let mut entries: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        let mut languages: BTreeMap<String, usize> = BTreeMap::new();
+        for (idx, card) in cards.iter().enumerate() {
+            let stats = card.stats();
+            for (property, count) in stats.entries {
+                // the previous Cards do not have the map
+                entries
+                    .entry(property)
+                    .or_insert_with(|| vec![0; idx])
+                    .push(count);
+            }
+            for counts in entries.values_mut() {
+                counts.resize(idx + 1, 0);
+            }
+            for (keyword, _) in card.keywords.iter().flatten().filter(|(_, set)| **set) {
+                *set_stats.keywords.entry(keyword.to_string()).or_default() += 1;
+            }
+            let mut card_languages = stats.localization_languages;
+            card_languages.extend(card.language.clone());
+            card_languages.sort();
+            card_languages.dedup();
+            for language in card_languages {
+                *languages.entry(language).or_default() += 1;
+            }
+            set_stats.with_name += usize::from(stats.has_name);
+            set_stats.with_photo += usize::from(stats.has_photo);
+            set_stats.with_birthday += usize::from(stats.has_birthday);
+            set_stats.invalid += usize::from(stats.validation.errors > 0);
+            set_stats.validation.errors += stats.validation.errors;
+            set_stats.validation.warnings += stats.validation.warnings;
+        }
+        set_stats.entries = entries
+            .into_iter()
+            .filter_map(|(property, counts)| Some((property, Distribution::of(counts)?)))
+            .collect();
+        set_stats.language_coverage = languages
+            .into_iter()
+            .map(|(language, count)| (language, count as f64 * 100.0 / cards.len() as f64))
+            .collect();
+        set_stats
+    }
+}
@@ -0,0 +1,220 @@
+//! Sanitization of the texts of a [`Card`] from legacy systems, e.g. a NUL in a name or CRLF line endings in a note.
+//!
+//! The control characters (C0, DEL and C1, see [`char::is_control`]) break the XML and vCard emitters.
+//! [`Card::sanitize_text`] strips or replaces them, except the ones allowed in the multi-line texts, and normalizes the
+//! line endings. The unpaired surrogates cannot be in a Rust string: a lossy conversion replaces them by
+//! U+FFFD, which is kept as is. The validation reports the control characters left in the texts with
+//! [`crate::ValidationCode::ControlCharacter`].
+//!
+//! The map keys, the localizations and the unknown properties are not sanitized.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::path::{get_value, set_values, PROPERTIES};
+use crate::validate::{ValidationCode, ValidationReport};
+use crate::{Card, CardPath, PathSegment};
+
+/// The sanitization of the texts of [`Card::sanitize_text`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextPolicy {
+    /// The character replacing a control character, `None` to strip it.
+    pub replacement: Option<char>,
+    /// The control characters kept in the multi-line texts (the notes and the full addresses), `\n` and `\t`
+    /// by default.
+    pub multiline_allowed: Vec<char>,
+    /// Replace the CRLF and CR line endings by LF, before the control characters are stripped.
+    pub line_endings: bool,
+    /// Normalize the texts to NFC, only with the `unicode` feature.
+    pub nfc: bool,
+}
+
+impl Default for TextPolicy {
+    fn default() -> Self {
+        Self {
+            replacement: None,
+            multiline_allowed: vec!['\n', '\t'],
+            line_endings: true,
+            nfc: true,
+        }
+    }
+}
+
+/// A text changed by [`Card::sanitize_text`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TextChange {
+    /// The path of the text (e.g. `notes/n1/note`).
+    pub path: String,
+    /// The text before the change.
+    pub before: String,
+    /// The text after the change.
+    pub after: String,
+}
+
+impl fmt::Display for TextChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {:?} -> {:?}", self.path, self.before, self.after)
+    }
+}
+
+/// Check if a path is a multi-line text: the text of a note or a full address
+fn is_multiline(path: &CardPath) -> bool {
+    let field = match path.property.as_str() {
+        "notes" => "note",
+        "addresses" => "full",
+        _ => return false,
+    };
+    path.segments.len() == 2 && path.segments[1] == PathSegment::Key(field.to_string())
+}
+
+/// Call a function on the texts of a value, the `@type` excluded
+fn for_each_text(path: &CardPath, value: &Value, f: &mut impl FnMut(&CardPath, &str)) {
+    match value {
+        Value::String(text) => f(path, text),
+        Value::Object(map) => {
+            for (key, field) in map.iter().filter(|(key, _)| *key != "@type") {
+                let mut child = path.clone();
+                child.segments.push(PathSegment::Key(key.clone()));
+                for_each_text(&child, field, f);
+            }
+        }
+        Value::Array(values) => {
+            for (idx, field) in values.iter().enumerate() {
+                let mut child = path.clone();
+                child.segments.push(PathSegment::Index(idx));
+                for_each_text(&child, field, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Call a function on the texts of the properties of a Card
+pub(crate) fn for_each_card_text(card: &Card, mut f: impl FnMut(&CardPath, &str)) {
+    for property in PROPERTIES {
+        let path = CardPath {
+            property: property.to_string(),
+            segments: Vec::new(),
+        };
+        if let Some(value) = get_value(card, &path) {
+            for_each_text(&path, &value, &mut f);
+        }
+    }
+}
+
+/// Normalize a text to NFC
+#[cfg(feature = "unicode")]
+fn nfc(text: String) -> String {
+    use unicode_normalization::UnicodeNormalization;
+    text.nfc().collect()
+}
+
+/// Keep a text as is, the NFC normalization needs the `unicode` feature
+#[cfg(not(feature = "unicode"))]
+fn nfc(text: String) -> String {
+    text
+}
+
+impl TextPolicy {
+    /// Sanitize a text
+    fn sanitize(&self, text: &str, multiline: bool) -> String {
+        let text = if self.line_endings {
+            text.replace("\r\n", "\n").replace('\r', "\n")
+        } else {
+            text.to_string()
+        };
+        let text: String = text
+            .chars()
+            .filter_map(|c| {
+                if !c.is_control() || (multiline && self.multiline_allowed.contains(&c)) {
+                    Some(c)
+                } else {
+                    self.replacement
+                }
+            })
+            .collect();
+        if self.nfc {
+            nfc(text)
+        } else {
+            text
+        }
+    }
+}
+
+/// Report the control characters of the texts of a Card, the line endings and the tabs of the multi-line texts
+/// excepted.
+/// A blank text is reported as an empty value instead.
+pub(crate) fn validate_control_characters(card: &Card, report: &mut ValidationReport) {
+    let mut found = Vec::new();
+    for_each_card_text(card, |path, text| {
+        if text.trim().is_empty() {
+            return;
+        }
+        let multiline = is_multiline(path);
+        let control = text
+            .chars()
+            .find(|c| c.is_control() && !(multiline && matches!(c, '\n' | '\r' | '\t')));
+        if let Some(control) = control {
+            found.push((path.to_string(), control));
+        }
+    });
+    found.sort();
+    for (path, control) in found {
+        report.add(
+            ValidationCode::ControlCharacter,
+            &path,
+            &format!(
+                "the text contains the control character U+{:04X}",
+                u32::from(control)
+            ),
+        );
+    }
+}
+
+impl Card {
+    /// Sanitize the texts of the Card: strip or replace their control characters, normalize their line endings
+    /// and optionally normalize them to NFC, see the [module documentation](crate::text).
+    ///
+    /// Returns the changes, sorted by path.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, Name, TextPolicy};
+    ///
+    /// let mut card = Card::new_with_latest_version("my:uri");
+    /// let mut name = Name::default();
+    /// name.full = Some("Jane\0 Doe".to_string());
+    /// card.name = Some(name);
+    /// let changes = card.sanitize_text(&TextPolicy::default());
+    /// assert_eq!(changes[0].path, "name/full");
+    /// assert_eq!(card.name.unwrap().full.as_deref(), Some("Jane Doe"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a sanitized text cannot be set, which cannot happen as only the texts are changed.
+    pub fn sanitize_text(&mut self, policy: &TextPolicy) -> Vec<TextChange> {
+        let mut changes = Vec::new();
+        for_each_card_text(self, |path, text| {
+            let after = policy.sanitize(text, is_multiline(path));
+            if after != text {
+                let change = TextChange {
+                    path: path.to_string(),
+                    before: text.to_string(),
+                    after,
+                };
+                changes.push((path.clone(), change));
+            }
+        });
+        changes.sort_by(|(_, a), (_, b)| a.path.cmp(&b.path));
+        set_values(
+            self,
+            changes
+                .iter()
+                .map(|(path, change)| (path, Value::String(change.after.clone()))),
+        )
+        .expect("Failed to set a sanitized text");
+        changes.into_iter().map(|(_, change)| change).collect()
+    }
+}
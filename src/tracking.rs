@@ -0,0 +1,275 @@
+//! Tracking of the modifications of a [`Card`], for sync clients deciding whether to upload it.
+//!
+//! With the `tracking` feature, a Card carries a revision counter, not serialized, bumped by the helper methods
+//! modifying it (e.g. [`Card::add_localization`], [`crate::path::set_value`], [`Card::edit`]).
+//! Direct mutations of the public fields cannot be tracked: use [`Card::edit`] to modify the fields of a tracked Card.
+//!
+//! With [`Card::touch_updated_on_mutation`], the tracked modifications also set the `updated` property of the Card
+//! to the time of a [`Clock`], once per edit guard (see [`Card::edit_scoped`]) instead of once per modification.
+
+use crate::Card;
+#[cfg(feature = "tracking")]
+use std::fmt;
+#[cfg(feature = "tracking")]
+use std::ops::{Deref, DerefMut};
+#[cfg(feature = "tracking")]
+use std::panic::RefUnwindSafe;
+#[cfg(feature = "tracking")]
+use std::sync::Arc;
+#[cfg(feature = "tracking")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The source of the `updated` timestamps, see [`Card::touch_updated_on_mutation`].
+///
+/// A Card being thread-safe and unwind-safe, so must be its clock.
+#[cfg(feature = "tracking")]
+pub trait Clock: Send + Sync + RefUnwindSafe {
+    /// Get the current time as a UTC timestamp of RFC 9553 (e.g. `2022-09-30T14:35:10Z`).
+    fn now(&self) -> String;
+}
+
+/// The [`Clock`] of the system time.
+#[cfg(feature = "tracking")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "tracking")]
+impl Clock for SystemClock {
+    fn now(&self) -> String {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        let (days, time) = (seconds / 86_400, seconds % 86_400);
+        // the civil date of a number of days since 1970-01-01, in the proleptic Gregorian calendar
+        let days = days + 719_468;
+        let era = days / 146_097;
+        let day_of_era = days % 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+        let year = year_of_era + era * 400 + u64::from(month <= 2);
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            year,
+            month,
+            day,
+            time / 3_600,
+            time % 3_600 / 60,
+            time % 60
+        )
+    }
+}
+
+/// The revision counter of a Card, ignored by the comparisons of Cards.
+#[cfg(feature = "tracking")]
+#[derive(Default)]
+pub(crate) struct Revision {
+    /// The number of modifications since the Card was created or parsed
+    current: u64,
+    /// The revision at the last [`Card::mark_clean`]
+    clean: u64,
+    /// The clock setting `updated` on the modifications, see [`Card::touch_updated_on_mutation`]
+    clock: Option<Arc<dyn Clock>>,
+    /// The number of open edit guards, deferring the update of `updated` to the last one
+    editing: u32,
+}
+
+#[cfg(feature = "tracking")]
+impl Clone for Revision {
+    fn clone(&self) -> Self {
+        // the edit guards are those of the original Card, a copy made inside one is not being edited
+        Revision {
+            current: self.current,
+            clean: self.clean,
+            clock: self.clock.clone(),
+            editing: 0,
+        }
+    }
+}
+
+#[cfg(feature = "tracking")]
+impl fmt::Debug for Revision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Revision")
+            .field("current", &self.current)
+            .field("clean", &self.clean)
+            .field("clock", &self.clock.is_some())
+            .finish()
+    }
+}
+
+#[cfg(feature = "tracking")]
+impl PartialEq for Revision {
+    fn eq(&self, _other: &Self) -> bool {
+        // two Cards with the same content are equal whatever their history
+        true
+    }
+}
+
+/// A guard giving mutable access to the fields of a Card, bumping its revision when dropped if it was modified.
+#[cfg(feature = "tracking")]
+pub struct CardEdit<'a> {
+    /// The edited Card
+    card: &'a mut Card,
+    /// If the Card was borrowed mutably
+    modified: bool,
+    /// The revision of the Card when the guard was created
+    start: u64,
+    /// The number of open edit guards of the Card with this one, restored when the guard is dropped
+    /// since the whole Card, with its revision, can be replaced through the guard
+    depth: u32,
+}
+
+#[cfg(feature = "tracking")]
+impl Deref for CardEdit<'_> {
+    type Target = Card;
+
+    fn deref(&self) -> &Card {
+        self.card
+    }
+}
+
+#[cfg(feature = "tracking")]
+impl DerefMut for CardEdit<'_> {
+    fn deref_mut(&mut self) -> &mut Card {
+        self.modified = true;
+        self.card
+    }
+}
+
+#[cfg(feature = "tracking")]
+impl Drop for CardEdit<'_> {
+    fn drop(&mut self) {
+        let revision = &mut self.card.revision;
+        revision.editing = self.depth - 1;
+        if self.modified {
+            revision.current += 1;
+        }
+        if revision.editing == 0 && revision.current != self.start {
+            self.card.touch_updated();
+        }
+    }
+}
+
+impl Card {
+    /// Record a modification of the Card, does nothing without the `tracking` feature
+    pub(crate) fn touch(&mut self) {
+        #[cfg(feature = "tracking")]
+        {
+            self.revision.current += 1;
+            if self.revision.editing == 0 {
+                self.touch_updated();
+            }
+        }
+    }
+
+    /// Set `updated` to the time of the clock of the Card, if any
+    #[cfg(feature = "tracking")]
+    fn touch_updated(&mut self) {
+        if let Some(clock) = &self.revision.clock {
+            self.updated = Some(clock.now());
+        }
+    }
+
+    /// Set the clock used to refresh the `updated` property of the Card on each tracked modification, or `None`
+    /// to stop refreshing it.
+    ///
+    /// The modifications made through an edit guard (see [`Card::edit`] and [`Card::edit_scoped`]) refresh
+    /// `updated` once, when the guard is dropped. The clock is not serialized, a parsed Card has none.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, Clock};
+    ///
+    /// struct Fixed;
+    ///
+    /// impl Clock for Fixed {
+    ///     fn now(&self) -> String {
+    ///         "2024-01-01T00:00:00Z".to_string()
+    ///     }
+    /// }
+    ///
+    /// let mut card = Card::new_with_latest_version("my:uri");
+    /// card.touch_updated_on_mutation(Some(std::sync::Arc::new(Fixed)));
+    /// card.add_keyword("friend");
+    /// assert_eq!(card.updated.as_deref(), Some("2024-01-01T00:00:00Z"));
+    /// ```
+    #[cfg(feature = "tracking")]
+    pub fn touch_updated_on_mutation(&mut self, clock: Option<Arc<dyn Clock>>) {
+        self.revision.clock = clock;
+    }
+
+    /// Get the revision of the Card, the number of modifications made by the helper methods since it was created or parsed.
+    #[cfg(feature = "tracking")]
+    pub fn revision(&self) -> u64 {
+        self.revision.current
+    }
+
+    /// Mark the Card as clean, e.g. after uploading it, and return its revision.
+    #[cfg(feature = "tracking")]
+    pub fn mark_clean(&mut self) -> u64 {
+        self.revision.clean = self.revision.current;
+        self.revision.current
+    }
+
+    /// Check if the Card was modified since the last [`Card::mark_clean`], or since it was created or parsed.
+    #[cfg(feature = "tracking")]
+    pub fn is_dirty(&self) -> bool {
+        self.revision.current != self.revision.clean
+    }
+
+    /// Check if the Card was modified since the given revision, see [`Card::revision`].
+    #[cfg(feature = "tracking")]
+    pub fn is_dirty_since(&self, revision: u64) -> bool {
+        self.revision.current > revision
+    }
+
+    /// Get mutable access to the fields of the Card, the revision is bumped once when the guard is dropped if a field was modified.
+    ///
+    /// ```rust
+    /// use jscontact::{Card, Name};
+    ///
+    /// let mut card = Card::new_with_latest_version("my:uri");
+    /// card.edit().name = Some(Name::default());
+    /// assert!(card.is_dirty_since(0));
+    /// ```
+    #[cfg(feature = "tracking")]
+    pub fn edit(&mut self) -> CardEdit<'_> {
+        self.revision.editing += 1;
+        let start = self.revision.current;
+        let depth = self.revision.editing;
+        CardEdit {
+            card: self,
+            modified: false,
+            start,
+            depth,
+        }
+    }
+
+    /// Modify the Card in a closure through an edit guard, see [`Card::edit`]: the revision is bumped, and `updated`
+    /// refreshed (see [`Card::touch_updated_on_mutation`]), once when the closure ends if the Card was modified.
+    ///
+    /// ```rust
+    /// use jscontact::Card;
+    ///
+    /// let mut card = Card::new_with_latest_version("my:uri");
+    /// let uid = card.edit_scoped(|card| card.uid.clone());
+    /// assert!(!card.is_dirty());
+    /// card.edit_scoped(|card| {
+    ///     card.language = Some("en".to_string());
+    ///     card.add_keyword("friend");
+    /// });
+    /// assert!(card.is_dirty());
+    /// ```
+    #[cfg(feature = "tracking")]
+    pub fn edit_scoped<R>(&mut self, f: impl FnOnce(&mut CardEdit<'_>) -> R) -> R {
+        let mut edit = self.edit();
+        f(&mut edit)
+    }
+}
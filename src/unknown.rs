@@ -0,0 +1,178 @@
+//! The properties of a Card unknown to this crate, e.g. `example.com:foo` or a typo such as `nmae`.
+//!
+//! The Card keeps its unknown properties in [`crate::Card::extensions`], so `deny_unknown_fields` cannot
+//! be used to reject the typos. The unknown properties are partitioned into the vendor-specific
+//! properties, whose name is prefixed by a domain name and a colon (see [`is_vendor_property`]), and the
+//! others. They are handled according to the way the Card is read:
+//! - the deserialization keeps all the unknown properties of the Card, see [`crate::Card::unknown_properties`]
+//!   for the ones that are not vendor-specific
+//! - [`crate::ParseOptions::reject_unknown_properties`] rejects the Cards with an unknown property that is not
+//!   vendor-specific, listing their paths
+//! - [`crate::Card::from_value_collecting`] keeps all the unknown properties, each one that is not vendor-specific
+//!   with an error at its path
+//!
+//! The objects inside the properties of a Card (e.g. an `EmailAddress`) have no room for the unknown members,
+//! which are ignored by the deserialization. They are reported as the unknown properties of the Card, with their
+//! path (e.g. `emails/e1/adress`), a vendor-specific member being ignored silently.
+
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::path::PROPERTIES;
+use crate::{Card, PathSegment};
+
+/// The unknown properties of a Card that are not vendor-specific, see
+/// [`crate::ParseOptions::reject_unknown_properties`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownProperties {
+    /// The paths of the unknown properties, sorted (e.g. `nmae` or `emails/e1/adress`).
+    pub paths: Vec<String>,
+}
+
+impl fmt::Display for UnknownProperties {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown properties {}, a vendor-specific property must be prefixed by a domain name (e.g. example.com:foo)",
+            self.paths.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for UnknownProperties {}
+
+/// Check if a label of a domain name has only ASCII letters, digits and inner hyphens
+fn is_label(label: &str) -> bool {
+    !label.is_empty()
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+/// Check if the name of a property is vendor-specific: a domain name with at least two labels, a colon and
+/// a non-empty name (e.g. `example.com:foo`), as RFC 9553 recommends.
+///
+/// ```rust
+/// use jscontact::unknown::is_vendor_property;
+///
+/// assert!(is_vendor_property("example.com:foo"));
+/// assert!(!is_vendor_property("nmae"));
+/// assert!(!is_vendor_property("example:foo"));
+/// ```
+pub fn is_vendor_property(name: &str) -> bool {
+    let Some((domain, property)) = name.split_once(':') else {
+        return false;
+    };
+    let labels: Vec<&str> = domain.split('.').collect();
+    !property.is_empty()
+        && labels.len() >= 2
+        && labels.iter().all(|label| is_label(label))
+        && labels
+            .last()
+            .is_some_and(|tld| tld.bytes().all(|b| b.is_ascii_alphabetic()))
+}
+
+/// Get the path of a child of a value
+fn child(path: &str, key: &str) -> String {
+    format!("{}/{}", path, PathSegment::Key(key.to_string()))
+}
+
+/// Check if a value is not serialized when read, e.g. an empty `speakToAs` object
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(values) => values.is_empty(),
+        Value::Object(members) => members
+            .iter()
+            .all(|(key, value)| key == "@type" || is_empty(value)),
+        _ => false,
+    }
+}
+
+/// Collect the paths of the members of the JSON that are missing from the JSON of the read value
+fn collect_ignored(json: &Value, read: &Value, path: &str, paths: &mut Vec<String>) {
+    match (json, read) {
+        (Value::Object(members), Value::Object(read)) => {
+            for (key, value) in members {
+                // the @type is checked by the deserialization, and not kept by the objects built without it
+                if key == "@type" {
+                    continue;
+                }
+                match read.get(key) {
+                    Some(read) => collect_ignored(value, read, &child(path, key), paths),
+                    None if is_vendor_property(key) || is_empty(value) => {}
+                    None => paths.push(child(path, key)),
+                }
+            }
+        }
+        (Value::Array(values), Value::Array(read)) => {
+            for (idx, (value, read)) in values.iter().zip(read).enumerate() {
+                let item = format!("{}/{}", path, PathSegment::Index(idx));
+                collect_ignored(value, read, &item, paths);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Get the sorted paths of the members of the properties of a Card that were ignored when reading it,
+/// the vendor-specific members excluded.
+pub(crate) fn ignored_members(object: &Map<String, Value>, card: &Card) -> Vec<String> {
+    let Ok(Value::Object(read)) = serde_json::to_value(card) else {
+        return Vec::new();
+    };
+    let mut paths = Vec::new();
+    for property in PROPERTIES {
+        if let (Some(json), Some(read)) = (object.get(*property), read.get(*property)) {
+            let path = PathSegment::Key(property.to_string()).to_string();
+            collect_ignored(json, read, &path, &mut paths);
+        }
+    }
+    paths.sort();
+    paths
+}
+
+/// Check that a read Card has no unknown property that is not vendor-specific, nor ignored member.
+pub(crate) fn check_unknown(
+    object: &Map<String, Value>,
+    card: &Card,
+) -> Result<(), UnknownProperties> {
+    let mut paths: Vec<String> = card
+        .unknown_properties()
+        .into_iter()
+        .map(|property| PathSegment::Key(property.to_string()).to_string())
+        .collect();
+    paths.extend(ignored_members(object, card));
+    paths.sort();
+    if paths.is_empty() {
+        return Ok(());
+    }
+    Err(UnknownProperties { paths })
+}
+
+impl Card {
+    /// Get the unknown properties of the Card that are not vendor-specific, sorted, e.g. a typo such as `nmae`,
+    /// see the [module documentation](crate::unknown).
+    ///
+    /// ```rust
+    /// use jscontact::Card;
+    ///
+    /// let json = r#"{"@type":"Card","version":"1.0","uid":"1","nmae":{},"example.com:foo":1}"#;
+    /// let card: Card = serde_json::from_str(json).unwrap();
+    /// assert_eq!(card.unknown_properties(), ["nmae"]);
+    /// assert_eq!(card.extensions.len(), 2);
+    /// ```
+    pub fn unknown_properties(&self) -> Vec<&str> {
+        let mut properties: Vec<&str> = self
+            .extensions
+            .keys()
+            .map(String::as_str)
+            .filter(|property| !is_vendor_property(property))
+            .collect();
+        properties.sort_unstable();
+        properties
+    }
+}
@@ -0,0 +1,582 @@
+//! Validation of the rules of RFC 9553 that are not enforced by the types.
+//!
+//! The report can be exported as JSON, each violation being `{ "path", "code", "message", "severity" }`.
+//! The codes (see [`ValidationCode`]) are stable identifiers: a code is never renamed nor reused for another rule,
+//! while the messages are meant for humans and can change.
+//!
+//! Each code has a fixed severity (see [`ValidationCode::severity`]): an error is a violation of a MUST of RFC 9553,
+//! a warning is a Card that is valid but likely not what its producer meant (e.g. a `language` that is not
+//! among the `preferredLanguages`). [`ValidationReport::is_valid`] only fails on the errors, a pipeline enforcing
+//! the warnings too can check [`ValidationReport::with_severity`].
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::empty::is_blank;
+use crate::path::PathSegment;
+use crate::text::validate_control_characters;
+use crate::{Card, Name, NameComponentKind, PlaceRef, SmallString, SpeakToAs};
+
+pub mod syntax;
+
+/// The stable identifier of a validation rule.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum ValidationCode {
+    /// `default-separator-not-ordered`: the defaultSeparator of a Name is set but isOrdered is not true.
+    DefaultSeparatorNotOrdered,
+    /// `separator-not-ordered`: a Name has separator components but isOrdered is not true.
+    SeparatorNotOrdered,
+    /// `consecutive-separators`: two separator components of a Name follow each other.
+    ConsecutiveSeparators,
+    /// `pref-out-of-range`: a pref property is not between 1 and 100.
+    PrefOutOfRange,
+    /// `list-as-out-of-range`: a listAs property is not greater than zero.
+    ListAsOutOfRange,
+    /// `unknown-place-ref`: the place of an anniversary references an address that does not exist, see [`crate::PlaceRef`].
+    UnknownPlaceRef,
+    /// `empty-speak-to-as`: a SpeakToAs has neither grammaticalGender nor pronouns.
+    EmptySpeakToAs,
+    /// `empty-pronouns`: the pronouns of a Pronouns object are empty.
+    EmptyPronouns,
+    /// `empty-value`: a required text (e.g. the number of a Phone or the uri of a Link) is empty, see [`crate::EmptyValue`].
+    EmptyValue,
+    /// `language-not-preferred`: a warning, the language of the Card is not among its preferredLanguages.
+    LanguageNotPreferred,
+    /// `localization-of-card-language`: a warning, the Card is localized to its own language.
+    LocalizationOfCardLanguage,
+    /// `duplicate-pref`: a warning, several entries of a map have the same pref, see [`crate::Card::renumber_prefs`].
+    DuplicatePref,
+    /// `control-character`: a warning, a text has a control character (e.g. NUL), see [`crate::Card::sanitize_text`].
+    ControlCharacter,
+}
+
+impl ValidationCode {
+    /// Get the severity of the rule, which never changes for a code.
+    pub fn severity(&self) -> ValidationSeverity {
+        match self {
+            ValidationCode::LanguageNotPreferred
+            | ValidationCode::LocalizationOfCardLanguage
+            | ValidationCode::DuplicatePref
+            | ValidationCode::ControlCharacter => ValidationSeverity::Warning,
+            _ => ValidationSeverity::Error,
+        }
+    }
+}
+
+/// The severity of a rule violation.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidationSeverity {
+    /// The Card violates a MUST of RFC 9553.
+    Error,
+    /// The Card violates a SHOULD of RFC 9553.
+    Warning,
+}
+
+/// A rule violation found while validating a [`Card`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    /// The path of the invalid property (e.g. `name/defaultSeparator`).
+    pub path: String,
+    /// The stable identifier of the violated rule.
+    pub code: ValidationCode,
+    /// A description of the violation.
+    pub message: String,
+    /// The severity of the violation.
+    pub severity: ValidationSeverity,
+}
+
+impl ValidationError {
+    /// Creates a new ValidationError object with the specified code, path and message.
+    pub fn new(code: ValidationCode, path: &str, message: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            code,
+            message: message.to_string(),
+            severity: code.severity(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// The list of violations found while validating a [`Card`].
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct ValidationReport {
+    /// The violations, in the order they were found.
+    pub errors: Vec<ValidationError>,
+}
+
+impl ValidationReport {
+    /// Creates an empty ValidationReport.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a violation.
+    pub fn add(&mut self, code: ValidationCode, path: &str, message: &str) {
+        self.errors.push(ValidationError::new(code, path, message));
+    }
+
+    /// Returns true if no violation with the error severity was found, warnings are allowed.
+    pub fn is_valid(&self) -> bool {
+        self.errors
+            .iter()
+            .all(|error| error.severity != ValidationSeverity::Error)
+    }
+
+    /// Count the violations by severity.
+    pub fn summary(&self) -> ValidationSummary {
+        let mut summary = ValidationSummary::default();
+        for error in &self.errors {
+            match error.severity {
+                ValidationSeverity::Error => summary.errors += 1,
+                ValidationSeverity::Warning => summary.warnings += 1,
+            }
+        }
+        summary
+    }
+
+    /// Get the violations of a severity, e.g. the warnings that [`ValidationReport::is_valid`] allows.
+    pub fn with_severity(
+        &self,
+        severity: ValidationSeverity,
+    ) -> impl Iterator<Item = &ValidationError> + '_ {
+        self.errors
+            .iter()
+            .filter(move |error| error.severity == severity)
+    }
+
+    /// Get the violations of a property and of its sub-properties, e.g. `name` matches `name/components/1`.
+    pub fn filter_path<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = &'a ValidationError> + 'a {
+        self.errors.iter().filter(move |error| {
+            error
+                .path
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/') || prefix.is_empty())
+        })
+    }
+
+    /// Export the report as JSON: `{ "errors": [{ "path", "code", "message", "severity" }] }`.
+    /// # Errors
+    /// Will return an error if the serialization fails.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+/// The number of violations by severity of a [`ValidationReport`].
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationSummary {
+    /// The number of violations with the error severity.
+    pub errors: usize,
+    /// The number of violations with the warning severity.
+    pub warnings: usize,
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for error in &self.errors {
+            writeln!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl Card {
+    /// Validates the Card against the rules of RFC 9553 that the types cannot express.
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::new();
+        if let Some(name) = &self.name {
+            validate_name(name, "name", &mut report);
+        }
+        validate_prefs(
+            &self.nicknames,
+            "nicknames",
+            |n| n.pref.map(u64::from),
+            &mut report,
+        );
+        if let Some(speak_to_as) = &self.speak_to_as {
+            validate_speak_to_as(speak_to_as, &mut report);
+            validate_prefs(
+                &speak_to_as.pronouns,
+                "speakToAs/pronouns",
+                |p| p.pref.map(u64::from),
+                &mut report,
+            );
+        }
+        validate_prefs(
+            &self.emails,
+            "emails",
+            |e| e.pref.map(u64::from),
+            &mut report,
+        );
+        validate_prefs(
+            &self.online_services,
+            "onlineServices",
+            |o| o.pref.map(u64::from),
+            &mut report,
+        );
+        validate_prefs(
+            &self.phones,
+            "phones",
+            |p| p.pref.map(u64::from),
+            &mut report,
+        );
+        validate_prefs(
+            &self.preferred_languages,
+            "preferredLanguages",
+            |l| l.pref.map(u64::from),
+            &mut report,
+        );
+        validate_prefs(&self.calendars, "calendars", |c| c.pref, &mut report);
+        validate_prefs(
+            &self.scheduling_addresses,
+            "schedulingAddresses",
+            |s| s.pref,
+            &mut report,
+        );
+        validate_prefs(&self.addresses, "addresses", |a| a.pref, &mut report);
+        validate_prefs(&self.crypto_keys, "cryptoKeys", |c| c.pref, &mut report);
+        validate_prefs(&self.directories, "directories", |d| d.pref, &mut report);
+        validate_prefs(&self.links, "links", |l| l.pref, &mut report);
+        validate_prefs(&self.media, "media", |m| m.pref, &mut report);
+        validate_list_as(&self.directories, "directories", |d| d.list_as, &mut report);
+        validate_list_as(
+            &self.personal_info,
+            "personalInfo",
+            |p| p.list_as,
+            &mut report,
+        );
+        validate_place_refs(self, &mut report);
+        validate_required_texts(self, &mut report);
+        validate_languages(self, &mut report);
+        validate_control_characters(self, &mut report);
+        report
+    }
+}
+
+impl Name {
+    /// Validates the Name against the rules of RFC 9553 that the types cannot express.
+    ///
+    /// The paths of the report are relative to the Name.
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::new();
+        validate_name(self, "", &mut report);
+        report
+    }
+}
+
+/// Join a parent path and a property name
+fn join_path(parent: &str, property: &str) -> String {
+    if parent.is_empty() {
+        property.to_string()
+    } else {
+        format!("{}/{}", parent, property)
+    }
+}
+
+/// Validate the separators of a Name
+fn validate_name(name: &Name, path: &str, report: &mut ValidationReport) {
+    let is_ordered = name.is_ordered == Some(true);
+    // an empty default separator is valid (e.g. Japanese names)
+    if name.default_separator.is_some() && !is_ordered {
+        report.add(
+            ValidationCode::DefaultSeparatorNotOrdered,
+            &join_path(path, "defaultSeparator"),
+            "defaultSeparator must not be set if isOrdered is not true",
+        );
+    }
+    let mut previous_is_separator = false;
+    for (idx, component) in name.components.iter().flatten().enumerate() {
+        let is_separator = component.kind == NameComponentKind::Separator;
+        let component_path = join_path(path, &format!("components/{}", idx));
+        if is_separator && !is_ordered {
+            report.add(
+                ValidationCode::SeparatorNotOrdered,
+                &component_path,
+                "separator components must not be set if isOrdered is not true",
+            );
+        }
+        if is_separator && previous_is_separator {
+            report.add(
+                ValidationCode::ConsecutiveSeparators,
+                &component_path,
+                "separator components must not be consecutive",
+            );
+        }
+        previous_is_separator = is_separator;
+    }
+}
+
+/// Get the entries of a map sorted by key, so the violations are reported in a stable order
+fn sorted_entries<T>(map: &Option<HashMap<SmallString, T>>) -> Vec<(&SmallString, &T)> {
+    let mut entries: Vec<(&SmallString, &T)> = map.iter().flatten().collect();
+    entries.sort_by_key(|(key, _)| *key);
+    entries
+}
+
+/// Validate that the pref properties of the entries of a map are between 1 and 100, and are not shared
+fn validate_prefs<T>(
+    map: &Option<HashMap<SmallString, T>>,
+    property: &str,
+    pref: impl Fn(&T) -> Option<u64>,
+    report: &mut ValidationReport,
+) {
+    let mut counts: HashMap<u64, usize> = HashMap::new();
+    for value in map.iter().flat_map(HashMap::values) {
+        if let Some(pref) = pref(value) {
+            *counts.entry(pref).or_default() += 1;
+        }
+    }
+    for (key, value) in sorted_entries(map) {
+        let path = format!("{}/{}/pref", property, PathSegment::Key(key.to_string()));
+        if pref(value).is_some_and(|pref| !(1..=100).contains(&pref)) {
+            report.add(
+                ValidationCode::PrefOutOfRange,
+                &path,
+                "pref must be between 1 and 100",
+            );
+        }
+        if let Some(pref) = pref(value).filter(|pref| counts[pref] > 1) {
+            report.add(
+                ValidationCode::DuplicatePref,
+                &path,
+                &format!("pref {} is shared with another entry", pref),
+            );
+        }
+    }
+}
+
+/// Validate that a SpeakToAs is not empty and that its pronouns are not empty
+fn validate_speak_to_as(speak_to_as: &SpeakToAs, report: &mut ValidationReport) {
+    if speak_to_as.is_empty() {
+        report.add(
+            ValidationCode::EmptySpeakToAs,
+            "speakToAs",
+            "speakToAs must have grammaticalGender or pronouns",
+        );
+    }
+    for (key, pronouns) in sorted_entries(&speak_to_as.pronouns) {
+        if pronouns.pronouns.trim().is_empty() {
+            report.add(
+                ValidationCode::EmptyPronouns,
+                &format!(
+                    "speakToAs/pronouns/{}/pronouns",
+                    PathSegment::Key(key.to_string())
+                ),
+                "pronouns must not be empty",
+            );
+        }
+    }
+}
+
+/// Validate that the places of the anniversaries reference existing addresses
+fn validate_place_refs(card: &Card, report: &mut ValidationReport) {
+    for (key, anniversary) in sorted_entries(&card.anniversaries) {
+        let Some(PlaceRef::Key(address_key)) = &anniversary.place else {
+            continue;
+        };
+        if anniversary.place_resolved(card).is_none() {
+            report.add(
+                ValidationCode::UnknownPlaceRef,
+                &format!("anniversaries/{}/place", PathSegment::Key(key.to_string())),
+                &format!("the address '{}' does not exist", address_key),
+            );
+        }
+    }
+}
+
+/// Get the primary subtag of a language tag, lowercased, e.g. `de` for `de-AT`
+fn primary_language(tag: &str) -> String {
+    tag.split('-')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// Validate that the language of the Card is consistent with its preferred languages and its localizations
+fn validate_languages(card: &Card, report: &mut ValidationReport) {
+    let Some(language) = &card.language else {
+        return;
+    };
+    let primary = primary_language(language);
+    let preferred: Vec<&str> = card
+        .preferred_languages
+        .iter()
+        .flatten()
+        .map(|(_, pref)| pref.language.as_str())
+        .collect();
+    if !preferred.is_empty()
+        && preferred
+            .iter()
+            .all(|preferred| primary_language(preferred) != primary)
+    {
+        report.add(
+            ValidationCode::LanguageNotPreferred,
+            "language",
+            &format!(
+                "the language '{}' is not among the preferredLanguages",
+                language
+            ),
+        );
+    }
+    let mut localized: Vec<&String> = card
+        .localizations
+        .iter()
+        .flatten()
+        .map(|(key, _)| key)
+        .collect();
+    localized.sort();
+    for localization in localized {
+        // the language itself or its primary language, e.g. `de` for a Card in `de-AT`
+        if localization.eq_ignore_ascii_case(language)
+            || localization.eq_ignore_ascii_case(&primary)
+        {
+            report.add(
+                ValidationCode::LocalizationOfCardLanguage,
+                &format!("localizations/{}", PathSegment::Key(localization.clone())),
+                &format!("the Card is localized to its own language '{}'", language),
+            );
+        }
+    }
+}
+
+/// Validate that the listAs properties of the entries of a map are greater than zero
+fn validate_list_as<T>(
+    map: &Option<HashMap<SmallString, T>>,
+    property: &str,
+    list_as: impl Fn(&T) -> Option<u64>,
+    report: &mut ValidationReport,
+) {
+    for (key, value) in sorted_entries(map) {
+        if list_as(value) == Some(0) {
+            report.add(
+                ValidationCode::ListAsOutOfRange,
+                &format!("{}/{}/listAs", property, PathSegment::Key(key.to_string())),
+                "listAs must be greater than zero",
+            );
+        }
+    }
+}
+
+/// Validate that the required texts of the entries of a map are not empty
+fn validate_required_text<T>(
+    map: &Option<HashMap<SmallString, T>>,
+    property: &str,
+    field: &str,
+    text: impl Fn(&T) -> Option<&str>,
+    report: &mut ValidationReport,
+) {
+    for (key, value) in sorted_entries(map) {
+        if text(value).is_some_and(is_blank) {
+            report.add(
+                ValidationCode::EmptyValue,
+                &format!(
+                    "{}/{}/{}",
+                    property,
+                    PathSegment::Key(key.to_string()),
+                    field
+                ),
+                &format!("{} must not be empty", field),
+            );
+        }
+    }
+}
+
+/// Validate that the required texts of the Card are not empty, e.g. the number of a phone
+fn validate_required_texts(card: &Card, report: &mut ValidationReport) {
+    validate_required_text(
+        &card.nicknames,
+        "nicknames",
+        "name",
+        |n| Some(&n.name),
+        report,
+    );
+    for (key, organization) in sorted_entries(&card.organizations) {
+        for (idx, unit) in organization.units.iter().flatten().enumerate() {
+            if is_blank(&unit.name) {
+                report.add(
+                    ValidationCode::EmptyValue,
+                    &format!(
+                        "organizations/{}/units/{}/name",
+                        PathSegment::Key(key.to_string()),
+                        idx
+                    ),
+                    "name must not be empty",
+                );
+            }
+        }
+    }
+    validate_required_text(&card.titles, "titles", "name", |t| Some(&t.name), report);
+    validate_required_text(
+        &card.emails,
+        "emails",
+        "address",
+        |e| Some(&e.address),
+        report,
+    );
+    // the uri of an online service is optional, but must not be empty when set
+    validate_required_text(
+        &card.online_services,
+        "onlineServices",
+        "uri",
+        |o| o.uri.as_deref(),
+        report,
+    );
+    validate_required_text(
+        &card.phones,
+        "phones",
+        "number",
+        |p| Some(&p.number),
+        report,
+    );
+    validate_required_text(
+        &card.calendars,
+        "calendars",
+        "uri",
+        |c| Some(&c.uri),
+        report,
+    );
+    validate_required_text(
+        &card.scheduling_addresses,
+        "schedulingAddresses",
+        "uri",
+        |s| Some(&s.uri),
+        report,
+    );
+    validate_required_text(
+        &card.crypto_keys,
+        "cryptoKeys",
+        "uri",
+        |c| Some(&c.uri),
+        report,
+    );
+    validate_required_text(
+        &card.directories,
+        "directories",
+        "uri",
+        |d| Some(&d.uri),
+        report,
+    );
+    validate_required_text(&card.links, "links", "uri", |l| Some(&l.uri), report);
+    validate_required_text(&card.media, "media", "uri", |m| Some(&m.uri[..]), report);
+    validate_required_text(&card.notes, "notes", "note", |n| Some(&n.note), report);
+    validate_required_text(
+        &card.personal_info,
+        "personalInfo",
+        "value",
+        |p| Some(&p.value),
+        report,
+    );
+}
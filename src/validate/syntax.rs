@@ -0,0 +1,250 @@
+//! Checks of the syntax of the values of a Card, written as small parsers so the crate does not depend on `regex`.
+//!
+//! The checks are about the shape of the values (e.g. a well-formed language tag), not about their meaning
+//! (e.g. a registered language subtag).
+
+use crate::resource::uri_scheme;
+
+/// Check that a string only contains ASCII letters and is in a range of lengths
+fn is_alpha(s: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&s.len()) && s.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+/// Check that a string only contains ASCII letters and digits and is in a range of lengths
+fn is_alphanum(s: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&s.len()) && s.bytes().all(|b| b.is_ascii_alphanumeric())
+}
+
+/// Check that a string is a number of digits in a range
+fn is_number(s: &str, digits: usize, min: u32, max: u32) -> bool {
+    s.len() == digits
+        && s.bytes().all(|b| b.is_ascii_digit())
+        && s.parse::<u32>().is_ok_and(|n| (min..=max).contains(&n))
+}
+
+/// Check the shape of a URI (RFC 3986): a scheme, a colon, then printable characters without spaces.
+///
+/// ```rust
+/// use jscontact::syntax::is_uri;
+///
+/// assert!(is_uri("mailto:jane_doe@example.com"));
+/// assert!(!is_uri("example.com/photo.jpg"));
+/// ```
+pub fn is_uri(uri: &str) -> bool {
+    let Some(scheme) = uri_scheme(uri) else {
+        return false;
+    };
+    let rest = &uri[scheme.len() + 1..];
+    !rest.is_empty() && rest.chars().all(|c| !c.is_whitespace() && !c.is_control())
+}
+
+/// Check the dot-atom of an addr-spec (RFC 5322): atoms of printable characters separated by single dots
+fn is_dot_atom(s: &str) -> bool {
+    const SPECIALS: &[u8] = b"!#$%&'*+-/=?^_`{|}~";
+    !s.is_empty()
+        && s.split('.').all(|atom| {
+            !atom.is_empty()
+                && atom
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || SPECIALS.contains(&b) || b >= 0x80)
+        })
+}
+
+/// Check a quoted local part of an addr-spec, e.g. `"john doe"`
+fn is_quoted_string(s: &str) -> bool {
+    let Some(inner) = s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) else {
+        return false;
+    };
+    let mut escaped = false;
+    for c in inner.chars() {
+        match (escaped, c) {
+            (true, _) => escaped = false,
+            (false, '\\') => escaped = true,
+            (false, '"') => return false,
+            (false, c) if c.is_control() => return false,
+            _ => {}
+        }
+    }
+    !escaped
+}
+
+/// Check a domain of an email address: labels of letters, digits and hyphens, or an address literal like `[192.0.2.1]`
+fn is_domain(domain: &str) -> bool {
+    if domain.starts_with('[') && domain.ends_with(']') {
+        return domain.len() > 2 && !domain[1..domain.len() - 1].contains(['[', ']', '\\']);
+    }
+    !domain.is_empty()
+        && domain.len() <= 255
+        && domain.split('.').all(|label| {
+            (1..=63).contains(&label.len())
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+        })
+}
+
+/// Check an email address, the addr-spec of RFC 5322 (e.g. `jane_doe@example.com`) without comments nor folding.
+///
+/// ```rust
+/// use jscontact::syntax::is_email_addr_spec;
+///
+/// assert!(is_email_addr_spec("jane_doe@example.com"));
+/// assert!(is_email_addr_spec("\"jane doe\"@example.com"));
+/// assert!(!is_email_addr_spec("jane doe@example.com"));
+/// ```
+pub fn is_email_addr_spec(address: &str) -> bool {
+    let Some((local, domain)) = address.rsplit_once('@') else {
+        return false;
+    };
+    (is_dot_atom(local) || is_quoted_string(local)) && is_domain(domain)
+}
+
+/// Check that a language tag is well-formed (RFC 5646, Section 2.1), e.g. `en`, `zh-Hant-TW` or `de-CH-1996`.
+///
+/// The subtags are not checked against the IANA registry, and the irregular grandfathered tags (e.g. `i-klingon`) are not supported.
+///
+/// ```rust
+/// use jscontact::syntax::is_language_tag;
+///
+/// assert!(is_language_tag("sr-Latn-RS"));
+/// assert!(is_language_tag("x-whatever"));
+/// assert!(!is_language_tag("en_US"));
+/// ```
+pub fn is_language_tag(tag: &str) -> bool {
+    let mut subtags = tag.split('-').peekable();
+    let Some(language) = subtags.next() else {
+        return false;
+    };
+    if language.eq_ignore_ascii_case("x") {
+        return is_private_use(subtags);
+    }
+    if !is_alpha(language, 2, 8) {
+        return false;
+    }
+    // up to 3 extended language subtags after a 2 or 3 letters language
+    if language.len() <= 3 {
+        for _ in 0..3 {
+            match subtags.peek() {
+                Some(extlang) if is_alpha(extlang, 3, 3) => subtags.next(),
+                _ => break,
+            };
+        }
+    }
+    if subtags.peek().is_some_and(|script| is_alpha(script, 4, 4)) {
+        subtags.next();
+    }
+    if subtags
+        .peek()
+        .is_some_and(|region| is_alpha(region, 2, 2) || is_number(region, 3, 0, 999))
+    {
+        subtags.next();
+    }
+    let is_variant = |subtag: &str| {
+        is_alphanum(subtag, 5, 8)
+            || (subtag.len() == 4
+                && subtag.as_bytes()[0].is_ascii_digit()
+                && is_alphanum(subtag, 4, 4))
+    };
+    while subtags.peek().is_some_and(|variant| is_variant(variant)) {
+        subtags.next();
+    }
+    while let Some(singleton) = subtags.next() {
+        if singleton.eq_ignore_ascii_case("x") {
+            return is_private_use(subtags);
+        }
+        if singleton.len() != 1 || !singleton.as_bytes()[0].is_ascii_alphanumeric() {
+            return false;
+        }
+        // an extension has at least one subtag of 2 to 8 characters
+        let mut count = 0;
+        while subtags
+            .peek()
+            .is_some_and(|subtag| is_alphanum(subtag, 2, 8))
+        {
+            subtags.next();
+            count += 1;
+        }
+        if count == 0 {
+            return false;
+        }
+    }
+    true
+}
+
+/// Check the subtags after the `x` of a private use sequence, at least one subtag of 1 to 8 characters
+fn is_private_use<'a>(subtags: impl Iterator<Item = &'a str>) -> bool {
+    let mut count = 0;
+    for subtag in subtags {
+        if !is_alphanum(subtag, 1, 8) {
+            return false;
+        }
+        count += 1;
+    }
+    count > 0
+}
+
+/// Check a country code, the 2 letters of an ISO 3166-1 alpha-2 code (e.g. `US`), in uppercase or lowercase.
+///
+/// ```rust
+/// use jscontact::syntax::is_country_code;
+///
+/// assert!(is_country_code("US"));
+/// assert!(!is_country_code("USA"));
+/// ```
+pub fn is_country_code(code: &str) -> bool {
+    is_alpha(code, 2, 2)
+}
+
+/// Check a UTCDateTime of RFC 9553 (Section 1.4.4), e.g. `2022-09-30T14:35:10Z` or `2022-09-30T14:35:10.522Z`.
+///
+/// The time must be in UTC with an uppercase `Z`, the fractional seconds must not have trailing zeros.
+///
+/// ```rust
+/// use jscontact::syntax::is_utc_date_time;
+///
+/// assert!(is_utc_date_time("2022-09-30T14:35:10Z"));
+/// assert!(!is_utc_date_time("2022-09-30T14:35:10+02:00"));
+/// ```
+pub fn is_utc_date_time(timestamp: &str) -> bool {
+    let Some((date, time)) = timestamp.split_once('T') else {
+        return false;
+    };
+    let Some(time) = time.strip_suffix('Z') else {
+        return false;
+    };
+    let mut date_parts = date.split('-');
+    let (Some(year), Some(month), Some(day), None) = (
+        date_parts.next(),
+        date_parts.next(),
+        date_parts.next(),
+        date_parts.next(),
+    ) else {
+        return false;
+    };
+    let (time, fraction) = match time.split_once('.') {
+        Some((time, fraction)) => (time, Some(fraction)),
+        None => (time, None),
+    };
+    let mut time_parts = time.split(':');
+    let (Some(hour), Some(minute), Some(second), None) = (
+        time_parts.next(),
+        time_parts.next(),
+        time_parts.next(),
+        time_parts.next(),
+    ) else {
+        return false;
+    };
+    let valid_fraction = fraction.is_none_or(|fraction| {
+        !fraction.is_empty()
+            && fraction.bytes().all(|b| b.is_ascii_digit())
+            && !fraction.ends_with('0')
+    });
+    is_number(year, 4, 0, 9999)
+        && is_number(month, 2, 1, 12)
+        && is_number(day, 2, 1, 31)
+        && is_number(hour, 2, 0, 23)
+        && is_number(minute, 2, 0, 59)
+        // a leap second
+        && is_number(second, 2, 0, 60)
+        && valid_fraction
+}
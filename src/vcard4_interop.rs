@@ -0,0 +1,851 @@
+//! Conversions between [`crate::Card`] and the [`vcard4::Vcard`] type.
+//!
+//! The mapping follows RFC 9555 for the properties both formats share.
+//! Everything that has no counterpart in the target format is collected in a [`crate::ConversionReport`].
+
+use std::collections::HashMap;
+
+use vcard4::{
+    helper,
+    parameter::{Parameters, TypeParameter},
+    property::{
+        AddressProperty, DateAndOrTime, DateTimeOrTextProperty, DeliveryAddress, Kind,
+        KindProperty, TextListProperty, TextOrUriProperty, TextProperty, UriProperty,
+    },
+    time::{Month, UtcOffset},
+    Date, DateTime, Uri, Vcard,
+};
+
+use crate::iri::iri_to_uri;
+use crate::vcard_map;
+use crate::{
+    Address, AddressComponent, AddressComponentKind, Anniversary, AnniversaryKind, Card, CardKind,
+    CardVersion, Context, ConversionReport, DateObject, EmailAddress, Link, Media, MediaKind, Name,
+    NameComponent, NameComponentKind, Nickname, Note, OrgUnit, Organization, PartialDate, Phone,
+    PhoneFeature, SmallString, Timestamp, Title, TitleKind,
+};
+use crate::{NicknameType, NoteType};
+
+impl Card {
+    /// Converts the Card to a [`vcard4::Vcard`].
+    /// The properties without vCard counterpart are listed in the returned [`ConversionReport`].
+    pub fn to_vcard4(&self) -> (Vcard, ConversionReport) {
+        let mut report = ConversionReport::new();
+        let mut vcard = Vcard::default();
+
+        vcard.uid = Some(text_or_uri(&self.uid));
+        vcard.formatted_name = vec![TextProperty::from(formatted_name(self))];
+        if let Some(kind) = &self.kind {
+            match kind {
+                CardKind::Individual => vcard.kind = Some(KindProperty::from(Kind::Individual)),
+                CardKind::Group => vcard.kind = Some(KindProperty::from(Kind::Group)),
+                CardKind::Org => vcard.kind = Some(KindProperty::from(Kind::Org)),
+                CardKind::Location => vcard.kind = Some(KindProperty::from(Kind::Location)),
+                CardKind::Application | CardKind::Device => {
+                    report.add_loss("kind", "vcard4 does not support this KIND value")
+                }
+            }
+        }
+        if let Some(prod_id) = &self.prod_id {
+            vcard.prod_id = Some(TextProperty::from(prod_id.clone()));
+        }
+        if let Some(updated) = &self.updated {
+            match to_vcard_date_time(updated) {
+                Some(date_time) => vcard.rev = Some(date_time.into()),
+                None => report.add_loss("updated", "invalid UTCDateTime"),
+            }
+        }
+        if let Some(name) = &self.name {
+            vcard.name = name_to_vcard(name, &mut report);
+        }
+        for (key, nickname) in sorted(&self.nicknames) {
+            let path = format!("nicknames/{}", key);
+            vcard.nickname.push(TextProperty {
+                group: None,
+                value: nickname.name.clone(),
+                parameters: parameters(
+                    &path,
+                    &nickname.contexts,
+                    nickname.pref.map(u64::from),
+                    &mut report,
+                ),
+            });
+        }
+        for (key, email) in sorted(&self.emails) {
+            let path = format!("emails/{}", key);
+            vcard.email.push(TextProperty {
+                group: None,
+                value: email.address.clone(),
+                parameters: parameters(
+                    &path,
+                    &email.contexts,
+                    email.pref.map(u64::from),
+                    &mut report,
+                ),
+            });
+        }
+        for (key, phone) in sorted(&self.phones) {
+            vcard.tel.push(phone_to_vcard(key, phone, &mut report));
+        }
+        for (key, address) in sorted(&self.addresses) {
+            vcard
+                .address
+                .push(address_to_vcard(key, address, &mut report));
+        }
+        for (key, organization) in sorted(&self.organizations) {
+            let mut values = vec![organization.name.clone().unwrap_or_default()];
+            for unit in organization.units.iter().flatten() {
+                values.push(unit.name.clone());
+            }
+            let mut org = TextListProperty::new_semi_colon(values);
+            let path = format!("organizations/{}", key);
+            org.parameters = parameters(&path, &organization.contexts, None, &mut report);
+            vcard.org.push(org);
+        }
+        for (key, title) in sorted(&self.titles) {
+            let property = TextProperty::from(title.name.clone());
+            match title.kind {
+                Some(TitleKind::Role) => vcard.role.push(property),
+                _ => vcard.title.push(property),
+            }
+            if title.organization_id.is_some() {
+                report.add_loss(
+                    &format!("titles/{}/organizationId", key),
+                    "vCard has no link between TITLE and ORG",
+                );
+            }
+        }
+        for (key, media) in sorted(&self.media) {
+            let Ok(uri) = iri_to_uri(&media.uri).parse::<Uri>() else {
+                report.add_loss(&format!("media/{}", key), "invalid URI");
+                continue;
+            };
+            let mut property = UriProperty::from(uri);
+            let path = format!("media/{}", key);
+            property.parameters = parameters(&path, &media.contexts, media.pref, &mut report);
+            if let Some(media_type) = &media.media_type {
+                let params = property.parameters.get_or_insert_with(Parameters::default);
+                params.media_type = Some(media_type.to_string());
+            }
+            match media.kind {
+                MediaKind::Photo => vcard.photo.push(TextOrUriProperty::Uri(property)),
+                MediaKind::Logo => vcard.logo.push(property),
+                MediaKind::Sound => vcard.sound.push(property),
+            }
+        }
+        for (key, anniversary) in sorted(&self.anniversaries) {
+            let path = format!("anniversaries/{}", key);
+            let target = match anniversary.kind {
+                AnniversaryKind::Birth if vcard.bday.is_none() => &mut vcard.bday,
+                AnniversaryKind::Wedding if vcard.anniversary.is_none() => &mut vcard.anniversary,
+                AnniversaryKind::Birth | AnniversaryKind::Wedding => {
+                    report.add_loss(&path, "vCard supports one BDAY and one ANNIVERSARY only");
+                    continue;
+                }
+                AnniversaryKind::Death => {
+                    report.add_loss(&path, "vCard has no death-date property");
+                    continue;
+                }
+                AnniversaryKind::Other(ref kind) => {
+                    report.add_loss(
+                        &path,
+                        &format!("vCard has no {} anniversary property", kind),
+                    );
+                    continue;
+                }
+            };
+            match date_to_vcard(&anniversary.date) {
+                Some(date) => *target = Some(date),
+                None => report.add_loss(&path, "vcard4 only supports complete dates"),
+            }
+            if anniversary.place.is_some() {
+                report.add_loss(&format!("{}/place", path), "vcard4 has no BIRTHPLACE");
+            }
+        }
+        for (key, link) in sorted(&self.links) {
+            match iri_to_uri(&link.uri).parse::<Uri>() {
+                Ok(uri) => {
+                    let mut property = UriProperty::from(uri);
+                    let path = format!("links/{}", key);
+                    property.parameters = parameters(&path, &link.contexts, link.pref, &mut report);
+                    vcard.url.push(property);
+                }
+                Err(_) => report.add_loss(&format!("links/{}", key), "invalid URI"),
+            }
+        }
+        for (key, note) in sorted(&self.notes) {
+            vcard.note.push(TextProperty::from(note.note.clone()));
+            if note.author.is_some() || note.created.is_some() {
+                report.add_loss(
+                    &format!("notes/{}", key),
+                    "vcard4 has no AUTHOR or CREATED parameters",
+                );
+            }
+        }
+        if let Some(keywords) = &self.keywords {
+            let mut values: Vec<String> = keywords
+                .iter()
+                .filter(|(_, enabled)| **enabled)
+                .map(|(keyword, _)| keyword.to_string())
+                .collect();
+            values.sort();
+            // an empty CATEGORIES would be an empty keyword
+            if !values.is_empty() {
+                vcard.categories.push(TextListProperty::new_comma(values));
+            }
+        }
+
+        let unsupported = [
+            ("created", self.created.is_some()),
+            ("language", self.language.is_some()),
+            ("members", self.members.is_some()),
+            ("relatedTo", self.related_to.is_some()),
+            ("speakToAs", self.speak_to_as.is_some()),
+            ("onlineServices", self.online_services.is_some()),
+            ("preferredLanguages", self.preferred_languages.is_some()),
+            ("calendars", self.calendars.is_some()),
+            ("schedulingAddresses", self.scheduling_addresses.is_some()),
+            ("cryptoKeys", self.crypto_keys.is_some()),
+            ("directories", self.directories.is_some()),
+            ("personalInfo", self.personal_info.is_some()),
+            ("localizations", self.get_raw_localizations().is_some()),
+        ];
+        for (path, is_set) in unsupported {
+            if is_set {
+                report.add_loss(path, "not supported by the vcard4 conversion");
+            }
+        }
+        (vcard, report)
+    }
+
+    /// Creates a Card from a [`vcard4::Vcard`].
+    /// The vCard properties without JSContact counterpart are listed in the returned [`ConversionReport`].
+    /// # Errors
+    /// Will return an error if the vCard has no UID.
+    pub fn from_vcard4(vcard: &Vcard) -> Result<(Card, ConversionReport), String> {
+        let mut report = ConversionReport::new();
+        let Some(uid) = &vcard.uid else {
+            return Err("vCard has no UID".into());
+        };
+        let uid = match uid {
+            TextOrUriProperty::Text(text) => text.value.clone(),
+            TextOrUriProperty::Uri(uri) => uri.value.to_string(),
+        };
+        let mut card = Card::new(CardVersion::OneDotZero, &uid);
+
+        card.kind = vcard.kind.as_ref().map(|kind| match kind.value {
+            Kind::Individual => CardKind::Individual,
+            Kind::Group => CardKind::Group,
+            Kind::Org => CardKind::Org,
+            Kind::Location => CardKind::Location,
+        });
+        card.prod_id = vcard.prod_id.as_ref().map(|prod_id| prod_id.value.clone());
+        card.updated = vcard
+            .rev
+            .as_ref()
+            .map(|rev| from_vcard_date_time(&rev.value));
+
+        let mut name = Name::default();
+        if let Some(formatted_name) = vcard.formatted_name.first() {
+            name.full = Some(formatted_name.value.clone());
+        }
+        if vcard.formatted_name.len() > 1 {
+            report.add_loss("FN", "only the first FN is kept");
+        }
+        if let Some(n) = &vcard.name {
+            name.components = Some(name_from_vcard(&n.value));
+        }
+        if name.full.is_some() || name.components.is_some() {
+            card.name = Some(name);
+        }
+
+        card.nicknames = keyed("k", &vcard.nickname, |nickname| Nickname {
+            nickname_type: Some(NicknameType::Nickname),
+            name: nickname.value.clone(),
+            contexts: contexts(&nickname.parameters),
+            pref: pref(&nickname.parameters).map(|pref| pref as u32),
+        });
+        card.emails = keyed("e", &vcard.email, |email| {
+            let mut email_address = EmailAddress::new(&email.value);
+            email_address.contexts = contexts(&email.parameters);
+            email_address.pref = pref(&email.parameters).map(|pref| pref as u32);
+            email_address
+        });
+        card.phones = keyed("tel", &vcard.tel, |tel| {
+            let (number, params) = match tel {
+                TextOrUriProperty::Text(text) => (text.value.clone(), &text.parameters),
+                TextOrUriProperty::Uri(uri) => (uri.value.to_string(), &uri.parameters),
+            };
+            let mut phone = Phone::new(&number);
+            phone.contexts = contexts(params);
+            phone.features = features(params);
+            phone.pref = pref(params).map(|pref| pref as u32);
+            phone
+        });
+        card.addresses = keyed("a", &vcard.address, address_from_vcard);
+        card.organizations = keyed("o", &vcard.org, |org| {
+            let mut values = org.value.iter();
+            let mut organization = Organization {
+                org_type: Some(crate::OrganizationType::Organization),
+                ..Default::default()
+            };
+            organization.name = values.next().filter(|name| !name.is_empty()).cloned();
+            let units: Vec<OrgUnit> = values.map(|unit| OrgUnit::new(unit)).collect();
+            if !units.is_empty() {
+                organization.units = Some(units);
+            }
+            organization.contexts = contexts(&org.parameters);
+            organization
+        });
+        let titles = vcard
+            .title
+            .iter()
+            .map(|title| (title, TitleKind::Title))
+            .chain(vcard.role.iter().map(|role| (role, TitleKind::Role)))
+            .collect::<Vec<_>>();
+        card.titles = keyed("t", &titles, |(property, kind)| {
+            let mut title = Title::new(&property.value);
+            title.kind = Some(kind.clone());
+            title
+        });
+
+        let mut media = Vec::new();
+        for photo in &vcard.photo {
+            match photo {
+                TextOrUriProperty::Uri(uri) => media.push((uri, MediaKind::Photo)),
+                TextOrUriProperty::Text(_) => report.add_loss("PHOTO", "PHOTO is not a URI"),
+            }
+        }
+        media.extend(vcard.logo.iter().map(|logo| (logo, MediaKind::Logo)));
+        media.extend(vcard.sound.iter().map(|sound| (sound, MediaKind::Sound)));
+        card.media = keyed("m", &media, |(property, kind)| {
+            let mut media = Media::new(&property.value.to_string(), kind.clone());
+            media.contexts = contexts(&property.parameters);
+            media.pref = pref(&property.parameters);
+            media.media_type = property
+                .parameters
+                .as_ref()
+                .and_then(|params| params.media_type.as_ref())
+                .map(|media_type| media_type.as_str().into());
+            media
+        });
+
+        let mut anniversaries = HashMap::new();
+        for (key, kind, property) in [
+            ("birth", AnniversaryKind::Birth, &vcard.bday),
+            ("wedding", AnniversaryKind::Wedding, &vcard.anniversary),
+        ] {
+            let Some(property) = property else {
+                continue;
+            };
+            match date_from_vcard(property) {
+                Some(date) => {
+                    anniversaries.insert(key.into(), Anniversary::new(kind, date));
+                }
+                None => report.add_loss(key, "only dates and date-times are supported"),
+            }
+        }
+        if !anniversaries.is_empty() {
+            card.anniversaries = Some(anniversaries);
+        }
+
+        card.links = keyed("l", &vcard.url, |url| {
+            let mut link = Link::new(&url.value.to_string());
+            link.contexts = contexts(&url.parameters);
+            link.pref = pref(&url.parameters);
+            link
+        });
+        card.notes = keyed("n", &vcard.note, |note| Note {
+            note_type: Some(NoteType::Note),
+            note: note.value.clone(),
+            created: None,
+            author: None,
+        });
+        let keywords: HashMap<SmallString, bool> = vcard
+            .categories
+            .iter()
+            .flat_map(|categories| categories.value.iter())
+            .map(|keyword| (keyword.as_str().into(), true))
+            .collect();
+        if !keywords.is_empty() {
+            card.keywords = Some(keywords);
+        }
+
+        let unsupported = [
+            ("SOURCE", vcard.source.is_empty()),
+            ("XML", vcard.xml.is_empty()),
+            ("GENDER", vcard.gender.is_none()),
+            ("IMPP", vcard.impp.is_empty()),
+            ("LANG", vcard.lang.is_empty()),
+            ("MEMBER", vcard.member.is_empty()),
+            ("RELATED", vcard.related.is_empty()),
+            ("TZ", vcard.timezone.is_empty()),
+            ("GEO", vcard.geo.is_empty()),
+            ("CLIENTPIDMAP", vcard.client_pid_map.is_empty()),
+            ("KEY", vcard.key.is_empty()),
+            ("FBURL", vcard.fburl.is_empty()),
+            ("CALADRURI", vcard.cal_adr_uri.is_empty()),
+            ("CALURI", vcard.cal_uri.is_empty()),
+        ];
+        for (property, is_empty) in unsupported {
+            if !is_empty {
+                report.add_loss(property, "not supported by the vcard4 conversion");
+            }
+        }
+        for extension in &vcard.extensions {
+            report.add_loss(&extension.name, "extension properties are not converted");
+        }
+        Ok((card, report))
+    }
+}
+
+impl From<&Card> for Vcard {
+    fn from(card: &Card) -> Self {
+        card.to_vcard4().0
+    }
+}
+
+impl TryFrom<&Vcard> for Card {
+    type Error = String;
+
+    fn try_from(vcard: &Vcard) -> Result<Self, Self::Error> {
+        Card::from_vcard4(vcard).map(|(card, _)| card)
+    }
+}
+
+/// Iterate over a map property sorted by key, to have a deterministic output
+fn sorted<V>(map: &Option<HashMap<SmallString, V>>) -> Vec<(&str, &V)> {
+    let mut entries: Vec<(&str, &V)> = map
+        .iter()
+        .flatten()
+        .map(|(key, value)| (key.as_str(), value))
+        .collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+/// Build a map property with generated keys (`prefix1`, `prefix2`, ...)
+fn keyed<T, V, F: Fn(&T) -> V>(
+    prefix: &str,
+    values: &[T],
+    f: F,
+) -> Option<HashMap<SmallString, V>> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(
+        values
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                let mut key = SmallString::from(prefix);
+                key.push_str(&(idx + 1).to_string());
+                (key, f(value))
+            })
+            .collect(),
+    )
+}
+
+/// The FN value: the full name, the name components or the uid as last resort
+fn formatted_name(card: &Card) -> String {
+    let Some(name) = &card.name else {
+        return card.uid.clone();
+    };
+    if let Some(full) = &name.full {
+        return full.clone();
+    }
+    let components = name
+        .components
+        .iter()
+        .flatten()
+        .filter(|component| component.kind != NameComponentKind::Separator)
+        .map(|component| component.value.as_str())
+        .collect::<Vec<_>>();
+    if components.is_empty() {
+        card.uid.clone()
+    } else {
+        components.join(" ")
+    }
+}
+
+/// Build a TextOrUriProperty, using the URI form when the value parses as an URI
+fn text_or_uri(value: &str) -> TextOrUriProperty {
+    match value.parse::<Uri>() {
+        Ok(uri) if value.contains(':') => TextOrUriProperty::Uri(UriProperty::from(uri)),
+        _ => TextOrUriProperty::Text(TextProperty::from(value.to_string())),
+    }
+}
+
+/// Build the TYPE and PREF parameters, see [`context_types`]
+fn parameters(
+    path: &str,
+    contexts: &Option<HashMap<Context, bool>>,
+    pref: Option<u64>,
+    report: &mut ConversionReport,
+) -> Option<Parameters> {
+    let types = context_types(path, contexts, report);
+    let pref = pref.and_then(vcard_map::pref_to_parameter);
+    if types.is_empty() && pref.is_none() {
+        return None;
+    }
+    let mut parameters = Parameters::default();
+    parameters.types = (!types.is_empty()).then_some(types);
+    parameters.pref = pref;
+    Some(parameters)
+}
+
+/// Build a TYPE parameter from its value
+fn type_parameter(value: &str) -> Option<TypeParameter> {
+    value.parse().ok()
+}
+
+/// Map the JSContact contexts to TYPE values, the vendor-specific contexts being reported as lost
+fn context_types(
+    path: &str,
+    contexts: &Option<HashMap<Context, bool>>,
+    report: &mut ConversionReport,
+) -> Vec<TypeParameter> {
+    let mut enabled: Vec<&Context> = contexts
+        .iter()
+        .flatten()
+        .filter(|(_, enabled)| **enabled)
+        .map(|(context, _)| context)
+        .collect();
+    enabled.sort();
+    let mut types = Vec::new();
+    for context in enabled {
+        match vcard_map::context_to_type(context).and_then(type_parameter) {
+            Some(type_param) => types.push(type_param),
+            None => {
+                let value = vcard_map::jscontact_value(context);
+                report.add_loss(
+                    &format!("{}/contexts/{}", path, value),
+                    &format!("vcard4 has no {} TYPE", value),
+                );
+            }
+        }
+    }
+    types.sort_by_key(|type_param| type_param.to_string());
+    types
+}
+
+/// Get the TYPE values of the parameters, without the `X-` prefix of the extensions
+///
+/// vcard4 keeps the unknown values (e.g. the legacy `pref`) as extensions too.
+fn type_values(parameters: &Option<Parameters>) -> Vec<String> {
+    parameters
+        .iter()
+        .flat_map(|params| params.types.iter().flatten())
+        .map(|type_param| match type_param {
+            TypeParameter::Extension(value) => value.clone(),
+            type_param => type_param.to_string(),
+        })
+        .collect()
+}
+
+/// Map the TYPE values to JSContact contexts
+fn contexts(parameters: &Option<Parameters>) -> Option<HashMap<Context, bool>> {
+    let contexts: HashMap<Context, bool> = type_values(parameters)
+        .iter()
+        .filter_map(|value| vcard_map::type_to_context(value))
+        .map(|context| (context, true))
+        .collect();
+    (!contexts.is_empty()).then_some(contexts)
+}
+
+/// Map the TYPE values of a TEL property to phone features
+fn features(parameters: &Option<Parameters>) -> Option<HashMap<PhoneFeature, bool>> {
+    let features: HashMap<PhoneFeature, bool> = type_values(parameters)
+        .iter()
+        .filter_map(|value| vcard_map::type_to_feature(value))
+        .map(|feature| (feature, true))
+        .collect();
+    (!features.is_empty()).then_some(features)
+}
+
+/// Get the preference from the PREF parameter or the legacy `TYPE=pref`
+fn pref(parameters: &Option<Parameters>) -> Option<u64> {
+    let types = type_values(parameters);
+    let types: Vec<&str> = types.iter().map(String::as_str).collect();
+    let pref = parameters.as_ref().and_then(|params| params.pref);
+    vcard_map::pref_from_vcard(pref.map(u64::from), &types)
+}
+
+/// Convert the name components to the N property
+fn name_to_vcard(name: &Name, report: &mut ConversionReport) -> Option<TextListProperty> {
+    let components = name.components.as_ref()?;
+    // family; given; additional; prefix; suffix
+    let mut values: [Vec<&str>; 5] = Default::default();
+    for (idx, component) in components.iter().enumerate() {
+        let position = match component.kind {
+            NameComponentKind::Surname | NameComponentKind::Surname2 => 0,
+            NameComponentKind::Given => 1,
+            NameComponentKind::Given2 => 2,
+            NameComponentKind::Title => 3,
+            NameComponentKind::Credential | NameComponentKind::Generation => 4,
+            NameComponentKind::Separator => continue,
+        };
+        if matches!(
+            component.kind,
+            NameComponentKind::Surname2 | NameComponentKind::Generation
+        ) {
+            report.add_warning(&format!(
+                "name/components/{} merged into a standard N component",
+                idx
+            ));
+        }
+        values[position].push(&component.value);
+    }
+    if name.phonetic_script.is_some() || name.phonetic_system.is_some() {
+        report.add_loss("name/phonetic", "vcard4 has no phonetic parameters");
+    }
+    if name.sort_as.is_some() {
+        report.add_loss("name/sortAs", "SORT-AS is not converted");
+    }
+    Some(TextListProperty::new_semi_colon(
+        values.iter().map(|value| value.join(",")).collect(),
+    ))
+}
+
+/// Convert the N property values to name components
+fn name_from_vcard(values: &[String]) -> Vec<NameComponent> {
+    let kinds = [
+        (3, NameComponentKind::Title),
+        (1, NameComponentKind::Given),
+        (2, NameComponentKind::Given2),
+        (0, NameComponentKind::Surname),
+        (4, NameComponentKind::Credential),
+    ];
+    let mut components = Vec::new();
+    for (position, kind) in kinds {
+        let Some(value) = values.get(position) else {
+            continue;
+        };
+        for part in value.split(',').filter(|part| !part.is_empty()) {
+            components.push(NameComponent::new(kind.clone(), part));
+        }
+    }
+    components
+}
+
+/// Convert a phone to the TEL property
+fn phone_to_vcard(key: &str, phone: &Phone, report: &mut ConversionReport) -> TextOrUriProperty {
+    let path = format!("phones/{}", key);
+    let mut params =
+        parameters(&path, &phone.contexts, phone.pref.map(u64::from), report).unwrap_or_default();
+    let mut features: Vec<&PhoneFeature> = phone
+        .features
+        .iter()
+        .flatten()
+        .filter(|(_, enabled)| **enabled)
+        .map(|(feature, _)| feature)
+        .collect();
+    features.sort_by_cached_key(|feature| vcard_map::jscontact_value(*feature));
+    for feature in features {
+        let Some(tel) = vcard_map::feature_to_type(feature).and_then(type_parameter) else {
+            let value = vcard_map::jscontact_value(feature);
+            report.add_loss(
+                &format!("{}/features/{}", path, value),
+                &format!("vcard4 has no {} TYPE", value),
+            );
+            continue;
+        };
+        params.types.get_or_insert_with(Vec::new).push(tel);
+    }
+    if let Some(types) = params.types.as_mut() {
+        types.sort_by_key(|type_param| type_param.to_string());
+    }
+    let params = (params != Parameters::default()).then_some(params);
+    let mut tel = text_or_uri(&phone.number);
+    match &mut tel {
+        TextOrUriProperty::Uri(uri) => uri.parameters = params,
+        TextOrUriProperty::Text(text) => text.parameters = params,
+    }
+    tel
+}
+
+/// Convert an address to the ADR property
+fn address_to_vcard(
+    key: &str,
+    address: &Address,
+    report: &mut ConversionReport,
+) -> AddressProperty {
+    let mut extended = Vec::new();
+    let mut street = Vec::new();
+    let mut value = DeliveryAddress::default();
+    for (idx, component) in address.components.iter().flatten().enumerate() {
+        let component_value = component.value.clone();
+        match component.kind {
+            AddressComponentKind::PostOfficeBox => value.po_box = Some(component_value),
+            AddressComponentKind::Apartment
+            | AddressComponentKind::Room
+            | AddressComponentKind::Floor
+            | AddressComponentKind::Building => extended.push(component_value),
+            AddressComponentKind::Number
+            | AddressComponentKind::Name
+            | AddressComponentKind::Direction => street.push(component_value),
+            AddressComponentKind::Locality => value.locality = Some(component_value),
+            AddressComponentKind::Region => value.region = Some(component_value),
+            AddressComponentKind::Postcode => value.postal_code = Some(component_value),
+            AddressComponentKind::Country => value.country_name = Some(component_value),
+            AddressComponentKind::Separator => {}
+            AddressComponentKind::Block
+            | AddressComponentKind::District
+            | AddressComponentKind::Subdistrict
+            | AddressComponentKind::Landmark => report.add_loss(
+                &format!("addresses/{}/components/{}", key, idx),
+                "no ADR component for this kind",
+            ),
+        }
+    }
+    if !extended.is_empty() {
+        value.extended_address = Some(extended.join(" "));
+    }
+    if !street.is_empty() {
+        value.street_address = Some(street.join(" "));
+    }
+    let path = format!("addresses/{}", key);
+    let mut params = parameters(&path, &None, address.pref, report).unwrap_or_default();
+    if let Some(contexts) = &address.contexts {
+        for (context, enabled) in contexts {
+            if !enabled {
+                continue;
+            }
+            match vcard_map::address_context_to_type(context).and_then(type_parameter) {
+                Some(type_param) => params.types.get_or_insert_with(Vec::new).push(type_param),
+                None => report.add_loss(
+                    &format!("addresses/{}/contexts", key),
+                    "vCard has no billing or delivery TYPE",
+                ),
+            }
+        }
+    }
+    if let Some(types) = params.types.as_mut() {
+        types.sort_by_key(|type_param| type_param.to_string());
+    }
+    params.label = address.full.clone();
+    if let Some(coordinates) = &address.coordinates {
+        match coordinates.parse::<Uri>() {
+            Ok(geo) => params.geo = Some(geo),
+            Err(_) => report.add_loss(&format!("addresses/{}/coordinates", key), "invalid URI"),
+        }
+    }
+    for (path, is_set) in [
+        ("countryCode", address.country_code.is_some()),
+        ("timeZone", address.time_zone.is_some()),
+    ] {
+        if is_set {
+            report.add_loss(
+                &format!("addresses/{}/{}", key, path),
+                "not supported by the vcard4 conversion",
+            );
+        }
+    }
+    AddressProperty {
+        group: None,
+        value,
+        parameters: (params != Parameters::default()).then_some(params),
+    }
+}
+
+/// Convert an ADR property to an address
+fn address_from_vcard(property: &AddressProperty) -> Address {
+    let value = &property.value;
+    let mut components = Vec::new();
+    for (kind, part) in [
+        (AddressComponentKind::PostOfficeBox, &value.po_box),
+        (AddressComponentKind::Apartment, &value.extended_address),
+        (AddressComponentKind::Name, &value.street_address),
+        (AddressComponentKind::Locality, &value.locality),
+        (AddressComponentKind::Region, &value.region),
+        (AddressComponentKind::Postcode, &value.postal_code),
+        (AddressComponentKind::Country, &value.country_name),
+    ] {
+        if let Some(part) = part {
+            components.push(AddressComponent::new(kind, part));
+        }
+    }
+    let mut address = Address {
+        address_type: Some(crate::AddressType::Address),
+        ..Default::default()
+    };
+    if !components.is_empty() {
+        address.components = Some(components);
+    }
+    let params = property.parameters.as_ref();
+    address.full = params.and_then(|params| params.label.clone());
+    address.coordinates = params
+        .and_then(|params| params.geo.as_ref())
+        .map(|geo| geo.to_string());
+    address.pref = pref(&property.parameters);
+    let contexts: HashMap<crate::AddressContext, bool> = type_values(&property.parameters)
+        .iter()
+        .filter_map(|value| vcard_map::type_to_address_context(value))
+        .map(|context| (context, true))
+        .collect();
+    address.contexts = (!contexts.is_empty()).then_some(contexts);
+    address
+}
+
+/// Convert a JSContact UTCDateTime (`2019-10-15T23:10:00Z`) to a vCard date-time
+fn to_vcard_date_time(utc: &str) -> Option<DateTime> {
+    let compact: String = utc.chars().filter(|c| *c != '-' && *c != ':').collect();
+    helper::parse_date_time(&compact).ok()
+}
+
+/// Convert a vCard date-time to a JSContact UTCDateTime
+fn from_vcard_date_time(date_time: &DateTime) -> String {
+    let utc = date_time.as_ref().to_offset(UtcOffset::UTC);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        utc.year(),
+        u8::from(utc.month()),
+        utc.day(),
+        utc.hour(),
+        utc.minute(),
+        utc.second()
+    )
+}
+
+/// Convert an anniversary date to the BDAY/ANNIVERSARY value
+fn date_to_vcard(date: &DateObject) -> Option<DateTimeOrTextProperty> {
+    match date {
+        DateObject::Timestamp(timestamp) => to_vcard_date_time(&timestamp.utc).map(Into::into),
+        DateObject::PartialDate(partial) => {
+            let (Some(year), Some(month), Some(day)) = (partial.year, partial.month, partial.day)
+            else {
+                return None;
+            };
+            let month = Month::try_from(u8::try_from(month).ok()?).ok()?;
+            let date = vcard4::time::Date::from_calendar_date(
+                i32::try_from(year).ok()?,
+                month,
+                u8::try_from(day).ok()?,
+            )
+            .ok()?;
+            Some(Date::from(date).into())
+        }
+    }
+}
+
+/// Convert the BDAY/ANNIVERSARY value to an anniversary date
+fn date_from_vcard(property: &DateTimeOrTextProperty) -> Option<DateObject> {
+    let DateTimeOrTextProperty::DateTime(date_time) = property else {
+        return None;
+    };
+    match date_time.value.first()? {
+        DateAndOrTime::Date(date) => {
+            let date = date.as_ref();
+            Some(DateObject::PartialDate(PartialDate {
+                year: u64::try_from(date.year()).ok(),
+                month: Some(u32::from(u8::from(date.month()))),
+                day: Some(u32::from(date.day())),
+                ..Default::default()
+            }))
+        }
+        DateAndOrTime::DateTime(date_time) => Some(DateObject::Timestamp(Timestamp::new(
+            &from_vcard_date_time(date_time),
+        ))),
+        DateAndOrTime::Time(_) => None,
+    }
+}
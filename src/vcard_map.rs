@@ -0,0 +1,112 @@
+//! The mapping between the JSContact contexts and phone features and the vCard TYPE parameter values (RFC 9555).
+//!
+//! It does not depend on a vCard library, so applications doing their own vCard handling map the values
+//! the same way as the `vcard4-interop` conversions.
+//!
+//! - the `private` context is the `home` TYPE, the `work` context the `work` TYPE
+//! - the `mobile` feature is the `cell` TYPE, the other features have the same name as their TYPE
+//! - the `main-number` feature and the `billing` and `delivery` address contexts have no TYPE, they are lost in vCard
+//! - the preference is the PREF parameter, the legacy `TYPE=pref` of vCard 3 being read as the most preferred
+//!
+//! The TYPE values are compared case-insensitively, as in vCard.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{AddressContext, Context, PhoneFeature};
+
+/// The TYPE values of the contexts, the mapping is bijective.
+pub const CONTEXT_TYPES: [(Context, &str); 2] =
+    [(Context::Private, "home"), (Context::Work, "work")];
+
+/// The TYPE values of the phone features, `main-number` has no TYPE.
+pub const FEATURE_TYPES: [(PhoneFeature, &str); 7] = [
+    (PhoneFeature::Fax, "fax"),
+    (PhoneFeature::Mobile, "cell"),
+    (PhoneFeature::Pager, "pager"),
+    (PhoneFeature::Text, "text"),
+    (PhoneFeature::Textphone, "textphone"),
+    (PhoneFeature::Video, "video"),
+    (PhoneFeature::Voice, "voice"),
+];
+
+/// The legacy TYPE value of the preferred property (vCard 3), replaced by the PREF parameter in vCard 4.
+pub const PREF_TYPE: &str = "pref";
+
+/// Get the TYPE value of a context, e.g. `home` for `private`, `None` for a vendor-specific context.
+pub fn context_to_type(context: &Context) -> Option<&'static str> {
+    match context {
+        Context::Private => Some("home"),
+        Context::Work => Some("work"),
+        Context::Other(_) => None,
+    }
+}
+
+/// Get the context of a TYPE value, e.g. `private` for `HOME`, `None` if the TYPE is not a context.
+pub fn type_to_context(value: &str) -> Option<Context> {
+    CONTEXT_TYPES
+        .iter()
+        .find(|(_, mapped)| mapped.eq_ignore_ascii_case(value))
+        .map(|(context, _)| context.clone())
+}
+
+/// Get the TYPE value of an address context, `None` for the `billing` and `delivery` contexts.
+pub fn address_context_to_type(context: &AddressContext) -> Option<&'static str> {
+    match context {
+        AddressContext::Private => context_to_type(&Context::Private),
+        AddressContext::Work => context_to_type(&Context::Work),
+        AddressContext::Billing | AddressContext::Delivery => None,
+    }
+}
+
+/// Get the address context of a TYPE value, `None` if the TYPE is not a context.
+pub fn type_to_address_context(value: &str) -> Option<AddressContext> {
+    type_to_context(value).and_then(|context| match context {
+        Context::Private => Some(AddressContext::Private),
+        Context::Work => Some(AddressContext::Work),
+        Context::Other(_) => None,
+    })
+}
+
+/// Get the TYPE value of a phone feature, e.g. `cell` for `mobile`, `None` for `main-number`.
+pub fn feature_to_type(feature: &PhoneFeature) -> Option<&'static str> {
+    FEATURE_TYPES
+        .iter()
+        .find(|(mapped, _)| mapped == feature)
+        .map(|(_, value)| *value)
+}
+
+/// Get the phone feature of a TYPE value, `None` if the TYPE is not a feature.
+pub fn type_to_feature(value: &str) -> Option<PhoneFeature> {
+    FEATURE_TYPES
+        .iter()
+        .find(|(_, mapped)| mapped.eq_ignore_ascii_case(value))
+        .map(|(feature, _)| feature.clone())
+}
+
+/// Get the PREF parameter of a preference, `None` if it is not between 1 and 100.
+pub fn pref_to_parameter(pref: u64) -> Option<u8> {
+    u8::try_from(pref)
+        .ok()
+        .filter(|pref| (1..=100).contains(pref))
+}
+
+/// Get the preference of a vCard property from its PREF parameter and its TYPE values.
+///
+/// The PREF parameter wins, a property without it having the legacy `TYPE=pref` is the most preferred (1).
+pub fn pref_from_vcard(pref: Option<u64>, types: &[&str]) -> Option<u64> {
+    pref.filter(|pref| (1..=100).contains(pref)).or_else(|| {
+        types
+            .iter()
+            .any(|value| value.eq_ignore_ascii_case(PREF_TYPE))
+            .then_some(1)
+    })
+}
+
+/// Get the JSContact value of a context or a feature, e.g. `main-number`, to report it when it has no TYPE
+pub(crate) fn jscontact_value<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(Value::String(value)) => value,
+        _ => String::new(),
+    }
+}
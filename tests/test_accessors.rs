@@ -0,0 +1,124 @@
+mod test {
+    use jscontact::Card;
+
+    #[test]
+    fn test_accessors_empty() {
+        let mut card = Card::new_with_latest_version("1234");
+        assert_eq!(card.full_name(), None);
+        assert_eq!(card.given_name(), None);
+        assert_eq!(card.surname(), None);
+        assert_eq!(card.primary_org_name(), None);
+        assert_eq!(card.job_title_name(), None);
+        assert_eq!(card.first_email(), None);
+        assert_eq!(card.first_phone_number(), None);
+
+        assert!(card.given_name_mut().is_none());
+        assert!(card.surname_mut().is_none());
+        assert!(card.primary_org_name_mut().is_none());
+        assert!(card.job_title_name_mut().is_none());
+        assert!(card.first_email_mut().is_none());
+        assert!(card.first_phone_number_mut().is_none());
+        // borrowing creates nothing
+        assert!(card.full_name_mut().is_none());
+        assert!(card.name.is_none());
+    }
+
+    #[test]
+    fn test_accessors_populated() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "name": {
+                "full": "Jane Doe",
+                "components": [
+                    { "kind": "title", "value": "Dr." },
+                    { "kind": "given", "value": "Jane" },
+                    { "kind": "given", "value": "Mary" },
+                    { "kind": "surname", "value": "Doe" }
+                ]
+            },
+            "emails": {
+                "e1": { "address": "jane@example.com" },
+                "e2": { "address": "jane.doe@example.org", "pref": 1 }
+            },
+            "phones": {
+                "p2": { "number": "tel:+1-555-555-5556" },
+                "p1": { "number": "tel:+1-555-555-5555" }
+            },
+            "organizations": {
+                "o2": { "name": "XYZ Corp." },
+                "o1": { "name": "ABC, Inc." }
+            },
+            "titles": {
+                "t1": { "name": "Project Leader", "kind": "role" },
+                "t2": { "name": "Research Scientist" }
+            }
+        });
+        let card: Card = serde_json::from_value(json)?;
+        assert_eq!(card.full_name(), Some("Jane Doe"));
+        assert_eq!(card.given_name(), Some("Jane"));
+        assert_eq!(card.surname(), Some("Doe"));
+        assert_eq!(card.primary_org_name(), Some("ABC, Inc."));
+        assert_eq!(card.job_title_name(), Some("Research Scientist"));
+        assert_eq!(card.first_email(), Some("jane.doe@example.org"));
+        assert_eq!(card.first_phone_number(), Some("tel:+1-555-555-5555"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_accessors_mut() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "name": {
+                "full": "Jane Doe",
+                "components": [
+                    { "kind": "given", "value": "Jane" },
+                    { "kind": "surname", "value": "Doe" }
+                ]
+            },
+            "emails": {
+                "e1": { "address": "jane@example.com" },
+                "e2": { "address": "jane.doe@example.org", "pref": 1 }
+            },
+            "phones": {
+                "p1": { "number": "tel:+1-555-555-5555" }
+            },
+            "organizations": {
+                "o2": { "name": "XYZ Corp." },
+                "o1": { "name": "ABC, Inc." }
+            },
+            "titles": {
+                "t1": { "name": "Project Leader", "kind": "role" },
+                "t2": { "name": "Research Scientist" }
+            }
+        });
+        let mut card: Card = serde_json::from_value(json)?;
+        *card.given_name_mut().unwrap() = "Janet".to_string();
+        card.surname_mut().unwrap().push_str("-Smith");
+        *card.primary_org_name_mut().unwrap() = Some("ABC, Ltd.".to_string());
+        *card.job_title_name_mut().unwrap() = "Lead Scientist".to_string();
+        *card.full_name_mut().unwrap() = Some("Janet Doe-Smith".to_string());
+        *card.first_email_mut().unwrap() = "janet@example.org".to_string();
+        *card.first_phone_number_mut().unwrap() = "tel:+1-555-555-0000".to_string();
+
+        assert_eq!(card.full_name(), Some("Janet Doe-Smith"));
+        assert_eq!(card.given_name(), Some("Janet"));
+        assert_eq!(card.surname(), Some("Doe-Smith"));
+        assert_eq!(card.primary_org_name(), Some("ABC, Ltd."));
+        assert_eq!(card.job_title_name(), Some("Lead Scientist"));
+        assert_eq!(card.first_email(), Some("janet@example.org"));
+        assert_eq!(card.first_phone_number(), Some("tel:+1-555-555-0000"));
+        // the other entries are left as they were
+        let emails = card.emails.as_ref().unwrap();
+        assert_eq!(emails["e1"].address, "jane@example.com");
+        assert_eq!(card.titles.as_ref().unwrap()["t1"].name, "Project Leader");
+        assert_eq!(
+            card.organizations.as_ref().unwrap()["o2"].name.as_deref(),
+            Some("XYZ Corp.")
+        );
+        Ok(())
+    }
+}
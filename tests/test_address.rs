@@ -0,0 +1,135 @@
+mod test {
+    use jscontact::{Address, AddressComponent, AddressComponentKind, Card};
+
+    fn get_address(json: &[u8], key: &str) -> Address {
+        let card: Card = serde_json::from_slice(json).unwrap();
+        card.addresses.unwrap().remove(key).unwrap()
+    }
+
+    #[test]
+    fn test_address_getters_figure_31() {
+        let k23 = get_address(include_bytes!("./rfc9553/figure_31.json"), "k23");
+        assert_eq!(k23.street_number(), Some("54321"));
+        assert_eq!(k23.street_name(), Some("Oak St"));
+        assert_eq!(k23.locality(), Some("Reston"));
+        assert_eq!(k23.region(), Some("VA"));
+        assert_eq!(k23.postcode(), Some("20190"));
+        assert_eq!(k23.country_name(), Some("USA"));
+        assert_eq!(k23.post_office_box(), None);
+        assert_eq!(k23.district(), None);
+        assert_eq!(
+            k23.components_of_kind(AddressComponentKind::Separator)
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_address_getters_figure_32() {
+        let k25 = get_address(include_bytes!("./rfc9553/figure_32.json"), "k25");
+        assert_eq!(k25.street_number(), Some("46"));
+        assert_eq!(k25.street_name(), Some("1 Sukhumvit 51 Alley"));
+        assert_eq!(k25.subdistrict(), Some("Khlong Tan Nuea"));
+        assert_eq!(k25.district(), Some(" Watthana"));
+        assert_eq!(k25.locality(), Some("Bangkok"));
+        assert_eq!(k25.country_name(), Some("Thailand"));
+        assert_eq!(k25.postcode(), Some("10110"));
+        assert_eq!(k25.region(), None);
+    }
+
+    #[test]
+    fn test_address_getters_figure_33() {
+        let k26 = get_address(include_bytes!("./rfc9553/figure_33.json"), "k26");
+        assert_eq!(k26.block(), Some("2-7"));
+        assert_eq!(k26.street_number(), Some("2"));
+        assert_eq!(k26.district(), Some("Marunouchi"));
+        assert_eq!(k26.locality(), Some("Chiyoda-ku"));
+        assert_eq!(k26.region(), Some("Tokyo"));
+        assert_eq!(k26.postcode(), Some("100-8994"));
+        assert_eq!(k26.country_name(), None);
+        assert_eq!(k26.component(AddressComponentKind::Separator), Some("-"));
+    }
+
+    #[test]
+    fn test_address_setters() {
+        let mut k23 = get_address(include_bytes!("./rfc9553/figure_31.json"), "k23");
+        k23.set_locality("Herndon");
+        k23.set_post_office_box("PO 123");
+        let components = k23.components.as_ref().unwrap();
+        assert_eq!(components.len(), 9);
+        assert_eq!(components[3].kind, AddressComponentKind::Locality);
+        assert_eq!(components[3].value, "Herndon");
+        assert_eq!(components[8].kind, AddressComponentKind::PostOfficeBox);
+        assert_eq!(k23.post_office_box(), Some("PO 123"));
+
+        let mut address = Address::default();
+        address.set_country_name("France");
+        assert_eq!(address.country_name(), Some("France"));
+        assert_eq!(address.components.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_address_from_simple() {
+        let k23 = get_address(include_bytes!("./rfc9553/figure_31.json"), "k23");
+        let address = Address::from_simple("Oak St", "Reston", Some("VA"), "20190", "US");
+        // same shape as figure 31, without the street number and the country name
+        let kinds = |address: &Address| -> Vec<AddressComponentKind> {
+            address
+                .components
+                .iter()
+                .flatten()
+                .map(|component| component.kind.clone())
+                .filter(|kind| {
+                    !matches!(
+                        kind,
+                        AddressComponentKind::Number | AddressComponentKind::Country
+                    )
+                })
+                .collect()
+        };
+        // the first separator of figure 31 follows the street number
+        assert_eq!(kinds(&address)[..], kinds(&k23)[1..]);
+        assert_eq!(address.is_ordered, k23.is_ordered);
+        assert_eq!(address.default_separator, k23.default_separator);
+        assert_eq!(address.country_code, k23.country_code);
+        assert_eq!(address.postcode(), Some("20190"));
+        assert_eq!(
+            address.component(AddressComponentKind::Separator),
+            Some(" ")
+        );
+
+        let json = serde_json::to_value(&address).unwrap();
+        assert_eq!(json["components"][0]["kind"], "name");
+        assert_eq!(json["countryCode"], "US");
+        assert_eq!(json["isOrdered"], true);
+
+        let address = Address::from_simple("Oak St", "", None, "20190", "");
+        assert_eq!(
+            address.components.unwrap(),
+            vec![
+                AddressComponent::name("Oak St"),
+                AddressComponent::postcode("20190")
+            ]
+        );
+        assert_eq!(address.country_code, None);
+        let address = Address::from_simple("", "Reston", Some("VA"), "", "US");
+        assert_eq!(
+            address.components.unwrap(),
+            vec![
+                AddressComponent::locality("Reston"),
+                AddressComponent::region("VA")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_address_component_constructors() {
+        let component = AddressComponent::post_office_box("PO 123");
+        assert_eq!(component.kind, AddressComponentKind::PostOfficeBox);
+        assert_eq!(component.value, "PO 123");
+        assert_eq!(
+            AddressComponent::locality("Reston"),
+            AddressComponent::new(AddressComponentKind::Locality, "Reston")
+        );
+    }
+}
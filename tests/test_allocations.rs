@@ -0,0 +1,95 @@
+// Allocation counting is done per thread so that tests running in parallel
+// do not pollute each other's numbers.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn count_allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+mod test {
+    use super::count_allocations;
+    use jscontact::{Card, CardVersion, Name};
+    use serde_json::Value;
+    use std::collections::HashMap;
+
+    fn build_cards(count: usize) -> Vec<Card> {
+        (0..count)
+            .map(|i| {
+                let mut card = Card::new(CardVersion::OneDotZero, &format!("uid:{}", i));
+                let mut name = Name::default();
+                name.full = Some(format!("John {}", i));
+                let mut translations: HashMap<String, Value> = HashMap::new();
+                translations.insert("name".to_string(), serde_json::to_value(&name).unwrap());
+                card.add_localization("en", translations.clone());
+                card.add_localization("fr", translations);
+                card
+            })
+            .collect()
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_languages_allocations() {
+        let cards = build_cards(10_000);
+
+        let mut found_vec = 0;
+        let vec_allocations = count_allocations(|| {
+            for card in &cards {
+                if card
+                    .get_available_languages_vec()
+                    .contains(&"fr".to_string())
+                {
+                    found_vec += 1;
+                }
+            }
+        });
+
+        let mut found_iter = 0;
+        let iter_allocations = count_allocations(|| {
+            for card in &cards {
+                if card.get_available_languages().any(|lang| lang == "fr") {
+                    found_iter += 1;
+                }
+            }
+        });
+
+        let mut found_has = 0;
+        let has_allocations = count_allocations(|| {
+            for card in &cards {
+                if card.has_localization("fr") {
+                    found_has += 1;
+                }
+            }
+        });
+
+        assert_eq!(found_vec, 10_000);
+        assert_eq!(found_iter, 10_000);
+        assert_eq!(found_has, 10_000);
+        assert!(vec_allocations >= 10_000 * 3);
+        assert_eq!(iter_allocations, 0);
+        assert_eq!(has_allocations, 0);
+    }
+}
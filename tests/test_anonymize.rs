@@ -0,0 +1,219 @@
+mod test {
+    use jscontact::{Card, SmallString};
+    use serde_json::{json, Value};
+
+    /// The maps whose keys are replaced by the anonymization
+    const REKEYED_MAPS: [&str; 3] = ["keywords", "members", "relatedTo"];
+
+    fn sample_card() -> Card {
+        let json = json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "kind": "group",
+            "members": {
+                "urn:uuid:b8767877-b4a1-4c70-9acc-505d3819e519": true
+            },
+            "relatedTo": {
+                "urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6": {
+                    "relation": { "friend": true }
+                }
+            },
+            "name": {
+                "components": [
+                    { "kind": "given", "value": "Johnathan" },
+                    { "kind": "surname", "value": "Smithson" }
+                ],
+                "full": "Johnathan Smithson",
+                "sortAs": { "surname": "Smithson" }
+            },
+            "emails": {
+                "e1": { "address": "johnathan@private.test", "contexts": { "work": true } },
+                "e2": { "address": "smithson@private.test" }
+            },
+            "phones": {
+                "tel0": { "number": "tel:+33-1-23-45-67-89", "features": { "mobile": true } }
+            },
+            "addresses": {
+                "k1": {
+                    "components": [
+                        { "kind": "number", "value": "54321" },
+                        { "kind": "separator", "value": " " },
+                        { "kind": "name", "value": "Oakwood Street" }
+                    ],
+                    "countryCode": "US",
+                    "coordinates": "geo:38.9582,-77.3578",
+                    "full": "54321 Oakwood Street"
+                }
+            },
+            "anniversaries": {
+                "k8": { "kind": "birth", "date": { "year": 1953, "month": 4, "day": 15 } }
+            },
+            "links": {
+                "l1": { "uri": "https://private.test/johnathan" }
+            },
+            "notes": {
+                "n1": { "note": "Met at the conference in Lisbon" }
+            },
+            "keywords": { "secret-project": true },
+            "localizations": {
+                "fr": {
+                    "name/components/1/value": "Smithsonne",
+                    "emails/e2/address": "smithson@prive.test",
+                    "keywords/secret-project": false,
+                    "notes/n1": { "note": "Rencontré à la conférence de Lisbonne" }
+                }
+            }
+        });
+        Card::try_from(json).unwrap()
+    }
+
+    /// Check that two values have the same structure, the keys of the rekeyed maps excepted
+    fn assert_same_structure(original: &Value, anonymized: &Value, path: &str) {
+        match (original, anonymized) {
+            (Value::Object(original), Value::Object(anonymized)) => {
+                assert_eq!(original.len(), anonymized.len(), "{}", path);
+                let rekeyed = REKEYED_MAPS.iter().any(|map| path.ends_with(map));
+                if rekeyed || path.ends_with("localizations") {
+                    return;
+                }
+                for (key, value) in original {
+                    let other = anonymized
+                        .get(key)
+                        .unwrap_or_else(|| panic!("{}/{}", path, key));
+                    assert_same_structure(value, other, &format!("{}/{}", path, key));
+                    if key == "kind" {
+                        assert_eq!(value, other, "{}/{}", path, key);
+                    }
+                }
+            }
+            (Value::Array(original), Value::Array(anonymized)) => {
+                assert_eq!(original.len(), anonymized.len(), "{}", path);
+                for (idx, (value, other)) in original.iter().zip(anonymized).enumerate() {
+                    assert_same_structure(value, other, &format!("{}/{}", path, idx));
+                }
+            }
+            (original, anonymized) => {
+                assert_eq!(
+                    std::mem::discriminant(original),
+                    std::mem::discriminant(anonymized),
+                    "{}",
+                    path
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_anonymize_removes_personal_data() {
+        let card = sample_card();
+        let anonymized = card.anonymize(42);
+        let json = serde_json::to_string(&anonymized).unwrap();
+        for personal in [
+            "03a0e51f",
+            "b8767877",
+            "f81d4fae",
+            "Johnathan",
+            "Smithson",
+            "private.test",
+            "prive.test",
+            "23-45-67",
+            "54321",
+            "Oakwood",
+            "38.9582",
+            "1953",
+            "Lisbon",
+            "secret-project",
+        ] {
+            assert!(!json.contains(personal), "{} found in {}", personal, json);
+        }
+
+        let name = anonymized.name.as_ref().unwrap();
+        let components = name.components.as_ref().unwrap();
+        let number = components[0].value.strip_prefix("Given").unwrap();
+        assert_eq!(components[1].value, format!("Surname{}", number));
+        assert_eq!(
+            name.full.as_deref(),
+            Some(format!("Given{} Surname{}", number, number).as_str())
+        );
+        let email = &anonymized.emails.as_ref().unwrap()[&SmallString::from("e1")];
+        assert!(email.address.starts_with("user"));
+        assert!(email.address.ends_with("@example.com"));
+        let phone = &anonymized.phones.as_ref().unwrap()[&SmallString::from("tel0")];
+        assert!(phone.number.starts_with("tel:+1-555-01"));
+        assert_eq!(phone.number.len(), "tel:+1-555-0100".len());
+        let link = &anonymized.links.as_ref().unwrap()[&SmallString::from("l1")];
+        assert!(link.uri.starts_with("https://example.com/r"));
+        let note = &anonymized.notes.as_ref().unwrap()[&SmallString::from("n1")];
+        assert_eq!(note.note, "REDACTED");
+        let address = &anonymized.addresses.as_ref().unwrap()[&SmallString::from("k1")];
+        assert_eq!(
+            address.component(jscontact::AddressComponentKind::Separator),
+            Some(" ")
+        );
+        assert_eq!(address.country_code.as_deref(), Some("US"));
+    }
+
+    #[test]
+    fn test_anonymize_keeps_structure() {
+        let mut cards = vec![sample_card()];
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/rfc9553");
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                // some figures are fragments of a Card
+                if let Ok(card) = Card::try_from(std::fs::read(&path).unwrap().as_slice()) {
+                    cards.push(card);
+                }
+            }
+        }
+        assert!(cards.len() > 20);
+        for card in cards {
+            let anonymized = card.anonymize(7);
+            assert_eq!(anonymized, card.anonymize(7));
+            assert_eq!(card.validate().is_valid(), anonymized.validate().is_valid());
+            assert_eq!(
+                card.get_available_languages().count(),
+                anonymized.get_available_languages().count()
+            );
+            assert_same_structure(
+                &serde_json::to_value(&card).unwrap(),
+                &serde_json::to_value(&anonymized).unwrap(),
+                "",
+            );
+        }
+    }
+
+    #[test]
+    fn test_anonymize_seed() {
+        let card = sample_card();
+        assert_eq!(card.anonymize(1), card.anonymize(1));
+        assert_ne!(card.anonymize(1), card.anonymize(2));
+        assert_eq!(card.anonymize(1).uid, card.anonymize(1).uid);
+        assert_ne!(card.anonymize(1).uid, card.anonymize(2).uid);
+    }
+
+    #[test]
+    fn test_anonymize_localizations() {
+        let anonymized = sample_card().anonymize(42);
+        let base_name = anonymized.name.clone().unwrap();
+        let base_email = anonymized.emails.as_ref().unwrap()[&SmallString::from("e2")].clone();
+        let keyword = anonymized
+            .keywords
+            .as_ref()
+            .unwrap()
+            .keys()
+            .next()
+            .unwrap()
+            .clone();
+
+        // the patches are scrubbed like the values they replace
+        let localized = anonymized.get_localized("fr").unwrap();
+        assert_eq!(localized.name.unwrap(), base_name);
+        let email = &localized.emails.as_ref().unwrap()[&SmallString::from("e2")];
+        assert_eq!(email.address, base_email.address);
+        assert_eq!(localized.keywords.unwrap().get(&keyword), Some(&false));
+        let note = &localized.notes.as_ref().unwrap()[&SmallString::from("n1")];
+        assert_eq!(note.note, "REDACTED");
+    }
+}
@@ -0,0 +1,240 @@
+// The public API is extracted from the sources, so the snapshot is the same
+// whatever features are enabled: items behind a feature are listed with their `cfg`.
+use std::path::Path;
+
+/// Environment variable used to write the snapshot instead of comparing it
+const UPDATE_VAR: &str = "UPDATE_API_SNAPSHOT";
+
+/// Get the source files of the crate with the `cfg` of their `mod` declaration
+fn source_files(src: &Path) -> Vec<(String, Option<String>)> {
+    let lib = std::fs::read_to_string(src.join("lib.rs")).unwrap();
+    let mut files = vec![("lib.rs".to_string(), None)];
+    let mut cfg = None;
+    for line in lib.lines() {
+        if line.starts_with("#[cfg(") {
+            cfg = Some(line.to_string());
+            continue;
+        }
+        let module = line
+            .strip_prefix("pub mod ")
+            .or_else(|| line.strip_prefix("mod "))
+            .and_then(|module| module.strip_suffix(';'));
+        if let Some(module) = module {
+            files.push((format!("{}.rs", module), cfg.take()));
+        } else if !line.starts_with("#[") {
+            cfg = None;
+        }
+    }
+    // the submodules of the modules, e.g. `validate/syntax.rs`
+    let mut idx = 1;
+    while idx < files.len() {
+        let (file, cfg) = files[idx].clone();
+        let source = std::fs::read_to_string(src.join(&file)).unwrap();
+        let parent = file.trim_end_matches(".rs");
+        for line in source.lines() {
+            let module = line
+                .strip_prefix("pub mod ")
+                .or_else(|| line.strip_prefix("mod "))
+                .and_then(|module| module.strip_suffix(';'));
+            if let Some(module) = module {
+                files.push((format!("{}/{}.rs", parent, module), cfg.clone()));
+            }
+        }
+        idx += 1;
+    }
+    files
+}
+
+/// Prefix an item with its `cfg` and `deprecated` attributes
+fn annotate(attributes: &[String], item: &str) -> String {
+    if attributes.is_empty() {
+        item.to_string()
+    } else {
+        format!("{} {}", attributes.join(" "), item)
+    }
+}
+
+/// Collect the public items of a source file, one line per item
+fn public_items(source: &str, file_cfg: &Option<String>) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut attributes: Vec<String> = file_cfg.iter().cloned().collect();
+    let mut container: Option<String> = None;
+    let mut container_attributes: Vec<String> = Vec::new();
+    let mut signature: Option<String> = None;
+    for line in source.lines() {
+        if let Some(sig) = signature.as_mut() {
+            sig.push(' ');
+            sig.push_str(line.trim());
+            if line.trim_end().ends_with('{') || line.trim_end().ends_with(';') {
+                let sig = signature.take().unwrap();
+                let sig = sig
+                    .trim_end_matches(['{', ';'])
+                    .trim()
+                    .replace("( ", "(")
+                    .replace(", )", ")")
+                    .replace("{ ", "{")
+                    .replace(", }", "}");
+                let item = match container.as_deref() {
+                    Some(container) => format!("{} {}", container, sig),
+                    None => sig,
+                };
+                items.push(annotate(&attributes, &item));
+                attributes = container_attributes.clone();
+            }
+            continue;
+        }
+        let trimmed = line.trim();
+        if trimmed.starts_with("#[cfg(") || trimmed.starts_with("#[deprecated") {
+            attributes.push(trimmed.to_string());
+            continue;
+        }
+        if trimmed.starts_with("#[") || trimmed.starts_with("//") {
+            continue;
+        }
+        if line == "}" || line == "};" {
+            container = None;
+            container_attributes = file_cfg.iter().cloned().collect();
+            attributes = container_attributes.clone();
+            continue;
+        }
+        if container.is_none() {
+            // top level item
+            if line.starts_with("pub struct ") || line.starts_with("pub enum ") {
+                let header = line.trim_end_matches(['{', ';']).trim().to_string();
+                items.push(annotate(&attributes, &header));
+                if line.ends_with('{') {
+                    container = Some(header);
+                    container_attributes = attributes.clone();
+                }
+            } else if line.starts_with("impl") {
+                // an empty impl (e.g. `impl Error for X {}`) has no body
+                let is_empty = line.ends_with("{}");
+                let header = line
+                    .trim_end_matches("{}")
+                    .trim_end_matches('{')
+                    .trim()
+                    .to_string();
+                if header.contains(" for ") {
+                    items.push(annotate(&attributes, &header));
+                }
+                if !is_empty {
+                    container = Some(header);
+                    container_attributes = attributes.clone();
+                }
+            } else if line.starts_with("pub ") && !line.starts_with("pub(") {
+                // a multi-line `pub use` is joined like a signature
+                let is_multiline_use = line.starts_with("pub use ") && line.ends_with('{');
+                if !is_multiline_use && (line.ends_with('{') || line.ends_with(';')) {
+                    let item = line.trim_end_matches(['{', ';']).trim();
+                    items.push(annotate(&attributes, item));
+                } else {
+                    signature = Some(line.to_string());
+                    continue;
+                }
+            } else if !line.is_empty() && !line.starts_with(' ') {
+                container = line.ends_with('{').then(|| "private".to_string());
+            }
+            if !line.is_empty() {
+                attributes = match container {
+                    Some(_) => container_attributes.clone(),
+                    None => file_cfg.iter().cloned().collect(),
+                };
+            }
+            continue;
+        }
+        let Some(current) = container.clone() else {
+            continue;
+        };
+        if current == "private" || !line.starts_with("    ") || line.starts_with("     ") {
+            continue;
+        }
+        if current.starts_with("pub struct ") {
+            if let Some(field) = trimmed.strip_prefix("pub ") {
+                if !field.starts_with('(') {
+                    items.push(annotate(
+                        &attributes,
+                        &format!("{} {}", current, field.trim_end_matches(',')),
+                    ));
+                }
+            }
+            attributes = container_attributes.clone();
+        } else if current.starts_with("pub enum ") {
+            let variant = trimmed.trim_end_matches(',');
+            // the fields of a struct variant are not listed
+            if !trimmed.starts_with("///") && !trimmed.is_empty() && variant != "}" {
+                let variant = match variant.strip_suffix('{') {
+                    Some(name) => format!("{}{{ .. }}", name),
+                    None => variant.to_string(),
+                };
+                items.push(annotate(&attributes, &format!("{} {}", current, variant)));
+            }
+            attributes = container_attributes.clone();
+        } else if current.starts_with("impl") && !current.contains(" for ") {
+            if trimmed.starts_with("pub fn ")
+                || trimmed.starts_with("pub const ")
+                || trimmed.starts_with("pub type ")
+            {
+                if trimmed.ends_with('{') || trimmed.ends_with(';') {
+                    let sig = trimmed.trim_end_matches(['{', ';']).trim();
+                    items.push(annotate(&attributes, &format!("{} {}", current, sig)));
+                } else {
+                    signature = Some(trimmed.to_string());
+                    continue;
+                }
+            }
+            if !trimmed.starts_with("///") {
+                attributes = container_attributes.clone();
+            }
+        }
+    }
+    items
+}
+
+/// Build the snapshot of the public API of the crate
+fn api_snapshot() -> String {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let mut snapshot = String::new();
+    for (file, cfg) in source_files(&src) {
+        let source = std::fs::read_to_string(src.join(&file)).unwrap();
+        snapshot.push_str(&format!("# {}\n", file));
+        for item in public_items(&source, &cfg) {
+            snapshot.push_str(&item);
+            snapshot.push('\n');
+        }
+    }
+    snapshot
+}
+
+mod test {
+    use super::{api_snapshot, UPDATE_VAR};
+    use std::path::Path;
+
+    #[test]
+    fn test_api_snapshot() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/api_snapshot.txt");
+        let snapshot = api_snapshot();
+        if std::env::var_os(UPDATE_VAR).is_some() {
+            std::fs::write(&path, &snapshot).unwrap();
+            return;
+        }
+        let expected = std::fs::read_to_string(&path).unwrap_or_default();
+        if snapshot == expected {
+            return;
+        }
+        let removed: Vec<&str> = expected
+            .lines()
+            .filter(|line| !snapshot.lines().any(|l| l == *line))
+            .collect();
+        let added: Vec<&str> = snapshot
+            .lines()
+            .filter(|line| !expected.lines().any(|l| l == *line))
+            .collect();
+        panic!(
+            "The public API changed.\n\nRemoved:\n  {}\n\nAdded:\n  {}\n\n\
+             If this is intended, update the snapshot with `{}=1 cargo test --test test_api_snapshot`",
+            removed.join("\n  "),
+            added.join("\n  "),
+            UPDATE_VAR
+        );
+    }
+}
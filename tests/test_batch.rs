@@ -0,0 +1,103 @@
+mod test {
+    use jscontact::{collect_languages, localize_all, BatchError, Card, LocalizeOptions};
+    use std::collections::BTreeSet;
+
+    fn cards() -> Vec<Card> {
+        let mut cards: Vec<Card> = [
+            include_bytes!("./rfc9553/figure_20.json").as_slice(),
+            include_bytes!("./rfc9553/figure_33.json"),
+            include_bytes!("./rfc9553/figure_39.json"),
+            include_bytes!("./rfc9553/figure_40.json"),
+            include_bytes!("./rfc9553/figure_01.json"),
+        ]
+        .into_iter()
+        .map(|json| serde_json::from_slice(json).unwrap())
+        .collect();
+        cards.push(
+            serde_json::from_value(serde_json::json!({
+                "@type": "Card",
+                "version": "1.0",
+                "uid": "bad",
+                "phones": { "tel0": { "number": "tel:+1-555-555-5555" } },
+                "localizations": {
+                    "es": {
+                        "phones/tel0/contexts": { "private": true },
+                        "phones/tel0/label": "casa"
+                    }
+                }
+            }))
+            .unwrap(),
+        );
+        cards
+    }
+
+    #[test]
+    fn test_collect_languages() {
+        let languages = collect_languages(&cards());
+        assert_eq!(
+            languages,
+            BTreeSet::from(["es", "jp", "uk-Cyrl", "yue"].map(String::from))
+        );
+        assert!(collect_languages(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_localize_all() {
+        let cards = cards();
+        let results = localize_all(&cards, "es", &LocalizeOptions::default());
+        assert_eq!(results.len(), cards.len());
+        // the Cards without Spanish localization are kept as is
+        assert_eq!(results[0].as_ref().unwrap(), &cards[0]);
+        assert_eq!(results[4].as_ref().unwrap(), &cards[4]);
+        let titles = results[3].as_ref().unwrap().titles.as_ref().unwrap();
+        assert_eq!(titles["t1"].name, "escritor");
+        // one bad Card does not sink the batch
+        let error = results[5].as_ref().unwrap_err();
+        assert_eq!(error.index, 5);
+        assert_eq!(error.uid, "bad");
+        assert!(error
+            .to_string()
+            .starts_with("Card 5 (bad): es: phones/tel0/contexts"));
+
+        let opts = LocalizeOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        let results = localize_all(&cards, "es", &opts);
+        let localized = results[5].as_ref().unwrap();
+        assert_eq!(
+            localized.phones.as_ref().unwrap()["tel0"].label.as_deref(),
+            Some("casa")
+        );
+
+        let opts = LocalizeOptions {
+            fallback_chain: true,
+            ..Default::default()
+        };
+        let results = localize_all(&cards, "uk-Cyrl-UA", &opts);
+        assert_eq!(
+            results[2].as_ref().unwrap().language.as_deref(),
+            Some("uk-Cyrl")
+        );
+    }
+
+    #[test]
+    fn test_localize_all_parallel_and_sequential() {
+        let cards: Vec<Card> = cards().into_iter().cycle().take(60).collect();
+        for language in collect_languages(&cards) {
+            for lenient in [false, true] {
+                let parallel = LocalizeOptions {
+                    lenient,
+                    ..Default::default()
+                };
+                let sequential = LocalizeOptions {
+                    parallel: false,
+                    ..parallel.clone()
+                };
+                let results: Vec<Result<Card, BatchError>> =
+                    localize_all(&cards, &language, &parallel);
+                assert_eq!(results, localize_all(&cards, &language, &sequential));
+            }
+        }
+    }
+}
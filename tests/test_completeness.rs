@@ -0,0 +1,118 @@
+mod test {
+    use jscontact::{Card, CompletenessItem, CompletenessWeights};
+    use serde_json::json;
+
+    fn full_card() -> Card {
+        serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "name": { "full": "Jane Doe" },
+            "emails": { "e1": { "address": "jane@example.com" } },
+            "phones": { "p1": { "number": "tel:+1-555-555-5555" } },
+            "addresses": { "a1": { "full": "54321 Oak St, Reston, VA 20190, USA" } },
+            "media": { "m1": { "kind": "photo", "uri": "https://example.com/jane.jpg" } },
+            "titles": { "t1": { "name": "Research Scientist" } },
+            "anniversaries": {
+                "k1": { "kind": "birth", "date": { "year": 1953, "month": 4, "day": 15 } }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_completeness_empty() {
+        let card = Card::new_with_latest_version("1234");
+        let completeness = card.completeness(&CompletenessWeights::default());
+        assert_eq!(completeness.score, 0.0);
+        assert_eq!(
+            completeness.missing,
+            [
+                CompletenessItem::Name,
+                CompletenessItem::Email,
+                CompletenessItem::Phone,
+                CompletenessItem::Address,
+                CompletenessItem::Photo,
+                CompletenessItem::Organization,
+                CompletenessItem::Birthday
+            ]
+        );
+    }
+
+    #[test]
+    fn test_completeness_full() {
+        let card = full_card();
+        let completeness = card.completeness(&CompletenessWeights::default());
+        assert_eq!(completeness.score, 1.0);
+        assert!(completeness.missing.is_empty());
+        assert_eq!(
+            card.completeness_score(&CompletenessWeights::default()),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_completeness_partial() {
+        let mut card = full_card();
+        card.media = None;
+        card.phones = None;
+        // an anniversary that is not a birthday
+        card.anniversaries = Some(
+            [(
+                "k1".into(),
+                serde_json::from_value(json!({ "kind": "wedding", "date": { "year": 1980 } }))
+                    .unwrap(),
+            )]
+            .into(),
+        );
+        let completeness = card.completeness(&CompletenessWeights::default());
+        assert_eq!(completeness.score, 7.0 / 10.5);
+        assert_eq!(
+            completeness.missing,
+            [
+                CompletenessItem::Phone,
+                CompletenessItem::Photo,
+                CompletenessItem::Birthday
+            ]
+        );
+        assert_eq!(completeness.missing[0].to_string(), "phone");
+    }
+
+    #[test]
+    fn test_completeness_custom_weights() {
+        let mut card = full_card();
+        card.emails = None;
+        card.addresses = None;
+        let weights = CompletenessWeights {
+            name: 1.0,
+            email: 5.0,
+            phone: 2.0,
+            address: 2.0,
+            photo: 0.0,
+            organization: -1.0,
+            birthday: 0.0,
+        };
+        assert_eq!(weights.weight(CompletenessItem::Organization), 0.0);
+        let completeness = card.completeness(&weights);
+        assert_eq!(completeness.score, 0.3);
+        assert_eq!(
+            completeness.missing,
+            [CompletenessItem::Email, CompletenessItem::Address]
+        );
+        assert_eq!(
+            serde_json::to_value(&completeness.missing).unwrap(),
+            json!(["email", "address"])
+        );
+
+        let nothing = CompletenessWeights {
+            name: 0.0,
+            email: 0.0,
+            phone: 0.0,
+            address: 0.0,
+            photo: 0.0,
+            organization: 0.0,
+            birthday: 0.0,
+        };
+        assert_eq!(card.completeness_score(&nothing), 1.0);
+    }
+}
@@ -0,0 +1,189 @@
+mod test {
+    use jscontact::{
+        Address, AddressComponent, Card, LocalizationConflict, Name, NameComponent,
+        NameComponentKind, ReindexPolicy, SmallString,
+    };
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    fn figure_20() -> Card {
+        let json = include_bytes!("./rfc9553/figure_20.json");
+        serde_json::from_slice(json).unwrap()
+    }
+
+    fn patches(card: &Card, language: &str) -> HashMap<String, Value> {
+        card.get_raw_localizations().unwrap()[language]
+            .clone()
+            .into_inner()
+    }
+
+    #[test]
+    fn test_name_components() {
+        let mut name = Name::default();
+        name.remove_component(0);
+        assert_eq!(name.components, None);
+        name.insert_component(0, NameComponent::new(NameComponentKind::Given, "Jane"));
+        name.insert_component(0, NameComponent::new(NameComponentKind::Title, "Dr."));
+        assert_eq!(name.to_full_string(), "Dr. Jane");
+        let removed = name.remove_component(1).unwrap();
+        assert_eq!(removed.value, "Jane");
+        assert_eq!(name.remove_component(1), None);
+    }
+
+    #[test]
+    fn test_remove_reindex() {
+        let mut card = figure_20();
+        let removed = card
+            .name_mut_guarded(ReindexPolicy::Reindex)
+            .remove_component(0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(removed.value, "孫");
+        assert_eq!(
+            patches(&card, "yue"),
+            HashMap::from([
+                ("name/phoneticSystem".to_string(), json!("jyut")),
+                ("name/phoneticScript".to_string(), json!("Latn")),
+                (
+                    "name/components/0/phonetic".to_string(),
+                    json!("zung1saan1")
+                ),
+                ("name/components/1/phonetic".to_string(), json!("man4")),
+                ("name/components/2/phonetic".to_string(), json!("jat6sin1")),
+            ])
+        );
+        let localized = card.get_localized("yue").unwrap();
+        let components = localized.name.unwrap().components.unwrap();
+        assert_eq!(components[0].value, "中山");
+        assert_eq!(components[0].phonetic.as_deref(), Some("zung1saan1"));
+        assert_eq!(components[2].phonetic.as_deref(), Some("jat6sin1"));
+
+        // out of bounds, nothing changes
+        let before = card.clone();
+        assert_eq!(
+            card.name_mut_guarded(ReindexPolicy::Reject)
+                .remove_component(3),
+            Ok(None)
+        );
+        assert_eq!(card, before);
+    }
+
+    #[test]
+    fn test_insert_reindex() {
+        let mut card = figure_20();
+        let title = NameComponent::new(NameComponentKind::Title, "Dr.");
+        card.name_mut_guarded(ReindexPolicy::Reindex)
+            .insert_component(1, title)
+            .unwrap();
+        let patches = patches(&card, "yue");
+        assert_eq!(patches["name/components/0/phonetic"], "syun1");
+        assert!(!patches.contains_key("name/components/1/phonetic"));
+        assert_eq!(patches["name/components/2/phonetic"], "zung1saan1");
+        assert_eq!(patches["name/components/4/phonetic"], "jat6sin1");
+        let localized = card.get_localized("yue").unwrap();
+        let components = localized.name.unwrap().components.unwrap();
+        assert_eq!(components[1].phonetic, None);
+        assert_eq!(components[4].phonetic.as_deref(), Some("jat6sin1"));
+    }
+
+    #[test]
+    fn test_reject() {
+        let mut card = figure_20();
+        let before = card.clone();
+        let conflict = card
+            .name_mut_guarded(ReindexPolicy::Reject)
+            .remove_component(2)
+            .unwrap_err();
+        assert_eq!(
+            conflict,
+            LocalizationConflict {
+                languages: vec!["yue".to_string()],
+                paths: vec![
+                    "name/components/2/phonetic".to_string(),
+                    "name/components/3/phonetic".to_string(),
+                ],
+            }
+        );
+        assert_eq!(
+            conflict.to_string(),
+            "The localizations yue patch the components: name/components/2/phonetic, name/components/3/phonetic"
+        );
+        assert_eq!(card, before);
+
+        // appending shifts no component
+        let component = NameComponent::new(NameComponentKind::Credential, "PhD");
+        card.name_mut_guarded(ReindexPolicy::Reject)
+            .insert_component(4, component)
+            .unwrap();
+        assert_eq!(patches(&card, "yue"), patches(&before, "yue"));
+    }
+
+    #[test]
+    fn test_whole_components_conflict() {
+        let mut card = figure_20();
+        card.add_localization(
+            "en",
+            HashMap::from([(
+                "name".to_string(),
+                json!({ "components": [{ "kind": "surname", "value": "Sun" }] }),
+            )]),
+        );
+        let before = card.clone();
+        let conflict = card
+            .name_mut_guarded(ReindexPolicy::Reindex)
+            .remove_component(0)
+            .unwrap_err();
+        assert_eq!(conflict.languages, vec!["en"]);
+        assert_eq!(conflict.paths, vec!["name"]);
+        assert_eq!(card, before);
+    }
+
+    #[test]
+    fn test_address_reindex() {
+        let mut card = Card::new_with_latest_version("my:uri");
+        let mut address = Address::default();
+        address.insert_component(0, AddressComponent::locality("Berlin"));
+        address.insert_component(0, AddressComponent::name("Hauptstraße"));
+        card.addresses = Some(HashMap::from([(SmallString::from("k/1"), address)]));
+        card.add_localization(
+            "en",
+            HashMap::from([
+                (
+                    "addresses/k~11/components/1/value".to_string(),
+                    json!("Berlin"),
+                ),
+                (
+                    "addresses/k~11/components/0/value".to_string(),
+                    json!("Main Street"),
+                ),
+            ]),
+        );
+        assert!(card
+            .address_mut_guarded("missing", ReindexPolicy::Reindex)
+            .is_none());
+
+        let mut guarded = card
+            .address_mut_guarded("k/1", ReindexPolicy::Reindex)
+            .unwrap();
+        guarded
+            .insert_component(1, AddressComponent::number("42"))
+            .unwrap();
+        guarded.remove_component(0).unwrap();
+        assert_eq!(
+            patches(&card, "en"),
+            HashMap::from([(
+                "addresses/k~11/components/1/value".to_string(),
+                json!("Berlin")
+            )])
+        );
+        let localized = card.get_localized("en").unwrap();
+        let address = &localized.addresses.unwrap()[&SmallString::from("k/1")];
+        let values: Vec<&str> = address
+            .components
+            .iter()
+            .flatten()
+            .map(|component| component.value.as_str())
+            .collect();
+        assert_eq!(values, ["42", "Berlin"]);
+    }
+}
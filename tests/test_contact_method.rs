@@ -0,0 +1,116 @@
+mod test {
+    use jscontact::{Card, ContactMethod, ContactMethodKind, Context};
+    use serde_json::Value;
+
+    /// The Card of the figures 25 to 30 (emails, online services, phones, preferred languages,
+    /// calendars and scheduling addresses) merged in one Card
+    fn card() -> Result<Card, Box<dyn std::error::Error>> {
+        let figures = [
+            include_str!("./rfc9553/figure_25.json"),
+            include_str!("./rfc9553/figure_26.json"),
+            include_str!("./rfc9553/figure_27.json"),
+            include_str!("./rfc9553/figure_28.json"),
+            include_str!("./rfc9553/figure_29.json"),
+            include_str!("./rfc9553/figure_30.json"),
+        ];
+        let mut merged = serde_json::Map::new();
+        for figure in figures {
+            if let Value::Object(object) = serde_json::from_str(figure)? {
+                merged.extend(object);
+            }
+        }
+        Ok(serde_json::from_value(Value::Object(merged))?)
+    }
+
+    /// Get the context, kind, key and value of the methods
+    fn summary<'a>(
+        methods: &'a [ContactMethod<'a>],
+    ) -> Vec<(Option<Context>, ContactMethodKind, &'a str, &'a str)> {
+        methods
+            .iter()
+            .map(|method| {
+                let info = method.info();
+                (info.context.clone(), info.kind, info.key, info.value)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_contact_methods() -> Result<(), Box<dyn std::error::Error>> {
+        let card = card()?;
+        let methods = card.contact_methods();
+        assert_eq!(
+            summary(&methods),
+            vec![
+                (None, ContactMethodKind::Email, "e2", "jane_doe@example.com"),
+                (
+                    None,
+                    ContactMethodKind::OnlineService,
+                    "x1",
+                    "xmpp:alice@example.com"
+                ),
+                (
+                    None,
+                    ContactMethodKind::OnlineService,
+                    "x2",
+                    "https://example2.com/@alice"
+                ),
+                (
+                    None,
+                    ContactMethodKind::SchedulingAddress,
+                    "sched1",
+                    "mailto:janedoe@example.com"
+                ),
+                (
+                    Some(Context::Private),
+                    ContactMethodKind::Phone,
+                    "tel0",
+                    "tel:+1-555-555-5555;ext=5555"
+                ),
+                (
+                    Some(Context::Work),
+                    ContactMethodKind::Phone,
+                    "tel3",
+                    "tel:+1-201-555-0123"
+                ),
+                (
+                    Some(Context::Work),
+                    ContactMethodKind::Email,
+                    "e1",
+                    "jqpublic@xyz.example.com"
+                ),
+            ]
+        );
+        let ContactMethod::Phone(phone, info) = &methods[4] else {
+            panic!("not a phone");
+        };
+        assert_eq!(phone.number, info.value);
+        assert_eq!(info.pref, Some(1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_contact_methods_by_context() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card = card()?;
+        // a method in two contexts is listed in both
+        let e1 = card.emails.as_mut().unwrap().get_mut("e1").unwrap();
+        e1.contexts.as_mut().unwrap().insert(Context::Private, true);
+        let groups = card.contact_methods_by_context();
+        let keys: Vec<(Option<Context>, Vec<&str>)> = groups
+            .iter()
+            .map(|(context, methods)| {
+                let keys = methods.iter().map(|method| method.info().key).collect();
+                (context.clone(), keys)
+            })
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (None, vec!["e2", "x1", "x2", "sched1"]),
+                (Some(Context::Private), vec!["tel0", "e1"]),
+                (Some(Context::Work), vec!["tel3", "e1"]),
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+mod test {
+    use jscontact::{Address, AddressContext, Context, EmailAddress, HasContexts, Phone};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_no_context() {
+        let mut phone = Phone::new("tel:+1-555-555-5555");
+        assert_eq!(phone.only_context(), None);
+        assert!(!phone.has_context(&Context::Work));
+        phone.contexts = Some(HashMap::new());
+        assert_eq!(phone.only_context(), None);
+        // a context set to false does not count
+        phone.contexts = Some(HashMap::from([(Context::Work, false)]));
+        assert_eq!(phone.only_context(), None);
+        assert!(!phone.has_context(&Context::Work));
+    }
+
+    #[test]
+    fn test_one_context() {
+        let mut email = EmailAddress::new("jane@example.com");
+        email.set_context(Context::Work);
+        assert_eq!(email.contexts, Some(Context::single(Context::Work)));
+        assert_eq!(email.only_context(), Some(Context::Work));
+        assert!(email.has_context(&Context::Work));
+        assert_eq!(
+            serde_json::to_value(&email).unwrap()["contexts"],
+            json!({ "work": true })
+        );
+
+        email.contexts = Some(HashMap::from([
+            (Context::Private, false),
+            (Context::Other("example.com:oncall".to_string()), true),
+        ]));
+        assert_eq!(
+            email.only_context(),
+            Some(Context::Other("example.com:oncall".to_string()))
+        );
+
+        let mut address = Address::default();
+        address.set_context(AddressContext::Billing);
+        assert_eq!(address.only_context(), Some(AddressContext::Billing));
+    }
+
+    #[test]
+    fn test_multiple_contexts() {
+        let mut phone = Phone::new("tel:+1-555-555-5555");
+        phone.contexts = Some(HashMap::from([
+            (Context::Private, true),
+            (Context::Work, true),
+        ]));
+        assert_eq!(phone.only_context(), None);
+        assert!(phone.has_context(&Context::Private));
+        assert!(phone.has_context(&Context::Work));
+        // setting a context removes the others
+        phone.set_context(Context::Private);
+        assert_eq!(phone.contexts, Some(Context::single(Context::Private)));
+        assert_eq!(phone.only_context(), Some(Context::Private));
+    }
+}
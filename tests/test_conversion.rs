@@ -0,0 +1,62 @@
+mod test {
+    use jscontact::{ConversionReport, LossRecord};
+    use serde_json::json;
+
+    #[test]
+    fn test_conversion_report() {
+        let mut report = ConversionReport::new();
+        assert!(report.is_lossless());
+        report.add_warning("phones/tel1: the features are approximated");
+        assert!(report.is_lossless());
+        report.add_loss("language", "no vCard property");
+        report.add_loss_with_value("titles/t1", "no MeCard field", json!({ "name": "CEO" }));
+        assert!(!report.is_lossless());
+        assert_eq!(
+            report.lossy[1],
+            LossRecord {
+                path: "titles/t1".to_string(),
+                reason: "no MeCard field".to_string(),
+                dropped_value: Some(json!({ "name": "CEO" })),
+            }
+        );
+    }
+
+    #[test]
+    fn test_conversion_report_merge() {
+        let mut report = ConversionReport::new();
+        report.add_loss("language", "no vCard property");
+        let mut other = ConversionReport::new();
+        other.add_loss("GEO", "no JSContact property");
+        other.add_warning("BDAY: the time is dropped");
+        report.merge(other);
+        let paths: Vec<&str> = report.lossy.iter().map(|loss| loss.path.as_str()).collect();
+        assert_eq!(paths, ["language", "GEO"]);
+        assert_eq!(report.warnings, ["BDAY: the time is dropped"]);
+        report.merge(ConversionReport::new());
+        assert_eq!(report.lossy.len(), 2);
+    }
+
+    #[test]
+    fn test_conversion_report_serde() {
+        let mut report = ConversionReport::new();
+        report.add_loss("language", "no vCard property");
+        report.add_loss_with_value("notes/n2", "only the first note", json!({ "note": "hi" }));
+        let value = serde_json::to_value(&report).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "lossy": [
+                    { "path": "language", "reason": "no vCard property" },
+                    {
+                        "path": "notes/n2",
+                        "reason": "only the first note",
+                        "dropped_value": { "note": "hi" }
+                    }
+                ],
+                "warnings": []
+            })
+        );
+        let parsed: ConversionReport = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, report);
+    }
+}
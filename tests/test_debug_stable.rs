@@ -0,0 +1,83 @@
+mod test {
+    use jscontact::Card;
+
+    #[test]
+    fn test_debug_stable() -> Result<(), Box<dyn std::error::Error>> {
+        // the same Card, with its members in another order
+        let first: Card = r#"{
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "emails": {
+                "e1": { "address": "jane@example.com" },
+                "e2": { "address": "jane@example.org" },
+                "e3": { "address": "jane@example.net" }
+            },
+            "phones": {
+                "tel0": { "number": "tel:+1-555-555-0000" },
+                "tel1": { "number": "tel:+1-555-555-0001" },
+                "tel2": { "number": "tel:+1-555-555-0002" }
+            },
+            "keywords": { "keyword0": true, "keyword1": true, "keyword2": true },
+            "example.com:ext0": { "b": 1, "a": 0 },
+            "example.com:ext1": { "b": 1, "a": 1 },
+            "example.com:ext2": { "b": 1, "a": 2 },
+            "localizations": {
+                "l0": { "emails/e1/label": "x", "emails/e2/label": "y" },
+                "l1": { "emails/e1/label": "x", "emails/e2/label": "y" },
+                "l2": { "emails/e1/label": "x", "emails/e2/label": "y" }
+            }
+        }"#
+        .parse()?;
+        let second: Card = r#"{
+            "localizations": {
+                "l2": { "emails/e2/label": "y", "emails/e1/label": "x" },
+                "l0": { "emails/e2/label": "y", "emails/e1/label": "x" },
+                "l1": { "emails/e2/label": "y", "emails/e1/label": "x" }
+            },
+            "example.com:ext2": { "a": 2, "b": 1 },
+            "example.com:ext0": { "a": 0, "b": 1 },
+            "example.com:ext1": { "a": 1, "b": 1 },
+            "keywords": { "keyword2": true, "keyword0": true, "keyword1": true },
+            "phones": {
+                "tel2": { "number": "tel:+1-555-555-0002" },
+                "tel0": { "number": "tel:+1-555-555-0000" },
+                "tel1": { "number": "tel:+1-555-555-0001" }
+            },
+            "emails": {
+                "e3": { "address": "jane@example.net" },
+                "e1": { "address": "jane@example.com" },
+                "e2": { "address": "jane@example.org" }
+            },
+            "uid": "1234",
+            "version": "1.0",
+            "@type": "Card"
+        }"#
+        .parse()?;
+        assert_eq!(first, second);
+        let debug = format!("{:?}", first.debug_stable());
+        assert_eq!(debug, format!("{:?}", second.debug_stable()));
+        assert_eq!(
+            format!("{:#?}", first.debug_stable()),
+            format!("{:#?}", second.debug_stable())
+        );
+        // the entries are sorted
+        let e1 = debug.find("\"e1\"").unwrap();
+        let e2 = debug.find("\"e2\"").unwrap();
+        let e3 = debug.find("\"e3\"").unwrap();
+        assert!(e1 < e2 && e2 < e3);
+        assert!(debug.contains(r#"extensions: {"example.com:ext0": {"a": 0, "b": 1}"#));
+        assert!(debug
+            .contains(r#"localizations: {"l0": {"emails/e1/label": "x", "emails/e2/label": "y"}"#));
+        Ok(())
+    }
+
+    #[test]
+    fn test_debug_stable_empty_card() {
+        let card = Card::new_with_latest_version("1234");
+        assert_eq!(
+            format!("{:?}", card.debug_stable()),
+            r#"Card { version: "1.0", uid: "1234" }"#
+        );
+    }
+}
@@ -0,0 +1,256 @@
+mod test {
+    use jscontact::dedup::{find_duplicates, merge_group, DuplicateMatch, MatchKey};
+    use jscontact::{
+        Card, DedupOptions, MergeStrategy, PlaceRef, SmallString, PLACE_KEY_PROPERTY,
+        PROVENANCE_PROPERTY,
+    };
+
+    /// The Cards searched for duplicates, two groups of duplicates and two Cards with a near-miss name
+    fn cards() -> Result<Vec<Card>, Box<dyn std::error::Error>> {
+        let json = serde_json::json!([
+            {
+                "@type": "Card",
+                "version": "1.0",
+                "uid": "urn:uuid:1",
+                "name": { "full": "John Smith" },
+                "emails": { "e1": { "address": "john@example.com" } },
+                "updated": "2024-01-01T00:00:00Z"
+            },
+            // exact uid duplicate
+            {
+                "@type": "Card",
+                "version": "1.0",
+                "uid": "urn:uuid:1",
+                "name": { "full": "John Smith" },
+                "phones": { "p1": { "number": "tel:+1-555-0100" } },
+                "updated": "2025-01-01T00:00:00Z"
+            },
+            // same email, different name
+            {
+                "@type": "Card",
+                "version": "1.0",
+                "uid": "urn:uuid:2",
+                "name": { "full": "Johnny S." },
+                "emails": {
+                    "e1": { "address": "johnny@example.com" },
+                    "e2": { "address": " John@Example.com" }
+                }
+            },
+            // near-miss names
+            {
+                "@type": "Card",
+                "version": "1.0",
+                "uid": "urn:uuid:3",
+                "name": { "full": "Jon Smith" }
+            },
+            {
+                "@type": "Card",
+                "version": "1.0",
+                "uid": "urn:uuid:4",
+                "name": { "full": "Jane Smith" }
+            },
+            // same phone, and a name similar to the next card
+            {
+                "@type": "Card",
+                "version": "1.0",
+                "uid": "urn:uuid:5",
+                "name": { "full": "Mary-Ann Jones" },
+                "phones": { "p1": { "number": "+1 (555) 0199" } }
+            },
+            {
+                "@type": "Card",
+                "version": "1.0",
+                "uid": "urn:uuid:6",
+                "phones": { "p1": { "number": "tel:+15550199" } }
+            },
+            {
+                "@type": "Card",
+                "version": "1.0",
+                "uid": "urn:uuid:7",
+                "name": { "full": "JONES, Mary Ann" }
+            },
+        ]);
+        Ok(serde_json::from_value(json)?)
+    }
+
+    #[test]
+    fn test_find_duplicates() -> Result<(), Box<dyn std::error::Error>> {
+        let groups = find_duplicates(&cards()?, &DedupOptions::default());
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].indexes, vec![0, 1, 2]);
+        assert_eq!(groups[0].cards[2].uid, "urn:uuid:2");
+        assert_eq!(
+            groups[0].matches,
+            vec![
+                DuplicateMatch {
+                    first: 0,
+                    second: 1,
+                    key: MatchKey::Uid("urn:uuid:1".to_string())
+                },
+                DuplicateMatch {
+                    first: 0,
+                    second: 1,
+                    key: MatchKey::Name(1.0)
+                },
+                DuplicateMatch {
+                    first: 0,
+                    second: 2,
+                    key: MatchKey::Email("john@example.com".to_string())
+                },
+            ]
+        );
+        assert_eq!(groups[1].indexes, vec![5, 6, 7]);
+        assert_eq!(
+            groups[1].matches,
+            vec![
+                DuplicateMatch {
+                    first: 5,
+                    second: 6,
+                    key: MatchKey::Phone("+15550199".to_string())
+                },
+                DuplicateMatch {
+                    first: 5,
+                    second: 7,
+                    key: MatchKey::Name(1.0)
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_duplicates_options() -> Result<(), Box<dyn std::error::Error>> {
+        let cards = cards()?;
+        let opts = DedupOptions {
+            match_uid: false,
+            match_email: false,
+            match_phone: false,
+            name_threshold: None,
+        };
+        assert!(find_duplicates(&cards, &opts).is_empty());
+
+        // "Jon Smith" and "Jane Smith" share a word with "John Smith"
+        let opts = DedupOptions {
+            name_threshold: Some(0.3),
+            ..opts
+        };
+        let groups = find_duplicates(&cards, &opts);
+        assert_eq!(groups[0].indexes, vec![0, 1, 3, 4]);
+        assert!(groups[0]
+            .matches
+            .iter()
+            .all(|duplicate| matches!(duplicate.key, MatchKey::Name(score) if score >= 0.3)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_group() -> Result<(), Box<dyn std::error::Error>> {
+        let groups = find_duplicates(&cards()?, &DedupOptions::default());
+        let merged = merge_group(&groups[0], MergeStrategy::PreferFirst);
+        assert_eq!(merged.uid, "urn:uuid:1");
+        assert_eq!(merged.updated.as_deref(), Some("2024-01-01T00:00:00Z"));
+        // the same address is not added twice, the other address gets a new key
+        let emails = merged.emails.as_ref().unwrap();
+        assert_eq!(emails.len(), 2);
+        assert_eq!(emails[&SmallString::from("e1")].address, "john@example.com");
+        assert_eq!(
+            emails[&SmallString::from("e1-2")].address,
+            "johnny@example.com"
+        );
+        assert_eq!(merged.phones.as_ref().unwrap().len(), 1);
+
+        let merged = merge_group(&groups[0], MergeStrategy::PreferLatest);
+        assert_eq!(merged.updated.as_deref(), Some("2025-01-01T00:00:00Z"));
+        assert_eq!(merged.emails.as_ref().unwrap().len(), 2);
+
+        let merged = merge_group(&groups[1], MergeStrategy::PreferLatest);
+        assert_eq!(merged.uid, "urn:uuid:5");
+        assert_eq!(merged.name.unwrap().full.as_deref(), Some("Mary-Ann Jones"));
+        assert_eq!(merged.phones.unwrap().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_group_provenance() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cards = cards()?;
+        cards[0].set_provenance("carddav");
+        cards[1].set_provenance("csv");
+        cards[2].set_provenance("jmap");
+        cards[2].set_provenance_of("emails/e1", "jmap-work");
+        let groups = find_duplicates(&cards, &DedupOptions::default());
+        let merged = merge_group(&groups[0], MergeStrategy::PreferFirst);
+        // the phone came from the second source
+        assert_eq!(merged.provenance_of("phones/p1"), Some("csv"));
+        assert_eq!(merged.provenance_of("phones/p1/number"), Some("csv"));
+        // the renamed email keeps the source of its entry
+        assert_eq!(merged.provenance_of("emails/e1-2"), Some("jmap-work"));
+        // the rest comes from the preferred Card
+        assert_eq!(merged.provenance_of("emails/e1"), Some("carddav"));
+        assert_eq!(merged.provenance_of("name"), Some("carddav"));
+
+        // the sources survive the serialization
+        let json = serde_json::to_value(&merged)?;
+        assert_eq!(json[PROVENANCE_PROPERTY]["phones/p1"], "csv");
+        let parsed: Card = serde_json::from_value(json)?;
+        assert_eq!(parsed.provenance_of("phones/p1"), Some("csv"));
+
+        // without sources, nothing is recorded
+        let merged = merge_group(&groups[1], MergeStrategy::PreferFirst);
+        assert_eq!(merged.provenance_of("phones/p1"), None);
+        assert!(!merged.extensions.contains_key(PROVENANCE_PROPERTY));
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_group_rewrites_references() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!([
+            {
+                "@type": "Card",
+                "version": "1.0",
+                "uid": "urn:uuid:1",
+                "emails": { "e1": { "address": "john@example.com" } },
+                "organizations": { "o1": { "name": "ABC" } },
+                "addresses": { "a1": { "full": "Paris" } }
+            },
+            {
+                "@type": "Card",
+                "version": "1.0",
+                "uid": "urn:uuid:2",
+                "emails": { "e1": { "address": "john@example.com" } },
+                "organizations": {
+                    "o1": { "name": "XYZ" },
+                    "o2": { "name": "ABC" }
+                },
+                "titles": {
+                    "t1": { "name": "Director", "organizationId": "o1" },
+                    "t2": { "name": "Founder", "organizationId": "o2" }
+                },
+                "addresses": { "a1": { "full": "Lyon" } },
+                "anniversaries": {
+                    "k1": {
+                        "kind": "birth",
+                        "date": { "@type": "PartialDate", "year": 1980 },
+                        "place": { "@type": "Address", PLACE_KEY_PROPERTY: "a1" }
+                    }
+                }
+            },
+        ]);
+        let cards: Vec<Card> = serde_json::from_value(json)?;
+        let groups = find_duplicates(&cards, &DedupOptions::default());
+        let merged = merge_group(&groups[0], MergeStrategy::PreferFirst);
+        let organizations = merged.organizations.as_ref().unwrap();
+        assert_eq!(organizations["o1-2"].name.as_deref(), Some("XYZ"));
+        assert_eq!(organizations.len(), 2);
+        // the title of the re-keyed organization follows it, the other one points to the same organization
+        let titles = merged.titles.as_ref().unwrap();
+        assert_eq!(titles["t1"].organization_id.as_deref(), Some("o1-2"));
+        assert_eq!(titles["t2"].organization_id.as_deref(), Some("o1"));
+        let anniversary = &merged.anniversaries.as_ref().unwrap()["k1"];
+        assert_eq!(anniversary.place, Some(PlaceRef::Key("a1-2".to_string())));
+        assert_eq!(
+            anniversary.place_resolved(&merged).unwrap().full.as_deref(),
+            Some("Lyon")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+mod test {
+    use std::collections::{BTreeSet, HashMap};
+    use std::process::Command;
+
+    use serde_json::Value;
+
+    /// The packages allowed in the dependency tree of the default features, the crate included.
+    ///
+    /// `indexmap`, `hashbrown` and `equivalent` come from the `preserve_order` feature of the `serde_json` dev-dependency,
+    /// as `cargo metadata` resolves the features of the dev-dependencies too.
+    const ALLOWED: &[&str] = &[
+        "equivalent",
+        "hashbrown",
+        "indexmap",
+        "itoa",
+        "jscontact",
+        "jsonptr",
+        "memchr",
+        "proc-macro2",
+        "quote",
+        "ryu",
+        "serde",
+        "serde_core",
+        "serde_derive",
+        "serde_json",
+        "syn",
+        "unicode-ident",
+    ];
+
+    /// Get the names of the packages the crate depends on with the default features, following the normal and build dependencies
+    fn default_dependencies() -> BTreeSet<String> {
+        let output = Command::new(env!("CARGO"))
+            .args(["metadata", "--format-version", "1", "--offline"])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let metadata: Value = serde_json::from_slice(&output.stdout).unwrap();
+        let names: HashMap<&str, &str> = metadata["packages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|package| {
+                (
+                    package["id"].as_str().unwrap(),
+                    package["name"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        let nodes: HashMap<&str, &Value> = metadata["resolve"]["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|node| (node["id"].as_str().unwrap(), node))
+            .collect();
+        let mut dependencies = BTreeSet::new();
+        let mut stack = vec![metadata["resolve"]["root"].as_str().unwrap()];
+        while let Some(id) = stack.pop() {
+            if !dependencies.insert(names[id].to_string()) {
+                continue;
+            }
+            for dep in nodes[id]["deps"].as_array().unwrap() {
+                let normal_or_build = dep["dep_kinds"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .any(|kind| kind["kind"].is_null() || kind["kind"] == "build");
+                if normal_or_build {
+                    stack.push(dep["pkg"].as_str().unwrap());
+                }
+            }
+        }
+        dependencies
+    }
+
+    #[test]
+    fn test_default_dependencies_allowlist() {
+        let dependencies = default_dependencies();
+        let unexpected: Vec<&String> = dependencies
+            .iter()
+            .filter(|name| !ALLOWED.contains(&name.as_str()))
+            .collect();
+        assert!(
+            unexpected.is_empty(),
+            "new dependencies with the default features: {:?}, keep the dependency tree small (e.g. no `regex`, see `jscontact::syntax`)",
+            unexpected
+        );
+        assert!(!dependencies.contains("regex"));
+    }
+}
@@ -0,0 +1,185 @@
+mod test {
+    use jscontact::{
+        parse_with, Card, ContentHash, KeyGen, KeyOrder, ParseOptions, RawValues, SerializeOptions,
+        DEFAULT_MAX_DEPTH,
+    };
+    use serde_json::{json, Map, Value};
+    use std::collections::HashMap;
+
+    /// A depth overflowing the stack when a value is cloned, compared, formatted or dropped with recursion
+    const DEEP: usize = 100_000;
+
+    /// An array nested `depth` times, built without recursion
+    fn nested(depth: usize) -> Value {
+        let mut value = json!(1);
+        for _ in 0..depth {
+            value = Value::Array(vec![value]);
+        }
+        value
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let mut card = Card::new_with_latest_version("1234");
+        card.extensions.insert(
+            "example.com:deep".to_string(),
+            nested(DEFAULT_MAX_DEPTH - 1),
+        );
+        assert!(card.check_depth(DEFAULT_MAX_DEPTH).is_ok());
+        assert!(card.to_string_with(&SerializeOptions::default()).is_ok());
+        card.extensions
+            .insert("example.com:deep".to_string(), nested(DEFAULT_MAX_DEPTH));
+        let error = card.check_depth(DEFAULT_MAX_DEPTH).unwrap_err();
+        assert_eq!(error.path, "example.com:deep");
+        assert_eq!(error.max_depth, DEFAULT_MAX_DEPTH);
+
+        let mut card = Card::new_with_latest_version("1234");
+        let patches = HashMap::from([("example.com:deep".to_string(), nested(3))]);
+        card.add_localization("fr", patches);
+        let error = card.check_depth(5).unwrap_err();
+        assert_eq!(error.path, "localizations/fr/example.com:deep");
+        assert!(card.check_depth(6).is_ok());
+    }
+
+    #[test]
+    fn test_depth_10k() {
+        let mut card = Card::new_with_latest_version("1234");
+        card.extensions
+            .insert("example.com:deep".to_string(), nested(10_000));
+
+        // canonicalize
+        for key_order in [KeyOrder::Canonical, KeyOrder::Alphabetical] {
+            let opts = SerializeOptions {
+                key_order,
+                ..Default::default()
+            };
+            let error = card.to_string_with(&opts).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "The value of 'example.com:deep' is nested deeper than 128 levels"
+            );
+        }
+        assert!(serde_json::to_value(&card).is_err());
+
+        // walk
+        assert_eq!(card.get_pointer("/example.com:deep"), None);
+        assert_eq!(card.get_pointer("/example.com:deep/0/0"), None);
+
+        // strict parse
+        let value = card.extensions.remove("example.com:deep").unwrap();
+        let mut object = serde_json::Map::new();
+        object.insert("@type".to_string(), json!("Card"));
+        object.insert("version".to_string(), json!("1.0"));
+        object.insert("uid".to_string(), json!("1234"));
+        object.insert("example.com:deep".to_string(), value);
+        let error = Card::try_from(Value::Object(object)).unwrap_err();
+        assert_eq!(error, "The value is nested deeper than 128 levels");
+
+        let (parsed, errors) = Card::from_value_collecting(nested(10_000));
+        assert!(parsed.is_none());
+        assert_eq!(
+            errors[0].message,
+            "The value is nested deeper than 128 levels"
+        );
+
+        let json = format!(
+            r#"{{"@type":"Card","version":"1.0","uid":"1","example.com:deep":{}1{}}}"#,
+            "[".repeat(10_000),
+            "]".repeat(10_000)
+        );
+        assert!(Card::from_str_with(&json, &ParseOptions::default()).is_err());
+        assert!(parse_with(&json, &ParseOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_deep_clone_eq_drop() {
+        let mut card = Card::new_with_latest_version("1234");
+        card.extensions
+            .insert("example.com:deep".to_string(), nested(DEEP));
+        card.add_localization(
+            "fr",
+            HashMap::from([("example.com:deep".to_string(), nested(DEEP))]),
+        );
+
+        let copy = card.clone();
+        assert_eq!(copy, card);
+        let (localized, _) = card.get_localized_lenient("de").unwrap();
+        assert_eq!(localized, card);
+
+        let mut other = Card::new_with_latest_version("1234");
+        other
+            .extensions
+            .insert("example.com:deep".to_string(), nested(DEEP - 1));
+        assert_ne!(other.extensions, card.extensions);
+
+        assert_eq!(
+            format!("{:?}", card.extensions),
+            r#"{"example.com:deep": <The value is nested deeper than 128 levels>}"#
+        );
+        assert!(format!("{:?}", card).contains("<The value is nested deeper than 128 levels>"));
+        assert!(format!("{:?}", card.debug_stable()).ends_with(
+            r#"extensions: {"example.com:deep": <The value is nested deeper than 128 levels>} }"#
+        ));
+
+        // the Cards and a map of raw values are dropped without recursion
+        let values: RawValues = HashMap::from([("deep".to_string(), nested(DEEP))]).into();
+        drop(values);
+    }
+
+    #[test]
+    fn test_deep_content_hash() {
+        // the deep values are dropped with a map of raw values
+        let deep: RawValues = HashMap::from([
+            ("a".to_string(), nested(DEEP)),
+            ("b".to_string(), nested(DEEP)),
+            ("c".to_string(), nested(DEEP - 1)),
+        ])
+        .into();
+        let mut entries = Map::new();
+        let key = ContentHash.key("e", &deep["a"], &entries);
+        entries.insert(key.clone(), deep.clone().remove("b").unwrap());
+        // the same entry replaces itself, another entry gets another key
+        assert_eq!(ContentHash.key("e", &deep["b"], &entries), key);
+        assert_ne!(ContentHash.key("e", &deep["c"], &entries), key);
+
+        // the keys of the objects are sorted
+        let a = json!({ "b": [1, { "d": null, "c": "x" }], "a": true });
+        let b = json!({ "a": true, "b": [1, { "c": "x", "d": null }] });
+        let key = ContentHash.key("e", &a, &Map::new());
+        assert_eq!(ContentHash.key("e", &b, &Map::new()), key);
+        entries.insert(key.clone(), b);
+        assert_eq!(ContentHash.key("e", &a, &entries), key);
+
+        let opts = SerializeOptions {
+            key_order: KeyOrder::Alphabetical,
+            ..Default::default()
+        };
+        let mut card = Card::new_with_latest_version("1234");
+        card.extensions.extend(entries);
+        assert!(card.to_string_with(&opts).is_err());
+    }
+
+    #[test]
+    fn test_parse_max_depth() {
+        let json = r#"{"@type":"Card","version":"1.0","uid":"1","example.com:deep":[[[1]]]}"#;
+        let opts = ParseOptions {
+            max_depth: 3,
+            ..Default::default()
+        };
+        let error = Card::from_str_with(json, &opts).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The value is nested deeper than 3 levels"
+        );
+        assert_eq!(
+            parse_with(json, &opts).unwrap_err(),
+            "Invalid JSON: The value is nested deeper than 3 levels"
+        );
+        let opts = ParseOptions {
+            max_depth: 4,
+            ..Default::default()
+        };
+        assert!(Card::from_str_with(json, &opts).is_ok());
+        assert!(parse_with(json, &opts).is_ok());
+    }
+}
@@ -0,0 +1,109 @@
+mod test {
+    use jscontact::{Card, Directory, DirectoryKind, LdapScope, LdapUrl, SmallString};
+
+    fn figure_36() -> Card {
+        Card::try_from(include_bytes!("./rfc9553/figure_36.json").as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_directory_entry_uri() {
+        let mut card = figure_36();
+        assert_eq!(
+            card.directory_entry_uri(),
+            Some("https://dir.example.com/addrbook/jdoe/Jean%20Dupont.vcf")
+        );
+
+        // the entry with the lowest pref is preferred
+        let mut entry = Directory::new("https://dir.example.com/jdoe.vcf");
+        entry.kind = Some(DirectoryKind::Entry);
+        entry.pref = Some(1);
+        let directories = card.directories.as_mut().unwrap();
+        directories.insert(SmallString::from("dir3"), entry);
+        assert_eq!(
+            card.directory_entry_uri(),
+            Some("https://dir.example.com/jdoe.vcf")
+        );
+
+        assert_eq!(
+            Card::new_with_latest_version("1").directory_entry_uri(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_org_directories() {
+        let mut card = figure_36();
+        let directories = card.org_directories();
+        assert_eq!(directories.len(), 1);
+        assert_eq!(directories[0].0, "dir2");
+
+        let mut first = Directory::new("ldap://first.example/o=Example");
+        first.kind = Some(DirectoryKind::Directory);
+        first.list_as = Some(1);
+        let mut unlisted = Directory::new("ldap://unlisted.example/o=Example");
+        unlisted.kind = Some(DirectoryKind::Directory);
+        let map = card.directories.as_mut().unwrap();
+        map.insert(SmallString::from("dir0"), unlisted);
+        map.insert(SmallString::from("dir9"), first);
+        let ids: Vec<&str> = card.org_directories().iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec!["dir9", "dir0", "dir2"]);
+    }
+
+    #[test]
+    fn test_ldap_components_figure_36() {
+        let card = figure_36();
+        let directories = card.directories.as_ref().unwrap();
+        let ldap = directories[&SmallString::from("dir2")]
+            .ldap_components()
+            .unwrap();
+        assert!(!ldap.secure);
+        assert_eq!(ldap.host.as_deref(), Some("ldap.example"));
+        assert_eq!(ldap.port, None);
+        assert_eq!(ldap.base_dn, "o=Example Tech,ou=Engineering");
+        assert!(ldap.attributes.is_empty());
+        assert_eq!(ldap.scope, LdapScope::Base);
+        assert_eq!(ldap.filter, None);
+
+        // the entry is not an LDAP URL
+        assert_eq!(
+            directories[&SmallString::from("dir1")].ldap_components(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ldap_url_parse() {
+        let ldap =
+            LdapUrl::parse("ldaps://ldap.example:636/ou=People,o=Example?cn,mail?sub?(uid=jdoe)")
+                .unwrap();
+        assert!(ldap.secure);
+        assert_eq!(ldap.host.as_deref(), Some("ldap.example"));
+        assert_eq!(ldap.port, Some(636));
+        assert_eq!(ldap.base_dn, "ou=People,o=Example");
+        assert_eq!(ldap.attributes, vec!["cn", "mail"]);
+        assert_eq!(ldap.scope, LdapScope::Sub);
+        assert_eq!(ldap.filter.as_deref(), Some("(uid=jdoe)"));
+
+        let ldap = LdapUrl::parse("ldap:///o=Example??one?(cn=Jean%20Dupont)?!e-bindname").unwrap();
+        assert_eq!(ldap.host, None);
+        assert!(ldap.attributes.is_empty());
+        assert_eq!(ldap.scope, LdapScope::One);
+        assert_eq!(ldap.filter.as_deref(), Some("(cn=Jean Dupont)"));
+        assert_eq!(ldap.extensions, vec!["!e-bindname"]);
+
+        let ldap = LdapUrl::parse("ldap://[2001:db8::7]:389").unwrap();
+        assert_eq!(ldap.host.as_deref(), Some("[2001:db8::7]"));
+        assert_eq!(ldap.port, Some(389));
+        assert_eq!(ldap.base_dn, "");
+
+        for invalid in [
+            "https://ldap.example/o=Example",
+            "ldap://ldap.example:port/o=Example",
+            "ldap://ldap.example/o=Example??subtree",
+            "ldap://ldap.example/o=Example%2",
+            "ldap://ldap.example/o=Example?????",
+        ] {
+            assert_eq!(LdapUrl::parse(invalid), None, "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+mod test {
+    use jscontact::Card;
+    use serde_json::json;
+
+    #[test]
+    fn test_display_minimal() {
+        let card = Card::new_with_latest_version("urn:uuid:1234");
+        assert_eq!(card.to_string(), "Card <urn:uuid:1234>");
+    }
+
+    #[test]
+    fn test_display_individual() {
+        let card = Card::try_from(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:1234",
+            "kind": "individual",
+            "name": {
+                "components": [
+                    { "kind": "given", "value": "John" },
+                    { "kind": "surname", "value": "Smith" }
+                ]
+            },
+            "emails": {
+                "e1": { "address": "john@example.com", "pref": 2 },
+                "e2": { "address": "jqpublic@xyz.example.com", "pref": 1 }
+            },
+            "phones": { "p1": { "number": "tel:+1-555-555-5555" } },
+            "organizations": {
+                "o2": { "name": "XYZ Corp" },
+                "o1": { "name": "ABC, Inc." }
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            card.to_string(),
+            "John Smith <jqpublic@xyz.example.com> (+1-555-555-5555) — ABC, Inc."
+        );
+
+        let mut card = card;
+        card.emails = None;
+        card.organizations = None;
+        assert_eq!(card.to_string(), "John Smith (+1-555-555-5555)");
+        card.name = None;
+        assert_eq!(card.to_string(), "(+1-555-555-5555)");
+    }
+
+    #[test]
+    fn test_display_org() {
+        let mut card = Card::try_from(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:1234",
+            "kind": "org",
+            "organizations": { "o1": { "name": "ABC, Inc." } },
+            "emails": { "e1": { "address": "info@abc.example.com" } }
+        }))
+        .unwrap();
+        // the organization names the Card, it is not repeated
+        assert_eq!(card.to_string(), "ABC, Inc. <info@abc.example.com>");
+        card.emails = None;
+        assert_eq!(card.to_string(), "ABC, Inc.");
+        card.kind = None;
+        assert_eq!(card.to_string(), "ABC, Inc.");
+    }
+}
@@ -0,0 +1,86 @@
+mod test {
+    use jscontact::{Card, CloneOptions, SelfRelation};
+
+    #[test]
+    fn test_clone_with_new_uid() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:1",
+            "created": "2022-09-30T14:35:10Z",
+            "updated": "2023-01-01T00:00:00Z",
+            "prodId": "ACME Contacts/1.0",
+            "name": { "full": "Jane Doe" },
+            "relatedTo": {
+                "urn:uuid:1": { "relation": { "me": true } },
+                "urn:uuid:3": { "relation": { "friend": true } }
+            },
+            "localizations": { "fr": { "name/full": "Jeanne Doe" } }
+        });
+        let card: Card = serde_json::from_value(json)?;
+        let original = card.clone();
+        let copy = card.clone_with_new_uid("urn:uuid:2");
+        assert_eq!(card, original);
+        assert_eq!(copy.uid, "urn:uuid:2");
+        assert_eq!(copy.created, None);
+        assert_eq!(copy.updated, None);
+        assert_eq!(copy.prod_id, None);
+        assert_eq!(copy.name, card.name);
+        let related_to = copy.related_to.as_ref().unwrap();
+        assert_eq!(related_to.len(), 1);
+        assert!(related_to.contains_key("urn:uuid:3"));
+        assert_eq!(
+            copy.get_localized("fr")?.name.unwrap().full.as_deref(),
+            Some("Jeanne Doe")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_clone_with_new_uid_options() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:1",
+            "relatedTo": {
+                "urn:uuid:1": { "relation": { "me": true } },
+                "urn:uuid:3": { "relation": { "friend": true } }
+            },
+            "localizations": { "fr": { "name/full": "Jeanne Doe" } }
+        });
+        let card: Card = serde_json::from_value(json)?;
+        let opts = CloneOptions {
+            self_relation: SelfRelation::Rewrite,
+            strip_localizations: true,
+        };
+        let copy = card.clone_with_new_uid_with("urn:uuid:2", &opts);
+        let related_to = copy.related_to.as_ref().unwrap();
+        assert_eq!(related_to.len(), 2);
+        assert_eq!(
+            related_to.get("urn:uuid:2"),
+            card.related_to.as_ref().unwrap().get("urn:uuid:1")
+        );
+        assert!(!related_to.contains_key("urn:uuid:1"));
+        assert!(copy.get_raw_localizations().is_none());
+        assert!(card.has_localization("fr"));
+        Ok(())
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_clone_with_random_uid() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:1",
+            "relatedTo": { "urn:uuid:1": { "relation": { "me": true } } }
+        });
+        let card: Card = serde_json::from_value(json)?;
+        let copy = card.clone_with_random_uid();
+        assert!(copy.uid.starts_with("urn:uuid:"));
+        assert_eq!(copy.uid.len(), "urn:uuid:".len() + 36);
+        assert_ne!(copy.uid, card.clone_with_random_uid().uid);
+        assert!(!copy.related_to.unwrap().contains_key("urn:uuid:1"));
+        Ok(())
+    }
+}
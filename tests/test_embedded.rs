@@ -0,0 +1,138 @@
+mod test {
+    use jscontact::{Card, CardKind, DEFAULT_MAX_DEPTH};
+    use serde_json::{json, Value};
+
+    /// An array nested `depth` times, built without recursion
+    fn nested(depth: usize) -> Value {
+        let mut value = json!(1);
+        for _ in 0..depth {
+            value = Value::Array(vec![value]);
+        }
+        value
+    }
+
+    fn assistant() -> Card {
+        serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:5678",
+            "kind": "individual",
+            "name": { "full": "John Smith" },
+            "emails": { "e1": { "address": "john@example.com" } }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_extension_card_round_trip() {
+        let mut card = Card::new_with_latest_version("urn:uuid:1234");
+        card.set_extension_card("example.com:assistant", &assistant())
+            .unwrap();
+        assert_eq!(
+            card.extensions["example.com:assistant"]["name"]["full"],
+            "John Smith"
+        );
+
+        let json = serde_json::to_string(&card).unwrap();
+        let parsed: Card = serde_json::from_str(&json).unwrap();
+        let embedded = parsed
+            .extension_as_card("example.com:assistant")
+            .unwrap()
+            .unwrap();
+        assert_eq!(embedded, assistant());
+        assert_eq!(embedded.kind, Some(CardKind::Individual));
+        assert!(parsed.extension_as_card("example.com:manager").is_none());
+    }
+
+    #[test]
+    fn test_extension_card_nested() {
+        let mut assistant = assistant();
+        let mut manager = Card::new_with_latest_version("urn:uuid:9999");
+        manager
+            .extensions
+            .insert("example.com:level".to_string(), json!(2));
+        assistant
+            .set_extension_card("example.com:manager", &manager)
+            .unwrap();
+        let mut card = Card::new_with_latest_version("urn:uuid:1234");
+        card.set_extension_card("example.com:assistant", &assistant)
+            .unwrap();
+
+        let embedded = card
+            .extension_as_card("example.com:assistant")
+            .unwrap()
+            .unwrap();
+        let manager = embedded
+            .extension_as_card("example.com:manager")
+            .unwrap()
+            .unwrap();
+        assert_eq!(manager.uid, "urn:uuid:9999");
+        assert_eq!(manager.extensions["example.com:level"], 2);
+    }
+
+    #[test]
+    fn test_extension_card_invalid() {
+        let mut card = Card::new_with_latest_version("urn:uuid:1234");
+        card.extensions.insert(
+            "example.com:assistant".to_string(),
+            json!({ "@type": "Card", "version": "1.0" }),
+        );
+        let error = card
+            .extension_as_card("example.com:assistant")
+            .unwrap()
+            .unwrap_err();
+        assert!(error.contains("uid"), "{}", error);
+        card.extensions
+            .insert("example.com:assistant".to_string(), json!("John"));
+        assert!(card
+            .extension_as_card("example.com:assistant")
+            .unwrap()
+            .is_err());
+    }
+
+    #[test]
+    fn test_extension_card_depth() {
+        // the embedded Card is one level deeper than its own values
+        let mut deep = Card::new_with_latest_version("urn:uuid:5678");
+        deep.extensions.insert(
+            "example.com:deep".to_string(),
+            nested(DEFAULT_MAX_DEPTH - 2),
+        );
+        let mut card = Card::new_with_latest_version("urn:uuid:1234");
+        card.set_extension_card("example.com:assistant", &deep)
+            .unwrap();
+        assert!(card.check_depth(DEFAULT_MAX_DEPTH).is_ok());
+        assert!(card
+            .extension_as_card("example.com:assistant")
+            .unwrap()
+            .is_ok());
+
+        deep.extensions.insert(
+            "example.com:deep".to_string(),
+            nested(DEFAULT_MAX_DEPTH - 1),
+        );
+        assert!(deep.check_depth(DEFAULT_MAX_DEPTH).is_ok());
+        assert_eq!(
+            card.set_extension_card("example.com:assistant", &deep)
+                .unwrap_err(),
+            format!(
+                "The value of 'example.com:assistant/example.com:deep' is nested deeper than {} levels",
+                DEFAULT_MAX_DEPTH
+            )
+        );
+
+        card.extensions.insert(
+            "example.com:assistant".to_string(),
+            json!({ "uid": "urn:uuid:5678", "example.com:deep": nested(DEFAULT_MAX_DEPTH - 1) }),
+        );
+        assert_eq!(
+            card.extension_as_card("example.com:assistant")
+                .unwrap()
+                .unwrap_err(),
+            format!(
+                "The value of 'example.com:assistant' is nested deeper than {} levels",
+                DEFAULT_MAX_DEPTH
+            )
+        );
+    }
+}
@@ -0,0 +1,103 @@
+mod test {
+    use jscontact::{
+        Calendar, Card, CryptoKey, Directory, EmailAddress, EmptyValue, Link, Media, MediaKind,
+        Nickname, Note, OrgUnit, PersonalInfo, PersonalInfoKind, Phone, SchedulingAddress, Title,
+        ValidationCode,
+    };
+    use serde_json::json;
+
+    #[test]
+    fn test_new_checked() {
+        for empty in ["", " \t\n"] {
+            let errors = [
+                Nickname::new_checked(empty).unwrap_err(),
+                OrgUnit::new_checked(empty).unwrap_err(),
+                Title::new_checked(empty).unwrap_err(),
+                EmailAddress::new_checked(empty).unwrap_err(),
+                Phone::new_checked(empty).unwrap_err(),
+                Calendar::new_checked(empty).unwrap_err(),
+                SchedulingAddress::new_checked(empty).unwrap_err(),
+                CryptoKey::new_checked(empty).unwrap_err(),
+                Directory::new_checked(empty).unwrap_err(),
+                Link::new_checked(empty).unwrap_err(),
+                Media::new_checked(empty, MediaKind::Photo).unwrap_err(),
+                Note::new_checked(empty).unwrap_err(),
+                PersonalInfo::new_checked(PersonalInfoKind::Hobby, empty).unwrap_err(),
+            ];
+            let fields: Vec<&str> = errors.iter().map(|error| error.field).collect();
+            assert_eq!(
+                fields,
+                [
+                    "name", "name", "name", "address", "number", "uri", "uri", "uri", "uri", "uri",
+                    "uri", "note", "value"
+                ]
+            );
+        }
+        assert_eq!(
+            Phone::new_checked("").unwrap_err().to_string(),
+            "The number must not be empty"
+        );
+        assert_eq!(
+            Phone::new_checked("tel:+1-555-555-5555").unwrap(),
+            Phone::new("tel:+1-555-555-5555")
+        );
+        assert_eq!(
+            Note::new_checked(" ").unwrap_err(),
+            EmptyValue { field: "note" }
+        );
+    }
+
+    #[test]
+    fn test_validate_empty_values() {
+        let (card, errors) = Card::from_value_collecting(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "nicknames": { "k1": { "name": "" } },
+            "organizations": { "o1": { "name": "ABC", "units": [{ "name": "Sales" }, { "name": " " }] } },
+            "titles": { "t1": { "name": "" } },
+            "emails": { "e1": { "address": "" }, "e2": { "address": "jane@example.com" } },
+            "onlineServices": { "s1": { "uri": "" }, "s2": { "service": "Mastodon" } },
+            "phones": { "p1": { "number": "\t" } },
+            "calendars": { "c1": { "uri": "" } },
+            "schedulingAddresses": { "s1": { "uri": "" } },
+            "cryptoKeys": { "k1": { "uri": "" } },
+            "directories": { "d1": { "uri": "" } },
+            "links": { "l1": { "uri": "" } },
+            "media": { "m1": { "kind": "photo", "uri": "" } },
+            "notes": { "n1": { "note": "" } },
+            "personalInfo": { "p1": { "kind": "hobby", "value": "" } },
+        }));
+        assert!(errors.is_empty());
+        let report = card.unwrap().validate();
+        assert!(report
+            .errors
+            .iter()
+            .all(|error| error.code == ValidationCode::EmptyValue));
+        let paths: Vec<&str> = report
+            .errors
+            .iter()
+            .map(|error| error.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "nicknames/k1/name",
+                "organizations/o1/units/1/name",
+                "titles/t1/name",
+                "emails/e1/address",
+                "onlineServices/s1/uri",
+                "phones/p1/number",
+                "calendars/c1/uri",
+                "schedulingAddresses/s1/uri",
+                "cryptoKeys/k1/uri",
+                "directories/d1/uri",
+                "links/l1/uri",
+                "media/m1/uri",
+                "notes/n1/note",
+                "personalInfo/p1/value",
+            ]
+        );
+        assert_eq!(report.errors[5].message, "number must not be empty");
+    }
+}
@@ -0,0 +1,205 @@
+// The expected values of tests/enums/enum_values.json come from the IANA JSContact registries (RFC 9553),
+// snapshotted in src/registry.rs, and from the stable codes of the validation report.
+// When adding a variant, add its value to the fixtures file: it is the contract of the wire format.
+
+/// Build the list of the variants of an enum, failing to compile if a variant is missing
+macro_rules! variants {
+    ($enum:ident: $($variant:ident),+ $(; $other:pat)?) => {{
+        let exhaustive = |value: &$enum| match value {
+            $($enum::$variant => (),)+
+            $($other => (),)?
+        };
+        let variants = vec![$($enum::$variant),+];
+        variants.iter().for_each(exhaustive);
+        (stringify!($enum), to_values(&variants), from_values::<$enum>)
+    }};
+}
+
+/// Serialize the variants of an enum
+fn to_values<T: serde::Serialize>(variants: &[T]) -> Vec<String> {
+    variants
+        .iter()
+        .map(|variant| match serde_json::to_value(variant).unwrap() {
+            serde_json::Value::String(value) => value,
+            value => panic!("{} is not a string", value),
+        })
+        .collect()
+}
+
+/// Deserialize values and serialize them back
+fn from_values<T: serde::Serialize + serde::de::DeserializeOwned>(
+    values: &[String],
+) -> Vec<String> {
+    let variants: Vec<T> = values
+        .iter()
+        .map(|value| serde_json::from_value(serde_json::Value::from(value.as_str())).unwrap())
+        .collect();
+    to_values(&variants)
+}
+
+mod test {
+    use super::{from_values, to_values};
+    use jscontact::registry::{registered_values, ENUM_VALUES};
+    use jscontact::{
+        AddressComponentKind, AddressContext, AnniversaryKind, CalendarKind, CardKind, CardVersion,
+        Context, DirectoryKind, GrammaticalGender, LinkKind, MediaKind, NameComponentKind,
+        PersonalInfoKind, PersonalInfoLevel, PhoneFeature, PhoneticSystem, RelationshipType,
+        TitleKind, ValidationCode, ValidationSeverity,
+    };
+    use std::collections::BTreeMap;
+
+    /// name of the enum, serialized variants and round trip of the values
+    type EnumValues = (&'static str, Vec<String>, fn(&[String]) -> Vec<String>);
+
+    fn all_enums() -> Vec<EnumValues> {
+        vec![
+            variants!(AddressComponentKind: Apartment, Block, Building, Country, Direction,
+                District, Floor, Landmark, Locality, Name, Number, Postcode, PostOfficeBox,
+                Region, Room, Separator, Subdistrict),
+            variants!(AddressContext: Billing, Delivery, Private, Work),
+            // vendor-specific kinds are kept as is, see test_anniversary_vendor_kind
+            variants!(AnniversaryKind: Birth, Death, Wedding; AnniversaryKind::Other(_)),
+            variants!(CalendarKind: Calendar, FreeBusy),
+            variants!(CardKind: Application, Device, Group, Individual, Location, Org),
+            variants!(CardVersion: OneDotZero; CardVersion::Other(_)),
+            variants!(Context: Private, Work; Context::Other(_)),
+            variants!(DirectoryKind: Directory, Entry),
+            variants!(GrammaticalGender: Animate, Common, Feminine, Inanimate, Masculine, Neuter),
+            variants!(LinkKind: Contact),
+            variants!(MediaKind: Photo, Sound, Logo),
+            variants!(NameComponentKind: Credential, Generation, Given, Given2, Separator,
+                Surname, Surname2, Title),
+            variants!(PersonalInfoKind: Expertise, Hobby, Interest),
+            variants!(PersonalInfoLevel: High, Medium, Low),
+            variants!(PhoneFeature: Fax, MainNumber, Mobile, Pager, Text, Textphone, Video, Voice;
+                PhoneFeature::Other(_)),
+            variants!(PhoneticSystem: Ipa, Jyut, Piny),
+            variants!(RelationshipType: Acquaintance, Agent, Child, CoResident, CoWorker,
+                Colleague, Contact, Crush, Date, Emergency, Friend, Kin, Me, Met, Muse, Neighbor,
+                Parent, Sibling, Spouse, Sweetheart),
+            variants!(TitleKind: Role, Title),
+            variants!(ValidationCode: DefaultSeparatorNotOrdered, SeparatorNotOrdered,
+                ConsecutiveSeparators, PrefOutOfRange, ListAsOutOfRange, UnknownPlaceRef, EmptySpeakToAs,
+                EmptyPronouns, EmptyValue, LanguageNotPreferred, LocalizationOfCardLanguage, DuplicatePref,
+                ControlCharacter),
+            variants!(ValidationSeverity: Error, Warning),
+        ]
+    }
+
+    #[test]
+    fn test_enum_values() {
+        let expected: BTreeMap<String, Vec<String>> =
+            serde_json::from_slice(include_bytes!("./enums/enum_values.json")).unwrap();
+        let enums = all_enums();
+        let names: Vec<&str> = enums.iter().map(|(name, _, _)| *name).collect();
+        assert_eq!(names, expected.keys().collect::<Vec<_>>());
+        for (name, values, round_trip) in enums {
+            assert_eq!(values, expected[name], "{}", name);
+            assert_eq!(round_trip(&values), values, "{}", name);
+        }
+    }
+
+    /// The registered properties of the enums, as (object type, property)
+    fn registered_properties(name: &str) -> Vec<(&'static str, &'static str)> {
+        match name {
+            "AddressComponentKind" => vec![("AddressComponent", "kind")],
+            "AddressContext" => vec![("Address", "contexts")],
+            "AnniversaryKind" => vec![("Anniversary", "kind")],
+            "CalendarKind" => vec![("Calendar", "kind")],
+            "CardKind" => vec![("Card", "kind")],
+            "Context" => [
+                "Calendar",
+                "CryptoKey",
+                "Directory",
+                "EmailAddress",
+                "LanguagePref",
+                "Link",
+                "Media",
+                "Nickname",
+                "OnlineService",
+                "Organization",
+                "Phone",
+                "Pronouns",
+                "SchedulingAddress",
+            ]
+            .into_iter()
+            .map(|context| (context, "contexts"))
+            .collect(),
+            "DirectoryKind" => vec![("Directory", "kind")],
+            "GrammaticalGender" => vec![("SpeakToAs", "grammaticalGender")],
+            "LinkKind" => vec![("Link", "kind")],
+            "MediaKind" => vec![("Media", "kind")],
+            "NameComponentKind" => vec![("NameComponent", "kind")],
+            "PersonalInfoKind" => vec![("PersonalInfo", "kind")],
+            "PersonalInfoLevel" => vec![("PersonalInfo", "level")],
+            "PhoneFeature" => vec![("Phone", "features")],
+            "PhoneticSystem" => vec![("Address", "phoneticSystem"), ("Name", "phoneticSystem")],
+            "RelationshipType" => vec![("Relation", "relation")],
+            "TitleKind" => vec![("Title", "kind")],
+            // the versions have their own registry, the validation codes are not registered
+            "CardVersion" | "ValidationCode" | "ValidationSeverity" => vec![],
+            _ => panic!("{} is not mapped to the registry", name),
+        }
+    }
+
+    #[test]
+    fn test_enum_values_registered() {
+        let mut checked = Vec::new();
+        for (name, mut values, _) in all_enums() {
+            values.sort();
+            for (context, property) in registered_properties(name) {
+                let mut registered = registered_values(context, property, "1.0");
+                registered.sort();
+                assert_eq!(values, registered, "{} for {}.{}", name, context, property);
+                checked.push((context, property));
+            }
+        }
+        // every registered value of 1.0 has a variant
+        for entry in ENUM_VALUES {
+            assert_eq!(entry.since, "1.0");
+            for context in entry.contexts {
+                assert!(
+                    checked.contains(&(*context, entry.property)),
+                    "{}.{} has no enum",
+                    context,
+                    entry.property
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_registered_values() {
+        assert_eq!(
+            registered_values("Address", "contexts", "1.0"),
+            vec!["billing", "delivery", "private", "work"]
+        );
+        assert_eq!(registered_values("Link", "kind", "1.1"), vec!["contact"]);
+        assert!(registered_values("Card", "kind", "0.9").is_empty());
+        assert!(registered_values("Card", "uid", "1.0").is_empty());
+    }
+
+    #[test]
+    fn test_enum_values_unknown() {
+        // the anniversary kinds, the contexts and the phone features keep the vendor-specific values
+        for value in [
+            "postofficebox",
+            "mainNumber",
+            "coWorker",
+            "freebusy",
+            "Work",
+        ] {
+            let value = serde_json::Value::from(value);
+            assert!(serde_json::from_value::<AddressComponentKind>(value.clone()).is_err());
+            let other = PhoneFeature::Other(value.as_str().unwrap().to_string());
+            assert_eq!(
+                serde_json::from_value::<PhoneFeature>(value.clone()).unwrap(),
+                other
+            );
+            assert!(serde_json::from_value::<RelationshipType>(value.clone()).is_err());
+            assert!(serde_json::from_value::<CalendarKind>(value.clone()).is_err());
+            let other = Context::Other(value.as_str().unwrap().to_string());
+            assert_eq!(serde_json::from_value::<Context>(value).unwrap(), other);
+        }
+    }
+}
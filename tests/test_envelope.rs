@@ -0,0 +1,72 @@
+mod test {
+    use jscontact::Card;
+    use serde_json::{json, Map, Value};
+
+    #[test]
+    fn test_serialize_into() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:1",
+            "name": { "full": "Jane Doe" },
+            "example.com:flag": true
+        }))?;
+        let mut object = json!({
+            "id": "c1",
+            "addressBookIds": { "b1": true },
+            "uid": "urn:uuid:old",
+            "name": null
+        })
+        .as_object()
+        .unwrap()
+        .clone();
+        let overwritten = card.serialize_into(&mut object)?;
+        assert_eq!(overwritten, ["name", "uid"]);
+        assert_eq!(
+            Value::Object(object),
+            json!({
+                "id": "c1",
+                "addressBookIds": { "b1": true },
+                "uid": "urn:uuid:1",
+                "name": { "full": "Jane Doe" },
+                "@type": "Card",
+                "version": "1.0",
+                "example.com:flag": true
+            })
+        );
+
+        let mut empty = Map::new();
+        assert!(card.serialize_into(&mut empty)?.is_empty());
+        assert_eq!(Value::Object(empty), serde_json::to_value(&card)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_from() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:1",
+            "name": { "full": "Jane Doe" }
+        }))?;
+        let mut object = Map::new();
+        object.insert("id".to_string(), json!("c1"));
+        object.insert("addressBookIds".to_string(), json!({ "b1": true }));
+        card.serialize_into(&mut object)?;
+
+        let ignore = ["id", "addressBookIds"];
+        assert_eq!(Card::deserialize_from(&object, &ignore)?, card);
+        // the object is left as is
+        assert_eq!(object["id"], "c1");
+
+        // without ignoring them, the server fields are unknown properties
+        let parsed = Card::deserialize_from(&object, &[])?;
+        assert_eq!(parsed.extensions["id"], "c1");
+        assert_eq!(parsed.extensions["addressBookIds"], json!({ "b1": true }));
+
+        object.remove("uid");
+        let error = Card::deserialize_from(&object, &ignore).unwrap_err();
+        assert!(error.to_string().contains("uid"));
+        Ok(())
+    }
+}
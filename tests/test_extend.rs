@@ -0,0 +1,192 @@
+mod test {
+    use jscontact::{
+        Card, CardBuilder, ContentHash, Context, EmailAddress, KeyGen, Nickname, PersonalInfo,
+        PersonalInfoKind, Phone, Sequential, SmallString,
+    };
+    use serde_json::{json, Map, Value};
+    use std::collections::HashMap;
+
+    fn keys<V>(map: &Option<HashMap<SmallString, V>>) -> Vec<String> {
+        let mut keys: Vec<String> = map
+            .iter()
+            .flatten()
+            .map(|(key, _)| key.to_string())
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_extend_twice() {
+        let mut card = Card::new_with_latest_version("my:uri");
+        card.extend_emails(["jane@example.com", "jane@work.example.com"].map(EmailAddress::new));
+        assert_eq!(keys(&card.emails), ["e1", "e2"]);
+        card.extend_emails([EmailAddress::new("j.doe@example.com")]);
+        assert_eq!(keys(&card.emails), ["e1", "e2", "e3"]);
+        let emails = card.emails.as_ref().unwrap();
+        assert_eq!(emails[&SmallString::from("e1")].address, "jane@example.com");
+        assert_eq!(
+            emails[&SmallString::from("e3")].address,
+            "j.doe@example.com"
+        );
+    }
+
+    #[test]
+    fn test_extend_existing_keys() {
+        let mut card = Card::new_with_latest_version("my:uri");
+        card.extend_phones_keyed([
+            ("tel7", Phone::new("tel:+15550007")),
+            ("work", Phone::new("tel:+15550100")),
+            ("tel2", Phone::new("tel:+15550002")),
+        ]);
+        card.extend_phones([Phone::new("tel:+15550008"), Phone::new("tel:+15550009")]);
+        assert_eq!(keys(&card.phones), ["tel2", "tel7", "tel8", "tel9", "work"]);
+        let phones = card.phones.as_ref().unwrap();
+        assert_eq!(phones[&SmallString::from("tel7")].number, "tel:+15550007");
+        assert_eq!(phones[&SmallString::from("tel9")].number, "tel:+15550009");
+        assert_eq!(phones[&SmallString::from("work")].number, "tel:+15550100");
+
+        // a keyed value replaces the entry with the same key
+        card.extend_phones_keyed([(String::from("work"), Phone::new("tel:+15550200"))]);
+        let phones = card.phones.as_ref().unwrap();
+        assert_eq!(phones.len(), 5);
+        assert_eq!(phones[&SmallString::from("work")].number, "tel:+15550200");
+    }
+
+    #[test]
+    fn test_extend_empty() {
+        let mut card = Card::new_with_latest_version("my:uri");
+        card.extend_notes([]);
+        card.extend_titles_keyed(Vec::<(String, _)>::new());
+        assert_eq!(card.notes, None);
+        assert_eq!(card.titles, None);
+    }
+
+    #[test]
+    fn test_extend_trait() {
+        let mut card = Card::new_with_latest_version("my:uri");
+        card.extend(["Johnny", "JD"].map(Nickname::new));
+        card.extend([PersonalInfo::new(PersonalInfoKind::Hobby, "chess")]);
+        assert_eq!(keys(&card.nicknames), ["k1", "k2"]);
+        assert_eq!(keys(&card.personal_info), ["pi1"]);
+    }
+
+    fn email(address: &str, contexts: &[Context]) -> EmailAddress {
+        let mut email = EmailAddress::new(address);
+        if !contexts.is_empty() {
+            email.contexts = Some(contexts.iter().map(|c| (c.clone(), true)).collect());
+        }
+        email
+    }
+
+    #[test]
+    fn test_extend_with_sequential() {
+        let mut card = Card::new_with_latest_version("my:uri");
+        card.extend_emails_keyed([
+            ("e1", EmailAddress::new("a@example.com")),
+            ("e4", EmailAddress::new("b@example.com")),
+            ("work", EmailAddress::new("c@example.com")),
+        ]);
+        card.extend_emails_with(
+            ["d@example.com", "e@example.com"].map(EmailAddress::new),
+            &Sequential::default(),
+        );
+        assert_eq!(keys(&card.emails), ["e1", "e4", "e5", "e6", "work"]);
+        card.extend_emails_with(
+            [EmailAddress::new("f@example.com")],
+            &Sequential("mail".to_string()),
+        );
+        assert_eq!(
+            keys(&card.emails),
+            ["e1", "e4", "e5", "e6", "mail1", "work"]
+        );
+        let emails = card.emails.as_ref().unwrap();
+        assert_eq!(emails[&SmallString::from("e4")].address, "b@example.com");
+        assert_eq!(emails[&SmallString::from("mail1")].address, "f@example.com");
+    }
+
+    #[test]
+    fn test_extend_with_content_hash() {
+        let entries = || {
+            [
+                email("jane@example.com", &[Context::Work, Context::Private]),
+                email("jane.doe@example.org", &[]),
+            ]
+        };
+        let mut card = Card::new_with_latest_version("my:uri");
+        card.extend_emails_with(entries(), &ContentHash);
+        let first = keys(&card.emails);
+        assert_eq!(first.len(), 2);
+        assert!(first
+            .iter()
+            .all(|key| key.starts_with('e') && key.len() == 17));
+
+        // importing the same entries again changes nothing
+        card.extend_emails_with(entries(), &ContentHash);
+        assert_eq!(keys(&card.emails), first);
+        // in another Card, the contexts being inserted in another order
+        let mut other = Card::new_with_latest_version("my:uri");
+        other.extend_emails_with(
+            [
+                email("jane.doe@example.org", &[]),
+                email("jane@example.com", &[Context::Private, Context::Work]),
+            ],
+            &ContentHash,
+        );
+        assert_eq!(keys(&other.emails), first);
+        assert_eq!(other.emails, card.emails);
+
+        // a different entry gets another key
+        card.extend_emails_with([email("jane@example.com", &[Context::Work])], &ContentHash);
+        assert_eq!(keys(&card.emails).len(), 3);
+    }
+
+    #[test]
+    fn test_content_hash_stable() {
+        let entry = json!({ "@type": "EmailAddress", "address": "jane@example.com" });
+        let key = ContentHash.key("e", &entry, &Map::new());
+        assert_eq!(key, "ee162aa2d3f2ba37c");
+        let reordered = json!({ "address": "jane@example.com", "@type": "EmailAddress" });
+        assert_eq!(ContentHash.key("e", &reordered, &Map::new()), key);
+
+        // a different entry with the same key: a suffix
+        let mut entries = Map::new();
+        entries.insert(key.clone(), Value::from("another entry"));
+        assert_eq!(ContentHash.key("e", &entry, &entries), format!("{}-2", key));
+        entries.insert(format!("{}-2", key), entry.clone());
+        assert_eq!(ContentHash.key("e", &entry, &entries), format!("{}-2", key));
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_extend_with_uuid() {
+        let mut card = Card::new_with_latest_version("my:uri");
+        card.extend_phones_with(
+            [Phone::new("tel:+15550001"), Phone::new("tel:+15550001")],
+            &jscontact::Uuid,
+        );
+        let keys = keys(&card.phones);
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().all(|key| key.len() == 36));
+    }
+
+    #[test]
+    fn test_builder_key_gen() {
+        let card = CardBuilder::new("my:uri")
+            .with_emails([EmailAddress::new("jane@example.com")])
+            .with_phones([Phone::new("tel:+15550001"), Phone::new("tel:+15550002")])
+            .build();
+        assert_eq!(keys(&card.emails), ["e1"]);
+        assert_eq!(keys(&card.phones), ["tel1", "tel2"]);
+
+        let build = || {
+            CardBuilder::new("my:uri")
+                .with_key_gen(ContentHash)
+                .with_nicknames(["Johnny", "JD"].map(Nickname::new))
+                .build()
+        };
+        let card = build();
+        assert_eq!(keys(&card.nicknames).len(), 2);
+        assert_eq!(keys(&card.nicknames), keys(&build().nicknames));
+    }
+}
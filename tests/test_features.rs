@@ -0,0 +1,113 @@
+// The serialized JSON does not depend on the features of the crate: two crates enabling different features
+// in the same dependency graph must see the same bytes. Each combination of `typed` and `jsonptr` runs
+// these tests against the same expected bytes.
+
+mod test {
+    use jscontact::{
+        Card, Context, EmailAddress, KeyOrder, Name, NameComponent, NameComponentKind,
+        SerializeOptions, TypeMembers,
+    };
+
+    #[cfg(all(feature = "typed", feature = "jsonptr"))]
+    const COMBINATION: &str = "typed, jsonptr";
+    #[cfg(all(feature = "typed", not(feature = "jsonptr")))]
+    const COMBINATION: &str = "typed";
+    #[cfg(all(not(feature = "typed"), feature = "jsonptr"))]
+    const COMBINATION: &str = "jsonptr";
+    #[cfg(all(not(feature = "typed"), not(feature = "jsonptr")))]
+    const COMBINATION: &str = "none";
+
+    /// The reference Card, built with the constructors
+    fn reference() -> Card {
+        let mut card = Card::new_with_latest_version("urn:uuid:1234");
+        let mut name = Name::default();
+        name.components = Some(vec![
+            NameComponent::new(NameComponentKind::Given, "Jane"),
+            NameComponent::new(NameComponentKind::Surname, "Doe"),
+        ]);
+        card.name = Some(name);
+        let mut email = EmailAddress::new("jane@example.com");
+        email.contexts = Some([(Context::Work, true)].into());
+        card.emails = Some([("e1".into(), email)].into());
+        card
+    }
+
+    fn to_string(card: &Card, type_members: TypeMembers) -> String {
+        let opts = SerializeOptions {
+            key_order: KeyOrder::Alphabetical,
+            type_members,
+            ..Default::default()
+        };
+        card.to_string_with(&opts).unwrap()
+    }
+
+    const TYPED: &str = concat!(
+        r#"{"@type":"Card","emails":{"e1":{"@type":"EmailAddress","address":"jane@example.com","contexts":{"work":true}}},"#,
+        r#""name":{"@type":"Name","components":[{"@type":"NameComponent","kind":"given","value":"Jane"},"#,
+        r#"{"@type":"NameComponent","kind":"surname","value":"Doe"}]},"uid":"urn:uuid:1234","version":"1.0"}"#
+    );
+
+    const UNTYPED: &str = concat!(
+        r#"{"@type":"Card","emails":{"e1":{"address":"jane@example.com","contexts":{"work":true}}},"#,
+        r#""name":{"components":[{"kind":"given","value":"Jane"},{"kind":"surname","value":"Doe"}]},"#,
+        r#""uid":"urn:uuid:1234","version":"1.0"}"#
+    );
+
+    #[test]
+    fn test_features_serialized_bytes() {
+        let card = reference();
+        assert_eq!(
+            to_string(&card, TypeMembers::Keep),
+            TYPED,
+            "{}",
+            COMBINATION
+        );
+        assert_eq!(
+            to_string(&card, TypeMembers::Omit),
+            UNTYPED,
+            "{}",
+            COMBINATION
+        );
+        // the plain serialization keeps the @type members
+        let plain: serde_json::Value = serde_json::to_value(&card).unwrap();
+        assert_eq!(
+            plain,
+            serde_json::from_str::<serde_json::Value>(TYPED).unwrap(),
+            "{}",
+            COMBINATION
+        );
+    }
+
+    #[test]
+    fn test_features_read_both() {
+        // the @type members are read if present, and kept as read
+        let typed: Card = serde_json::from_str(TYPED).unwrap();
+        assert_eq!(typed, reference(), "{}", COMBINATION);
+        assert_eq!(
+            to_string(&typed, TypeMembers::Keep),
+            TYPED,
+            "{}",
+            COMBINATION
+        );
+        let untyped: Card = serde_json::from_str(UNTYPED).unwrap();
+        assert_eq!(
+            to_string(&untyped, TypeMembers::Keep),
+            UNTYPED,
+            "{}",
+            COMBINATION
+        );
+        assert_eq!(
+            to_string(&untyped, TypeMembers::Omit),
+            UNTYPED,
+            "{}",
+            COMBINATION
+        );
+        // a wrong @type is rejected
+        let wrong = TYPED.replace(r#""@type":"EmailAddress""#, r#""@type":"Phone""#);
+        assert!(
+            serde_json::from_str::<Card>(&wrong).is_err(),
+            "{}",
+            COMBINATION
+        );
+    }
+}
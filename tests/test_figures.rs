@@ -0,0 +1,37 @@
+// The figures of RFC 9553 are generated by build.rs into OUT_DIR from tests/rfc9553/raws,
+// this test checks that the figures of tests/rfc9553 used by the other tests are up to date.
+use std::path::Path;
+
+/// Environment variable used to copy the generated figures into tests/rfc9553 instead of comparing them
+const UPDATE_VAR: &str = "UPDATE_FIGURES";
+
+mod test {
+    use super::{Path, UPDATE_VAR};
+
+    #[test]
+    fn test_figures() {
+        let generated = Path::new(env!("OUT_DIR")).join("rfc9553");
+        let figures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/rfc9553");
+        let entries = std::fs::read_dir(&generated)
+            .expect("no generated figures, run `python tests/get_figures.py` first");
+        let update = std::env::var_os(UPDATE_VAR).is_some();
+        let mut outdated = Vec::new();
+        for entry in entries {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap();
+            let contents = std::fs::read_to_string(&path).unwrap();
+            if update {
+                std::fs::write(figures.join(name), &contents).unwrap();
+            } else if std::fs::read_to_string(figures.join(name)).ok() != Some(contents) {
+                outdated.push(name.to_string_lossy().to_string());
+            }
+        }
+        outdated.sort();
+        assert!(
+            outdated.is_empty(),
+            "outdated figures in tests/rfc9553: {}\nrun `{}=1 cargo test --test test_figures` to update them",
+            outdated.join(", "),
+            UPDATE_VAR
+        );
+    }
+}
@@ -0,0 +1,280 @@
+mod test {
+    use jscontact::{Card, FixApplied, FixRules};
+    use serde_json::{json, Value};
+
+    fn fix(path: &str, before: Value, after: Value) -> FixApplied {
+        FixApplied {
+            path: path.to_string(),
+            before,
+            after,
+        }
+    }
+
+    fn only(rules: impl FnOnce(&mut FixRules)) -> FixRules {
+        let mut only = FixRules::none();
+        rules(&mut only);
+        only
+    }
+
+    #[test]
+    fn test_zero_pref() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "emails": { "e1": { "address": "jane@example.com", "pref": 0 } },
+            "speakToAs": { "pronouns": { "k19": { "pronouns": "they/them", "pref": 0 } } },
+            "phones": { "tel0": { "number": "+15551234567", "pref": 2 } }
+        }))?;
+        let fixes = card.fix_common_issues(&only(|rules| rules.zero_pref = true));
+        assert_eq!(
+            fixes,
+            vec![
+                fix("emails/e1/pref", json!(0), json!(1)),
+                fix("speakToAs/pronouns/k19/pref", json!(0), json!(1)),
+            ]
+        );
+        let value = serde_json::to_value(&card)?;
+        assert_eq!(value["emails"]["e1"]["pref"], 1);
+        assert_eq!(value["speakToAs"]["pronouns"]["k19"]["pref"], 1);
+        assert_eq!(value["phones"]["tel0"]["pref"], 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_country_code_case() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "addresses": {
+                "k23": { "countryCode": "de" },
+                "k24": { "countryCode": "Fr" },
+                "k25": { "countryCode": "US" },
+                "k26": { "countryCode": "usa" }
+            }
+        }))?;
+        let fixes = card.fix_common_issues(&only(|rules| rules.country_code_case = true));
+        assert_eq!(
+            fixes,
+            vec![
+                fix("addresses/k23/countryCode", json!("de"), json!("DE")),
+                fix("addresses/k24/countryCode", json!("Fr"), json!("FR")),
+            ]
+        );
+        let value = serde_json::to_value(&card)?;
+        assert_eq!(value["addresses"]["k23"]["countryCode"], "DE");
+        // not a country code, left to the validation
+        assert_eq!(value["addresses"]["k26"]["countryCode"], "usa");
+        Ok(())
+    }
+
+    #[test]
+    fn test_utc_offset() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "created": "2022-09-30T14:35:10+00:00",
+            "updated": "2022-10-01T08:00:00Z",
+            "notes": { "n1": { "note": "Open office", "created": "2022-11-23T15:01:32+00:00" } },
+            "anniversaries": {
+                "k8": { "kind": "birth", "date": { "@type": "Timestamp", "utc": "1953-10-15T23:10:00+00:00" } },
+                "k9": { "kind": "birth", "date": { "@type": "Timestamp", "utc": "1953-10-15T23:10:00+02:00" } }
+            }
+        }))?;
+        let fixes = card.fix_common_issues(&only(|rules| rules.utc_offset = true));
+        assert_eq!(
+            fixes,
+            vec![
+                fix(
+                    "anniversaries/k8/date/utc",
+                    json!("1953-10-15T23:10:00+00:00"),
+                    json!("1953-10-15T23:10:00Z")
+                ),
+                fix(
+                    "created",
+                    json!("2022-09-30T14:35:10+00:00"),
+                    json!("2022-09-30T14:35:10Z")
+                ),
+                fix(
+                    "notes/n1/created",
+                    json!("2022-11-23T15:01:32+00:00"),
+                    json!("2022-11-23T15:01:32Z")
+                ),
+            ]
+        );
+        assert_eq!(card.created.as_deref(), Some("2022-09-30T14:35:10Z"));
+        assert_eq!(card.updated.as_deref(), Some("2022-10-01T08:00:00Z"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_partial_date_zero() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "anniversaries": {
+                "k8": { "kind": "birth", "date": { "year": 1953, "month": 0, "day": 15 } },
+                "k9": { "kind": "birth", "date": { "year": 1953, "month": 10, "day": 0 } },
+                "k10": { "kind": "birth", "date": { "year": 1953, "month": 10, "day": 15 } }
+            }
+        }))?;
+        let fixes = card.fix_common_issues(&only(|rules| rules.partial_date_zero = true));
+        assert_eq!(
+            fixes,
+            vec![
+                fix("anniversaries/k8/date/day", json!(15), Value::Null),
+                fix("anniversaries/k8/date/month", json!(0), Value::Null),
+                fix("anniversaries/k9/date/day", json!(0), Value::Null),
+            ]
+        );
+        let value = serde_json::to_value(&card)?;
+        let anniversaries = &value["anniversaries"];
+        assert_eq!(anniversaries["k8"]["date"]["year"], 1953);
+        assert_eq!(anniversaries["k8"]["date"].get("month"), None);
+        assert_eq!(anniversaries["k8"]["date"].get("day"), None);
+        assert_eq!(anniversaries["k9"]["date"]["month"], 10);
+        assert_eq!(anniversaries["k9"]["date"].get("day"), None);
+        assert_eq!(anniversaries["k10"]["date"]["day"], 15);
+        Ok(())
+    }
+
+    #[test]
+    fn test_phone_tel_scheme() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "phones": {
+                "tel0": { "number": "+15551234567" },
+                "tel1": { "number": "tel:+15551234568" },
+                "tel2": { "number": "+1 555 123 4569" },
+                "tel3": { "number": "5551234570" }
+            }
+        }))?;
+        let fixes = card.fix_common_issues(&only(|rules| rules.phone_tel_scheme = true));
+        assert_eq!(
+            fixes,
+            vec![fix(
+                "phones/tel0/number",
+                json!("+15551234567"),
+                json!("tel:+15551234567")
+            )]
+        );
+        let value = serde_json::to_value(&card)?;
+        assert_eq!(value["phones"]["tel0"]["number"], "tel:+15551234567");
+        assert_eq!(value["phones"]["tel2"]["number"], "+1 555 123 4569");
+        Ok(())
+    }
+
+    #[test]
+    fn test_scheduling_mailto_scheme() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "schedulingAddresses": {
+                "sched1": { "uri": "calendar@example.com" },
+                "sched2": { "uri": "mailto:jane@example.com" },
+                "sched3": { "uri": "https://example.com/calendar" }
+            }
+        }))?;
+        let fixes = card.fix_common_issues(&only(|rules| rules.scheduling_mailto_scheme = true));
+        assert_eq!(
+            fixes,
+            vec![fix(
+                "schedulingAddresses/sched1/uri",
+                json!("calendar@example.com"),
+                json!("mailto:calendar@example.com")
+            )]
+        );
+        let value = serde_json::to_value(&card)?;
+        assert_eq!(
+            value["schedulingAddresses"]["sched1"]["uri"],
+            "mailto:calendar@example.com"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_messy_card() -> Result<(), Box<dyn std::error::Error>> {
+        let messy: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "created": "2022-09-30T14:35:10+00:00",
+            "emails": { "e1": { "address": "jane@example.com", "pref": 0 } },
+            "phones": { "tel0": { "number": "+15551234567", "pref": 0 } },
+            "schedulingAddresses": { "sched1": { "uri": "calendar@example.com" } },
+            "addresses": { "k23": { "countryCode": "de", "full": "Berlin" } },
+            "anniversaries": { "k8": { "kind": "birth", "date": { "year": 1953, "month": 10, "day": 0 } } }
+        }))?;
+
+        let mut conservative = messy.clone();
+        let fixes = conservative.fix_common_issues(&FixRules::default());
+        let paths: Vec<&str> = fixes.iter().map(|fix| fix.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "addresses/k23/countryCode",
+                "anniversaries/k8/date/day",
+                "created",
+                "emails/e1/pref",
+                "phones/tel0/pref",
+            ]
+        );
+        let value = serde_json::to_value(&conservative)?;
+        assert_eq!(value["phones"]["tel0"]["number"], "+15551234567");
+        assert_eq!(
+            value["schedulingAddresses"]["sched1"]["uri"],
+            "calendar@example.com"
+        );
+
+        let mut fixed = messy.clone();
+        let fixes = fixed.fix_common_issues(&FixRules::all());
+        assert_eq!(fixes.len(), 7);
+        assert_eq!(fixes[5].to_string(), "phones/tel0/pref: 0 -> 1");
+        let value = serde_json::to_value(&fixed)?;
+        assert_eq!(value["phones"]["tel0"]["number"], "tel:+15551234567");
+        assert_eq!(
+            value["schedulingAddresses"]["sched1"]["uri"],
+            "mailto:calendar@example.com"
+        );
+        // the fixes are idempotent
+        assert!(fixed.fix_common_issues(&FixRules::all()).is_empty());
+
+        let mut untouched = messy.clone();
+        assert!(untouched.fix_common_issues(&FixRules::none()).is_empty());
+        assert_eq!(untouched, messy);
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_entries() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "phones": { "p1": { "number": " " }, "p2": { "number": "tel:+1-555-555-5555" } },
+            "notes": { "n1": { "note": "" } },
+            "onlineServices": { "s1": { "uri": "" } }
+        }))?;
+        assert!(card.fix_common_issues(&FixRules::default()).is_empty());
+
+        let fixes = card.fix_common_issues(&only(|rules| rules.empty_entries = true));
+        assert_eq!(
+            fixes,
+            vec![
+                fix("notes/n1", json!({ "note": "" }), Value::Null),
+                fix("phones/p1", json!({ "number": " " }), Value::Null),
+            ]
+        );
+        assert_eq!(card.phones.as_ref().unwrap().len(), 1);
+        assert!(card.notes.unwrap().is_empty());
+        // the uri of an online service is optional, the entry is kept
+        assert_eq!(card.online_services.unwrap().len(), 1);
+        Ok(())
+    }
+}
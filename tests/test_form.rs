@@ -0,0 +1,99 @@
+mod test {
+    use jscontact::{
+        AddressComponentKind, CardFormBuilder, Context, DateObject, NameComponentKind, PhoneFeature,
+    };
+
+    /// A submission of the form, with an invalid email address and birthday
+    const SUBMISSION: &[(&str, &str)] = &[
+        ("name.given", "John"),
+        ("name.surname", "Doe"),
+        ("name.title", ""),
+        ("emails.work", "john.doe@example.com"),
+        ("emails.private", "john.doe"),
+        ("phones.mobile", "+1 555-0100"),
+        ("phones/work", "tel:+1-555-0101"),
+        ("address.number", "54321"),
+        ("address.name", "Oak St"),
+        ("address/locality", "Reston"),
+        ("address.countryCode", "us"),
+        ("organization", "ABC, Inc."),
+        ("title", "Project Leader"),
+        ("birthday", "1953-13-11"),
+    ];
+
+    #[test]
+    fn test_form_submission() {
+        let mut form = CardFormBuilder::new("urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af");
+        for (field, value) in SUBMISSION {
+            form.set(field, value);
+        }
+        let (card, errors) = form.build();
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors["emails.private"], "invalid email address 'john.doe'");
+        assert_eq!(errors["birthday"], "invalid month '13'");
+
+        let name = card.name.as_ref().unwrap();
+        assert_eq!(name.to_full_string(), "John Doe");
+        assert!(name
+            .components
+            .iter()
+            .flatten()
+            .all(|component| component.kind != NameComponentKind::Title));
+
+        let emails = card.emails.as_ref().unwrap();
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails["work"].address, "john.doe@example.com");
+        assert!(emails["work"].contexts.as_ref().unwrap()[&Context::Work]);
+
+        let phones = card.phones.as_ref().unwrap();
+        assert_eq!(phones["mobile"].number, "+1 555-0100");
+        assert!(phones["mobile"].features.as_ref().unwrap()[&PhoneFeature::Mobile]);
+        assert!(phones["work"].contexts.as_ref().unwrap()[&Context::Work]);
+
+        let address = &card.addresses.as_ref().unwrap()["a1"];
+        assert_eq!(address.locality(), Some("Reston"));
+        assert_eq!(address.country_code.as_deref(), Some("US"));
+        assert_eq!(address.components.as_ref().unwrap().len(), 3);
+        assert_eq!(
+            address.components.as_ref().unwrap()[0].kind,
+            AddressComponentKind::Number
+        );
+
+        assert_eq!(
+            card.organizations.as_ref().unwrap()["o1"].name.as_deref(),
+            Some("ABC, Inc.")
+        );
+        assert_eq!(card.titles.as_ref().unwrap()["t1"].name, "Project Leader");
+        assert!(card.anniversaries.is_none());
+    }
+
+    #[test]
+    fn test_form_resubmission() {
+        let mut form = CardFormBuilder::new("1234");
+        form.set("birthday", "1953-13-11")
+            .set("unknown", "x")
+            .set("emails.example.com:oncall", "jane@example.com")
+            .set("name.separator", "-");
+        assert_eq!(form.errors()["unknown"], "unknown field 'unknown'");
+        assert_eq!(
+            form.errors()["emails.example.com:oncall"],
+            "unknown email context 'example.com:oncall'"
+        );
+        assert_eq!(
+            form.errors()["name.separator"],
+            "unknown name component 'separator'"
+        );
+
+        // the corrected field clears its error
+        form.set("birthday", "--04-15");
+        assert!(!form.errors().contains_key("birthday"));
+        let (card, _) = form.build();
+        let DateObject::PartialDate(date) = &card.anniversaries.as_ref().unwrap()["birthday"].date
+        else {
+            panic!("not a partial date");
+        };
+        assert_eq!((date.year, date.month, date.day), (None, Some(4), Some(15)));
+        assert!(card.name.is_none());
+    }
+}
@@ -0,0 +1,146 @@
+mod test {
+    use jscontact::{
+        Address, AddressComponent, AddressComponentKind, AddressFormatter, CountryAddressFormatter,
+        DisplayOrderFormatter, FormatContext, Phone, PhoneFormatter,
+    };
+
+    /// An unordered address, laid out by its country
+    fn address(country_code: &str) -> Address {
+        let mut address = Address::default();
+        address.components = Some(vec![
+            AddressComponent::postcode("100-0005"),
+            AddressComponent::number("1"),
+            AddressComponent::name("Chiyoda"),
+            AddressComponent::locality("Tokyo"),
+            AddressComponent::region("Tokyo-to"),
+            AddressComponent::building("Marunouchi Building"),
+            AddressComponent::country("Japan"),
+        ]);
+        address.country_code = Some(country_code.to_string());
+        address
+    }
+
+    #[test]
+    fn test_us_and_jp_layouts() {
+        assert_eq!(
+            address("US").to_label(),
+            "1 Chiyoda\nMarunouchi Building\nTokyo, Tokyo-to 100-0005\nJapan"
+        );
+        assert_eq!(
+            address("jp").to_label(),
+            "100-0005\nTokyo-toTokyoChiyoda1\nMarunouchi Building\nJapan"
+        );
+        // the US layout is the default
+        assert_eq!(address("US").to_label(), address("BR").to_label());
+    }
+
+    #[test]
+    fn test_display_order() {
+        let formatter = DisplayOrderFormatter::default();
+        assert_eq!(
+            formatter.format(&address("US"), &FormatContext::default()),
+            "1 Chiyoda Marunouchi Building Tokyo Tokyo-to 100-0005 Japan"
+        );
+        assert_eq!(
+            formatter.format(&address("JP"), &FormatContext::with_country_code("JP")),
+            "1 Chiyoda Marunouchi Building Tokyo Tokyo-to 100-0005"
+        );
+        // the default order is the order of the default layout
+        let layout = address("US").to_label().replace(['\n', ','], " ");
+        assert_eq!(
+            layout.replace("  ", " "),
+            formatter.format(&address("US"), &FormatContext::default())
+        );
+
+        let formatter = DisplayOrderFormatter {
+            order: vec![
+                AddressComponentKind::Country,
+                AddressComponentKind::Postcode,
+                AddressComponentKind::Region,
+                AddressComponentKind::Locality,
+            ],
+        };
+        assert_eq!(
+            address("JP").to_label_with(&formatter, &FormatContext::default()),
+            "Japan 100-0005 Tokyo-to Tokyo 1 Chiyoda Marunouchi Building"
+        );
+    }
+
+    #[test]
+    fn test_european_layouts() {
+        let mut address = Address::default();
+        address.components = Some(vec![
+            AddressComponent::locality("Berlin"),
+            AddressComponent::name("Hauptstraße"),
+            AddressComponent::number("5"),
+            AddressComponent::postcode("10115"),
+            AddressComponent::apartment("3"),
+        ]);
+        address.country_code = Some("DE".to_string());
+        assert_eq!(address.to_label(), "Hauptstraße 5\n3\n10115 Berlin");
+        address.country_code = Some("FR".to_string());
+        assert_eq!(address.to_label(), "3\n5 Hauptstraße\n10115 Berlin");
+        address.country_code = Some("GB".to_string());
+        assert_eq!(address.to_label(), "3\n5 Hauptstraße\nBerlin\n10115");
+    }
+
+    #[test]
+    fn test_context() {
+        let japan = FormatContext::with_country_code("JP");
+        let formatter = CountryAddressFormatter;
+        assert_eq!(
+            address("JP").to_label_with(&formatter, &japan),
+            "100-0005\nTokyo-toTokyoChiyoda1\nMarunouchi Building"
+        );
+        let one_line = FormatContext {
+            line_separator: ", ".to_string(),
+            ..FormatContext::default()
+        };
+        assert_eq!(
+            formatter.format(&address("US"), &one_line),
+            "1 Chiyoda, Marunouchi Building, Tokyo, Tokyo-to 100-0005, Japan"
+        );
+    }
+
+    #[test]
+    fn test_ordered() {
+        let mut address = Address::from_simple("54321 Oak St", "Reston", Some("VA"), "20190", "US");
+        assert_eq!(address.to_label(), "54321 Oak St, Reston, VA 20190");
+        address.components = None;
+        address.full = Some("54321 Oak St, Reston".to_string());
+        assert_eq!(address.to_label(), "54321 Oak St, Reston");
+    }
+
+    /// A formatter keeping the last four digits
+    struct Masked;
+
+    impl PhoneFormatter for Masked {
+        fn format(&self, phone: &Phone, _context: &FormatContext) -> String {
+            let digits: Vec<char> = phone.number.chars().filter(char::is_ascii_digit).collect();
+            let last: String = digits[digits.len().saturating_sub(4)..].iter().collect();
+            format!("***-{}", last)
+        }
+    }
+
+    #[test]
+    fn test_custom_formatters() {
+        let phone = Phone::new("tel:+1-555-555-0123");
+        assert_eq!(
+            phone.to_display_with(&Masked, &FormatContext::default()),
+            "***-0123"
+        );
+
+        struct Full;
+        impl AddressFormatter for Full {
+            fn format(&self, address: &Address, _context: &FormatContext) -> String {
+                address.full.clone().unwrap_or_default()
+            }
+        }
+        let mut address = address("US");
+        address.full = Some("Marunouchi Building".to_string());
+        assert_eq!(
+            address.to_label_with(&Full, &FormatContext::default()),
+            "Marunouchi Building"
+        );
+    }
+}
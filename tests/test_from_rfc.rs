@@ -3,8 +3,8 @@ mod test {
     use jscontact::{
         AddressComponentKind, AnniversaryKind, CalendarKind, Card, CardKind, CardVersion, Context,
         DateObject, DirectoryKind, GrammaticalGender, LinkKind, MediaKind, NameComponentKind,
-        PersonalInfoKind, PersonalInfoLevel, PhoneFeature, PhoneticSystem, RelationshipType,
-        TitleKind,
+        PersonalInfoKind, PersonalInfoLevel, PhoneFeature, PhoneticSystem, Relation,
+        RelationshipType, TitleKind,
     };
 
     #[test]
@@ -109,6 +109,8 @@ mod test {
         let json = include_bytes!("./rfc9553/figure_13.json");
 
         let card: Card = serde_json::from_slice(json).unwrap();
+        // the empty relation object is legal
+        assert!(card.validate().is_valid());
         let related_to = card.related_to.unwrap();
         assert_eq!(related_to.len(), 2);
         let f81d4fae = related_to
@@ -116,9 +118,18 @@ mod test {
             .unwrap();
         let relation = f81d4fae.relation.as_ref().unwrap();
         assert_eq!(relation.get(&RelationshipType::Friend).unwrap(), &true);
+        assert!(!f81d4fae.is_unspecified());
         let email = related_to.get("8cacdfb7d1ffdb59@example.com").unwrap();
         let relation = email.relation.as_ref().unwrap();
         assert_eq!(relation.len(), 0);
+        assert!(email.is_unspecified());
+
+        // the empty relation is serialized back, distinct from a missing relation
+        assert_eq!(serde_json::to_string(email).unwrap(), r#"{"relation":{}}"#);
+        let missing: Relation = serde_json::from_str("{}").unwrap();
+        assert_eq!(missing.relation, None);
+        assert!(missing.is_unspecified());
+        assert_eq!(serde_json::to_string(&missing).unwrap(), "{}");
     }
 
     #[test]
@@ -584,16 +595,16 @@ mod test {
         let res45 = media.get("res45").unwrap();
         assert_eq!(res45.kind, MediaKind::Sound);
         assert_eq!(
-            res45.uri,
+            &*res45.uri,
             "CID:JOHNQ.part8.19960229T080000.xyzMail@example.com"
         );
         let res47 = media.get("res47").unwrap();
         assert_eq!(res47.kind, MediaKind::Logo);
-        assert_eq!(res47.uri, "https://www.example.com/pub/logos/abccorp.jpg");
+        assert_eq!(&*res47.uri, "https://www.example.com/pub/logos/abccorp.jpg");
         let res1 = media.get("res1").unwrap();
         assert_eq!(res1.kind, MediaKind::Photo);
         assert_eq!(
-            res1.uri,
+            &*res1.uri,
             "data:image/jpeg;base64,/9j/4AAQSkZJRgABAQAASABIAAD/4..."
         );
     }
@@ -654,6 +665,7 @@ mod test {
         assert_eq!(anniversaries.len(), 2);
         let k8 = anniversaries.get("k8").unwrap();
         assert_eq!(k8.kind, AnniversaryKind::Birth);
+        assert_eq!(k8.kind.to_string(), "birth");
         let date_k8 = match &k8.date {
             DateObject::PartialDate(date) => date,
             e => panic!("{}", format!("Invalid type {:?}", e)),
@@ -663,12 +675,13 @@ mod test {
         assert_eq!(date_k8.day, Some(15));
         let k9 = anniversaries.get("k9").unwrap();
         assert_eq!(k9.kind, AnniversaryKind::Death);
+        assert_eq!("death".parse(), Ok(AnniversaryKind::Death));
         let date_k9 = match &k9.date {
             DateObject::Timestamp(date) => date,
             e => panic!("{}", format!("Invalid type {:?}", e)),
         };
         assert_eq!(date_k9.utc, "2019-10-15T23:10:00Z");
-        let place = k9.place.as_ref().unwrap();
+        let place = k9.place.as_ref().unwrap().as_address().unwrap();
         assert_eq!(
             place.full,
             Some("4445 Tree Street\nNew England, ND 58647\nUSA".to_string())
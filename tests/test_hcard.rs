@@ -0,0 +1,74 @@
+mod test {
+    use jscontact::{Card, HCardOptions};
+
+    #[test]
+    fn test_to_h_card() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:1234",
+            "name": { "full": "Jane <Doe> & Co" },
+            "emails": {
+                "e1": { "address": "jane@example.com", "pref": 2 },
+                "e2": { "address": "jane.doe@example.com", "pref": 1 }
+            },
+            "phones": { "p1": { "number": "tel:+1-555-555-0100" } },
+            "organizations": { "o1": { "name": "ABC, Inc." } },
+            "media": {
+                "m1": { "kind": "photo", "uri": "https://example.com/jane.jpg?size=1&crop=0" }
+            },
+            "addresses": { "a1": { "full": "1 Main St, Springfield" } },
+            "notes": { "n1": { "note": "Prefers <b>email</b> & calls" } }
+        });
+        let card = Card::try_from(json)?;
+        assert_eq!(
+            card.to_h_card(),
+            [
+                r#"<div class="h-card">"#,
+                r#"<span class="p-name">Jane &lt;Doe&gt; &amp; Co</span>"#,
+                r#"<a class="u-email" href="mailto:jane.doe@example.com">jane.doe@example.com</a>"#,
+                r#"<span class="p-tel">+1-555-555-0100</span>"#,
+                r#"<span class="p-org">ABC, Inc.</span>"#,
+                r#"<img class="u-photo" src="https://example.com/jane.jpg?size=1&amp;crop=0" alt="Jane &lt;Doe&gt; &amp; Co">"#,
+                r#"<span class="p-adr">1 Main St, Springfield</span>"#,
+                r#"<span class="p-note">Prefers &lt;b&gt;email&lt;/b&gt; &amp; calls</span>"#,
+                "</div>",
+            ]
+            .join("\n")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_h_card_with() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:1234",
+            "name": { "full": "Jane Doe" },
+            "emails": { "e1": { "address": "jane@example.com" } },
+            "phones": { "p1": { "number": "tel:+1-555-555-0100" } },
+            "notes": { "n1": { "note": "Prefers <b>email</b> & calls" } }
+        });
+        let card = Card::try_from(json)?;
+        let mut opts = HCardOptions::none();
+        assert_eq!(card.to_h_card_with(&opts), "<div class=\"h-card\">\n</div>");
+        opts.tel = true;
+        opts.note = true;
+        assert_eq!(
+            card.to_h_card_with(&opts),
+            [
+                r#"<div class="h-card">"#,
+                r#"<span class="p-tel">+1-555-555-0100</span>"#,
+                r#"<span class="p-note">Prefers &lt;b&gt;email&lt;/b&gt; &amp; calls</span>"#,
+                "</div>",
+            ]
+            .join("\n")
+        );
+
+        // the missing values are left out
+        let card = Card::new_with_latest_version("urn:uuid:0");
+        assert_eq!(card.to_h_card(), "<div class=\"h-card\">\n</div>");
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+mod test {
+    use std::path::Path;
+
+    use jscontact::{CardSet, ImportReport, ImportSource};
+
+    fn cards_dir() -> &'static Path {
+        Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/import/cards"))
+    }
+
+    fn file(name: &str) -> Option<String> {
+        Some(cards_dir().join(name).display().to_string())
+    }
+
+    #[test]
+    fn test_load_directory() {
+        let (cards, report) = CardSet::load_with_report(ImportSource::Directory(cards_dir()));
+        // invalid.json is rejected, README.txt is not a JSON file
+        assert_eq!(cards.0.len(), 3);
+        assert_eq!(report.cards, 3);
+        // the phone p2 and the pref of e1 of the first Card of warnings.json, the vendor keys are not warnings
+        assert_eq!(report.cards_with_warnings, 1);
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(report.rejected[0].file, file("invalid.json"));
+        assert!(report.rejected[0].message.contains("uid"));
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].file, file("warnings.json"));
+        assert_eq!(report.warnings[0].path, "0/phones/p2");
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].path, "0/emails/e1/pref");
+        assert_eq!(report.validation_codes["pref-out-of-range"], 1);
+        assert_eq!(
+            report.top_unknown_keys(10),
+            vec![("example.com:department", 2), ("example.com:badge", 1)]
+        );
+        assert_eq!(report.top_unknown_keys(1).len(), 1);
+    }
+
+    #[test]
+    fn test_load_ndjson() {
+        let ndjson = [
+            r#"{"@type":"Card","version":"1.0","uid":"1"}"#,
+            "",
+            r#"{"@type":"Card","version":"1.0","uid":"2","emails":{"e1":{"address":"a@example.com","pref":101}}}"#,
+            r#"{"@type":"Card","#,
+        ]
+        .join("\n");
+        let (cards, report) = CardSet::load_with_report(ImportSource::Ndjson(&ndjson));
+        assert_eq!(cards.0.len(), 2);
+        assert_eq!(report.cards_with_warnings, 1);
+        assert_eq!(report.violations[0].line, Some(3));
+        assert_eq!(report.violations[0].path, "emails/e1/pref");
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(report.rejected[0].line, Some(4));
+        assert!(report.rejected[0].message.starts_with("Invalid JSON: "));
+        assert!(report.rejected[0]
+            .to_string()
+            .starts_with("line 4: Invalid JSON: "));
+
+        let missing = cards_dir().join("missing.ndjson");
+        let (cards, report) = CardSet::load_with_report(ImportSource::NdjsonFile(&missing));
+        assert!(cards.0.is_empty());
+        assert_eq!(report.rejected[0].file, Some(missing.display().to_string()));
+    }
+
+    #[test]
+    fn test_report_summary() {
+        let (_, report) = CardSet::load_with_report(ImportSource::Directory(cards_dir()));
+        let summary = report.summary();
+        assert!(summary.starts_with("3 cards loaded, 1 with warnings, 1 rejected\n"));
+        assert!(summary.contains(&format!(
+            "  rejected {}: missing field `uid`\n",
+            file("invalid.json").unwrap()
+        )));
+        assert!(summary.contains(
+            "unknown keys:\n  example.com:department: 2 cards\n  example.com:badge: 1 cards\n"
+        ));
+        assert!(summary.ends_with("validation:\n  pref-out-of-range: 1\n"));
+
+        // the report is serializable
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(serde_json::from_str::<ImportReport>(&json).unwrap(), report);
+    }
+}
@@ -0,0 +1,108 @@
+mod test {
+    use jscontact::syntax::is_uri;
+    use jscontact::{iri_to_uri, uri_to_iri, Card};
+    use serde_json::json;
+
+    const JAPANESE: &str = "https://例え.jp/プロフィール?名前=太郎#連絡先";
+    const EMOJI: &str = "https://example.com/😀/👋🏽?reaction=🎉";
+
+    #[test]
+    fn test_iri_to_uri_path() {
+        assert_eq!(
+            iri_to_uri(EMOJI),
+            "https://example.com/%F0%9F%98%80/%F0%9F%91%8B%F0%9F%8F%BD?reaction=%F0%9F%8E%89"
+        );
+        assert_eq!(
+            iri_to_uri("urn:example:日本"),
+            "urn:example:%E6%97%A5%E6%9C%AC"
+        );
+        // the ASCII characters are kept as written
+        assert_eq!(
+            iri_to_uri("https://user@example.com:8080/a%2Fb c"),
+            "https://user@example.com:8080/a%2Fb c"
+        );
+    }
+
+    #[cfg(not(feature = "idna"))]
+    #[test]
+    fn test_iri_to_uri_host() {
+        assert_eq!(
+            iri_to_uri(JAPANESE),
+            "https://%E4%BE%8B%E3%81%88.jp/%E3%83%97%E3%83%AD%E3%83%95%E3%82%A3%E3%83%BC%E3%83%AB\
+             ?%E5%90%8D%E5%89%8D=%E5%A4%AA%E9%83%8E#%E9%80%A3%E7%B5%A1%E5%85%88"
+        );
+    }
+
+    #[cfg(feature = "idna")]
+    #[test]
+    fn test_iri_to_uri_host() {
+        assert_eq!(
+            iri_to_uri(JAPANESE),
+            "https://xn--r8jz45g.jp/%E3%83%97%E3%83%AD%E3%83%95%E3%82%A3%E3%83%BC%E3%83%AB\
+             ?%E5%90%8D%E5%89%8D=%E5%A4%AA%E9%83%8E#%E9%80%A3%E7%B5%A1%E5%85%88"
+        );
+        assert_eq!(
+            iri_to_uri("https://ユーザー@例え.jp:8443/"),
+            "https://%E3%83%A6%E3%83%BC%E3%82%B6%E3%83%BC@xn--r8jz45g.jp:8443/"
+        );
+        assert_eq!(uri_to_iri("https://XN--R8JZ45G.jp/"), "https://例え.jp/");
+    }
+
+    #[test]
+    fn test_iri_round_trip() {
+        for iri in [
+            JAPANESE,
+            EMOJI,
+            "https://ユーザー@例え.jp:8443/",
+            "https://[2001:db8::1]:8080/写真.jpg",
+            "mailto:太郎@例え.jp",
+            "https://example.com/a%2Fb",
+        ] {
+            let uri = iri_to_uri(iri);
+            assert!(uri.is_ascii(), "{}", uri);
+            assert_eq!(uri_to_iri(&uri), iri);
+        }
+    }
+
+    #[test]
+    fn test_uri_to_iri_kept_escapes() {
+        // ASCII, invalid UTF-8, a bidirectional formatting character and a no-break space
+        assert_eq!(
+            uri_to_iri("https://example.com/%41%2f%E3%83%FF%E2%80%AE%C2%A0%e6%97%a5%"),
+            "https://example.com/%41%2f%E3%83%FF%E2%80%AE%C2%A0日%"
+        );
+    }
+
+    #[test]
+    fn test_iri_in_card() {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "links": { "l1": { "uri": EMOJI } },
+            "directories": { "d1": { "kind": "entry", "uri": JAPANESE } }
+        }))
+        .unwrap();
+        assert!(is_uri(JAPANESE));
+        assert!(is_uri(EMOJI));
+        assert!(card.validate().is_valid());
+        assert_eq!(
+            card.links.as_ref().unwrap()["l1"].uri_scheme(),
+            Some("https")
+        );
+        assert_eq!(
+            card.directories.as_ref().unwrap()["d1"].uri_scheme(),
+            Some("https")
+        );
+        // kept as written in the Card
+        let value = serde_json::to_value(&card).unwrap();
+        assert_eq!(value["links"]["l1"]["uri"], EMOJI);
+
+        let mecard = card.to_mecard();
+        assert_eq!(
+            mecard,
+            "MECARD:URL:https\\://example.com/%F0%9F%98%80/%F0%9F%91%8B%F0%9F%8F%BD?reaction=%F0%9F%8E%89;;"
+        );
+        assert!(mecard.is_ascii());
+    }
+}
@@ -0,0 +1,326 @@
+mod test {
+    use jscontact::{
+        AddressComponentKind, AnniversaryKind, Card, CardKind, Context, DateObject,
+        NameComponentKind, PhoneFeature, TitleKind,
+    };
+    use serde_json::{json, Value};
+
+    const RDAP: &str = include_str!("./jcard/rdap.json");
+
+    /// A Card with a property of each kind converted to jCard
+    fn card() -> Result<Card, Box<dyn std::error::Error>> {
+        let json = json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "kind": "individual",
+            "name": {
+                "full": "Jane Doe",
+                "components": [
+                    { "kind": "given", "value": "Jane" },
+                    { "kind": "surname", "value": "Doe" },
+                    { "kind": "credential", "value": "PhD" }
+                ]
+            },
+            "emails": {
+                "e1": { "address": "jane@example.com", "contexts": { "work": true }, "pref": 1 },
+                "e2": { "address": "jane.doe@example.org" }
+            },
+            "phones": {
+                "tel1": {
+                    "number": "tel:+1-555-555-5555",
+                    "contexts": { "private": true },
+                    "features": { "mobile": true, "voice": true }
+                }
+            },
+            "organizations": {
+                "o1": { "name": "ABC, Inc.", "units": [{ "name": "Sales" }] }
+            },
+            "addresses": {
+                "a1": {
+                    "components": [
+                        { "kind": "name", "value": "54321 Oak St" },
+                        { "kind": "locality", "value": "Reston" },
+                        { "kind": "region", "value": "VA" },
+                        { "kind": "postcode", "value": "20190" },
+                        { "kind": "country", "value": "USA" }
+                    ],
+                    "contexts": { "work": true }
+                }
+            },
+            "anniversaries": {
+                "birth": { "kind": "birth", "date": { "year": 1953, "month": 4, "day": 15 } }
+            }
+        });
+        Ok(serde_json::from_value(json)?)
+    }
+
+    #[test]
+    fn test_to_jcard() -> Result<(), Box<dyn std::error::Error>> {
+        let (jcard, report) = card()?.to_jcard_with_report();
+        assert!(report.is_lossless());
+        let properties = jcard[1].as_array().unwrap();
+        let find = |name: &str| -> Vec<&Value> {
+            properties
+                .iter()
+                .filter(|property| property[0] == name)
+                .collect()
+        };
+        assert_eq!(jcard[0], "vcard");
+        assert_eq!(properties[0], json!(["version", {}, "text", "4.0"]));
+        assert_eq!(find("fn"), [&json!(["fn", {}, "text", "Jane Doe"])]);
+        assert_eq!(
+            find("n"),
+            [&json!(["n", {}, "text", ["Doe", "Jane", "", "", "PhD"]])]
+        );
+        assert_eq!(
+            find("email"),
+            [
+                &json!(["email", {"type": "work", "pref": "1"}, "text", "jane@example.com"]),
+                &json!(["email", {}, "text", "jane.doe@example.org"]),
+            ]
+        );
+        assert_eq!(
+            find("tel"),
+            [&json!(["tel", {"type": ["cell", "home", "voice"]}, "uri", "tel:+1-555-555-5555"])]
+        );
+        assert_eq!(
+            find("org"),
+            [&json!(["org", {}, "text", ["ABC, Inc.", "Sales"]])]
+        );
+        assert_eq!(
+            find("adr"),
+            [&json!([
+                "adr",
+                {"type": "work"},
+                "text",
+                ["", "", "54321 Oak St", "Reston", "VA", "20190", "USA"]
+            ])]
+        );
+        assert_eq!(find("bday"), [&json!(["bday", {}, "date", "1953-04-15"])]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_jcard_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let card = card()?;
+        let jcard = card.to_jcard();
+        let (parsed, report) = Card::from_jcard(&jcard)?;
+        assert!(report.is_lossless());
+        assert_eq!(parsed.uid, card.uid);
+        assert_eq!(parsed.kind, card.kind);
+        assert_eq!(parsed.to_jcard(), jcard);
+
+        let name = parsed.name.as_ref().unwrap();
+        assert_eq!(name.full.as_deref(), Some("Jane Doe"));
+        let components = name.components.as_ref().unwrap();
+        let kinds: Vec<&NameComponentKind> =
+            components.iter().map(|component| &component.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                &NameComponentKind::Given,
+                &NameComponentKind::Surname,
+                &NameComponentKind::Credential
+            ]
+        );
+        let emails = parsed.emails.as_ref().unwrap();
+        assert_eq!(emails["e1"].address, "jane@example.com");
+        assert_eq!(emails["e1"].pref, Some(1));
+        assert_eq!(emails["e1"].contexts, Some([(Context::Work, true)].into()));
+        let phone = &parsed.phones.as_ref().unwrap()["tel1"];
+        assert_eq!(phone.number, "tel:+1-555-555-5555");
+        assert_eq!(
+            phone.features,
+            Some([(PhoneFeature::Mobile, true), (PhoneFeature::Voice, true)].into())
+        );
+        let organization = &parsed.organizations.as_ref().unwrap()["o1"];
+        assert_eq!(organization.name.as_deref(), Some("ABC, Inc."));
+        assert_eq!(organization.units.as_ref().unwrap()[0].name, "Sales");
+        let address = &parsed.addresses.as_ref().unwrap()["a1"];
+        assert_eq!(address.components.as_ref().unwrap().len(), 5);
+        let birth = &parsed.anniversaries.as_ref().unwrap()["birth"];
+        assert_eq!(birth.kind, AnniversaryKind::Birth);
+        let DateObject::PartialDate(date) = &birth.date else {
+            panic!("the birth date is not a PartialDate");
+        };
+        assert_eq!(
+            (date.year, date.month, date.day),
+            (Some(1953), Some(4), Some(15))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_jcard_losses() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "language": "en",
+            "name": { "full": "Jane Doe" },
+            "anniversaries": {
+                "b1": { "kind": "birth", "date": { "day": 15 } }
+            }
+        }))?;
+        let (jcard, report) = card.to_jcard_with_report();
+        let paths: Vec<&str> = report.lossy.iter().map(|loss| loss.path.as_str()).collect();
+        assert_eq!(paths, ["anniversaries/b1", "language"]);
+
+        card.anniversaries = Some(
+            [(
+                "d1".into(),
+                serde_json::from_value(json!({ "kind": "death", "date": { "year": 2020 } }))?,
+            )]
+            .into(),
+        );
+        let (_, report) = card.to_jcard_with_report();
+        assert_eq!(report.lossy[0].path, "anniversaries/d1");
+        assert_eq!(report.lossy[0].reason, "vCard has no death-date property");
+        assert!(!jcard[1]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|property| property[0] == "bday"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_jcard_vendor_values() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "phones": {
+                "tel1": {
+                    "number": "tel:+1-555-555-5555",
+                    "contexts": { "private": true, "example.com:school": true },
+                    "features": { "main-number": true, "example.com:satellite": true, "voice": true }
+                }
+            }
+        }))?;
+        let (jcard, report) = card.to_jcard_with_report();
+        let losses: Vec<(&str, &str)> = report
+            .lossy
+            .iter()
+            .map(|loss| (loss.path.as_str(), loss.reason.as_str()))
+            .collect();
+        assert_eq!(
+            losses,
+            [
+                (
+                    "phones/tel1/contexts/example.com:school",
+                    "vCard has no example.com:school TYPE"
+                ),
+                (
+                    "phones/tel1/features/example.com:satellite",
+                    "vCard has no example.com:satellite TYPE"
+                ),
+                (
+                    "phones/tel1/features/main-number",
+                    "vCard has no main-number TYPE"
+                ),
+            ]
+        );
+        let tel = jcard[1]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|property| property[0] == "tel")
+            .unwrap();
+        assert_eq!(tel[1], json!({ "type": ["home", "voice"] }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_rdap_jcard() -> Result<(), Box<dyn std::error::Error>> {
+        let jcard: Value = serde_json::from_str(RDAP)?;
+        assert_eq!(
+            Card::from_jcard(&jcard).unwrap_err(),
+            "jCard has no UID".to_string()
+        );
+        let (card, report) = Card::from_jcard_with_uid(&jcard, "XXXX")?;
+        assert_eq!(card.uid, "XXXX");
+        assert_eq!(card.kind, Some(CardKind::Individual));
+        let name = card.name.as_ref().unwrap();
+        assert_eq!(name.full.as_deref(), Some("Joe User"));
+        let credentials: Vec<&str> = name
+            .components
+            .iter()
+            .flatten()
+            .filter(|component| component.kind == NameComponentKind::Credential)
+            .map(|component| component.value.as_str())
+            .collect();
+        assert_eq!(credentials, ["ing. jr", "M.Sc."]);
+
+        let titles = card.titles.as_ref().unwrap();
+        assert_eq!(titles["t1"].name, "Research Scientist");
+        assert_eq!(titles["t2"].kind, Some(TitleKind::Role));
+        let organization = &card.organizations.as_ref().unwrap()["o1"];
+        assert_eq!(organization.name.as_deref(), Some("Example"));
+        assert_eq!(organization.contexts, Some([(Context::Work, true)].into()));
+
+        let addresses = card.addresses.as_ref().unwrap();
+        let kinds: Vec<&AddressComponentKind> = addresses["a1"]
+            .components
+            .iter()
+            .flatten()
+            .map(|component| &component.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                &AddressComponentKind::Apartment,
+                &AddressComponentKind::Name,
+                &AddressComponentKind::Locality,
+                &AddressComponentKind::Region,
+                &AddressComponentKind::Postcode,
+                &AddressComponentKind::Country
+            ]
+        );
+        assert!(addresses["a2"].components.is_none());
+        assert_eq!(
+            addresses["a2"].full.as_deref(),
+            Some("123 Maple Ave\nSuite 90001\nVancouver\nBC\n1239\n")
+        );
+
+        let phones = card.phones.as_ref().unwrap();
+        assert_eq!(phones["tel1"].number, "tel:+1-555-555-1234;ext=102");
+        assert_eq!(phones["tel1"].pref, Some(1));
+        assert_eq!(
+            phones["tel2"].features,
+            Some(
+                [
+                    (PhoneFeature::Mobile, true),
+                    (PhoneFeature::Voice, true),
+                    (PhoneFeature::Video, true),
+                    (PhoneFeature::Text, true)
+                ]
+                .into()
+            )
+        );
+        assert_eq!(
+            card.emails.as_ref().unwrap()["e1"].address,
+            "joe.user@example.com"
+        );
+        assert_eq!(
+            card.links.as_ref().unwrap()["l1"].uri,
+            "https://example.org"
+        );
+
+        let losses: Vec<&str> = report.lossy.iter().map(|loss| loss.path.as_str()).collect();
+        assert_eq!(losses, ["LANG", "GEO", "KEY", "TZ"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_jcard_errors() {
+        assert!(Card::from_jcard(&json!({ "vcard": [] })).is_err());
+        assert!(Card::from_jcard(&json!(["vcalendar", []])).is_err());
+        assert!(Card::from_jcard(&json!(["vcard", [["fn", {}, "text"]]])).is_err());
+        assert_eq!(
+            Card::from_jcard(&json!(["vcard", [["version", {}, "text", "3.0"]]])).unwrap_err(),
+            "jCard version 3.0 is not 4.0"
+        );
+    }
+}
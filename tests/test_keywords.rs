@@ -0,0 +1,151 @@
+mod test {
+    use jscontact::{Card, KeywordMerge, KeywordNormalization};
+    use serde_json::json;
+
+    fn keywords(card: &Card) -> Vec<String> {
+        let mut keywords: Vec<String> = card
+            .keywords
+            .iter()
+            .flatten()
+            .map(|(keyword, _)| keyword.to_string())
+            .collect();
+        keywords.sort();
+        keywords
+    }
+
+    #[test]
+    fn test_has_keyword_ci() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "keywords": { "IETF": true, "Music": true }
+        }))?;
+        assert!(card.has_keyword_ci("ietf"));
+        assert!(card.has_keyword_ci("MUSIC"));
+        assert!(!card.has_keyword_ci("ietf "));
+        assert!(!card.has_keyword_ci("sports"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_keywords_duplicates() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "keywords": {
+                "IETF": true,
+                "ietf": true,
+                " Ietf ": true,
+                "Music": true,
+                "rust": true,
+                "  ": true
+            }
+        }))?;
+        let merges = card.normalize_keywords(&KeywordNormalization::default());
+        assert_eq!(
+            merges,
+            vec![KeywordMerge {
+                keyword: "ietf".to_string(),
+                merged: vec![" Ietf ".to_string(), "IETF".to_string(), "ietf".to_string()],
+            }]
+        );
+        assert_eq!(merges[0].to_string(), " Ietf , IETF, ietf -> ietf");
+        assert_eq!(keywords(&card), ["ietf", "music", "rust"]);
+        // the normalization is idempotent
+        let normalized = card.clone();
+        assert!(card
+            .normalize_keywords(&KeywordNormalization::default())
+            .is_empty());
+        assert_eq!(card, normalized);
+
+        // the case is kept without the lowercase step
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "keywords": { "IETF": true, " IETF": true, "ietf": true }
+        }))?;
+        let merges = card.normalize_keywords(&KeywordNormalization {
+            lowercase: false,
+            ..Default::default()
+        });
+        assert_eq!(merges.len(), 1);
+        assert_eq!(keywords(&card), ["IETF", "ietf"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_keyword() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "keywords": { "ietf": true }
+        }))?;
+        card.add_keyword(" IETF");
+        assert_eq!(keywords(&card), [" IETF", "ietf"]);
+
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "keywords": { "ietf": true }
+        }))?;
+        card.add_keyword_normalized(" IETF", &KeywordNormalization::default());
+        card.add_keyword_normalized("Rust ", &KeywordNormalization::default());
+        card.add_keyword_normalized(" ", &KeywordNormalization::default());
+        assert_eq!(keywords(&card), ["ietf", "rust"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_keywords_turkish_i() -> Result<(), Box<dyn std::error::Error>> {
+        // the dotted capital I (U+0130) and the dotless small i (U+0131)
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "keywords": { "İstanbul": true, "istanbul": true, "ıspanak": true, "ISPANAK": true }
+        }))?;
+        card.normalize_keywords(&KeywordNormalization::default());
+        #[cfg(feature = "unicode")]
+        {
+            // the default case folding is not the Turkish one: İ is i with a combining dot
+            assert_eq!(
+                keywords(&card),
+                ["ispanak", "istanbul", "i\u{307}stanbul", "ıspanak"]
+            );
+            assert!(card.has_keyword_ci("İSTANBUL"));
+        }
+        #[cfg(not(feature = "unicode"))]
+        {
+            // only the ASCII letters are folded
+            assert_eq!(
+                keywords(&card),
+                ["ispanak", "istanbul", "İstanbul", "ıspanak"]
+            );
+            assert!(card.has_keyword_ci("İSTANBUL"));
+            assert!(!card.has_keyword_ci("i\u{307}stanbul"));
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_normalize_keywords_nfc() -> Result<(), Box<dyn std::error::Error>> {
+        // e with a combining acute accent, and the precomposed é
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "keywords": { "Cafe\u{301}": true, "café": true }
+        }))?;
+        assert!(card.has_keyword_ci("CAFÉ"));
+        let merges = card.normalize_keywords(&KeywordNormalization::default());
+        assert_eq!(merges.len(), 1);
+        assert_eq!(keywords(&card), ["café"]);
+        Ok(())
+    }
+}
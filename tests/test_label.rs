@@ -0,0 +1,88 @@
+mod test {
+    use jscontact::{
+        Address, AddressContext, Context, EmailAddress, LabelStrings, Phone, PhoneFeature,
+    };
+    use std::collections::HashMap;
+
+    fn build_phone(features: &[PhoneFeature], contexts: &[Context]) -> Phone {
+        let mut phone = Phone::new("tel:+1-555-555-5555");
+        if !features.is_empty() {
+            phone.features = Some(features.iter().map(|f| (f.clone(), true)).collect());
+        }
+        if !contexts.is_empty() {
+            phone.contexts = Some(contexts.iter().map(|c| (c.clone(), true)).collect());
+        }
+        phone
+    }
+
+    #[test]
+    fn test_phone_display_label() {
+        use PhoneFeature::*;
+        let cases = [
+            (vec![], vec![], "Phone"),
+            (vec![Voice], vec![], "Voice"),
+            (vec![Voice, Mobile], vec![], "Mobile"),
+            (vec![Fax], vec![Context::Work], "Work fax"),
+            (
+                vec![Voice, Text, Mobile],
+                vec![Context::Private],
+                "Home mobile",
+            ),
+            (vec![], vec![Context::Private, Context::Work], "Work"),
+        ];
+        for (features, contexts, expected) in cases {
+            let phone = build_phone(&features, &contexts);
+            assert_eq!(phone.display_label(), expected, "{:?}", phone);
+        }
+    }
+
+    #[test]
+    fn test_phone_display_label_explicit() {
+        let mut phone = build_phone(&[PhoneFeature::Fax], &[Context::Work]);
+        phone.label = Some("Reception".to_string());
+        assert_eq!(phone.display_label(), "Reception");
+
+        let mut phone = build_phone(&[PhoneFeature::Fax], &[]);
+        phone.features = Some(HashMap::from([(PhoneFeature::Fax, false)]));
+        assert_eq!(phone.display_label(), "Phone");
+    }
+
+    #[test]
+    fn test_display_label_strings() {
+        let strings = LabelStrings {
+            combined: "{feature} ({context})".to_string(),
+            phone: "Téléphone".to_string(),
+            email: "Courriel".to_string(),
+            work: "travail".to_string(),
+            mobile: "Portable".to_string(),
+            ..Default::default()
+        };
+        let phone = build_phone(&[PhoneFeature::Mobile], &[Context::Work]);
+        assert_eq!(phone.display_label_with(&strings), "Portable (travail)");
+        let phone = build_phone(&[], &[]);
+        assert_eq!(phone.display_label_with(&strings), "Téléphone");
+        let email = EmailAddress::new("jane@example.com");
+        assert_eq!(email.display_label_with(&strings), "Courriel");
+    }
+
+    #[test]
+    fn test_email_address_display_label() {
+        let mut email = EmailAddress::new("jane@example.com");
+        assert_eq!(email.display_label(), "Email");
+        email.contexts = Some(HashMap::from([(Context::Private, true)]));
+        assert_eq!(email.display_label(), "Home");
+        email.label = Some("Personal".to_string());
+        assert_eq!(email.display_label(), "Personal");
+    }
+
+    #[test]
+    fn test_address_display_label() {
+        let mut address = Address::default();
+        assert_eq!(address.display_label(), "Address");
+        address.contexts = Some(HashMap::from([
+            (AddressContext::Delivery, true),
+            (AddressContext::Billing, true),
+        ]));
+        assert_eq!(address.display_label(), "Billing");
+    }
+}
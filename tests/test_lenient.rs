@@ -0,0 +1,72 @@
+mod test {
+    use jscontact::{Card, FieldError, SmallString};
+    use serde_json::json;
+
+    #[test]
+    fn test_from_value_collecting() {
+        let json = json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "name": { "full": "John Smith" },
+            "anniversaries": {
+                "k1": { "kind": "birth", "date": { "year": "nineteen" } }
+            },
+            "phones": {
+                "p1": { "number": "tel:+1-555-0100" },
+                "p2": { "number": 5550101 }
+            }
+        });
+        // serde only reports the first error
+        assert!(Card::try_from(json.clone()).is_err());
+
+        let (card, errors) = Card::from_value_collecting(json);
+        let card = card.unwrap();
+        assert_eq!(card.name.unwrap().full.as_deref(), Some("John Smith"));
+        let phones = card.phones.unwrap();
+        assert_eq!(phones.len(), 1);
+        assert_eq!(phones[&SmallString::from("p1")].number, "tel:+1-555-0100");
+        // the map is kept without its invalid entries
+        assert_eq!(card.anniversaries.map(|map| map.len()), Some(0));
+
+        let paths: Vec<&str> = errors.iter().map(|error| error.path.as_str()).collect();
+        assert_eq!(paths, vec!["anniversaries/k1", "phones/p2"]);
+        assert!(errors[1].message.contains("phones"));
+    }
+
+    #[test]
+    fn test_from_value_collecting_invalid_card() {
+        let (card, errors) = Card::from_value_collecting(json!([]));
+        assert!(card.is_none());
+        assert_eq!(
+            errors,
+            vec![FieldError::new("", "The Card is not an object")]
+        );
+
+        // the uid is required
+        let (card, errors) = Card::from_value_collecting(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "kind": "unknown"
+        }));
+        assert!(card.is_none());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "");
+
+        // a scalar property is left unset, the invalid localizations are dropped
+        let (card, errors) = Card::from_value_collecting(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "kind": "unknown",
+            "localizations": "en",
+            "keywords": { "a": true }
+        }));
+        let card = card.unwrap();
+        assert_eq!(card.kind, None);
+        assert!(!card.has_localization("en"));
+        assert_eq!(card.keywords.unwrap().len(), 1);
+        let paths: Vec<&str> = errors.iter().map(|error| error.path.as_str()).collect();
+        assert_eq!(paths, vec!["kind", "localizations"]);
+    }
+}
@@ -0,0 +1,255 @@
+mod test {
+    use jscontact::{
+        parse_with, Card, CardPath, CardSet, ImportSource, LimitKind, LimitViolation, Limits,
+        ParseOptions, ReadError, TextClass, DEFAULT_MAX_BYTES,
+    };
+    use serde_json::json;
+    use std::io::Read;
+
+    const CARD: &str = r#"{"@type":"Card","version":"1.0","uid":"1234"}"#;
+
+    #[test]
+    fn test_from_reader_limited() {
+        // just under the limit, and exactly at it
+        let card = Card::from_reader_limited(CARD.as_bytes(), CARD.len() + 1).unwrap();
+        assert_eq!(card.uid, "1234");
+        assert!(Card::from_reader_limited(CARD.as_bytes(), CARD.len()).is_ok());
+
+        let error = Card::from_reader_limited(CARD.as_bytes(), CARD.len() - 1).unwrap_err();
+        assert!(matches!(error, ReadError::TooLarge { limit } if limit == CARD.len() - 1));
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "The input is larger than the limit of {} bytes",
+                CARD.len() - 1
+            )
+        );
+
+        // the trailing bytes count
+        let padded = format!("{}\n\n", CARD);
+        assert!(matches!(
+            Card::from_reader_limited(padded.as_bytes(), CARD.len()),
+            Err(ReadError::TooLarge { .. })
+        ));
+
+        let invalid = Card::from_reader_limited(r#"{"uid":"1"}"#.as_bytes(), 1024).unwrap_err();
+        assert!(matches!(invalid, ReadError::Json(_)));
+    }
+
+    #[test]
+    fn test_from_reader_unbounded_stream() {
+        // an endless stream of whitespace is rejected once the limit is reached
+        let stream = b"{\"uid\":".chain(std::io::repeat(b' '));
+        assert!(matches!(
+            Card::from_reader_limited(stream, 1024),
+            Err(ReadError::TooLarge { limit: 1024 })
+        ));
+        let stream = b"[".chain(std::io::repeat(b' '));
+        assert!(matches!(
+            CardSet::from_reader_limited(stream, 1024),
+            Err(ReadError::TooLarge { limit: 1024 })
+        ));
+        let error = Card::from_reader(std::io::repeat(b' ')).unwrap_err();
+        assert!(error.is_io());
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "The input is larger than the limit of {} bytes",
+                DEFAULT_MAX_BYTES
+            )
+        );
+    }
+
+    #[test]
+    fn test_card_set_from_reader_limited() {
+        let json = format!("[{},{}]", CARD, CARD);
+        let cards = CardSet::from_reader_limited(json.as_bytes(), json.len()).unwrap();
+        assert_eq!(cards.0.len(), 2);
+        assert!(matches!(
+            CardSet::from_reader_limited(json.as_bytes(), json.len() - 1),
+            Err(ReadError::TooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_max_bytes() {
+        let opts = ParseOptions {
+            max_bytes: CARD.len() - 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_with(CARD, &opts).unwrap_err(),
+            format!(
+                "Invalid JSON: The input is larger than the limit of {} bytes",
+                CARD.len() - 1
+            )
+        );
+        assert!(Card::from_str_with(CARD, &opts).is_err());
+        let opts = ParseOptions {
+            max_bytes: CARD.len(),
+            ..Default::default()
+        };
+        assert!(parse_with(CARD, &opts).is_ok());
+        assert!(Card::from_str_with(CARD, &opts).is_ok());
+    }
+
+    #[test]
+    fn test_load_with_report_limited() {
+        let ndjson = format!("{}\n{}", CARD, CARD);
+        let (cards, report) =
+            CardSet::load_with_report_limited(ImportSource::Ndjson(&ndjson), ndjson.len());
+        assert_eq!(cards.0.len(), 2);
+        assert!(report.rejected.is_empty());
+        let (cards, report) =
+            CardSet::load_with_report_limited(ImportSource::Ndjson(&ndjson), ndjson.len() - 1);
+        assert!(cards.0.is_empty());
+        assert!(report.rejected[0]
+            .message
+            .starts_with("The input is larger"));
+
+        let (cards, report) = CardSet::load_with_report_limited(
+            ImportSource::Directory(std::path::Path::new("tests/rfc9553")),
+            512,
+        );
+        assert!(!cards.0.is_empty());
+        assert!(report
+            .rejected
+            .iter()
+            .all(|rejected| rejected.message == "The input is larger than the limit of 512 bytes"));
+        assert!(!report.rejected.is_empty());
+    }
+
+    fn limited_card() -> Card {
+        let emails: serde_json::Map<String, serde_json::Value> = (1..=12)
+            .map(|idx| {
+                (
+                    format!("e{:02}", idx),
+                    json!({ "address": format!("jane{}@example.com", idx) }),
+                )
+            })
+            .collect();
+        serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "name": { "full": "Jane Doe" },
+            "emails": emails,
+            "phones": { "p1": { "number": "tel:+1-555-555-5555" } },
+            "notes": { "n1": { "note": "x".repeat(5000) }, "n2": { "note": "short" } }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_check_limits_entries() {
+        let card = limited_card();
+        let mut limits = Limits::default();
+        assert!(card.check_limits(&limits).is_empty());
+        limits.max_entries = Some(5);
+        limits
+            .max_entries_by_property
+            .insert("emails".to_string(), 10);
+        assert_eq!(
+            card.check_limits(&limits),
+            [LimitViolation {
+                path: "emails".to_string(),
+                kind: LimitKind::Entries,
+                limit: 10,
+                actual: 12,
+            }]
+        );
+        assert_eq!(
+            card.check_limits(&limits)[0].to_string(),
+            "emails: 12 entries, the limit is 10"
+        );
+    }
+
+    #[test]
+    fn test_check_limits_length() {
+        let card = limited_card();
+        let limits = Limits {
+            max_note_length: Some(4096),
+            max_text_length: Some(17),
+            ..Default::default()
+        };
+        let violations = card.check_limits(&limits);
+        let found: Vec<(&str, LimitKind, usize, usize)> = violations
+            .iter()
+            .map(|violation| {
+                (
+                    violation.path.as_str(),
+                    violation.kind,
+                    violation.limit,
+                    violation.actual,
+                )
+            })
+            .collect();
+        // the names, the URIs, the short note and the shorter addresses are within the limits
+        assert_eq!(
+            found,
+            [
+                ("emails/e10/address", LimitKind::Length, 17, 18),
+                ("emails/e11/address", LimitKind::Length, 17, 18),
+                ("emails/e12/address", LimitKind::Length, 17, 18),
+                ("notes/n1/note", LimitKind::Length, 4096, 5000),
+                ("phones/p1/number", LimitKind::Length, 17, 19),
+            ]
+        );
+        assert_eq!(
+            violations[3].to_string(),
+            "notes/n1/note: 5000 bytes, the limit is 4096"
+        );
+    }
+
+    #[test]
+    fn test_check_limits_size() {
+        let card = limited_card();
+        let size = serde_json::to_string(&card).unwrap().len();
+        let limits = Limits {
+            max_bytes: Some(1000),
+            ..Default::default()
+        };
+        let violations = card.check_limits(&limits);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "");
+        assert_eq!(violations[0].kind, LimitKind::Size);
+        assert_eq!(violations[0].actual, size);
+        assert_eq!(
+            violations[0].to_string(),
+            format!("the Card: {} bytes, the limit is 1000", size)
+        );
+    }
+
+    #[test]
+    fn test_check_limits_jmap_defaults() {
+        let limits = Limits::jmap_defaults();
+        let violations = limited_card().check_limits(&limits);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "notes/n1/note");
+        assert!(Card::new_with_latest_version("1234")
+            .check_limits(&limits)
+            .is_empty());
+        // the card is valid, the limits are not RFC 9553 rules
+        assert!(limited_card().validate().is_valid());
+    }
+
+    #[test]
+    fn test_text_class() {
+        for (path, class) in [
+            ("uid", TextClass::Uri),
+            ("links/l1/uri", TextClass::Uri),
+            ("notes/n1/note", TextClass::Note),
+            ("notes/n1/author/name", TextClass::Other),
+            ("name/components/0/value", TextClass::Name),
+            ("titles/t1/name", TextClass::Name),
+            ("emails/e1/address", TextClass::Other),
+        ] {
+            assert_eq!(
+                TextClass::of(&CardPath::parse(path).unwrap()),
+                class,
+                "{}",
+                path
+            );
+        }
+    }
+}
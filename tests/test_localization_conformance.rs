@@ -0,0 +1,480 @@
+//! The contract of the localization, run with and without the `jsonptr` feature, which selects the implementation
+//! applying the patches: both must give the same localized Cards and the same errors.
+//!
+//! Run `cargo test --test test_localization_conformance --no-default-features` to check the other implementation.
+
+mod test {
+    use jscontact::Card;
+    use serde_json::{json, Value};
+
+    /// The class of the error of a scenario
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Class {
+        /// The path of a patch cannot be resolved
+        Path,
+        /// A patched property is invalid once patched
+        Value,
+        /// A patch of a property or value that is not localizable
+        NotLocalizable,
+        /// A patch of an unknown property
+        UnknownProperty,
+    }
+
+    /// The expected outcome of a scenario
+    enum Expected {
+        /// The properties of the localized Card that differ from the base Card, `null` for a removed property
+        Patched(Value),
+        /// The class of the error
+        Error(Class),
+    }
+    use Expected::{Error, Patched};
+
+    fn base() -> Value {
+        json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "conformance",
+            "name": {
+                "components": [
+                    { "kind": "given", "value": "Jane" },
+                    { "kind": "surname", "value": "Doe" }
+                ],
+                "full": "Jane Doe"
+            },
+            "titles": { "t1": { "name": "Boss" } },
+            "emails": {
+                "e1": { "address": "jane@example.com", "label": "home", "contexts": { "private": true } }
+            },
+            "phones": { "p1": { "number": "tel:+1-555-555-5555", "features": { "voice": true } } },
+            "addresses": {
+                "a1": { "components": [{ "kind": "locality", "value": "Paris" }], "full": "Paris" }
+            },
+            "organizations": { "o1": { "name": "ACME", "units": [{ "name": "Sales" }] } },
+            "links": { "a/b": { "uri": "https://example.com", "label": "site" } },
+            "notes": { "n1": { "note": "Hello" } },
+            "personalInfo": { "pi1": { "kind": "hobby", "value": "reading" } },
+            "speakToAs": { "grammaticalGender": "feminine" }
+        })
+    }
+
+    /// The name of the base Card with other components and full name
+    fn name(components: Value, full: &str) -> Value {
+        json!({ "name": { "components": components, "full": full } })
+    }
+
+    /// The components of the name of the base Card followed by other components
+    fn components(others: Value) -> Value {
+        let mut components = vec![
+            json!({ "kind": "given", "value": "Jane" }),
+            json!({ "kind": "surname", "value": "Doe" }),
+        ];
+        components.extend(others.as_array().unwrap().iter().cloned());
+        Value::Array(components)
+    }
+
+    fn title(titles: Value) -> Value {
+        json!({ "titles": titles })
+    }
+
+    fn scenarios() -> Vec<(&'static str, Value, Expected)> {
+        let email = |email: Value| json!({ "emails": { "e1": email } });
+        let address = |address: Value| json!({ "addresses": { "a1": address } });
+        vec![
+            (
+                "replace_string",
+                json!({ "name/full": "Jeanne Doe" }),
+                Patched(name(components(json!([])), "Jeanne Doe")),
+            ),
+            (
+                "replace_object",
+                json!({ "name": { "full": "Jeanne" } }),
+                Patched(json!({ "name": { "full": "Jeanne" } })),
+            ),
+            (
+                "replace_component_value",
+                json!({ "name/components/0/value": "Jeanne" }),
+                Patched(name(
+                    json!([{ "kind": "given", "value": "Jeanne" }, { "kind": "surname", "value": "Doe" }]),
+                    "Jane Doe",
+                )),
+            ),
+            (
+                "replace_component",
+                json!({ "name/components/1": { "kind": "surname", "value": "Dupont" } }),
+                Patched(name(
+                    json!([{ "kind": "given", "value": "Jane" }, { "kind": "surname", "value": "Dupont" }]),
+                    "Jane Doe",
+                )),
+            ),
+            (
+                "append_at_len",
+                json!({ "name/components/2": { "kind": "generation", "value": "Jr." } }),
+                Patched(name(
+                    components(json!([{ "kind": "generation", "value": "Jr." }])),
+                    "Jane Doe",
+                )),
+            ),
+            (
+                "append_dash",
+                json!({ "name/components/-": { "kind": "generation", "value": "Jr." } }),
+                Patched(name(
+                    components(json!([{ "kind": "generation", "value": "Jr." }])),
+                    "Jane Doe",
+                )),
+            ),
+            (
+                // the missing components are placeholders, invalid if not patched
+                "placeholder_gap",
+                json!({ "name/components/4": { "kind": "generation", "value": "Jr." } }),
+                Error(Class::Value),
+            ),
+            (
+                "placeholder_filled",
+                json!({
+                    "name/components/2": { "kind": "title", "value": "Dr." },
+                    "name/components/3": { "kind": "generation", "value": "Jr." }
+                }),
+                Patched(name(
+                    components(json!([
+                        { "kind": "title", "value": "Dr." },
+                        { "kind": "generation", "value": "Jr." }
+                    ])),
+                    "Jane Doe",
+                )),
+            ),
+            (
+                "fields_at_new_index",
+                json!({ "name/components/2/kind": "generation", "name/components/2/value": "Jr." }),
+                Patched(name(
+                    components(json!([{ "kind": "generation", "value": "Jr." }])),
+                    "Jane Doe",
+                )),
+            ),
+            (
+                "fields_beyond_len",
+                json!({
+                    "addresses/a1/components/1/kind": "country",
+                    "addresses/a1/components/1/value": "France",
+                    "addresses/a1/components/2/kind": "postcode",
+                    "addresses/a1/components/2/value": "75001"
+                }),
+                Patched(address(json!({
+                    "components": [
+                        { "kind": "locality", "value": "Paris" },
+                        { "kind": "country", "value": "France" },
+                        { "kind": "postcode", "value": "75001" }
+                    ],
+                    "full": "Paris"
+                }))),
+            ),
+            (
+                // the patches are applied in the order of their paths, `10` before `2`
+                "index_ten_before_two",
+                json!({
+                    "name/components/2": { "kind": "given2", "value": "A" },
+                    "name/components/3": { "kind": "given2", "value": "B" },
+                    "name/components/4": { "kind": "given2", "value": "C" },
+                    "name/components/5": { "kind": "given2", "value": "D" },
+                    "name/components/6": { "kind": "given2", "value": "E" },
+                    "name/components/7": { "kind": "given2", "value": "F" },
+                    "name/components/8": { "kind": "given2", "value": "G" },
+                    "name/components/9": { "kind": "given2", "value": "H" },
+                    "name/components/10": { "kind": "given2", "value": "I" }
+                }),
+                Patched(name(
+                    components(Value::Array(
+                        ["A", "B", "C", "D", "E", "F", "G", "H", "I"]
+                            .iter()
+                            .map(|value| json!({ "kind": "given2", "value": value }))
+                            .collect(),
+                    )),
+                    "Jane Doe",
+                )),
+            ),
+            (
+                "title_name",
+                json!({ "titles/t1/name": "Chef" }),
+                Patched(title(json!({ "t1": { "name": "Chef" } }))),
+            ),
+            (
+                "new_map_entry",
+                json!({ "titles/t2": { "name": "Chef" } }),
+                Patched(title(
+                    json!({ "t1": { "name": "Boss" }, "t2": { "name": "Chef" } }),
+                )),
+            ),
+            (
+                "field_of_missing_entry",
+                json!({ "titles/t2/name": "Chef" }),
+                Patched(title(
+                    json!({ "t1": { "name": "Boss" }, "t2": { "name": "Chef" } }),
+                )),
+            ),
+            (
+                "missing_property_field",
+                json!({ "nicknames/k1/name": "Janie" }),
+                Patched(json!({ "nicknames": { "k1": { "name": "Janie" } } })),
+            ),
+            (
+                "missing_property_whole",
+                json!({ "nicknames": { "k1": { "name": "Janie" } } }),
+                Patched(json!({ "nicknames": { "k1": { "name": "Janie" } } })),
+            ),
+            (
+                "null_field",
+                json!({ "emails/e1/label": null }),
+                Patched(email(
+                    json!({ "address": "jane@example.com", "contexts": { "private": true } }),
+                )),
+            ),
+            (
+                "null_property",
+                json!({ "titles": null }),
+                Error(Class::NotLocalizable),
+            ),
+            (
+                "null_entry",
+                json!({ "titles/t1": null }),
+                Error(Class::NotLocalizable),
+            ),
+            (
+                "null_missing_path",
+                json!({ "titles/t9/name": null }),
+                Patched(json!({})),
+            ),
+            (
+                "null_missing_property",
+                json!({ "nicknames/k1/name": null }),
+                Patched(json!({})),
+            ),
+            (
+                "null_through_string",
+                json!({ "name/full/x": null }),
+                Patched(json!({})),
+            ),
+            (
+                "null_array_element",
+                json!({ "name/components/0": null }),
+                Patched(name(
+                    json!([{ "kind": "surname", "value": "Doe" }]),
+                    "Jane Doe",
+                )),
+            ),
+            (
+                "null_array_beyond",
+                json!({ "name/components/9": null }),
+                Patched(json!({})),
+            ),
+            (
+                "email_label",
+                json!({ "emails/e1/label": "maison" }),
+                Patched(email(json!({
+                    "address": "jane@example.com",
+                    "label": "maison",
+                    "contexts": { "private": true }
+                }))),
+            ),
+            (
+                "context_add",
+                json!({ "emails/e1/contexts/work": true }),
+                Error(Class::NotLocalizable),
+            ),
+            (
+                "context_replace",
+                json!({ "emails/e1/contexts": { "work": true } }),
+                Error(Class::NotLocalizable),
+            ),
+            (
+                "context_remove",
+                json!({ "emails/e1/contexts/private": null }),
+                Error(Class::NotLocalizable),
+            ),
+            (
+                "address_component",
+                json!({ "addresses/a1/components/0/value": "パリ" }),
+                Patched(address(json!({
+                    "components": [{ "kind": "locality", "value": "パリ" }],
+                    "full": "Paris"
+                }))),
+            ),
+            (
+                "address_full",
+                json!({ "addresses/a1/full": "パリ" }),
+                Patched(address(json!({
+                    "components": [{ "kind": "locality", "value": "Paris" }],
+                    "full": "パリ"
+                }))),
+            ),
+            (
+                "type_mismatch",
+                json!({ "titles/t1/name": 42 }),
+                Error(Class::Value),
+            ),
+            (
+                "required_removed",
+                json!({ "titles/t1/name": null }),
+                Error(Class::Value),
+            ),
+            (
+                "descend_into_string",
+                json!({ "name/full/x": "y" }),
+                Error(Class::Path),
+            ),
+            (
+                "key_on_array",
+                json!({ "name/components/foo": { "kind": "given", "value": "x" } }),
+                Error(Class::Path),
+            ),
+            (
+                "leading_zero_index",
+                json!({ "name/components/01": { "kind": "given", "value": "x" } }),
+                Error(Class::Path),
+            ),
+            (
+                "index_key_on_map",
+                json!({ "titles/0": { "name": "Chef" } }),
+                Patched(title(
+                    json!({ "t1": { "name": "Boss" }, "0": { "name": "Chef" } }),
+                )),
+            ),
+            (
+                "dash_in_middle",
+                json!({ "name/components/-/value": "x" }),
+                Error(Class::Path),
+            ),
+            (
+                "org_unit",
+                json!({ "organizations/o1/units/0/name": "Ventes" }),
+                Patched(
+                    json!({ "organizations": { "o1": { "name": "ACME", "units": [{ "name": "Ventes" }] } } }),
+                ),
+            ),
+            (
+                "speak_to_as",
+                json!({ "speakToAs/grammaticalGender": "masculine" }),
+                Error(Class::NotLocalizable),
+            ),
+            (
+                "personal_info",
+                json!({ "personalInfo/pi1/value": "lecture" }),
+                Patched(
+                    json!({ "personalInfo": { "pi1": { "kind": "hobby", "value": "lecture" } } }),
+                ),
+            ),
+            (
+                "note",
+                json!({ "notes/n1/note": "Bonjour" }),
+                Patched(json!({ "notes": { "n1": { "note": "Bonjour" } } })),
+            ),
+            (
+                "escaped_key",
+                json!({ "links/a~1b/label": "site web" }),
+                Patched(
+                    json!({ "links": { "a/b": { "uri": "https://example.com", "label": "site web" } } }),
+                ),
+            ),
+            (
+                // a patch of an object is applied before the patches of its fields
+                "object_then_field",
+                json!({ "name": { "full": "Jeanne" }, "name/full": "Jeanne Dupont" }),
+                Patched(json!({ "name": { "full": "Jeanne Dupont" } })),
+            ),
+            (
+                "several_properties",
+                json!({ "name/full": "Jeanne", "titles/t1/name": "Chef", "notes/n1/note": "Bonjour" }),
+                Patched(json!({
+                    "name": name(components(json!([])), "Jeanne")["name"],
+                    "titles": { "t1": { "name": "Chef" } },
+                    "notes": { "n1": { "note": "Bonjour" } }
+                })),
+            ),
+            (
+                "phone_feature",
+                json!({ "phones/p1/features/text": true }),
+                Error(Class::NotLocalizable),
+            ),
+            (
+                "non_localizable",
+                json!({ "uid": "other" }),
+                Error(Class::NotLocalizable),
+            ),
+            (
+                "unknown_property",
+                json!({ "nmae/full": "x" }),
+                Error(Class::UnknownProperty),
+            ),
+            ("empty", json!({}), Patched(json!({}))),
+        ]
+    }
+
+    /// Classify an error of the localization
+    fn classify(error: &str) -> Option<Class> {
+        if error.contains(": cannot resolve '") {
+            Some(Class::Path)
+        } else if error.starts_with("Invalid value for ") {
+            Some(Class::Value)
+        } else if error.ends_with(": The value is not localizable") {
+            Some(Class::NotLocalizable)
+        } else if error.contains(": Unknown property '") {
+            Some(Class::UnknownProperty)
+        } else {
+            None
+        }
+    }
+
+    #[test]
+    fn test_localization_conformance() {
+        let scenarios = scenarios();
+        assert!(scenarios.len() >= 40);
+        for (scenario, patches, expected) in scenarios {
+            let mut json = base();
+            json["localizations"] = json!({ "fr": patches });
+            let card: Card = serde_json::from_value(json).unwrap();
+            let localized = card.get_localized("fr");
+            match expected {
+                Patched(properties) => {
+                    let mut json = base();
+                    json["language"] = json!("fr");
+                    for (property, value) in properties.as_object().unwrap() {
+                        match value {
+                            Value::Null => json.as_object_mut().unwrap().remove(property),
+                            value => json
+                                .as_object_mut()
+                                .unwrap()
+                                .insert(property.clone(), value.clone()),
+                        };
+                    }
+                    let expected: Card = serde_json::from_value(json).unwrap();
+                    assert_eq!(localized.as_ref(), Ok(&expected), "{}", scenario);
+                }
+                Error(class) => {
+                    let error = localized.expect_err(scenario);
+                    assert_eq!(classify(&error), Some(class), "{}: {}", scenario, error);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_localization_conformance_messages() {
+        let localize = |patches: Value| {
+            let mut json = base();
+            json["localizations"] = json!({ "fr": patches });
+            serde_json::from_value::<Card>(json)
+                .unwrap()
+                .get_localized("fr")
+        };
+        assert_eq!(
+            localize(json!({ "name/full/x": "y" })).unwrap_err(),
+            "name/full/x: cannot resolve 'x'"
+        );
+        assert_eq!(
+            localize(json!({ "name/components/-/value": "x" })).unwrap_err(),
+            "name/components/-/value: cannot resolve '-'"
+        );
+        assert_eq!(
+            localize(json!({ "titles/t1/name": null })).unwrap_err(),
+            "Invalid value for titles: missing field `name`"
+        );
+    }
+}
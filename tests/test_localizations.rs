@@ -1,10 +1,19 @@
 mod test {
 
     use jscontact::{
-        AddressComponentKind, CalendarKind, Card, DirectoryKind, LinkKind, MediaKind,
-        NameComponentKind, PersonalInfoKind, PersonalInfoLevel, TitleKind,
+        AddressComponentKind, CalendarKind, Card, Context, DirectoryKind, LinkKind,
+        LocalizationIssue, LocalizationMergePolicy, LocalizeOptions, MediaKind, NameComponentKind,
+        PatchLimits, PersonalInfoKind, PersonalInfoLevel, PhoneFeature, TitleKind,
     };
 
+    /// Write the JSON of a test into the temporary directory, to inspect it without touching the source tree
+    fn write_json(name: &str, json: &serde_json::Value) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::env::temp_dir().join("jscontact").join("localizations");
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join(name), serde_json::to_string_pretty(json)?)?;
+        Ok(())
+    }
+
     #[test]
     fn test_localizations() -> Result<(), Box<dyn std::error::Error>> {
         let json = serde_json::json!({
@@ -46,10 +55,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
 
         let localizations = card.get_localized("en").unwrap();
@@ -111,10 +117,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_full_replacement_hashtable.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_full_replacement_hashtable.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
 
         let localizations = card.get_localized("en").unwrap();
@@ -156,10 +159,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_name.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_name.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
 
         let localizations = card.get_localized("en")?;
@@ -184,10 +184,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_name_path.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_name_path.json", &json)?;
         let card: Card = serde_json::from_value(json)?;
 
         let localizations = card.get_localized("en")?;
@@ -218,10 +215,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_name_components.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_name_components.json", &json)?;
         let card: Card = serde_json::from_value(json)?;
 
         let localizations = card.get_localized("en")?;
@@ -257,10 +251,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_name_components_2.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_name_components_2.json", &json)?;
         let card: Card = serde_json::from_value(json)?;
 
         let localizations = card.get_localized("en")?;
@@ -295,9 +286,9 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_name_components_path_object_1.json",
-            serde_json::to_string_pretty(&json)?,
+        write_json(
+            "test_localizations_name_components_path_object_1.json",
+            &json,
         )?;
         let card: Card = serde_json::from_value(json)?;
 
@@ -337,10 +328,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_titles.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_titles.json", &json)?;
         let card: Card = serde_json::from_value(json)?;
 
         let localizations = card.get_localized("en")?;
@@ -372,10 +360,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_titles_path_object_1.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_titles_path_object_1.json", &json)?;
         let card: Card = serde_json::from_value(json)?;
 
         let localizations = card.get_localized("en")?;
@@ -405,10 +390,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_titles_path_object_2.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_titles_path_object_2.json", &json)?;
         let card: Card = serde_json::from_value(json)?;
 
         let localizations = card.get_localized("en")?;
@@ -440,10 +422,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_nicknames.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_nicknames.json", &json)?;
         let card: Card = serde_json::from_value(json)?;
 
         let localizations = card.get_localized("en")?;
@@ -473,10 +452,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_nicknames_path_object_1.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_nicknames_path_object_1.json", &json)?;
         let card: Card = serde_json::from_value(json)?;
 
         let localizations = card.get_localized("en")?;
@@ -504,10 +480,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_nicknames_path_object_2.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_nicknames_path_object_2.json", &json)?;
         let card: Card = serde_json::from_value(json)?;
 
         let localizations = card.get_localized("en")?;
@@ -552,10 +525,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_addresses.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_addresses.json", &json)?;
         let card: Card = serde_json::from_value(json)?;
 
         let localizations = card.get_localized("en")?;
@@ -615,10 +585,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_addresses_path_object_1.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_addresses_path_object_1.json", &json)?;
         let card: Card = serde_json::from_value(json)?;
 
         let localizations = card.get_localized("en")?;
@@ -676,10 +643,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_addresses_path_object_2.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_addresses_path_object_2.json", &json)?;
         let card: Card = serde_json::from_value(json)?;
 
         let localizations = card.get_localized("en")?;
@@ -710,7 +674,6 @@ mod test {
     /// The card in the test is not valid as the RFC states
     /// Because we cannot replace inexistent components
     /// But we still handle it
-    #[cfg(not(feature = "jsonptr"))]
     #[test]
     fn test_localizations_addresses_path_object_3_invalid() -> Result<(), Box<dyn std::error::Error>>
     {
@@ -761,9 +724,9 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_addresses_path_object_3_invalid.json",
-            serde_json::to_string_pretty(&json)?,
+        write_json(
+            "test_localizations_addresses_path_object_3_invalid.json",
+            &json,
         )?;
         let card: Card = serde_json::from_value(json)?;
 
@@ -795,7 +758,6 @@ mod test {
     /// The card in the test is not valid as the RFC states
     /// Because we cannot replace inexistent components
     /// But we still handle it
-    #[cfg(not(feature = "jsonptr"))]
     #[test]
     fn test_localizations_addresses_path_object_4_invalid() -> Result<(), Box<dyn std::error::Error>>
     {
@@ -832,9 +794,9 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_addresses_path_object_4_invalid.json",
-            serde_json::to_string_pretty(&json)?,
+        write_json(
+            "test_localizations_addresses_path_object_4_invalid.json",
+            &json,
         )?;
         let card: Card = serde_json::from_value(json)?;
 
@@ -908,10 +870,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_personal_info.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_personal_info.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
 
         let localizations = card.get_localized("en")?;
@@ -975,10 +934,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_personal_info_path_object_1.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_personal_info_path_object_1.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
 
         let localizations = card.get_localized("en")?;
@@ -1043,10 +999,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_personal_info_path_object_2.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_personal_info_path_object_2.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
 
         let localizations = card.get_localized("en")?;
@@ -1105,10 +1058,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_personal_info_path_object_3.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_personal_info_path_object_3.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
 
         let localizations = card.get_localized("en")?;
@@ -1151,10 +1101,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_notes.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_notes.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
 
         let localizations = card.get_localized("en")?;
@@ -1184,10 +1131,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_notes_path_object_1.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_notes_path_object_1.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
 
         let localizations = card.get_localized("en")?;
@@ -1215,10 +1159,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_notes_path_object_2.json",
-            serde_json::to_string_pretty(&json)?,
-        )?;
+        write_json("test_localizations_notes_path_object_2.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
 
         let localizations = card.get_localized("en")?;
@@ -1229,6 +1170,42 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_localizations_speak_to_as_path() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "speakToAs": {
+                "grammaticalGender": "neuter",
+                "pronouns": {
+                    "k19": { "pronouns": "they/them", "pref": 2 }
+                }
+            },
+            "localizations": {
+                "fr": {
+                    "speakToAs/pronouns/k19/pronouns": "iel"
+                },
+                "de": {
+                    "speakToAs/grammaticalGender": "masculine"
+                }
+            }
+        });
+        write_json("test_localizations_speak_to_as_path.json", &json)?;
+        let card: Card = serde_json::from_value(json).unwrap();
+
+        let localized = card.get_localized("fr")?;
+        let speak_to_as = localized.speak_to_as.unwrap();
+        let k19 = &speak_to_as.pronouns.as_ref().unwrap()["k19"];
+        assert_eq!(k19.pronouns, "iel");
+        assert_eq!(k19.pref, Some(2));
+
+        // the grammatical gender is not localizable
+        assert!(card.get_localized("de").is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_localizations_keywords() -> Result<(), Box<dyn std::error::Error>> {
         let json = serde_json::json!({
@@ -1246,16 +1223,44 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_keywords.json",
-            serde_json::to_string_pretty(&json).unwrap(),
-        )?;
+        write_json("test_localizations_keywords.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
 
         let localizations = card.get_localized("en").unwrap();
         let keywords = localizations.keywords.unwrap();
         let k1 = keywords.get("a_keyword").unwrap();
         assert_eq!(k1, &true);
+        // the whole map is replaced
+        assert_eq!(keywords.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_localizations_keywords_path_object() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "keywords": {
+                "internet": true,
+                "web": true
+            },
+            "localizations": {
+                "en": {
+                    "keywords/IETF": true,
+                    "keywords/web": null
+                }
+            }
+        });
+        write_json("test_localizations_keywords_path_object.json", &json)?;
+        let card: Card = serde_json::from_value(json).unwrap();
+
+        let localizations = card.get_localized("en").unwrap();
+        let keywords = localizations.keywords.unwrap();
+        assert_eq!(keywords.len(), 2);
+        assert_eq!(keywords.get("internet"), Some(&true));
+        assert_eq!(keywords.get("IETF"), Some(&true));
+        assert_eq!(keywords.get("web"), None);
         Ok(())
     }
 
@@ -1299,23 +1304,20 @@ mod test {
             }
         });
 
-        std::fs::write(
-            "tests/localizations/test_localizations_media.json",
-            serde_json::to_string_pretty(&json).unwrap(),
-        )?;
+        write_json("test_localizations_media.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
         let localized = card.get_localized("en").unwrap();
         let media = localized.media.unwrap();
         let res45 = media.get("res45").unwrap();
         assert_eq!(res45.kind, MediaKind::Sound);
-        assert_eq!(res45.uri, "CID:");
+        assert_eq!(&*res45.uri, "CID:");
         let res47 = media.get("res47").unwrap();
         assert_eq!(res47.kind, MediaKind::Logo);
-        assert_eq!(res47.uri, "https://www.example.com/pub/logos/abccorp.jpg");
+        assert_eq!(&*res47.uri, "https://www.example.com/pub/logos/abccorp.jpg");
         let res1 = media.get("res1").unwrap();
         assert_eq!(res1.kind, MediaKind::Photo);
         assert_eq!(
-            res1.uri,
+            &*res1.uri,
             "data:image/jpeg;base64,/9j/4AAQSkZJRgABAQAASABIAAD/4..."
         );
         Ok(())
@@ -1359,23 +1361,20 @@ mod test {
             }
         });
 
-        std::fs::write(
-            "tests/localizations/test_localizations_media_path_object_1.json",
-            serde_json::to_string_pretty(&json).unwrap(),
-        )?;
+        write_json("test_localizations_media_path_object_1.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
         let localized = card.get_localized("en").unwrap();
         let media = localized.media.unwrap();
         let res45 = media.get("res45").unwrap();
         assert_eq!(res45.kind, MediaKind::Sound);
-        assert_eq!(res45.uri, "CID:");
+        assert_eq!(&*res45.uri, "CID:");
         let res47 = media.get("res47").unwrap();
         assert_eq!(res47.kind, MediaKind::Logo);
-        assert_eq!(res47.uri, "https://www.example.com/pub/logos/abccorp.jpg");
+        assert_eq!(&*res47.uri, "https://www.example.com/pub/logos/abccorp.jpg");
         let res1 = media.get("res1").unwrap();
         assert_eq!(res1.kind, MediaKind::Photo);
         assert_eq!(
-            res1.uri,
+            &*res1.uri,
             "data:image/jpeg;base64,/9j/4AAQSkZJRgABAQAASABIAAD/4..."
         );
         Ok(())
@@ -1414,23 +1413,20 @@ mod test {
             }
         });
 
-        std::fs::write(
-            "tests/localizations/test_localizations_media_path_object_2.json",
-            serde_json::to_string_pretty(&json).unwrap(),
-        )?;
+        write_json("test_localizations_media_path_object_2.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
         let localized = card.get_localized("en").unwrap();
         let media = localized.media.unwrap();
         let res45 = media.get("res45").unwrap();
         assert_eq!(res45.kind, MediaKind::Sound);
-        assert_eq!(res45.uri, "CID:");
+        assert_eq!(&*res45.uri, "CID:");
         let res47 = media.get("res47").unwrap();
         assert_eq!(res47.kind, MediaKind::Logo);
-        assert_eq!(res47.uri, "https://www.example.com/pub/logos/abccorp.jpg");
+        assert_eq!(&*res47.uri, "https://www.example.com/pub/logos/abccorp.jpg");
         let res1 = media.get("res1").unwrap();
         assert_eq!(res1.kind, MediaKind::Photo);
         assert_eq!(
-            res1.uri,
+            &*res1.uri,
             "data:image/jpeg;base64,/9j/4AAQSkZJRgABAQAASABIAAD/4..."
         );
         Ok(())
@@ -1461,10 +1457,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_links.json",
-            serde_json::to_string_pretty(&json).unwrap(),
-        )?;
+        write_json("test_localizations_links.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
         let localized = card.get_localized("en").unwrap();
         let links = localized.links.unwrap();
@@ -1498,10 +1491,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_links_path_object_1.json",
-            serde_json::to_string_pretty(&json).unwrap(),
-        )?;
+        write_json("test_localizations_links_path_object_1.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
         let localized = card.get_localized("en").unwrap();
         let links = localized.links.unwrap();
@@ -1533,10 +1523,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_links_path_object_2.json",
-            serde_json::to_string_pretty(&json).unwrap(),
-        )?;
+        write_json("test_localizations_links_path_object_2.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
         let localized = card.get_localized("en").unwrap();
         let links = localized.links.unwrap();
@@ -1586,10 +1573,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_directory.json",
-            serde_json::to_string_pretty(&json).unwrap(),
-        )?;
+        write_json("test_localizations_directory.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
         let localized = card.get_localized("en").unwrap();
         let directories = localized.directories.unwrap();
@@ -1643,10 +1627,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_directory_path_object_1.json",
-            serde_json::to_string_pretty(&json).unwrap(),
-        )?;
+        write_json("test_localizations_directory_path_object_1.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
         let localized = card.get_localized("en").unwrap();
         let directories = localized.directories.unwrap();
@@ -1696,10 +1677,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_directory_path_object_2.json",
-            serde_json::to_string_pretty(&json).unwrap(),
-        )?;
+        write_json("test_localizations_directory_path_object_2.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
         let localized = card.get_localized("en").unwrap();
         let directories = localized.directories.unwrap();
@@ -1750,10 +1728,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_calendars.json",
-            serde_json::to_string_pretty(&json).unwrap(),
-        )?;
+        write_json("test_localizations_calendars.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
         let localized = card.get_localized("en").unwrap();
         let calendars = localized.calendars.unwrap();
@@ -1800,10 +1775,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_calendars_path_object_1.json",
-            serde_json::to_string_pretty(&json).unwrap(),
-        )?;
+        write_json("test_localizations_calendars_path_object_1.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
         let localized = card.get_localized("en").unwrap();
         let calendars = localized.calendars.unwrap();
@@ -1846,10 +1818,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_calendars_path_object_2.json",
-            serde_json::to_string_pretty(&json).unwrap(),
-        )?;
+        write_json("test_localizations_calendars_path_object_2.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
         let localized = card.get_localized("en").unwrap();
         let calendars = localized.calendars.unwrap();
@@ -1887,10 +1856,7 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_scheduling_addresses.json",
-            serde_json::to_string_pretty(&json).unwrap(),
-        )?;
+        write_json("test_localizations_scheduling_addresses.json", &json)?;
         let card: Card = serde_json::from_value(json).unwrap();
         let localized = card.get_localized("en").unwrap();
         let scheduling_addresses = localized.scheduling_addresses.unwrap();
@@ -1922,9 +1888,9 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_scheduling_addresses_path_object_1.json",
-            serde_json::to_string_pretty(&json).unwrap(),
+        write_json(
+            "test_localizations_scheduling_addresses_path_object_1.json",
+            &json,
         )?;
         let card: Card = serde_json::from_value(json).unwrap();
         let localized = card.get_localized("en").unwrap();
@@ -1955,9 +1921,9 @@ mod test {
                 }
             }
         });
-        std::fs::write(
-            "tests/localizations/test_localizations_scheduling_addresses_path_object_2.json",
-            serde_json::to_string_pretty(&json).unwrap(),
+        write_json(
+            "test_localizations_scheduling_addresses_path_object_2.json",
+            &json,
         )?;
         let card: Card = serde_json::from_value(json).unwrap();
         let localized = card.get_localized("en").unwrap();
@@ -1967,4 +1933,478 @@ mod test {
         assert_eq!(sched1.label, Some("Jane Doe english".to_string()));
         Ok(())
     }
+
+    #[test]
+    fn test_localizations_chain() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "titles": {
+                "t1": { "name": "Manager" },
+                "t2": { "name": "Engineer" }
+            },
+            "localizations": {
+                "fr": {
+                    "titles/t1/name": "Directeur",
+                    "titles/t2/name": "Ingénieur",
+                    "name/full": "Jean Dupont"
+                },
+                "fr-CA": {
+                    "titles/t1/name": "Gérant"
+                },
+                "zh-Hant": {
+                    "name/full": "王小明"
+                }
+            }
+        });
+        write_json("test_localizations_chain.json", &json)?;
+        let card: Card = serde_json::from_value(json).unwrap();
+
+        let localized = card.get_localized_chain("fr-CA")?;
+        assert_eq!(localized.language, Some("fr-CA".to_string()));
+        let titles = localized.titles.unwrap();
+        assert_eq!(titles.get("t1").unwrap().name, "Gérant");
+        assert_eq!(titles.get("t2").unwrap().name, "Ingénieur");
+        assert_eq!(localized.name.unwrap().full.unwrap(), "Jean Dupont");
+
+        let localized = card.get_localized_chain("fr-BE")?;
+        assert_eq!(localized.language, Some("fr".to_string()));
+        let titles = localized.titles.unwrap();
+        assert_eq!(titles.get("t1").unwrap().name, "Directeur");
+
+        let localized = card.get_localized_chain("zh-Hant-TW")?;
+        assert_eq!(localized.language, Some("zh-Hant".to_string()));
+        assert_eq!(localized.name.unwrap().full.unwrap(), "王小明");
+
+        let localized = card.get_localized_chain("de-DE")?;
+        assert_eq!(localized, card);
+        Ok(())
+    }
+
+    #[test]
+    fn test_localizations_identity_and_fallback() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "language": "en",
+            "name": { "full": "John Smith" },
+            "localizations": {
+                "en": { "name/full": "Ignored" },
+                "fr": { "name/full": "Jean Dupont" },
+                "de": { "uid/full": "Johann" }
+            }
+        });
+        write_json("test_localizations_identity_and_fallback.json", &json)?;
+        let card: Card = serde_json::from_value(json).unwrap();
+
+        // the Card is already in English, its patches are not applied
+        let localized = card.get_localized("EN")?;
+        assert_eq!(localized, card);
+        assert!(card.is_localizable_into("en"));
+
+        // exact match
+        let localized = card.get_localized("fr")?;
+        assert_eq!(localized.language, Some("fr".to_string()));
+        assert_eq!(localized.name.unwrap().full.unwrap(), "Jean Dupont");
+        assert_eq!(card.get_localized_or_base("fr"), card.get_localized("fr")?);
+        assert!(card.is_localizable_into("fr"));
+
+        // unknown language and invalid localization fall back to the base Card
+        assert!(!card.is_localizable_into("es"));
+        assert_eq!(card.get_localized_or_base("es"), card);
+        assert!(card.get_localized("de").is_err());
+        assert_eq!(card.get_localized_or_base("de"), card);
+        Ok(())
+    }
+
+    #[test]
+    fn test_localizations_not_localizable() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "phones": {
+                "tel0": {
+                    "number": "tel:+1-555-555-5555",
+                    "contexts": { "work": true },
+                    "label": "office"
+                }
+            },
+            "localizations": {
+                "fr": {
+                    "phones/tel0/contexts": { "private": true },
+                    "phones/tel0/label": "bureau"
+                },
+                "de": {
+                    "phones/tel0": {
+                        "number": "tel:+1-555-555-5555",
+                        "contexts": { "private": true },
+                        "label": "Büro"
+                    }
+                },
+                "es": {
+                    "phones/tel0/number": "tel:+1-555-555-5555",
+                    "phones/tel0/label": "oficina"
+                }
+            }
+        });
+        write_json("test_localizations_not_localizable.json", &json)?;
+        let card: Card = serde_json::from_value(json).unwrap();
+        let work = |card: &Card| card.phones.as_ref().unwrap()["tel0"].contexts.clone();
+        let label = |card: &Card| card.phones.as_ref().unwrap()["tel0"].label.clone();
+
+        // the contexts are not localizable
+        let error = card.get_localized("fr").unwrap_err();
+        assert_eq!(
+            error,
+            "fr: phones/tel0/contexts: The value is not localizable"
+        );
+        assert!(card.get_localized("de").is_err());
+        assert!(card.get_localized_chain("fr-CA").is_err());
+        assert_eq!(card.get_localized_or_base("fr"), card);
+
+        // the lenient localization skips them
+        let (localized, issues) = card.get_localized_lenient("fr")?;
+        assert_eq!(work(&localized), work(&card));
+        assert_eq!(label(&localized), Some("bureau".to_string()));
+        assert_eq!(
+            issues,
+            vec![LocalizationIssue {
+                language: "fr".to_string(),
+                path: "phones/tel0/contexts".to_string(),
+                message: "The value is not localizable".to_string(),
+            }]
+        );
+        // the other fields of an object patch are localized
+        let (localized, issues) = card.get_localized_lenient("de")?;
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "phones/tel0/contexts");
+        assert!(work(&localized).unwrap()[&Context::Work]);
+        assert_eq!(label(&localized), Some("Büro".to_string()));
+
+        // an unchanged value is not an issue
+        let localized = card.get_localized("es")?;
+        assert_eq!(label(&localized), Some("oficina".to_string()));
+        assert_eq!(card.get_localized_lenient("es")?.1, vec![]);
+        Ok(())
+    }
+
+    /// A Card with an address of one component and the patches of a localization
+    fn card_with_patches(patches: serde_json::Value) -> Card {
+        serde_json::from_value(serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "addresses": {
+                "k26": { "components": [{ "kind": "region", "value": "reg" }] }
+            },
+            "localizations": { "en": patches }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_localizations_large_index() {
+        let card = card_with_patches(serde_json::json!({
+            "addresses/k26/components/1000000000/value": "far away"
+        }));
+        let start = std::time::Instant::now();
+        let error = card.get_localized("en").unwrap_err();
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+        assert_eq!(
+            error,
+            "Invalid path 'addresses/k26/components/1000000000/value': index 1000000000 is more than 64 elements past the end of the array"
+        );
+        assert!(card.get_localized_lenient("en").is_err());
+        assert!(card.get_localized_chain("en-US").is_err());
+
+        // the delta is counted from the end of the array
+        let card = card_with_patches(serde_json::json!({
+            "addresses/k26/components/1/value": "東京都",
+            "addresses/k26/components/1/kind": "region"
+        }));
+        let localized = card.get_localized("en").unwrap();
+        let components = localized.addresses.unwrap()["k26"]
+            .components
+            .clone()
+            .unwrap();
+        assert_eq!(components.len(), 2);
+        let strict = LocalizeOptions {
+            limits: PatchLimits {
+                max_index_delta: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(card.get_localized_with("en", &strict).is_ok());
+    }
+
+    #[test]
+    fn test_localizations_deep_path() {
+        let card = card_with_patches(serde_json::json!({
+            format!("addresses{}", "/a".repeat(10_000)): "deep"
+        }));
+        let error = card.get_localized("en").unwrap_err();
+        assert!(error.contains("10001 segments (maximum 16)"), "{}", error);
+    }
+
+    #[test]
+    fn test_localizations_patch_count() {
+        let patches: serde_json::Map<String, serde_json::Value> = (0..1_000)
+            .map(|idx| {
+                (
+                    format!("nicknames/k{}", idx),
+                    serde_json::json!({ "name": format!("Nick {}", idx) }),
+                )
+            })
+            .collect();
+        let card = card_with_patches(serde_json::Value::Object(patches));
+        let localized = card.get_localized("en").unwrap();
+        assert_eq!(localized.nicknames.unwrap().len(), 1_000);
+
+        let opts = LocalizeOptions {
+            limits: PatchLimits {
+                max_patches: 999,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            card.get_localized_with("en", &opts).unwrap_err(),
+            "Too many patches for 'en': 1000 (maximum 999)"
+        );
+    }
+
+    #[test]
+    fn test_localizations_unknown_property() {
+        let card = card_with_patches(serde_json::json!({
+            "pgones/tel0/label": "bureau",
+            "addresses/k26/components/0/value": "région"
+        }));
+        let error = card.get_localized("en").unwrap_err();
+        assert!(
+            error
+                .starts_with("en: pgones/tel0/label: Unknown property 'pgones', expected one of: "),
+            "{}",
+            error
+        );
+        assert!(error.contains("phones"), "{}", error);
+
+        // the lenient localization reports it and applies the other patches
+        let (localized, issues) = card.get_localized_lenient("en").unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "pgones/tel0/label");
+        let components = localized.addresses.unwrap()["k26"]
+            .components
+            .clone()
+            .unwrap();
+        assert_eq!(components[0].value, "région");
+    }
+
+    #[test]
+    fn test_localizations_property_prefix() {
+        let card: Card = serde_json::from_value(serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "name": { "full": "Joe" },
+            "localizations": { "en": { "namespace/x": "Jane" } }
+        }))
+        .unwrap();
+        assert!(card.get_localized("en").is_err());
+        let (localized, issues) = card.get_localized_lenient("en").unwrap();
+        assert_eq!(issues[0].path, "namespace/x");
+        assert!(issues[0]
+            .message
+            .starts_with("Unknown property 'namespace'"));
+        assert_eq!(localized.name, card.name);
+    }
+
+    /// A Card with a French localization, and a donor with French and German localizations
+    fn cards_to_merge() -> (Card, Card) {
+        let card: Card = serde_json::from_value(serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "titles": {
+                "t1": { "name": "Research Scientist" },
+                "t2": { "name": "Project Leader" }
+            },
+            "localizations": {
+                "fr": { "titles/t1/name": "Chercheur" }
+            }
+        }))
+        .unwrap();
+        let donor: Card = serde_json::from_value(serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "localizations": {
+                "fr": { "titles/t2/name": "Chef de projet" },
+                "de": { "titles/t1/name": "Forscher" }
+            }
+        }))
+        .unwrap();
+        (card, donor)
+    }
+
+    #[test]
+    fn test_localizations_merge_error() {
+        let (mut card, donor) = cards_to_merge();
+        let before = card.clone();
+        let error = card
+            .merge_localizations_from(&donor, LocalizationMergePolicy::Error)
+            .unwrap_err();
+        assert_eq!(error, "The Card already has other patches for 'fr'");
+        assert_eq!(card, before);
+
+        // the same patches are skipped
+        let report = card
+            .merge_localizations_from(&before, LocalizationMergePolicy::Error)
+            .unwrap();
+        assert_eq!(report.skipped, ["fr"]);
+        assert!(report.added.is_empty() && report.merged.is_empty());
+    }
+
+    #[test]
+    fn test_localizations_merge_overwrite() {
+        let (mut card, donor) = cards_to_merge();
+        let report = card
+            .merge_localizations_from(&donor, LocalizationMergePolicy::Overwrite)
+            .unwrap();
+        assert_eq!(report.added, ["de"]);
+        assert_eq!(report.merged, ["fr"]);
+        assert!(report.skipped.is_empty());
+        let fr = card.get_localized("fr").unwrap().titles.unwrap();
+        assert_eq!(fr["t1"].name, "Research Scientist");
+        assert_eq!(fr["t2"].name, "Chef de projet");
+        let de = card.get_localized("de").unwrap().titles.unwrap();
+        assert_eq!(de["t1"].name, "Forscher");
+    }
+
+    #[test]
+    fn test_localizations_merge_patches() {
+        let (mut card, donor) = cards_to_merge();
+        let report = card
+            .merge_localizations_from(&donor, LocalizationMergePolicy::MergePatches)
+            .unwrap();
+        assert_eq!(report.added, ["de"]);
+        assert_eq!(report.merged, ["fr"]);
+        let fr = card.get_localized("fr").unwrap().titles.unwrap();
+        assert_eq!(fr["t1"].name, "Chercheur");
+        assert_eq!(fr["t2"].name, "Chef de projet");
+        assert_eq!(card.get_raw_localizations().unwrap()["fr"].len(), 2);
+    }
+
+    #[test]
+    fn test_localizations_merge_invalid() {
+        let (mut card, _) = cards_to_merge();
+        let before = card.clone();
+        let donor = card_with_patches(serde_json::json!({ "titles/t9/name": "Inconnu" }));
+        let error = card
+            .merge_localizations_from(&donor, LocalizationMergePolicy::Overwrite)
+            .unwrap_err();
+        assert_eq!(
+            error,
+            "en: titles/t9/name: the patched value does not exist in the Card"
+        );
+        let donor = card_with_patches(serde_json::json!({ "uid": "5678" }));
+        assert!(card
+            .merge_localizations_from(&donor, LocalizationMergePolicy::Overwrite)
+            .is_err());
+        assert_eq!(card, before);
+    }
+
+    /// A Card whose phone has a vendor-specific context and feature, with a localization
+    fn card_with_vendor_context(patches: serde_json::Value) -> Card {
+        serde_json::from_value(serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "phones": {
+                "tel0": {
+                    "number": "+1-555-555-5555",
+                    "contexts": { "example.com:oncall": true, "work": true },
+                    "features": { "example.com:satellite": true, "voice": true },
+                    "label": "Office"
+                }
+            },
+            "localizations": { "fr": patches }
+        }))
+        .unwrap()
+    }
+
+    /// Check that the phone keeps its vendor-specific context and feature, with their original spelling
+    fn check_vendor_context(card: &Card) {
+        let phone = &card.phones.as_ref().unwrap()["tel0"];
+        let oncall = Context::Other("example.com:oncall".to_string());
+        assert!(phone.contexts.as_ref().unwrap()[&oncall]);
+        let satellite = PhoneFeature::Other("example.com:satellite".to_string());
+        assert!(phone.features.as_ref().unwrap()[&satellite]);
+        let value = serde_json::to_value(card).unwrap();
+        assert_eq!(
+            value["phones"]["tel0"]["contexts"]["example.com:oncall"],
+            true
+        );
+        assert_eq!(
+            value["phones"]["tel0"]["features"]["example.com:satellite"],
+            true
+        );
+    }
+
+    #[test]
+    fn test_localizations_vendor_context_label() {
+        let card = card_with_vendor_context(serde_json::json!({ "phones/tel0/label": "Bureau" }));
+        check_vendor_context(&card);
+        let localized = card.get_localized("fr").unwrap();
+        assert_eq!(
+            localized.phones.as_ref().unwrap()["tel0"].label.as_deref(),
+            Some("Bureau")
+        );
+        check_vendor_context(&localized);
+    }
+
+    #[test]
+    fn test_localizations_vendor_context_object() {
+        // the whole phone is patched, its contexts are unchanged
+        let card = card_with_vendor_context(serde_json::json!({
+            "phones/tel0": {
+                "number": "+1-555-555-5555",
+                "contexts": { "work": true, "example.com:oncall": true },
+                "features": { "voice": true, "example.com:satellite": true },
+                "label": "Bureau"
+            }
+        }));
+        let localized = card.get_localized("fr").unwrap();
+        assert_eq!(
+            localized.phones.as_ref().unwrap()["tel0"].label.as_deref(),
+            Some("Bureau")
+        );
+        check_vendor_context(&localized);
+    }
+
+    #[test]
+    fn test_localizations_vendor_context_patch() {
+        let card = card_with_vendor_context(serde_json::json!({
+            "phones/tel0/contexts": { "example.com:oncall": true },
+            "phones/tel0/label": "Bureau"
+        }));
+
+        // the contexts are not localizable
+        let error = card.get_localized("fr").unwrap_err();
+        assert_eq!(
+            error,
+            "fr: phones/tel0/contexts: The value is not localizable"
+        );
+
+        let (localized, issues) = card.get_localized_lenient("fr").unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "phones/tel0/contexts");
+        let phone = &localized.phones.as_ref().unwrap()["tel0"];
+        assert_eq!(phone.label.as_deref(), Some("Bureau"));
+        assert_eq!(phone.contexts.as_ref().unwrap().len(), 2);
+        check_vendor_context(&localized);
+    }
 }
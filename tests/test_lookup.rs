@@ -0,0 +1,54 @@
+mod test {
+    use jscontact::lookup::get_ci;
+    use jscontact::Card;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_get_ci() {
+        let map = HashMap::from([
+            ("Dir1".to_string(), "a"),
+            ("dir1".to_string(), "b"),
+            ("DIR1".to_string(), "c"),
+        ]);
+        // the exact match wins
+        assert_eq!(get_ci(&map, "Dir1").map(|(_, v)| *v), Some("a"));
+        assert_eq!(get_ci(&map, "dir1").map(|(_, v)| *v), Some("b"));
+        // otherwise the first key in sorted order
+        assert_eq!(get_ci(&map, "dIR1").map(|(k, _)| k.as_str()), Some("DIR1"));
+        // the key is trimmed
+        assert_eq!(get_ci(&map, " dir1\n").map(|(_, v)| *v), Some("b"));
+        assert_eq!(get_ci(&map, "dir"), None);
+        assert_eq!(get_ci(&HashMap::<String, ()>::new(), "dir1"), None);
+    }
+
+    #[test]
+    fn test_card_lookup_ci() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "emails": {
+                "Work": { "address": "work@example.com" },
+                "WORK": { "address": "WORK@example.com" },
+                "home": { "address": "home@example.com" }
+            },
+            "phones": { "Tel0": { "number": "tel:+1-555-555-5555" } },
+            "addresses": { "k23": { "full": "Somewhere" } }
+        }))?;
+        let (key, email) = card.email_by_key_ci("work").unwrap();
+        assert_eq!((key, email.address.as_str()), ("WORK", "WORK@example.com"));
+        let (key, _) = card.email_by_key_ci("Work").unwrap();
+        assert_eq!(key, "Work");
+        assert_eq!(card.email_by_key_ci(" HOME ").unwrap().0, "home");
+        assert!(card.email_by_key_ci("private").is_none());
+        assert_eq!(card.phone_by_key_ci("tel0").unwrap().0, "Tel0");
+        assert_eq!(
+            card.address_by_key_ci("K23").unwrap().1.full.as_deref(),
+            Some("Somewhere")
+        );
+        let empty = Card::new_with_latest_version("1234");
+        assert!(empty.address_by_key_ci("k23").is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+mod test {
+    use jscontact::{
+        infer_media_type, Calendar, Card, CryptoKey, Link, Media, MediaKind, SmallString,
+    };
+
+    #[test]
+    fn test_infer_media_type_data_uri() {
+        assert_eq!(
+            infer_media_type("data:image/jpeg;base64,/9j/4AAQSkZJRgABAQAASABIAAD/4..."),
+            Some("image/jpeg")
+        );
+        assert_eq!(
+            infer_media_type("data:Application/PGP-Keys;base64,LS0t"),
+            Some("application/pgp-keys")
+        );
+        assert_eq!(
+            infer_media_type("data:,Hello%2C%20World"),
+            Some("text/plain")
+        );
+        assert_eq!(
+            infer_media_type("data:;base64,SGVsbG8="),
+            Some("text/plain")
+        );
+        assert_eq!(
+            infer_media_type("data:application/x-unknown;base64,AAAA"),
+            None
+        );
+        assert_eq!(infer_media_type("data:image/png"), None);
+    }
+
+    #[test]
+    fn test_infer_media_type_extension() {
+        let cases = [
+            (
+                "https://www.example.com/pub/logos/abccorp.jpg",
+                Some("image/jpeg"),
+            ),
+            ("https://www.example.com/photo.PNG", Some("image/png")),
+            (
+                "webcal://calendar.example.com/calA.ics",
+                Some("text/calendar"),
+            ),
+            ("https://example.com/jdoe.vcf", Some("text/vcard")),
+            (
+                "https://example.com/keys/jdoe.asc",
+                Some("application/pgp-keys"),
+            ),
+            ("file:///home/jdoe/cert.pem", Some("application/x-pem-file")),
+            // query strings and fragments are ignored
+            (
+                "https://cdn.example.com/a.webp?width=200&v=1.2",
+                Some("image/webp"),
+            ),
+            ("https://cdn.example.com/a.gif#frame.png", Some("image/gif")),
+            ("https://cdn.example.com/image?name=a.png", None),
+            // unknown extensions, no extension, or only a host
+            ("https://example.com/archive.tar.xz", None),
+            ("https://calendar.example.com/busy/project-a", None),
+            ("https://example.com", None),
+            ("https://example.com/", None),
+            ("CID:JOHNQ.part8.19960229T080000.xyzMail@example.com", None),
+            ("not a uri", None),
+        ];
+        for (uri, media_type) in cases {
+            assert_eq!(infer_media_type(uri), media_type, "{}", uri);
+        }
+    }
+
+    #[test]
+    fn test_ensure_media_type() {
+        let mut media = Media::new("https://example.com/me.png", MediaKind::Photo);
+        assert!(media.ensure_media_type());
+        assert_eq!(media.media_type.as_deref(), Some("image/png"));
+
+        // an existing value is never overwritten
+        let mut key = CryptoKey::new("https://example.com/key.asc");
+        key.media_type = Some(SmallString::from("application/octet-stream"));
+        assert!(!key.ensure_media_type());
+        assert_eq!(key.media_type.as_deref(), Some("application/octet-stream"));
+
+        let mut calendar = Calendar::new("https://example.com/busy");
+        assert!(!calendar.ensure_media_type());
+        assert_eq!(calendar.media_type, None);
+    }
+
+    #[test]
+    fn test_card_infer_media_types() {
+        let mut card =
+            Card::try_from(include_bytes!("./rfc9553/figure_38.json").as_slice()).unwrap();
+        assert_eq!(card.infer_media_types(), 2);
+        let media = card.media.as_ref().unwrap();
+        assert_eq!(
+            media[&SmallString::from("res1")].media_type.as_deref(),
+            Some("image/jpeg")
+        );
+        assert_eq!(
+            media[&SmallString::from("res47")].media_type.as_deref(),
+            Some("image/jpeg")
+        );
+        assert_eq!(media[&SmallString::from("res45")].media_type, None);
+        assert_eq!(card.infer_media_types(), 0);
+
+        let mut card =
+            Card::try_from(include_bytes!("./rfc9553/figure_29.json").as_slice()).unwrap();
+        assert_eq!(card.infer_media_types(), 1);
+        let mut card =
+            Card::try_from(include_bytes!("./rfc9553/figure_35.json").as_slice()).unwrap();
+        assert_eq!(card.infer_media_types(), 1);
+    }
+
+    #[test]
+    fn test_calendar_is_ics() {
+        let webcal = Calendar::new("WEBCAL://calendar.example.com/jane");
+        assert!(webcal.is_webcal());
+        assert!(webcal.is_ics());
+        let https = Calendar::new("https://calendar.example.com/jane.ics");
+        assert!(!https.is_webcal());
+        assert!(https.is_ics());
+        let mut page = Calendar::new("https://calendar.example.com/jane");
+        assert!(!page.is_ics());
+        page.media_type = Some("text/calendar; charset=utf-8".into());
+        assert!(page.is_ics());
+        // the media type wins over the URI
+        let mut html = Calendar::new("webcal://calendar.example.com/jane.ics");
+        html.media_type = Some("text/html".into());
+        assert!(!html.is_ics());
+        // a relative URI has no scheme, nor an inferred media type
+        assert!(!Calendar::new("calendars/jane.ics").is_webcal());
+        assert!(!Calendar::new("calendars/jane.ics").is_ics());
+    }
+
+    #[test]
+    fn test_link_is_probably_html() {
+        assert!(Link::new("https://www.example.com/").is_probably_html());
+        assert!(Link::new("HTTP://www.example.com/jane.html").is_probably_html());
+        assert!(!Link::new("https://www.example.com/cv.pdf").is_probably_html());
+        assert!(!Link::new("CID:part1.0603@example.com").is_probably_html());
+        assert!(!Link::new("data:text/html,<p>Jane</p>").is_probably_html());
+        assert!(!Link::new("jane/index.html").is_probably_html());
+        let mut link = Link::new("cid:part2.0603@example.com");
+        link.media_type = Some("application/xhtml+xml".into());
+        assert!(link.is_probably_html());
+        let mut link = Link::new("https://www.example.com/feed");
+        link.media_type = Some("application/rss+xml".into());
+        assert!(!link.is_probably_html());
+    }
+
+    #[test]
+    fn test_photo_preferring() {
+        let card = Card::try_from(serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "media": {
+                "jpeg": { "kind": "photo", "uri": "data:image/jpeg;base64,/9j/", "pref": 1 },
+                "webp": { "kind": "photo", "uri": "https://example.com/jane.webp", "pref": 2 },
+                "png": { "kind": "photo", "uri": "cid:photo@example.com", "mediaType": "image/png", "pref": 3 },
+                "unknown": { "kind": "photo", "uri": "https://example.com/photo" },
+                "logo": { "kind": "logo", "uri": "https://example.com/logo.webp" }
+            }
+        }))
+        .unwrap();
+        let preferring = |accepted: &[&str]| {
+            let media = card.media.as_ref().unwrap();
+            let photo = card.photo_preferring(accepted).unwrap();
+            media
+                .iter()
+                .find(|(_, media)| *media == photo)
+                .map(|(key, _)| key.to_string())
+                .unwrap()
+        };
+        assert_eq!(preferring(&["image/webp", "image/png"]), "webp");
+        assert_eq!(preferring(&["image/png", "image/webp"]), "png");
+        assert_eq!(preferring(&["IMAGE/PNG"]), "png");
+        // the pref breaks the ties of a wildcard
+        assert_eq!(preferring(&["image/*"]), "jpeg");
+        assert_eq!(preferring(&["image/gif", "image/*"]), "jpeg");
+        assert_eq!(preferring(&["*/*"]), "jpeg");
+        // no photo matches, the preferred one with a known media type is used
+        assert_eq!(preferring(&["image/avif"]), "jpeg");
+        assert_eq!(preferring(&[]), "jpeg");
+
+        let mut card = Card::new_with_latest_version("1234");
+        assert!(card.photo_preferring(&["image/*"]).is_none());
+        let media = card.media.get_or_insert_with(Default::default);
+        media.insert(
+            "unknown".into(),
+            Media::new("https://example.com/photo", MediaKind::Photo),
+        );
+        media.insert(
+            "logo".into(),
+            Media::new("https://example.com/logo.png", MediaKind::Logo),
+        );
+        assert_eq!(
+            &*card.photo_preferring(&["image/png"]).unwrap().uri,
+            "https://example.com/photo"
+        );
+    }
+}
@@ -0,0 +1,285 @@
+// Live heap bytes are counted per thread so that tests running in parallel
+// do not pollute each other's numbers.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+thread_local! {
+    static LIVE_BYTES: Cell<isize> = const { Cell::new(0) };
+    static ALLOCATIONS: Cell<isize> = const { Cell::new(0) };
+    static ALLOCATION_CALLS: Cell<isize> = const { Cell::new(0) };
+}
+
+/// Size of the heap chunk of an allocation, with the header and the minimum size of a typical malloc
+fn chunk_size(layout: Layout) -> isize {
+    ((layout.size() + 8).next_multiple_of(16)).max(32) as isize
+}
+
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LIVE_BYTES.with(|bytes| bytes.set(bytes.get() + chunk_size(layout)));
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        ALLOCATION_CALLS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.with(|bytes| bytes.set(bytes.get() - chunk_size(layout)));
+        ALLOCATIONS.with(|count| count.set(count.get() - 1));
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Get the live heap bytes and the live allocations created by the closure
+fn live_heap<F: FnOnce()>(f: F) -> (isize, isize) {
+    let bytes = LIVE_BYTES.with(Cell::get);
+    let allocations = ALLOCATIONS.with(Cell::get);
+    f();
+    (
+        LIVE_BYTES.with(Cell::get) - bytes,
+        ALLOCATIONS.with(Cell::get) - allocations,
+    )
+}
+
+/// Get the number of allocations made by the closure, including the ones freed before it returns
+#[cfg(feature = "shared")]
+fn allocation_calls<F: FnOnce()>(f: F) -> isize {
+    let calls = ALLOCATION_CALLS.with(Cell::get);
+    f();
+    ALLOCATION_CALLS.with(Cell::get) - calls
+}
+
+mod test {
+    use super::live_heap;
+    use jscontact::Card;
+
+    const CARDS: usize = 10_000;
+
+    /// The number of map keys and media types of a synthetic Card
+    const SMALL_STRINGS: isize = 7;
+
+    /// A map key or a media type of a synthetic Card, short enough to be stored inline with the `compact` feature
+    /// or too long for it
+    fn small_string(value: &str, inline: bool) -> String {
+        match inline {
+            true => value.to_string(),
+            false => format!("{}-{}", value, "x".repeat(24)),
+        }
+    }
+
+    fn synthetic_card_with(i: usize, inline: bool) -> String {
+        let key = |key: &str| small_string(key, inline);
+        serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": format!("urn:uuid:{}", i),
+            "emails": {
+                key("e1"): { "address": format!("user{}@example.com", i), "contexts": { "work": true } },
+                key("e2"): { "address": format!("home{}@example.com", i), "contexts": { "private": true } }
+            },
+            "phones": {
+                key("p1"): { "number": "+1-555-555-5555", "features": { "voice": true, "mobile": true } }
+            },
+            "media": {
+                key("m1"): {
+                    "kind": "photo",
+                    "uri": "https://example.com/photo.jpg",
+                    "mediaType": key("image/jpeg")
+                }
+            },
+            "links": {
+                key("l1"): { "uri": "https://example.com", "mediaType": key("text/html") }
+            },
+            "keywords": { "internet": true, "friends": true }
+        })
+        .to_string()
+    }
+
+    /// Get the live heap bytes and allocations per Card of the parsed Cards
+    fn footprint(inline: bool) -> (isize, isize) {
+        let sources: Vec<String> = (0..CARDS).map(|i| synthetic_card_with(i, inline)).collect();
+        sources_footprint(&sources)
+    }
+
+    /// Get the live heap bytes and allocations per Card of the Cards parsed from JSON texts
+    fn sources_footprint(sources: &[String]) -> (isize, isize) {
+        let mut cards: Vec<Card> = Vec::with_capacity(CARDS);
+        let (bytes, allocations) = live_heap(|| {
+            for source in sources {
+                cards.push(serde_json::from_str(source).unwrap());
+            }
+        });
+        assert_eq!(cards.len(), CARDS);
+        (bytes / CARDS as isize, allocations / CARDS as isize)
+    }
+
+    #[test]
+    fn test_card_heap_footprint() {
+        let (short_bytes, short_allocations) = footprint(true);
+        let (long_bytes, long_allocations) = footprint(false);
+        // the short map keys and media types are stored inline with the compact feature, each saving an allocation
+        // of at least 32 bytes, the long ones are allocated whatever the features
+        #[cfg(feature = "compact")]
+        {
+            assert_eq!(
+                short_allocations,
+                long_allocations - SMALL_STRINGS,
+                "{} {}",
+                short_allocations,
+                long_allocations
+            );
+            assert!(
+                short_bytes <= long_bytes - 32 * SMALL_STRINGS,
+                "{} {}",
+                short_bytes,
+                long_bytes
+            );
+        }
+        #[cfg(not(feature = "compact"))]
+        {
+            assert_eq!(
+                short_allocations, long_allocations,
+                "{} {}",
+                short_allocations, long_allocations
+            );
+            // only the size of the allocations differs
+            assert!(
+                long_bytes - short_bytes < 32 * SMALL_STRINGS,
+                "{} {}",
+                short_bytes,
+                long_bytes
+            );
+        }
+    }
+
+    #[test]
+    fn test_contexts_heap_footprint() {
+        let with: Vec<String> = (0..CARDS).map(|i| synthetic_card_with(i, true)).collect();
+        let without: Vec<String> = with
+            .iter()
+            .map(|source| {
+                let mut card: serde_json::Value = serde_json::from_str(source).unwrap();
+                for (property, map) in [("emails", "contexts"), ("phones", "features")] {
+                    for entry in card[property].as_object_mut().unwrap().values_mut() {
+                        entry.as_object_mut().unwrap().remove(map);
+                    }
+                }
+                card.to_string()
+            })
+            .collect();
+        let (with_bytes, with_allocations) = sources_footprint(&with);
+        let (without_bytes, without_allocations) = sources_footprint(&without);
+        // the registered contexts and features make no allocation, each map is one table of 4 buckets, whose
+        // entries are as large as a String (32 bytes) since the vendor-specific values are kept
+        assert_eq!(with_allocations - without_allocations, 3);
+        assert!(
+            with_bytes - without_bytes <= 3 * (4 * 32 + 32),
+            "{} {}",
+            with_bytes,
+            without_bytes
+        );
+    }
+
+    #[cfg(feature = "shared")]
+    mod interned {
+        use super::super::{allocation_calls, live_heap};
+        use super::{synthetic_card_with, CARDS};
+        use jscontact::{Card, CardSeed, CardSet, Interner, ReadError};
+        use serde::de::DeserializeSeed;
+
+        fn synthetic_batch() -> String {
+            let cards: Vec<String> = (0..CARDS).map(|i| synthetic_card_with(i, true)).collect();
+            format!("[{}]", cards.join(","))
+        }
+
+        #[test]
+        fn test_interned_allocations() {
+            let batch = synthetic_batch();
+            let mut plain = CardSet::default();
+            let plain_calls = allocation_calls(|| {
+                plain = serde_json::from_reader(batch.as_bytes()).unwrap();
+            });
+            let mut interned = CardSet::default();
+            let interned_calls = allocation_calls(|| {
+                interned = CardSet::from_reader_interned(batch.as_bytes()).unwrap();
+            });
+            assert_eq!(interned, plain);
+            // the photo of the Cards after the first one makes no allocation, not even a temporary one,
+            // the interner making one for its table
+            assert!(
+                interned_calls <= plain_calls - (CARDS as isize - 1) + 1,
+                "{} {}",
+                interned_calls,
+                plain_calls
+            );
+        }
+
+        #[test]
+        fn test_interned_heap_footprint() {
+            let batch = synthetic_batch();
+            let mut plain = CardSet::default();
+            let (plain_bytes, plain_allocations) = live_heap(|| {
+                plain = serde_json::from_str(&batch).unwrap();
+            });
+            let mut interned = CardSet::default();
+            let (interned_bytes, interned_allocations) = live_heap(|| {
+                interned = CardSet::from_reader_interned(batch.as_bytes()).unwrap();
+            });
+            assert_eq!(interned, plain);
+            // the photo of all the Cards is one allocation
+            assert!(
+                interned_allocations <= plain_allocations - CARDS as isize + 1,
+                "{} {}",
+                interned_allocations,
+                plain_allocations
+            );
+            assert!(interned_bytes < plain_bytes);
+        }
+
+        #[test]
+        fn test_card_seed_equal() {
+            let mut interner = Interner::new();
+            let sources: Vec<String> = (0..10).map(|i| synthetic_card_with(i, true)).collect();
+            for source in &sources {
+                let mut deserializer = serde_json::Deserializer::from_str(source);
+                let card = CardSeed::new(&mut interner)
+                    .deserialize(&mut deserializer)
+                    .unwrap();
+                assert_eq!(card, serde_json::from_str::<Card>(source).unwrap());
+            }
+            assert_eq!(interner.len(), 1);
+            // the interner is only active during the deserialization with the seed
+            let first: Card = serde_json::from_str(&sources[0]).unwrap();
+            let second: Card = serde_json::from_str(&sources[1]).unwrap();
+            let uri = |card: &Card| card.media.as_ref().unwrap()["m1"].uri.clone();
+            assert!(!std::sync::Arc::ptr_eq(&uri(&first), &uri(&second)));
+        }
+
+        #[test]
+        fn test_interned_errors() {
+            let json =
+                r#"[{"@type":"Card","version":"1.0","uid":"1"},{"@type":"Card","version":"1.0"}]"#;
+            let error = CardSet::from_reader_interned(json.as_bytes()).unwrap_err();
+            assert!(
+                error.to_string().starts_with("Card at index 1: "),
+                "{}",
+                error
+            );
+            assert!(CardSet::from_reader_interned(&b"[] []"[..]).is_err());
+        }
+
+        #[test]
+        fn test_interned_limited() {
+            let batch = synthetic_batch();
+            let error = CardSet::from_reader_interned_limited(batch.as_bytes(), 1024).unwrap_err();
+            assert!(matches!(error, ReadError::TooLarge { limit: 1024 }));
+            let cards =
+                CardSet::from_reader_interned_limited(batch.as_bytes(), batch.len()).unwrap();
+            assert_eq!(cards.0.len(), CARDS);
+        }
+    }
+}
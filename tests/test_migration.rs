@@ -0,0 +1,226 @@
+mod test {
+    use jscontact::migration::PROPERTY_ALIASES;
+    use jscontact::{
+        parse, Card, CardKind, CardVersion, MigrationRegistry, ParseOptions, PropertyAlias,
+        UpgradeError, VersionMigration,
+    };
+    use serde_json::{json, Value};
+
+    /// A dummy 1.1 version whose `nickname` property becomes the `example.com:nickname` extension in 1.0
+    struct Downgrade11;
+
+    impl VersionMigration for Downgrade11 {
+        fn source_version(&self) -> CardVersion {
+            CardVersion::Other("1.1".to_string())
+        }
+
+        fn target_version(&self) -> CardVersion {
+            CardVersion::OneDotZero
+        }
+
+        fn migrate(&self, card: &mut Value) -> Result<(), UpgradeError> {
+            let object = card
+                .as_object_mut()
+                .ok_or_else(|| UpgradeError::Migration("not an object".to_string()))?;
+            if let Some(nickname) = object.remove("nickname") {
+                object.insert("example.com:nickname".to_string(), nickname);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_future_version_is_lenient() -> Result<(), Box<dyn std::error::Error>> {
+        let json = json!({
+            "@type": "Card",
+            "version": "1.1",
+            "uid": "1234",
+            "nickname": "Johnny",
+            "example.com:foo": { "bar": 1 },
+            "name": { "full": "John Smith" }
+        });
+        let card = Card::try_from(json)?;
+        assert_eq!(card.version, CardVersion::Other("1.1".to_string()));
+        assert_eq!(card.extensions["nickname"], json!("Johnny"));
+        assert_eq!(card.extensions["example.com:foo"], json!({ "bar": 1 }));
+        assert_eq!(
+            card.name.as_ref().unwrap().full.as_deref(),
+            Some("John Smith")
+        );
+
+        // the unknown properties are serialized back
+        let value = serde_json::to_value(&card)?;
+        assert_eq!(value["version"], json!("1.1"));
+        assert_eq!(value["nickname"], json!("Johnny"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_upgrade_to_same_version() -> Result<(), Box<dyn std::error::Error>> {
+        let json = json!({ "@type": "Card", "version": "1.0", "uid": "1234" });
+        let card = Card::try_from(json)?;
+        assert_eq!(card.upgrade_to(CardVersion::OneDotZero)?, card);
+        Ok(())
+    }
+
+    #[test]
+    fn test_upgrade_to_registered_downgrade() -> Result<(), Box<dyn std::error::Error>> {
+        let mut registry = MigrationRegistry::new();
+        registry.register(Downgrade11);
+        registry.register(Downgrade11);
+        assert_eq!(registry.len(), 1);
+        assert_eq!(format!("{:?}", registry), r#"["1.1 -> 1.0"]"#);
+        let json = json!({
+            "@type": "Card",
+            "version": "1.1",
+            "uid": "1234",
+            "nickname": "Johnny",
+            "example.com:foo": { "bar": 1 },
+            "name": { "full": "John Smith" }
+        });
+        let card = Card::try_from(json)?;
+        let downgraded = card.upgrade_to_with(&registry, CardVersion::OneDotZero)?;
+        assert_eq!(downgraded.version, CardVersion::OneDotZero);
+        assert!(!downgraded.extensions.contains_key("nickname"));
+        assert_eq!(
+            downgraded.extensions["example.com:nickname"],
+            json!("Johnny")
+        );
+        assert_eq!(
+            downgraded.extensions["example.com:foo"],
+            json!({ "bar": 1 })
+        );
+        assert_eq!(downgraded.name, card.name);
+        Ok(())
+    }
+
+    #[test]
+    fn test_upgrade_to_without_migration() -> Result<(), Box<dyn std::error::Error>> {
+        // the registry is not global, the migration of 1.1 is only used with its registry
+        let json =
+            json!({ "@type": "Card", "version": "1.1", "uid": "1234", "nickname": "Johnny" });
+        let error = Card::try_from(json)?
+            .upgrade_to(CardVersion::OneDotZero)
+            .unwrap_err();
+        assert!(matches!(error, UpgradeError::NoMigration { .. }));
+
+        // no migration is registered for 1.2
+        let json = json!({
+            "@type": "Card",
+            "version": "1.2",
+            "uid": "1234",
+            "nickname": "Johnny",
+            "example.com:foo": { "bar": 1 },
+            "name": { "full": "John Smith" }
+        });
+        let mut card = Card::try_from(json)?;
+        let error = card.upgrade_to(CardVersion::OneDotZero).unwrap_err();
+        assert_eq!(
+            error,
+            UpgradeError::NoMigration {
+                from: CardVersion::Other("1.2".to_string()),
+                to: CardVersion::OneDotZero,
+                incompatible_properties: vec!["nickname".to_string()],
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "No migration from 1.2 to 1.0, incompatible properties: nickname"
+        );
+
+        // vendor-specific properties are compatible with 1.0
+        card.extensions.remove("nickname");
+        let downgraded = card.upgrade_to(CardVersion::OneDotZero)?;
+        assert_eq!(downgraded.version, CardVersion::OneDotZero);
+
+        let error = downgraded
+            .upgrade_to(CardVersion::Other("2.0".to_string()))
+            .unwrap_err();
+        assert!(matches!(error, UpgradeError::NoMigration { .. }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_aliases_lenient() {
+        let (card, errors) = Card::from_value_collecting(json!({
+            "type": "Card",
+            "@version": "1.0",
+            "@uid": "1234",
+            "@kind": "individual",
+            "name": { "full": "John Smith" }
+        }));
+        let card = card.unwrap();
+        assert_eq!(card.version, CardVersion::OneDotZero);
+        assert_eq!(card.uid, "1234");
+        assert_eq!(card.kind, Some(CardKind::Individual));
+        assert!(card.extensions.is_empty());
+        let errors: Vec<(&str, &str)> = errors
+            .iter()
+            .map(|error| (error.path.as_str(), error.message.as_str()))
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                ("@kind", "alias of `kind`, read as `kind`"),
+                ("@uid", "alias of `uid`, read as `uid`"),
+                ("@version", "alias of `version`, read as `version`"),
+                ("type", "alias of `@type`, read as `@type`"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_aliases_with_property() {
+        // the alias of a property that is set is an unknown property
+        let value = json!({ "@type": "Card", "version": "1.0", "@version": "1.1", "uid": "1234" });
+        let (card, errors) = Card::from_value_collecting(value.clone());
+        assert_eq!(card.unwrap().version, CardVersion::OneDotZero);
+        assert_eq!(errors[0].path, "@version");
+        assert_eq!(errors[0].message, "unknown property, kept as is");
+        let card = Card::try_from(value).unwrap();
+        assert_eq!(card.unknown_properties(), ["@version"]);
+    }
+
+    #[test]
+    fn test_aliases_strict() {
+        for (alias, property) in [
+            ("@version", "version"),
+            ("@uid", "uid"),
+            ("@kind", "kind"),
+            ("type", "@type"),
+        ] {
+            let mut value = json!({ "@type": "Card", "version": "1.0", "uid": "1234" });
+            let object = value.as_object_mut().unwrap();
+            let json = object.remove(property).unwrap_or(json!("individual"));
+            object.insert(alias.to_string(), json);
+            let expected = format!(
+                "`{}` is not a JSContact property, rename it to `{}`",
+                alias, property
+            );
+            assert_eq!(Card::try_from(value.clone()).unwrap_err(), expected);
+            let json = value.to_string();
+            let error = Card::from_str_with(&json, &ParseOptions::default()).unwrap_err();
+            assert_eq!(error.to_string(), expected);
+            if property == "@type" {
+                // parse adds the @type of an object with a version and a uid
+                assert!(parse(&json).is_ok());
+                continue;
+            }
+            assert_eq!(
+                parse(&json).unwrap_err(),
+                format!("Invalid Card: {}", expected)
+            );
+        }
+    }
+
+    #[test]
+    fn test_property_aliases() {
+        assert_eq!(
+            PROPERTY_ALIASES[0],
+            PropertyAlias {
+                alias: "@version",
+                property: "version"
+            }
+        );
+    }
+}
@@ -0,0 +1,97 @@
+mod test {
+    use jscontact::{Card, CardSet};
+    use serde_json::json;
+
+    fn figure_01() -> &'static str {
+        std::str::from_utf8(include_bytes!("./rfc9553/figure_01.json")).unwrap()
+    }
+
+    #[test]
+    fn test_missing_version() {
+        let error = serde_json::from_value::<Card>(json!({ "@type": "Card", "uid": "1234" }))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "missing required property `version`: version must be the JSContact version of the Card, e.g. \"1.0\""
+        );
+    }
+
+    #[test]
+    fn test_missing_uid() {
+        let error =
+            r#"{ "@type": "Card", "version": "1.0" }"#.parse::<Card>().unwrap_err().to_string();
+        assert!(
+            error.starts_with(
+                "missing required property `uid`: uid must be a URI identifying the contact"
+            ),
+            "{}",
+            error
+        );
+        let error = serde_json::from_value::<Card>(json!({ "version": "1.0", "uid": "1234" }))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "missing required property `@type`: @type must be \"Card\""
+        );
+        // the other errors are the errors of serde
+        let error =
+            serde_json::from_value::<Card>(json!({ "@type": "Card", "version": "1.0", "uid": 1 }))
+                .unwrap_err()
+                .to_string();
+        assert!(error.starts_with("invalid type: integer `1`"), "{}", error);
+        // an error before the end of the Card is reported even if a required property is missing
+        let error =
+            serde_json::from_value::<Card>(json!({ "@type": "Card", "emails": { "e1": {} } }))
+                .unwrap_err()
+                .to_string();
+        assert!(error.starts_with("missing field `address`"), "{}", error);
+    }
+
+    #[test]
+    fn test_escaped_required() {
+        // the escaped names are the names of the required properties
+        let card = r#"{ "@type": "Card", "versio\u006e": "1.0", "\u0075id": "1234" }"#
+            .parse::<Card>()
+            .unwrap();
+        assert_eq!(card.uid, "1234");
+        let error = r#"{ "@type": "Card", "versio\u006e": "1.0", "UID": "1234" }"#
+            .parse::<Card>()
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.starts_with("missing required property `uid`: "),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_card_set() {
+        let card: Card = figure_01().parse().unwrap();
+        let json = format!("[{}, {}]", figure_01(), figure_01());
+        let cards: CardSet = serde_json::from_str(&json).unwrap();
+        assert_eq!(cards, CardSet(vec![card.clone(), card]));
+        // the round trip is unchanged
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_value(&cards).unwrap(), value);
+
+        let json = format!(
+            r#"[{}, {{ "@type": "Card", "version": "1.0" }}]"#,
+            figure_01()
+        );
+        let error = serde_json::from_str::<CardSet>(&json)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.starts_with("Card at index 1: missing required property `uid`: "),
+            "{}",
+            error
+        );
+        let error = serde_json::from_str::<CardSet>("{}")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("expected an array of Cards"), "{}", error);
+    }
+}
@@ -0,0 +1,191 @@
+mod test {
+    use jscontact::{Card, CardVersion, Name, NameComponent, NameComponentKind};
+
+    /// components, isOrdered, defaultSeparator and the expected result
+    type Case<'a, T> = (
+        &'a [(NameComponentKind, &'a str)],
+        Option<bool>,
+        Option<&'a str>,
+        T,
+    );
+
+    fn build_name(
+        components: &[(NameComponentKind, &str)],
+        is_ordered: Option<bool>,
+        default_separator: Option<&str>,
+    ) -> Name {
+        let mut name = Name::default();
+        name.components = Some(
+            components
+                .iter()
+                .map(|(kind, value)| NameComponent::new(kind.clone(), value))
+                .collect(),
+        );
+        name.is_ordered = is_ordered;
+        name.default_separator = default_separator.map(String::from);
+        name
+    }
+
+    #[test]
+    fn test_name_to_full_string() {
+        use NameComponentKind::*;
+        let cases: Vec<Case<&str>> = vec![
+            (
+                &[(Given, "John"), (Surname, "Smith")],
+                None,
+                None,
+                "John Smith",
+            ),
+            (
+                &[(Given, "John"), (Surname, "Smith")],
+                Some(true),
+                Some(", "),
+                "John, Smith",
+            ),
+            (
+                &[(Surname, "Smith"), (Separator, ", "), (Given, "John")],
+                Some(true),
+                None,
+                "Smith, John",
+            ),
+            (
+                &[
+                    (Surname, "Smith"),
+                    (Separator, ", "),
+                    (Given, "John"),
+                    (Given2, "Quincy"),
+                ],
+                Some(true),
+                Some("_"),
+                "Smith, John_Quincy",
+            ),
+            // Japanese names use an empty separator
+            (
+                &[(Surname, "大久保"), (Given, "正仁")],
+                Some(true),
+                Some(""),
+                "大久保正仁",
+            ),
+            // separators and default separator are ignored if the name is not ordered
+            (
+                &[(Given, "John"), (Separator, "-"), (Surname, "Smith")],
+                Some(false),
+                Some("_"),
+                "John Smith",
+            ),
+            // leading and trailing separators are not between two components
+            (
+                &[(Separator, "-"), (Given, "John"), (Separator, "-")],
+                Some(true),
+                None,
+                "John",
+            ),
+            (&[], Some(true), None, ""),
+        ];
+        for (components, is_ordered, default_separator, expected) in cases {
+            let name = build_name(components, is_ordered, default_separator);
+            assert_eq!(name.to_full_string(), expected, "{:?}", components);
+        }
+    }
+
+    #[test]
+    fn test_name_default_display_order() {
+        use NameComponentKind::*;
+        assert_eq!(
+            NameComponentKind::default_display_order(),
+            [Title, Given, Given2, Surname, Surname2, Generation, Credential]
+        );
+        let components = [
+            (Credential, "PhD"),
+            (Surname, "Smith"),
+            (Generation, "Jr."),
+            (Given2, "Quincy"),
+            (Title, "Dr."),
+            (Surname2, "Adams"),
+            (Given, "John"),
+        ];
+        let name = build_name(&components, None, None);
+        assert_eq!(name.to_full_string(), "Dr. John Quincy Smith Adams Jr. PhD");
+
+        // the order can be overridden, the missing kinds coming last
+        assert_eq!(
+            name.to_full_string_with_order(&[Surname, Surname2, Given]),
+            "Smith Adams John PhD Jr. Quincy Dr."
+        );
+        // an ordered name keeps its order
+        let name = build_name(&components, Some(true), None);
+        assert_eq!(name.to_full_string(), "PhD Smith Jr. Quincy Dr. Adams John");
+    }
+
+    #[test]
+    fn test_name_to_full_string_without_components() {
+        let mut name = Name::default();
+        assert_eq!(name.to_full_string(), "");
+        name.full = Some("John Smith".to_string());
+        assert_eq!(name.to_full_string(), "John Smith");
+    }
+
+    #[test]
+    fn test_name_validate() {
+        use NameComponentKind::*;
+        let cases: Vec<Case<Vec<&str>>> = vec![
+            (&[(Given, "John"), (Surname, "Smith")], None, None, vec![]),
+            (
+                &[(Given, "John"), (Surname, "Smith")],
+                Some(true),
+                Some(""),
+                vec![],
+            ),
+            (
+                &[(Given, "John"), (Surname, "Smith")],
+                None,
+                Some(" "),
+                vec!["defaultSeparator"],
+            ),
+            (
+                &[(Given, "John"), (Surname, "Smith")],
+                Some(false),
+                Some(""),
+                vec!["defaultSeparator"],
+            ),
+            (
+                &[
+                    (Given, "John"),
+                    (Separator, "-"),
+                    (Separator, "-"),
+                    (Surname, "Smith"),
+                ],
+                Some(true),
+                None,
+                vec!["components/2"],
+            ),
+            (
+                &[(Given, "John"), (Separator, "-"), (Surname, "Smith")],
+                None,
+                None,
+                vec!["components/1"],
+            ),
+        ];
+        for (components, is_ordered, default_separator, expected) in cases {
+            let name = build_name(components, is_ordered, default_separator);
+            let report = name.validate();
+            let paths: Vec<&str> = report.errors.iter().map(|e| e.path.as_str()).collect();
+            assert_eq!(paths, expected, "{:?}", components);
+        }
+    }
+
+    #[test]
+    fn test_card_validate_name() {
+        let mut card = Card::new(CardVersion::OneDotZero, "my:uri");
+        assert!(card.validate().is_valid());
+        card.name = Some(build_name(
+            &[(NameComponentKind::Given, "John")],
+            None,
+            Some(" "),
+        ));
+        let report = card.validate();
+        assert!(!report.is_valid());
+        assert_eq!(report.errors[0].path, "name/defaultSeparator");
+        assert!(report.to_string().starts_with("name/defaultSeparator: "));
+    }
+}
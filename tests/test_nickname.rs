@@ -0,0 +1,242 @@
+mod test {
+    use jscontact::{Card, Context, Nickname, ReindexPolicy, SmallString};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_nickname_builder() -> Result<(), Box<dyn std::error::Error>> {
+        let nickname = Nickname::new("Jo")
+            .with_context(Context::Private)
+            .with_context(Context::Work)
+            .with_pref(1);
+        assert_eq!(nickname.name, "Jo");
+        assert_eq!(nickname.contexts.unwrap().len(), 2);
+        assert_eq!(nickname.pref, Some(1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_preferred_nickname() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "nicknames": {
+                "k1": { "@type": "Nickname", "name": "Johnny", "pref": 2 },
+                "k2": {
+                    "@type": "Nickname",
+                    "name": "Jo",
+                    "pref": 1,
+                    "contexts": { "private": true }
+                },
+                "k3": { "@type": "Nickname", "name": "JS", "contexts": { "work": true } }
+            }
+        });
+        let card: Card = serde_json::from_value(json)?;
+        // the pref decides without context
+        assert_eq!(card.preferred_nickname(None).unwrap().name, "Jo");
+        assert_eq!(
+            card.preferred_nickname(Some(Context::Private))
+                .unwrap()
+                .name,
+            "Jo"
+        );
+        // a nickname in the context is preferred over a lower pref
+        assert_eq!(
+            card.preferred_nickname(Some(Context::Work)).unwrap().name,
+            "JS"
+        );
+        assert!(Card::new_with_latest_version("1")
+            .preferred_nickname(None)
+            .is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_localized_nickname() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "nicknames": {
+                "k1": { "@type": "Nickname", "name": "Johnny", "pref": 2 },
+                "k2": {
+                    "@type": "Nickname",
+                    "name": "Jo",
+                    "pref": 1,
+                    "contexts": { "private": true }
+                },
+                "k3": { "@type": "Nickname", "name": "JS", "contexts": { "work": true } }
+            }
+        });
+        let mut card: Card = serde_json::from_value(json)?;
+        card.add_localization(
+            "fr",
+            HashMap::from([("nicknames/k3/name".to_string(), serde_json::json!("Jean-S"))]),
+        );
+        let localized = card.get_localized("fr")?;
+        assert_eq!(
+            localized
+                .preferred_nickname(Some(Context::Work))
+                .unwrap()
+                .name,
+            "Jean-S"
+        );
+
+        // the contexts and the pref are not localizable
+        card.add_localization(
+            "de",
+            HashMap::from([
+                (
+                    "nicknames/k1/contexts".to_string(),
+                    serde_json::json!({ "work": true }),
+                ),
+                ("nicknames/k1/pref".to_string(), serde_json::json!(null)),
+            ]),
+        );
+        assert!(card.get_localized("de").is_err());
+        let (localized, issues) = card.get_localized_lenient("de")?;
+        assert_eq!(issues.len(), 2);
+        assert_eq!(localized.nicknames, card.nicknames);
+        Ok(())
+    }
+
+    #[test]
+    fn test_nickname_strings() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "nicknames": {
+                "k1": { "@type": "Nickname", "name": "Johnny", "pref": 2 },
+                "k2": {
+                    "@type": "Nickname",
+                    "name": "Jo",
+                    "pref": 1,
+                    "contexts": { "private": true }
+                },
+                "k3": { "@type": "Nickname", "name": "JS", "contexts": { "work": true } }
+            }
+        });
+        let card: Card = serde_json::from_value(json)?;
+        // sorted by pref, the nickname without pref last
+        assert_eq!(card.nickname_strings(), ["Jo", "Johnny", "JS"]);
+        assert!(Card::new_with_latest_version("1")
+            .nickname_strings()
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_nicknames_from_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card = Card::new_with_latest_version("1234");
+        let names = ["Jo", "Johnny", "JS"].map(String::from);
+        card.set_nicknames_from(names.clone(), ReindexPolicy::Reindex)?;
+        assert_eq!(card.nickname_strings(), names);
+        let nicknames = card.nicknames.as_ref().unwrap();
+        assert_eq!(nicknames[&SmallString::from("k1")].name, "Jo");
+        assert_eq!(nicknames[&SmallString::from("k3")].name, "JS");
+
+        card.set_nicknames_from(Vec::new(), ReindexPolicy::Reindex)?;
+        assert!(card.nicknames.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_nicknames_from_keeps_matching_entries() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "nicknames": {
+                "k1": { "@type": "Nickname", "name": "Johnny", "pref": 2 },
+                "k2": {
+                    "@type": "Nickname",
+                    "name": "Jo",
+                    "pref": 1,
+                    "contexts": { "private": true }
+                },
+                "k3": { "@type": "Nickname", "name": "JS", "contexts": { "work": true } }
+            }
+        });
+        let mut card: Card = serde_json::from_value(json)?;
+        card.set_nicknames_from(
+            ["JS", "Jojo", "Jo"].map(String::from),
+            ReindexPolicy::Reindex,
+        )?;
+        let nicknames = card.nicknames.as_ref().unwrap();
+        assert_eq!(nicknames.len(), 3);
+        // the matching nicknames keep their key, contexts and pref
+        assert_eq!(
+            nicknames[&SmallString::from("k2")],
+            Nickname::new("Jo")
+                .with_pref(1)
+                .with_context(Context::Private)
+        );
+        assert_eq!(
+            nicknames[&SmallString::from("k3")],
+            Nickname::new("JS").with_context(Context::Work)
+        );
+        // the new name continues the keys
+        assert_eq!(nicknames[&SmallString::from("k4")], Nickname::new("Jojo"));
+        assert_eq!(card.nickname_strings(), ["Jo", "JS", "Jojo"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_nicknames_from_localizations() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "nicknames": {
+                "k1": { "@type": "Nickname", "name": "Johnny", "pref": 2 },
+                "k2": {
+                    "@type": "Nickname",
+                    "name": "Jo",
+                    "pref": 1,
+                    "contexts": { "private": true }
+                },
+                "k3": { "@type": "Nickname", "name": "JS", "contexts": { "work": true } }
+            }
+        });
+        let mut card: Card = serde_json::from_value(json)?;
+        card.add_localization(
+            "fr",
+            HashMap::from([
+                (
+                    "nicknames/k1/name".to_string(),
+                    serde_json::json!("Jeannot"),
+                ),
+                ("nicknames/k3/name".to_string(), serde_json::json!("Jean-S")),
+            ]),
+        );
+        let names = ["Jo", "JS"].map(String::from);
+
+        // the patch of the removed nickname k1 is a conflict, nothing changes
+        let before = card.clone();
+        let conflict = card
+            .set_nicknames_from(names.clone(), ReindexPolicy::Reject)
+            .unwrap_err();
+        assert_eq!(conflict.languages, ["fr"]);
+        assert_eq!(conflict.paths, ["nicknames/k1/name"]);
+        assert_eq!(card, before);
+
+        // or is removed with it
+        card.set_nicknames_from(names.clone(), ReindexPolicy::Reindex)?;
+        let patches = &card.get_raw_localizations().unwrap()["fr"];
+        assert_eq!(patches.len(), 1);
+        assert_eq!(patches["nicknames/k3/name"], "Jean-S");
+
+        // a patch of all the nicknames is always a conflict
+        card.add_localization(
+            "de",
+            HashMap::from([("nicknames".to_string(), serde_json::json!({}))]),
+        );
+        let conflict = card
+            .set_nicknames_from(names, ReindexPolicy::Reindex)
+            .unwrap_err();
+        assert_eq!(conflict.languages, ["de"]);
+        assert_eq!(conflict.paths, ["nicknames"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+mod test {
+    use jscontact::{
+        Address, AddressChange, AddressChangeKind, AddressComponent, AddressComponentKind,
+        AddressNormalizeOptions, Card, ReindexPolicy, SmallString,
+    };
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    fn figure_32() -> Card {
+        let json = include_bytes!("./rfc9553/figure_32.json");
+        serde_json::from_slice(json).unwrap()
+    }
+
+    fn values(address: &Address) -> Vec<&str> {
+        address
+            .components
+            .iter()
+            .flatten()
+            .map(|component| component.value.as_str())
+            .collect()
+    }
+
+    /// An address with a duplicate postcode, an empty component and whitespace
+    fn imported() -> Address {
+        let mut address = Address::default();
+        address.components = Some(vec![
+            AddressComponent::number(" 54321 "),
+            AddressComponent::separator(" "),
+            AddressComponent::name("Oak St"),
+            AddressComponent::locality(" "),
+            AddressComponent::locality("Reston"),
+            AddressComponent::postcode("20190"),
+            AddressComponent::postcode("20191"),
+        ]);
+        address
+    }
+
+    #[test]
+    fn test_normalize_trim() {
+        let mut card = figure_32();
+        let address = card.addresses.as_mut().unwrap().get_mut("k25").unwrap();
+        let changes = address.normalize(&AddressNormalizeOptions::default());
+        assert_eq!(
+            changes,
+            vec![AddressChange {
+                path: "components/3".to_string(),
+                kind: AddressChangeKind::Trimmed,
+                before: " Watthana".to_string(),
+                after: Some("Watthana".to_string()),
+            }]
+        );
+        assert_eq!(
+            changes[0].to_string(),
+            r#"components/3: " Watthana" -> "Watthana""#
+        );
+        assert_eq!(values(address)[3], "Watthana");
+        // already normalized
+        assert!(address
+            .normalize(&AddressNormalizeOptions::default())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_normalize_rules() {
+        let mut address = imported();
+        let changes = address.normalize(&AddressNormalizeOptions::default());
+        let summary: Vec<(&str, AddressChangeKind)> = changes
+            .iter()
+            .map(|change| (change.path.as_str(), change.kind))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("components/0", AddressChangeKind::Trimmed),
+                ("components/3", AddressChangeKind::EmptyRemoved),
+                ("components/6", AddressChangeKind::DuplicateRemoved),
+            ]
+        );
+        // the separator is kept with its whitespace
+        assert_eq!(
+            values(&address),
+            ["54321", " ", "Oak St", "Reston", "20190"]
+        );
+        assert_eq!(changes[2].to_string(), r#"components/6: "20191" removed"#);
+
+        // each rule can be enabled alone
+        let mut address = imported();
+        let mut opts = AddressNormalizeOptions::none();
+        assert!(address.normalize(&opts).is_empty());
+        opts.remove_duplicates = true;
+        let changes = address.normalize(&opts);
+        // the first locality is kept, even if it is blank
+        assert_eq!(changes.len(), 2);
+        assert_eq!(values(&address), [" 54321 ", " ", "Oak St", " ", "20190"]);
+        let mut address = imported();
+        let mut opts = AddressNormalizeOptions::none();
+        opts.remove_empty = true;
+        // a blank value is not empty without trimming
+        assert!(address.normalize(&opts).is_empty());
+        opts.trim = true;
+        assert_eq!(address.normalize(&opts).len(), 2);
+        assert_eq!(address.components.as_ref().unwrap().len(), 6);
+        assert!(address
+            .components
+            .iter()
+            .flatten()
+            .all(|component| component.kind != AddressComponentKind::Locality
+                || component.value == "Reston"));
+    }
+
+    fn card_with_localization(patches: Value) -> Card {
+        let mut card = Card::new_with_latest_version("my:uri");
+        card.addresses = Some(HashMap::from([(SmallString::from("k1"), imported())]));
+        let patches: HashMap<String, Value> = serde_json::from_value(patches).unwrap();
+        card.add_localization("en", patches);
+        card
+    }
+
+    #[test]
+    fn test_normalize_addresses_reindex() {
+        let mut card = card_with_localization(json!({
+            "addresses/k1/components/2/value": "Oak Street",
+            "addresses/k1/components/3/value": "Downtown",
+            "addresses/k1/components/4/value": "Reston City"
+        }));
+        let changes = card
+            .normalize_addresses(&AddressNormalizeOptions::default(), ReindexPolicy::Reindex)
+            .unwrap();
+        let paths: Vec<&str> = changes.iter().map(|change| change.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "addresses/k1/components/0",
+                "addresses/k1/components/3",
+                "addresses/k1/components/6"
+            ]
+        );
+        assert_eq!(
+            card.get_raw_localizations().unwrap()["en"],
+            HashMap::from([
+                (
+                    "addresses/k1/components/2/value".to_string(),
+                    json!("Oak Street")
+                ),
+                (
+                    "addresses/k1/components/3/value".to_string(),
+                    json!("Reston City")
+                ),
+            ])
+        );
+        let localized = card.get_localized("en").unwrap();
+        let address = &localized.addresses.unwrap()["k1"];
+        assert_eq!(
+            values(address),
+            ["54321", " ", "Oak Street", "Reston City", "20190"]
+        );
+    }
+
+    #[test]
+    fn test_normalize_addresses_conflict() {
+        // the patch refers to a shifted component
+        let mut card = card_with_localization(json!({
+            "addresses/k1/components/4/value": "Reston City"
+        }));
+        let before = card.clone();
+        let conflict = card
+            .normalize_addresses(&AddressNormalizeOptions::default(), ReindexPolicy::Reject)
+            .unwrap_err();
+        assert_eq!(conflict.languages, ["en"]);
+        assert_eq!(conflict.paths, ["addresses/k1/components/4/value"]);
+        assert_eq!(card, before);
+
+        // the patch replaces the whole components, whatever the policy
+        let mut card = card_with_localization(json!({
+            "addresses/k1/components": [{ "kind": "locality", "value": "Reston" }]
+        }));
+        let before = card.clone();
+        assert!(card
+            .normalize_addresses(&AddressNormalizeOptions::default(), ReindexPolicy::Reindex)
+            .is_err());
+        assert_eq!(card, before);
+
+        // only trimmed, the indices do not change
+        let mut card = card_with_localization(json!({
+            "addresses/k1/components": [{ "kind": "locality", "value": "Reston" }]
+        }));
+        let mut opts = AddressNormalizeOptions::none();
+        opts.trim = true;
+        let changes = card
+            .normalize_addresses(&opts, ReindexPolicy::Reject)
+            .unwrap();
+        // the blank locality is trimmed, not removed
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].after.as_deref(), Some("54321"));
+        assert_eq!(changes[1].after.as_deref(), Some(""));
+    }
+}
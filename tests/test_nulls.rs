@@ -0,0 +1,138 @@
+mod test {
+    use jscontact::{parse_with, Card, FieldError, ParseOptions, Parsed};
+    use serde_json::{json, Value};
+
+    const PROPERTIES: &str = include_str!("./nulls/properties.json");
+    const NESTED: &str = include_str!("./nulls/nested.json");
+
+    fn reject_nulls() -> ParseOptions {
+        ParseOptions {
+            reject_nulls: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_null_properties() {
+        // read as absent
+        let card: Card = serde_json::from_str(PROPERTIES).unwrap();
+        assert!(card.name.is_none());
+        assert!(card.nicknames.is_none());
+        assert!(card.keywords.is_none());
+        assert!(card.get_raw_localizations().is_none());
+        // the unknown properties are kept as is
+        assert_eq!(card.extensions["example.com:custom"], Value::Null);
+        assert_eq!(
+            serde_json::to_value(&card).unwrap(),
+            json!({
+                "@type": "Card",
+                "version": "1.0",
+                "uid": "nulls",
+                "example.com:custom": null
+            })
+        );
+
+        let error = Card::from_str_with(PROPERTIES, &reject_nulls()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "null value at addresses, the property must be omitted instead"
+        );
+        assert_eq!(
+            parse_with(PROPERTIES, &reject_nulls()).unwrap_err(),
+            "Invalid Card: null value at addresses, the property must be omitted instead"
+        );
+        let array = format!("[{}]", PROPERTIES);
+        assert!(parse_with(&array, &reject_nulls())
+            .unwrap_err()
+            .starts_with("Invalid Card at index 0: null value at addresses"));
+
+        let (lenient, errors) =
+            Card::from_value_collecting(serde_json::from_str(PROPERTIES).unwrap());
+        assert_eq!(lenient, Some(card));
+        let paths: Vec<&str> = errors.iter().map(|error| error.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "addresses",
+                "anniversaries",
+                "calendars",
+                "created",
+                "cryptoKeys",
+                "directories",
+                "emails",
+                "keywords",
+                "kind",
+                "language",
+                "links",
+                "localizations",
+                "media",
+                "members",
+                "name",
+                "nicknames",
+                "notes",
+                "onlineServices",
+                "organizations",
+                "personalInfo",
+                "phones",
+                "preferredLanguages",
+                "prodId",
+                "relatedTo",
+                "schedulingAddresses",
+                "speakToAs",
+                "titles",
+                "updated",
+            ]
+        );
+        assert!(errors
+            .iter()
+            .all(|error| error.message == "null value, read as absent"));
+    }
+
+    #[test]
+    fn test_nested_nulls() {
+        assert!(serde_json::from_str::<Card>(NESTED).is_err());
+        let error = Card::from_str_with(NESTED, &reject_nulls()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "null value at emails/e1/label, the property must be omitted instead"
+        );
+
+        let (card, errors) = Card::from_value_collecting(serde_json::from_str(NESTED).unwrap());
+        let card = card.unwrap();
+        let null = |path: &str| FieldError::new(path, "null value, read as absent");
+        for path in [
+            "emails/e1/label",
+            "emails/e1/pref",
+            "keywords/family",
+            "name/components/1",
+            "name/full",
+            "nicknames/k1",
+            "nicknames/k2/contexts",
+            "phones/p1/features/text",
+            "relatedTo/urn:uuid:1/relation",
+            "speakToAs/grammaticalGender",
+        ] {
+            assert!(errors.contains(&null(path)), "{}", path);
+        }
+        let name = card.name.as_ref().unwrap();
+        assert_eq!(name.components.as_ref().unwrap().len(), 2);
+        assert_eq!(card.nicknames.as_ref().unwrap().len(), 1);
+        assert_eq!(card.keywords.as_ref().unwrap().len(), 1);
+        // the null patches of the localizations and the unknown properties are kept
+        assert_eq!(
+            card.get_raw_localizations().unwrap()["fr"]["name/full"],
+            Value::Null
+        );
+        assert_eq!(
+            card.extensions["example.com:custom"],
+            json!({ "value": null })
+        );
+
+        // the lenient Card is read again without null
+        let json = serde_json::to_string(&card).unwrap();
+        let Parsed::Card(reparsed) = parse_with(&json, &reject_nulls()).unwrap() else {
+            panic!("expected a Card");
+        };
+        assert_eq!(reparsed, card);
+    }
+}
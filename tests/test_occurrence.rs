@@ -0,0 +1,196 @@
+#![cfg(feature = "chrono-tz")]
+
+mod test {
+    use jscontact::{AnniversaryKind, Card, LeapDayPolicy, Timestamp};
+    use serde_json::json;
+
+    fn next(card: &Card, kind: AnniversaryKind, after: &str, tz: &str) -> Option<String> {
+        card.next_occurrence(kind, Timestamp::new(after), tz)
+            .map(|timestamp| timestamp.utc)
+    }
+
+    #[test]
+    fn test_next_birthday_without_year() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "anniversaries": {
+                "k1": { "kind": "birth", "date": { "month": 4, "day": 15 } }
+            }
+        }))?;
+        let birth = || AnniversaryKind::Birth;
+        assert_eq!(
+            next(&card, birth(), "2025-06-01T00:00:00Z", "Europe/Paris").as_deref(),
+            Some("2026-04-14T22:00:00Z")
+        );
+        assert_eq!(
+            next(&card, birth(), "2026-04-14T21:59:59Z", "Europe/Paris").as_deref(),
+            Some("2026-04-14T22:00:00Z")
+        );
+        // strictly after
+        assert_eq!(
+            next(&card, birth(), "2026-04-14T22:00:00Z", "Europe/Paris").as_deref(),
+            Some("2027-04-14T22:00:00Z")
+        );
+        // the day starts earlier in a time zone ahead of UTC
+        assert_eq!(
+            next(&card, birth(), "2025-04-14T11:00:00Z", "Pacific/Auckland").as_deref(),
+            Some("2025-04-14T12:00:00Z")
+        );
+        assert_eq!(
+            next(
+                &card,
+                birth(),
+                "2025-04-14T11:00:00Z",
+                "America/Los_Angeles"
+            )
+            .as_deref(),
+            Some("2025-04-15T07:00:00Z")
+        );
+        assert_eq!(
+            next(&card, birth(), "2025-04-14T11:00:00Z", "Mars/Olympus"),
+            None
+        );
+        assert_eq!(next(&card, birth(), "yesterday", "Europe/Paris"), None);
+        assert_eq!(
+            next(
+                &card,
+                AnniversaryKind::Wedding,
+                "2025-06-01T00:00:00Z",
+                "Europe/Paris"
+            ),
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_leap_day() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "anniversaries": {
+                "k1": { "kind": "birth", "date": { "year": 2000, "month": 2, "day": 29 } }
+            }
+        }))?;
+        let after = || Timestamp::new("2025-01-01T00:00:00Z");
+        let next_with = |after: Timestamp, policy| {
+            card.next_occurrence_with(AnniversaryKind::Birth, after, "America/New_York", policy)
+                .map(|timestamp| timestamp.utc)
+        };
+        assert_eq!(
+            next_with(after(), LeapDayPolicy::February28).as_deref(),
+            Some("2025-02-28T05:00:00Z")
+        );
+        assert_eq!(
+            next_with(after(), LeapDayPolicy::March1).as_deref(),
+            Some("2025-03-01T05:00:00Z")
+        );
+        assert_eq!(
+            card.next_occurrence(AnniversaryKind::Birth, after(), "America/New_York"),
+            card.next_occurrence_with(
+                AnniversaryKind::Birth,
+                after(),
+                "America/New_York",
+                LeapDayPolicy::February28
+            )
+        );
+        // on February 29 in the leap years, whatever the policy
+        for policy in [LeapDayPolicy::February28, LeapDayPolicy::March1] {
+            assert_eq!(
+                next_with(Timestamp::new("2027-06-01T00:00:00Z"), policy).as_deref(),
+                Some("2028-02-29T05:00:00Z")
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_death_anniversary_timestamp() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "anniversaries": {
+                "k1": {
+                    "kind": "death",
+                    "date": { "@type": "Timestamp", "utc": "2010-07-20T23:30:00Z" }
+                },
+                "k2": { "kind": "birth", "date": { "year": 1950 } }
+            }
+        }))?;
+        let death = || AnniversaryKind::Death;
+        // the day of the timestamp in the time zone
+        assert_eq!(
+            next(&card, death(), "2025-01-01T00:00:00Z", "Europe/Paris").as_deref(),
+            Some("2025-07-20T22:00:00Z")
+        );
+        assert_eq!(
+            next(&card, death(), "2025-01-01T00:00:00Z", "America/New_York").as_deref(),
+            Some("2025-07-20T04:00:00Z")
+        );
+        // the first occurrence is the day itself
+        assert_eq!(
+            next(&card, death(), "2005-01-01T00:00:00Z", "Europe/Paris").as_deref(),
+            Some("2010-07-20T22:00:00Z")
+        );
+        // a year alone does not recur
+        assert_eq!(
+            next(
+                &card,
+                AnniversaryKind::Birth,
+                "2025-01-01T00:00:00Z",
+                "Europe/Paris"
+            ),
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_occurrence_earliest() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "anniversaries": {
+                "k1": { "kind": "wedding", "date": { "month": 9, "day": 1 } },
+                "k2": { "kind": "wedding", "date": { "year": 2030, "month": 6, "day": 1 } },
+                "k3": { "kind": "wedding", "date": { "month": 3, "day": 1 } }
+            }
+        }))?;
+        assert_eq!(
+            next(
+                &card,
+                AnniversaryKind::Wedding,
+                "2025-06-01T00:00:00Z",
+                "UTC"
+            )
+            .as_deref(),
+            Some("2025-09-01T00:00:00Z")
+        );
+        assert_eq!(
+            next(
+                &card,
+                AnniversaryKind::Wedding,
+                "2029-12-01T00:00:00Z",
+                "UTC"
+            )
+            .as_deref(),
+            Some("2030-03-01T00:00:00Z")
+        );
+        assert_eq!(
+            next(
+                &card,
+                AnniversaryKind::Wedding,
+                "2030-04-01T00:00:00Z",
+                "UTC"
+            )
+            .as_deref(),
+            Some("2030-06-01T00:00:00Z")
+        );
+        Ok(())
+    }
+}
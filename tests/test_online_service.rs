@@ -0,0 +1,84 @@
+mod test {
+    use jscontact::{Card, Context};
+    use serde_json::json;
+
+    #[test]
+    fn test_preferred_online_service() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "onlineServices": {
+                "os1": {
+                    "service": "Mastodon",
+                    "user": "@jane@mastodon.social",
+                    "uri": "https://mastodon.social/@jane",
+                    "pref": 2
+                },
+                "os2": {
+                    "service": "mastodon",
+                    "user": "@jane@fosstodon.org",
+                    "uri": "https://fosstodon.org/@jane",
+                    "contexts": { "work": true },
+                    "pref": 1
+                },
+                "os3": { "uri": "xmpp:jane@example.com" }
+            }
+        }))?;
+        let preferred = card.preferred_online_service("MASTODON", None).unwrap();
+        assert_eq!(preferred.user.as_deref(), Some("@jane@fosstodon.org"));
+        let private = card
+            .preferred_online_service("mastodon", Some(Context::Private))
+            .unwrap();
+        assert_eq!(private.user.as_deref(), Some("@jane@fosstodon.org"));
+        let work = card
+            .preferred_online_service("mastodon", Some(Context::Work))
+            .unwrap();
+        assert_eq!(work.uri.as_deref(), Some("https://fosstodon.org/@jane"));
+
+        // the service of a bare URI is its scheme
+        let xmpp = card.preferred_online_service("xmpp", None).unwrap();
+        assert_eq!(xmpp.uri.as_deref(), Some("xmpp:jane@example.com"));
+        assert!(card.preferred_online_service("matrix", None).is_none());
+        assert!(Card::new_with_latest_version("1")
+            .preferred_online_service("mastodon", None)
+            .is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_online_services_grouped() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "onlineServices": {
+                "os1": { "service": "Mastodon", "user": "@jane@mastodon.social", "pref": 2 },
+                "os2": { "service": "mastodon", "user": "@jane@fosstodon.org", "pref": 1 },
+                "os3": { "uri": "xmpp:jane@example.com" },
+                "os4": { "service": " Mastodon ", "user": "@jdoe@example.social" },
+                "os5": { "user": "jane" }
+            }
+        }))?;
+        let groups = card.online_services_grouped();
+        assert_eq!(
+            groups.keys().map(String::as_str).collect::<Vec<_>>(),
+            ["mastodon", "xmpp"]
+        );
+        // sorted by pref, the service without pref last
+        let users: Vec<Option<&str>> = groups["mastodon"]
+            .iter()
+            .map(|service| service.user.as_deref())
+            .collect();
+        assert_eq!(
+            users,
+            [
+                Some("@jane@fosstodon.org"),
+                Some("@jane@mastodon.social"),
+                Some("@jdoe@example.social"),
+            ]
+        );
+        assert_eq!(groups["xmpp"].len(), 1);
+        Ok(())
+    }
+}
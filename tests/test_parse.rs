@@ -0,0 +1,120 @@
+mod test {
+    use jscontact::{parse, parse_with, Card, ParseOptions, Parsed};
+    use serde_json::json;
+
+    fn figure_01() -> &'static str {
+        std::str::from_utf8(include_bytes!("./rfc9553/figure_01.json")).unwrap()
+    }
+
+    #[test]
+    fn test_parse_card() {
+        let card: Card = figure_01().parse().unwrap();
+        assert_eq!(parse(figure_01()).unwrap(), Parsed::Card(card.clone()));
+
+        // without @type, a version and a uid make a Card
+        let json = json!({ "version": "1.0", "uid": "1234" }).to_string();
+        let Parsed::Card(card) = parse(&json).unwrap() else {
+            panic!("not a Card");
+        };
+        assert_eq!(card.uid, "1234");
+    }
+
+    #[test]
+    fn test_parse_cards() {
+        let card: Card = figure_01().parse().unwrap();
+        let json = format!("[{}, {}]", figure_01(), figure_01());
+        assert_eq!(
+            parse(&json).unwrap(),
+            Parsed::Cards(vec![card.clone(), card])
+        );
+        assert_eq!(parse("[]").unwrap(), Parsed::Cards(vec![]));
+    }
+
+    #[test]
+    fn test_parse_envelope() {
+        let card: Card = figure_01().parse().unwrap();
+        let json = format!(
+            r#"{{ "accountId": "a1", "state": "s1", "list": [{}], "notFound": [] }}"#,
+            figure_01()
+        );
+        assert_eq!(parse(&json).unwrap(), Parsed::Cards(vec![card]));
+    }
+
+    #[test]
+    fn test_parse_unknown() {
+        let json = json!({ "@type": "Group", "uid": "1234" });
+        assert_eq!(parse(&json.to_string()).unwrap(), Parsed::Unknown(json));
+        let json = json!({ "accountId": "a1" });
+        assert_eq!(parse(&json.to_string()).unwrap(), Parsed::Unknown(json));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("{ \"@type\": ")
+            .unwrap_err()
+            .starts_with("Invalid JSON: "));
+        assert_eq!(
+            parse("\"Card\"").unwrap_err(),
+            "Expected a Card, an array of Cards or an object with a list of Cards, found a string"
+        );
+        assert_eq!(
+            parse(r#"{ "list": {} }"#).unwrap_err(),
+            "Invalid envelope: list must be an array, found an object"
+        );
+        let error = parse(r#"{ "@type": "Card", "version": "1.0" }"#).unwrap_err();
+        assert!(
+            error.starts_with("Invalid Card: missing required property `uid`: "),
+            "{}",
+            error
+        );
+        let json = format!(r#"[{}, {{ "@type": "Card" }}]"#, figure_01());
+        let error = parse(&json).unwrap_err();
+        assert!(error.starts_with("Invalid Card at index 1: "), "{}", error);
+        let json = format!(r#"{{ "list": [{}, 1] }}"#, figure_01());
+        let error = parse(&json).unwrap_err();
+        assert!(error.starts_with("Invalid Card at list/1: "), "{}", error);
+    }
+
+    #[test]
+    fn test_parse_verify_localizations() {
+        let json = json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "titles": { "t1": { "name": "Boss" } },
+            "notes": { "n1": { "note": "A note" } },
+            "localizations": {
+                "fr": {
+                    "titles/t1/name": 42,
+                    "notes/n1/note": "Une note"
+                },
+                "de": {
+                    "titles/t1/name": "Chef"
+                }
+            }
+        })
+        .to_string();
+        let opts = ParseOptions {
+            verify_localizations: true,
+            ..Default::default()
+        };
+        let card = Card::from_str_with(&json, &opts).unwrap();
+        let issues = card.localization_parse_issues();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].language, "fr");
+        assert_eq!(issues[0].path, "titles/t1/name");
+        // the localization fails with the same error
+        assert_eq!(card.get_localized("fr").unwrap_err(), issues[0].message);
+        assert!(card.get_localized("de").is_ok());
+
+        // the issues are only recorded when asked, and are ignored by the comparisons
+        let unverified: Card = json.parse().unwrap();
+        assert!(unverified.localization_parse_issues().is_empty());
+        assert_eq!(unverified, card);
+
+        let Ok(Parsed::Cards(cards)) = parse_with(&format!("[{}]", json), &opts) else {
+            panic!("expected cards");
+        };
+        assert_eq!(cards[0].localization_parse_issues(), issues);
+    }
+}
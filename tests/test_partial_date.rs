@@ -0,0 +1,125 @@
+mod test {
+    use jscontact::{PartialDate, PartialDateError};
+
+    fn date(year: Option<u64>, month: Option<u32>, day: Option<u32>) -> PartialDate {
+        let mut date = PartialDate::default();
+        date.year = year;
+        date.month = month;
+        date.day = day;
+        date
+    }
+
+    #[test]
+    fn test_to_iso_string() {
+        let cases = [
+            (date(Some(1953), Some(4), Some(15)), Some("1953-04-15")),
+            (date(Some(1953), Some(4), None), Some("1953-04")),
+            (date(Some(1953), None, None), Some("1953")),
+            (date(None, Some(4), Some(15)), Some("--04-15")),
+            (date(Some(7), Some(1), Some(2)), Some("0007-01-02")),
+            (date(None, Some(2), Some(29)), Some("--02-29")),
+            (date(Some(2000), Some(2), Some(29)), Some("2000-02-29")),
+            // not representable
+            (date(None, None, None), None),
+            (date(None, None, Some(15)), None),
+            (date(None, Some(4), None), None),
+            (date(Some(1953), None, Some(15)), None),
+            (date(Some(10000), None, None), None),
+            (date(Some(1953), Some(13), None), None),
+            (date(Some(1953), Some(4), Some(31)), None),
+            (date(Some(1900), Some(2), Some(29)), None),
+        ];
+        for (date, expected) in cases {
+            assert_eq!(date.to_iso_string().as_deref(), expected, "{:?}", date);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let years = [None, Some(0), Some(1953), Some(2024), Some(9999)];
+        let months = [None, Some(1), Some(2), Some(4), Some(12)];
+        let days = [
+            None,
+            Some(1),
+            Some(15),
+            Some(28),
+            Some(29),
+            Some(30),
+            Some(31),
+        ];
+        let mut representable = 0;
+        for year in years {
+            for month in months {
+                for day in days {
+                    let date = date(year, month, day);
+                    let Some(string) = date.to_iso_string() else {
+                        continue;
+                    };
+                    representable += 1;
+                    assert_eq!(PartialDate::from_iso_str(&string), Ok(date), "{}", string);
+                }
+            }
+        }
+        assert!(representable > 50);
+    }
+
+    #[test]
+    fn test_from_iso_str_basic_format() {
+        assert_eq!(
+            PartialDate::from_iso_str("19530415"),
+            Ok(date(Some(1953), Some(4), Some(15)))
+        );
+        assert_eq!(
+            PartialDate::from_iso_str("--0415"),
+            Ok(date(None, Some(4), Some(15)))
+        );
+    }
+
+    #[test]
+    fn test_from_iso_str_errors() {
+        for invalid in [
+            "",
+            "--",
+            "---15",
+            "--04",
+            "--4-15",
+            "--04-15-01",
+            "195",
+            "195304",
+            "1953-4",
+            "1953-04-5",
+            "1953-04-15T00:00:00Z",
+            "1953/04/15",
+            "--é415",
+        ] {
+            assert_eq!(
+                PartialDate::from_iso_str(invalid),
+                Err(PartialDateError::Format(invalid.to_string())),
+                "{}",
+                invalid
+            );
+        }
+        assert_eq!(
+            PartialDate::from_iso_str("1953-13"),
+            Err(PartialDateError::Month("13".to_string()))
+        );
+        assert_eq!(
+            PartialDate::from_iso_str("--00-01"),
+            Err(PartialDateError::Month("00".to_string()))
+        );
+        assert_eq!(
+            PartialDate::from_iso_str("2023-02-29"),
+            Err(PartialDateError::Day("29".to_string()))
+        );
+        assert_eq!(
+            PartialDate::from_iso_str("--04-31"),
+            Err(PartialDateError::Day("31".to_string()))
+        );
+        assert_eq!(
+            PartialDate::from_iso_str("1953-13")
+                .unwrap_err()
+                .to_string(),
+            "invalid month '13'"
+        );
+    }
+}
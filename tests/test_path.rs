@@ -0,0 +1,329 @@
+mod test {
+    use jscontact::path::{get_value, set_value, set_values};
+    use jscontact::{
+        AddressComponent, AddressComponentKind, Card, CardPath, PathSegment, SmallString,
+    };
+    use serde_json::json;
+    use PathSegment::{Index, Key};
+
+    fn key(key: &str) -> PathSegment {
+        Key(key.to_string())
+    }
+
+    #[test]
+    fn test_path_parse() {
+        let cases: Vec<(&str, &str, Vec<PathSegment>)> = vec![
+            ("name", "name", vec![]),
+            ("name/full", "name", vec![key("full")]),
+            ("name/components", "name", vec![key("components")]),
+            (
+                "name/components/0/value",
+                "name",
+                vec![key("components"), Index(0), key("value")],
+            ),
+            ("titles/t1", "titles", vec![key("t1")]),
+            ("titles/t1/name", "titles", vec![key("t1"), key("name")]),
+            (
+                "addresses/k26/components/12/kind",
+                "addresses",
+                vec![key("k26"), key("components"), Index(12), key("kind")],
+            ),
+            (
+                "addresses/k26/isOrdered",
+                "addresses",
+                vec![key("k26"), key("isOrdered")],
+            ),
+            (
+                "calendars/project-a/uri",
+                "calendars",
+                vec![key("project-a"), key("uri")],
+            ),
+            ("keywords/IETF", "keywords", vec![key("IETF")]),
+            // map keys made of digits with a leading zero are not indexes
+            ("notes/007", "notes", vec![key("007")]),
+            ("notes/0", "notes", vec![Index(0)]),
+        ];
+        for (path, property, segments) in cases {
+            let parsed = CardPath::parse(path).unwrap();
+            assert_eq!(parsed.property, property, "{}", path);
+            assert_eq!(parsed.segments, segments, "{}", path);
+            assert_eq!(parsed.to_string(), path);
+        }
+    }
+
+    #[test]
+    fn test_path_parse_escaping() {
+        let path: CardPath = "keywords/a~1b~0c".parse().unwrap();
+        assert_eq!(path.segments, vec![key("a/b~c")]);
+        assert_eq!(path.to_string(), "keywords/a~1b~0c");
+        let path = CardPath {
+            property: "keywords".to_string(),
+            segments: vec![key("~/")],
+        };
+        assert_eq!(path.to_string(), "keywords/~0~1");
+    }
+
+    #[test]
+    fn test_path_parse_errors() {
+        for path in [
+            "",
+            "/name",
+            "name/",
+            "name//full",
+            "unknown/full",
+            "localizations/en",
+            "keywords/a~2",
+            "keywords/a~",
+        ] {
+            assert!(CardPath::parse(path).is_err(), "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_path_get_value() {
+        let card = Card::try_from(include_bytes!("./rfc9553/figure_33.json").as_slice()).unwrap();
+        let get = |path: &str| get_value(&card, &CardPath::parse(path).unwrap());
+        assert_eq!(
+            get("addresses/k26/components/6/kind"),
+            Some(json!("region"))
+        );
+        assert_eq!(get("addresses/k26/isOrdered"), Some(json!(true)));
+        assert_eq!(get("addresses/k26/components/100"), None);
+        assert_eq!(get("addresses/k27"), None);
+        assert_eq!(get("name"), None);
+        assert_eq!(get("version"), Some(json!("1.0")));
+    }
+
+    #[test]
+    fn test_path_set_value() {
+        let mut card =
+            Card::try_from(include_bytes!("./rfc9553/figure_33.json").as_slice()).unwrap();
+        let path = CardPath::parse("addresses/k26/components/6/value").unwrap();
+        set_value(&mut card, &path, json!("大阪府")).unwrap();
+        let addresses = card.addresses.as_ref().unwrap();
+        let address = &addresses[&SmallString::from("k26")];
+        assert_eq!(address.region(), Some("大阪府"));
+
+        // a null value removes the property
+        let path = CardPath::parse("addresses/k26/full").unwrap();
+        set_value(&mut card, &path, json!(null)).unwrap();
+        assert!(card.addresses.as_ref().unwrap()[&SmallString::from("k26")]
+            .full
+            .is_none());
+        let path = CardPath::parse("keywords/web").unwrap();
+        set_value(&mut card, &path, json!(null)).unwrap();
+        assert!(card.keywords.is_none());
+        set_value(&mut card, &path, json!(true)).unwrap();
+        assert_eq!(card.keywords.as_ref().unwrap().len(), 1);
+
+        // the Card is unchanged if the value is invalid
+        let before = card.addresses.clone();
+        let path = CardPath::parse("addresses/k26/components/0/kind").unwrap();
+        assert!(set_value(&mut card, &path, json!("invalid")).is_err());
+        let path = CardPath::parse("addresses/k26/isOrdered/0").unwrap();
+        assert!(set_value(&mut card, &path, json!(true)).is_err());
+        assert_eq!(card.addresses, before);
+        let path = CardPath::parse("uid").unwrap();
+        assert!(set_value(&mut card, &path, json!(null)).is_err());
+    }
+
+    #[test]
+    fn test_path_set_value_far_past_the_end() {
+        let mut card =
+            Card::try_from(include_bytes!("./rfc9553/figure_33.json").as_slice()).unwrap();
+        let before = card.addresses.clone();
+        let len = before.as_ref().unwrap()["k26"]
+            .components
+            .as_ref()
+            .unwrap()
+            .len();
+        let path =
+            CardPath::parse(&format!("addresses/k26/components/{}/value", usize::MAX)).unwrap();
+        assert_eq!(
+            set_value(&mut card, &path, json!("Japan")).unwrap_err(),
+            format!(
+                "{}: cannot resolve '{}': more than 64 elements past the end of the array",
+                path,
+                usize::MAX
+            )
+        );
+        let path =
+            CardPath::parse(&format!("addresses/k26/components/{}/value", len + 65)).unwrap();
+        assert!(set_value(&mut card, &path, json!("Japan")).is_err());
+        assert_eq!(card.addresses, before);
+
+        // the missing elements up to the limit are created
+        let patches = [
+            (
+                format!("addresses/k26/components/{}/kind", len + 64),
+                json!("country"),
+            ),
+            (
+                format!("addresses/k26/components/{}/value", len + 64),
+                json!("Japan"),
+            ),
+        ];
+        let paths: Vec<CardPath> = patches
+            .iter()
+            .map(|(path, _)| CardPath::parse(path).unwrap())
+            .collect();
+        let result = set_values(
+            &mut card,
+            paths
+                .iter()
+                .zip(patches.iter().map(|(_, value)| value.clone())),
+        );
+        // the created elements are empty, so the components are invalid
+        assert!(result
+            .unwrap_err()
+            .starts_with("Invalid value for addresses"));
+    }
+
+    #[test]
+    fn test_path_set_values_in_any_order() {
+        let mut card =
+            Card::try_from(include_bytes!("./rfc9553/figure_33.json").as_slice()).unwrap();
+        let patches = [
+            ("addresses/k26/components/10/value", json!("Japan")),
+            ("addresses/k26/components/9/kind", json!("separator")),
+            ("addresses/k26/components/10/kind", json!("country")),
+            ("addresses/k26/components/9/value", json!(" ")),
+        ];
+        let paths: Vec<CardPath> = patches
+            .iter()
+            .map(|(path, _)| CardPath::parse(path).unwrap())
+            .collect();
+        set_values(
+            &mut card,
+            paths
+                .iter()
+                .zip(patches.iter().map(|(_, value)| value.clone())),
+        )
+        .unwrap();
+        let addresses = card.addresses.unwrap();
+        let address = &addresses[&SmallString::from("k26")];
+        assert_eq!(address.components.as_ref().unwrap().len(), 11);
+        assert_eq!(
+            address.component(AddressComponentKind::Country),
+            Some("Japan")
+        );
+    }
+
+    #[test]
+    fn test_get_pointer() {
+        let card = Card::try_from(include_bytes!("./rfc9553/figure_33.json").as_slice()).unwrap();
+        // scalars, with and without the leading slash
+        assert_eq!(card.get_pointer("version"), Some(json!("1.0")));
+        assert_eq!(
+            card.get_pointer("/addresses/k26/isOrdered"),
+            Some(json!(true))
+        );
+        assert_eq!(
+            card.get_pointer_as::<String>("addresses/k26/components/6/kind"),
+            Some("region".to_string())
+        );
+        // objects and array elements
+        assert_eq!(
+            card.get_pointer("/addresses/k26/components/0"),
+            Some(json!({ "kind": "block", "value": "2-7" }))
+        );
+        let component: AddressComponent =
+            card.get_pointer_as("addresses/k26/components/5").unwrap();
+        assert_eq!(component.kind, AddressComponentKind::Locality);
+        assert_eq!(
+            card.get_pointer("/localizations/jp/addresses~1k26/components/0/value"),
+            Some(json!("東京都"))
+        );
+
+        // missing paths
+        assert_eq!(card.get_pointer("name/full"), None);
+        assert_eq!(card.get_pointer("/addresses/k26/components/100"), None);
+        assert_eq!(card.get_pointer("localizations/jp"), None);
+        assert_eq!(card.get_pointer("/unknown"), None);
+        assert_eq!(card.get_pointer_as::<u64>("version"), None);
+    }
+
+    /// Render a tiny template: `{{property}}` is replaced by the property, `{{property/key}}` by a value of it,
+    /// and `{{?property}}...{{/}}` is kept only if the property is set
+    fn render(card: &Card, template: &str) -> String {
+        let mut output = String::new();
+        let mut rest = template;
+        let mut skipping = false;
+        while let Some(start) = rest.find("{{") {
+            let end = start + rest[start..].find("}}").unwrap();
+            if !skipping {
+                output.push_str(&rest[..start]);
+            }
+            let tag = &rest[start + 2..end];
+            rest = &rest[end + 2..];
+            if let Some(property) = tag.strip_prefix('?') {
+                skipping = !card.property_is_set(property);
+            } else if tag == "/" {
+                skipping = false;
+            } else if !skipping {
+                let (property, key) = tag.split_once('/').unwrap_or((tag, ""));
+                let value = card.property_as_value(property).unwrap();
+                let value = if key.is_empty() { &value } else { &value[key] };
+                match value {
+                    serde_json::Value::String(text) => output.push_str(text),
+                    value => output.push_str(&value.to_string()),
+                }
+            }
+        }
+        output.push_str(rest);
+        output
+    }
+
+    #[test]
+    fn test_property_template() {
+        let mut card = Card::new_with_latest_version("1234");
+        card.set_property_from_value("name", json!({ "full": "Jane Doe" }))
+            .unwrap();
+        card.set_property_from_value("emails", json!({ "e1": { "address": "jane@example.com" } }))
+            .unwrap();
+        let template = "{{name/full}} <{{emails/e1}}>{{?titles}}, {{titles}}{{/}}";
+        assert!(card.property_is_set("name"));
+        assert!(card.property_is_set("emails"));
+        assert!(!card.property_is_set("titles"));
+        assert_eq!(
+            render(&card, template),
+            r#"Jane Doe <{"address":"jane@example.com"}>"#
+        );
+        assert_eq!(card.property_as_value("titles"), Some(json!(null)));
+    }
+
+    #[test]
+    fn test_property_access() {
+        let mut card = Card::new_with_latest_version("1234");
+        assert!(card.property_is_set("uid"));
+        assert!(!card.property_is_set("unknown"));
+        assert_eq!(card.property_as_value("unknown"), None);
+        assert_eq!(card.property_as_value("prodId"), Some(json!(null)));
+
+        let error = card
+            .set_property_from_value("nmae", json!({ "full": "Jane" }))
+            .unwrap_err();
+        assert!(
+            error.starts_with("Unknown property 'nmae', expected one of: version, created, uid,"),
+            "{}",
+            error
+        );
+        assert!(error.ends_with("personalInfo, localizations"), "{}", error);
+        let error = card
+            .set_property_from_value("name", json!("Jane"))
+            .unwrap_err();
+        assert!(error.starts_with("Invalid value for name: "), "{}", error);
+
+        card.set_property_from_value("localizations", json!({ "fr": { "name/full": "Jeanne" } }))
+            .unwrap();
+        assert!(card.property_is_set("localizations"));
+        card.set_property_from_value("localizations", json!(null))
+            .unwrap();
+        assert!(!card.property_is_set("localizations"));
+
+        // every property of the list can be read
+        for name in Card::property_names() {
+            assert!(card.property_as_value(name).is_some(), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "phonenumber")]
+
+mod test {
+    use jscontact::{FormatContext, Phone, PhoneNumberFormatter};
+
+    #[test]
+    fn test_national_and_international() {
+        let phone = Phone::new("tel:+1-202-555-0123");
+        let us = FormatContext::with_country_code("US");
+        let de = FormatContext::with_country_code("DE");
+        assert_eq!(
+            phone.to_display_with(&PhoneNumberFormatter, &us),
+            "(202) 555-0123"
+        );
+        assert_eq!(
+            phone.to_display_with(&PhoneNumberFormatter, &de),
+            "+1 202-555-0123"
+        );
+        assert_eq!(
+            phone.to_display_with(&PhoneNumberFormatter, &FormatContext::default()),
+            "+1 202-555-0123"
+        );
+    }
+
+    #[test]
+    fn test_local_number() {
+        let phone = Phone::new("030 123456");
+        let de = FormatContext::with_country_code("de");
+        assert_eq!(
+            phone.to_display_with(&PhoneNumberFormatter, &de),
+            "030 123456"
+        );
+        // without the country of the number, it cannot be parsed
+        assert_eq!(
+            phone.to_display_with(&PhoneNumberFormatter, &FormatContext::default()),
+            "030 123456"
+        );
+        let uri = Phone::new("tel:+49-30-123456;ext=12");
+        assert_eq!(
+            uri.to_display_with(&PhoneNumberFormatter, &FormatContext::default()),
+            "+49 30 123456"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+mod test {
+    use jscontact::{Card, PlaceRef, ValidationCode, PLACE_KEY_PROPERTY};
+    use serde_json::json;
+
+    fn place(card: &Card) -> &PlaceRef {
+        card.anniversaries.as_ref().unwrap()["k9"]
+            .place
+            .as_ref()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_place_inline() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "addresses": {
+                "k23": { "full": "4445 Tree Street\nNew England, ND 58647\nUSA" }
+            },
+            "anniversaries": {
+                "k9": {
+                    "kind": "birth",
+                    "date": { "year": 1953 },
+                    "place": { "full": "Paris, France" }
+                }
+            }
+        }))?;
+        let address = place(&card).as_address().unwrap();
+        assert_eq!(address.full.as_deref(), Some("Paris, France"));
+        let anniversary = &card.anniversaries.as_ref().unwrap()["k9"];
+        assert_eq!(anniversary.place_resolved(&card), Some(address));
+
+        // the round trip keeps the inline form
+        let value = serde_json::to_value(&card)?;
+        assert_eq!(
+            value["anniversaries"]["k9"]["place"]["full"],
+            "Paris, France"
+        );
+        assert_eq!(serde_json::from_value::<Card>(value)?, card);
+        assert!(card.validate().is_valid());
+        Ok(())
+    }
+
+    #[test]
+    fn test_place_key() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "addresses": {
+                "k23": { "full": "4445 Tree Street\nNew England, ND 58647\nUSA" }
+            },
+            "anniversaries": {
+                "k9": {
+                    "kind": "birth",
+                    "date": { "year": 1953 },
+                    "place": { PLACE_KEY_PROPERTY: "k23" }
+                }
+            }
+        }))?;
+        assert_eq!(place(&card), &PlaceRef::Key("k23".to_string()));
+        let anniversary = &card.anniversaries.as_ref().unwrap()["k9"];
+        let resolved = anniversary.place_resolved(&card).unwrap();
+        assert_eq!(resolved, &card.addresses.as_ref().unwrap()["k23"]);
+        assert!(card.validate().is_valid());
+
+        // the reference is an Address object on the wire
+        let value = serde_json::to_value(&card)?;
+        let place = &value["anniversaries"]["k9"]["place"];
+        assert_eq!(
+            place,
+            &json!({ "@type": "Address", "jscontact.rs:addressKey": "k23" })
+        );
+        assert!(serde_json::from_value::<jscontact::Address>(place.clone()).is_ok());
+
+        // the round trip is stable
+        let json = serde_json::to_string(&card)?;
+        let parsed: Card = json.parse()?;
+        assert_eq!(parsed, card);
+        assert_eq!(serde_json::to_string(&parsed)?, json);
+        Ok(())
+    }
+
+    #[test]
+    fn test_place_key_unknown() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "addresses": {
+                "k23": { "full": "4445 Tree Street\nNew England, ND 58647\nUSA" }
+            },
+            "anniversaries": {
+                "k9": {
+                    "kind": "birth",
+                    "date": { "year": 1953 },
+                    "place": { "@type": "Address", PLACE_KEY_PROPERTY: "k99" }
+                }
+            }
+        }))?;
+        let anniversary = &card.anniversaries.as_ref().unwrap()["k9"];
+        assert_eq!(anniversary.place_resolved(&card), None);
+        let report = card.validate();
+        assert!(!report.is_valid());
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].code, ValidationCode::UnknownPlaceRef);
+        assert_eq!(report.errors[0].path, "anniversaries/k9/place");
+        assert_eq!(
+            report.errors[0].to_string(),
+            "anniversaries/k9/place: the address 'k99' does not exist"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_expand_place_refs() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "addresses": {
+                "k23": { "full": "4445 Tree Street\nNew England, ND 58647\nUSA" }
+            },
+            "anniversaries": {
+                "k9": {
+                    "kind": "birth",
+                    "date": { "year": 1953 },
+                    "place": { PLACE_KEY_PROPERTY: "k23" }
+                }
+            }
+        }))?;
+        assert_eq!(card.expand_place_refs(), 1);
+        let address = place(&card).as_address().unwrap();
+        assert_eq!(address, &card.addresses.as_ref().unwrap()["k23"]);
+        assert_eq!(card.expand_place_refs(), 0);
+
+        // an unknown reference is kept
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "addresses": {
+                "k23": { "full": "4445 Tree Street\nNew England, ND 58647\nUSA" }
+            },
+            "anniversaries": {
+                "k9": {
+                    "kind": "birth",
+                    "date": { "year": 1953 },
+                    "place": { PLACE_KEY_PROPERTY: "k99" }
+                }
+            }
+        }))?;
+        assert_eq!(card.expand_place_refs(), 0);
+        assert_eq!(place(&card), &PlaceRef::Key("k99".to_string()));
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+mod test {
+    use jscontact::{Card, PrefProperty, ValidationCode, ValidationSeverity};
+    use serde_json::json;
+
+    /// Get the keys and prefs of the emails, sorted by key
+    fn email_prefs(card: &Card) -> Vec<(String, Option<u32>)> {
+        let mut prefs: Vec<(String, Option<u32>)> = card
+            .emails
+            .iter()
+            .flatten()
+            .map(|(key, email)| (key.to_string(), email.pref))
+            .collect();
+        prefs.sort();
+        prefs
+    }
+
+    #[test]
+    fn test_duplicate_pref_warning() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "emails": {
+                "e3": { "address": "c@example.com", "pref": 1 },
+                "e1": { "address": "a@example.com", "pref": 1 },
+                "e2": { "address": "b@example.com" },
+                "e4": { "address": "d@example.com", "pref": 5 }
+            },
+            "phones": {
+                "p1": { "number": "tel:+1-555-555-0001", "pref": 2 },
+                "p2": { "number": "tel:+1-555-555-0002", "pref": 2 }
+            },
+            "speakToAs": {
+                "pronouns": {
+                    "k1": { "pronouns": "they/them", "pref": 3 },
+                    "k2": { "pronouns": "xe/xir", "pref": 3 }
+                }
+            }
+        }))?;
+        let report = card.validate();
+        assert!(report.is_valid());
+        let paths: Vec<&str> = report
+            .with_severity(ValidationSeverity::Warning)
+            .filter(|error| error.code == ValidationCode::DuplicatePref)
+            .map(|error| error.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "speakToAs/pronouns/k1/pref",
+                "speakToAs/pronouns/k2/pref",
+                "emails/e1/pref",
+                "emails/e3/pref",
+                "phones/p1/pref",
+                "phones/p2/pref",
+            ]
+        );
+        assert_eq!(
+            report.errors[2].message,
+            "pref 1 is shared with another entry"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_renumber_prefs() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "emails": {
+                "e3": { "address": "c@example.com", "pref": 1 },
+                "e1": { "address": "a@example.com", "pref": 1 },
+                "e2": { "address": "b@example.com" },
+                "e4": { "address": "d@example.com", "pref": 5 }
+            },
+            "phones": {
+                "p1": { "number": "tel:+1-555-555-0001", "pref": 2 },
+                "p2": { "number": "tel:+1-555-555-0002", "pref": 2 }
+            },
+            "speakToAs": {
+                "pronouns": {
+                    "k1": { "pronouns": "they/them", "pref": 3 },
+                    "k2": { "pronouns": "xe/xir", "pref": 3 }
+                }
+            }
+        }))?;
+
+        // the ties are broken by key, an entry without pref comes last
+        assert_eq!(card.renumber_prefs(PrefProperty::Emails), 3);
+        assert_eq!(
+            email_prefs(&card),
+            [
+                ("e1".to_string(), Some(1)),
+                ("e2".to_string(), Some(4)),
+                ("e3".to_string(), Some(2)),
+                ("e4".to_string(), Some(3)),
+            ]
+        );
+        // already renumbered
+        assert_eq!(card.renumber_prefs(PrefProperty::Emails), 0);
+        // the other maps are left as is
+        assert_eq!(card.phones.as_ref().unwrap()["p2"].pref, Some(2));
+
+        assert_eq!(card.renumber_all_prefs(), 3);
+        let report = card.validate();
+        assert!(report.errors.is_empty(), "{}", report);
+        let phones = card.phones.as_ref().unwrap();
+        assert_eq!((phones["p1"].pref, phones["p2"].pref), (Some(1), Some(2)));
+        let pronouns = card
+            .speak_to_as
+            .as_ref()
+            .unwrap()
+            .pronouns
+            .as_ref()
+            .unwrap();
+        assert_eq!(
+            (pronouns["k1"].pref, pronouns["k2"].pref),
+            (Some(1), Some(2))
+        );
+        Ok(())
+    }
+}
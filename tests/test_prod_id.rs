@@ -0,0 +1,75 @@
+mod test {
+    use jscontact::{Card, CardBuilder, CardKind, ProdId};
+
+    #[test]
+    fn test_prod_id_parse() {
+        let cases = [
+            (
+                "ACME Contacts App version 1.23.5",
+                "ACME Contacts App",
+                Some("1.23.5"),
+            ),
+            (
+                "-//Apple Inc.//iPhone OS 17.2//EN",
+                "iPhone OS",
+                Some("17.2"),
+            ),
+            (
+                "-//Apple Inc.//Mac OS X 10.15.7//EN",
+                "Mac OS X",
+                Some("10.15.7"),
+            ),
+            (
+                "-//Google Inc//Google Contacts//EN",
+                "Google Contacts",
+                None,
+            ),
+            (
+                "-//Sabre//Sabre VObject 4.4.2//EN",
+                "Sabre VObject",
+                Some("4.4.2"),
+            ),
+            (
+                "-//Nextcloud//Contacts v5.5.3//EN",
+                "Contacts",
+                Some("5.5.3"),
+            ),
+            ("DAVx5/4.3.9-ose", "DAVx5", Some("4.3.9-ose")),
+            ("-//Example Corp.////EN", "Example Corp.", None),
+            ("Thunderbird", "Thunderbird", None),
+        ];
+        for (prod_id, product, version) in cases {
+            let parsed = ProdId::parse(prod_id).unwrap();
+            assert_eq!(parsed.product, product, "{}", prod_id);
+            assert_eq!(parsed.version.as_deref(), version, "{}", prod_id);
+        }
+        assert_eq!(ProdId::parse("  "), None);
+    }
+
+    #[test]
+    fn test_generator() {
+        let mut card = Card::new_with_latest_version("1234");
+        assert_eq!(card.generator(), None);
+        card.set_generator("jscontact", "0.2.1");
+        assert_eq!(card.prod_id.as_deref(), Some("jscontact 0.2.1"));
+        assert_eq!(
+            card.generator(),
+            Some(("jscontact".to_string(), Some("0.2.1".to_string())))
+        );
+        assert_eq!(ProdId::new("jscontact", "").to_string(), "jscontact");
+    }
+
+    #[test]
+    fn test_builder_with_generator() {
+        let card = CardBuilder::new("1234")
+            .with_kind(CardKind::Org)
+            .with_generator("jscontact", env!("CARGO_PKG_VERSION"))
+            .build();
+        assert_eq!(card.kind, Some(CardKind::Org));
+        assert_eq!(
+            card.prod_id,
+            Some(format!("jscontact {}", env!("CARGO_PKG_VERSION")))
+        );
+        assert_eq!(CardBuilder::new("1234").build().prod_id, None);
+    }
+}
@@ -0,0 +1,167 @@
+mod test {
+    use jscontact::{Card, Name};
+
+    #[test]
+    fn test_to_mecard() -> Result<(), Box<dyn std::error::Error>> {
+        let card = Card::try_from(serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:1234",
+            "name": {
+                "components": [
+                    { "kind": "given", "value": "Jane" },
+                    { "kind": "surname", "value": "Doe" }
+                ]
+            },
+            "phones": {
+                "p1": { "number": "tel:+1-555-555-0100", "pref": 2 },
+                "p2": { "number": "tel:+1-555-555-0199", "pref": 1 }
+            },
+            "emails": {
+                "e1": { "address": "jane.doe@example.com" }
+            },
+            "addresses": {
+                "a1": { "full": "1 Main St, Springfield" }
+            },
+            "links": {
+                "l1": { "uri": "https://example.com/jane" }
+            },
+            "notes": {
+                "n2": { "note": "second" },
+                "n1": { "note": "Meet at 10:00; bring the badge" }
+            }
+        }))?;
+        assert_eq!(
+            card.to_mecard(),
+            concat!(
+                "MECARD:N:Doe,Jane;TEL:+1-555-555-0199;EMAIL:jane.doe@example.com;",
+                r"ADR:1 Main St\, Springfield;URL:https\://example.com/jane;",
+                r"NOTE:Meet at 10\:00\; bring the badge;;"
+            )
+        );
+
+        let card = Card::try_from(serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:5678",
+            "name": { "full": "Doe; Jr \\ Jane" }
+        }))?;
+        assert_eq!(card.to_mecard(), r"MECARD:N:Doe\; Jr \\ Jane;;");
+
+        let empty = Card::new_with_latest_version("urn:uuid:0");
+        assert_eq!(empty.to_mecard(), "MECARD:;");
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_mecard_with_report() -> Result<(), Box<dyn std::error::Error>> {
+        let card = Card::try_from(serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:1234",
+            "name": {
+                "components": [
+                    { "kind": "given", "value": "Jane" },
+                    { "kind": "surname", "value": "Doe" }
+                ]
+            },
+            "phones": {
+                "p1": { "number": "tel:+1-555-555-0100", "pref": 2 },
+                "p2": { "number": "tel:+1-555-555-0199", "pref": 1 }
+            },
+            "emails": {
+                "e1": { "address": "jane.doe@example.com" }
+            },
+            "addresses": {
+                "a1": { "full": "1 Main St, Springfield" }
+            },
+            "links": {
+                "l1": { "uri": "https://example.com/jane" }
+            },
+            "notes": {
+                "n2": { "note": "second" },
+                "n1": { "note": "Meet at 10:00; bring the badge" }
+            }
+        }))?;
+        let (mecard, report) = card.to_mecard_with_report();
+        assert_eq!(mecard, card.to_mecard());
+        let paths: Vec<&str> = report.lossy.iter().map(|loss| loss.path.as_str()).collect();
+        assert_eq!(paths, ["notes/n2", "phones/p1"]);
+        assert_eq!(
+            report.lossy[1].dropped_value,
+            Some(serde_json::json!({ "number": "tel:+1-555-555-0100", "pref": 2 }))
+        );
+
+        let card = Card::try_from(serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:5678",
+            "name": {
+                "full": "Dr. Jane Doe",
+                "components": [
+                    { "kind": "title", "value": "Dr." },
+                    { "kind": "given", "value": "Jane" },
+                    { "kind": "surname", "value": "Doe" }
+                ]
+            },
+            "organizations": { "o1": { "name": "ABC, Inc." } },
+            "addresses": { "a1": { "countryCode": "US" } }
+        }))?;
+        let (mecard, report) = card.to_mecard_with_report();
+        assert_eq!(mecard, "MECARD:N:Doe,Jane;;");
+        let paths: Vec<&str> = report.lossy.iter().map(|loss| loss.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "addresses",
+                "name/components/0",
+                "name/full",
+                "organizations"
+            ]
+        );
+        assert!(Card::new_with_latest_version("urn:uuid:0")
+            .to_mecard_with_report()
+            .1
+            .is_lossless());
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_vcard_minimal() -> Result<(), Box<dyn std::error::Error>> {
+        let card = Card::try_from(serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:1234",
+            "name": {
+                "components": [
+                    { "kind": "given", "value": "Jane" },
+                    { "kind": "surname", "value": "Doe" }
+                ]
+            },
+            "phones": {
+                "p1": { "number": "tel:+1-555-555-0100", "pref": 2 },
+                "p2": { "number": "tel:+1-555-555-0199", "pref": 1 }
+            },
+            "emails": { "e1": { "address": "jane.doe@example.com" } },
+            "addresses": { "a1": { "full": "1 Main St, Springfield" } }
+        }))?;
+        assert_eq!(
+            card.to_vcard_minimal(),
+            "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\r\nTEL;VALUE=uri:tel:+1-555-555-0199\r\n\
+             EMAIL:jane.doe@example.com\r\nEND:VCARD\r\n"
+        );
+
+        let mut card = Card::new_with_latest_version("urn:uuid:5678");
+        let mut name = Name::default();
+        name.full = Some("Doe; Jr, Jane".to_string());
+        card.name = Some(name);
+        assert_eq!(
+            card.to_vcard_minimal(),
+            "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Doe\\; Jr\\, Jane\r\nEND:VCARD\r\n"
+        );
+        // the uid is the formatted name of a Card without name
+        let card = Card::new_with_latest_version("urn:uuid:0");
+        assert!(card.to_vcard_minimal().contains("\r\nFN:urn:uuid:0\r\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+mod test {
+    use jscontact::Card;
+
+    const SECRETS: [&str; 10] = [
+        "Jane",
+        "Doe",
+        "jane.doe@example.com",
+        "555",
+        "Springfield",
+        "Evergreen",
+        "secret conference",
+        "1953",
+        "janedoe",
+        "Jeanne",
+    ];
+
+    #[test]
+    fn test_debug_redacted() -> Result<(), Box<dyn std::error::Error>> {
+        let card = Card::try_from(serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "name": {
+                "components": [
+                    { "kind": "given", "value": "Jane" },
+                    { "kind": "surname", "value": "Doe" }
+                ],
+                "full": "Jane Doe"
+            },
+            "emails": { "e1": { "address": "jane.doe@example.com", "pref": 1 } },
+            "phones": { "p1": { "number": "tel:+1-555-555-5555", "features": { "voice": true } } },
+            "addresses": {
+                "a1": {
+                    "components": [{ "kind": "locality", "value": "Springfield" }],
+                    "full": "742 Evergreen Terrace, Springfield",
+                    "countryCode": "US"
+                }
+            },
+            "notes": { "n1": { "note": "Met at the secret conference" } },
+            "anniversaries": { "k1": { "kind": "birth", "date": { "year": 1953, "month": 4, "day": 15 } } },
+            "onlineServices": { "o1": { "service": "Mastodon", "user": "@janedoe@example.social" } },
+            "localizations": { "fr": { "name/full": "Jeanne Biche" } },
+            "example.com:secret": "hidden-extension-value"
+        }))?;
+        let redacted = format!("{:?}", card.debug_redacted());
+        for secret in SECRETS {
+            assert!(!redacted.contains(secret), "{} in {}", secret, redacted);
+        }
+        assert!(!redacted.contains("hidden-extension-value"));
+        // the kinds and the structure are kept
+        assert!(redacted.contains(r#"address: "j***@e***""#), "{}", redacted);
+        assert!(redacted.contains("Locality"));
+        assert!(redacted.contains("Mastodon"));
+        assert!(redacted.contains("Birth"));
+        assert!(redacted.contains(r#"note: "28 chars""#));
+        assert!(redacted.contains(r#"localizations: ["fr"]"#));
+        assert!(redacted.contains(r#"extensions: ["example.com:secret"]"#));
+        assert!(redacted.contains(r#"uid: "1234""#));
+        Ok(())
+    }
+
+    #[test]
+    fn test_debug() -> Result<(), Box<dyn std::error::Error>> {
+        let card = Card::try_from(serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "emails": { "e1": { "address": "jane.doe@example.com", "pref": 1 } }
+        }))?;
+        let debug = format!("{:?}", card.emails.as_ref().unwrap());
+        if cfg!(feature = "redact-debug") {
+            assert!(!debug.contains("jane.doe@example.com"));
+            assert!(debug.contains("j***@e***"));
+        } else {
+            // the full Debug output is kept by default
+            assert!(debug.contains("jane.doe@example.com"));
+        }
+        Ok(())
+    }
+}
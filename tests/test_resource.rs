@@ -1,5 +1,16 @@
 mod test {
-    use jscontact::{Calendar, Resource};
+    use jscontact::{
+        Anniversary, AnniversaryKind, Calendar, CalendarKind, Card, CryptoKey, DateObject,
+        Directory, DirectoryKind, Link, LinkKind, Media, MediaKind, PartialDate, PersonalInfo,
+        PersonalInfoKind, Resource, ResourceRef, SmallString, Title, TitleKind,
+    };
+    use serde::Serialize;
+
+    /// Check that a kind string is the serialized kind
+    fn check<T: Serialize>(kind_str: Option<&str>, kind: &T) {
+        let value = serde_json::to_value(kind).unwrap();
+        assert_eq!(kind_str, value.as_str());
+    }
 
     #[test]
     fn test_resource_to_calendar() {
@@ -13,4 +24,111 @@ mod test {
 
         assert_eq!(calendar, my_calendar);
     }
+
+    #[test]
+    fn test_kind_str() {
+        for kind in [CalendarKind::Calendar, CalendarKind::FreeBusy] {
+            let mut calendar = Calendar::new("https://example.com/cal.ics");
+            calendar.kind = Some(kind.clone());
+            check(calendar.kind_str(), &kind);
+            assert_eq!(kind.to_string(), kind.as_str());
+        }
+        for kind in [DirectoryKind::Directory, DirectoryKind::Entry] {
+            let mut directory = Directory::new("https://example.com/dir");
+            directory.kind = Some(kind.clone());
+            check(directory.kind_str(), &kind);
+            assert_eq!(kind.to_string(), kind.as_str());
+        }
+        let mut link = Link::new("https://example.com");
+        assert_eq!(link.kind_str(), None);
+        link.kind = Some(LinkKind::Contact);
+        check(link.kind_str(), &LinkKind::Contact);
+        for kind in [MediaKind::Photo, MediaKind::Sound, MediaKind::Logo] {
+            check(
+                Media::new("https://example.com/m", kind.clone()).kind_str(),
+                &kind,
+            );
+            assert_eq!(kind.to_string(), kind.as_str());
+        }
+        for kind in [TitleKind::Role, TitleKind::Title] {
+            let mut title = Title::new("Manager");
+            title.kind = Some(kind.clone());
+            check(title.kind_str(), &kind);
+        }
+        for kind in [
+            PersonalInfoKind::Expertise,
+            PersonalInfoKind::Hobby,
+            PersonalInfoKind::Interest,
+        ] {
+            check(PersonalInfo::new(kind.clone(), "chess").kind_str(), &kind);
+        }
+        for kind in [
+            AnniversaryKind::Birth,
+            AnniversaryKind::Death,
+            AnniversaryKind::Wedding,
+            AnniversaryKind::Other("example.com:graduation".to_string()),
+        ] {
+            let date = DateObject::PartialDate(PartialDate::default());
+            check(Anniversary::new(kind.clone(), date).kind_str(), &kind);
+            assert_eq!(kind.to_string(), kind.as_str());
+        }
+        // no kind is defined for the crypto keys
+        let mut key = CryptoKey::new("https://example.com/key.asc");
+        assert_eq!(key.kind_str(), None);
+        key.kind = Some("example.com:signing".to_string());
+        assert_eq!(key.kind_str(), Some("example.com:signing"));
+    }
+
+    #[test]
+    fn test_card_resources() {
+        let mut card: Card =
+            serde_json::from_slice(include_bytes!("./rfc9553/figure_37.json")).unwrap();
+        let key: Card = serde_json::from_slice(include_bytes!("./rfc9553/figure_35.json")).unwrap();
+        card.crypto_keys = key.crypto_keys;
+        let resources = card.resources();
+        let table: Vec<(&str, &str, Option<&str>)> = resources
+            .iter()
+            .map(|(property, key, resource)| (*property, key.as_str(), resource.kind_str()))
+            .collect();
+        assert_eq!(
+            table,
+            vec![
+                ("cryptoKeys", "mykey2", None),
+                ("links", "link3", Some("contact"))
+            ]
+        );
+        let (_, key, link) = &resources[1];
+        assert_eq!(*key, &SmallString::from("link3"));
+        assert!(matches!(link, ResourceRef::Link(_)));
+        assert_eq!(link.uri(), "mailto:contact@example.com");
+        assert_eq!(link.label(), None);
+        assert!(Card::new_with_latest_version("1").resources().is_empty());
+    }
+
+    #[test]
+    fn test_uri_scheme() {
+        let cases = [
+            ("webcal://calendar.example.com/jane.ics", Some("webcal")),
+            ("HTTPS://www.example.com/", Some("HTTPS")),
+            ("CID:part1.0603@example.com", Some("CID")),
+            ("data:image/png;base64,iVBORw0KGgo=", Some("data")),
+            ("tel:+1-555-555-5555", Some("tel")),
+            ("photos/jane.jpg", None),
+            ("photos/jane:2.jpg", None),
+            ("//example.com/jane.jpg", None),
+            (":empty", None),
+            ("", None),
+        ];
+        for (uri, scheme) in cases {
+            assert_eq!(Link::new(uri).uri_scheme(), scheme, "{}", uri);
+            assert_eq!(Resource::new(uri.to_string()).uri_scheme(), scheme);
+        }
+        let media = Media::new("CID:part1.0603@example.com", MediaKind::Photo);
+        assert_eq!(ResourceRef::Media(&media).uri_scheme(), Some("CID"));
+        assert_eq!(CryptoKey::new("data:,key").uri_scheme(), Some("data"));
+        assert_eq!(
+            Directory::new("ldap://ldap.example/").uri_scheme(),
+            Some("ldap")
+        );
+    }
 }
@@ -0,0 +1,111 @@
+mod test {
+    use jscontact::{Card, Context, EmailAddress, SchedulingAddress, SmallString};
+    use std::collections::HashMap;
+
+    fn figure(bytes: &[u8]) -> Card {
+        Card::try_from(bytes).unwrap()
+    }
+
+    #[test]
+    fn test_scheduling_uri_figure_30() {
+        let mut card = figure(include_bytes!("./rfc9553/figure_30.json"));
+        assert_eq!(
+            card.scheduling_uri(None).as_deref(),
+            Some("mailto:janedoe@example.com")
+        );
+
+        // the address in the context is preferred over a lower pref
+        let mut private = SchedulingAddress::new("mailto:jane@home.example");
+        private.contexts = Some(HashMap::from([(Context::Private, true)]));
+        let mut other = SchedulingAddress::new("https://cal.example.com/jane");
+        other.pref = Some(1);
+        let addresses = card.scheduling_addresses.as_mut().unwrap();
+        addresses.insert(SmallString::from("sched2"), private);
+        addresses.insert(SmallString::from("sched3"), other);
+        assert_eq!(
+            card.scheduling_uri(Some(Context::Private)).as_deref(),
+            Some("mailto:jane@home.example")
+        );
+        assert_eq!(
+            card.scheduling_uri(Some(Context::Work)).as_deref(),
+            Some("https://cal.example.com/jane")
+        );
+    }
+
+    #[test]
+    fn test_scheduling_uri_from_emails() {
+        let mut card = figure(include_bytes!("./rfc9553/figure_25.json"));
+        assert_eq!(
+            card.scheduling_uri(None).as_deref(),
+            Some("mailto:jqpublic@xyz.example.com")
+        );
+        assert_eq!(
+            card.scheduling_uri(Some(Context::Private)).as_deref(),
+            Some("mailto:jane_doe@example.com")
+        );
+
+        let emails = card.emails.as_mut().unwrap();
+        emails.clear();
+        emails.insert(
+            SmallString::from("e1"),
+            EmailAddress::new("\"jane doe\"@example.com"),
+        );
+        assert_eq!(
+            card.scheduling_uri(None).as_deref(),
+            Some("mailto:%22jane%20doe%22@example.com")
+        );
+        // a comma would separate two addresses
+        let emails = card.emails.as_mut().unwrap();
+        emails.insert(
+            SmallString::from("e1"),
+            EmailAddress::new("\"doe,jane\"@example.com"),
+        );
+        let uri = card.scheduling_uri(None).unwrap();
+        assert_eq!(uri, "mailto:%22doe%2Cjane%22@example.com");
+        assert_eq!(
+            SchedulingAddress::new(&uri).email().as_deref(),
+            Some("\"doe,jane\"@example.com")
+        );
+        // `&` and `=` are not qchars
+        let emails = card.emails.as_mut().unwrap();
+        emails.insert(
+            SmallString::from("e1"),
+            EmailAddress::new("jane&john=doe@example.com"),
+        );
+        let uri = card.scheduling_uri(None).unwrap();
+        assert_eq!(uri, "mailto:jane%26john%3Ddoe@example.com");
+        assert_eq!(
+            SchedulingAddress::new(&uri).email().as_deref(),
+            Some("jane&john=doe@example.com")
+        );
+
+        assert_eq!(
+            Card::new_with_latest_version("1").scheduling_uri(None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_scheduling_address_email() {
+        let cases = [
+            ("mailto:janedoe@example.com", Some("janedoe@example.com")),
+            ("MAILTO:janedoe@example.com", Some("janedoe@example.com")),
+            (
+                "mailto:%22jane%20doe%22@example.com?subject=invite",
+                Some("\"jane doe\"@example.com"),
+            ),
+            ("mailto:a@example.com,b@example.com", Some("a@example.com")),
+            ("mailto:?to=a@example.com", None),
+            ("mailto:jane%2@example.com", None),
+            ("https://cal.example.com/jane", None),
+        ];
+        for (uri, email) in cases {
+            assert_eq!(
+                SchedulingAddress::new(uri).email().as_deref(),
+                email,
+                "{}",
+                uri
+            );
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::panic::{RefUnwindSafe, UnwindSafe};
+
+/// Fails to compile if the type cannot be shared between threads
+fn assert_thread_safe<T: Send + Sync + Unpin + UnwindSafe + RefUnwindSafe>() {}
+
+mod test {
+    use super::assert_thread_safe;
+    use jscontact::*;
+    use serde_json::Value;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_public_types_are_thread_safe() {
+        assert_thread_safe::<Card>();
+        assert_thread_safe::<CardVersion>();
+        assert_thread_safe::<CardKind>();
+        assert_thread_safe::<Calendar>();
+        assert_thread_safe::<CalendarKind>();
+        assert_thread_safe::<SchedulingAddress>();
+        assert_thread_safe::<CryptoKey>();
+        assert_thread_safe::<Directory>();
+        assert_thread_safe::<DirectoryKind>();
+        assert_thread_safe::<Media>();
+        assert_thread_safe::<MediaKind>();
+        assert_thread_safe::<Link>();
+        assert_thread_safe::<LinkKind>();
+        assert_thread_safe::<Relation>();
+        assert_thread_safe::<RelationshipType>();
+        assert_thread_safe::<Name>();
+        assert_thread_safe::<PhoneticSystem>();
+        assert_thread_safe::<NameComponent>();
+        assert_thread_safe::<NameComponentKind>();
+        assert_thread_safe::<Nickname>();
+        assert_thread_safe::<Organization>();
+        assert_thread_safe::<OrgUnit>();
+        assert_thread_safe::<SpeakToAs>();
+        assert_thread_safe::<GrammaticalGender>();
+        assert_thread_safe::<Pronouns>();
+        assert_thread_safe::<Title>();
+        assert_thread_safe::<TitleKind>();
+        assert_thread_safe::<EmailAddress>();
+        assert_thread_safe::<OnlineService>();
+        assert_thread_safe::<Phone>();
+        assert_thread_safe::<PhoneFeature>();
+        assert_thread_safe::<Context>();
+        assert_thread_safe::<LanguagePref>();
+        assert_thread_safe::<Anniversary>();
+        assert_thread_safe::<AnniversaryKind>();
+        assert_thread_safe::<DateObject>();
+        assert_thread_safe::<Timestamp>();
+        assert_thread_safe::<PartialDate>();
+        assert_thread_safe::<Address>();
+        assert_thread_safe::<AddressContext>();
+        assert_thread_safe::<AddressComponent>();
+        assert_thread_safe::<AddressComponentKind>();
+        assert_thread_safe::<Note>();
+        assert_thread_safe::<Author>();
+        assert_thread_safe::<PersonalInfo>();
+        assert_thread_safe::<PersonalInfoKind>();
+        assert_thread_safe::<PersonalInfoLevel>();
+        assert_thread_safe::<Resource>();
+        assert_thread_safe::<ValidationError>();
+        assert_thread_safe::<ValidationReport>();
+        assert_thread_safe::<LabelStrings>();
+        assert_thread_safe::<LossRecord>();
+        assert_thread_safe::<ConversionReport>();
+    }
+
+    #[test]
+    fn test_shared_card_localization() {
+        let mut card = Card::new(CardVersion::OneDotZero, "my:uri");
+        let mut name = Name::default();
+        name.full = Some("John".to_string());
+        card.name = Some(name);
+        let mut translations: HashMap<String, Value> = HashMap::new();
+        translations.insert("name/full".to_string(), Value::from("Jean"));
+        card.add_localization("fr", translations);
+        let card = Arc::new(card);
+
+        let results: Vec<Arc<Card>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = ["fr", "de"]
+                .into_iter()
+                .map(|language| {
+                    let card = Arc::clone(&card);
+                    scope.spawn(move || card.get_localized_shared(language).unwrap())
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        let french = results[0].name.as_ref().unwrap().full.as_deref();
+        assert_eq!(french, Some("Jean"));
+        // no localization: the same allocation is shared
+        assert!(Arc::ptr_eq(&results[1], &card));
+    }
+}
@@ -0,0 +1,105 @@
+mod test {
+    use jscontact::{Card, KeyOrder, SerializeOptions};
+    use serde_json::json;
+
+    fn figure_06() -> Card {
+        std::str::from_utf8(include_bytes!("./rfc9553/figure_06.json"))
+            .unwrap()
+            .parse()
+            .unwrap()
+    }
+
+    fn to_string(card: &Card, key_order: KeyOrder) -> String {
+        let opts = SerializeOptions {
+            key_order,
+            pretty: true,
+            ..Default::default()
+        };
+        card.to_string_with(&opts).unwrap()
+    }
+
+    #[test]
+    fn test_serialize_canonical() {
+        let expected = r#"{
+  "@type": "Card",
+  "version": "1.0",
+  "uid": "22B2C7DF-9120-4969-8460-05956FE6B065",
+  "name": {
+    "components": [
+      {
+        "value": "John",
+        "kind": "given"
+      },
+      {
+        "value": "Doe",
+        "kind": "surname"
+      }
+    ],
+    "isOrdered": true
+  },
+  "kind": "individual"
+}"#;
+        assert_eq!(to_string(&figure_06(), KeyOrder::Canonical), expected);
+    }
+
+    #[test]
+    fn test_serialize_alphabetical() {
+        let expected = r#"{
+  "@type": "Card",
+  "kind": "individual",
+  "name": {
+    "components": [
+      {
+        "kind": "given",
+        "value": "John"
+      },
+      {
+        "kind": "surname",
+        "value": "Doe"
+      }
+    ],
+    "isOrdered": true
+  },
+  "uid": "22B2C7DF-9120-4969-8460-05956FE6B065",
+  "version": "1.0"
+}"#;
+        assert_eq!(to_string(&figure_06(), KeyOrder::Alphabetical), expected);
+    }
+
+    #[test]
+    fn test_serialize_maps_sorted() {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "example.com:b": 1,
+            "example.com:a": { "z": 1, "y": 2 },
+            "emails": {
+                "k3": { "address": "c@example.com" },
+                "k1": { "address": "a@example.com", "contexts": { "work": true, "private": true } },
+                "k2": { "address": "b@example.com" }
+            },
+            "localizations": {
+                "fr": { "titles/t2/name": "b", "titles/t1/name": "a" }
+            }
+        }))
+        .unwrap();
+        let json = card.to_string_with(&SerializeOptions::default()).unwrap();
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"@type":"Card","version":"1.0","uid":"1234","#,
+                r#""localizations":{"fr":{"titles/t1/name":"a","titles/t2/name":"b"}},"#,
+                r#""emails":{"k1":{"address":"a@example.com","contexts":{"private":true,"work":true}},"#,
+                r#""k2":{"address":"b@example.com"},"k3":{"address":"c@example.com"}},"#,
+                r#""example.com:a":{"z":1,"y":2},"example.com:b":1}"#
+            )
+        );
+        // the output is the same on each call and parses back to the same Card
+        assert_eq!(
+            card.to_string_with(&SerializeOptions::default()).unwrap(),
+            json
+        );
+        assert_eq!(json.parse::<Card>().unwrap(), card);
+    }
+}
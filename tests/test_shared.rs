@@ -0,0 +1,85 @@
+mod test {
+    use jscontact::Card;
+    use serde_json::{json, Value};
+
+    /// The size of a photo of the test Card
+    const PHOTO_SIZE: usize = 2 * 1024 * 1024;
+
+    /// A Card with five photos of 2 MB, localized in French and German
+    fn photo_card() -> Value {
+        let photo = format!("data:image/jpeg;base64,{}", "A".repeat(PHOTO_SIZE));
+        let media: serde_json::Map<String, Value> = (1..=5)
+            .map(|idx| {
+                (
+                    format!("m{}", idx),
+                    json!({ "kind": "photo", "uri": photo, "label": "photo" }),
+                )
+            })
+            .collect();
+        json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "language": "en",
+            "name": { "full": "Jane Doe" },
+            "media": media,
+            "localizations": {
+                "fr": { "name/full": "Jeanne Doe", "media/m1/label": "photo d'identité" },
+                "de": { "name": null }
+            }
+        })
+    }
+
+    #[test]
+    fn test_shared_localized_equality() {
+        let json = photo_card();
+        let card: Card = serde_json::from_value(json.clone()).unwrap();
+        // the serialized format does not depend on the `shared` feature
+        let mut expected = json.clone();
+        expected.as_object_mut().unwrap().remove("localizations");
+        let mut base = serde_json::to_value(&card).unwrap();
+        base.as_object_mut().unwrap().remove("localizations");
+        assert_eq!(base, expected);
+
+        let localized = card.get_localized("fr").unwrap();
+        let value = serde_json::to_value(&localized).unwrap();
+        assert_eq!(value["name"]["full"], "Jeanne Doe");
+        assert_eq!(value["media"]["m1"]["label"], "photo d'identité");
+        assert_eq!(value["media"]["m2"]["label"], "photo");
+        assert_eq!(value["media"], {
+            let mut media = expected["media"].clone();
+            media["m1"]["label"] = json!("photo d'identité");
+            media
+        });
+
+        // a null patch of a whole property removes it
+        let localized = card.get_localized("de").unwrap();
+        assert_eq!(localized.name, None);
+        assert_eq!(localized.media, card.media);
+    }
+
+    #[test]
+    fn test_shared_clone_cost() {
+        let card: Card = serde_json::from_value(photo_card()).unwrap();
+        let clones: Vec<Card> = (0..20).map(|_| card.clone()).collect();
+        assert!(clones.iter().all(|clone| *clone == card));
+
+        #[cfg(feature = "shared")]
+        {
+            use std::sync::Arc;
+
+            // the clones share the photos instead of copying 200 MB
+            let media = card.media.as_ref().unwrap();
+            assert_eq!(Arc::strong_count(&media["m1"].uri), 21);
+            let cloned = clones[0].media.as_ref().unwrap();
+            assert!(Arc::ptr_eq(&media["m1"].uri, &cloned["m1"].uri));
+            drop(clones);
+            assert_eq!(Arc::strong_count(&media["m1"].uri), 1);
+
+            // the localization only copies the patched properties
+            let localized = card.get_localized("de").unwrap();
+            let localized_media = localized.media.as_ref().unwrap();
+            assert!(Arc::ptr_eq(&media["m1"].uri, &localized_media["m1"].uri));
+        }
+    }
+}
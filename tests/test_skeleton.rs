@@ -0,0 +1,103 @@
+mod test {
+    use jscontact::{Card, CardKind, SkeletonFields};
+    use serde_json::json;
+
+    #[test]
+    fn test_to_skeleton() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "kind": "group",
+            "created": "2022-09-30T14:35:10Z",
+            "updated": "2022-09-30T14:35:10Z",
+            "prodId": "ACME Contacts App version 1.23.5",
+            "language": "en",
+            "name": {
+                "components": [
+                    { "kind": "title", "value": "The" },
+                    { "kind": "surname", "value": "Doe family" }
+                ],
+                "isOrdered": true
+            },
+            "members": {
+                "urn:uuid:b8767877-b4a1-4c70-9acc-505d3819e519": true,
+                "urn:uuid:e3a1e3c0-f4d9-4fb0-84b1-1c7b4e1e6a2d": true
+            },
+            "nicknames": { "k1": { "name": "The Does" } },
+            "emails": { "e1": { "address": "family@example.com", "pref": 1 } },
+            "phones": { "p1": { "number": "tel:+1-555-555-5555" } },
+            "keywords": { "family": true },
+            "notes": { "n1": { "note": "Neighbours" } },
+            "localizations": {
+                "fr": { "name/components/1/value": "famille Doe" }
+            },
+            "example.com:custom": { "value": 1 }
+        }))?;
+        let skeleton = card.to_skeleton();
+        assert!(skeleton.validate().is_valid());
+        assert!(skeleton.is_skeleton_of(&card));
+        assert_eq!(
+            serde_json::to_value(&skeleton)?,
+            json!({
+                "@type": "Card",
+                "version": "1.0",
+                "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+                "kind": "group",
+                "name": {
+                    "components": [
+                        { "kind": "title", "value": "The" },
+                        { "kind": "surname", "value": "Doe family" }
+                    ],
+                    "isOrdered": true
+                }
+            })
+        );
+
+        let all = card.to_skeleton_with(&SkeletonFields::all());
+        assert_eq!(all.members, card.members);
+        assert!(all.validate().is_valid());
+        assert!(all.is_skeleton_of(&card));
+
+        let none = card.to_skeleton_with(&SkeletonFields::none());
+        assert_eq!(
+            none,
+            Card::new_with_latest_version("urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af")
+        );
+        assert!(none.is_skeleton_of(&card));
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_skeleton_of() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "kind": "group",
+            "name": { "full": "The Doe family" },
+            "members": { "urn:uuid:b8767877-b4a1-4c70-9acc-505d3819e519": true },
+            "emails": { "e1": { "address": "family@example.com" } }
+        }))?;
+        // the full Card is not a skeleton
+        assert!(!card.is_skeleton_of(&card));
+        assert!(card.to_skeleton().is_skeleton_of(&card.to_skeleton()));
+
+        let mut other_uid = card.to_skeleton();
+        other_uid.uid = "urn:uuid:other".to_string();
+        assert!(!other_uid.is_skeleton_of(&card));
+
+        let mut other_kind = card.to_skeleton();
+        other_kind.kind = Some(CardKind::Individual);
+        assert!(!other_kind.is_skeleton_of(&card));
+
+        let mut other_members = card.to_skeleton_with(&SkeletonFields::all());
+        other_members.members.as_mut().unwrap().clear();
+        assert!(!other_members.is_skeleton_of(&card));
+
+        let mut with_keyword = card.to_skeleton();
+        with_keyword.add_keyword("family");
+        assert!(!with_keyword.is_skeleton_of(&card));
+        Ok(())
+    }
+}
@@ -0,0 +1,206 @@
+mod test {
+    use jscontact::{Card, CardKind, CardSet, Name, OrgUnit, Organization, SmallString, SortOrder};
+    use serde_json::json;
+
+    fn figure_22() -> Card {
+        Card::try_from(include_bytes!("./rfc9553/figure_22.json").as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_name_sort_key() {
+        let card = Card::try_from(include_bytes!("./rfc9553/figure_19.json").as_slice()).unwrap();
+        assert_eq!(
+            card.name.unwrap().sort_key(),
+            vec!["Pau Shou Chang", "", "Robert", "Pau", ""]
+        );
+
+        let mut name = Name::default();
+        assert_eq!(name.sort_key(), vec![""; 5]);
+        name.full = Some("Jane Doe".to_string());
+        assert_eq!(name.sort_key(), vec!["Jane Doe", "", "", "", ""]);
+    }
+
+    #[test]
+    fn test_name_sort_key_missing_component() {
+        let name = |components: serde_json::Value| -> Name {
+            serde_json::from_value(json!({ "components": components })).unwrap()
+        };
+        let without_surname2 = name(json!([
+            { "kind": "surname", "value": "Doe" },
+            { "kind": "given", "value": "Zack" }
+        ]));
+        let with_surname2 = name(json!([
+            { "kind": "surname", "value": "Doe" },
+            { "kind": "surname2", "value": "Adams" },
+            { "kind": "given", "value": "Amy" }
+        ]));
+        // the given name is not compared with the second surname
+        assert_eq!(without_surname2.sort_key(), vec!["Doe", "", "Zack", "", ""]);
+        assert!(without_surname2.sort_key() < with_surname2.sort_key());
+    }
+
+    #[test]
+    fn test_primary_organization() {
+        let mut card = figure_22();
+        assert_eq!(
+            card.primary_organization().unwrap().name.as_deref(),
+            Some("ABC, Inc.")
+        );
+
+        let second: Organization = serde_json::from_value(json!({ "name": "Acme" })).unwrap();
+        let organizations = card.organizations.as_mut().unwrap();
+        organizations.insert(SmallString::from("o0"), second);
+        assert_eq!(
+            card.primary_organization().unwrap().name.as_deref(),
+            Some("Acme")
+        );
+        assert!(Card::new_with_latest_version("1")
+            .primary_organization()
+            .is_none());
+    }
+
+    #[test]
+    fn test_org_sort_key() {
+        let mut card = figure_22();
+        // not an org, the organization is not part of the key
+        assert_eq!(card.sort_key(), vec![""; 5]);
+
+        card.kind = Some(CardKind::Org);
+        assert_eq!(card.sort_key(), vec!["ABC", "", "", "", "", ""]);
+        let mut name = Name::default();
+        name.full = Some("ABC North America".to_string());
+        card.name = Some(name);
+        assert_eq!(
+            card.sort_key(),
+            vec!["ABC", "ABC North America", "", "", "", ""]
+        );
+
+        let organization = card.organizations.as_mut().unwrap().get_mut("o1").unwrap();
+        organization.sort_as = None;
+        assert_eq!(
+            card.sort_key(),
+            vec!["ABC, Inc.", "ABC North America", "", "", "", ""]
+        );
+
+        // an org without organization keeps the place of the organization
+        card.organizations = None;
+        assert_eq!(
+            card.sort_key(),
+            vec!["", "ABC North America", "", "", "", ""]
+        );
+    }
+
+    #[test]
+    fn test_sorted_units() {
+        let mut card = figure_22();
+        let organization = card.organizations.as_mut().unwrap().get_mut("o1").unwrap();
+        let names = |organization: &Organization| -> Vec<String> {
+            organization
+                .sorted_units()
+                .iter()
+                .map(|unit| unit.name.clone())
+                .collect()
+        };
+        assert_eq!(
+            names(organization),
+            vec!["Marketing", "North American Division"]
+        );
+
+        let mut unit = OrgUnit::new("The Board");
+        unit.sort_as = Some("Board".to_string());
+        organization.units.as_mut().unwrap().push(unit);
+        assert_eq!(
+            names(organization),
+            vec!["The Board", "Marketing", "North American Division"]
+        );
+    }
+
+    fn card_set() -> CardSet {
+        serde_json::from_value(json!([
+            {
+                "@type": "Card", "version": "1.0", "uid": "urn:c",
+                "updated": "2024-01-01T00:00:00Z",
+                "name": { "components": [{ "kind": "surname", "value": "Doe" }] }
+            },
+            { "@type": "Card", "version": "1.0", "uid": "urn:e" },
+            {
+                "@type": "Card", "version": "1.0", "uid": "urn:b",
+                "updated": "2023-06-01T00:00:00Z",
+                "name": { "full": "Zoe Zed" }
+            },
+            {
+                "@type": "Card", "version": "1.0", "uid": "urn:a",
+                "updated": "2024-01-01T00:00:00Z",
+                "name": {
+                    "components": [{ "kind": "surname", "value": "Doe" }]
+                }
+            },
+            { "@type": "Card", "version": "1.0", "uid": "urn:d", "updated": "2023-06-01T00:00:00Z" }
+        ]))
+        .unwrap()
+    }
+
+    fn uids(cards: &CardSet) -> Vec<&str> {
+        cards.0.iter().map(|card| card.uid.as_str()).collect()
+    }
+
+    #[test]
+    fn test_card_set_sort() {
+        let mut cards = card_set();
+        cards.sort(SortOrder::ByUid);
+        assert_eq!(uids(&cards), ["urn:a", "urn:b", "urn:c", "urn:d", "urn:e"]);
+        // the same names are sorted by uid, the Cards without name last
+        cards.sort(SortOrder::ByDisplayName);
+        assert_eq!(uids(&cards), ["urn:a", "urn:c", "urn:b", "urn:d", "urn:e"]);
+        // the same timestamps are sorted by uid, the Cards without updated last
+        cards.sort(SortOrder::ByUpdated);
+        assert_eq!(uids(&cards), ["urn:b", "urn:d", "urn:a", "urn:c", "urn:e"]);
+
+        // the result does not depend on the initial order
+        let mut reversed = card_set();
+        reversed.0.reverse();
+        for order in [
+            SortOrder::ByUid,
+            SortOrder::ByDisplayName,
+            SortOrder::ByUpdated,
+        ] {
+            let mut cards = card_set();
+            cards.sort(order);
+            reversed.sort(order);
+            assert_eq!(uids(&cards), uids(&reversed));
+        }
+    }
+
+    #[test]
+    fn test_card_set_sort_as() {
+        let mut cards: CardSet = serde_json::from_value(json!([
+            {
+                "@type": "Card", "version": "1.0", "uid": "urn:1",
+                "name": {
+                    "components": [{ "kind": "surname", "value": "van Gogh" }],
+                    "sortAs": { "surname": "Gogh" }
+                }
+            },
+            {
+                "@type": "Card", "version": "1.0", "uid": "urn:2",
+                "name": { "components": [{ "kind": "surname", "value": "Monet" }] }
+            }
+        ]))
+        .unwrap();
+        cards.sort(SortOrder::ByDisplayName);
+        assert_eq!(uids(&cards), ["urn:1", "urn:2"]);
+    }
+
+    #[test]
+    fn test_card_set_to_string_sorted() {
+        let cards = card_set();
+        let mut reversed = card_set();
+        reversed.0.reverse();
+        let json = cards.to_string_sorted().unwrap();
+        assert_eq!(json, reversed.to_string_sorted().unwrap());
+        assert!(json.starts_with(r#"[{"@type":"Card","version":"1.0","uid":"urn:a","#));
+        let parsed: CardSet = serde_json::from_str(&json).unwrap();
+        assert_eq!(uids(&parsed), ["urn:a", "urn:b", "urn:c", "urn:d", "urn:e"]);
+        assert_eq!(CardSet::default().to_string_sorted().unwrap(), "[]");
+    }
+}
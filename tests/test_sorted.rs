@@ -0,0 +1,75 @@
+mod test {
+    use jscontact::{Card, Context};
+    use serde_json::json;
+
+    fn keys<T>(sorted: Vec<(&str, &T)>) -> Vec<String> {
+        sorted.into_iter().map(|(key, _)| key.to_string()).collect()
+    }
+
+    #[test]
+    fn test_titles_sorted() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "titles": {
+                "t2": { "name": "Project Leader" },
+                "t1": { "name": "Research Scientist" }
+            }
+        }))?;
+        let titles = card.titles_sorted();
+        assert_eq!(titles[0].1.name, "Research Scientist");
+        assert_eq!(keys(titles), ["t1", "t2"]);
+        assert!(Card::new_with_latest_version("1")
+            .titles_sorted()
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_nicknames_sorted() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "nicknames": {
+                "k4": { "name": "Johnny" },
+                "k3": { "name": "JS", "contexts": { "work": true } },
+                "k2": { "name": "Jo", "pref": 1, "contexts": { "private": true } },
+                "k1": { "name": "John", "pref": 1, "contexts": { "work": true } }
+            }
+        }))?;
+        // the key breaks the ties, the nicknames without pref come last
+        assert_eq!(keys(card.nicknames_sorted()), ["k1", "k2", "k3", "k4"]);
+        assert_eq!(keys(card.nicknames_sorted_in(Context::Work)), ["k1", "k3"]);
+        assert_eq!(keys(card.nicknames_sorted_in(Context::Private)), ["k2"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pronouns_sorted() -> Result<(), Box<dyn std::error::Error>> {
+        let card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "speakToAs": {
+                "pronouns": {
+                    "k21": { "pronouns": "they/them", "contexts": { "private": true } },
+                    "k20": { "pronouns": "she/her", "pref": 2, "contexts": { "work": true } },
+                    "k19": { "pronouns": "xe/xir", "pref": 1 }
+                }
+            }
+        }))?;
+        let speak_to_as = card.speak_to_as.as_ref().unwrap();
+        assert_eq!(keys(speak_to_as.pronouns_sorted()), ["k19", "k20", "k21"]);
+        assert_eq!(
+            keys(speak_to_as.pronouns_sorted_in(Context::Private)),
+            ["k21"]
+        );
+        assert_eq!(
+            speak_to_as.pronouns_sorted_in(Context::Work)[0].1.pronouns,
+            "she/her"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+mod test {
+    use jscontact::{Card, CardSetStats, Distribution};
+    use serde_json::json;
+
+    fn corpus() -> Vec<Card> {
+        [
+            json!({
+                "@type": "Card",
+                "version": "1.0",
+                "uid": "c1",
+                "language": "en",
+                "name": { "full": "Jane Doe" },
+                "emails": {
+                    "e1": { "address": "jane@example.com", "pref": 1 },
+                    "e2": { "address": "jane@example.org", "pref": 1 }
+                },
+                "phones": { "p1": { "number": "tel:+1-555-555-5555" } },
+                "media": { "m1": { "kind": "photo", "uri": "https://example.com/jane.png" } },
+                "anniversaries": { "a1": { "kind": "birth", "date": { "year": 1953, "month": 4, "day": 15 } } },
+                "keywords": { "friend": true, "family": true },
+                "titles": { "t1": { "name": "Engineer" } },
+                "localizations": { "fr": { "titles/t1/name": "Ingénieure" } }
+            }),
+            json!({
+                "@type": "Card",
+                "version": "1.0",
+                "uid": "c2",
+                "language": "fr",
+                "name": { "components": [{ "kind": "given", "value": "Jean" }] },
+                "emails": { "e1": { "address": "jean@example.com" } },
+                "keywords": { "friend": true },
+                "notes": { "n1": { "note": "Met at the conference" } }
+            }),
+            json!({
+                "@type": "Card",
+                "version": "1.0",
+                "uid": "c3",
+                "emails": {
+                    "e1": { "address": "a@example.com" },
+                    "e2": { "address": "b@example.com" },
+                    "e3": { "address": "c@example.com", "pref": 0 }
+                },
+                "media": { "m1": { "kind": "logo", "uri": "https://example.com/logo.png" } },
+                "keywords": { "work": true, "friend": false }
+            }),
+            json!({ "@type": "Card", "version": "1.0", "uid": "c4" }),
+        ]
+        .into_iter()
+        .map(|card| serde_json::from_value(card).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn test_card_stats() {
+        let cards = corpus();
+        let stats = cards[0].stats();
+        assert_eq!(stats.entries["emails"], 2);
+        assert_eq!(stats.entries["phones"], 1);
+        assert_eq!(stats.entries["media"], 1);
+        assert!(!stats.entries.contains_key("notes"));
+        assert_eq!(stats.localization_languages, ["fr"]);
+        assert!(stats.has_name && stats.has_photo && stats.has_birthday);
+        // each of the emails sharing the pref 1 is a warning
+        assert_eq!(stats.validation.errors, 0);
+        assert_eq!(stats.validation.warnings, 2);
+        assert_eq!(stats.largest_properties.len(), 5);
+        assert_eq!(stats.largest_properties[0].property, "emails");
+        assert_eq!(
+            stats.largest_properties[0].bytes,
+            serde_json::to_vec(&cards[0].emails).unwrap().len()
+        );
+        assert!(stats
+            .largest_properties
+            .windows(2)
+            .all(|pair| pair[0].bytes >= pair[1].bytes));
+
+        let stats = cards[2].stats();
+        assert!(!stats.has_name && !stats.has_photo && !stats.has_birthday);
+        assert_eq!(stats.validation.errors, 1);
+
+        let json = serde_json::to_value(cards[3].stats()).unwrap();
+        assert_eq!(json["entries"], json!({}));
+        assert_eq!(json["validation"], json!({ "errors": 0, "warnings": 0 }));
+    }
+
+    #[test]
+    fn test_card_set_stats() {
+        let stats = CardSetStats::from(corpus().as_slice());
+        assert_eq!(stats.cards, 4);
+        assert_eq!(
+            stats.entries["emails"],
+            Distribution {
+                min: 0,
+                median: 1.5,
+                max: 3
+            }
+        );
+        assert_eq!(
+            stats.entries["notes"],
+            Distribution {
+                min: 0,
+                median: 0.0,
+                max: 1
+            }
+        );
+        assert_eq!(stats.entries["keywords"].max, 2);
+        assert!(!stats.entries.contains_key("addresses"));
+        // the keywords set to false are not counted
+        assert_eq!(
+            stats.top_keywords(10),
+            [("friend", 2), ("family", 1), ("work", 1)]
+        );
+        assert_eq!(stats.top_keywords(1), [("friend", 2)]);
+        assert_eq!(stats.language_coverage["en"], 25.0);
+        assert_eq!(stats.language_coverage["fr"], 50.0);
+        assert_eq!(stats.with_name, 2);
+        assert_eq!(stats.with_photo, 1);
+        assert_eq!(stats.with_birthday, 1);
+        assert_eq!(stats.invalid, 1);
+        assert_eq!(stats.validation.errors, 1);
+        assert_eq!(stats.validation.warnings, 2);
+    }
+
+    #[test]
+    fn test_empty_card_set_stats() {
+        let stats = CardSetStats::from(&[][..]);
+        assert_eq!(stats, CardSetStats::default());
+    }
+}
@@ -0,0 +1,127 @@
+mod test {
+    use jscontact::syntax::{
+        is_country_code, is_email_addr_spec, is_language_tag, is_uri, is_utc_date_time,
+    };
+
+    #[test]
+    fn test_syntax_uri() {
+        for uri in [
+            "https://www.example.com/photo.jpg",
+            "mailto:jane_doe@example.com",
+            "tel:+1-555-555-5555;ext=5555",
+            "urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6",
+            "data:image/png;base64,iVBORw0KGgo=",
+            "xmpp:alice@example.com",
+        ] {
+            assert!(is_uri(uri), "{}", uri);
+        }
+        for uri in [
+            "",
+            "www.example.com",
+            ":foo",
+            "1http://example.com",
+            "http:",
+            "http://example.com/a b",
+            "ht_tp://example.com",
+        ] {
+            assert!(!is_uri(uri), "{}", uri);
+        }
+    }
+
+    #[test]
+    fn test_syntax_email() {
+        for address in [
+            "jane_doe@example.com",
+            "jane.doe+tag@mail.example.com",
+            "\"jane doe\"@example.com",
+            "\"jane\\\"doe\"@example.com",
+            "user@[192.0.2.1]",
+            "δοκιμή@παράδειγμα.δοκιμή",
+        ] {
+            assert!(is_email_addr_spec(address), "{}", address);
+        }
+        for address in [
+            "",
+            "jane.doe",
+            "@example.com",
+            "jane@",
+            "jane..doe@example.com",
+            ".jane@example.com",
+            "jane doe@example.com",
+            "\"jane@example.com",
+            "jane@-example.com",
+            "jane@example..com",
+        ] {
+            assert!(!is_email_addr_spec(address), "{}", address);
+        }
+    }
+
+    #[test]
+    fn test_syntax_language_tag() {
+        for tag in [
+            "en",
+            "de-CH",
+            "zh-Hant-TW",
+            "sr-Latn-RS",
+            "zh-yue-HK",
+            "es-419",
+            "de-CH-1996",
+            "sl-rozaj-biske",
+            "en-US-u-ca-gregory",
+            "en-x-private",
+            "x-whatever",
+            "EN-us",
+        ] {
+            assert!(is_language_tag(tag), "{}", tag);
+        }
+        for tag in [
+            "",
+            "e",
+            "en_US",
+            "en-",
+            "toolonglanguage",
+            "en-u",
+            "en-x",
+            "x",
+            "en-US-u-ca-x",
+            "123",
+        ] {
+            assert!(!is_language_tag(tag), "{}", tag);
+        }
+    }
+
+    #[test]
+    fn test_syntax_country_code() {
+        assert!(is_country_code("US"));
+        assert!(is_country_code("de"));
+        for code in ["", "U", "USA", "U1", "ÜS"] {
+            assert!(!is_country_code(code), "{}", code);
+        }
+    }
+
+    #[test]
+    fn test_syntax_utc_date_time() {
+        for timestamp in [
+            "2022-09-30T14:35:10Z",
+            "2022-09-30T14:35:10.522Z",
+            "2016-12-31T23:59:60Z",
+        ] {
+            assert!(is_utc_date_time(timestamp), "{}", timestamp);
+        }
+        for timestamp in [
+            "",
+            "2022-09-30",
+            "2022-09-30T14:35:10",
+            "2022-09-30t14:35:10z",
+            "2022-09-30T14:35:10+02:00",
+            "2022-09-30T14:35:10.520Z",
+            "2022-09-30T14:35:10.Z",
+            "2022-13-30T14:35:10Z",
+            "2022-09-30T24:00:00Z",
+            "22-09-30T14:35:10Z",
+            "2022-09-30T14:35Z",
+        ] {
+            assert!(!is_utc_date_time(timestamp), "{}", timestamp);
+        }
+    }
+}
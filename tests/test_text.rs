@@ -0,0 +1,189 @@
+mod test {
+    use jscontact::{Card, TextChange, TextPolicy, ValidationCode, ValidationSeverity};
+    use serde_json::json;
+
+    #[test]
+    fn test_sanitize_text() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "name": {
+                "full": "Jane\u{0} Doe",
+                "components": [
+                    { "kind": "given", "value": "Jane\u{0}" },
+                    { "kind": "surname", "value": "Doe" }
+                ]
+            },
+            "notes": { "n1": { "note": "First line\r\nSecond\tline\rThird line" } },
+            "titles": { "t1": { "name": "Research\tScientist" } },
+            "addresses": { "a1": { "full": "4445 Tree Street\r\nNew England, ND 58647" } }
+        }))?;
+        let changes = card.sanitize_text(&TextPolicy::default());
+        let paths: Vec<&str> = changes.iter().map(|change| change.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "addresses/a1/full",
+                "name/components/0/value",
+                "name/full",
+                "notes/n1/note",
+                "titles/t1/name"
+            ]
+        );
+        assert_eq!(
+            changes[2],
+            TextChange {
+                path: "name/full".to_string(),
+                before: "Jane\0 Doe".to_string(),
+                after: "Jane Doe".to_string(),
+            }
+        );
+        let name = card.name.as_ref().unwrap();
+        assert_eq!(name.full.as_deref(), Some("Jane Doe"));
+        assert_eq!(name.components.as_ref().unwrap()[0].value, "Jane");
+        let notes = card.notes.as_ref().unwrap();
+        assert_eq!(notes["n1"].note, "First line\nSecond\tline\nThird line");
+        assert_eq!(
+            card.addresses.as_ref().unwrap()["a1"].full.as_deref(),
+            Some("4445 Tree Street\nNew England, ND 58647")
+        );
+        assert_eq!(
+            card.titles.as_ref().unwrap()["t1"].name,
+            "ResearchScientist"
+        );
+        // sanitizing twice changes nothing
+        assert!(card.sanitize_text(&TextPolicy::default()).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sanitize_text_policy() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "name": {
+                "full": "Jane\u{0} Doe",
+                "components": [
+                    { "kind": "given", "value": "Jane\u{0}" },
+                    { "kind": "surname", "value": "Doe" }
+                ]
+            },
+            "notes": { "n1": { "note": "First line\r\nSecond\tline\rThird line" } }
+        }))?;
+        let policy = TextPolicy {
+            replacement: Some('\u{FFFD}'),
+            multiline_allowed: Vec::new(),
+            line_endings: false,
+            nfc: false,
+        };
+        card.sanitize_text(&policy);
+        assert_eq!(
+            card.name.as_ref().unwrap().full.as_deref(),
+            Some("Jane\u{FFFD} Doe")
+        );
+        let notes = card.notes.as_ref().unwrap();
+        assert_eq!(
+            notes["n1"].note,
+            "First line\u{FFFD}\u{FFFD}Second\u{FFFD}line\u{FFFD}Third line"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_sanitize_text_display() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "name": {
+                "full": "Jane\u{0} Doe",
+                "components": [
+                    { "kind": "given", "value": "Jane\u{0}" },
+                    { "kind": "surname", "value": "Doe" }
+                ]
+            }
+        }))?;
+        let changes = card.sanitize_text(&TextPolicy::default());
+        assert_eq!(
+            changes[1].to_string(),
+            r#"name/full: "Jane\0 Doe" -> "Jane Doe""#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_control_characters() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af",
+            "name": {
+                "full": "Jane\u{0} Doe",
+                "components": [
+                    { "kind": "given", "value": "Jane\u{0}" },
+                    { "kind": "surname", "value": "Doe" }
+                ]
+            },
+            "titles": { "t1": { "name": "Research\tScientist" } }
+        }))?;
+        let report = card.validate();
+        let found: Vec<(&str, &str)> = report
+            .errors
+            .iter()
+            .filter(|error| error.code == ValidationCode::ControlCharacter)
+            .map(|error| (error.path.as_str(), error.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    "name/components/0/value",
+                    "the text contains the control character U+0000"
+                ),
+                (
+                    "name/full",
+                    "the text contains the control character U+0000"
+                ),
+                (
+                    "titles/t1/name",
+                    "the text contains the control character U+0009"
+                ),
+            ]
+        );
+        assert!(report
+            .errors
+            .iter()
+            .filter(|error| error.code == ValidationCode::ControlCharacter)
+            .all(|error| error.severity == ValidationSeverity::Warning));
+        card.sanitize_text(&TextPolicy::default());
+        assert!(!card
+            .validate()
+            .errors
+            .iter()
+            .any(|error| error.code == ValidationCode::ControlCharacter));
+        Ok(())
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_sanitize_text_nfc() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card: Card = serde_json::from_value(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "titles": { "t1": { "name": "Caf\u{65}\u{301}" } }
+        }))?;
+        let changes = card.sanitize_text(&TextPolicy::default());
+        assert_eq!(changes.len(), 1);
+        assert_eq!(card.titles.as_ref().unwrap()["t1"].name, "Caf\u{e9}");
+        let policy = TextPolicy {
+            nfc: false,
+            ..TextPolicy::default()
+        };
+        card.titles.as_mut().unwrap().get_mut("t1").unwrap().name = "Caf\u{65}\u{301}".to_string();
+        assert!(card.sanitize_text(&policy).is_empty());
+        Ok(())
+    }
+}
@@ -1,7 +1,3 @@
-// we force to use the without `type` API by disabling the feature "typed"
-// because rfc examples are in without `type` format
-#![cfg(not(feature = "typed"))]
-
 // These test are present to validate that the examples in the RFC can be
 // correctly created, encoded and decoded by the library.
 mod test {
@@ -10,14 +6,24 @@ mod test {
 
     use jscontact::{
         Card, CardVersion, Name, NameComponent, NameComponentKind, PersonalInfo, PersonalInfoKind,
-        PersonalInfoLevel, PhoneticSystem,
+        PersonalInfoLevel, PhoneticSystem, SerializeOptions, TypeMembers,
     };
+    use serde_json::Value;
+
+    /// Serialize a Card without the `@type` members of its properties, as the RFC examples
+    fn untyped(card: &Card) -> Value {
+        let opts = SerializeOptions {
+            type_members: TypeMembers::Omit,
+            ..Default::default()
+        };
+        serde_json::from_str(&card.to_string_with(&opts).unwrap()).unwrap()
+    }
 
     #[test]
     fn test_figure_01() {
         let verifier = include_bytes!("./rfc9553/figure_01.json");
         let verifier: Card = serde_json::from_slice(verifier).unwrap();
-        let verifier = serde_json::to_value(verifier).unwrap();
+        let verifier = untyped(&verifier);
 
         let mut card = Card::new(
             CardVersion::OneDotZero,
@@ -31,7 +37,7 @@ mod test {
         name.components = Some(vec![name_component_1, name_component_2]);
         name.phonetic_system = Some(PhoneticSystem::Ipa);
         card.name = Some(name);
-        let card_value = serde_json::to_value(card).unwrap();
+        let card_value = untyped(&card);
         assert_eq!(verifier, card_value);
     }
 
@@ -39,7 +45,7 @@ mod test {
     fn test_figure_44() {
         let verifier = include_bytes!("./rfc9553/figure_44.json");
         let verifier: Card = serde_json::from_slice(verifier).unwrap();
-        let verifier = serde_json::to_value(verifier).unwrap();
+        let verifier = untyped(&verifier);
 
         let mut card = Card::new(
             CardVersion::OneDotZero,
@@ -48,15 +54,15 @@ mod test {
         let mut personal_infos = HashMap::new();
         let mut personal_info = PersonalInfo::new(PersonalInfoKind::Expertise, "chemistry");
         personal_info.level = Some(PersonalInfoLevel::High);
-        personal_infos.insert("pi2".to_string(), personal_info.into());
+        personal_infos.insert("pi2".into(), personal_info);
         let mut personal_info = PersonalInfo::new(PersonalInfoKind::Hobby, "reading");
         personal_info.level = Some(PersonalInfoLevel::High);
-        personal_infos.insert("pi1".to_string(), personal_info.into());
+        personal_infos.insert("pi1".into(), personal_info);
         let mut personal_info = PersonalInfo::new(PersonalInfoKind::Interest, "r&b music");
         personal_info.level = Some(PersonalInfoLevel::Medium);
-        personal_infos.insert("pi6".to_string(), personal_info.into());
+        personal_infos.insert("pi6".into(), personal_info);
         card.personal_info = Some(personal_infos);
-        let card_value = serde_json::to_value(card).unwrap();
+        let card_value = untyped(&card);
         assert_eq!(verifier, card_value);
     }
 }
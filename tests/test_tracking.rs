@@ -0,0 +1,238 @@
+#![cfg(feature = "tracking")]
+
+mod test {
+    use jscontact::dedup::{find_duplicates, merge_group};
+    use jscontact::path::set_value;
+    use jscontact::{
+        Card, CardPath, Clock, DedupOptions, EmailAddress, Media, MediaKind, MergeStrategy,
+        SmallString, SystemClock,
+    };
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    /// A clock counting the seconds of a day, one per call
+    #[derive(Default)]
+    struct TickClock(AtomicU32);
+
+    impl Clock for TickClock {
+        fn now(&self) -> String {
+            let tick = self.0.fetch_add(1, Ordering::SeqCst);
+            format!("2024-01-01T00:00:{:02}Z", tick)
+        }
+    }
+
+    fn card() -> Result<Card, Box<dyn std::error::Error>> {
+        Ok(serde_json::from_slice(include_bytes!(
+            "./rfc9553/figure_01.json"
+        ))?)
+    }
+
+    #[test]
+    fn test_tracking_parsed_card_is_clean() -> Result<(), Box<dyn std::error::Error>> {
+        let card = card()?;
+        assert_eq!(card.revision(), 0);
+        assert!(!card.is_dirty());
+        assert!(!card.is_dirty_since(0));
+        assert_eq!(Card::new_with_latest_version("1234").revision(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tracking_helpers_bump_revision() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card = card()?;
+        card.set_generator("ACME Contacts", "1.2");
+        assert_eq!(card.revision(), 1);
+        card.add_localization("fr", HashMap::new());
+        assert_eq!(card.revision(), 2);
+        let path = CardPath::parse("name/full")?;
+        set_value(&mut card, &path, serde_json::json!("Jane Doe"))?;
+        assert_eq!(card.revision(), 3);
+        assert!(card.is_dirty_since(2));
+        assert!(!card.is_dirty_since(3));
+
+        // nothing to infer
+        assert_eq!(card.infer_media_types(), 0);
+        assert_eq!(card.revision(), 3);
+        card.edit().media = Some(HashMap::from([(
+            SmallString::from("m1"),
+            Media::new("https://example.com/photo.png", MediaKind::Photo),
+        )]));
+        assert_eq!(card.revision(), 4);
+        assert_eq!(card.infer_media_types(), 1);
+        assert_eq!(card.revision(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tracking_edit_guard() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card = card()?;
+        {
+            let edit = card.edit();
+            // reading does not modify the Card
+            assert!(!edit.uid.is_empty());
+        }
+        assert_eq!(card.revision(), 0);
+        {
+            let mut edit = card.edit();
+            edit.kind = None;
+            edit.language = Some("en".to_string());
+        }
+        // one modification per guard
+        assert_eq!(card.revision(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tracking_edit_guard_replace_card() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card = card()?;
+        let clock = Arc::new(TickClock::default());
+        card.touch_updated_on_mutation(Some(clock.clone()));
+        let mut other = card.clone();
+        other.language = Some("en".to_string());
+        // the whole Card, with its revision, is replaced while the guard is open
+        *card.edit() = other.clone();
+        assert_eq!(card.language.as_deref(), Some("en"));
+        assert_eq!(card.revision(), 1);
+        assert_eq!(card.updated.as_deref(), Some("2024-01-01T00:00:00Z"));
+        card.edit_scoped(|card| {
+            card.edit_scoped(|card| **card = other.clone());
+            card.kind = None;
+        });
+        assert_eq!(card.revision(), 2);
+        assert_eq!(card.updated.as_deref(), Some("2024-01-01T00:00:01Z"));
+
+        // the guard is not left open
+        card.add_keyword("friend");
+        assert_eq!(card.updated.as_deref(), Some("2024-01-01T00:00:02Z"));
+        assert_eq!(clock.0.load(Ordering::SeqCst), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tracking_edit_guard_clone() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card = card()?;
+        card.touch_updated_on_mutation(Some(Arc::new(TickClock::default())));
+        // a copy made inside a guard is not being edited
+        let mut copy = card.edit().clone();
+        copy.add_keyword("friend");
+        assert_eq!(copy.updated.as_deref(), Some("2024-01-01T00:00:00Z"));
+        let mut copy = card.edit_scoped(|card| card.clone());
+        copy.add_keyword("friend");
+        assert_eq!(copy.updated.as_deref(), Some("2024-01-01T00:00:01Z"));
+        assert_eq!(card.revision(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tracking_accessors_bump_revision() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card = card()?;
+        *card.full_name_mut().unwrap() = Some("John Smith".to_string());
+        assert_eq!(card.revision(), 1);
+        card.given_name_mut().unwrap().push_str("ny");
+        assert_eq!(card.revision(), 2);
+
+        // a missing field is not borrowed, nor created
+        assert!(card.first_phone_number_mut().is_none());
+        assert!(card.job_title_name_mut().is_none());
+        assert_eq!(card.revision(), 2);
+        assert!(card.is_dirty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tracking_mark_clean() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card = card()?;
+        card.edit().language = Some("en".to_string());
+        assert!(card.is_dirty());
+        assert_eq!(card.mark_clean(), 1);
+        assert!(!card.is_dirty());
+        assert!(card.is_dirty_since(0));
+        card.edit().language = None;
+        assert!(card.is_dirty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tracking_merge() -> Result<(), Box<dyn std::error::Error>> {
+        let mut first = card()?;
+        first.edit().emails = Some(HashMap::from([(
+            SmallString::from("e1"),
+            EmailAddress::new("jane@example.com"),
+        )]));
+        first.mark_clean();
+        let second = card()?;
+        let groups = find_duplicates(&[first, second], &DedupOptions::default());
+        let merged = merge_group(&groups[0], MergeStrategy::PreferFirst);
+        assert!(merged.is_dirty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tracking_not_serialized() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card = card()?;
+        let json = serde_json::to_value(&card)?;
+        card.edit().language = Some("en".to_string());
+        card.edit().language = None;
+        assert_eq!(card.revision(), 2);
+        // the revision is neither serialized nor compared
+        assert_eq!(serde_json::to_value(&card)?, json);
+        assert_eq!(card, self::card()?);
+        let cloned: Card = serde_json::from_value(json)?;
+        assert_eq!(cloned.revision(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tracking_touch_updated() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card = card()?;
+        let clock = Arc::new(TickClock::default());
+        card.touch_updated_on_mutation(Some(clock.clone()));
+        card.add_keyword("friend");
+        assert_eq!(card.updated.as_deref(), Some("2024-01-01T00:00:00Z"));
+        card.add_keyword("family");
+        assert_eq!(card.updated.as_deref(), Some("2024-01-01T00:00:01Z"));
+
+        // once per edit block
+        card.edit_scoped(|card| {
+            card.language = Some("en".to_string());
+            card.add_keyword("work");
+            card.add_localization("fr", HashMap::new());
+        });
+        assert_eq!(card.updated.as_deref(), Some("2024-01-01T00:00:02Z"));
+        assert_eq!(clock.0.load(Ordering::SeqCst), 3);
+        {
+            let mut edit = card.edit();
+            edit.kind = None;
+            edit.edit_scoped(|card| card.add_keyword("home"));
+        }
+        assert_eq!(card.updated.as_deref(), Some("2024-01-01T00:00:03Z"));
+        assert_eq!(clock.0.load(Ordering::SeqCst), 4);
+
+        // not for the read-only blocks
+        let revision = card.revision();
+        let keywords = card.edit_scoped(|card| card.keywords.as_ref().map(HashMap::len));
+        assert_eq!(keywords, Some(4));
+        assert_eq!(card.revision(), revision);
+        assert_eq!(card.updated.as_deref(), Some("2024-01-01T00:00:03Z"));
+        assert_eq!(clock.0.load(Ordering::SeqCst), 4);
+
+        card.touch_updated_on_mutation(None);
+        card.add_keyword("other");
+        assert_eq!(card.updated.as_deref(), Some("2024-01-01T00:00:03Z"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tracking_system_clock() -> Result<(), Box<dyn std::error::Error>> {
+        let now = SystemClock.now();
+        assert_eq!(now.len(), 20);
+        assert!(now.starts_with("20") && now.ends_with('Z'));
+        assert_eq!(&now[10..11], "T");
+        let mut card = card()?;
+        card.touch_updated_on_mutation(Some(Arc::new(SystemClock)));
+        card.edit().language = None;
+        assert!(card.updated.as_deref().unwrap() >= now.as_str());
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+mod test {
+    use jscontact::unknown::is_vendor_property;
+    use jscontact::{parse_with, Card, FieldError, ParseOptions, Parsed};
+    use serde_json::json;
+
+    const CARD: &str = include_str!("./unknown/card.json");
+
+    fn reject_unknown() -> ParseOptions {
+        ParseOptions {
+            reject_unknown_properties: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_is_vendor_property() {
+        assert!(is_vendor_property("example.com:foo"));
+        assert!(is_vendor_property("jscontact.rs:addressKey"));
+        assert!(is_vendor_property("mail.example-1.co.uk:foo:bar"));
+        for name in [
+            "nmae",
+            "example:foo",
+            "example.com:",
+            ":foo",
+            ".com:foo",
+            "example..com:foo",
+            "-example.com:foo",
+            "example.123:foo",
+            "exa mple.com:foo",
+        ] {
+            assert!(!is_vendor_property(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_unknown_properties_kept() {
+        let card: Card = serde_json::from_str(CARD).unwrap();
+        assert_eq!(card.unknown_properties(), ["nmae"]);
+        assert_eq!(card.extensions["nmae"], json!({ "full": "Jane" }));
+        assert_eq!(card.extensions["example.com:custom"], json!({ "value": 1 }));
+        // the default options keep them too
+        let Parsed::Card(parsed) = parse_with(CARD, &ParseOptions::default()).unwrap() else {
+            panic!("expected a Card");
+        };
+        assert_eq!(parsed, card);
+    }
+
+    #[test]
+    fn test_reject_unknown_properties() {
+        let error = Card::from_str_with(CARD, &reject_unknown()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown properties emails/e1/adress, nmae, a vendor-specific property must be prefixed by a domain name (e.g. example.com:foo)"
+        );
+        assert!(parse_with(CARD, &reject_unknown())
+            .unwrap_err()
+            .starts_with("Invalid Card: unknown properties emails/e1/adress, nmae,"));
+        let array = format!("[{}]", CARD);
+        assert!(parse_with(&array, &reject_unknown())
+            .unwrap_err()
+            .starts_with("Invalid Card at index 0: unknown properties"));
+
+        // only the vendor-specific properties
+        let mut value: serde_json::Value = serde_json::from_str(CARD).unwrap();
+        value.as_object_mut().unwrap().remove("nmae");
+        value["emails"]["e1"]
+            .as_object_mut()
+            .unwrap()
+            .remove("adress");
+        let json = value.to_string();
+        let card = Card::from_str_with(&json, &reject_unknown()).unwrap();
+        assert!(card.unknown_properties().is_empty());
+        assert_eq!(card.extensions.len(), 1);
+    }
+
+    #[test]
+    fn test_unknown_properties_collecting() {
+        let (card, errors) = Card::from_value_collecting(serde_json::from_str(CARD).unwrap());
+        let card = card.unwrap();
+        assert!(card.extensions.contains_key("nmae"));
+        assert_eq!(
+            errors,
+            [
+                FieldError::new("emails/e1/adress", "unknown property, ignored"),
+                FieldError::new("nmae", "unknown property, kept as is"),
+            ]
+        );
+        // the members of an invalid entry are not reported
+        let (_, errors) = Card::from_value_collecting(json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1",
+            "emails": { "e1": { "adress": "jane@example.com" } }
+        }));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "emails/e1");
+    }
+
+    #[test]
+    fn test_rfc_figures_have_no_unknown_property() {
+        let mut checked = 0;
+        for entry in std::fs::read_dir("tests/rfc9553").unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+            let json = std::fs::read_to_string(&path).unwrap();
+            if serde_json::from_str::<Card>(&json).is_err() {
+                continue;
+            }
+            let result = Card::from_str_with(&json, &reject_unknown());
+            assert!(result.is_ok(), "{}: {:?}", path.display(), result);
+            checked += 1;
+        }
+        assert!(checked > 0);
+    }
+}
@@ -0,0 +1,213 @@
+mod test {
+    use jscontact::{
+        Card, ValidationCode, ValidationError, ValidationReport, ValidationSeverity,
+        ValidationSummary,
+    };
+
+    fn invalid_card() -> Card {
+        let json = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "name": {
+                "components": [
+                    { "kind": "given", "value": "John" },
+                    { "kind": "separator", "value": " " },
+                    { "kind": "surname", "value": "Smith" }
+                ],
+                "defaultSeparator": " "
+            },
+            "emails": {
+                "e2": { "address": "jane@example.com", "pref": 101 },
+                "e1": { "address": "john@example.com", "pref": 0 },
+                "e3": { "address": "joe@example.com", "pref": 100 }
+            },
+            "speakToAs": {
+                "pronouns": { "k/1": { "pronouns": "they/them", "pref": 200 } }
+            },
+            "directories": {
+                "dir1": { "uri": "https://dir.example.com", "kind": "entry", "listAs": 0 }
+            }
+        });
+        Card::try_from(json).unwrap()
+    }
+
+    #[test]
+    fn test_validate_pref_and_list_as() {
+        let report = invalid_card().validate();
+        let errors: Vec<(&str, ValidationCode)> = report
+            .errors
+            .iter()
+            .map(|error| (error.path.as_str(), error.code))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                (
+                    "name/defaultSeparator",
+                    ValidationCode::DefaultSeparatorNotOrdered
+                ),
+                ("name/components/1", ValidationCode::SeparatorNotOrdered),
+                (
+                    "speakToAs/pronouns/k~11/pref",
+                    ValidationCode::PrefOutOfRange
+                ),
+                ("emails/e1/pref", ValidationCode::PrefOutOfRange),
+                ("emails/e2/pref", ValidationCode::PrefOutOfRange),
+                ("directories/dir1/listAs", ValidationCode::ListAsOutOfRange),
+            ]
+        );
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn test_validate_summary_and_filter() {
+        let mut report = invalid_card().validate();
+        assert_eq!(
+            report.summary(),
+            ValidationSummary {
+                errors: 6,
+                warnings: 0
+            }
+        );
+        assert_eq!(report.filter_path("name").count(), 2);
+        assert_eq!(report.filter_path("emails/e1").count(), 1);
+        assert_eq!(report.filter_path("emails/e").count(), 0);
+        assert_eq!(report.filter_path("").count(), 6);
+
+        // warnings do not make the Card invalid
+        let mut warnings = ValidationReport::new();
+        let mut warning = ValidationError::new(ValidationCode::PrefOutOfRange, "phones/p1", "");
+        warning.severity = ValidationSeverity::Warning;
+        warnings.errors.push(warning.clone());
+        assert!(warnings.is_valid());
+        report.errors.push(warning);
+        assert_eq!(report.summary().warnings, 1);
+    }
+
+    #[test]
+    fn test_validate_json_report() -> Result<(), Box<dyn std::error::Error>> {
+        let report = invalid_card().validate();
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()?)?;
+        // the schema of the report is a contract, the snapshot must not drift
+        let expected: serde_json::Value =
+            serde_json::from_slice(include_bytes!("./validation/report.json"))?;
+        assert_eq!(json, expected);
+        assert_eq!(
+            serde_json::to_value(report.summary())?,
+            serde_json::json!({ "errors": 6, "warnings": 0 })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_speak_to_as() -> Result<(), Box<dyn std::error::Error>> {
+        let card = Card::try_from(serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "speakToAs": {
+                "pronouns": {
+                    "k1": { "pronouns": "they/them" },
+                    "k2": { "pronouns": " " }
+                }
+            }
+        }))?;
+        let report = card.validate();
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].code, ValidationCode::EmptyPronouns);
+        assert_eq!(report.errors[0].path, "speakToAs/pronouns/k2/pronouns");
+
+        // neither grammaticalGender nor pronouns
+        let mut card = Card::try_from(serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "speakToAs": { "pronouns": {} }
+        }))?;
+        let speak_to_as = card.speak_to_as.as_ref().unwrap();
+        assert!(speak_to_as.is_empty());
+        let report = card.validate();
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].code, ValidationCode::EmptySpeakToAs);
+        assert_eq!(report.errors[0].path, "speakToAs");
+        // an empty SpeakToAs is not serialized
+        let json = serde_json::to_value(&card)?;
+        assert!(json.get("speakToAs").is_none());
+        card.speak_to_as = Some(Default::default());
+        assert!(serde_json::to_value(&card)?.get("speakToAs").is_none());
+
+        let card: Card = serde_json::from_slice(include_bytes!("./rfc9553/figure_23.json"))?;
+        assert!(!card.speak_to_as.as_ref().unwrap().is_empty());
+        assert!(card.validate().errors.is_empty());
+        assert!(serde_json::to_value(&card)?.get("speakToAs").is_some());
+        Ok(())
+    }
+
+    /// The Card of the figures 10 (`language`) and 28 (`preferredLanguages`) merged in one Card
+    fn figures_10_and_28() -> Card {
+        let mut json: serde_json::Value =
+            serde_json::from_slice(include_bytes!("./rfc9553/figure_10.json")).unwrap();
+        let figure_28: serde_json::Value =
+            serde_json::from_slice(include_bytes!("./rfc9553/figure_28.json")).unwrap();
+        json["preferredLanguages"] = figure_28["preferredLanguages"].clone();
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_validate_languages() -> Result<(), Box<dyn std::error::Error>> {
+        let mut card = figures_10_and_28();
+        assert_eq!(
+            card.languages_in_use().into_iter().collect::<Vec<_>>(),
+            ["de-AT", "en", "fr"]
+        );
+        let report = card.validate();
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].code, ValidationCode::LanguageNotPreferred);
+        assert_eq!(report.errors[0].path, "language");
+        assert_eq!(report.errors[0].severity, ValidationSeverity::Warning);
+        // a warning does not make the Card invalid
+        assert!(report.is_valid());
+        assert_eq!(report.with_severity(ValidationSeverity::Warning).count(), 1);
+        assert_eq!(report.with_severity(ValidationSeverity::Error).count(), 0);
+
+        // the primary language is enough
+        card.language = Some("en-US".to_string());
+        assert!(card.validate().errors.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_self_localization() -> Result<(), Box<dyn std::error::Error>> {
+        let card = Card::try_from(serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "1234",
+            "language": "de-AT",
+            "titles": { "t1": { "name": "Chef" } },
+            "localizations": {
+                "de": { "titles/t1/name": "Chef" },
+                "de-AT": { "titles/t1/name": "Chef" },
+                "de-CH": { "titles/t1/name": "Chef" },
+                "en": { "titles/t1/name": "Boss" }
+            }
+        }))?;
+        let report = card.validate();
+        let paths: Vec<&str> = report
+            .errors
+            .iter()
+            .map(|error| error.path.as_str())
+            .collect();
+        assert_eq!(paths, ["localizations/de", "localizations/de-AT"]);
+        assert!(report
+            .errors
+            .iter()
+            .all(|error| error.code == ValidationCode::LocalizationOfCardLanguage));
+        assert_eq!(report.summary().warnings, 2);
+        assert_eq!(
+            card.languages_in_use().into_iter().collect::<Vec<_>>(),
+            ["de", "de-AT", "de-CH", "en"]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,242 @@
+#![cfg(feature = "vcard4-interop")]
+
+mod test {
+    use jscontact::{
+        Address, AddressComponent, AddressComponentKind, Anniversary, AnniversaryKind, Card,
+        CardKind, CardVersion, Context, DateObject, EmailAddress, Media, MediaKind, Name,
+        NameComponent, NameComponentKind, OrgUnit, Organization, PartialDate, Phone, PhoneFeature,
+        SmallString,
+    };
+    use std::collections::HashMap;
+    use vcard4::Vcard;
+
+    fn build_card() -> Card {
+        let mut card = Card::new(CardVersion::OneDotZero, "urn:uuid:1234");
+        card.kind = Some(CardKind::Individual);
+        let mut name = Name::default();
+        name.full = Some("Mr. John Smith".to_string());
+        name.components = Some(vec![
+            NameComponent::new(NameComponentKind::Title, "Mr."),
+            NameComponent::new(NameComponentKind::Given, "John"),
+            NameComponent::new(NameComponentKind::Surname, "Smith"),
+        ]);
+        card.name = Some(name);
+
+        let mut email = EmailAddress::new("john@example.com");
+        email.contexts = Some(HashMap::from([(Context::Work, true)]));
+        email.pref = Some(1);
+        card.emails = Some(HashMap::from([(SmallString::from("e1"), email)]));
+
+        let mut phone = Phone::new("+1-555-555-5555");
+        phone.features = Some(HashMap::from([(PhoneFeature::Mobile, true)]));
+        card.phones = Some(HashMap::from([(SmallString::from("tel1"), phone)]));
+
+        let mut address = Address::default();
+        address.components = Some(vec![
+            AddressComponent::new(AddressComponentKind::Name, "Main Street"),
+            AddressComponent::new(AddressComponentKind::Locality, "Springfield"),
+            AddressComponent::new(AddressComponentKind::Country, "USA"),
+        ]);
+        card.addresses = Some(HashMap::from([(SmallString::from("a1"), address)]));
+
+        let mut organization = Organization::default();
+        organization.name = Some("ABC, Inc.".to_string());
+        organization.units = Some(vec![OrgUnit::new("North American Division")]);
+        card.organizations = Some(HashMap::from([(SmallString::from("o1"), organization)]));
+
+        let mut photo = Media::new("https://example.com/photo.jpg", MediaKind::Photo);
+        photo.media_type = Some("image/jpeg".into());
+        card.media = Some(HashMap::from([(SmallString::from("m1"), photo)]));
+
+        let mut birthday = PartialDate::default();
+        birthday.year = Some(1953);
+        birthday.month = Some(4);
+        birthday.day = Some(15);
+        card.anniversaries = Some(HashMap::from([(
+            SmallString::from("k1"),
+            Anniversary::new(AnniversaryKind::Birth, DateObject::PartialDate(birthday)),
+        )]));
+        card
+    }
+
+    #[test]
+    fn test_card_to_vcard4() {
+        let card = build_card();
+        let (vcard, report) = card.to_vcard4();
+        assert!(report.is_lossless());
+        assert_eq!(vcard.formatted_name[0].value, "Mr. John Smith");
+        assert_eq!(
+            vcard.name.as_ref().unwrap().value,
+            vec!["Smith", "John", "", "Mr.", ""]
+        );
+        assert_eq!(vcard.email[0].value, "john@example.com");
+        assert_eq!(vcard.email[0].parameters.as_ref().unwrap().pref, Some(1));
+        assert_eq!(vcard.tel.len(), 1);
+        assert_eq!(
+            vcard.address[0].value.locality.as_deref(),
+            Some("Springfield")
+        );
+        assert_eq!(
+            vcard.org[0].value,
+            vec!["ABC, Inc.", "North American Division"]
+        );
+        assert_eq!(vcard.photo.len(), 1);
+        assert!(vcard.bday.is_some());
+
+        let text = vcard.to_string();
+        assert!(text.contains("BDAY:19530415"));
+        assert!(text.contains("EMAIL;PREF=1;TYPE=work:john@example.com"));
+        assert!(text.contains("TEL;TYPE=cell:+1-555-555-5555"));
+    }
+
+    #[test]
+    fn test_card_from_vcard4() {
+        let card = build_card();
+        let vcard = Vcard::from(&card);
+        let (converted, report) = Card::from_vcard4(&vcard).unwrap();
+        assert!(report.is_lossless());
+        assert_eq!(converted.uid, card.uid);
+        assert_eq!(converted.kind, Some(CardKind::Individual));
+        assert_eq!(
+            converted.name.as_ref().unwrap().full,
+            card.name.unwrap().full
+        );
+        assert_eq!(converted.name.unwrap().components.unwrap().len(), 3);
+        let email = &converted.emails.unwrap()["e1"];
+        assert_eq!(email.address, "john@example.com");
+        assert_eq!(email.contexts, Some(HashMap::from([(Context::Work, true)])));
+        let phone = &converted.phones.unwrap()["tel1"];
+        assert_eq!(
+            phone.features,
+            Some(HashMap::from([(PhoneFeature::Mobile, true)]))
+        );
+        let organization = &converted.organizations.unwrap()["o1"];
+        assert_eq!(organization.name.as_deref(), Some("ABC, Inc."));
+        let photo = &converted.media.unwrap()["m1"];
+        assert_eq!(photo.media_type.as_deref(), Some("image/jpeg"));
+        assert_eq!(
+            converted.anniversaries.unwrap()["birth"].date,
+            card.anniversaries.unwrap()["k1"].date
+        );
+    }
+
+    #[test]
+    fn test_vcard4_keywords() {
+        let mut card = build_card();
+        card.keywords = Some(HashMap::from([
+            (SmallString::from("friend"), true),
+            (SmallString::from("work"), false),
+        ]));
+        let (vcard, _) = card.to_vcard4();
+        assert_eq!(vcard.categories[0].value, vec!["friend"]);
+
+        // no enabled keyword, no CATEGORIES
+        card.keywords = Some(HashMap::from([(SmallString::from("work"), false)]));
+        let (vcard, report) = card.to_vcard4();
+        assert!(report.is_lossless());
+        assert!(vcard.categories.is_empty());
+        assert!(!vcard.to_string().contains("CATEGORIES"));
+        let (converted, _) = Card::from_vcard4(&vcard).unwrap();
+        assert_eq!(converted.keywords, None);
+    }
+
+    #[test]
+    fn test_vcard4_lossy() {
+        let mut card = build_card();
+        card.kind = Some(CardKind::Device);
+        let mut death = PartialDate::default();
+        death.year = Some(2020);
+        card.anniversaries.as_mut().unwrap().insert(
+            SmallString::from("k2"),
+            Anniversary::new(AnniversaryKind::Death, DateObject::PartialDate(death)),
+        );
+        let (_, report) = card.to_vcard4();
+        let paths: Vec<&str> = report.lossy.iter().map(|loss| loss.path.as_str()).collect();
+        assert_eq!(paths, vec!["kind", "anniversaries/k2"]);
+        assert_eq!(report.lossy[1].reason, "vCard has no death-date property");
+    }
+
+    #[test]
+    fn test_vcard4_lossy_vendor_values() {
+        let mut card = build_card();
+        let phone = card.phones.as_mut().unwrap().get_mut("tel1").unwrap();
+        phone.features = Some(HashMap::from([
+            (PhoneFeature::MainNumber, true),
+            (
+                PhoneFeature::Other("example.com:satellite".to_string()),
+                true,
+            ),
+            (PhoneFeature::Voice, true),
+        ]));
+        let email = card.emails.as_mut().unwrap().get_mut("e1").unwrap();
+        email.contexts = Some(HashMap::from([
+            (Context::Work, true),
+            (Context::Other("example.com:school".to_string()), true),
+        ]));
+        let (_, report) = card.to_vcard4();
+        let losses: Vec<(&str, &str)> = report
+            .lossy
+            .iter()
+            .map(|loss| (loss.path.as_str(), loss.reason.as_str()))
+            .collect();
+        assert_eq!(
+            losses,
+            vec![
+                (
+                    "emails/e1/contexts/example.com:school",
+                    "vcard4 has no example.com:school TYPE"
+                ),
+                (
+                    "phones/tel1/features/example.com:satellite",
+                    "vcard4 has no example.com:satellite TYPE"
+                ),
+                (
+                    "phones/tel1/features/main-number",
+                    "vcard4 has no main-number TYPE"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_vcard4_parsed() {
+        let text = "BEGIN:VCARD\r\nVERSION:4.0\r\nUID:urn:uuid:4fbe8971\r\nFN:Jane Doe\r\nN:Doe;Jane;;;\r\nEMAIL;TYPE=home:jane@example.com\r\nX-CUSTOM:value\r\nEND:VCARD\r\n";
+        let vcards = vcard4::parse(text).unwrap();
+        let (card, report) = Card::from_vcard4(&vcards[0]).unwrap();
+        assert_eq!(card.uid, "urn:uuid:4fbe8971");
+        assert_eq!(card.name.unwrap().full.as_deref(), Some("Jane Doe"));
+        assert_eq!(
+            card.emails.unwrap()["e1"].contexts,
+            Some(HashMap::from([(Context::Private, true)]))
+        );
+        assert_eq!(report.lossy.len(), 1);
+        assert_eq!(report.lossy[0].path, "X-CUSTOM");
+    }
+
+    #[test]
+    fn test_vcard4_legacy_pref() {
+        let text = "BEGIN:VCARD\r\nVERSION:4.0\r\nUID:urn:uuid:4fbe8971\r\nFN:Jane Doe\r\nEMAIL;TYPE=home,pref:jane@example.com\r\nEMAIL;TYPE=work,pref;PREF=2:doe@example.com\r\nTEL;VALUE=uri;TYPE=cell,main-number:tel:+1-555-555-5555\r\nEND:VCARD\r\n";
+        let vcards = vcard4::parse(text).unwrap();
+        let (card, _) = Card::from_vcard4(&vcards[0]).unwrap();
+        let emails = card.emails.unwrap();
+        assert_eq!(emails["e1"].pref, Some(1));
+        assert_eq!(
+            emails["e1"].contexts,
+            Some(HashMap::from([(Context::Private, true)]))
+        );
+        assert_eq!(emails["e2"].pref, Some(2));
+        let phones = card.phones.unwrap();
+        assert_eq!(
+            phones["tel1"].features,
+            Some(HashMap::from([(PhoneFeature::Mobile, true)]))
+        );
+        assert_eq!(phones["tel1"].pref, None);
+    }
+
+    #[test]
+    fn test_vcard4_without_uid() {
+        let text = "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\r\nEND:VCARD\r\n";
+        let vcards = vcard4::parse(text).unwrap();
+        assert!(Card::try_from(&vcards[0]).is_err());
+    }
+}
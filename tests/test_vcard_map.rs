@@ -0,0 +1,84 @@
+mod test {
+    use jscontact::vcard_map::{
+        address_context_to_type, context_to_type, feature_to_type, pref_from_vcard,
+        pref_to_parameter, type_to_address_context, type_to_context, type_to_feature,
+        CONTEXT_TYPES, FEATURE_TYPES, PREF_TYPE,
+    };
+    use jscontact::{AddressContext, Context, PhoneFeature};
+
+    #[test]
+    fn test_contexts_bijective() {
+        for context in [Context::Private, Context::Work] {
+            let value = context_to_type(&context).unwrap();
+            assert!(CONTEXT_TYPES.contains(&(context.clone(), value)));
+            assert_eq!(type_to_context(value), Some(context));
+        }
+        assert_eq!(context_to_type(&Context::Private), Some("home"));
+        let vendor = Context::Other("example.com:oncall".to_string());
+        assert_eq!(context_to_type(&vendor), None);
+        assert_eq!(type_to_context("HOME"), Some(Context::Private));
+        assert_eq!(type_to_context("Work"), Some(Context::Work));
+        assert_eq!(type_to_context("private"), None);
+        assert_eq!(type_to_context("cell"), None);
+    }
+
+    #[test]
+    fn test_address_contexts_lossy() {
+        for context in [AddressContext::Private, AddressContext::Work] {
+            let value = address_context_to_type(&context).unwrap();
+            assert_eq!(type_to_address_context(value), Some(context));
+        }
+        // RFC 9555: no vCard TYPE for billing and delivery
+        assert_eq!(address_context_to_type(&AddressContext::Billing), None);
+        assert_eq!(address_context_to_type(&AddressContext::Delivery), None);
+        assert_eq!(type_to_address_context("billing"), None);
+        assert_eq!(
+            type_to_address_context("home"),
+            Some(AddressContext::Private)
+        );
+    }
+
+    #[test]
+    fn test_features() {
+        let features = [
+            PhoneFeature::Fax,
+            PhoneFeature::Mobile,
+            PhoneFeature::Pager,
+            PhoneFeature::Text,
+            PhoneFeature::Textphone,
+            PhoneFeature::Video,
+            PhoneFeature::Voice,
+        ];
+        for feature in features.iter() {
+            let value = feature_to_type(feature).unwrap();
+            assert_eq!(type_to_feature(value).as_ref(), Some(feature));
+        }
+        assert_eq!(FEATURE_TYPES.len(), features.len());
+        assert_eq!(feature_to_type(&PhoneFeature::Mobile), Some("cell"));
+        assert_eq!(type_to_feature("CELL"), Some(PhoneFeature::Mobile));
+        assert_eq!(type_to_feature("mobile"), None);
+        // lossy: vCard has no main-number TYPE
+        assert_eq!(feature_to_type(&PhoneFeature::MainNumber), None);
+        assert_eq!(type_to_feature("main-number"), None);
+    }
+
+    #[test]
+    fn test_pref() {
+        assert_eq!(pref_to_parameter(1), Some(1));
+        assert_eq!(pref_to_parameter(100), Some(100));
+        assert_eq!(pref_to_parameter(0), None);
+        assert_eq!(pref_to_parameter(101), None);
+        for pref in 1..=100 {
+            let parameter = pref_to_parameter(pref).map(u64::from);
+            assert_eq!(pref_from_vcard(parameter, &[]), Some(pref));
+        }
+
+        assert_eq!(pref_from_vcard(None, &["home"]), None);
+        assert_eq!(pref_from_vcard(None, &["home", PREF_TYPE]), Some(1));
+        assert_eq!(pref_from_vcard(None, &["PREF"]), Some(1));
+        // the PREF parameter wins over the legacy TYPE=pref
+        assert_eq!(pref_from_vcard(Some(3), &["pref"]), Some(3));
+        assert_eq!(pref_from_vcard(Some(0), &["pref"]), Some(1));
+        assert_eq!(pref_from_vcard(Some(0), &[]), None);
+    }
+}
@@ -75,9 +75,56 @@ mod test {
         );
         card.add_localization("en", translations);
 
-        let langs = card.get_available_languages();
+        let langs: Vec<&str> = card.get_available_languages().collect();
         assert_eq!(langs, vec!["en"]);
-        let localized = card.get_localized(&langs[0]).unwrap();
+        let localized = card.get_localized(langs[0]).unwrap();
         assert_eq!(localized.name.unwrap().full.unwrap(), "Johny");
     }
+
+    #[test]
+    fn test_anniversary_vendor_kind() {
+        use jscontact::{Anniversary, AnniversaryKind, DateObject, Timestamp};
+
+        let json = r#"
+        {
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "22B2C7DF-9120-4969-8460-05956FE6B065",
+            "anniversaries": {
+                "k1": {
+                    "kind": "example.com:graduation",
+                    "date": {
+                        "@type": "Timestamp",
+                        "utc": "2010-06-30T12:00:00Z"
+                    }
+                }
+            }
+        }"#;
+        let card: Card = serde_json::from_str(json).unwrap();
+        let k1 = card.anniversaries.as_ref().unwrap().get("k1").unwrap();
+        assert_eq!(
+            k1.kind,
+            AnniversaryKind::Other("example.com:graduation".to_string())
+        );
+        assert_eq!(k1.kind.to_string(), "example.com:graduation");
+
+        let value = serde_json::to_value(&card).unwrap();
+        assert_eq!(
+            value["anniversaries"]["k1"]["kind"],
+            "example.com:graduation"
+        );
+        let card_2: Card = serde_json::from_value(value).unwrap();
+        assert_eq!(card, card_2);
+
+        let date = DateObject::Timestamp(Timestamp::new("2010-06-30T12:00:00Z"));
+        assert_eq!(
+            Anniversary::wedding(date.clone()).kind,
+            AnniversaryKind::Wedding
+        );
+        assert_eq!(
+            Anniversary::birth(date.clone()).kind,
+            AnniversaryKind::Birth
+        );
+        assert_eq!(Anniversary::death(date).kind, AnniversaryKind::Death);
+    }
 }